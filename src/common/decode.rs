@@ -39,6 +39,8 @@ pub enum FontFileHeader {
     TYP1,
     /// 'OTTO' - OpenType font with CFF outlines. (Internally treated as SFNT)
     OTTO,
+    /// 'ttcf' - TrueType/OpenType font collection.
+    TTCF,
     /// 'wOFF' - Web Open Font Format. (Internally treated as SFNT)
     WOFF,
     /// 'wOF2' - Web Open Font Format 2. (Internally treated as SFNT after decompression)
@@ -56,6 +58,7 @@ impl From<u32> for FontFileHeader {
             0x74727565 => FontFileHeader::TRUE,      // 'true'
             0x74797031 => FontFileHeader::TYP1,      // 'typ1'
             0x4F54544F => FontFileHeader::OTTO,      // 'OTTO'
+            0x74746366 => FontFileHeader::TTCF,      // 'ttcf'
             0x774F4646 => FontFileHeader::WOFF,      // 'wOFF'
             0x774F4632 => FontFileHeader::WOF2,      // 'wOF2'
             0x73766720 => FontFileHeader::SVG,       // 'svg '
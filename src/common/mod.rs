@@ -6,6 +6,10 @@
 
 pub mod decode;
 pub mod snft;
+pub mod woff;
+pub mod woff2;
 
 pub use decode::*;
-pub use snft::*;
\ No newline at end of file
+pub use snft::*;
+pub use woff::*;
+pub use woff2::*;
\ No newline at end of file
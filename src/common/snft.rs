@@ -1,14 +1,15 @@
 //! Aurora Font Library
-//! 
+//!
 //! Author: Colton McGraw <github.com/ColtMcG1>
 //! License: Apache-2.0
 //! Date: January 2026
-//! 
-//! SNFT (Simple New Font Table) representation and utilities for the Aurora Font Library. 
+//!
+//! SNFT (Simple New Font Table) representation and utilities for the Aurora Font Library.
 //! This module provides functions to extract and validate SNFT tables from font data streams.
 
 use crate::error::{Error, IoError};
 use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
 
 /// SNFT (Simple New Font Table) representation and utilities.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -20,7 +21,6 @@ pub struct SnftTable {
 }
 
 impl SnftTable {
-
     /// Retrieves a table entry by its tag.
     pub fn get_table_by_tag(&self, tag: &[u8; 4]) -> Option<&SnftTableEntry> {
         self.tables.iter().find(|t| &t.tag == tag)
@@ -39,6 +39,32 @@ impl SnftTable {
         self.tables.len()
     }
 
+    /// Returns the raw bytes of a table from the font data the directory was read from.
+    ///
+    /// Returns `IoError::InvalidTag` if the table is missing and
+    /// `IoError::TruncatedTable` if it extends past the end of `data`.
+    pub fn table_data<'a>(&self, tag: &[u8; 4], data: &'a [u8]) -> Result<&'a [u8], Error> {
+        let entry = self
+            .get_table_by_tag(tag)
+            .ok_or(Error::Io(IoError::InvalidTag(*tag)))?;
+        let start = entry.offset as usize;
+        data.get(start..start + entry.length as usize)
+            .ok_or(Error::Io(IoError::TruncatedTable {
+                table: *tag,
+                expected_len: entry.length as usize,
+                found_len: data.len().saturating_sub(start),
+            }))
+    }
+
+    /// Returns a stream over a table from the font data the directory was read from.
+    pub(crate) fn table_stream<'a>(
+        &self,
+        tag: &[u8; 4],
+        data: &'a [u8],
+    ) -> Result<FontDataStream<'a>, Error> {
+        Ok(FontDataStream::new(self.table_data(tag, data)?))
+    }
+
     /// Checks if a table with the specified tag exists.
     pub fn has_table(&self, tag: &[u8; 4]) -> bool {
         self.tables.iter().any(|t| &t.tag == tag)
@@ -182,19 +208,88 @@ impl SnftTableEntry {
 /// SNFT file tag constant.
 pub const SNFT_TAG: u32 = u32::from_be_bytes([0x53, 0x4E, 0x46, 0x54]);
 
+/// Font collection file tag constant ('ttcf').
+pub const TTCF_TAG: u32 = u32::from_be_bytes([0x74, 0x74, 0x63, 0x66]);
+
+/// Font collection (TTC/OTC) header representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SnftCollectionHeader {
+    /// Major version of the collection header (1 or 2).
+    pub major_version: u16,
+    /// Minor version of the collection header.
+    pub minor_version: u16,
+    /// Offsets of each font's table directory from the start of the file.
+    pub table_directory_offsets: Vec<u32>,
+    /// DSIG tag, length and offset (version 2 only).
+    pub dsig: Option<(u32, u32, u32)>,
+}
+
+/// Font collection representation: a header plus one directory per font.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SnftCollection {
+    /// Collection header.
+    pub header: SnftCollectionHeader,
+    /// Table directories of the fonts in the collection.
+    pub fonts: Vec<SnftTable>,
+}
+
+impl SnftCollection {
+    /// Returns the number of fonts in the collection.
+    pub fn num_fonts(&self) -> usize {
+        self.fonts.len()
+    }
+}
+
+/// Extracts a font collection (TTC/OTC) from the provided data stream.
+///
+/// # Errors
+/// * Returns `IoError::InvalidTag` if the stream does not start with 'ttcf'.
+pub fn read_snft_collection(data: &mut FontDataStream) -> Result<SnftCollection, Error> {
+    let tag = data.read_tag()?;
+    if u32::from_be_bytes(tag) != TTCF_TAG {
+        return Err(Error::Io(IoError::InvalidTag(tag)));
+    }
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    let num_fonts = data.read_u32()?;
+    let mut table_directory_offsets = Vec::new();
+    for _ in 0..num_fonts {
+        table_directory_offsets.push(data.read_u32()?);
+    }
+    let dsig = if major_version >= 2 {
+        Some((data.read_u32()?, data.read_u32()?, data.read_u32()?))
+    } else {
+        None
+    };
+
+    let mut fonts = Vec::with_capacity(table_directory_offsets.len());
+    for &offset in &table_directory_offsets {
+        data.seek(offset as usize);
+        fonts.push(read_snft(data)?);
+    }
+
+    Ok(SnftCollection {
+        header: SnftCollectionHeader {
+            major_version,
+            minor_version,
+            table_directory_offsets,
+            dsig,
+        },
+        fonts,
+    })
+}
+
 /// Extracts SNFT table entries from the provided data stream.
-/// 
+///
 /// # Arguments
 /// * `data` - A mutable reference to the font data stream.
-/// 
+///
 /// # Returns
 /// * `Result<SnftTable, Error>` - On success, returns the extracted SNFT table representation; on failure, returns an error.
-/// 
+///
 /// # Errors
 /// * Returns `Error` if reading from the data stream fails or if the SNFT format is invalid.
-pub fn read_snft(
-    data: &mut FontDataStream,
-) -> Result<SnftTable, Error> {
+pub fn read_snft(data: &mut FontDataStream) -> Result<SnftTable, Error> {
     let mut tables = Vec::new();
 
     // Read SNFT header
@@ -204,17 +299,12 @@ pub fn read_snft(
     for _ in 0..header.num_tables {
         tables.push(read_snft_table_entry(data)?);
     }
-    
-    Ok(SnftTable {
-        header,
-        tables,
-    })
+
+    Ok(SnftTable { header, tables })
 }
 
 /// Extracts the SNFT table header from the provided data stream.
-pub fn read_snft_header(
-    data: &mut FontDataStream,
-) -> Result<SnftTableHeader, Error> {
+pub fn read_snft_header(data: &mut FontDataStream) -> Result<SnftTableHeader, Error> {
     let sfnt_version = data.read_u32()?;
     let num_tables = data.read_u16()?;
     let search_range = data.read_u16()?;
@@ -231,9 +321,7 @@ pub fn read_snft_header(
 }
 
 /// Extract an SNFT table entry from the data stream.
-pub fn read_snft_table_entry(
-    data: &mut FontDataStream,
-) -> Result<SnftTableEntry, Error> {
+pub fn read_snft_table_entry(data: &mut FontDataStream) -> Result<SnftTableEntry, Error> {
     let tag_bytes = data.read_bytes(4)?;
     let tag = [tag_bytes[0], tag_bytes[1], tag_bytes[2], tag_bytes[3]];
     let checksum = data.read_u32()?;
@@ -248,6 +336,70 @@ pub fn read_snft_table_entry(
     })
 }
 
+/// Serializes a complete SNFT font from `(tag, data)` pairs.
+///
+/// Tables are sorted by tag, checksummed and padded to 4 bytes. When a `head`
+/// table is present its `checkSumAdjustment` is recomputed for the whole font,
+/// so the output must start at the writer's current position.
+///
+/// # Errors
+/// * Returns `Error` if a table is too large for the directory.
+pub fn write_snft(
+    writer: &mut FontDataWriter,
+    version: u32,
+    tables: &[([u8; 4], Vec<u8>)],
+) -> Result<(), Error> {
+    let mut sorted: Vec<&([u8; 4], Vec<u8>)> = tables.iter().collect();
+    sorted.sort_by_key(|(tag, _)| *tag);
+
+    let start = writer.position();
+    let num_tables = sorted.len() as u16;
+    let entry_selector = if num_tables == 0 {
+        0
+    } else {
+        15 - num_tables.leading_zeros() as u16
+    };
+    let search_range = (1u16 << entry_selector) * 16;
+    writer.write_u32(version);
+    writer.write_u16(num_tables);
+    writer.write_u16(search_range);
+    writer.write_u16(entry_selector);
+    writer.write_u16((num_tables * 16).saturating_sub(search_range));
+
+    let mut placeholders = Vec::with_capacity(sorted.len());
+    for (tag, data) in &sorted {
+        let mut checksum_data = data.clone();
+        if tag == b"head" && checksum_data.len() >= 12 {
+            checksum_data[8..12].fill(0);
+        }
+        writer.write_tag(*tag);
+        writer.write_u32(SnftTableEntry::new(*tag, 0, 0, 0).compute_checksum(&checksum_data));
+        placeholders.push(writer.reserve_offset32(start));
+        writer.write_u32(u32::try_from(data.len()).map_err(|_| IoError::InvalidData)?);
+    }
+
+    let mut head_position = None;
+    for ((tag, data), placeholder) in sorted.iter().zip(placeholders) {
+        writer.patch_offset_here(placeholder)?;
+        if tag == b"head" && data.len() >= 12 {
+            head_position = Some(writer.position());
+            writer.write_bytes(&data[..8]);
+            writer.write_u32(0);
+            writer.write_bytes(&data[12..]);
+        } else {
+            writer.write_bytes(data);
+        }
+        writer.align(4);
+    }
+
+    if let Some(position) = head_position {
+        let entry = SnftTableEntry::new(*b"head", 0, 0, 0);
+        let checksum = entry.compute_checksum(&writer.as_bytes()[start..]);
+        writer.write_at_u32(position + 8, 0xB1B0AFBAu32.wrapping_sub(checksum))?;
+    }
+    Ok(())
+}
+
 /// Validates the checksums of SNFT tables against the data in the stream.
 pub fn validate_snft_tables(
     tables: Vec<SnftTableEntry>,
@@ -1,5 +1,217 @@
 //! Aurora Font Library
-//! 
+//!
 //! Author: Colton McGraw <github.com/ColtMcG1>
 //! License: Apache-2.0
-//! Date: January 2026
\ No newline at end of file
+//! Date: January 2026
+//!
+//! WOFF 1.0 (Web Open Font Format) representation and utilities for the Aurora
+//! Font Library. This module provides functions to encode SNFT fonts as WOFF files.
+
+use crate::compress::zlib_compress;
+use crate::error::{Error, IoError};
+use crate::io::writer::FontDataWriter;
+
+use super::snft::SnftTable;
+
+/// WOFF file signature ('wOFF').
+pub const WOFF_SIGNATURE: u32 = 0x774F4646;
+
+/// Size of the WOFF header in bytes.
+pub const WOFF_HEADER_SIZE: usize = 44;
+
+/// Size of a WOFF table directory entry in bytes.
+pub const WOFF_TABLE_ENTRY_SIZE: usize = 20;
+
+/// Options controlling WOFF encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WoffEncodeOptions<'a> {
+    /// Major version of the WOFF file (font version, not format version).
+    pub major_version: u16,
+    /// Minor version of the WOFF file.
+    pub minor_version: u16,
+    /// Optional extended metadata (an uncompressed XML document).
+    pub metadata: Option<&'a [u8]>,
+    /// Optional private data block.
+    pub private_data: Option<&'a [u8]>,
+}
+
+/// WOFF header representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WoffHeader {
+    /// 'wOFF' signature.
+    pub signature: u32,
+    /// SFNT version of the wrapped font.
+    pub flavor: u32,
+    /// Total size of the WOFF file.
+    pub length: u32,
+    /// Number of table directory entries.
+    pub num_tables: u16,
+    /// Total size needed for the uncompressed font data.
+    pub total_sfnt_size: u32,
+    /// Major version of the WOFF file.
+    pub major_version: u16,
+    /// Minor version of the WOFF file.
+    pub minor_version: u16,
+    /// Offset to the metadata block.
+    pub meta_offset: u32,
+    /// Length of the compressed metadata block.
+    pub meta_length: u32,
+    /// Uncompressed size of the metadata block.
+    pub meta_orig_length: u32,
+    /// Offset to the private data block.
+    pub priv_offset: u32,
+    /// Length of the private data block.
+    pub priv_length: u32,
+}
+
+/// WOFF table directory entry representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WoffTableEntry {
+    /// 4-byte tag identifying the table.
+    pub tag: [u8; 4],
+    /// Offset of the table data in the WOFF file.
+    pub offset: u32,
+    /// Length of the stored (possibly compressed) table data.
+    pub comp_length: u32,
+    /// Length of the uncompressed table data.
+    pub orig_length: u32,
+    /// Checksum of the uncompressed table, copied from the SNFT directory.
+    pub orig_checksum: u32,
+}
+
+/// Encodes an SNFT font as a WOFF 1.0 file.
+///
+/// # Arguments
+/// * `snft` - The parsed SNFT table directory of the font.
+/// * `source` - The complete source font bytes the directory refers to.
+/// * `options` - Version numbers and optional metadata/private blocks.
+///
+/// Each table is zlib-compressed only when that makes it smaller, otherwise it
+/// is stored as-is. Original table checksums are preserved.
+///
+/// # Errors
+/// * Returns `Error` if a table lies outside of `source`.
+pub fn encode_woff(
+    snft: &SnftTable,
+    source: &[u8],
+    options: &WoffEncodeOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut tables = snft.tables.clone();
+    tables.sort_by_key(|t| t.tag);
+
+    // Compress tables up front so the directory can be written in one pass.
+    let mut stored: Vec<(WoffTableEntry, Vec<u8>)> = Vec::with_capacity(tables.len());
+    let mut total_sfnt_size = 12 + 16 * tables.len() as u32;
+    for table in &tables {
+        let start = table.offset as usize;
+        let end = start + table.length as usize;
+        let data = source
+            .get(start..end)
+            .ok_or(Error::Io(IoError::TruncatedTable {
+                table: table.tag,
+                expected_len: table.length as usize,
+                found_len: source.len().saturating_sub(start),
+            }))?;
+        let compressed = zlib_compress(data);
+        let body = if compressed.len() < data.len() {
+            compressed
+        } else {
+            data.to_vec()
+        };
+        total_sfnt_size += (table.length + 3) & !3;
+        stored.push((
+            WoffTableEntry {
+                tag: table.tag,
+                offset: 0,
+                comp_length: body.len() as u32,
+                orig_length: table.length,
+                orig_checksum: table.checksum,
+            },
+            body,
+        ));
+    }
+
+    let mut offset = (WOFF_HEADER_SIZE + WOFF_TABLE_ENTRY_SIZE * stored.len()) as u32;
+    for (entry, body) in &mut stored {
+        entry.offset = offset;
+        offset += (body.len() as u32 + 3) & !3;
+    }
+
+    let metadata = options.metadata.map(|m| (zlib_compress(m), m.len() as u32));
+    let (meta_offset, meta_length, meta_orig_length) = match &metadata {
+        Some((compressed, orig_len)) => {
+            let meta = (offset, compressed.len() as u32, *orig_len);
+            offset += compressed.len() as u32;
+            meta
+        }
+        None => (0, 0, 0),
+    };
+    let (priv_offset, priv_length) = match options.private_data {
+        Some(private) => {
+            offset = (offset + 3) & !3;
+            let block = (offset, private.len() as u32);
+            offset += private.len() as u32;
+            block
+        }
+        None => (0, 0),
+    };
+
+    let header = WoffHeader {
+        signature: WOFF_SIGNATURE,
+        flavor: snft.header.version,
+        length: offset,
+        num_tables: stored.len() as u16,
+        total_sfnt_size,
+        major_version: options.major_version,
+        minor_version: options.minor_version,
+        meta_offset,
+        meta_length,
+        meta_orig_length,
+        priv_offset,
+        priv_length,
+    };
+
+    let mut writer = FontDataWriter::with_capacity(offset as usize);
+    write_woff_header(&mut writer, &header);
+    for (entry, _) in &stored {
+        write_woff_table_entry(&mut writer, entry);
+    }
+    for (_, body) in &stored {
+        writer.write_bytes(body);
+        writer.align(4);
+    }
+    if let Some((compressed, _)) = &metadata {
+        writer.write_bytes(compressed);
+    }
+    if let Some(private) = options.private_data {
+        writer.align(4);
+        writer.write_bytes(private);
+    }
+    writer.into_bytes()
+}
+
+/// Writes a WOFF header.
+pub fn write_woff_header(writer: &mut FontDataWriter, header: &WoffHeader) {
+    writer.write_u32(header.signature);
+    writer.write_u32(header.flavor);
+    writer.write_u32(header.length);
+    writer.write_u16(header.num_tables);
+    writer.write_u16(0); // reserved
+    writer.write_u32(header.total_sfnt_size);
+    writer.write_u16(header.major_version);
+    writer.write_u16(header.minor_version);
+    writer.write_u32(header.meta_offset);
+    writer.write_u32(header.meta_length);
+    writer.write_u32(header.meta_orig_length);
+    writer.write_u32(header.priv_offset);
+    writer.write_u32(header.priv_length);
+}
+
+/// Writes a WOFF table directory entry.
+pub fn write_woff_table_entry(writer: &mut FontDataWriter, entry: &WoffTableEntry) {
+    writer.write_tag(entry.tag);
+    writer.write_u32(entry.offset);
+    writer.write_u32(entry.comp_length);
+    writer.write_u32(entry.orig_length);
+    writer.write_u32(entry.orig_checksum);
+}
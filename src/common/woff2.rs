@@ -1,5 +1,574 @@
 //! Aurora Font Library
-//! 
+//!
 //! Author: Colton McGraw <github.com/ColtMcG1>
 //! License: Apache-2.0
-//! Date: January 2026
\ No newline at end of file
+//! Date: January 2026
+//!
+//! WOFF 2.0 (Web Open Font Format 2) representation and utilities for the Aurora
+//! Font Library. This module provides functions to encode SNFT fonts and font
+//! collections as WOFF2 files, including the `glyf`/`loca` and `hmtx` transforms.
+
+use std::collections::HashMap;
+
+use crate::components::glyf::{GlyfTable, Glyph, read_glyf, write_composite_components};
+use crate::components::head::read_head;
+use crate::components::hhea::read_hhea;
+use crate::components::hmtx::{HmtxTable, read_hmtx};
+use crate::components::loca::read_loca;
+use crate::components::maxp::read_maxp;
+use crate::compress::brotli_compress;
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::snft::{SnftCollection, SnftTable, SnftTableEntry, TTCF_TAG};
+
+/// WOFF2 file signature ('wOF2').
+pub const WOFF2_SIGNATURE: u32 = 0x774F4632;
+
+/// Size of the WOFF2 header in bytes.
+pub const WOFF2_HEADER_SIZE: usize = 48;
+
+/// Tags with a one-byte index in the WOFF2 table directory.
+pub const WOFF2_KNOWN_TAGS: [[u8; 4]; 63] = [
+    *b"cmap", *b"head", *b"hhea", *b"hmtx", *b"maxp", *b"name", *b"OS/2", *b"post", *b"cvt ",
+    *b"fpgm", *b"glyf", *b"loca", *b"prep", *b"CFF ", *b"VORG", *b"EBDT", *b"EBLC", *b"gasp",
+    *b"hdmx", *b"kern", *b"LTSH", *b"PCLT", *b"VDMX", *b"vhea", *b"vmtx", *b"BASE", *b"GDEF",
+    *b"GPOS", *b"GSUB", *b"EBSC", *b"JSTF", *b"MATH", *b"CBDT", *b"CBLC", *b"COLR", *b"CPAL",
+    *b"SVG ", *b"sbix", *b"acnt", *b"avar", *b"bdat", *b"bloc", *b"bsln", *b"cvar", *b"fdsc",
+    *b"feat", *b"fmtx", *b"fvar", *b"gvar", *b"hsty", *b"just", *b"lcar", *b"mort", *b"morx",
+    *b"opbd", *b"prop", *b"trak", *b"Zapf", *b"Silf", *b"Glat", *b"Gloc", *b"Feat", *b"Sill",
+];
+
+/// Options controlling WOFF2 encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Woff2EncodeOptions<'a> {
+    /// Major version of the WOFF2 file (font version, not format version).
+    pub major_version: u16,
+    /// Minor version of the WOFF2 file.
+    pub minor_version: u16,
+    /// Optional extended metadata (an uncompressed XML document).
+    pub metadata: Option<&'a [u8]>,
+    /// Optional private data block.
+    pub private_data: Option<&'a [u8]>,
+    /// Store `glyf`, `loca` and `hmtx` untransformed.
+    pub skip_transforms: bool,
+}
+
+/// A table as stored in the WOFF2 table directory and data stream.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Woff2TableEntry {
+    /// 4-byte tag identifying the table.
+    pub tag: [u8; 4],
+    /// Transformation version (bits 6-7 of the flags byte).
+    pub transform_version: u8,
+    /// Length of the original table.
+    pub orig_length: u32,
+    /// Length of the transformed table, present for transformed tables.
+    pub transform_length: Option<u32>,
+    /// Table data as written to the compressed stream.
+    pub data: Vec<u8>,
+}
+
+impl Woff2TableEntry {
+    /// Returns the flags byte of the directory entry.
+    pub fn flags(&self) -> u8 {
+        let index = WOFF2_KNOWN_TAGS
+            .iter()
+            .position(|t| *t == self.tag)
+            .unwrap_or(63) as u8;
+        index | (self.transform_version << 6)
+    }
+}
+
+/// Encodes an SNFT font as a WOFF2 file.
+///
+/// # Arguments
+/// * `snft` - The parsed SNFT table directory of the font.
+/// * `source` - The complete source font bytes the directory refers to.
+/// * `options` - Version numbers, optional metadata/private blocks and transform control.
+///
+/// TrueType fonts get the `glyf`/`loca` transform, and the `hmtx` transform
+/// when every left side bearing equals the glyph's xMin.
+///
+/// # Errors
+/// * Returns `Error` if a table lies outside of `source` or cannot be parsed.
+pub fn encode_woff2(
+    snft: &SnftTable,
+    source: &[u8],
+    options: &Woff2EncodeOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut tables = Vec::new();
+    let mut seen = HashMap::new();
+    encode_font_tables(snft, source, options, &mut tables, &mut seen)?;
+    let total_sfnt_size = 12
+        + 16 * tables.len() as u32
+        + tables.iter().map(|t| (t.orig_length + 3) & !3).sum::<u32>();
+    write_woff2(snft.header.version, &tables, None, total_sfnt_size, options)
+}
+
+/// Encodes a font collection (TTC/OTC) as a WOFF2 file.
+///
+/// Tables shared between fonts of the collection are stored once.
+///
+/// # Errors
+/// * Returns `Error` if a table lies outside of `source` or cannot be parsed.
+pub fn encode_woff2_collection(
+    collection: &SnftCollection,
+    source: &[u8],
+    options: &Woff2EncodeOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut tables = Vec::new();
+    let mut seen = HashMap::new();
+    let mut fonts = Vec::with_capacity(collection.fonts.len());
+    for font in &collection.fonts {
+        let indices = encode_font_tables(font, source, options, &mut tables, &mut seen)?;
+        fonts.push((font.header.version, indices));
+    }
+
+    let mut directory = FontDataWriter::new();
+    directory.write_u32(
+        ((collection.header.major_version as u32) << 16) | collection.header.minor_version as u32,
+    );
+    write_255_uint16(&mut directory, fonts.len() as u16);
+    for (flavor, indices) in &fonts {
+        write_255_uint16(&mut directory, indices.len() as u16);
+        directory.write_u32(*flavor);
+        for &index in indices {
+            write_255_uint16(&mut directory, index);
+        }
+    }
+
+    let header_size = if collection.header.dsig.is_some() {
+        24
+    } else {
+        12
+    };
+    let total_sfnt_size = header_size
+        + 4 * fonts.len() as u32
+        + fonts
+            .iter()
+            .map(|(_, i)| 12 + 16 * i.len() as u32)
+            .sum::<u32>()
+        + tables.iter().map(|t| (t.orig_length + 3) & !3).sum::<u32>();
+    write_woff2(
+        TTCF_TAG,
+        &tables,
+        Some(directory.as_bytes()),
+        total_sfnt_size,
+        options,
+    )
+}
+
+/// Writes the WOFF2 header, directories, compressed data and extra blocks.
+fn write_woff2(
+    flavor: u32,
+    tables: &[Woff2TableEntry],
+    collection_directory: Option<&[u8]>,
+    total_sfnt_size: u32,
+    options: &Woff2EncodeOptions,
+) -> Result<Vec<u8>, Error> {
+    let mut stream = Vec::new();
+    for table in tables {
+        stream.extend_from_slice(&table.data);
+    }
+    let compressed = brotli_compress(&stream);
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u32(WOFF2_SIGNATURE);
+    writer.write_u32(flavor);
+    let length_field = writer.position();
+    writer.write_u32(0);
+    writer.write_u16(tables.len() as u16);
+    writer.write_u16(0); // reserved
+    writer.write_u32(total_sfnt_size);
+    writer.write_u32(compressed.len() as u32);
+    writer.write_u16(options.major_version);
+    writer.write_u16(options.minor_version);
+    let meta_fields = writer.position();
+    writer.write_zeros(20);
+
+    for table in tables {
+        write_woff2_table_entry(&mut writer, table);
+    }
+    if let Some(directory) = collection_directory {
+        writer.write_bytes(directory);
+    }
+    writer.write_bytes(&compressed);
+    writer.align(4);
+
+    let mut extra = [0u32; 5];
+    if let Some(metadata) = options.metadata {
+        let compressed = brotli_compress(metadata);
+        extra[0] = writer.position() as u32;
+        extra[1] = compressed.len() as u32;
+        extra[2] = metadata.len() as u32;
+        writer.write_bytes(&compressed);
+        writer.align(4);
+    }
+    if let Some(private) = options.private_data {
+        extra[3] = writer.position() as u32;
+        extra[4] = private.len() as u32;
+        writer.write_bytes(private);
+        writer.align(4);
+    }
+    for (i, value) in extra.iter().enumerate() {
+        writer.write_at_u32(meta_fields + 4 * i, *value)?;
+    }
+    let length = writer.len() as u32;
+    writer.write_at_u32(length_field, length)?;
+    writer.into_bytes()
+}
+
+/// Writes a WOFF2 table directory entry.
+pub fn write_woff2_table_entry(writer: &mut FontDataWriter, table: &Woff2TableEntry) {
+    let flags = table.flags();
+    writer.write_u8(flags);
+    if flags & 0x3F == 63 {
+        writer.write_tag(table.tag);
+    }
+    write_uint_base128(writer, table.orig_length);
+    if let Some(length) = table.transform_length {
+        write_uint_base128(writer, length);
+    }
+}
+
+/// Encodes the tables of one font, appending new tables to `tables`.
+///
+/// `seen` maps source table offsets to their index in `tables` so tables shared
+/// between fonts of a collection are only stored once. Returns the directory
+/// indices of the font's tables.
+fn encode_font_tables(
+    snft: &SnftTable,
+    source: &[u8],
+    options: &Woff2EncodeOptions,
+    tables: &mut Vec<Woff2TableEntry>,
+    seen: &mut HashMap<([u8; 4], u32), u16>,
+) -> Result<Vec<u16>, Error> {
+    let mut entries: Vec<&SnftTableEntry> = snft.tables.iter().collect();
+    entries.sort_by_key(|t| t.tag);
+
+    let transform_glyf = !options.skip_transforms
+        && snft.has_table(b"glyf")
+        && snft.has_table(b"loca")
+        && snft.has_table(b"head")
+        && snft.has_table(b"maxp");
+    let mut x_mins: Option<Vec<i16>> = None;
+    let mut indices = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        if entry.tag == *b"loca" && snft.has_table(b"glyf") {
+            // Written right after `glyf`, which must precede it in the directory.
+            continue;
+        }
+        if let Some(&index) = seen.get(&(entry.tag, entry.offset)) {
+            indices.push(index);
+            if entry.tag == *b"glyf"
+                && let Some(loca) = snft.get_table_by_tag(b"loca")
+            {
+                indices.push(seen[&(loca.tag, loca.offset)]);
+            }
+            continue;
+        }
+        let data = snft.table_data(&entry.tag, source)?;
+
+        let mut encoded = vec![Woff2TableEntry {
+            tag: entry.tag,
+            transform_version: 0,
+            orig_length: entry.length,
+            transform_length: None,
+            data: data.to_vec(),
+        }];
+        match &entry.tag {
+            b"glyf" => {
+                let loca_entry = snft.get_table_by_tag(b"loca");
+                let loca_data = match loca_entry {
+                    Some(e) => snft.table_data(&e.tag, source)?,
+                    None => &[],
+                };
+                if transform_glyf {
+                    let head =
+                        read_head(&mut FontDataStream::new(snft.table_data(b"head", source)?))?;
+                    let maxp =
+                        read_maxp(&mut FontDataStream::new(snft.table_data(b"maxp", source)?))?;
+                    let loca = read_loca(
+                        &mut FontDataStream::new(loca_data),
+                        head.has_long_loca(),
+                        maxp.num_glyphs,
+                    )?;
+                    let glyf = read_glyf(&mut FontDataStream::new(data), &loca)?;
+                    let transformed = transform_glyf_table(&glyf, head.index_to_loc_format);
+                    encoded[0].transform_length = Some(transformed.len() as u32);
+                    encoded[0].data = transformed;
+                    x_mins = Some(glyph_x_mins(&glyf));
+                } else {
+                    encoded[0].transform_version = 3;
+                }
+                if let Some(loca_entry) = loca_entry {
+                    encoded.push(Woff2TableEntry {
+                        tag: *b"loca",
+                        transform_version: if transform_glyf { 0 } else { 3 },
+                        orig_length: loca_entry.length,
+                        transform_length: transform_glyf.then_some(0),
+                        data: if transform_glyf {
+                            Vec::new()
+                        } else {
+                            loca_data.to_vec()
+                        },
+                    });
+                }
+            }
+            b"hmtx" => {
+                if let Some(x_mins) = &x_mins {
+                    let hhea =
+                        read_hhea(&mut FontDataStream::new(snft.table_data(b"hhea", source)?))?;
+                    let hmtx = read_hmtx(
+                        &mut FontDataStream::new(data),
+                        hhea.number_of_h_metrics,
+                        x_mins.len() as u16,
+                    )?;
+                    if let Some(transformed) = transform_hmtx_table(&hmtx, x_mins) {
+                        encoded[0].transform_version = 1;
+                        encoded[0].transform_length = Some(transformed.len() as u32);
+                        encoded[0].data = transformed;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for table in encoded {
+            let index = tables.len() as u16;
+            let offset = snft
+                .get_table_by_tag(&table.tag)
+                .map_or(entry.offset, |e| e.offset);
+            seen.insert((table.tag, offset), index);
+            indices.push(index);
+            tables.push(table);
+        }
+    }
+    Ok(indices)
+}
+
+/// Returns the xMin of every glyph, with 0 for empty glyphs.
+fn glyph_x_mins(glyf: &GlyfTable) -> Vec<i16> {
+    glyf.glyphs
+        .iter()
+        .map(|g| g.header().map_or(0, |h| h.x_min))
+        .collect()
+}
+
+/// Applies the WOFF2 `glyf` transform (version 0).
+pub fn transform_glyf_table(glyf: &GlyfTable, index_format: i16) -> Vec<u8> {
+    let num_glyphs = glyf.glyphs.len();
+    let bitmap_size = num_glyphs.div_ceil(32) * 4;
+
+    let mut n_contours = FontDataWriter::new();
+    let mut n_points = FontDataWriter::new();
+    let mut flags = FontDataWriter::new();
+    let mut glyphs = FontDataWriter::new();
+    let mut composites = FontDataWriter::new();
+    let mut bbox_bitmap = vec![0u8; bitmap_size];
+    let mut bboxes = FontDataWriter::new();
+    let mut instructions = FontDataWriter::new();
+    let mut overlap_bitmap = vec![0u8; bitmap_size];
+    let mut has_overlap = false;
+
+    for (glyph_id, glyph) in glyf.glyphs.iter().enumerate() {
+        let bit = 0x80u8 >> (glyph_id % 8);
+        match glyph {
+            Glyph::Simple(g) if !g.points.is_empty() => {
+                n_contours.write_i16(g.end_points.len() as i16);
+                let mut previous_end = -1i32;
+                for &end in &g.end_points {
+                    write_255_uint16(&mut n_points, (end as i32 - previous_end) as u16);
+                    previous_end = end as i32;
+                }
+                let (mut last_x, mut last_y) = (0i32, 0i32);
+                for point in &g.points {
+                    let dx = point.x as i32 - last_x;
+                    let dy = point.y as i32 - last_y;
+                    write_triplet(&mut flags, &mut glyphs, dx, dy, point.on_curve);
+                    last_x = point.x as i32;
+                    last_y = point.y as i32;
+                }
+                write_255_uint16(&mut glyphs, g.instructions.len() as u16);
+                instructions.write_bytes(&g.instructions);
+
+                let (x_min, y_min, x_max, y_max) = g.compute_bounds();
+                let h = &g.header;
+                if (h.x_min, h.y_min, h.x_max, h.y_max) != (x_min, y_min, x_max, y_max) {
+                    bbox_bitmap[glyph_id / 8] |= bit;
+                    write_bbox(&mut bboxes, glyph);
+                }
+                if g.overlap {
+                    overlap_bitmap[glyph_id / 8] |= bit;
+                    has_overlap = true;
+                }
+            }
+            Glyph::Composite(g) => {
+                n_contours.write_i16(-1);
+                write_composite_components(&mut composites, g);
+                if g.has_instructions() {
+                    write_255_uint16(&mut glyphs, g.instructions.len() as u16);
+                    instructions.write_bytes(&g.instructions);
+                }
+                bbox_bitmap[glyph_id / 8] |= bit;
+                write_bbox(&mut bboxes, glyph);
+            }
+            _ => n_contours.write_i16(0),
+        }
+    }
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(0); // reserved
+    writer.write_u16(has_overlap as u16);
+    writer.write_u16(num_glyphs as u16);
+    writer.write_u16(index_format as u16);
+    writer.write_u32(n_contours.len() as u32);
+    writer.write_u32(n_points.len() as u32);
+    writer.write_u32(flags.len() as u32);
+    writer.write_u32(glyphs.len() as u32);
+    writer.write_u32(composites.len() as u32);
+    writer.write_u32((bbox_bitmap.len() + bboxes.len()) as u32);
+    writer.write_u32(instructions.len() as u32);
+    writer.write_bytes(n_contours.as_bytes());
+    writer.write_bytes(n_points.as_bytes());
+    writer.write_bytes(flags.as_bytes());
+    writer.write_bytes(glyphs.as_bytes());
+    writer.write_bytes(composites.as_bytes());
+    writer.write_bytes(&bbox_bitmap);
+    writer.write_bytes(bboxes.as_bytes());
+    writer.write_bytes(instructions.as_bytes());
+    if has_overlap {
+        writer.write_bytes(&overlap_bitmap);
+    }
+    writer.as_bytes().to_vec()
+}
+
+/// Writes the explicit bounding box of a glyph to the bbox stream.
+fn write_bbox(writer: &mut FontDataWriter, glyph: &Glyph) {
+    let header = glyph.header().copied().unwrap_or_default();
+    writer.write_i16(header.x_min);
+    writer.write_i16(header.y_min);
+    writer.write_i16(header.x_max);
+    writer.write_i16(header.y_max);
+}
+
+/// Writes one point using the WOFF2 triplet encoding.
+///
+/// The flag byte goes to `flags` and the coordinate bytes to `glyphs`.
+pub fn write_triplet(
+    flags: &mut FontDataWriter,
+    glyphs: &mut FontDataWriter,
+    dx: i32,
+    dy: i32,
+    on_curve: bool,
+) {
+    let on_curve_bit = if on_curve { 0 } else { 128 };
+    let abs_x = dx.unsigned_abs();
+    let abs_y = dy.unsigned_abs();
+    let x_sign = (dx >= 0) as u32;
+    let y_sign = (dy >= 0) as u32;
+    let xy_signs = x_sign + 2 * y_sign;
+
+    let flag = if dx == 0 && abs_y < 1280 {
+        glyphs.write_u8(abs_y as u8);
+        ((abs_y & 0xF00) >> 7) + y_sign
+    } else if dy == 0 && abs_x < 1280 {
+        glyphs.write_u8(abs_x as u8);
+        10 + ((abs_x & 0xF00) >> 7) + x_sign
+    } else if abs_x < 65 && abs_y < 65 {
+        glyphs.write_u8((((abs_x - 1) & 0xF) << 4 | ((abs_y - 1) & 0xF)) as u8);
+        20 + ((abs_x - 1) & 0x30) + (((abs_y - 1) & 0x30) >> 2) + xy_signs
+    } else if abs_x < 769 && abs_y < 769 {
+        glyphs.write_u8((abs_x - 1) as u8);
+        glyphs.write_u8((abs_y - 1) as u8);
+        84 + 12 * (((abs_x - 1) & 0x300) >> 8) + (((abs_y - 1) & 0x300) >> 6) + xy_signs
+    } else if abs_x < 4096 && abs_y < 4096 {
+        glyphs.write_u8((abs_x >> 4) as u8);
+        glyphs.write_u8((((abs_x & 0xF) << 4) | (abs_y >> 8)) as u8);
+        glyphs.write_u8(abs_y as u8);
+        120 + xy_signs
+    } else {
+        glyphs.write_u16(abs_x as u16);
+        glyphs.write_u16(abs_y as u16);
+        124 + xy_signs
+    };
+    flags.write_u8((flag + on_curve_bit) as u8);
+}
+
+/// Applies the WOFF2 `hmtx` transform (version 1).
+///
+/// Returns `None` when neither side bearing array can be dropped, i.e. when
+/// some left side bearings differ from the glyphs' xMin.
+pub fn transform_hmtx_table(hmtx: &HmtxTable, x_mins: &[i16]) -> Option<Vec<u8>> {
+    let n = hmtx.h_metrics.len();
+    let proportional = hmtx
+        .h_metrics
+        .iter()
+        .enumerate()
+        .all(|(i, m)| x_mins.get(i) == Some(&m.lsb));
+    let monospaced = hmtx
+        .left_side_bearings
+        .iter()
+        .enumerate()
+        .all(|(i, lsb)| x_mins.get(n + i) == Some(lsb));
+    if !proportional && !monospaced {
+        return None;
+    }
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u8(proportional as u8 | (monospaced as u8) << 1);
+    for metric in &hmtx.h_metrics {
+        writer.write_u16(metric.advance_width);
+    }
+    if !proportional {
+        for metric in &hmtx.h_metrics {
+            writer.write_i16(metric.lsb);
+        }
+    }
+    if !monospaced {
+        for lsb in &hmtx.left_side_bearings {
+            writer.write_i16(*lsb);
+        }
+    }
+    Some(writer.as_bytes().to_vec())
+}
+
+/// Writes a `UIntBase128` value (big-endian base 128, at most 5 bytes).
+pub fn write_uint_base128(writer: &mut FontDataWriter, value: u32) {
+    let mut bytes = [0u8; 5];
+    let mut len = 0;
+    let mut v = value;
+    loop {
+        bytes[len] = (v & 0x7F) as u8;
+        len += 1;
+        v >>= 7;
+        if v == 0 {
+            break;
+        }
+    }
+    for i in (0..len).rev() {
+        writer.write_u8(bytes[i] | if i > 0 { 0x80 } else { 0 });
+    }
+}
+
+/// Writes a `255UInt16` value.
+pub fn write_255_uint16(writer: &mut FontDataWriter, value: u16) {
+    match value {
+        0..253 => writer.write_u8(value as u8),
+        253..506 => {
+            writer.write_u8(255);
+            writer.write_u8((value - 253) as u8);
+        }
+        506..762 => {
+            writer.write_u8(254);
+            writer.write_u8((value - 506) as u8);
+        }
+        _ => {
+            writer.write_u8(253);
+            writer.write_u16(value);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Common Apple Advanced Typography structures for the Aurora Font Library.
+//! Lookup tables and extended state tables are shared by `morx`, `kerx` and
+//! `ankr`, so they are parsed here, along with the driver that runs a state
+//! table over a glyph run.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Glyph id marking a glyph deleted by a state machine.
+pub const DELETED_GLYPH: u16 = 0xFFFF;
+/// State machine class of the end of the glyph run.
+pub const CLASS_END_OF_TEXT: u16 = 0;
+/// State machine class of glyphs missing from the class lookup.
+pub const CLASS_OUT_OF_BOUNDS: u16 = 1;
+/// State machine class of deleted glyphs.
+pub const CLASS_DELETED_GLYPH: u16 = 2;
+/// State machine class of the end of a line.
+pub const CLASS_END_OF_LINE: u16 = 3;
+/// Entry flag shared by every state table: process the current glyph again
+/// in the new state.
+pub const ENTRY_DONT_ADVANCE: u16 = 0x4000;
+
+/// Consecutive passes over one glyph allowed before the state machine is
+/// forced to advance.
+pub(crate) const MAX_DONT_ADVANCE: usize = 64;
+/// Glyphs a run may grow to per input glyph, and the floor of that limit, as
+/// in HarfBuzz.
+pub(crate) const MAX_LEN_FACTOR: usize = 32;
+pub(crate) const MAX_LEN_MIN: usize = 16384;
+/// Transitions a state machine may take per input glyph, and the floor of
+/// that limit, as in HarfBuzz.
+const MAX_OPS_FACTOR: usize = 64;
+const MAX_OPS_MIN: usize = 16384;
+/// Terminating glyph of segment and single lookups.
+const LOOKUP_TERMINATOR: u16 = 0xFFFF;
+
+/// A lookup table mapping glyph ids to 16-bit values.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AatLookup {
+    /// Value of each glyph that has one.
+    pub values: BTreeMap<u16, u16>,
+}
+
+impl AatLookup {
+    /// Creates a lookup from glyph and value pairs.
+    pub fn new(values: impl IntoIterator<Item = (u16, u16)>) -> Self {
+        AatLookup {
+            values: values.into_iter().collect(),
+        }
+    }
+
+    /// Returns the value of a glyph.
+    pub fn get(&self, glyph_id: u16) -> Option<u16> {
+        self.values.get(&glyph_id).copied()
+    }
+}
+
+/// An extended state table: a finite state machine whose transitions are
+/// chosen by glyph class.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StateTable<T> {
+    /// Number of glyph classes, including the four predefined ones.
+    pub class_count: u16,
+    /// Class of each glyph. Glyphs without one are out of bounds (class 1).
+    pub classes: AatLookup,
+    /// Entry index for each class, per state. State 0 starts the run.
+    pub states: Vec<Vec<u16>>,
+    /// State transitions and their actions.
+    pub entries: Vec<StateEntry<T>>,
+}
+
+/// A state transition.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StateEntry<T> {
+    /// State to move to.
+    pub new_state: u16,
+    /// Entry flags; their meaning depends on the subtable type, except for
+    /// `ENTRY_DONT_ADVANCE`.
+    pub flags: u16,
+    /// Subtable-specific data.
+    pub data: T,
+}
+
+impl<T> StateTable<T> {
+    /// Returns the class of a glyph.
+    pub fn class(&self, glyph_id: u16) -> u16 {
+        if glyph_id == DELETED_GLYPH {
+            return CLASS_DELETED_GLYPH;
+        }
+        self.classes.get(glyph_id).unwrap_or(CLASS_OUT_OF_BOUNDS)
+    }
+
+    /// Returns the transition taken from `state` on a glyph of `class`.
+    pub fn entry(&self, state: u16, class: u16) -> Option<&StateEntry<T>> {
+        let row = self.states.get(state as usize)?;
+        self.entries.get(*row.get(class as usize)? as usize)
+    }
+
+    /// Runs the state machine over `glyphs`.
+    ///
+    /// `action` is called for every transition with the entry, the glyphs and
+    /// the current position, which is `glyphs.len()` at the end of the run.
+    /// It returns the position to continue from, which lets actions that
+    /// insert glyphs step over them. The run stops early once the number of
+    /// transitions exceeds a multiple of the initial glyph count.
+    pub fn drive(
+        &self,
+        glyphs: &mut Vec<u16>,
+        mut action: impl FnMut(&StateEntry<T>, &mut Vec<u16>, usize) -> usize,
+    ) {
+        let mut state = 0;
+        let mut index = 0;
+        let mut repeats = 0;
+        let mut ops = (glyphs.len() * MAX_OPS_FACTOR).max(MAX_OPS_MIN);
+        while ops > 0 {
+            ops -= 1;
+            let at_end = index >= glyphs.len();
+            let class = match glyphs.get(index) {
+                Some(&glyph) => self.class(glyph),
+                None => CLASS_END_OF_TEXT,
+            };
+            let Some(entry) = self.entry(state, class) else {
+                break;
+            };
+            index = action(entry, glyphs, index);
+            state = entry.new_state;
+            if at_end {
+                break;
+            }
+            if entry.flags & ENTRY_DONT_ADVANCE != 0 && repeats < MAX_DONT_ADVANCE {
+                repeats += 1;
+            } else {
+                index += 1;
+                repeats = 0;
+            }
+        }
+    }
+}
+
+/// Extracts a lookup table. `num_glyphs` is needed by format 0, which has a
+/// value for every glyph.
+pub fn read_lookup(data: &mut FontDataStream, num_glyphs: u16) -> Result<AatLookup, Error> {
+    let format = data.read_u16()?;
+    let mut values = BTreeMap::new();
+    match format {
+        0 => {
+            for glyph in 0..num_glyphs {
+                values.insert(glyph, data.read_u16()?);
+            }
+        }
+        2 | 4 | 6 => {
+            let unit_size = data.read_u16()? as usize;
+            let count = data.read_u16()?;
+            data.skip(6);
+            let minimum = if format == 6 { 4 } else { 6 };
+            if unit_size < minimum {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            let start = data.position();
+            for index in 0..count as usize {
+                let mut unit = data.substream(start + index * unit_size)?;
+                if format == 6 {
+                    let glyph = unit.read_u16()?;
+                    let value = unit.read_u16()?;
+                    if glyph != LOOKUP_TERMINATOR {
+                        values.insert(glyph, value);
+                    }
+                    continue;
+                }
+                let last = unit.read_u16()?;
+                let first = unit.read_u16()?;
+                let value = unit.read_u16()?;
+                if first == LOOKUP_TERMINATOR && last == LOOKUP_TERMINATOR {
+                    continue;
+                }
+                if first > last {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                if format == 2 {
+                    values.extend((first..=last).map(|glyph| (glyph, value)));
+                } else {
+                    // Offset, from the lookup start, of one value per glyph.
+                    let mut array = data.substream(value as usize)?;
+                    for glyph in first..=last {
+                        values.insert(glyph, array.read_u16()?);
+                    }
+                }
+            }
+        }
+        8 => {
+            let first = data.read_u16()?;
+            let count = data.read_u16()?;
+            for index in 0..count {
+                let glyph = first
+                    .checked_add(index)
+                    .ok_or(Error::Io(IoError::InvalidData))?;
+                values.insert(glyph, data.read_u16()?);
+            }
+        }
+        10 => {
+            let unit_size = data.read_u16()?;
+            let first = data.read_u16()?;
+            let count = data.read_u16()?;
+            for index in 0..count {
+                let glyph = first
+                    .checked_add(index)
+                    .ok_or(Error::Io(IoError::InvalidData))?;
+                let value = match unit_size {
+                    1 => data.read_u8()? as u16,
+                    2 => data.read_u16()?,
+                    _ => return Err(Error::InvalidFormat),
+                };
+                values.insert(glyph, value);
+            }
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    Ok(AatLookup { values })
+}
+
+/// Extracts an extended state table from a stream starting at its header;
+/// offsets are measured from there. `read_data` reads the subtable-specific
+/// data following each entry's new state and flags.
+///
+/// The number of states and entries is not recorded, so only those reachable
+/// from state 0 are read.
+pub fn read_state_table<T>(
+    data: &FontDataStream,
+    num_glyphs: u16,
+    mut read_data: impl FnMut(&mut FontDataStream) -> Result<T, Error>,
+) -> Result<StateTable<T>, Error> {
+    let class_count = data.read_at_u32(0)?;
+    let class_table = data.read_at_u32(4)? as usize;
+    let state_array = data.read_at_u32(8)? as usize;
+    let entry_table = data.read_at_u32(12)? as usize;
+    let class_count = u16::try_from(class_count).map_err(|_| Error::Io(IoError::InvalidData))?;
+    let classes = read_lookup(&mut data.substream(class_table)?, num_glyphs)?;
+
+    let row_size = class_count as usize * 2;
+    let mut entry_stream = data.substream(entry_table)?;
+    let mut states: Vec<Vec<u16>> = Vec::new();
+    let mut entries = Vec::new();
+    let mut state_count = 1;
+    let mut entry_count = 0;
+    while states.len() < state_count || entries.len() < entry_count {
+        while states.len() < state_count {
+            let mut row_stream = data.substream(state_array + states.len() * row_size)?;
+            let row = (0..class_count)
+                .map(|_| row_stream.read_u16())
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(&max) = row.iter().max() {
+                entry_count = entry_count.max(max as usize + 1);
+            }
+            states.push(row);
+        }
+        while entries.len() < entry_count {
+            let new_state = entry_stream.read_u16()?;
+            let flags = entry_stream.read_u16()?;
+            let data = read_data(&mut entry_stream)?;
+            state_count = state_count.max(new_state as usize + 1);
+            entries.push(StateEntry {
+                new_state,
+                flags,
+                data,
+            });
+        }
+    }
+    Ok(StateTable {
+        class_count,
+        classes,
+        states,
+        entries,
+    })
+}
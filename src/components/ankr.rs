@@ -0,0 +1,58 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `ankr` (Anchor Point) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::components::aat::read_lookup;
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Anchor point table: attachment points of glyphs, used by `kerx`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AnkrTable {
+    /// Table version (0).
+    pub version: u16,
+    /// Table flags (0).
+    pub flags: u16,
+    /// Anchor points `(x, y)` of each glyph that has any, in font units.
+    pub anchors: BTreeMap<u16, Vec<(i16, i16)>>,
+}
+
+impl AnkrTable {
+    /// Returns an anchor point of a glyph.
+    pub fn anchor(&self, glyph_id: u16, index: u16) -> Option<(i16, i16)> {
+        self.anchors.get(&glyph_id)?.get(index as usize).copied()
+    }
+}
+
+/// Extracts the `ankr` table from the provided data stream. `num_glyphs` is
+/// the glyph count from `maxp`.
+pub fn read_ankr(data: &mut FontDataStream, num_glyphs: u16) -> Result<AnkrTable, Error> {
+    let version = data.read_u16()?;
+    if version != 0 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let flags = data.read_u16()?;
+    let lookup_offset = data.read_u32()? as usize;
+    let glyph_data = data.read_u32()? as usize;
+    let lookup = read_lookup(&mut data.substream(lookup_offset)?, num_glyphs)?;
+    let mut anchors = BTreeMap::new();
+    for (glyph, offset) in lookup.values {
+        let mut points = data.substream(glyph_data + offset as usize)?;
+        let count = points.read_u32()?;
+        let points = (0..count)
+            .map(|_| Ok((points.read_i16()?, points.read_i16()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        anchors.insert(glyph, points);
+    }
+    Ok(AnkrTable {
+        version,
+        flags,
+        anchors,
+    })
+}
@@ -0,0 +1,176 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `avar` (Axis Variations) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::fvar::to_f2dot14;
+use super::variation::{
+    DeltaSetIndexMap, ItemVariationStore, read_delta_set_index_map, read_item_variation_store,
+    write_delta_set_index_map, write_item_variation_store,
+};
+
+/// Axis variations table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AvarTable {
+    /// Major version (1 or 2).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// `(from, to)` normalized coordinate pairs of each axis, in `fvar` order.
+    pub segment_maps: Vec<Vec<(f32, f32)>>,
+    /// Version 2: item of each axis in `variation_store`; without it the
+    /// item of axis `i` is `(0, i)`.
+    pub axis_index_map: Option<DeltaSetIndexMap>,
+    /// Version 2: deltas, in `F2Dot14` units, added to the segment-mapped
+    /// coordinates.
+    pub variation_store: Option<ItemVariationStore>,
+}
+
+impl AvarTable {
+    /// Applies the whole table to default-normalized coordinates: the segment
+    /// maps of every axis, then the version 2 deltas, which depend on all the
+    /// segment-mapped coordinates at once.
+    pub fn map_coords(&self, coords: &[f32]) -> Vec<f32> {
+        let mapped: Vec<f32> = coords
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| self.map(i, value))
+            .collect();
+        let Some(store) = &self.variation_store else {
+            return mapped;
+        };
+        let scalars = store.region_scalars(&mapped);
+        mapped
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let (outer, inner) = match &self.axis_index_map {
+                    Some(map) => match map.get(i as u32) {
+                        Some(item) => item,
+                        None => return value,
+                    },
+                    None => (0, i as u16),
+                };
+                if (outer, inner) == (0xFFFF, 0xFFFF) {
+                    return value;
+                }
+                let delta = store.delta_with_scalars(outer, inner, &scalars);
+                to_f2dot14((value + delta / 16384.0).clamp(-1.0, 1.0))
+            })
+            .collect()
+    }
+
+    /// Returns whether the mapping of an axis depends on the other axes.
+    pub fn has_cross_axis_mapping(&self) -> bool {
+        self.variation_store.is_some()
+    }
+
+    /// Applies the segment map of axis `axis_index` to a normalized coordinate.
+    /// Axes without a map, or with fewer than the three required entries, are
+    /// left unchanged.
+    pub fn map(&self, axis_index: usize, value: f32) -> f32 {
+        match self.segment_maps.get(axis_index) {
+            Some(map) if map.len() >= 3 => to_f2dot14(map_segments(map, value)),
+            _ => value,
+        }
+    }
+}
+
+/// Interpolates `value` through piecewise-linear `(from, to)` pairs.
+fn map_segments(map: &[(f32, f32)], value: f32) -> f32 {
+    let Some(upper) = map.iter().position(|&(from, _)| from >= value) else {
+        let &(from, to) = map.last().unwrap_or(&(0.0, 0.0));
+        return value - from + to;
+    };
+    let (from1, to1) = map[upper];
+    if from1 == value || upper == 0 {
+        return value - from1 + to1;
+    }
+    let (from0, to0) = map[upper - 1];
+    to0 + (to1 - to0) * (value - from0) / (from1 - from0)
+}
+
+/// Extracts the `avar` table from the provided data stream.
+pub fn read_avar(data: &mut FontDataStream) -> Result<AvarTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 && major_version != 2 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    data.skip(2); // reserved
+    let axis_count = data.read_u16()?;
+    let mut segment_maps = Vec::with_capacity(axis_count as usize);
+    for _ in 0..axis_count {
+        let count = data.read_u16()?;
+        let map = (0..count)
+            .map(|_| Ok((data.read_f2dot14()?, data.read_f2dot14()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        segment_maps.push(map);
+    }
+    let (mut axis_index_map, mut variation_store) = (None, None);
+    if major_version == 2 {
+        let map_offset = data.read_u32()? as usize;
+        let store_offset = data.read_u32()? as usize;
+        if map_offset != 0 {
+            axis_index_map = Some(read_delta_set_index_map(&mut data.substream(map_offset)?)?);
+        }
+        if store_offset != 0 {
+            variation_store = Some(read_item_variation_store(
+                &mut data.substream(store_offset)?,
+            )?);
+        }
+    }
+    Ok(AvarTable {
+        major_version,
+        minor_version,
+        segment_maps,
+        axis_index_map,
+        variation_store,
+    })
+}
+
+/// Serializes the `avar` table. The version 2 fields are written when
+/// `major_version` is 2.
+pub fn write_avar(writer: &mut FontDataWriter, avar: &AvarTable) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(avar.major_version);
+    writer.write_u16(avar.minor_version);
+    writer.write_u16(0);
+    writer.write_u16(avar.segment_maps.len() as u16);
+    for map in &avar.segment_maps {
+        writer.write_u16(map.len() as u16);
+        for &(from, to) in map {
+            writer.write_f2dot14(from);
+            writer.write_f2dot14(to);
+        }
+    }
+    if avar.major_version < 2 {
+        return Ok(());
+    }
+    match &avar.axis_index_map {
+        Some(map) => {
+            let offset = writer.reserve_offset32(base);
+            let mut bytes = FontDataWriter::new();
+            write_delta_set_index_map(&mut bytes, map);
+            writer.defer_subtable(offset, bytes.into_bytes()?);
+        }
+        None => writer.write_u32(0),
+    }
+    match &avar.variation_store {
+        Some(store) => {
+            let offset = writer.reserve_offset32(base);
+            let mut bytes = FontDataWriter::new();
+            write_item_variation_store(&mut bytes, store)?;
+            writer.defer_subtable(offset, bytes.into_bytes()?);
+        }
+        None => writer.write_u32(0),
+    }
+    Ok(())
+}
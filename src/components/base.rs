@@ -0,0 +1,365 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `BASE` (Baseline) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::tags::script::ScriptTag;
+
+use super::layout::{DeviceTable, read_at, read_device, read_optional_at};
+use super::variation::{ItemVariationStore, read_item_variation_store};
+
+/// Baseline tag: hanging baseline of Tibetan and similar scripts.
+pub const BASELINE_HANGING: [u8; 4] = *b"hang";
+/// Baseline tag: bottom of the ideographic character face.
+pub const BASELINE_IDEOGRAPHIC_FACE_BOTTOM: [u8; 4] = *b"icfb";
+/// Baseline tag: top of the ideographic character face.
+pub const BASELINE_IDEOGRAPHIC_FACE_TOP: [u8; 4] = *b"icft";
+/// Baseline tag: bottom of the ideographic em-box.
+pub const BASELINE_IDEOGRAPHIC: [u8; 4] = *b"ideo";
+/// Baseline tag: top of the ideographic em-box.
+pub const BASELINE_IDEOGRAPHIC_TOP: [u8; 4] = *b"idtp";
+/// Baseline tag: math formula baseline.
+pub const BASELINE_MATH: [u8; 4] = *b"math";
+/// Baseline tag: alphabetic baseline of Latin, Greek and Cyrillic.
+pub const BASELINE_ROMAN: [u8; 4] = *b"romn";
+
+/// Baseline table: baseline positions and line extents per script.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BaseTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0 or 1).
+    pub minor_version: u16,
+    /// Baselines for horizontal text.
+    pub horizontal: Option<BaseAxis>,
+    /// Baselines for vertical text.
+    pub vertical: Option<BaseAxis>,
+    /// Item variation store referenced by variation index tables (version
+    /// 1.1).
+    pub item_var_store: Option<ItemVariationStore>,
+}
+
+/// Baselines of one text direction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BaseAxis {
+    /// Baseline tags, in the order of every script's coordinates.
+    pub baseline_tags: Vec<[u8; 4]>,
+    /// Scripts in tag order.
+    pub scripts: Vec<BaseScriptRecord>,
+}
+
+/// A script with its tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BaseScriptRecord {
+    /// OpenType script tag.
+    pub script_tag: [u8; 4],
+    /// Baseline data of the script.
+    pub script: BaseScript,
+}
+
+/// Baseline data of a script.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BaseScript {
+    /// Baseline positions.
+    pub base_values: Option<BaseValues>,
+    /// Extents used when no language system matches.
+    pub default_min_max: Option<MinMax>,
+    /// Extents of language systems in tag order.
+    pub lang_sys_records: Vec<BaseLangSysRecord>,
+}
+
+/// A language system's extents with its tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BaseLangSysRecord {
+    /// OpenType language system tag.
+    pub lang_sys_tag: [u8; 4],
+    /// Extents of the language system.
+    pub min_max: MinMax,
+}
+
+/// Baseline positions of a script.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BaseValues {
+    /// Index of the script's own baseline in the axis tags.
+    pub default_baseline_index: u16,
+    /// Position of each baseline, in the order of the axis tags.
+    pub coordinates: Vec<BaseCoord>,
+}
+
+/// Lowest and highest extents of glyphs, with feature-specific overrides.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MinMax {
+    /// Lowest extent (lowest descent or leftmost position).
+    pub min: Option<BaseCoord>,
+    /// Highest extent (highest ascent or rightmost position).
+    pub max: Option<BaseCoord>,
+    /// Extents while features are applied, in tag order.
+    pub features: Vec<FeatMinMaxRecord>,
+}
+
+/// Extents while a feature is applied.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FeatMinMaxRecord {
+    /// OpenType feature tag.
+    pub feature_tag: [u8; 4],
+    /// Lowest extent.
+    pub min: Option<BaseCoord>,
+    /// Highest extent.
+    pub max: Option<BaseCoord>,
+}
+
+/// A baseline or extent position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BaseCoord {
+    /// Format 1: coordinate in font units.
+    Coordinate(i16),
+    /// Format 2: coordinate that follows a contour point of a glyph when it
+    /// is hinted.
+    GlyphPoint {
+        /// Coordinate in font units.
+        coordinate: i16,
+        /// Glyph whose outline holds the point.
+        reference_glyph: u16,
+        /// Index of the contour point.
+        base_coord_point: u16,
+    },
+    /// Format 3: coordinate adjusted by a device or variation index table.
+    Device(i16, DeviceTable),
+}
+
+impl BaseCoord {
+    /// Returns the coordinate in font units, without adjustments.
+    pub fn coordinate(&self) -> i16 {
+        match self {
+            BaseCoord::Coordinate(coordinate)
+            | BaseCoord::GlyphPoint { coordinate, .. }
+            | BaseCoord::Device(coordinate, _) => *coordinate,
+        }
+    }
+}
+
+/// A baseline position with the extents of the language system it was looked
+/// up for, in font units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Baseline {
+    /// Position of the baseline.
+    pub coordinate: i16,
+    /// Lowest extent of the language system, if the font gives one.
+    pub min: Option<i16>,
+    /// Highest extent of the language system, if the font gives one.
+    pub max: Option<i16>,
+}
+
+impl BaseScript {
+    /// Returns the extents of a language system, or the script's default
+    /// extents when the language has none or is `None`.
+    pub fn min_max(&self, language: Option<[u8; 4]>) -> Option<&MinMax> {
+        language
+            .and_then(|language| {
+                self.lang_sys_records
+                    .iter()
+                    .find(|record| record.lang_sys_tag == language)
+            })
+            .map(|record| &record.min_max)
+            .or(self.default_min_max.as_ref())
+    }
+}
+
+impl BaseAxis {
+    /// Returns the baseline data of a script, falling back to the `DFLT`
+    /// script.
+    pub fn script(&self, script: ScriptTag) -> Option<&BaseScript> {
+        let find = |tag: [u8; 4]| {
+            self.scripts
+                .iter()
+                .find(|record| record.script_tag == tag)
+                .map(|record| &record.script)
+        };
+        find(script.tag()).or_else(|| find(ScriptTag::Default.tag()))
+    }
+
+    /// Returns the position of a baseline for a script, with the extents of
+    /// `language`.
+    ///
+    /// Returns `None` if the axis does not list the baseline or the script
+    /// has no baseline positions.
+    pub fn baseline(
+        &self,
+        script: ScriptTag,
+        language: Option<[u8; 4]>,
+        baseline_tag: [u8; 4],
+    ) -> Option<Baseline> {
+        let index = self
+            .baseline_tags
+            .iter()
+            .position(|&tag| tag == baseline_tag)?;
+        let script = self.script(script)?;
+        let coordinate = script.base_values.as_ref()?.coordinates.get(index)?;
+        let min_max = script.min_max(language);
+        Some(Baseline {
+            coordinate: coordinate.coordinate(),
+            min: min_max
+                .and_then(|m| m.min.as_ref())
+                .map(BaseCoord::coordinate),
+            max: min_max
+                .and_then(|m| m.max.as_ref())
+                .map(BaseCoord::coordinate),
+        })
+    }
+}
+
+impl BaseTable {
+    /// Returns the position of a baseline in horizontal text for a script,
+    /// with the extents of `language`; see [`BaseAxis::baseline`].
+    pub fn baseline(
+        &self,
+        script: ScriptTag,
+        language: Option<[u8; 4]>,
+        baseline_tag: [u8; 4],
+    ) -> Option<Baseline> {
+        self.horizontal
+            .as_ref()?
+            .baseline(script, language, baseline_tag)
+    }
+}
+
+/// Extracts the `BASE` table from the provided data stream.
+pub fn read_base(data: &mut FontDataStream) -> Result<BaseTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let horizontal_offset = data.read_u16()? as usize;
+    let vertical_offset = data.read_u16()? as usize;
+    let item_var_store_offset = if minor_version >= 1 {
+        data.read_u32()? as usize
+    } else {
+        0
+    };
+    Ok(BaseTable {
+        major_version,
+        minor_version,
+        horizontal: read_optional_at(data, horizontal_offset, read_base_axis)?,
+        vertical: read_optional_at(data, vertical_offset, read_base_axis)?,
+        item_var_store: read_optional_at(data, item_var_store_offset, read_item_variation_store)?,
+    })
+}
+
+fn read_base_axis(data: &mut FontDataStream) -> Result<BaseAxis, Error> {
+    let tag_list_offset = data.read_u16()? as usize;
+    let script_list_offset = data.read_u16()? as usize;
+    let baseline_tags = read_optional_at(data, tag_list_offset, |list| {
+        let count = list.read_u16()?;
+        (0..count)
+            .map(|_| list.read_tag())
+            .collect::<Result<Vec<_>, _>>()
+    })?
+    .unwrap_or_default();
+    let scripts = read_optional_at(data, script_list_offset, |list| {
+        let base = list.substream(0)?;
+        let count = list.read_u16()?;
+        (0..count)
+            .map(|_| {
+                let script_tag = list.read_tag()?;
+                let offset = list.read_u16()? as usize;
+                Ok(BaseScriptRecord {
+                    script_tag,
+                    script: read_at(&base, offset, read_base_script)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+    })?
+    .unwrap_or_default();
+    Ok(BaseAxis {
+        baseline_tags,
+        scripts,
+    })
+}
+
+fn read_base_script(data: &mut FontDataStream) -> Result<BaseScript, Error> {
+    let base = data.substream(0)?;
+    let values_offset = data.read_u16()? as usize;
+    let default_min_max_offset = data.read_u16()? as usize;
+    let count = data.read_u16()?;
+    let lang_sys_records = (0..count)
+        .map(|_| {
+            let lang_sys_tag = data.read_tag()?;
+            let offset = data.read_u16()? as usize;
+            Ok(BaseLangSysRecord {
+                lang_sys_tag,
+                min_max: read_at(&base, offset, read_min_max)?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(BaseScript {
+        base_values: read_optional_at(&base, values_offset, |values| {
+            let base = values.substream(0)?;
+            let default_baseline_index = values.read_u16()?;
+            let count = values.read_u16()?;
+            let coordinates = (0..count)
+                .map(|_| {
+                    let offset = values.read_u16()? as usize;
+                    read_at(&base, offset, read_base_coord)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(BaseValues {
+                default_baseline_index,
+                coordinates,
+            })
+        })?,
+        default_min_max: read_optional_at(&base, default_min_max_offset, read_min_max)?,
+        lang_sys_records,
+    })
+}
+
+fn read_min_max(data: &mut FontDataStream) -> Result<MinMax, Error> {
+    let base = data.substream(0)?;
+    let min_offset = data.read_u16()? as usize;
+    let max_offset = data.read_u16()? as usize;
+    let count = data.read_u16()?;
+    let features = (0..count)
+        .map(|_| {
+            let feature_tag = data.read_tag()?;
+            let min_offset = data.read_u16()? as usize;
+            let max_offset = data.read_u16()? as usize;
+            Ok(FeatMinMaxRecord {
+                feature_tag,
+                min: read_optional_at(&base, min_offset, read_base_coord)?,
+                max: read_optional_at(&base, max_offset, read_base_coord)?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(MinMax {
+        min: read_optional_at(&base, min_offset, read_base_coord)?,
+        max: read_optional_at(&base, max_offset, read_base_coord)?,
+        features,
+    })
+}
+
+/// Extracts a base coordinate table.
+pub fn read_base_coord(data: &mut FontDataStream) -> Result<BaseCoord, Error> {
+    let format = data.read_u16()?;
+    let coordinate = data.read_i16()?;
+    match format {
+        1 => Ok(BaseCoord::Coordinate(coordinate)),
+        2 => Ok(BaseCoord::GlyphPoint {
+            coordinate,
+            reference_glyph: data.read_u16()?,
+            base_coord_point: data.read_u16()?,
+        }),
+        3 => {
+            let offset = data.read_u16()? as usize;
+            Ok(BaseCoord::Device(
+                coordinate,
+                read_at(data, offset, read_device)?,
+            ))
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
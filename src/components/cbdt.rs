@@ -0,0 +1,26 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `CBDT` (Color Bitmap Data) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+
+use super::ebdt::{EbdtTable, read_data_table};
+
+pub use super::ebdt::{
+    BitmapData, BitmapGlyph, EbdtComponent, GlyphImage, GlyphImageData, GlyphMetrics,
+    read_glyph_image,
+};
+
+/// Color bitmap data table; laid out as `EBDT` with major version 3.
+/// Early color fonts used major version 2, which is also accepted.
+pub type CbdtTable = EbdtTable;
+
+/// Extracts the `CBDT` table from the provided data stream.
+pub fn read_cbdt(data: &mut FontDataStream) -> Result<CbdtTable, Error> {
+    read_data_table(data, &[2, 3])
+}
@@ -0,0 +1,26 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `CBLC` (Color Bitmap Location) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+
+use super::eblc::{EblcTable, read_location_table};
+
+pub use super::eblc::{
+    BigGlyphMetrics, BitmapSize, GlyphLocation, IndexSubTable, IndexSubTableData, SbitLineMetrics,
+    SmallGlyphMetrics,
+};
+
+/// Color bitmap location table; laid out as `EBLC` with major version 3.
+/// Early color fonts used major version 2, which is also accepted.
+pub type CblcTable = EblcTable;
+
+/// Extracts the `CBLC` table from the provided data stream.
+pub fn read_cblc(data: &mut FontDataStream) -> Result<CblcTable, Error> {
+    read_location_table(data, &[2, 3], true)
+}
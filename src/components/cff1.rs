@@ -0,0 +1,1002 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `CFF ` (Compact Font Format version 1) table representation and utilities for the
+//! Aurora Font Library. Only the first font of a FontSet is supported, as in OpenType.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Top DICT operator: charset offset.
+pub const OP_CHARSET: u16 = 15;
+/// Top DICT operator: encoding offset.
+pub const OP_ENCODING: u16 = 16;
+/// Top DICT operator: CharStrings INDEX offset.
+pub const OP_CHAR_STRINGS: u16 = 17;
+/// Top/Font DICT operator: Private DICT size and offset.
+pub const OP_PRIVATE: u16 = 18;
+/// Private DICT operator: local subroutine INDEX offset (from the Private DICT).
+pub const OP_SUBRS: u16 = 19;
+/// Top DICT operator: registry, ordering and supplement of a CID-keyed font.
+pub const OP_ROS: u16 = 0x0C1E;
+/// Top DICT operator: number of CIDs.
+pub const OP_CID_COUNT: u16 = 0x0C22;
+/// Top DICT operator: Font DICT INDEX offset.
+pub const OP_FD_ARRAY: u16 = 0x0C24;
+/// Top DICT operator: FDSelect offset.
+pub const OP_FD_SELECT: u16 = 0x0C25;
+
+/// Charstring operator: horizontal stem hints.
+pub const CS_HSTEM: u16 = 1;
+/// Charstring operator: vertical stem hints.
+pub const CS_VSTEM: u16 = 3;
+/// Charstring operator: call a local subroutine.
+pub const CS_CALLSUBR: u16 = 10;
+/// Charstring operator: return from a subroutine.
+pub const CS_RETURN: u16 = 11;
+/// Charstring operator: end of the glyph.
+pub const CS_ENDCHAR: u16 = 14;
+/// Charstring operator: horizontal stem hints that may be masked.
+pub const CS_HSTEMHM: u16 = 18;
+/// Charstring operator: hint mask.
+pub const CS_HINTMASK: u16 = 19;
+/// Charstring operator: counter mask.
+pub const CS_CNTRMASK: u16 = 20;
+/// Charstring operator: vertical stem hints that may be masked.
+pub const CS_VSTEMHM: u16 = 23;
+/// Charstring operator: call a global subroutine.
+pub const CS_CALLGSUBR: u16 = 29;
+
+/// Number of predefined strings; custom strings start at this SID.
+pub const STANDARD_STRING_COUNT: u16 = 391;
+
+/// Maximum subroutine nesting depth allowed by the Type 2 charstring format.
+const MAX_SUBR_DEPTH: usize = 10;
+
+/// Standard Encoding: SIDs of the codes 161..=251 (0 where undefined).
+const STANDARD_ENCODING_HIGH: [u16; 91] = [
+    96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 0, 111, 112, 113, 114,
+    0, 115, 116, 117, 118, 119, 120, 121, 122, 0, 123, 0, 124, 125, 126, 127, 128, 129, 130, 131,
+    0, 132, 133, 0, 134, 135, 136, 137, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 138, 0,
+    139, 0, 0, 0, 0, 140, 141, 142, 143, 0, 0, 0, 0, 0, 144, 0, 0, 0, 145, 0, 0, 146, 147, 148,
+    149,
+];
+
+/// Returns the SID a Standard Encoding code maps to (0 for undefined codes).
+pub fn standard_encoding_sid(code: u8) -> u16 {
+    match code {
+        32..=126 => code as u16 - 31,
+        161..=251 => STANDARD_ENCODING_HIGH[code as usize - 161],
+        _ => 0,
+    }
+}
+
+/// A DICT operand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operand {
+    /// An integer operand.
+    Integer(i32),
+    /// A real number operand.
+    Real(f64),
+}
+
+impl Operand {
+    /// Returns the operand as an integer, truncating reals.
+    pub fn as_i32(&self) -> i32 {
+        match *self {
+            Operand::Integer(value) => value,
+            Operand::Real(value) => value as i32,
+        }
+    }
+}
+
+/// A Top, Font or Private DICT as `(operator, operands)` entries in file order.
+/// Two-byte operators are stored as `0x0C00 | second_byte`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Dict {
+    /// Entries in file order.
+    pub entries: Vec<(u16, Vec<Operand>)>,
+}
+
+impl Dict {
+    /// Returns the operands of `operator`, if present.
+    pub fn get(&self, operator: u16) -> Option<&[Operand]> {
+        self.entries
+            .iter()
+            .find(|(op, _)| *op == operator)
+            .map(|(_, operands)| operands.as_slice())
+    }
+
+    /// Replaces the operands of `operator` or appends a new entry.
+    pub fn set(&mut self, operator: u16, operands: Vec<Operand>) {
+        match self.entries.iter_mut().find(|(op, _)| *op == operator) {
+            Some(entry) => entry.1 = operands,
+            None => self.entries.push((operator, operands)),
+        }
+    }
+
+    /// Removes `operator` from the dictionary.
+    pub fn remove(&mut self, operator: u16) {
+        self.entries.retain(|(op, _)| *op != operator);
+    }
+}
+
+/// A Private DICT and its local subroutines.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PrivateDict {
+    /// Private DICT entries; `OP_SUBRS` is rewritten on serialization.
+    pub dict: Dict,
+    /// Local subroutines.
+    pub local_subrs: Vec<Vec<u8>>,
+}
+
+/// A Font DICT of a CID-keyed font.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FontDict {
+    /// Font DICT entries; `OP_PRIVATE` is rewritten on serialization.
+    pub dict: Dict,
+    /// The Private DICT of the font.
+    pub private: PrivateDict,
+}
+
+/// Character code assignments of a name-keyed font.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CffEncoding {
+    /// Adobe Standard Encoding.
+    #[default]
+    Standard,
+    /// Expert Encoding.
+    Expert,
+    /// Custom encoding: `(code, glyph_id)` pairs and supplementary `(code, SID)` pairs.
+    Custom {
+        /// Codes of glyphs.
+        codes: Vec<(u8, u16)>,
+        /// Additional codes mapped by SID.
+        supplements: Vec<(u8, u16)>,
+    },
+}
+
+/// Compact Font Format table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CffTable {
+    /// Major version (1).
+    pub major_version: u8,
+    /// Minor version (0).
+    pub minor_version: u8,
+    /// PostScript name of the font.
+    pub name: Vec<u8>,
+    /// Top DICT; offset operators are rewritten on serialization.
+    pub top_dict: Dict,
+    /// Custom strings, indexed by SID minus `STANDARD_STRING_COUNT`.
+    pub strings: Vec<Vec<u8>>,
+    /// Global subroutines.
+    pub global_subrs: Vec<Vec<u8>>,
+    /// Type 2 charstrings indexed by glyph id.
+    pub char_strings: Vec<Vec<u8>>,
+    /// SID (name-keyed) or CID (CID-keyed) of each glyph; glyph 0 is always 0.
+    pub charset: Vec<u16>,
+    /// Encoding of a name-keyed font.
+    pub encoding: CffEncoding,
+    /// Private DICT of a name-keyed font.
+    pub private: Option<PrivateDict>,
+    /// Font DICTs of a CID-keyed font.
+    pub fd_array: Vec<FontDict>,
+    /// Font DICT index of each glyph of a CID-keyed font.
+    pub fd_select: Vec<u8>,
+}
+
+impl CffTable {
+    /// Returns true for CID-keyed fonts.
+    pub fn is_cid(&self) -> bool {
+        self.top_dict.get(OP_ROS).is_some()
+    }
+
+    /// Returns the local subroutines used by a glyph.
+    pub fn local_subrs(&self, glyph_id: u16) -> &[Vec<u8>] {
+        if self.is_cid() {
+            let fd = self.fd_select.get(glyph_id as usize).copied().unwrap_or(0) as usize;
+            self.fd_array
+                .get(fd)
+                .map_or(&[], |font| font.private.local_subrs.as_slice())
+        } else {
+            self.private
+                .as_ref()
+                .map_or(&[], |private| private.local_subrs.as_slice())
+        }
+    }
+
+    /// Returns the glyph with the given SID (name-keyed fonts) or CID.
+    pub fn glyph_for_charset_id(&self, id: u16) -> Option<u16> {
+        self.charset.iter().position(|&c| c == id).map(|g| g as u16)
+    }
+}
+
+/// Returns the bias added to subroutine numbers for an INDEX of `count` subroutines.
+pub fn subr_bias(count: usize) -> i32 {
+    if count < 1240 {
+        107
+    } else if count < 33900 {
+        1131
+    } else {
+        32768
+    }
+}
+
+/// Extracts an INDEX structure.
+pub fn read_index(data: &mut FontDataStream) -> Result<Vec<Vec<u8>>, Error> {
+    let count = data.read_u16()? as usize;
+    read_index_items(data, count)
+}
+
+/// Extracts the items of an INDEX structure following its count field.
+pub(crate) fn read_index_items(
+    data: &mut FontDataStream,
+    count: usize,
+) -> Result<Vec<Vec<u8>>, Error> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let off_size = data.read_u8()?;
+    if !(1..=4).contains(&off_size) {
+        return Err(Error::InvalidFormat);
+    }
+    let offsets = (0..=count)
+        .map(|_| {
+            let mut offset = 0usize;
+            for _ in 0..off_size {
+                offset = (offset << 8) | data.read_u8()? as usize;
+            }
+            Ok(offset)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let base = data.position() - 1;
+    let items = offsets
+        .windows(2)
+        .map(|w| {
+            if w[1] < w[0] {
+                return Err(Error::Io(IoError::InvalidOffset { offset: w[1] }));
+            }
+            Ok(data.slice_at(base + w[0], w[1] - w[0])?.to_vec())
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    data.seek(base + offsets[count]);
+    Ok(items)
+}
+
+/// Serializes an INDEX structure with the smallest offset size.
+pub fn write_index(writer: &mut FontDataWriter, items: &[Vec<u8>]) {
+    writer.write_u16(items.len() as u16);
+    write_index_items(writer, items);
+}
+
+/// Serializes the items of an INDEX structure following its count field.
+pub(crate) fn write_index_items(writer: &mut FontDataWriter, items: &[Vec<u8>]) {
+    if items.is_empty() {
+        return;
+    }
+    let total: usize = items.iter().map(Vec::len).sum::<usize>() + 1;
+    let off_size = match total {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x10000..=0xFF_FFFF => 3,
+        _ => 4,
+    };
+    writer.write_u8(off_size);
+    let mut offset = 1;
+    let write_offset = |writer: &mut FontDataWriter, offset: usize| {
+        for i in (0..off_size).rev() {
+            writer.write_u8((offset >> (8 * i as usize)) as u8);
+        }
+    };
+    write_offset(writer, offset);
+    for item in items {
+        offset += item.len();
+        write_offset(writer, offset);
+    }
+    for item in items {
+        writer.write_bytes(item);
+    }
+}
+
+fn read_real(data: &mut FontDataStream) -> Result<f64, Error> {
+    let mut text = String::new();
+    'nibbles: loop {
+        let byte = data.read_u8()?;
+        for nibble in [byte >> 4, byte & 0xF] {
+            match nibble {
+                0..=9 => text.push((b'0' + nibble) as char),
+                0xA => text.push('.'),
+                0xB => text.push('E'),
+                0xC => text.push_str("E-"),
+                0xE => text.push('-'),
+                0xF => break 'nibbles,
+                _ => return Err(Error::InvalidFormat),
+            }
+        }
+    }
+    text.parse().map_err(|_| Error::InvalidFormat)
+}
+
+/// Extracts a DICT occupying the whole stream.
+pub fn read_dict(data: &mut FontDataStream) -> Result<Dict, Error> {
+    let mut dict = Dict::default();
+    let mut operands = Vec::new();
+    while !data.is_eof() {
+        let b0 = data.read_u8()?;
+        match b0 {
+            // 22..=24 are the CFF2 `vsindex`, `blend` and `vstore` operators.
+            0..=24 => {
+                let operator = if b0 == 12 {
+                    0x0C00 | data.read_u8()? as u16
+                } else {
+                    b0 as u16
+                };
+                dict.entries.push((operator, std::mem::take(&mut operands)));
+            }
+            28 => operands.push(Operand::Integer(data.read_i16()? as i32)),
+            29 => operands.push(Operand::Integer(data.read_i32()?)),
+            30 => operands.push(Operand::Real(read_real(data)?)),
+            32..=246 => operands.push(Operand::Integer(b0 as i32 - 139)),
+            247..=250 => {
+                let b1 = data.read_u8()? as i32;
+                operands.push(Operand::Integer((b0 as i32 - 247) * 256 + b1 + 108));
+            }
+            251..=254 => {
+                let b1 = data.read_u8()? as i32;
+                operands.push(Operand::Integer(-(b0 as i32 - 251) * 256 - b1 - 108));
+            }
+            _ => return Err(Error::InvalidFormat),
+        }
+    }
+    Ok(dict)
+}
+
+fn write_dict_integer(writer: &mut FontDataWriter, value: i32) {
+    match value {
+        -107..=107 => writer.write_u8((value + 139) as u8),
+        108..=1131 => {
+            let v = value - 108;
+            writer.write_u8((v / 256 + 247) as u8);
+            writer.write_u8((v % 256) as u8);
+        }
+        -1131..=-108 => {
+            let v = -value - 108;
+            writer.write_u8((v / 256 + 251) as u8);
+            writer.write_u8((v % 256) as u8);
+        }
+        -32768..=32767 => {
+            writer.write_u8(28);
+            writer.write_i16(value as i16);
+        }
+        _ => {
+            writer.write_u8(29);
+            writer.write_i32(value);
+        }
+    }
+}
+
+fn write_real(writer: &mut FontDataWriter, value: f64) {
+    let text = format!("{value}");
+    let mut nibbles: Vec<u8> = text
+        .bytes()
+        .map(|c| match c {
+            b'0'..=b'9' => c - b'0',
+            b'.' => 0xA,
+            _ => 0xE,
+        })
+        .collect();
+    nibbles.push(0xF);
+    if nibbles.len() % 2 == 1 {
+        nibbles.push(0xF);
+    }
+    writer.write_u8(30);
+    for pair in nibbles.chunks(2) {
+        writer.write_u8((pair[0] << 4) | pair[1]);
+    }
+}
+
+/// Serializes a DICT. Operands of operators in `offset_operators` use the
+/// fixed five-byte integer form so the DICT size does not depend on them.
+pub fn write_dict(writer: &mut FontDataWriter, dict: &Dict, offset_operators: &[u16]) {
+    for (operator, operands) in &dict.entries {
+        for operand in operands {
+            match *operand {
+                Operand::Integer(value) if offset_operators.contains(operator) => {
+                    writer.write_u8(29);
+                    writer.write_i32(value);
+                }
+                Operand::Integer(value) => write_dict_integer(writer, value),
+                Operand::Real(value) => write_real(writer, value),
+            }
+        }
+        if *operator >= 0x0C00 {
+            writer.write_u8(12);
+        }
+        writer.write_u8(*operator as u8);
+    }
+}
+
+fn offset_operand(dict: &Dict, operator: u16, index: usize) -> Option<usize> {
+    dict.get(operator)
+        .and_then(|operands| operands.get(index))
+        .map(|operand| operand.as_i32().max(0) as usize)
+}
+
+fn read_private(data: &FontDataStream, dict: &Dict) -> Result<Option<PrivateDict>, Error> {
+    let (Some(size), Some(offset)) = (
+        offset_operand(dict, OP_PRIVATE, 0),
+        offset_operand(dict, OP_PRIVATE, 1),
+    ) else {
+        return Ok(None);
+    };
+    let bytes = data.slice_at(offset, size)?;
+    let private = read_dict(&mut FontDataStream::new(bytes))?;
+    let local_subrs = match offset_operand(&private, OP_SUBRS, 0) {
+        Some(subrs) if subrs != 0 => read_index(&mut data.substream(offset + subrs)?)?,
+        _ => Vec::new(),
+    };
+    Ok(Some(PrivateDict {
+        dict: private,
+        local_subrs,
+    }))
+}
+
+fn read_charset(
+    data: &FontDataStream,
+    offset: usize,
+    num_glyphs: usize,
+) -> Result<Vec<u16>, Error> {
+    if offset == 0 {
+        // ISOAdobe: SIDs 0..=228 in glyph order.
+        return Ok((0..num_glyphs as u16).collect());
+    }
+    if offset <= 2 {
+        return Err(Error::InvalidFormat);
+    }
+    let mut charset = vec![0];
+    let mut data = data.substream(offset)?;
+    let format = data.read_u8()?;
+    while charset.len() < num_glyphs {
+        match format {
+            0 => charset.push(data.read_u16()?),
+            1 | 2 => {
+                let first = data.read_u16()?;
+                let left = if format == 1 {
+                    data.read_u8()? as u16
+                } else {
+                    data.read_u16()?
+                };
+                charset.extend((0..=left).map(|i| first.wrapping_add(i)));
+            }
+            _ => return Err(Error::InvalidFormat),
+        }
+    }
+    charset.truncate(num_glyphs);
+    Ok(charset)
+}
+
+fn write_charset(writer: &mut FontDataWriter, charset: &[u16]) {
+    let ids = charset.get(1..).unwrap_or_default();
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for &id in ids {
+        match ranges.last_mut() {
+            Some((first, left)) if first.wrapping_add(*left + 1) == id => *left += 1,
+            _ => ranges.push((id, 0)),
+        }
+    }
+    if ranges.len() * 4 < ids.len() * 2 {
+        writer.write_u8(2);
+        for (first, left) in ranges {
+            writer.write_u16(first);
+            writer.write_u16(left);
+        }
+    } else {
+        writer.write_u8(0);
+        for &id in ids {
+            writer.write_u16(id);
+        }
+    }
+}
+
+fn read_encoding(data: &FontDataStream, offset: usize) -> Result<CffEncoding, Error> {
+    match offset {
+        0 => return Ok(CffEncoding::Standard),
+        1 => return Ok(CffEncoding::Expert),
+        _ => {}
+    }
+    let mut data = data.substream(offset)?;
+    let format = data.read_u8()?;
+    let mut codes = Vec::new();
+    match format & 0x7F {
+        0 => {
+            let count = data.read_u8()?;
+            for glyph in 1..=count as u16 {
+                codes.push((data.read_u8()?, glyph));
+            }
+        }
+        1 => {
+            let ranges = data.read_u8()?;
+            let mut glyph = 1;
+            for _ in 0..ranges {
+                let first = data.read_u8()?;
+                let left = data.read_u8()?;
+                for code in first..=first.saturating_add(left) {
+                    codes.push((code, glyph));
+                    glyph += 1;
+                }
+            }
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    let mut supplements = Vec::new();
+    if format & 0x80 != 0 {
+        let count = data.read_u8()?;
+        for _ in 0..count {
+            let code = data.read_u8()?;
+            supplements.push((code, data.read_u16()?));
+        }
+    }
+    Ok(CffEncoding::Custom { codes, supplements })
+}
+
+/// Writes a custom encoding in format 0. Glyphs must be listed in order
+/// starting at glyph 1; gaps are filled with code 0.
+fn write_encoding(writer: &mut FontDataWriter, codes: &[(u8, u16)], supplements: &[(u8, u16)]) {
+    let count = codes
+        .iter()
+        .map(|&(_, glyph)| glyph)
+        .max()
+        .unwrap_or(0)
+        .min(255);
+    writer.write_u8(if supplements.is_empty() { 0 } else { 0x80 });
+    writer.write_u8(count as u8);
+    for glyph in 1..=count {
+        let code = codes
+            .iter()
+            .find(|&&(_, g)| g == glyph)
+            .map_or(0, |&(code, _)| code);
+        writer.write_u8(code);
+    }
+    if !supplements.is_empty() {
+        writer.write_u8(supplements.len() as u8);
+        for &(code, sid) in supplements {
+            writer.write_u8(code);
+            writer.write_u16(sid);
+        }
+    }
+}
+
+fn read_fd_select(
+    data: &FontDataStream,
+    offset: usize,
+    num_glyphs: usize,
+) -> Result<Vec<u8>, Error> {
+    let mut data = data.substream(offset)?;
+    match data.read_u8()? {
+        0 => (0..num_glyphs).map(|_| data.read_u8()).collect(),
+        3 => {
+            let count = data.read_u16()?;
+            let mut select = vec![0; num_glyphs];
+            let mut first = data.read_u16()? as usize;
+            for _ in 0..count {
+                let fd = data.read_u8()?;
+                let next = data.read_u16()? as usize;
+                for slot in select.iter_mut().take(next).skip(first) {
+                    *slot = fd;
+                }
+                first = next;
+            }
+            Ok(select)
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+fn write_fd_select(writer: &mut FontDataWriter, select: &[u8]) {
+    let mut ranges: Vec<(u16, u8)> = Vec::new();
+    for (glyph, &fd) in select.iter().enumerate() {
+        if ranges.last().is_none_or(|&(_, last)| last != fd) {
+            ranges.push((glyph as u16, fd));
+        }
+    }
+    if 5 + ranges.len() * 3 < select.len() {
+        writer.write_u8(3);
+        writer.write_u16(ranges.len() as u16);
+        for (first, fd) in ranges {
+            writer.write_u16(first);
+            writer.write_u8(fd);
+        }
+        writer.write_u16(select.len() as u16);
+    } else {
+        writer.write_u8(0);
+        writer.write_bytes(select);
+    }
+}
+
+/// Extracts the `CFF ` table from the provided data stream.
+pub fn read_cff(data: &mut FontDataStream) -> Result<CffTable, Error> {
+    let base = data.substream(0)?;
+    let major_version = data.read_u8()?;
+    let minor_version = data.read_u8()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let header_size = data.read_u8()? as usize;
+    data.seek(header_size);
+    let names = read_index(data)?;
+    let top_dicts = read_index(data)?;
+    let strings = read_index(data)?;
+    let global_subrs = read_index(data)?;
+    let top_dict = read_dict(&mut FontDataStream::new(
+        top_dicts.first().ok_or(Error::InvalidFormat)?,
+    ))?;
+
+    let char_strings_offset =
+        offset_operand(&top_dict, OP_CHAR_STRINGS, 0).ok_or(Error::InvalidFormat)?;
+    let char_strings = read_index(&mut base.substream(char_strings_offset)?)?;
+    let num_glyphs = char_strings.len();
+    let charset = read_charset(
+        &base,
+        offset_operand(&top_dict, OP_CHARSET, 0).unwrap_or(0),
+        num_glyphs,
+    )?;
+
+    let mut cff = CffTable {
+        major_version,
+        minor_version,
+        name: names.into_iter().next().unwrap_or_default(),
+        strings,
+        global_subrs,
+        char_strings,
+        charset,
+        ..Default::default()
+    };
+    if top_dict.get(OP_ROS).is_some() {
+        let fd_array_offset =
+            offset_operand(&top_dict, OP_FD_ARRAY, 0).ok_or(Error::InvalidFormat)?;
+        for bytes in read_index(&mut base.substream(fd_array_offset)?)? {
+            let dict = read_dict(&mut FontDataStream::new(&bytes))?;
+            let private = read_private(&base, &dict)?.unwrap_or_default();
+            cff.fd_array.push(FontDict { dict, private });
+        }
+        cff.fd_select = match offset_operand(&top_dict, OP_FD_SELECT, 0) {
+            Some(offset) => read_fd_select(&base, offset, num_glyphs)?,
+            None => vec![0; num_glyphs],
+        };
+    } else {
+        cff.encoding = read_encoding(
+            &base,
+            offset_operand(&top_dict, OP_ENCODING, 0).unwrap_or(0),
+        )?;
+        cff.private = read_private(&base, &top_dict)?;
+    }
+    cff.top_dict = top_dict;
+    Ok(cff)
+}
+
+fn serialize_private(private: &PrivateDict) -> Vec<u8> {
+    let mut dict = private.dict.clone();
+    dict.remove(OP_SUBRS);
+    let mut size = FontDataWriter::new();
+    write_dict(&mut size, &dict, &[]);
+    if !private.local_subrs.is_empty() {
+        let offset = size.len() as i32 + 6;
+        dict.set(OP_SUBRS, vec![Operand::Integer(offset)]);
+    }
+    let mut writer = FontDataWriter::new();
+    write_dict(&mut writer, &dict, &[OP_SUBRS]);
+    if !private.local_subrs.is_empty() {
+        write_index(&mut writer, &private.local_subrs);
+    }
+    writer.as_bytes().to_vec()
+}
+
+/// Returns the size of the Private DICT proper, excluding its local subroutines.
+fn private_dict_size(private: &PrivateDict, bytes: &[u8]) -> usize {
+    if private.local_subrs.is_empty() {
+        bytes.len()
+    } else {
+        let mut index = FontDataWriter::new();
+        write_index(&mut index, &private.local_subrs);
+        bytes.len() - index.len()
+    }
+}
+
+/// Serializes the `CFF ` table. Offsets in the Top, Font and Private DICTs are
+/// recomputed; the encoding is written only for custom encodings.
+pub fn write_cff(writer: &mut FontDataWriter, cff: &CffTable) -> Result<(), Error> {
+    let cid = cff.is_cid();
+    let mut top_dict = cff.top_dict.clone();
+    for operator in [
+        OP_CHARSET,
+        OP_ENCODING,
+        OP_CHAR_STRINGS,
+        OP_PRIVATE,
+        OP_FD_ARRAY,
+        OP_FD_SELECT,
+    ] {
+        top_dict.remove(operator);
+    }
+    let placeholder = |count: usize| vec![Operand::Integer(0); count];
+    top_dict.set(OP_CHARSET, placeholder(1));
+    if !cid && matches!(cff.encoding, CffEncoding::Custom { .. }) {
+        top_dict.set(OP_ENCODING, placeholder(1));
+    }
+    top_dict.set(OP_CHAR_STRINGS, placeholder(1));
+    if cid {
+        top_dict.set(OP_FD_ARRAY, placeholder(1));
+        top_dict.set(OP_FD_SELECT, placeholder(1));
+    } else if cff.private.is_some() {
+        top_dict.set(OP_PRIVATE, placeholder(2));
+    }
+    let offset_operators = [
+        OP_CHARSET,
+        OP_ENCODING,
+        OP_CHAR_STRINGS,
+        OP_PRIVATE,
+        OP_FD_ARRAY,
+        OP_FD_SELECT,
+    ];
+
+    let serialize_index = |items: &[Vec<u8>]| {
+        let mut w = FontDataWriter::new();
+        write_index(&mut w, items);
+        w.as_bytes().to_vec()
+    };
+    let serialize_dict = |dict: &Dict| {
+        let mut w = FontDataWriter::new();
+        write_dict(&mut w, dict, &offset_operators);
+        w.as_bytes().to_vec()
+    };
+    let name_index = serialize_index(std::slice::from_ref(&cff.name));
+    let string_index = serialize_index(&cff.strings);
+    let global_subr_index = serialize_index(&cff.global_subrs);
+    let top_dict_size = serialize_index(&[serialize_dict(&top_dict)]).len();
+
+    let offset =
+        4 + name_index.len() + top_dict_size + string_index.len() + global_subr_index.len();
+    let mut tail = FontDataWriter::new();
+    let place = |tail: &mut FontDataWriter, bytes: &[u8]| {
+        let at = offset + tail.len();
+        tail.write_bytes(bytes);
+        Operand::Integer(at as i32)
+    };
+
+    let mut charset = FontDataWriter::new();
+    write_charset(&mut charset, &cff.charset);
+    top_dict.set(OP_CHARSET, vec![place(&mut tail, charset.as_bytes())]);
+    if let (false, CffEncoding::Custom { codes, supplements }) = (cid, &cff.encoding) {
+        let mut encoding = FontDataWriter::new();
+        write_encoding(&mut encoding, codes, supplements);
+        top_dict.set(OP_ENCODING, vec![place(&mut tail, encoding.as_bytes())]);
+    }
+    if cid {
+        let mut select = FontDataWriter::new();
+        write_fd_select(&mut select, &cff.fd_select);
+        top_dict.set(OP_FD_SELECT, vec![place(&mut tail, select.as_bytes())]);
+    }
+    top_dict.set(
+        OP_CHAR_STRINGS,
+        vec![place(&mut tail, &serialize_index(&cff.char_strings))],
+    );
+
+    if cid {
+        let privates: Vec<_> = cff
+            .fd_array
+            .iter()
+            .map(|fd| serialize_private(&fd.private))
+            .collect();
+        let mut font_dicts: Vec<Dict> = cff
+            .fd_array
+            .iter()
+            .map(|fd| {
+                let mut dict = fd.dict.clone();
+                dict.set(OP_PRIVATE, placeholder(2));
+                dict
+            })
+            .collect();
+        let array_size =
+            serialize_index(&font_dicts.iter().map(serialize_dict).collect::<Vec<_>>()).len();
+        let mut private_offset = offset + tail.len() + array_size;
+        for ((dict, bytes), fd) in font_dicts.iter_mut().zip(&privates).zip(&cff.fd_array) {
+            let size = private_dict_size(&fd.private, bytes);
+            dict.set(
+                OP_PRIVATE,
+                vec![
+                    Operand::Integer(size as i32),
+                    Operand::Integer(private_offset as i32),
+                ],
+            );
+            private_offset += bytes.len();
+        }
+        let array = serialize_index(&font_dicts.iter().map(serialize_dict).collect::<Vec<_>>());
+        top_dict.set(OP_FD_ARRAY, vec![place(&mut tail, &array)]);
+        for bytes in &privates {
+            tail.write_bytes(bytes);
+        }
+    } else if let Some(private) = &cff.private {
+        let bytes = serialize_private(private);
+        let size = private_dict_size(private, &bytes);
+        let at = place(&mut tail, &bytes);
+        top_dict.set(OP_PRIVATE, vec![Operand::Integer(size as i32), at]);
+    }
+
+    writer.write_u8(cff.major_version);
+    writer.write_u8(cff.minor_version);
+    writer.write_u8(4);
+    writer.write_u8(4);
+    writer.write_bytes(&name_index);
+    writer.write_bytes(&serialize_index(&[serialize_dict(&top_dict)]));
+    writer.write_bytes(&string_index);
+    writer.write_bytes(&global_subr_index);
+    writer.write_bytes(tail.as_bytes());
+    Ok(())
+}
+
+/// A lexical element of a Type 2 charstring.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CharStringToken {
+    /// A number pushed on the argument stack.
+    Number(f64),
+    /// An operator; two-byte operators are `0x0C00 | second_byte`.
+    Operator(u16),
+    /// `hintmask` or `cntrmask` with its mask bytes.
+    Mask(u16, Vec<u8>),
+}
+
+/// Reads the next charstring token. `stem_count` and `stack_len` track the
+/// state needed to size hint masks and must persist across subroutine calls.
+pub fn read_char_string_token(
+    data: &mut FontDataStream,
+    stem_count: &mut usize,
+    stack_len: &mut usize,
+) -> Result<CharStringToken, Error> {
+    let b0 = data.read_u8()?;
+    let number = match b0 {
+        28 => data.read_i16()? as f64,
+        32..=246 => (b0 as i32 - 139) as f64,
+        247..=250 => ((b0 as i32 - 247) * 256 + data.read_u8()? as i32 + 108) as f64,
+        251..=254 => (-(b0 as i32 - 251) * 256 - data.read_u8()? as i32 - 108) as f64,
+        255 => data.read_i32()? as f64 / 65536.0,
+        _ => {
+            let operator = if b0 == 12 {
+                0x0C00 | data.read_u8()? as u16
+            } else {
+                b0 as u16
+            };
+            match operator {
+                CS_HSTEM | CS_VSTEM | CS_HSTEMHM | CS_VSTEMHM => *stem_count += *stack_len / 2,
+                CS_HINTMASK | CS_CNTRMASK => {
+                    *stem_count += *stack_len / 2;
+                    *stack_len = 0;
+                    let mask = data.read_bytes(stem_count.div_ceil(8))?.to_vec();
+                    return Ok(CharStringToken::Mask(operator, mask));
+                }
+                _ => {}
+            }
+            *stack_len = match operator {
+                // Subroutine calls consume only the subroutine number.
+                CS_CALLSUBR | CS_CALLGSUBR => stack_len.saturating_sub(1),
+                CS_RETURN => *stack_len,
+                // Arithmetic operators: and, or, add, sub, div, eq, mul, drop.
+                0x0C03 | 0x0C04 | 0x0C0A..=0x0C0C | 0x0C0F | 0x0C18 | 0x0C12 => {
+                    stack_len.saturating_sub(1)
+                }
+                // put, roll, ifelse.
+                0x0C14 | 0x0C1E => stack_len.saturating_sub(2),
+                0x0C16 => stack_len.saturating_sub(3),
+                // random, dup.
+                0x0C17 | 0x0C1B => *stack_len + 1,
+                // not, abs, neg, get, sqrt, exch, index.
+                0x0C05 | 0x0C09 | 0x0C0E | 0x0C15 | 0x0C1A | 0x0C1C | 0x0C1D => *stack_len,
+                _ => 0,
+            };
+            return Ok(CharStringToken::Operator(operator));
+        }
+    };
+    *stack_len += 1;
+    Ok(CharStringToken::Number(number))
+}
+
+/// Encodes charstring tokens.
+pub fn write_char_string(writer: &mut FontDataWriter, tokens: &[CharStringToken]) {
+    for token in tokens {
+        match token {
+            CharStringToken::Number(value) => {
+                if value.fract() == 0.0 && (-32768.0..=32767.0).contains(value) {
+                    let value = *value as i32;
+                    if (-1131..=1131).contains(&value) {
+                        write_dict_integer(writer, value);
+                    } else {
+                        writer.write_u8(28);
+                        writer.write_i16(value as i16);
+                    }
+                } else {
+                    writer.write_u8(255);
+                    writer.write_i32((value * 65536.0).round() as i32);
+                }
+            }
+            CharStringToken::Operator(operator) | CharStringToken::Mask(operator, _) => {
+                if *operator >= 0x0C00 {
+                    writer.write_u8(12);
+                }
+                writer.write_u8(*operator as u8);
+                if let CharStringToken::Mask(_, mask) = token {
+                    writer.write_bytes(mask);
+                }
+            }
+        }
+    }
+}
+
+/// Inlines the subroutine calls of a charstring.
+struct Flattener<'a> {
+    global_subrs: &'a [Vec<u8>],
+    local_subrs: &'a [Vec<u8>],
+    stem_count: usize,
+    stack_len: usize,
+    stack: Vec<f64>,
+    output: Vec<CharStringToken>,
+    ended: bool,
+}
+
+impl Flattener<'_> {
+    fn walk(&mut self, data: &[u8], depth: usize) -> Result<(), Error> {
+        if depth > MAX_SUBR_DEPTH {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let mut stream = FontDataStream::new(data);
+        while !stream.is_eof() && !self.ended {
+            let token =
+                read_char_string_token(&mut stream, &mut self.stem_count, &mut self.stack_len)?;
+            match token {
+                CharStringToken::Number(value) => self.stack.push(value),
+                CharStringToken::Operator(operator @ (CS_CALLSUBR | CS_CALLGSUBR)) => {
+                    let subrs = if operator == CS_CALLSUBR {
+                        self.local_subrs
+                    } else {
+                        self.global_subrs
+                    };
+                    let number = self.stack.pop().ok_or(Error::Io(IoError::InvalidData))?;
+                    self.output.pop();
+                    let index = usize::try_from(number as i32 + subr_bias(subrs.len()))
+                        .map_err(|_| Error::Io(IoError::InvalidData))?;
+                    let subr = subrs.get(index).ok_or(Error::Io(IoError::InvalidData))?;
+                    self.walk(subr, depth + 1)?;
+                    continue;
+                }
+                CharStringToken::Operator(CS_RETURN) => break,
+                CharStringToken::Operator(CS_ENDCHAR) => self.ended = true,
+                _ => self.stack.clear(),
+            }
+            self.output.push(token);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the tokens of a glyph's charstring with every subroutine call
+/// inlined, up to and including `endchar`.
+pub fn flatten_char_string(cff: &CffTable, glyph_id: u16) -> Result<Vec<CharStringToken>, Error> {
+    let data =
+        cff.char_strings
+            .get(glyph_id as usize)
+            .ok_or(Error::Io(IoError::InvalidGlyphIndex {
+                index: glyph_id as u32,
+            }))?;
+    let mut flattener = Flattener {
+        global_subrs: &cff.global_subrs,
+        local_subrs: cff.local_subrs(glyph_id),
+        stem_count: 0,
+        stack_len: 0,
+        stack: Vec::new(),
+        output: Vec::new(),
+        ended: false,
+    };
+    flattener.walk(data, 0)?;
+    Ok(flattener.output)
+}
@@ -0,0 +1,591 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `CFF2` (Compact Font Format version 2) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::cff1::{
+    CS_CALLGSUBR, CS_CALLSUBR, CS_RETURN, CharStringToken, Dict, FontDict, OP_CHAR_STRINGS,
+    OP_FD_ARRAY, OP_FD_SELECT, OP_PRIVATE, OP_SUBRS, Operand, PrivateDict, read_char_string_token,
+    read_dict, read_index_items, subr_bias, write_dict, write_index_items,
+};
+use super::variation::{ItemVariationStore, read_item_variation_store, write_item_variation_store};
+
+/// DICT operator: selects the item variation data of following blends.
+pub const OP_VSINDEX: u16 = 22;
+/// DICT operator: blends default values with their deltas.
+pub const OP_BLEND: u16 = 23;
+/// Top DICT operator: offset of the variation store.
+pub const OP_VSTORE: u16 = 24;
+
+/// Charstring operator: selects the item variation data of following blends.
+pub const CS_VSINDEX: u16 = 15;
+/// Charstring operator: blends default values with their deltas.
+pub const CS_BLEND: u16 = 16;
+
+/// Maximum subroutine nesting depth allowed by the specification.
+const MAX_SUBR_DEPTH: usize = 10;
+
+/// Compact Font Format version 2 table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cff2Table {
+    /// Major version (2).
+    pub major_version: u8,
+    /// Minor version (0).
+    pub minor_version: u8,
+    /// Top DICT; offset operators are rewritten on serialization.
+    pub top_dict: Dict,
+    /// Global subroutines.
+    pub global_subrs: Vec<Vec<u8>>,
+    /// Type 2 charstrings indexed by glyph id.
+    pub char_strings: Vec<Vec<u8>>,
+    /// Font DICTs.
+    pub fd_array: Vec<FontDict>,
+    /// Font DICT index of each glyph; empty when all glyphs use Font DICT 0.
+    pub fd_select: Vec<u16>,
+    /// Variation store referenced by `blend`.
+    pub variation_store: Option<ItemVariationStore>,
+}
+
+impl Cff2Table {
+    /// Returns the Font DICT index of a glyph.
+    pub fn fd_index(&self, glyph_id: u16) -> usize {
+        self.fd_select.get(glyph_id as usize).copied().unwrap_or(0) as usize
+    }
+
+    /// Returns the local subroutines used by a glyph.
+    pub fn local_subrs(&self, glyph_id: u16) -> &[Vec<u8>] {
+        self.fd_array
+            .get(self.fd_index(glyph_id))
+            .map_or(&[], |fd| fd.private.local_subrs.as_slice())
+    }
+
+    /// Returns the item variation data index used by a glyph's blends unless
+    /// the charstring selects another with `vsindex`.
+    pub fn default_vsindex(&self, glyph_id: u16) -> u16 {
+        self.fd_array
+            .get(self.fd_index(glyph_id))
+            .and_then(|fd| fd.private.dict.get(OP_VSINDEX))
+            .and_then(|operands| operands.first())
+            .map_or(0, |operand| operand.as_i32().max(0) as u16)
+    }
+
+    /// Returns the number of regions of item variation data `vsindex`.
+    pub fn region_count(&self, vsindex: u16) -> usize {
+        self.variation_store
+            .as_ref()
+            .and_then(|store| store.data.get(vsindex as usize))
+            .map_or(0, |data| data.region_indexes.len())
+    }
+}
+
+/// Extracts a CFF2 INDEX structure (32-bit count).
+pub fn read_cff2_index(data: &mut FontDataStream) -> Result<Vec<Vec<u8>>, Error> {
+    let count = data.read_u32()? as usize;
+    read_index_items(data, count)
+}
+
+/// Serializes a CFF2 INDEX structure.
+pub fn write_cff2_index(writer: &mut FontDataWriter, items: &[Vec<u8>]) {
+    writer.write_u32(items.len() as u32);
+    write_index_items(writer, items);
+}
+
+fn offset_operand(dict: &Dict, operator: u16, index: usize) -> Option<usize> {
+    dict.get(operator)
+        .and_then(|operands| operands.get(index))
+        .map(|operand| operand.as_i32().max(0) as usize)
+}
+
+fn read_private(data: &FontDataStream, dict: &Dict) -> Result<PrivateDict, Error> {
+    let (Some(size), Some(offset)) = (
+        offset_operand(dict, OP_PRIVATE, 0),
+        offset_operand(dict, OP_PRIVATE, 1),
+    ) else {
+        return Ok(PrivateDict::default());
+    };
+    let private = read_dict(&mut FontDataStream::new(data.slice_at(offset, size)?))?;
+    let local_subrs = match offset_operand(&private, OP_SUBRS, 0) {
+        Some(subrs) if subrs != 0 => read_cff2_index(&mut data.substream(offset + subrs)?)?,
+        _ => Vec::new(),
+    };
+    Ok(PrivateDict {
+        dict: private,
+        local_subrs,
+    })
+}
+
+fn read_fd_select(
+    data: &FontDataStream,
+    offset: usize,
+    num_glyphs: usize,
+) -> Result<Vec<u16>, Error> {
+    let mut data = data.substream(offset)?;
+    let format = data.read_u8()?;
+    match format {
+        0 => (0..num_glyphs)
+            .map(|_| data.read_u8().map(u16::from))
+            .collect(),
+        3 | 4 => {
+            let read_glyph = |data: &mut FontDataStream| match format {
+                3 => data.read_u16().map(|g| g as usize),
+                _ => data.read_u32().map(|g| g as usize),
+            };
+            let count = if format == 3 {
+                data.read_u16()? as usize
+            } else {
+                data.read_u32()? as usize
+            };
+            let mut select = vec![0; num_glyphs];
+            let mut first = read_glyph(&mut data)?;
+            for _ in 0..count {
+                let fd = if format == 3 {
+                    data.read_u8()? as u16
+                } else {
+                    data.read_u16()?
+                };
+                let next = read_glyph(&mut data)?;
+                for slot in select.iter_mut().take(next).skip(first) {
+                    *slot = fd;
+                }
+                first = next;
+            }
+            Ok(select)
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+fn write_fd_select(writer: &mut FontDataWriter, select: &[u16]) {
+    let mut ranges: Vec<(usize, u16)> = Vec::new();
+    for (glyph, &fd) in select.iter().enumerate() {
+        if ranges.last().is_none_or(|&(_, last)| last != fd) {
+            ranges.push((glyph, fd));
+        }
+    }
+    if select.len() > u16::MAX as usize || select.iter().any(|&fd| fd > 0xFF) {
+        writer.write_u8(4);
+        writer.write_u32(ranges.len() as u32);
+        for (first, fd) in ranges {
+            writer.write_u32(first as u32);
+            writer.write_u16(fd);
+        }
+        writer.write_u32(select.len() as u32);
+    } else if 5 + ranges.len() * 3 < select.len() {
+        writer.write_u8(3);
+        writer.write_u16(ranges.len() as u16);
+        for (first, fd) in ranges {
+            writer.write_u16(first as u16);
+            writer.write_u8(fd as u8);
+        }
+        writer.write_u16(select.len() as u16);
+    } else {
+        writer.write_u8(0);
+        for &fd in select {
+            writer.write_u8(fd as u8);
+        }
+    }
+}
+
+/// Extracts the `CFF2` table from the provided data stream.
+pub fn read_cff2(data: &mut FontDataStream) -> Result<Cff2Table, Error> {
+    let base = data.substream(0)?;
+    let major_version = data.read_u8()?;
+    let minor_version = data.read_u8()?;
+    if major_version != 2 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let header_size = data.read_u8()? as usize;
+    let top_dict_length = data.read_u16()? as usize;
+    let top_dict = read_dict(&mut FontDataStream::new(
+        base.slice_at(header_size, top_dict_length)?,
+    ))?;
+    data.seek(header_size + top_dict_length);
+    let global_subrs = read_cff2_index(data)?;
+
+    let char_strings_offset =
+        offset_operand(&top_dict, OP_CHAR_STRINGS, 0).ok_or(Error::InvalidFormat)?;
+    let char_strings = read_cff2_index(&mut base.substream(char_strings_offset)?)?;
+    let num_glyphs = char_strings.len();
+
+    let mut fd_array = Vec::new();
+    if let Some(offset) = offset_operand(&top_dict, OP_FD_ARRAY, 0) {
+        for bytes in read_cff2_index(&mut base.substream(offset)?)? {
+            let dict = read_dict(&mut FontDataStream::new(&bytes))?;
+            let private = read_private(&base, &dict)?;
+            fd_array.push(FontDict { dict, private });
+        }
+    }
+    let fd_select = match offset_operand(&top_dict, OP_FD_SELECT, 0) {
+        Some(offset) if fd_array.len() > 1 => read_fd_select(&base, offset, num_glyphs)?,
+        _ => Vec::new(),
+    };
+    let variation_store = match offset_operand(&top_dict, OP_VSTORE, 0) {
+        // The store is preceded by its 16-bit length.
+        Some(offset) if offset != 0 => {
+            Some(read_item_variation_store(&mut base.substream(offset + 2)?)?)
+        }
+        _ => None,
+    };
+    Ok(Cff2Table {
+        major_version,
+        minor_version,
+        top_dict,
+        global_subrs,
+        char_strings,
+        fd_array,
+        fd_select,
+        variation_store,
+    })
+}
+
+fn serialize_private(private: &PrivateDict) -> (Vec<u8>, usize) {
+    let mut dict = private.dict.clone();
+    dict.remove(OP_SUBRS);
+    if !private.local_subrs.is_empty() {
+        dict.set(OP_SUBRS, vec![Operand::Integer(0)]);
+    }
+    let mut size = FontDataWriter::new();
+    write_dict(&mut size, &dict, &[OP_SUBRS]);
+    let dict_size = size.len();
+    if !private.local_subrs.is_empty() {
+        dict.set(OP_SUBRS, vec![Operand::Integer(dict_size as i32)]);
+    }
+    let mut writer = FontDataWriter::new();
+    write_dict(&mut writer, &dict, &[OP_SUBRS]);
+    if !private.local_subrs.is_empty() {
+        write_cff2_index(&mut writer, &private.local_subrs);
+    }
+    (writer.as_bytes().to_vec(), dict_size)
+}
+
+/// Serializes the `CFF2` table. Offsets in the Top, Font and Private DICTs
+/// are recomputed.
+pub fn write_cff2(writer: &mut FontDataWriter, cff2: &Cff2Table) -> Result<(), Error> {
+    let offset_operators = [
+        OP_CHAR_STRINGS,
+        OP_FD_ARRAY,
+        OP_FD_SELECT,
+        OP_VSTORE,
+        OP_PRIVATE,
+    ];
+    let mut top_dict = cff2.top_dict.clone();
+    for operator in [OP_CHAR_STRINGS, OP_FD_ARRAY, OP_FD_SELECT, OP_VSTORE] {
+        top_dict.remove(operator);
+    }
+    let zero = || vec![Operand::Integer(0)];
+    top_dict.set(OP_CHAR_STRINGS, zero());
+    top_dict.set(OP_FD_ARRAY, zero());
+    let with_fd_select = cff2.fd_array.len() > 1;
+    if with_fd_select {
+        top_dict.set(OP_FD_SELECT, zero());
+    }
+    if cff2.variation_store.is_some() {
+        top_dict.set(OP_VSTORE, zero());
+    }
+    let dict_bytes = |dict: &Dict| {
+        let mut w = FontDataWriter::new();
+        write_dict(&mut w, dict, &offset_operators);
+        w.as_bytes().to_vec()
+    };
+    let top_dict_length = dict_bytes(&top_dict).len();
+    let mut global_subrs = FontDataWriter::new();
+    write_cff2_index(&mut global_subrs, &cff2.global_subrs);
+
+    let offset = 5 + top_dict_length + global_subrs.len();
+    let mut tail = FontDataWriter::new();
+    let place = |tail: &mut FontDataWriter, bytes: &[u8]| {
+        let at = offset + tail.len();
+        tail.write_bytes(bytes);
+        vec![Operand::Integer(at as i32)]
+    };
+
+    if let Some(store) = &cff2.variation_store {
+        let mut bytes = FontDataWriter::new();
+        write_item_variation_store(&mut bytes, store)?;
+        let bytes = bytes.into_bytes()?;
+        let mut with_length = FontDataWriter::new();
+        with_length.write_u16(bytes.len() as u16);
+        with_length.write_bytes(&bytes);
+        top_dict.set(OP_VSTORE, place(&mut tail, with_length.as_bytes()));
+    }
+    if with_fd_select {
+        let mut select = FontDataWriter::new();
+        let full: Vec<u16> = (0..cff2.char_strings.len())
+            .map(|g| cff2.fd_select.get(g).copied().unwrap_or(0))
+            .collect();
+        write_fd_select(&mut select, &full);
+        top_dict.set(OP_FD_SELECT, place(&mut tail, select.as_bytes()));
+    }
+    let mut char_strings = FontDataWriter::new();
+    write_cff2_index(&mut char_strings, &cff2.char_strings);
+    top_dict.set(OP_CHAR_STRINGS, place(&mut tail, char_strings.as_bytes()));
+
+    let privates: Vec<_> = cff2
+        .fd_array
+        .iter()
+        .map(|fd| serialize_private(&fd.private))
+        .collect();
+    let mut font_dicts: Vec<Dict> = cff2
+        .fd_array
+        .iter()
+        .map(|fd| {
+            let mut dict = fd.dict.clone();
+            dict.set(OP_PRIVATE, vec![Operand::Integer(0); 2]);
+            dict
+        })
+        .collect();
+    let mut array = FontDataWriter::new();
+    write_cff2_index(
+        &mut array,
+        &font_dicts.iter().map(dict_bytes).collect::<Vec<_>>(),
+    );
+    let mut private_offset = offset + tail.len() + array.len();
+    for (dict, (bytes, size)) in font_dicts.iter_mut().zip(&privates) {
+        dict.set(
+            OP_PRIVATE,
+            vec![
+                Operand::Integer(*size as i32),
+                Operand::Integer(private_offset as i32),
+            ],
+        );
+        private_offset += bytes.len();
+    }
+    let mut array = FontDataWriter::new();
+    write_cff2_index(
+        &mut array,
+        &font_dicts.iter().map(dict_bytes).collect::<Vec<_>>(),
+    );
+    top_dict.set(OP_FD_ARRAY, place(&mut tail, array.as_bytes()));
+    for (bytes, _) in &privates {
+        tail.write_bytes(bytes);
+    }
+
+    writer.write_u8(cff2.major_version);
+    writer.write_u8(cff2.minor_version);
+    writer.write_u8(5);
+    writer.write_u16(top_dict_length as u16);
+    writer.write_bytes(&dict_bytes(&top_dict));
+    writer.write_bytes(global_subrs.as_bytes());
+    writer.write_bytes(tail.as_bytes());
+    Ok(())
+}
+
+/// Inlines the subroutine calls of a charstring.
+struct Flattener<'a> {
+    cff2: &'a Cff2Table,
+    local_subrs: &'a [Vec<u8>],
+    vsindex: u16,
+    stem_count: usize,
+    stack_len: usize,
+    stack: Vec<f64>,
+    output: Vec<CharStringToken>,
+}
+
+impl Flattener<'_> {
+    fn walk(&mut self, data: &[u8], depth: usize) -> Result<(), Error> {
+        if depth > MAX_SUBR_DEPTH {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let mut stream = FontDataStream::new(data);
+        while !stream.is_eof() {
+            let token =
+                read_char_string_token(&mut stream, &mut self.stem_count, &mut self.stack_len)?;
+            match token {
+                CharStringToken::Number(value) => self.stack.push(value),
+                CharStringToken::Operator(operator @ (CS_CALLSUBR | CS_CALLGSUBR)) => {
+                    let subrs = if operator == CS_CALLSUBR {
+                        self.local_subrs
+                    } else {
+                        &self.cff2.global_subrs
+                    };
+                    let number = self.stack.pop().ok_or(Error::Io(IoError::InvalidData))?;
+                    self.output.pop();
+                    let index = usize::try_from(number as i32 + subr_bias(subrs.len()))
+                        .map_err(|_| Error::Io(IoError::InvalidData))?;
+                    let subr = subrs.get(index).ok_or(Error::Io(IoError::InvalidData))?;
+                    self.walk(subr, depth + 1)?;
+                    continue;
+                }
+                CharStringToken::Operator(CS_RETURN) => break,
+                CharStringToken::Operator(CS_VSINDEX) => {
+                    self.vsindex = self.stack.pop().unwrap_or(0.0) as u16;
+                    self.stack.clear();
+                }
+                CharStringToken::Operator(CS_BLEND) => {
+                    // Blend leaves its `n` default values on the stack.
+                    let n = self.stack.last().copied().unwrap_or(0.0) as usize;
+                    let operands = n * (self.cff2.region_count(self.vsindex) + 1) + 1;
+                    let kept = self.stack.len().saturating_sub(operands);
+                    self.stack.truncate(kept);
+                    self.stack.extend(std::iter::repeat_n(0.0, n));
+                    self.stack_len = self.stack.len();
+                }
+                _ => self.stack.clear(),
+            }
+            self.output.push(token);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the tokens of a glyph's charstring with every subroutine call
+/// inlined. `blend` and `vsindex` operators are kept.
+pub fn flatten_char_string(cff2: &Cff2Table, glyph_id: u16) -> Result<Vec<CharStringToken>, Error> {
+    let data =
+        cff2.char_strings
+            .get(glyph_id as usize)
+            .ok_or(Error::Io(IoError::InvalidGlyphIndex {
+                index: glyph_id as u32,
+            }))?;
+    let mut flattener = Flattener {
+        cff2,
+        local_subrs: cff2.local_subrs(glyph_id),
+        vsindex: cff2.default_vsindex(glyph_id),
+        stem_count: 0,
+        stack_len: 0,
+        stack: Vec::new(),
+        output: Vec::new(),
+    };
+    flattener.walk(data, 0)?;
+    Ok(flattener.output)
+}
+
+/// The operands of one `blend`.
+pub struct Blend<'a> {
+    /// Item variation data index selected for the blend.
+    pub vsindex: u16,
+    /// Default values.
+    pub defaults: &'a [f64],
+    /// Deltas: one row of region deltas per default value.
+    pub deltas: Vec<&'a [f64]>,
+}
+
+/// Replaces every `blend` of flattened charstring tokens with the tokens
+/// returned by `rewrite`. `vsindex` operators are kept only with `keep_vsindex`.
+pub fn rewrite_char_string_blends(
+    cff2: &Cff2Table,
+    tokens: &[CharStringToken],
+    default_vsindex: u16,
+    keep_vsindex: bool,
+    mut rewrite: impl FnMut(Blend) -> Vec<CharStringToken>,
+) -> Vec<CharStringToken> {
+    let mut output: Vec<CharStringToken> = Vec::with_capacity(tokens.len());
+    let mut numbers: Vec<f64> = Vec::new();
+    let mut vsindex = default_vsindex;
+    for token in tokens {
+        match token {
+            CharStringToken::Number(value) => {
+                numbers.push(*value);
+                output.push(token.clone());
+            }
+            CharStringToken::Operator(CS_VSINDEX) => {
+                vsindex = numbers.last().copied().unwrap_or(0.0) as u16;
+                numbers.clear();
+                if keep_vsindex {
+                    output.push(token.clone());
+                } else {
+                    output.pop();
+                }
+            }
+            CharStringToken::Operator(CS_BLEND) => {
+                let n = numbers.last().copied().unwrap_or(0.0) as usize;
+                let region_count = cff2.region_count(vsindex);
+                let count = n * (region_count + 1) + 1;
+                let start = numbers.len().saturating_sub(count);
+                output.truncate(output.len() - (numbers.len() - start));
+                let operands: Vec<f64> = numbers.drain(start..).collect();
+                let defaults = &operands[..n.min(operands.len())];
+                let deltas = operands[n.min(operands.len())..operands.len().saturating_sub(1)]
+                    .chunks(region_count.max(1))
+                    .collect();
+                let replacement = rewrite(Blend {
+                    vsindex,
+                    defaults,
+                    deltas,
+                });
+                for token in &replacement {
+                    if let CharStringToken::Number(value) = token {
+                        numbers.push(*value);
+                    }
+                }
+                output.extend(replacement);
+            }
+            _ => {
+                numbers.clear();
+                output.push(token.clone());
+            }
+        }
+    }
+    output
+}
+
+/// Replaces every `blend` of a DICT with the operands returned by `rewrite`,
+/// which may end with a new `blend`. `vsindex` entries are kept only with
+/// `keep_vsindex`.
+pub fn rewrite_dict_blends(
+    cff2: &Cff2Table,
+    dict: &Dict,
+    keep_vsindex: bool,
+    mut rewrite: impl FnMut(Blend) -> (Vec<Operand>, bool),
+) -> Dict {
+    let mut output = Dict::default();
+    let mut pending: Vec<Operand> = Vec::new();
+    let mut vsindex = 0;
+    for (operator, operands) in &dict.entries {
+        pending.extend(operands);
+        match *operator {
+            OP_VSINDEX => {
+                vsindex = pending
+                    .last()
+                    .map_or(0, |operand| operand.as_i32().max(0) as u16);
+                if keep_vsindex {
+                    output
+                        .entries
+                        .push((OP_VSINDEX, std::mem::take(&mut pending)));
+                }
+                pending.clear();
+            }
+            OP_BLEND => {
+                let n = pending
+                    .last()
+                    .map_or(0, |operand| operand.as_i32().max(0) as usize);
+                let region_count = cff2.region_count(vsindex);
+                let count = n * (region_count + 1) + 1;
+                let start = pending.len().saturating_sub(count);
+                let operands: Vec<f64> = pending
+                    .drain(start..)
+                    .map(|operand| match operand {
+                        Operand::Integer(value) => value as f64,
+                        Operand::Real(value) => value,
+                    })
+                    .collect();
+                let defaults = &operands[..n.min(operands.len())];
+                let deltas = operands[n.min(operands.len())..operands.len().saturating_sub(1)]
+                    .chunks(region_count.max(1))
+                    .collect();
+                let (replacement, is_blend) = rewrite(Blend {
+                    vsindex,
+                    defaults,
+                    deltas,
+                });
+                pending.extend(replacement);
+                if is_blend {
+                    output
+                        .entries
+                        .push((OP_BLEND, std::mem::take(&mut pending)));
+                }
+            }
+            _ => output
+                .entries
+                .push((*operator, std::mem::take(&mut pending))),
+        }
+    }
+    output
+}
@@ -0,0 +1,549 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `cmap` (Character to Glyph Index Mapping) table representation and utilities for the
+//! Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Platform ID: Unicode.
+pub const PLATFORM_UNICODE: u16 = 0;
+/// Platform ID: Macintosh.
+pub const PLATFORM_MACINTOSH: u16 = 1;
+/// Platform ID: Windows.
+pub const PLATFORM_WINDOWS: u16 = 3;
+
+/// Encoding records consulted for Unicode lookups, most complete first.
+const UNICODE_PREFERENCE: [(u16, u16); 8] = [
+    (3, 10),
+    (0, 6),
+    (0, 4),
+    (3, 1),
+    (0, 3),
+    (0, 2),
+    (0, 1),
+    (0, 0),
+];
+
+/// Character to glyph index mapping table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CmapTable {
+    /// Table version (0).
+    pub version: u16,
+    /// Encoding records in file order.
+    pub records: Vec<EncodingRecord>,
+}
+
+/// A platform/encoding pair and its subtable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingRecord {
+    /// Platform ID.
+    pub platform_id: u16,
+    /// Platform-specific encoding ID.
+    pub encoding_id: u16,
+    /// Mapping subtable.
+    pub subtable: CmapSubtable,
+}
+
+/// Glyph variants selected by one Unicode variation selector (format 14).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VariationSelector {
+    /// The variation selector code point.
+    pub var_selector: u32,
+    /// Ranges `(start, additional_count)` of base characters using their default glyph.
+    pub default_uvs: Vec<(u32, u8)>,
+    /// Base characters mapped to a specific glyph.
+    pub non_default_uvs: BTreeMap<u32, u16>,
+}
+
+/// A `cmap` subtable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CmapSubtable {
+    /// Formats 0, 4, 6, 10, 12 and 13: character codes mapped to glyph ids.
+    /// Unmapped codes (glyph 0) are omitted.
+    Mapping {
+        /// Subtable format used when writing.
+        format: u16,
+        /// Language field (Macintosh platform only).
+        language: u32,
+        /// Character code to glyph id mappings.
+        mappings: BTreeMap<u32, u16>,
+    },
+    /// Format 14: Unicode variation sequences.
+    Variations(Vec<VariationSelector>),
+    /// Formats kept as raw bytes (2 and 8).
+    Raw(Vec<u8>),
+}
+
+impl CmapTable {
+    /// Returns the preferred Unicode mapping subtable, if any.
+    pub fn unicode_mappings(&self) -> Option<&BTreeMap<u32, u16>> {
+        UNICODE_PREFERENCE.iter().find_map(|&(platform, encoding)| {
+            self.records
+                .iter()
+                .find_map(|record| match &record.subtable {
+                    CmapSubtable::Mapping { mappings, .. }
+                        if record.platform_id == platform && record.encoding_id == encoding =>
+                    {
+                        Some(mappings)
+                    }
+                    _ => None,
+                })
+        })
+    }
+
+    /// Returns the mapping subtable of the Windows symbol encoding (3, 0), if
+    /// any. Symbol fonts map their codes into U+F020..U+F0FF.
+    pub fn symbol_mappings(&self) -> Option<&BTreeMap<u32, u16>> {
+        self.records
+            .iter()
+            .find_map(|record| match &record.subtable {
+                CmapSubtable::Mapping { mappings, .. }
+                    if record.platform_id == PLATFORM_WINDOWS && record.encoding_id == 0 =>
+                {
+                    Some(mappings)
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the Unicode variation sequences, if any.
+    pub fn variation_selectors(&self) -> Option<&[VariationSelector]> {
+        self.records
+            .iter()
+            .find_map(|record| match &record.subtable {
+                CmapSubtable::Variations(selectors) => Some(selectors.as_slice()),
+                _ => None,
+            })
+    }
+
+    /// Maps a Unicode code point to a glyph id.
+    pub fn glyph_id(&self, code_point: u32) -> Option<u16> {
+        self.unicode_mappings()?.get(&code_point).copied()
+    }
+
+    /// Maps a code point followed by a variation selector to a glyph id. Falls
+    /// back to the default mapping for default variation sequences.
+    pub fn variant_glyph_id(&self, code_point: u32, var_selector: u32) -> Option<u16> {
+        let selector = self
+            .variation_selectors()?
+            .iter()
+            .find(|s| s.var_selector == var_selector)?;
+        if let Some(&glyph) = selector.non_default_uvs.get(&code_point) {
+            return Some(glyph);
+        }
+        selector
+            .default_uvs
+            .iter()
+            .any(|&(start, count)| (start..=start + count as u32).contains(&code_point))
+            .then(|| self.glyph_id(code_point))
+            .flatten()
+    }
+}
+
+/// Extracts the `cmap` table from the provided data stream.
+pub fn read_cmap(data: &mut FontDataStream) -> Result<CmapTable, Error> {
+    let version = data.read_u16()?;
+    let count = data.read_u16()?;
+    let mut records = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let platform_id = data.read_u16()?;
+        let encoding_id = data.read_u16()?;
+        let offset = data.read_u32()? as usize;
+        let subtable = read_cmap_subtable(&mut data.substream(offset)?)?;
+        records.push(EncodingRecord {
+            platform_id,
+            encoding_id,
+            subtable,
+        });
+    }
+    Ok(CmapTable { version, records })
+}
+
+/// Extracts a single `cmap` subtable.
+pub fn read_cmap_subtable(data: &mut FontDataStream) -> Result<CmapSubtable, Error> {
+    let format = data.read_u16()?;
+    let mut mappings = BTreeMap::new();
+    let mut insert = |code: u32, glyph: u16| {
+        if glyph != 0 {
+            mappings.insert(code, glyph);
+        }
+    };
+    let language = match format {
+        0 => {
+            data.read_u16()?;
+            let language = data.read_u16()? as u32;
+            for code in 0..256 {
+                insert(code, data.read_u8()? as u16);
+            }
+            language
+        }
+        2 => {
+            let length = data.read_u16()? as usize;
+            data.seek(0);
+            return Ok(CmapSubtable::Raw(data.read_bytes(length)?.to_vec()));
+        }
+        4 => {
+            data.read_u16()?;
+            let language = data.read_u16()? as u32;
+            let seg_count = (data.read_u16()? / 2) as usize;
+            data.skip(6);
+            let array = |data: &mut FontDataStream| -> Result<Vec<u16>, Error> {
+                (0..seg_count).map(|_| data.read_u16()).collect()
+            };
+            let end_codes = array(data)?;
+            data.read_u16()?;
+            let start_codes = array(data)?;
+            let id_deltas = array(data)?;
+            let range_offsets_start = data.position();
+            let id_range_offsets = array(data)?;
+            for i in 0..seg_count {
+                let (start, end) = (start_codes[i], end_codes[i]);
+                if start > end {
+                    continue;
+                }
+                for code in start..=end {
+                    if code == 0xFFFF {
+                        break;
+                    }
+                    let glyph = if id_range_offsets[i] == 0 {
+                        code.wrapping_add(id_deltas[i])
+                    } else {
+                        let position = range_offsets_start
+                            + i * 2
+                            + id_range_offsets[i] as usize
+                            + (code - start) as usize * 2;
+                        match data.read_at_u16(position) {
+                            Ok(0) | Err(_) => 0,
+                            Ok(glyph) => glyph.wrapping_add(id_deltas[i]),
+                        }
+                    };
+                    insert(code as u32, glyph);
+                }
+            }
+            language
+        }
+        6 => {
+            data.read_u16()?;
+            let language = data.read_u16()? as u32;
+            let first_code = data.read_u16()? as u32;
+            let count = data.read_u16()? as u32;
+            for code in first_code..first_code + count {
+                insert(code, data.read_u16()?);
+            }
+            language
+        }
+        8 => {
+            data.read_u16()?;
+            let length = data.read_u32()? as usize;
+            data.seek(0);
+            return Ok(CmapSubtable::Raw(data.read_bytes(length)?.to_vec()));
+        }
+        10 => {
+            data.read_u16()?;
+            data.read_u32()?;
+            let language = data.read_u32()?;
+            let start = data.read_u32()?;
+            let count = data.read_u32()?;
+            for code in start..start.saturating_add(count) {
+                insert(code, data.read_u16()?);
+            }
+            language
+        }
+        12 | 13 => {
+            data.read_u16()?;
+            data.read_u32()?;
+            let language = data.read_u32()?;
+            let count = data.read_u32()?;
+            for _ in 0..count {
+                let start = data.read_u32()?;
+                let end = data.read_u32()?.min(0x10FFFF);
+                let glyph = data.read_u32()?;
+                for code in start..=end {
+                    let id = if format == 12 {
+                        glyph + (code - start)
+                    } else {
+                        glyph
+                    };
+                    insert(code, id as u16);
+                }
+            }
+            language
+        }
+        14 => {
+            data.read_u32()?;
+            let count = data.read_u32()?;
+            let mut selectors = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let var_selector = data.read_u24()?;
+                let default_offset = data.read_u32()? as usize;
+                let non_default_offset = data.read_u32()? as usize;
+                let mut selector = VariationSelector {
+                    var_selector,
+                    ..Default::default()
+                };
+                if default_offset != 0 {
+                    let mut ranges = data.substream(default_offset)?;
+                    let count = ranges.read_u32()?;
+                    for _ in 0..count {
+                        let start = ranges.read_u24()?;
+                        selector.default_uvs.push((start, ranges.read_u8()?));
+                    }
+                }
+                if non_default_offset != 0 {
+                    let mut uvs = data.substream(non_default_offset)?;
+                    let count = uvs.read_u32()?;
+                    for _ in 0..count {
+                        let code = uvs.read_u24()?;
+                        selector.non_default_uvs.insert(code, uvs.read_u16()?);
+                    }
+                }
+                selectors.push(selector);
+            }
+            return Ok(CmapSubtable::Variations(selectors));
+        }
+        _ => return Err(Error::InvalidFormat),
+    };
+    Ok(CmapSubtable::Mapping {
+        format,
+        language,
+        mappings,
+    })
+}
+
+/// Serializes the `cmap` table. Records are sorted by platform and encoding
+/// and identical subtables are stored once.
+pub fn write_cmap(writer: &mut FontDataWriter, cmap: &CmapTable) -> Result<(), Error> {
+    let base = writer.position();
+    let mut records: Vec<_> = cmap.records.iter().collect();
+    records.sort_by_key(|r| (r.platform_id, r.encoding_id));
+    writer.write_u16(cmap.version);
+    writer.write_u16(records.len() as u16);
+    for record in records {
+        writer.write_u16(record.platform_id);
+        writer.write_u16(record.encoding_id);
+        let mut subtable = FontDataWriter::new();
+        write_cmap_subtable(&mut subtable, &record.subtable)?;
+        let placeholder = writer.reserve_offset32(base);
+        writer.defer_subtable(placeholder, subtable.into_bytes()?);
+    }
+    Ok(())
+}
+
+/// Splits mappings into runs of consecutive codes, each further split into
+/// runs where the glyph id also increases by one.
+fn code_runs(mappings: &BTreeMap<u32, u16>, glyph_step: u16) -> Vec<(u32, u32, u16)> {
+    let mut runs: Vec<(u32, u32, u16)> = Vec::new();
+    for (&code, &glyph) in mappings {
+        if let Some(last) = runs.last_mut()
+            && last.1 + 1 == code
+            && last
+                .2
+                .wrapping_add(((code - last.0) as u16).wrapping_mul(glyph_step))
+                == glyph
+        {
+            last.1 = code;
+            continue;
+        }
+        runs.push((code, code, glyph));
+    }
+    runs
+}
+
+/// Builds format 4 segments `(start, end, id_delta, glyph ids)`. Contiguous
+/// code ranges use a glyph id array when that is smaller than one delta
+/// segment per glyph run.
+fn format4_segments(mappings: &BTreeMap<u32, u16>) -> Vec<(u16, u16, u16, Option<Vec<u16>>)> {
+    let bmp: BTreeMap<u32, u16> = mappings
+        .range(..0xFFFF)
+        .map(|(&code, &glyph)| (code, glyph))
+        .collect();
+    let runs = code_runs(&bmp, 1);
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < runs.len() {
+        let mut j = i + 1;
+        while j < runs.len() && runs[j - 1].1 + 1 == runs[j].0 {
+            j += 1;
+        }
+        let (start, end) = (runs[i].0, runs[j - 1].1);
+        let length = (end - start + 1) as usize;
+        if 8 + 2 * length < 8 * (j - i) {
+            let glyphs = (start..=end).map(|code| bmp[&code]).collect();
+            segments.push((start as u16, end as u16, 0, Some(glyphs)));
+        } else {
+            for &(start, end, glyph) in &runs[i..j] {
+                segments.push((
+                    start as u16,
+                    end as u16,
+                    glyph.wrapping_sub(start as u16),
+                    None,
+                ));
+            }
+        }
+        i = j;
+    }
+    segments.push((0xFFFF, 0xFFFF, 1, None));
+    segments
+}
+
+/// Serializes a single `cmap` subtable.
+pub fn write_cmap_subtable(
+    writer: &mut FontDataWriter,
+    subtable: &CmapSubtable,
+) -> Result<(), Error> {
+    let start = writer.position();
+    let (format, language, mappings) = match subtable {
+        CmapSubtable::Raw(bytes) => {
+            writer.write_bytes(bytes);
+            return Ok(());
+        }
+        CmapSubtable::Variations(selectors) => {
+            writer.write_u16(14);
+            let length = writer.reserve_offset32(start);
+            writer.write_u32(selectors.len() as u32);
+            let mut selectors: Vec<_> = selectors.iter().collect();
+            selectors.sort_by_key(|s| s.var_selector);
+            for selector in selectors {
+                writer.write_u24(selector.var_selector);
+                if selector.default_uvs.is_empty() {
+                    writer.write_u32(0);
+                } else {
+                    let mut ranges = FontDataWriter::new();
+                    ranges.write_u32(selector.default_uvs.len() as u32);
+                    for &(start, count) in &selector.default_uvs {
+                        ranges.write_u24(start);
+                        ranges.write_u8(count);
+                    }
+                    let placeholder = writer.reserve_offset32(start);
+                    writer.defer_subtable(placeholder, ranges.into_bytes()?);
+                }
+                if selector.non_default_uvs.is_empty() {
+                    writer.write_u32(0);
+                } else {
+                    let mut uvs = FontDataWriter::new();
+                    uvs.write_u32(selector.non_default_uvs.len() as u32);
+                    for (&code, &glyph) in &selector.non_default_uvs {
+                        uvs.write_u24(code);
+                        uvs.write_u16(glyph);
+                    }
+                    let placeholder = writer.reserve_offset32(start);
+                    writer.defer_subtable(placeholder, uvs.into_bytes()?);
+                }
+            }
+            writer.flush_subtables(1)?;
+            let end = writer.position();
+            return writer.patch_offset(length, end);
+        }
+        CmapSubtable::Mapping {
+            format,
+            language,
+            mappings,
+        } => (*format, *language, mappings),
+    };
+
+    match format {
+        0 => {
+            writer.write_u16(0);
+            writer.write_u16(262);
+            writer.write_u16(language as u16);
+            for code in 0..256 {
+                let glyph = mappings.get(&code).copied().unwrap_or(0);
+                writer.write_u8(u8::try_from(glyph).map_err(|_| IoError::InvalidData)?);
+            }
+        }
+        4 => {
+            let segments = format4_segments(mappings);
+            let seg_count = segments.len();
+            let array_len: usize = segments
+                .iter()
+                .filter_map(|s| s.3.as_ref())
+                .map(Vec::len)
+                .sum();
+            let length = 16 + seg_count * 8 + array_len * 2;
+            writer.write_u16(4);
+            writer.write_u16(u16::try_from(length).map_err(|_| IoError::InvalidData)?);
+            writer.write_u16(language as u16);
+            let entry_selector = (seg_count as u16).ilog2() as u16;
+            let search_range = 2 << entry_selector;
+            writer.write_u16(seg_count as u16 * 2);
+            writer.write_u16(search_range);
+            writer.write_u16(entry_selector);
+            writer.write_u16(seg_count as u16 * 2 - search_range);
+            for segment in &segments {
+                writer.write_u16(segment.1);
+            }
+            writer.write_u16(0);
+            for segment in &segments {
+                writer.write_u16(segment.0);
+            }
+            for segment in &segments {
+                writer.write_u16(segment.2);
+            }
+            let mut array_offset = 0;
+            for (i, segment) in segments.iter().enumerate() {
+                match &segment.3 {
+                    Some(glyphs) => {
+                        writer.write_u16(((seg_count - i + array_offset) * 2) as u16);
+                        array_offset += glyphs.len();
+                    }
+                    None => writer.write_u16(0),
+                }
+            }
+            for glyphs in segments.iter().filter_map(|s| s.3.as_ref()) {
+                for &glyph in glyphs {
+                    writer.write_u16(glyph);
+                }
+            }
+        }
+        6 | 10 => {
+            let first = mappings.keys().next().copied().unwrap_or(0);
+            let last = mappings.keys().next_back().copied().unwrap_or(0);
+            let count = if mappings.is_empty() {
+                0
+            } else {
+                last - first + 1
+            };
+            if format == 6 {
+                let first = u16::try_from(first).map_err(|_| IoError::InvalidData)?;
+                writer.write_u16(6);
+                writer.write_u16(u16::try_from(10 + count * 2).map_err(|_| IoError::InvalidData)?);
+                writer.write_u16(language as u16);
+                writer.write_u16(first);
+                writer.write_u16(count as u16);
+            } else {
+                writer.write_u16(10);
+                writer.write_u16(0);
+                writer.write_u32(20 + count * 2);
+                writer.write_u32(language);
+                writer.write_u32(first);
+                writer.write_u32(count);
+            }
+            for code in first..first + count {
+                writer.write_u16(mappings.get(&code).copied().unwrap_or(0));
+            }
+        }
+        12 | 13 => {
+            let groups = code_runs(mappings, if format == 12 { 1 } else { 0 });
+            writer.write_u16(format);
+            writer.write_u16(0);
+            writer.write_u32(16 + groups.len() as u32 * 12);
+            writer.write_u32(language);
+            writer.write_u32(groups.len() as u32);
+            for (start, end, glyph) in groups {
+                writer.write_u32(start);
+                writer.write_u32(end);
+                writer.write_u32(glyph as u32);
+            }
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    Ok(())
+}
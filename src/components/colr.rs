@@ -0,0 +1,1282 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `COLR` (Color) table representation and utilities for the Aurora Font Library.
+//!
+//! Version 0 color glyphs are stacks of solid color layers. Version 1 color
+//! glyphs are graphs of paint tables (fills, gradients, glyph clips,
+//! transforms and compositing) rendered through a [`ColorPainter`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::f32::consts::PI;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+use super::cpal::{Color, CpalTable};
+use super::layout::read_optional_at;
+use super::variation::{
+    DeltaSetIndexMap, ItemVariationStore, read_delta_set_index_map, read_item_variation_store,
+};
+
+/// Palette index meaning the text foreground color.
+pub const FOREGROUND_COLOR_INDEX: u16 = 0xFFFF;
+/// Variation index base meaning the values of a variable table do not vary.
+pub const NO_VARIATION_INDEX: u32 = 0xFFFF_FFFF;
+
+/// Maximum nesting of paint tables, when reading and when painting.
+const MAX_PAINT_DEPTH: usize = 64;
+/// Maximum number of paints visited while painting one glyph. Shared paints
+/// are visited once per reference, so a small graph can expand greatly.
+const MAX_PAINT_EDGES: usize = 65536;
+
+/// A layer of a version 0 color glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayerRecord {
+    /// Glyph drawn as the layer.
+    pub glyph_id: u16,
+    /// `CPAL` palette entry filling the layer, or `FOREGROUND_COLOR_INDEX`.
+    pub palette_index: u16,
+}
+
+/// A layer with its color resolved against a palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorLayer {
+    /// Glyph drawn as the layer.
+    pub glyph_id: u16,
+    /// Fill color.
+    pub color: Color,
+}
+
+/// How a gradient continues outside its color stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Extend {
+    /// The end colors continue.
+    #[default]
+    Pad,
+    /// The color line repeats.
+    Repeat,
+    /// The color line repeats, mirrored every other time.
+    Reflect,
+}
+
+/// Compositing and blending modes of `PaintComposite`, in the order of their
+/// numeric values (Porter-Duff modes, then separable and non-separable
+/// blend modes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompositeMode {
+    /// No source or backdrop.
+    Clear,
+    /// The source only.
+    Src,
+    /// The backdrop only.
+    Dest,
+    /// The source over the backdrop.
+    SrcOver,
+    /// The backdrop over the source.
+    DestOver,
+    /// The source where the backdrop is.
+    SrcIn,
+    /// The backdrop where the source is.
+    DestIn,
+    /// The source where the backdrop is not.
+    SrcOut,
+    /// The backdrop where the source is not.
+    DestOut,
+    /// The source over the backdrop, where the backdrop is.
+    SrcAtop,
+    /// The backdrop over the source, where the source is.
+    DestAtop,
+    /// The source and backdrop where only one of them is.
+    Xor,
+    /// The sum of the source and backdrop.
+    Plus,
+    /// Complements multiplied, then complemented.
+    Screen,
+    /// Multiply or screen, depending on the backdrop.
+    Overlay,
+    /// The darker of the source and backdrop.
+    Darken,
+    /// The lighter of the source and backdrop.
+    Lighten,
+    /// The backdrop brightened to reflect the source.
+    ColorDodge,
+    /// The backdrop darkened to reflect the source.
+    ColorBurn,
+    /// Multiply or screen, depending on the source.
+    HardLight,
+    /// Darken or lighten, depending on the source.
+    SoftLight,
+    /// The difference of the darker from the lighter.
+    Difference,
+    /// Like `Difference`, with lower contrast.
+    Exclusion,
+    /// The product of the source and backdrop.
+    Multiply,
+    /// The hue of the source with the saturation and luminosity of the backdrop.
+    Hue,
+    /// The saturation of the source with the hue and luminosity of the backdrop.
+    Saturation,
+    /// The hue and saturation of the source with the luminosity of the backdrop.
+    Color,
+    /// The luminosity of the source with the hue and saturation of the backdrop.
+    Luminosity,
+}
+
+/// Composite modes in the order of their numeric values.
+const COMPOSITE_MODES: [CompositeMode; 28] = [
+    CompositeMode::Clear,
+    CompositeMode::Src,
+    CompositeMode::Dest,
+    CompositeMode::SrcOver,
+    CompositeMode::DestOver,
+    CompositeMode::SrcIn,
+    CompositeMode::DestIn,
+    CompositeMode::SrcOut,
+    CompositeMode::DestOut,
+    CompositeMode::SrcAtop,
+    CompositeMode::DestAtop,
+    CompositeMode::Xor,
+    CompositeMode::Plus,
+    CompositeMode::Screen,
+    CompositeMode::Overlay,
+    CompositeMode::Darken,
+    CompositeMode::Lighten,
+    CompositeMode::ColorDodge,
+    CompositeMode::ColorBurn,
+    CompositeMode::HardLight,
+    CompositeMode::SoftLight,
+    CompositeMode::Difference,
+    CompositeMode::Exclusion,
+    CompositeMode::Multiply,
+    CompositeMode::Hue,
+    CompositeMode::Saturation,
+    CompositeMode::Color,
+    CompositeMode::Luminosity,
+];
+
+/// A 2x3 affine transform: `x' = xx * x + xy * y + dx`, `y' = yx * x + yy * y + dy`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// X scale component.
+    pub xx: f32,
+    /// Y shear component.
+    pub yx: f32,
+    /// X shear component.
+    pub xy: f32,
+    /// Y scale component.
+    pub yy: f32,
+    /// X translation.
+    pub dx: f32,
+    /// Y translation.
+    pub dy: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform {
+    /// The identity transform.
+    pub const IDENTITY: Transform = Transform {
+        xx: 1.0,
+        yx: 0.0,
+        xy: 0.0,
+        yy: 1.0,
+        dx: 0.0,
+        dy: 0.0,
+    };
+
+    /// Returns a translation.
+    pub fn translate(dx: f32, dy: f32) -> Self {
+        Transform {
+            dx,
+            dy,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Returns a scale about the origin.
+    pub fn scale(scale_x: f32, scale_y: f32) -> Self {
+        Transform {
+            xx: scale_x,
+            yy: scale_y,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Returns a counter-clockwise rotation by `angle` radians.
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Transform {
+            xx: cos,
+            yx: sin,
+            xy: -sin,
+            yy: cos,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Returns a skew by angles in radians; positive `x_angle` skews
+    /// counter-clockwise from the y axis, positive `y_angle` counter-clockwise
+    /// from the x axis.
+    pub fn skew(x_angle: f32, y_angle: f32) -> Self {
+        Transform {
+            yx: y_angle.tan(),
+            xy: -x_angle.tan(),
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Returns the transform applying `other` first, then `self`.
+    pub fn multiply(&self, other: &Transform) -> Self {
+        Transform {
+            xx: self.xx * other.xx + self.xy * other.yx,
+            yx: self.yx * other.xx + self.yy * other.yx,
+            xy: self.xx * other.xy + self.xy * other.yy,
+            yy: self.yx * other.xy + self.yy * other.yy,
+            dx: self.xx * other.dx + self.xy * other.dy + self.dx,
+            dy: self.yx * other.dx + self.yy * other.dy + self.dy,
+        }
+    }
+
+    /// Returns the transform applied about `center` instead of the origin.
+    pub fn around(&self, center: (f32, f32)) -> Self {
+        Transform::translate(center.0, center.1)
+            .multiply(self)
+            .multiply(&Transform::translate(-center.0, -center.1))
+    }
+
+    /// Transforms a point.
+    pub fn apply(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (
+            self.xx * x + self.xy * y + self.dx,
+            self.yx * x + self.yy * y + self.dy,
+        )
+    }
+}
+
+/// A color stop of a color line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    /// Position on the color line.
+    pub stop_offset: f32,
+    /// `CPAL` palette entry, or `FOREGROUND_COLOR_INDEX`.
+    pub palette_index: u16,
+    /// Alpha multiplied with the palette color.
+    pub alpha: f32,
+    /// First variation index of the offset and alpha (variable color lines).
+    pub var_index_base: Option<u32>,
+}
+
+/// The color stops of a gradient.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorLine {
+    /// Extend mode.
+    pub extend: Extend,
+    /// Color stops in file order.
+    pub stops: Vec<ColorStop>,
+}
+
+/// Index of a paint in [`ColrTable::paints`].
+pub type PaintId = usize;
+
+/// A paint table of a version 1 color glyph. `var_index_base` is set for the
+/// variable formats; `center` is set for the formats about a center point.
+/// Child paints are referenced by their index in [`ColrTable::paints`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    /// Format 1: paints of the layer list, bottom first.
+    ColrLayers {
+        first_layer_index: u32,
+        num_layers: u8,
+    },
+    /// Formats 2 and 3: a solid color.
+    Solid {
+        palette_index: u16,
+        alpha: f32,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 4 and 5: a linear gradient from `p0` towards `p1`, rotated by `p2`.
+    LinearGradient {
+        color_line: ColorLine,
+        x0: i16,
+        y0: i16,
+        x1: i16,
+        y1: i16,
+        x2: i16,
+        y2: i16,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 6 and 7: a gradient between two circles.
+    RadialGradient {
+        color_line: ColorLine,
+        x0: i16,
+        y0: i16,
+        radius0: u16,
+        x1: i16,
+        y1: i16,
+        radius1: u16,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 8 and 9: a sweep gradient; angles are in units of 180 degrees.
+    SweepGradient {
+        color_line: ColorLine,
+        center_x: i16,
+        center_y: i16,
+        start_angle: f32,
+        end_angle: f32,
+        var_index_base: Option<u32>,
+    },
+    /// Format 10: `paint` clipped by the outline of a glyph.
+    Glyph { paint: PaintId, glyph_id: u16 },
+    /// Format 11: the version 1 color glyph of another base glyph.
+    ColrGlyph { glyph_id: u16 },
+    /// Formats 12 and 13: an affine transform.
+    Transform {
+        paint: PaintId,
+        transform: Transform,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 14 and 15: a translation.
+    Translate {
+        paint: PaintId,
+        dx: i16,
+        dy: i16,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 16 to 19: a scale.
+    Scale {
+        paint: PaintId,
+        scale_x: f32,
+        scale_y: f32,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 20 to 23: a uniform scale.
+    ScaleUniform {
+        paint: PaintId,
+        scale: f32,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 24 to 27: a rotation; the angle is in units of 180 degrees.
+    Rotate {
+        paint: PaintId,
+        angle: f32,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Formats 28 to 31: a skew; angles are in units of 180 degrees.
+    Skew {
+        paint: PaintId,
+        x_skew_angle: f32,
+        y_skew_angle: f32,
+        center: Option<(i16, i16)>,
+        var_index_base: Option<u32>,
+    },
+    /// Format 32: `source` composited onto `backdrop`.
+    Composite {
+        source: PaintId,
+        mode: CompositeMode,
+        backdrop: PaintId,
+    },
+}
+
+/// A clip box in font units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClipBox {
+    /// Left edge.
+    pub x_min: i16,
+    /// Bottom edge.
+    pub y_min: i16,
+    /// Right edge.
+    pub x_max: i16,
+    /// Top edge.
+    pub y_max: i16,
+    /// First variation index of the bounds (format 2).
+    pub var_index_base: Option<u32>,
+}
+
+/// The clip box of a range of base glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Clip {
+    /// First glyph of the range.
+    pub start_glyph_id: u16,
+    /// Last glyph of the range, inclusive.
+    pub end_glyph_id: u16,
+    /// The shared clip box.
+    pub clip_box: ClipBox,
+}
+
+/// A resolved gradient color stop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// Position on the color line.
+    pub offset: f32,
+    /// Color with the stop alpha applied.
+    pub color: Color,
+}
+
+/// A fill with colors and geometry resolved at a location.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Brush {
+    /// A solid color.
+    Solid(Color),
+    /// A linear gradient from `p0` (offset 0) to `p1` (offset 1).
+    LinearGradient {
+        p0: (f32, f32),
+        p1: (f32, f32),
+        stops: Vec<GradientStop>,
+        extend: Extend,
+    },
+    /// A gradient from circle `c0`, `r0` (offset 0) to circle `c1`, `r1` (offset 1).
+    RadialGradient {
+        c0: (f32, f32),
+        r0: f32,
+        c1: (f32, f32),
+        r1: f32,
+        stops: Vec<GradientStop>,
+        extend: Extend,
+    },
+    /// A sweep gradient around `center`, counter-clockwise from `start_angle`
+    /// (offset 0) to `end_angle` (offset 1), in degrees.
+    SweepGradient {
+        center: (f32, f32),
+        start_angle: f32,
+        end_angle: f32,
+        stops: Vec<GradientStop>,
+        extend: Extend,
+    },
+}
+
+/// A clip shape pushed by [`ColorPainter::push_clip`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipShape {
+    /// The outline of a glyph.
+    Glyph(u16),
+    /// A rectangle in font units.
+    Box {
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    },
+}
+
+/// Receives the drawing operations of a color glyph. Pushes and pops are
+/// balanced, even when painting stops with an error; transforms and clips
+/// apply to everything painted until they are popped.
+pub trait ColorPainter {
+    /// Composes `transform` with the current transform.
+    fn push_transform(&mut self, transform: Transform);
+    /// Restores the transform before the matching push.
+    fn pop_transform(&mut self);
+    /// Intersects the clip region with `clip`, in current coordinates.
+    fn push_clip(&mut self, clip: ClipShape);
+    /// Restores the clip region before the matching push.
+    fn pop_clip(&mut self);
+    /// Fills the current clip region.
+    fn fill(&mut self, brush: Brush);
+    /// Starts an offscreen layer composited with `mode` when popped.
+    fn push_layer(&mut self, mode: CompositeMode);
+    /// Composites the current layer onto the one below.
+    fn pop_layer(&mut self);
+}
+
+/// Color table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColrTable {
+    /// Table version (0 or 1).
+    pub version: u16,
+    /// Layers of each version 0 color glyph, bottom first.
+    pub base_glyphs: BTreeMap<u16, Vec<LayerRecord>>,
+    /// Root paint of each version 1 color glyph.
+    pub base_glyph_paints: BTreeMap<u16, PaintId>,
+    /// Paints referenced by `Paint::ColrLayers`.
+    pub layer_list: Vec<PaintId>,
+    /// Every paint of the version 1 graphs. A paint table referenced from
+    /// several places is stored once.
+    pub paints: Vec<Paint>,
+    /// Clip boxes of version 1 color glyphs.
+    pub clips: Vec<Clip>,
+    /// Maps variation indices to delta-set items; without it an index is
+    /// split into outer and inner halves.
+    pub var_index_map: Option<DeltaSetIndexMap>,
+    /// Deltas of the variable paints.
+    pub item_variation_store: Option<ItemVariationStore>,
+}
+
+impl ColrTable {
+    /// Returns the version 0 layers of a glyph.
+    pub fn layers(&self, glyph_id: u16) -> Option<&[LayerRecord]> {
+        self.base_glyphs.get(&glyph_id).map(Vec::as_slice)
+    }
+
+    /// Returns the version 0 layers of a glyph, bottom first, with the colors
+    /// of a `CPAL` palette. `FOREGROUND_COLOR_INDEX` and entries missing from
+    /// the palette take `foreground`.
+    ///
+    /// # Arguments
+    /// * `glyph_id` - The base glyph.
+    /// * `cpal` - The palettes of the font.
+    /// * `palette_index` - Palette to use; 0 is the default palette.
+    /// * `foreground` - The text color.
+    pub fn color_layers(
+        &self,
+        glyph_id: u16,
+        cpal: &CpalTable,
+        palette_index: usize,
+        foreground: Color,
+    ) -> Option<Vec<ColorLayer>> {
+        let layers = self.layers(glyph_id)?;
+        Some(
+            layers
+                .iter()
+                .map(|layer| ColorLayer {
+                    glyph_id: layer.glyph_id,
+                    color: match layer.palette_index {
+                        FOREGROUND_COLOR_INDEX => foreground,
+                        index => cpal.color(palette_index, index).unwrap_or(foreground),
+                    },
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns a paint of the version 1 graphs.
+    pub fn paint(&self, id: PaintId) -> Option<&Paint> {
+        self.paints.get(id)
+    }
+
+    /// Returns the clip box of a version 1 color glyph.
+    pub fn clip_box(&self, glyph_id: u16) -> Option<&ClipBox> {
+        self.clips
+            .iter()
+            .find(|clip| (clip.start_glyph_id..=clip.end_glyph_id).contains(&glyph_id))
+            .map(|clip| &clip.clip_box)
+    }
+
+    /// Returns the deltas of `count` consecutive variation indices starting
+    /// at `var_index_base`, at normalized coordinates `coords`.
+    pub fn deltas(&self, var_index_base: Option<u32>, count: usize, coords: &[f32]) -> Vec<f32> {
+        let (Some(base), Some(store)) = (
+            var_index_base.filter(|&base| base != NO_VARIATION_INDEX),
+            &self.item_variation_store,
+        ) else {
+            return vec![0.0; count];
+        };
+        (0..count as u32)
+            .map(|i| {
+                let index = base.saturating_add(i);
+                let item = match &self.var_index_map {
+                    Some(map) => map.get(index),
+                    None => Some(((index >> 16) as u16, index as u16)),
+                };
+                item.map_or(0.0, |(outer, inner)| store.delta(outer, inner, coords))
+            })
+            .collect()
+    }
+
+    /// Paints a color glyph: the version 1 paint graph if the glyph has one,
+    /// else its version 0 layers. Returns `false` for glyphs without color.
+    ///
+    /// # Arguments
+    /// * `glyph_id` - The base glyph.
+    /// * `cpal` - The palettes of the font.
+    /// * `palette_index` - Palette to use; 0 is the default palette.
+    /// * `foreground` - The text color.
+    /// * `coords` - Normalized variation coordinates; empty for the default.
+    /// * `painter` - Receives the drawing operations.
+    ///
+    /// Returns `IoError::InvalidData` if the paint graph has a cycle, nests
+    /// too deeply or expands to too many paints; the painter may have
+    /// received part of the glyph, with every push popped.
+    pub fn paint_glyph(
+        &self,
+        glyph_id: u16,
+        cpal: &CpalTable,
+        palette_index: usize,
+        foreground: Color,
+        coords: &[f32],
+        painter: &mut dyn ColorPainter,
+    ) -> Result<bool, Error> {
+        let mut walker = PaintWalker {
+            colr: self,
+            cpal,
+            palette_index,
+            foreground,
+            coords,
+            glyphs: Vec::new(),
+            layers: Vec::new(),
+            edges: 0,
+        };
+        if self.base_glyph_paints.contains_key(&glyph_id) {
+            walker.walk_colr_glyph(glyph_id, painter, 0)?;
+            return Ok(true);
+        }
+        let Some(layers) = self.color_layers(glyph_id, cpal, palette_index, foreground) else {
+            return Ok(false);
+        };
+        for layer in layers {
+            painter.push_clip(ClipShape::Glyph(layer.glyph_id));
+            painter.fill(Brush::Solid(layer.color));
+            painter.pop_clip();
+        }
+        Ok(true)
+    }
+}
+
+/// State of a paint graph traversal.
+struct PaintWalker<'a> {
+    colr: &'a ColrTable,
+    cpal: &'a CpalTable,
+    palette_index: usize,
+    foreground: Color,
+    coords: &'a [f32],
+    /// Base glyphs and layer indices being painted, to detect cycles.
+    glyphs: Vec<u16>,
+    layers: Vec<u32>,
+    /// Paints visited so far.
+    edges: usize,
+}
+
+impl PaintWalker<'_> {
+    fn color(&self, palette_index: u16, alpha: f32) -> Color {
+        let color = match palette_index {
+            FOREGROUND_COLOR_INDEX => self.foreground,
+            index => self
+                .cpal
+                .color(self.palette_index, index)
+                .unwrap_or(self.foreground),
+        };
+        Color {
+            alpha: (color.alpha as f32 * alpha.clamp(0.0, 1.0)).round() as u8,
+            ..color
+        }
+    }
+
+    fn deltas(&self, var_index_base: Option<u32>, count: usize) -> Vec<f32> {
+        self.colr.deltas(var_index_base, count, self.coords)
+    }
+
+    /// Resolves the stops of a color line, sorted by offset.
+    fn stops(&self, color_line: &ColorLine) -> Vec<GradientStop> {
+        let mut stops: Vec<GradientStop> = color_line
+            .stops
+            .iter()
+            .map(|stop| {
+                let d = self.deltas(stop.var_index_base, 2);
+                GradientStop {
+                    offset: stop.stop_offset + d[0] / 16384.0,
+                    color: self.color(stop.palette_index, stop.alpha + d[1] / 16384.0),
+                }
+            })
+            .collect();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        stops
+    }
+
+    /// Paints the root paint of a base glyph inside its clip box.
+    fn walk_colr_glyph(
+        &mut self,
+        glyph_id: u16,
+        painter: &mut dyn ColorPainter,
+        depth: usize,
+    ) -> Result<(), Error> {
+        let colr = self.colr;
+        let paint = *colr.base_glyph_paints.get(&glyph_id).ok_or(Error::Io(
+            IoError::InvalidGlyphIndex {
+                index: glyph_id as u32,
+            },
+        ))?;
+        if self.glyphs.contains(&glyph_id) {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        self.glyphs.push(glyph_id);
+        let clip = colr.clip_box(glyph_id).map(|clip_box| {
+            let d = self.deltas(clip_box.var_index_base, 4);
+            ClipShape::Box {
+                x_min: clip_box.x_min as f32 + d[0],
+                y_min: clip_box.y_min as f32 + d[1],
+                x_max: clip_box.x_max as f32 + d[2],
+                y_max: clip_box.y_max as f32 + d[3],
+            }
+        });
+        if let Some(clip) = clip {
+            painter.push_clip(clip);
+        }
+        let result = self.walk(paint, painter, depth + 1);
+        if clip.is_some() {
+            painter.pop_clip();
+        }
+        self.glyphs.pop();
+        result
+    }
+
+    fn walk_transformed(
+        &mut self,
+        transform: Transform,
+        paint: PaintId,
+        painter: &mut dyn ColorPainter,
+        depth: usize,
+    ) -> Result<(), Error> {
+        painter.push_transform(transform);
+        let result = self.walk(paint, painter, depth + 1);
+        painter.pop_transform();
+        result
+    }
+
+    fn walk(
+        &mut self,
+        paint: PaintId,
+        painter: &mut dyn ColorPainter,
+        depth: usize,
+    ) -> Result<(), Error> {
+        self.edges += 1;
+        if depth > MAX_PAINT_DEPTH || self.edges > MAX_PAINT_EDGES {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let colr = self.colr;
+        let paint = colr.paint(paint).ok_or(Error::Io(IoError::InvalidData))?;
+        let center = |center: Option<(i16, i16)>, d: &[f32]| {
+            center.map(|(x, y)| {
+                (
+                    x as f32 + d.first().copied().unwrap_or(0.0),
+                    y as f32 + d.get(1).copied().unwrap_or(0.0),
+                )
+            })
+        };
+        match paint {
+            Paint::ColrLayers {
+                first_layer_index,
+                num_layers,
+            } => {
+                for index in (*first_layer_index..).take(*num_layers as usize) {
+                    let layer = *colr
+                        .layer_list
+                        .get(index as usize)
+                        .ok_or(Error::Io(IoError::InvalidData))?;
+                    if self.layers.contains(&index) {
+                        return Err(Error::Io(IoError::InvalidData));
+                    }
+                    self.layers.push(index);
+                    self.walk(layer, painter, depth + 1)?;
+                    self.layers.pop();
+                }
+            }
+            Paint::Solid {
+                palette_index,
+                alpha,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 1);
+                painter.fill(Brush::Solid(
+                    self.color(*palette_index, alpha + d[0] / 16384.0),
+                ));
+            }
+            Paint::LinearGradient {
+                color_line,
+                x0,
+                y0,
+                x1,
+                y1,
+                x2,
+                y2,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 6);
+                let p0 = (*x0 as f32 + d[0], *y0 as f32 + d[1]);
+                let p1 = (*x1 as f32 + d[2], *y1 as f32 + d[3]);
+                let p2 = (*x2 as f32 + d[4], *y2 as f32 + d[5]);
+                // Colors are constant along lines parallel to p0-p2, so the
+                // gradient runs from p0 to p1 projected on the normal of p0-p2.
+                let normal = (p2.1 - p0.1, p0.0 - p2.0);
+                let length = normal.0 * normal.0 + normal.1 * normal.1;
+                let p1 = if length == 0.0 {
+                    p1
+                } else {
+                    let t = ((p1.0 - p0.0) * normal.0 + (p1.1 - p0.1) * normal.1) / length;
+                    (p0.0 + normal.0 * t, p0.1 + normal.1 * t)
+                };
+                painter.fill(Brush::LinearGradient {
+                    p0,
+                    p1,
+                    stops: self.stops(color_line),
+                    extend: color_line.extend,
+                });
+            }
+            Paint::RadialGradient {
+                color_line,
+                x0,
+                y0,
+                radius0,
+                x1,
+                y1,
+                radius1,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 6);
+                painter.fill(Brush::RadialGradient {
+                    c0: (*x0 as f32 + d[0], *y0 as f32 + d[1]),
+                    r0: *radius0 as f32 + d[2],
+                    c1: (*x1 as f32 + d[3], *y1 as f32 + d[4]),
+                    r1: *radius1 as f32 + d[5],
+                    stops: self.stops(color_line),
+                    extend: color_line.extend,
+                });
+            }
+            Paint::SweepGradient {
+                color_line,
+                center_x,
+                center_y,
+                start_angle,
+                end_angle,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 4);
+                painter.fill(Brush::SweepGradient {
+                    center: (*center_x as f32 + d[0], *center_y as f32 + d[1]),
+                    start_angle: (start_angle + d[2] / 16384.0) * 180.0,
+                    end_angle: (end_angle + d[3] / 16384.0) * 180.0,
+                    stops: self.stops(color_line),
+                    extend: color_line.extend,
+                });
+            }
+            Paint::Glyph { paint, glyph_id } => {
+                painter.push_clip(ClipShape::Glyph(*glyph_id));
+                let result = self.walk(*paint, painter, depth + 1);
+                painter.pop_clip();
+                result?;
+            }
+            Paint::ColrGlyph { glyph_id } => self.walk_colr_glyph(*glyph_id, painter, depth)?,
+            Paint::Transform {
+                paint,
+                transform,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 6);
+                let transform = Transform {
+                    xx: transform.xx + d[0] / 65536.0,
+                    yx: transform.yx + d[1] / 65536.0,
+                    xy: transform.xy + d[2] / 65536.0,
+                    yy: transform.yy + d[3] / 65536.0,
+                    dx: transform.dx + d[4] / 65536.0,
+                    dy: transform.dy + d[5] / 65536.0,
+                };
+                self.walk_transformed(transform, *paint, painter, depth)?;
+            }
+            Paint::Translate {
+                paint,
+                dx,
+                dy,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 2);
+                let transform = Transform::translate(*dx as f32 + d[0], *dy as f32 + d[1]);
+                self.walk_transformed(transform, *paint, painter, depth)?;
+            }
+            Paint::Scale {
+                paint,
+                scale_x,
+                scale_y,
+                center: origin,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 4);
+                let transform =
+                    Transform::scale(scale_x + d[0] / 16384.0, scale_y + d[1] / 16384.0);
+                let transform = match center(*origin, &d[2..]) {
+                    Some(origin) => transform.around(origin),
+                    None => transform,
+                };
+                self.walk_transformed(transform, *paint, painter, depth)?;
+            }
+            Paint::ScaleUniform {
+                paint,
+                scale,
+                center: origin,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 3);
+                let scale = scale + d[0] / 16384.0;
+                let transform = Transform::scale(scale, scale);
+                let transform = match center(*origin, &d[1..]) {
+                    Some(origin) => transform.around(origin),
+                    None => transform,
+                };
+                self.walk_transformed(transform, *paint, painter, depth)?;
+            }
+            Paint::Rotate {
+                paint,
+                angle,
+                center: origin,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 3);
+                let transform = Transform::rotate((angle + d[0] / 16384.0) * PI);
+                let transform = match center(*origin, &d[1..]) {
+                    Some(origin) => transform.around(origin),
+                    None => transform,
+                };
+                self.walk_transformed(transform, *paint, painter, depth)?;
+            }
+            Paint::Skew {
+                paint,
+                x_skew_angle,
+                y_skew_angle,
+                center: origin,
+                var_index_base,
+            } => {
+                let d = self.deltas(*var_index_base, 4);
+                let transform = Transform::skew(
+                    (x_skew_angle + d[0] / 16384.0) * PI,
+                    (y_skew_angle + d[1] / 16384.0) * PI,
+                );
+                let transform = match center(*origin, &d[2..]) {
+                    Some(origin) => transform.around(origin),
+                    None => transform,
+                };
+                self.walk_transformed(transform, *paint, painter, depth)?;
+            }
+            Paint::Composite {
+                source,
+                mode,
+                backdrop,
+            } => {
+                painter.push_layer(CompositeMode::SrcOver);
+                let result = self.walk(*backdrop, painter, depth + 1).and_then(|()| {
+                    painter.push_layer(*mode);
+                    let result = self.walk(*source, painter, depth + 1);
+                    painter.pop_layer();
+                    result
+                });
+                painter.pop_layer();
+                result?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extracts a color line; variable color lines have a variation index per stop.
+pub fn read_color_line(data: &mut FontDataStream, variable: bool) -> Result<ColorLine, Error> {
+    let extend = match data.read_u8()? {
+        1 => Extend::Repeat,
+        2 => Extend::Reflect,
+        // Unknown modes are treated as pad.
+        _ => Extend::Pad,
+    };
+    let count = data.read_u16()?;
+    let stops = (0..count)
+        .map(|_| {
+            Ok(ColorStop {
+                stop_offset: data.read_f2dot14()?,
+                palette_index: data.read_u16()?,
+                alpha: data.read_f2dot14()?,
+                var_index_base: if variable {
+                    Some(data.read_u32()?)
+                } else {
+                    None
+                },
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(ColorLine { extend, stops })
+}
+
+/// Extracts the paints of a `COLR` table, decoding each paint table once
+/// however many paints reference it.
+struct PaintReader<'a> {
+    /// The whole `COLR` table; paints are keyed by their offset in it.
+    table: FontDataStream<'a>,
+    paints: Vec<Paint>,
+    ids: HashMap<usize, PaintId>,
+}
+
+impl<'a> PaintReader<'a> {
+    /// Returns the paint at `offset` in the table, decoding it and the
+    /// paints it references if they were not seen yet.
+    fn read(&mut self, offset: usize, depth: usize) -> Result<PaintId, Error> {
+        if let Some(&id) = self.ids.get(&offset) {
+            return Ok(id);
+        }
+        if depth > MAX_PAINT_DEPTH {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let paint = self.decode(offset, depth)?;
+        let id = self.paints.len();
+        self.paints.push(paint);
+        self.ids.insert(offset, id);
+        Ok(id)
+    }
+
+    fn decode(&mut self, offset: usize, depth: usize) -> Result<Paint, Error> {
+        let mut data = self.table.substream(offset)?;
+        let base = self.table.substream(offset)?;
+        let format = data.read_u8()?;
+        let variable = format % 2 == 1;
+        let child = |reader: &mut Self, data: &mut FontDataStream| -> Result<PaintId, Error> {
+            let child_offset = data.read_u24()? as usize;
+            reader.read(offset + child_offset, depth + 1)
+        };
+        let color_line = |data: &mut FontDataStream| -> Result<ColorLine, Error> {
+            let offset = data.read_u24()? as usize;
+            read_color_line(&mut base.substream(offset)?, variable)
+        };
+        let var_index_base = |data: &mut FontDataStream| -> Result<Option<u32>, Error> {
+            if variable {
+                data.read_u32().map(Some)
+            } else {
+                Ok(None)
+            }
+        };
+        let center =
+            |data: &mut FontDataStream, present: bool| -> Result<Option<(i16, i16)>, Error> {
+                if present {
+                    Ok(Some((data.read_i16()?, data.read_i16()?)))
+                } else {
+                    Ok(None)
+                }
+            };
+
+        Ok(match format {
+            1 => Paint::ColrLayers {
+                num_layers: data.read_u8()?,
+                first_layer_index: data.read_u32()?,
+            },
+            2 | 3 => Paint::Solid {
+                palette_index: data.read_u16()?,
+                alpha: data.read_f2dot14()?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            4 | 5 => Paint::LinearGradient {
+                color_line: color_line(&mut data)?,
+                x0: data.read_i16()?,
+                y0: data.read_i16()?,
+                x1: data.read_i16()?,
+                y1: data.read_i16()?,
+                x2: data.read_i16()?,
+                y2: data.read_i16()?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            6 | 7 => Paint::RadialGradient {
+                color_line: color_line(&mut data)?,
+                x0: data.read_i16()?,
+                y0: data.read_i16()?,
+                radius0: data.read_u16()?,
+                x1: data.read_i16()?,
+                y1: data.read_i16()?,
+                radius1: data.read_u16()?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            8 | 9 => Paint::SweepGradient {
+                color_line: color_line(&mut data)?,
+                center_x: data.read_i16()?,
+                center_y: data.read_i16()?,
+                start_angle: data.read_f2dot14()?,
+                end_angle: data.read_f2dot14()?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            10 => Paint::Glyph {
+                paint: child(self, &mut data)?,
+                glyph_id: data.read_u16()?,
+            },
+            11 => Paint::ColrGlyph {
+                glyph_id: data.read_u16()?,
+            },
+            12 | 13 => {
+                let paint = child(self, &mut data)?;
+                let offset = data.read_u24()? as usize;
+                let mut affine = base.substream(offset)?;
+                Paint::Transform {
+                    paint,
+                    transform: Transform {
+                        xx: affine.read_fixed()?,
+                        yx: affine.read_fixed()?,
+                        xy: affine.read_fixed()?,
+                        yy: affine.read_fixed()?,
+                        dx: affine.read_fixed()?,
+                        dy: affine.read_fixed()?,
+                    },
+                    var_index_base: var_index_base(&mut affine)?,
+                }
+            }
+            14 | 15 => Paint::Translate {
+                paint: child(self, &mut data)?,
+                dx: data.read_i16()?,
+                dy: data.read_i16()?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            16..=19 => Paint::Scale {
+                paint: child(self, &mut data)?,
+                scale_x: data.read_f2dot14()?,
+                scale_y: data.read_f2dot14()?,
+                center: center(&mut data, format >= 18)?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            20..=23 => Paint::ScaleUniform {
+                paint: child(self, &mut data)?,
+                scale: data.read_f2dot14()?,
+                center: center(&mut data, format >= 22)?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            24..=27 => Paint::Rotate {
+                paint: child(self, &mut data)?,
+                angle: data.read_f2dot14()?,
+                center: center(&mut data, format >= 26)?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            28..=31 => Paint::Skew {
+                paint: child(self, &mut data)?,
+                x_skew_angle: data.read_f2dot14()?,
+                y_skew_angle: data.read_f2dot14()?,
+                center: center(&mut data, format >= 30)?,
+                var_index_base: var_index_base(&mut data)?,
+            },
+            32 => Paint::Composite {
+                source: child(self, &mut data)?,
+                mode: *COMPOSITE_MODES
+                    .get(data.read_u8()? as usize)
+                    .ok_or(Error::InvalidFormat)?,
+                backdrop: child(self, &mut data)?,
+            },
+            _ => return Err(Error::InvalidFormat),
+        })
+    }
+}
+
+/// Extracts the base glyph list at `list_offset` in the table.
+fn read_base_glyph_list(
+    reader: &mut PaintReader,
+    list_offset: usize,
+) -> Result<BTreeMap<u16, PaintId>, Error> {
+    let mut data = reader.table.substream(list_offset)?;
+    let count = data.read_u32()?;
+    let mut paints = BTreeMap::new();
+    for _ in 0..count {
+        let glyph_id = data.read_u16()?;
+        let offset = data.read_u32()? as usize;
+        paints.insert(glyph_id, reader.read(list_offset + offset, 0)?);
+    }
+    Ok(paints)
+}
+
+/// Extracts the layer list at `list_offset` in the table.
+fn read_layer_list(reader: &mut PaintReader, list_offset: usize) -> Result<Vec<PaintId>, Error> {
+    let mut data = reader.table.substream(list_offset)?;
+    let count = data.read_u32()?;
+    (0..count)
+        .map(|_| {
+            let offset = data.read_u32()? as usize;
+            reader.read(list_offset + offset, 0)
+        })
+        .collect()
+}
+
+fn read_clip_list(data: &mut FontDataStream) -> Result<Vec<Clip>, Error> {
+    let format = data.read_u8()?;
+    if format != 1 {
+        return Err(Error::InvalidFormat);
+    }
+    let count = data.read_u32()?;
+    (0..count)
+        .map(|_| {
+            let start_glyph_id = data.read_u16()?;
+            let end_glyph_id = data.read_u16()?;
+            let offset = data.read_u24()? as usize;
+            let mut clip_box = data.substream(offset)?;
+            let format = clip_box.read_u8()?;
+            if !(1..=2).contains(&format) {
+                return Err(Error::InvalidFormat);
+            }
+            Ok(Clip {
+                start_glyph_id,
+                end_glyph_id,
+                clip_box: ClipBox {
+                    x_min: clip_box.read_i16()?,
+                    y_min: clip_box.read_i16()?,
+                    x_max: clip_box.read_i16()?,
+                    y_max: clip_box.read_i16()?,
+                    var_index_base: if format == 2 {
+                        Some(clip_box.read_u32()?)
+                    } else {
+                        None
+                    },
+                },
+            })
+        })
+        .collect()
+}
+
+/// Extracts the `COLR` table from the provided data stream.
+pub fn read_colr(data: &mut FontDataStream) -> Result<ColrTable, Error> {
+    let version = data.read_u16()?;
+    if version > 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let num_base_glyph_records = data.read_u16()? as usize;
+    let base_glyph_records_offset = data.read_u32()? as usize;
+    let layer_records_offset = data.read_u32()? as usize;
+    let num_layer_records = data.read_u16()? as usize;
+
+    let mut layers = Vec::with_capacity(num_layer_records);
+    if num_layer_records > 0 {
+        let mut records = data.substream(layer_records_offset)?;
+        for _ in 0..num_layer_records {
+            layers.push(LayerRecord {
+                glyph_id: records.read_u16()?,
+                palette_index: records.read_u16()?,
+            });
+        }
+    }
+    let mut base_glyphs = BTreeMap::new();
+    if num_base_glyph_records > 0 {
+        let mut records = data.substream(base_glyph_records_offset)?;
+        for _ in 0..num_base_glyph_records {
+            let glyph_id = records.read_u16()?;
+            let first = records.read_u16()? as usize;
+            let count = records.read_u16()? as usize;
+            let glyph_layers = layers
+                .get(first..first + count)
+                .ok_or(Error::Io(IoError::InvalidData))?;
+            base_glyphs.insert(glyph_id, glyph_layers.to_vec());
+        }
+    }
+
+    let mut colr = ColrTable {
+        version,
+        base_glyphs,
+        ..Default::default()
+    };
+    if version >= 1 {
+        let base_glyph_list_offset = data.read_u32()? as usize;
+        let layer_list_offset = data.read_u32()? as usize;
+        let clip_list_offset = data.read_u32()? as usize;
+        let var_index_map_offset = data.read_u32()? as usize;
+        let item_variation_store_offset = data.read_u32()? as usize;
+        let mut reader = PaintReader {
+            table: data.substream(0)?,
+            paints: Vec::new(),
+            ids: HashMap::new(),
+        };
+        if base_glyph_list_offset != 0 {
+            colr.base_glyph_paints = read_base_glyph_list(&mut reader, base_glyph_list_offset)?;
+        }
+        if layer_list_offset != 0 {
+            colr.layer_list = read_layer_list(&mut reader, layer_list_offset)?;
+        }
+        colr.paints = reader.paints;
+        colr.clips = read_optional_at(data, clip_list_offset, read_clip_list)?.unwrap_or_default();
+        colr.var_index_map =
+            read_optional_at(data, var_index_map_offset, read_delta_set_index_map)?;
+        colr.item_variation_store =
+            read_optional_at(data, item_variation_store_offset, read_item_variation_store)?;
+    }
+    Ok(colr)
+}
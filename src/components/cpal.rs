@@ -0,0 +1,175 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `CPAL` (Color Palette) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Palette type flag: the palette is suitable for a light background.
+pub const USABLE_WITH_LIGHT_BACKGROUND: u32 = 0x0001;
+/// Palette type flag: the palette is suitable for a dark background.
+pub const USABLE_WITH_DARK_BACKGROUND: u32 = 0x0002;
+/// Name ID value meaning a palette or entry has no label.
+pub const NO_LABEL: u16 = 0xFFFF;
+
+/// An sRGB color with straight (non-premultiplied) alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color {
+    /// Red component.
+    pub red: u8,
+    /// Green component.
+    pub green: u8,
+    /// Blue component.
+    pub blue: u8,
+    /// Alpha component; 0 is transparent.
+    pub alpha: u8,
+}
+
+impl Color {
+    /// Creates a color from its RGBA components.
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Color {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+/// Color palette table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CpalTable {
+    /// Table version (0 or 1).
+    pub version: u16,
+    /// Number of entries in every palette.
+    pub num_palette_entries: u16,
+    /// Colors of each palette.
+    pub palettes: Vec<Vec<Color>>,
+    /// Type flags of each palette (version 1).
+    pub palette_types: Option<Vec<u32>>,
+    /// Name ID of each palette label, or `NO_LABEL` (version 1).
+    pub palette_labels: Option<Vec<u16>>,
+    /// Name ID of each palette entry label, or `NO_LABEL` (version 1).
+    pub palette_entry_labels: Option<Vec<u16>>,
+}
+
+impl CpalTable {
+    /// Returns a color of a palette.
+    pub fn color(&self, palette_index: usize, entry_index: u16) -> Option<Color> {
+        self.palettes
+            .get(palette_index)?
+            .get(entry_index as usize)
+            .copied()
+    }
+
+    /// Returns the type flags of a palette; 0 without palette types.
+    pub fn palette_type(&self, palette_index: usize) -> u32 {
+        self.palette_types
+            .as_ref()
+            .and_then(|types| types.get(palette_index))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the name ID of a palette label.
+    pub fn palette_label(&self, palette_index: usize) -> Option<u16> {
+        self.palette_labels
+            .as_ref()?
+            .get(palette_index)
+            .copied()
+            .filter(|&name_id| name_id != NO_LABEL)
+    }
+
+    /// Returns the name ID of a palette entry label.
+    pub fn palette_entry_label(&self, entry_index: u16) -> Option<u16> {
+        self.palette_entry_labels
+            .as_ref()?
+            .get(entry_index as usize)
+            .copied()
+            .filter(|&name_id| name_id != NO_LABEL)
+    }
+
+    /// Returns the index of the first palette with all of the type `flags`,
+    /// such as `USABLE_WITH_DARK_BACKGROUND`, or 0 (the default palette).
+    pub fn find_palette(&self, flags: u32) -> usize {
+        (0..self.palettes.len())
+            .find(|&index| self.palette_type(index) & flags == flags)
+            .unwrap_or(0)
+    }
+}
+
+/// Extracts the `CPAL` table from the provided data stream.
+pub fn read_cpal(data: &mut FontDataStream) -> Result<CpalTable, Error> {
+    let version = data.read_u16()?;
+    if version > 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let num_palette_entries = data.read_u16()?;
+    let num_palettes = data.read_u16()? as usize;
+    let num_color_records = data.read_u16()? as usize;
+    let color_records_offset = data.read_u32()? as usize;
+    let first_indices = (0..num_palettes)
+        .map(|_| data.read_u16())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut records = data.substream(color_records_offset)?;
+    let colors = (0..num_color_records)
+        .map(|_| {
+            let blue = records.read_u8()?;
+            let green = records.read_u8()?;
+            let red = records.read_u8()?;
+            let alpha = records.read_u8()?;
+            Ok(Color::rgba(red, green, blue, alpha))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let palettes = first_indices
+        .iter()
+        .map(|&first| {
+            let first = first as usize;
+            colors
+                .get(first..first + num_palette_entries as usize)
+                .map(<[Color]>::to_vec)
+                .ok_or(Error::Io(IoError::InvalidData))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (mut palette_types, mut palette_labels, mut palette_entry_labels) = (None, None, None);
+    if version >= 1 {
+        let types_offset = data.read_u32()? as usize;
+        let labels_offset = data.read_u32()? as usize;
+        let entry_labels_offset = data.read_u32()? as usize;
+        if types_offset != 0 {
+            let mut types = data.substream(types_offset)?;
+            palette_types = Some(
+                (0..num_palettes)
+                    .map(|_| types.read_u32())
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+        let read_labels = |offset: usize, count: usize| -> Result<Option<Vec<u16>>, Error> {
+            if offset == 0 {
+                return Ok(None);
+            }
+            let mut labels = data.substream(offset)?;
+            (0..count)
+                .map(|_| labels.read_u16())
+                .collect::<Result<Vec<_>, _>>()
+                .map(Some)
+        };
+        palette_labels = read_labels(labels_offset, num_palettes)?;
+        palette_entry_labels = read_labels(entry_labels_offset, num_palette_entries as usize)?;
+    }
+    Ok(CpalTable {
+        version,
+        num_palette_entries,
+        palettes,
+        palette_types,
+        palette_labels,
+        palette_entry_labels,
+    })
+}
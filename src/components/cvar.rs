@@ -0,0 +1,95 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `cvar` (CVT Variations) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::variation::{TupleVariation, read_tuple_variations, write_tuple_variations};
+
+/// Control value table variations.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CvarTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Tuple variations with one delta per control value; point numbers are
+    /// control value indices.
+    pub variations: Vec<TupleVariation>,
+}
+
+impl CvarTable {
+    /// Returns the accumulated delta of each of `count` control values at
+    /// normalized coordinates `coords`.
+    pub fn deltas(&self, count: usize, coords: &[f32]) -> Vec<f32> {
+        let mut total = vec![0.0; count];
+        for tuple in &self.variations {
+            let scalar = tuple.scalar(coords);
+            if scalar == 0.0 {
+                continue;
+            }
+            for (sum, delta) in total.iter_mut().zip(cvt_deltas(tuple, count)) {
+                *sum += delta * scalar;
+            }
+        }
+        total
+    }
+}
+
+/// Returns the deltas of a tuple for each of `count` control values. Unlike
+/// `gvar`, values without an explicit delta are not interpolated and keep a
+/// zero delta.
+pub fn cvt_deltas(tuple: &TupleVariation, count: usize) -> Vec<f32> {
+    let mut deltas = vec![0.0; count];
+    let Some(values) = tuple.deltas.first() else {
+        return deltas;
+    };
+    match &tuple.point_numbers {
+        None => {
+            for (delta, &value) in deltas.iter_mut().zip(values) {
+                *delta = value as f32;
+            }
+        }
+        Some(indices) => {
+            for (&index, &value) in indices.iter().zip(values) {
+                if let Some(delta) = deltas.get_mut(index as usize) {
+                    *delta = value as f32;
+                }
+            }
+        }
+    }
+    deltas
+}
+
+/// Extracts the `cvar` table from the provided data stream.
+///
+/// `axis_count` comes from `fvar`.
+pub fn read_cvar(data: &mut FontDataStream, axis_count: usize) -> Result<CvarTable, Error> {
+    let base = data.substream(data.position())?;
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let variations = read_tuple_variations(&mut base.substream(4)?, &base, axis_count, &[], 1)?;
+    Ok(CvarTable {
+        major_version,
+        minor_version,
+        variations,
+    })
+}
+
+/// Serializes the `cvar` table. Peaks are always embedded, as `cvar` has no
+/// shared tuples.
+pub fn write_cvar(writer: &mut FontDataWriter, cvar: &CvarTable) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(cvar.major_version);
+    writer.write_u16(cvar.minor_version);
+    write_tuple_variations(writer, base, &cvar.variations, &[])
+}
@@ -0,0 +1,49 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `cvt ` (Control Value Table) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::cvar::CvarTable;
+
+/// Control value table: values in font units referenced by TrueType instructions.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CvtTable {
+    /// Control values (FWord).
+    pub values: Vec<i16>,
+}
+
+impl CvtTable {
+    /// Returns the control values at normalized coordinates `coords`, with the
+    /// `cvar` deltas applied. The values are left unrounded for the hinting
+    /// engine to scale.
+    pub fn cvt_at(&self, cvar: &CvarTable, coords: &[f32]) -> Vec<f32> {
+        self.values
+            .iter()
+            .zip(cvar.deltas(self.values.len(), coords))
+            .map(|(&value, delta)| value as f32 + delta)
+            .collect()
+    }
+}
+
+/// Extracts the `cvt ` table from the provided data stream. The number of
+/// values is implied by the table length.
+pub fn read_cvt(data: &mut FontDataStream) -> Result<CvtTable, Error> {
+    let values = (0..data.remaining_bytes().len() / 2)
+        .map(|_| data.read_i16())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CvtTable { values })
+}
+
+/// Serializes the `cvt ` table.
+pub fn write_cvt(writer: &mut FontDataWriter, cvt: &CvtTable) {
+    for &value in &cvt.values {
+        writer.write_i16(value);
+    }
+}
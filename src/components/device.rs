@@ -0,0 +1,95 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Size-specific rendering guidance for the Aurora Font Library. `gasp`,
+//! `hdmx`, `VDMX` and `LTSH` each describe how a font behaves at particular
+//! pixel sizes; they are combined here into the hints for one size.
+
+use super::gasp::{
+    GASP_DOGRAY, GASP_GRIDFIT, GASP_SYMMETRIC_GRIDFIT, GASP_SYMMETRIC_SMOOTHING, GaspTable,
+};
+use super::hdmx::HdmxTable;
+use super::ltsh::LtshTable;
+use super::vdmx::VdmxTable;
+
+/// The device-metric tables of a font; each is optional.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeviceMetrics {
+    /// Grid-fitting and scan-conversion procedure table.
+    pub gasp: Option<GaspTable>,
+    /// Horizontal device metrics table.
+    pub hdmx: Option<HdmxTable>,
+    /// Vertical device metrics table.
+    pub vdmx: Option<VdmxTable>,
+    /// Linear threshold table.
+    pub ltsh: Option<LtshTable>,
+}
+
+/// How to render a font at one size.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RenderingHints {
+    /// Pixels per em the hints are for.
+    pub ppem: u16,
+    /// Whether outlines should be grid-fitted (hinted).
+    pub gridfit: bool,
+    /// Whether outlines should be anti-aliased.
+    pub grayscale: bool,
+    /// Whether ClearType grid-fitting should be symmetric.
+    pub symmetric_gridfit: bool,
+    /// Whether ClearType should smooth along both axes.
+    pub symmetric_smoothing: bool,
+    /// Hinted advance width of each glyph in pixels, from `hdmx`.
+    pub device_widths: Option<Vec<u8>>,
+    /// Hinted `(y_max, y_min)` of the font in pixels for square pixels,
+    /// from `VDMX`.
+    pub vertical_bounds: Option<(i16, i16)>,
+    /// Whether each glyph's hinted advance scales linearly at this size,
+    /// from `LTSH`; empty without it.
+    pub linear: Vec<bool>,
+}
+
+impl RenderingHints {
+    /// Returns the hinted advance width of a glyph in pixels, if the font
+    /// records one for this size.
+    pub fn device_width(&self, glyph_id: u16) -> Option<u8> {
+        self.device_widths.as_ref()?.get(glyph_id as usize).copied()
+    }
+
+    /// Returns whether a glyph's advance can be scaled linearly instead of
+    /// hinted. Without `LTSH` no glyph is known to be linear.
+    pub fn is_linear(&self, glyph_id: u16) -> bool {
+        self.linear.get(glyph_id as usize).copied().unwrap_or(false)
+    }
+}
+
+impl DeviceMetrics {
+    /// Returns the rendering hints for `ppem` pixels per em. Without `gasp`,
+    /// outlines are hinted and anti-aliased at every size.
+    pub fn rendering_hints(&self, ppem: u16) -> RenderingHints {
+        let behavior = self
+            .gasp
+            .as_ref()
+            .map_or(GASP_GRIDFIT | GASP_DOGRAY, |gasp| gasp.behavior(ppem));
+        RenderingHints {
+            ppem,
+            gridfit: behavior & GASP_GRIDFIT != 0,
+            grayscale: behavior & GASP_DOGRAY != 0,
+            symmetric_gridfit: behavior & GASP_SYMMETRIC_GRIDFIT != 0,
+            symmetric_smoothing: behavior & GASP_SYMMETRIC_SMOOTHING != 0,
+            device_widths: self
+                .hdmx
+                .as_ref()
+                .and_then(|hdmx| hdmx.record(ppem))
+                .map(|record| record.widths.clone()),
+            vertical_bounds: self.vdmx.as_ref().and_then(|vdmx| vdmx.bounds(ppem, 1, 1)),
+            linear: self.ltsh.as_ref().map_or(Vec::new(), |ltsh| {
+                (0..ltsh.y_pels.len())
+                    .map(|glyph_id| ltsh.is_linear(glyph_id as u16, ppem))
+                    .collect()
+            }),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `DSIG` (Digital Signature) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Permission flag: the font may not be resigned.
+pub const DSIG_CANNOT_BE_RESIGNED: u16 = 0x0001;
+
+/// Digital signature table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DsigTable {
+    /// Table version (1).
+    pub version: u32,
+    /// Permission flags (`DSIG_CANNOT_BE_RESIGNED`).
+    pub flags: u16,
+    /// Signatures in file order.
+    pub signatures: Vec<DsigSignature>,
+}
+
+/// A signature block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DsigSignature {
+    /// Format of the signature block (1).
+    pub format: u32,
+    /// PKCS#7 packet, DER-encoded.
+    pub signature: Vec<u8>,
+}
+
+impl DsigTable {
+    /// Returns an unsigned table, as left in a font whose signature was
+    /// removed after editing.
+    pub fn unsigned() -> Self {
+        DsigTable {
+            version: 1,
+            flags: 0,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the table holds at least one signature. Any
+    /// signature goes stale once the font data is changed.
+    pub fn is_signed(&self) -> bool {
+        !self.signatures.is_empty()
+    }
+}
+
+/// Extracts the `DSIG` table from the provided data stream.
+pub fn read_dsig(data: &mut FontDataStream) -> Result<DsigTable, Error> {
+    let version = data.read_u32()?;
+    if version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version)));
+    }
+    let count = data.read_u16()?;
+    let flags = data.read_u16()?;
+    let signatures = (0..count)
+        .map(|_| {
+            let format = data.read_u32()?;
+            let length = data.read_u32()? as usize;
+            let offset = data.read_u32()? as usize;
+            if format != 1 {
+                return Err(Error::InvalidFormat);
+            }
+            // Signature block: two reserved words, then the PKCS#7 length.
+            let block = FontDataStream::new(data.slice_at(offset, length)?);
+            let signature_length = block.read_at_u32(4)? as usize;
+            Ok(DsigSignature {
+                format,
+                signature: block.slice_at(8, signature_length)?.to_vec(),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(DsigTable {
+        version,
+        flags,
+        signatures,
+    })
+}
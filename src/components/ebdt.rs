@@ -0,0 +1,399 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `EBDT` (Embedded Bitmap Data) table representation and utilities for the Aurora Font Library.
+//!
+//! The image decoding here is shared with `CBDT`, which adds the PNG image formats 17-19.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+use super::eblc::{
+    BigGlyphMetrics, BitmapSize, EblcTable, GlyphLocation, SmallGlyphMetrics, read_big_metrics,
+    read_small_metrics,
+};
+use super::ebsc::EbscTable;
+
+/// Maximum nesting of component bitmaps.
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+/// Metrics stored with a glyph image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphMetrics {
+    /// Metrics for the direction of the strike.
+    Small(SmallGlyphMetrics),
+    /// Metrics for both directions.
+    Big(BigGlyphMetrics),
+}
+
+/// A glyph placed in a component bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EbdtComponent {
+    /// Component glyph, drawn from the same strike.
+    pub glyph_id: u16,
+    /// Column of the component's left edge within the composite bitmap.
+    pub x_offset: i8,
+    /// Row of the component's top edge within the composite bitmap.
+    pub y_offset: i8,
+}
+
+/// Image data of a glyph image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlyphImageData {
+    /// Rows of pixels, each padded to a byte boundary.
+    ByteAligned(Vec<u8>),
+    /// Rows of pixels packed without padding.
+    BitAligned(Vec<u8>),
+    /// Glyphs combined into one bitmap.
+    Composite(Vec<EbdtComponent>),
+    /// A PNG file.
+    Png(Vec<u8>),
+}
+
+/// A glyph image as stored in the data table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphImage {
+    /// Metrics stored with the image; `None` when the location table holds them.
+    pub metrics: Option<GlyphMetrics>,
+    /// Image data.
+    pub data: GlyphImageData,
+}
+
+/// Decoded bitmap data of a glyph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitmapData {
+    /// A PNG file to be decoded by the caller.
+    Png(Vec<u8>),
+    /// Rows of pixels from top to bottom. Strikes of up to 8 bits per pixel
+    /// hold one byte per pixel in `0..1 << bit_depth`; 32-bit strikes hold
+    /// premultiplied BGRA pixels.
+    Pixels(Vec<u8>),
+}
+
+/// A glyph bitmap resolved by [`EbdtTable::bitmap_glyph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitmapGlyph {
+    /// Horizontal pixels per em of the strike the bitmap comes from.
+    pub ppem_x: u8,
+    /// Vertical pixels per em of the strike the bitmap comes from.
+    pub ppem_y: u8,
+    /// Factor from the strike size to the requested size; metrics and pixels
+    /// are those of the strike and should be scaled by it.
+    pub scale: f32,
+    /// Bits per pixel of the strike.
+    pub bit_depth: u8,
+    /// Glyph metrics.
+    pub metrics: BigGlyphMetrics,
+    /// Bitmap data.
+    pub data: BitmapData,
+}
+
+/// Embedded bitmap data table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EbdtTable {
+    /// Major version: 2 for `EBDT`, 3 for `CBDT` (2 in early color fonts).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Raw table bytes, header included; image offsets of the location table
+    /// are relative to their start.
+    pub data: Vec<u8>,
+}
+
+impl EbdtTable {
+    /// Reads the glyph image at `location`.
+    pub fn image(&self, location: &GlyphLocation) -> Result<GlyphImage, Error> {
+        let table = FontDataStream::new(&self.data);
+        let mut image = table.substream(location.offset as usize)?;
+        read_glyph_image(&mut image, location.image_format, location.length as usize)
+    }
+
+    /// Returns the bitmap of `glyph_id` for `ppem` pixels per em, or `None`
+    /// if no strike can draw it.
+    ///
+    /// A strike of exactly `ppem` is preferred, then the strike named by the
+    /// `EBSC` scaling record for `ppem`. Color tables fall back to the nearest
+    /// strike, as color glyphs have no outlines to fall back to.
+    pub fn bitmap_glyph(
+        &self,
+        eblc: &EblcTable,
+        ebsc: Option<&EbscTable>,
+        glyph_id: u16,
+        ppem: u8,
+    ) -> Result<Option<BitmapGlyph>, Error> {
+        let strike = eblc
+            .strike(glyph_id, ppem)
+            .or_else(|| {
+                let scale = ebsc?.scale(ppem)?;
+                eblc.strike(glyph_id, scale.substitute_ppem_y)
+            })
+            .or_else(|| {
+                eblc.color
+                    .then(|| eblc.nearest_strike(glyph_id, ppem))
+                    .flatten()
+            });
+        let Some(strike) = strike else {
+            return Ok(None);
+        };
+        let Some(location) = strike.location(glyph_id) else {
+            return Ok(None);
+        };
+        let (metrics, data) = self.render(strike, &location, 0)?;
+        Ok(Some(BitmapGlyph {
+            ppem_x: strike.ppem_x,
+            ppem_y: strike.ppem_y,
+            scale: ppem as f32 / strike.ppem_y.max(1) as f32,
+            bit_depth: strike.bit_depth,
+            metrics,
+            data,
+        }))
+    }
+
+    /// Decodes the image at `location`, composing component bitmaps.
+    fn render(
+        &self,
+        strike: &BitmapSize,
+        location: &GlyphLocation,
+        depth: usize,
+    ) -> Result<(BigGlyphMetrics, BitmapData), Error> {
+        let image = self.image(location)?;
+        let metrics = match image.metrics {
+            Some(GlyphMetrics::Big(metrics)) => metrics,
+            Some(GlyphMetrics::Small(metrics)) => metrics.to_big(strike.flags),
+            None => location.metrics.ok_or(Error::Io(IoError::InvalidData))?,
+        };
+        let (width, height) = (metrics.width as usize, metrics.height as usize);
+        let data = match image.data {
+            GlyphImageData::Png(png) => BitmapData::Png(png),
+            GlyphImageData::ByteAligned(bits) => {
+                BitmapData::Pixels(decode_pixels(&bits, width, height, strike.bit_depth, true)?)
+            }
+            GlyphImageData::BitAligned(bits) => BitmapData::Pixels(decode_pixels(
+                &bits,
+                width,
+                height,
+                strike.bit_depth,
+                false,
+            )?),
+            GlyphImageData::Composite(components) => {
+                if depth >= MAX_COMPONENT_DEPTH {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                let pixel_size = pixel_size(strike.bit_depth)?;
+                let mut pixels = vec![0u8; width * height * pixel_size];
+                for component in components {
+                    let location = strike
+                        .location(component.glyph_id)
+                        .ok_or(Error::Io(IoError::InvalidData))?;
+                    let (part, BitmapData::Pixels(part_pixels)) =
+                        self.render(strike, &location, depth + 1)?
+                    else {
+                        return Err(Error::Io(IoError::InvalidData));
+                    };
+                    compose(
+                        &mut pixels,
+                        (width, height),
+                        &part_pixels,
+                        (part.width as usize, part.height as usize),
+                        (component.x_offset as isize, component.y_offset as isize),
+                        pixel_size,
+                    );
+                }
+                BitmapData::Pixels(pixels)
+            }
+        };
+        Ok((metrics, data))
+    }
+}
+
+/// Returns the bytes per decoded pixel of a strike bit depth.
+fn pixel_size(bit_depth: u8) -> Result<usize, Error> {
+    match bit_depth {
+        1 | 2 | 4 | 8 => Ok(1),
+        32 => Ok(4),
+        _ => Err(Error::Io(IoError::InvalidData)),
+    }
+}
+
+/// Unpacks `height` rows of `width` pixels, most significant bits first.
+fn decode_pixels(
+    bits: &[u8],
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    byte_aligned: bool,
+) -> Result<Vec<u8>, Error> {
+    let depth = bit_depth as usize;
+    if pixel_size(bit_depth)? == 4 {
+        let size = width * height * 4;
+        return bits
+            .get(..size)
+            .map(<[u8]>::to_vec)
+            .ok_or(Error::Io(IoError::InvalidData));
+    }
+    let row_bits = width * depth;
+    let stride = if byte_aligned {
+        row_bits.div_ceil(8) * 8
+    } else {
+        row_bits
+    };
+    if bits.len() * 8 < stride * height {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    let mask = ((1u16 << depth) - 1) as u8;
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let bit = y * stride + x * depth;
+            let shift = 8 - depth - bit % 8;
+            pixels.push((bits[bit / 8] >> shift) & mask);
+        }
+    }
+    Ok(pixels)
+}
+
+/// Draws a component bitmap onto a composite at `offset`. Gray levels are
+/// merged by taking the darker one; BGRA pixels are composed source-over.
+fn compose(
+    target: &mut [u8],
+    (width, height): (usize, usize),
+    source: &[u8],
+    (source_width, source_height): (usize, usize),
+    (x_offset, y_offset): (isize, isize),
+    pixel_size: usize,
+) {
+    for sy in 0..source_height {
+        let ty = y_offset + sy as isize;
+        if ty < 0 || ty >= height as isize {
+            continue;
+        }
+        for sx in 0..source_width {
+            let tx = x_offset + sx as isize;
+            if tx < 0 || tx >= width as isize {
+                continue;
+            }
+            let from = (sy * source_width + sx) * pixel_size;
+            let to = (ty as usize * width + tx as usize) * pixel_size;
+            let pixel = &source[from..from + pixel_size];
+            let under = &mut target[to..to + pixel_size];
+            if pixel_size == 1 {
+                under[0] = under[0].max(pixel[0]);
+            } else {
+                let inverse_alpha = 255 - pixel[3] as u16;
+                for (under, &over) in under.iter_mut().zip(pixel) {
+                    *under = (over as u16 + (*under as u16 * inverse_alpha + 127) / 255) as u8;
+                }
+            }
+        }
+    }
+}
+
+/// Reads the components of a component bitmap.
+fn read_components(data: &mut FontDataStream) -> Result<Vec<EbdtComponent>, Error> {
+    let count = data.read_u16()?;
+    (0..count)
+        .map(|_| {
+            Ok(EbdtComponent {
+                glyph_id: data.read_u16()?,
+                x_offset: data.read_i8()?,
+                y_offset: data.read_i8()?,
+            })
+        })
+        .collect()
+}
+
+/// Reads a glyph image of `length` bytes in `image_format` from the
+/// provided data stream. Formats 1-2 and 5-9 are `EBDT` formats; 17-19 are
+/// the `CBDT` PNG formats.
+pub fn read_glyph_image(
+    data: &mut FontDataStream,
+    image_format: u16,
+    length: usize,
+) -> Result<GlyphImage, Error> {
+    let bytes = |data: &mut FontDataStream, header: usize| -> Result<Vec<u8>, Error> {
+        let size = length
+            .checked_sub(header)
+            .ok_or(Error::Io(IoError::InvalidData))?;
+        Ok(data.read_bytes(size)?.to_vec())
+    };
+    let png = |data: &mut FontDataStream| -> Result<GlyphImageData, Error> {
+        let size = data.read_u32()? as usize;
+        Ok(GlyphImageData::Png(data.read_bytes(size)?.to_vec()))
+    };
+    let image = match image_format {
+        1 => GlyphImage {
+            metrics: Some(GlyphMetrics::Small(read_small_metrics(data)?)),
+            data: GlyphImageData::ByteAligned(bytes(data, 5)?),
+        },
+        2 => GlyphImage {
+            metrics: Some(GlyphMetrics::Small(read_small_metrics(data)?)),
+            data: GlyphImageData::BitAligned(bytes(data, 5)?),
+        },
+        5 => GlyphImage {
+            metrics: None,
+            data: GlyphImageData::BitAligned(bytes(data, 0)?),
+        },
+        6 => GlyphImage {
+            metrics: Some(GlyphMetrics::Big(read_big_metrics(data)?)),
+            data: GlyphImageData::ByteAligned(bytes(data, 8)?),
+        },
+        7 => GlyphImage {
+            metrics: Some(GlyphMetrics::Big(read_big_metrics(data)?)),
+            data: GlyphImageData::BitAligned(bytes(data, 8)?),
+        },
+        8 => {
+            let metrics = read_small_metrics(data)?;
+            // One padding byte.
+            data.read_u8()?;
+            GlyphImage {
+                metrics: Some(GlyphMetrics::Small(metrics)),
+                data: GlyphImageData::Composite(read_components(data)?),
+            }
+        }
+        9 => GlyphImage {
+            metrics: Some(GlyphMetrics::Big(read_big_metrics(data)?)),
+            data: GlyphImageData::Composite(read_components(data)?),
+        },
+        17 => GlyphImage {
+            metrics: Some(GlyphMetrics::Small(read_small_metrics(data)?)),
+            data: png(data)?,
+        },
+        18 => GlyphImage {
+            metrics: Some(GlyphMetrics::Big(read_big_metrics(data)?)),
+            data: png(data)?,
+        },
+        19 => GlyphImage {
+            metrics: None,
+            data: png(data)?,
+        },
+        // Format 3 is obsolete and format 4 is Apple's compressed format.
+        _ => return Err(Error::InvalidFormat),
+    };
+    Ok(image)
+}
+
+/// Reads an `EBDT` or `CBDT` table with one of the given major versions.
+pub(crate) fn read_data_table(
+    data: &mut FontDataStream,
+    major_versions: &[u16],
+) -> Result<EbdtTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if !major_versions.contains(&major_version) {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    data.reset();
+    Ok(EbdtTable {
+        major_version,
+        minor_version,
+        data: data.remaining_bytes().to_vec(),
+    })
+}
+
+/// Extracts the `EBDT` table from the provided data stream.
+pub fn read_ebdt(data: &mut FontDataStream) -> Result<EbdtTable, Error> {
+    read_data_table(data, &[2])
+}
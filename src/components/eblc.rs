@@ -0,0 +1,462 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `EBLC` (Embedded Bitmap Location) table representation and utilities for the Aurora Font Library.
+//!
+//! The structures here are shared with `CBLC`, which differs only in its version.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Small glyph metrics in a strike describe horizontal text.
+pub const HORIZONTAL_METRICS: i8 = 0x01;
+/// Small glyph metrics in a strike describe vertical text.
+pub const VERTICAL_METRICS: i8 = 0x02;
+
+/// Line metrics of a strike in one direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SbitLineMetrics {
+    /// Distance from the baseline to the top of the line.
+    pub ascender: i8,
+    /// Distance from the baseline to the bottom of the line, negative below it.
+    pub descender: i8,
+    /// Widest glyph bitmap.
+    pub width_max: u8,
+    /// Rise of the caret slope.
+    pub caret_slope_numerator: i8,
+    /// Run of the caret slope.
+    pub caret_slope_denominator: i8,
+    /// Caret shift for slanted fonts.
+    pub caret_offset: i8,
+    /// Smallest origin side bearing.
+    pub min_origin_sb: i8,
+    /// Smallest advance side bearing.
+    pub min_advance_sb: i8,
+    /// Largest extent before the baseline.
+    pub max_before_bl: i8,
+    /// Smallest extent after the baseline.
+    pub min_after_bl: i8,
+}
+
+/// Metrics of a glyph bitmap for both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BigGlyphMetrics {
+    /// Bitmap height in pixels.
+    pub height: u8,
+    /// Bitmap width in pixels.
+    pub width: u8,
+    /// Distance from the horizontal origin to the left edge of the bitmap.
+    pub hori_bearing_x: i8,
+    /// Distance from the horizontal origin to the top edge of the bitmap.
+    pub hori_bearing_y: i8,
+    /// Horizontal advance.
+    pub hori_advance: u8,
+    /// Distance from the vertical origin to the left edge of the bitmap.
+    pub vert_bearing_x: i8,
+    /// Distance from the vertical origin to the top edge of the bitmap.
+    pub vert_bearing_y: i8,
+    /// Vertical advance.
+    pub vert_advance: u8,
+}
+
+/// Metrics of a glyph bitmap for the direction given by the strike flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SmallGlyphMetrics {
+    /// Bitmap height in pixels.
+    pub height: u8,
+    /// Bitmap width in pixels.
+    pub width: u8,
+    /// Distance from the origin to the left edge of the bitmap.
+    pub bearing_x: i8,
+    /// Distance from the origin to the top edge of the bitmap.
+    pub bearing_y: i8,
+    /// Advance.
+    pub advance: u8,
+}
+
+impl SmallGlyphMetrics {
+    /// Expands the metrics to big metrics, filling the direction selected by
+    /// the strike `flags` (horizontal unless only `VERTICAL_METRICS` is set).
+    pub fn to_big(self, flags: i8) -> BigGlyphMetrics {
+        let mut big = BigGlyphMetrics {
+            height: self.height,
+            width: self.width,
+            ..Default::default()
+        };
+        if flags & (HORIZONTAL_METRICS | VERTICAL_METRICS) == VERTICAL_METRICS {
+            big.vert_bearing_x = self.bearing_x;
+            big.vert_bearing_y = self.bearing_y;
+            big.vert_advance = self.advance;
+        } else {
+            big.hori_bearing_x = self.bearing_x;
+            big.hori_bearing_y = self.bearing_y;
+            big.hori_advance = self.advance;
+        }
+        big
+    }
+}
+
+/// Glyph locations of an index subtable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexSubTableData {
+    /// Format 1: 32-bit offsets of every glyph in the range, plus an end offset.
+    Format1(Vec<u32>),
+    /// Format 2: every glyph in the range has the same size and metrics.
+    Format2 {
+        /// Size of every image in bytes.
+        image_size: u32,
+        /// Metrics of every glyph.
+        metrics: BigGlyphMetrics,
+    },
+    /// Format 3: 16-bit offsets of every glyph in the range, plus an end offset.
+    Format3(Vec<u16>),
+    /// Format 4: sparse `(glyph_id, offset)` pairs, ending with a sentinel pair
+    /// whose offset ends the last image.
+    Format4(Vec<(u16, u16)>),
+    /// Format 5: sparse glyphs of the same size and metrics.
+    Format5 {
+        /// Size of every image in bytes.
+        image_size: u32,
+        /// Metrics of every glyph.
+        metrics: BigGlyphMetrics,
+        /// Sorted glyphs with an image, in image order.
+        glyph_ids: Vec<u16>,
+    },
+}
+
+/// Location of a glyph image in the data table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphLocation {
+    /// Offset of the image from the start of the data table.
+    pub offset: u32,
+    /// Length of the image in bytes.
+    pub length: u32,
+    /// Image format in the data table.
+    pub image_format: u16,
+    /// Metrics stored in the location table, for image formats without their own.
+    pub metrics: Option<BigGlyphMetrics>,
+}
+
+/// Image locations of a range of glyphs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexSubTable {
+    /// First glyph of the range.
+    pub first_glyph_index: u16,
+    /// Last glyph of the range, inclusive.
+    pub last_glyph_index: u16,
+    /// Format of the images in the data table.
+    pub image_format: u16,
+    /// Offset of the first image from the start of the data table.
+    pub image_data_offset: u32,
+    /// Glyph locations.
+    pub data: IndexSubTableData,
+}
+
+impl IndexSubTable {
+    /// Returns the index subtable format.
+    pub fn index_format(&self) -> u16 {
+        match self.data {
+            IndexSubTableData::Format1(_) => 1,
+            IndexSubTableData::Format2 { .. } => 2,
+            IndexSubTableData::Format3(_) => 3,
+            IndexSubTableData::Format4(_) => 4,
+            IndexSubTableData::Format5 { .. } => 5,
+        }
+    }
+
+    /// Returns the image location of `glyph_id`, or `None` if the subtable
+    /// has no image for it.
+    pub fn location(&self, glyph_id: u16) -> Option<GlyphLocation> {
+        if glyph_id < self.first_glyph_index || glyph_id > self.last_glyph_index {
+            return None;
+        }
+        let index = (glyph_id - self.first_glyph_index) as usize;
+        let (start, end, metrics) = match &self.data {
+            IndexSubTableData::Format1(offsets) => {
+                (*offsets.get(index)?, *offsets.get(index + 1)?, None)
+            }
+            IndexSubTableData::Format3(offsets) => (
+                *offsets.get(index)? as u32,
+                *offsets.get(index + 1)? as u32,
+                None,
+            ),
+            IndexSubTableData::Format2 {
+                image_size,
+                metrics,
+            } => {
+                let start = image_size.checked_mul(index as u32)?;
+                (start, start.checked_add(*image_size)?, Some(*metrics))
+            }
+            IndexSubTableData::Format4(pairs) => {
+                let position = pairs
+                    .iter()
+                    .take(pairs.len().saturating_sub(1))
+                    .position(|&(id, _)| id == glyph_id)?;
+                (pairs[position].1 as u32, pairs[position + 1].1 as u32, None)
+            }
+            IndexSubTableData::Format5 {
+                image_size,
+                metrics,
+                glyph_ids,
+            } => {
+                let position = glyph_ids.binary_search(&glyph_id).ok()? as u32;
+                let start = image_size.checked_mul(position)?;
+                (start, start.checked_add(*image_size)?, Some(*metrics))
+            }
+        };
+        if end <= start {
+            return None;
+        }
+        Some(GlyphLocation {
+            offset: self.image_data_offset.checked_add(start)?,
+            length: end - start,
+            image_format: self.image_format,
+            metrics,
+        })
+    }
+}
+
+/// A bitmap strike: the bitmaps of a range of glyphs at one size.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BitmapSize {
+    /// Not used; always 0.
+    pub color_ref: u32,
+    /// Horizontal line metrics.
+    pub hori: SbitLineMetrics,
+    /// Vertical line metrics.
+    pub vert: SbitLineMetrics,
+    /// Lowest glyph id with a bitmap in the strike.
+    pub start_glyph_index: u16,
+    /// Highest glyph id with a bitmap in the strike.
+    pub end_glyph_index: u16,
+    /// Horizontal pixels per em.
+    pub ppem_x: u8,
+    /// Vertical pixels per em.
+    pub ppem_y: u8,
+    /// Bits per pixel: 1, 2, 4 or 8, or 32 for color bitmaps.
+    pub bit_depth: u8,
+    /// `HORIZONTAL_METRICS` and `VERTICAL_METRICS` flags.
+    pub flags: i8,
+    /// Index subtables, sorted by glyph range.
+    pub index_subtables: Vec<IndexSubTable>,
+}
+
+impl BitmapSize {
+    /// Returns the image location of `glyph_id` in the strike.
+    pub fn location(&self, glyph_id: u16) -> Option<GlyphLocation> {
+        if glyph_id < self.start_glyph_index || glyph_id > self.end_glyph_index {
+            return None;
+        }
+        self.index_subtables
+            .iter()
+            .find_map(|subtable| subtable.location(glyph_id))
+    }
+}
+
+/// Embedded bitmap location table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EblcTable {
+    /// Major version: 2 for `EBLC`, 3 for `CBLC` (2 in early color fonts).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Bitmap strikes.
+    pub sizes: Vec<BitmapSize>,
+    /// `true` for a `CBLC` table.
+    pub color: bool,
+}
+
+impl EblcTable {
+    /// Returns the strike with the given vertical ppem that has an image for
+    /// `glyph_id`.
+    pub fn strike(&self, glyph_id: u16, ppem: u8) -> Option<&BitmapSize> {
+        self.sizes
+            .iter()
+            .find(|size| size.ppem_y == ppem && size.location(glyph_id).is_some())
+    }
+
+    /// Returns the strike best suited for `ppem` among those with an image for
+    /// `glyph_id`: the smallest strike at least as large, or the largest one
+    /// when all are smaller.
+    pub fn nearest_strike(&self, glyph_id: u16, ppem: u8) -> Option<&BitmapSize> {
+        let covering = || {
+            self.sizes
+                .iter()
+                .filter(move |size| size.location(glyph_id).is_some())
+        };
+        let larger = covering()
+            .filter(|size| size.ppem_y >= ppem)
+            .min_by_key(|size| size.ppem_y);
+        larger.or_else(|| covering().max_by_key(|size| size.ppem_y))
+    }
+}
+
+/// Reads the line metrics of a strike.
+pub(crate) fn read_line_metrics(data: &mut FontDataStream) -> Result<SbitLineMetrics, Error> {
+    let metrics = SbitLineMetrics {
+        ascender: data.read_i8()?,
+        descender: data.read_i8()?,
+        width_max: data.read_u8()?,
+        caret_slope_numerator: data.read_i8()?,
+        caret_slope_denominator: data.read_i8()?,
+        caret_offset: data.read_i8()?,
+        min_origin_sb: data.read_i8()?,
+        min_advance_sb: data.read_i8()?,
+        max_before_bl: data.read_i8()?,
+        min_after_bl: data.read_i8()?,
+    };
+    // Two padding bytes.
+    data.read_u16()?;
+    Ok(metrics)
+}
+
+/// Reads big glyph metrics.
+pub(crate) fn read_big_metrics(data: &mut FontDataStream) -> Result<BigGlyphMetrics, Error> {
+    Ok(BigGlyphMetrics {
+        height: data.read_u8()?,
+        width: data.read_u8()?,
+        hori_bearing_x: data.read_i8()?,
+        hori_bearing_y: data.read_i8()?,
+        hori_advance: data.read_u8()?,
+        vert_bearing_x: data.read_i8()?,
+        vert_bearing_y: data.read_i8()?,
+        vert_advance: data.read_u8()?,
+    })
+}
+
+/// Reads small glyph metrics.
+pub(crate) fn read_small_metrics(data: &mut FontDataStream) -> Result<SmallGlyphMetrics, Error> {
+    Ok(SmallGlyphMetrics {
+        height: data.read_u8()?,
+        width: data.read_u8()?,
+        bearing_x: data.read_i8()?,
+        bearing_y: data.read_i8()?,
+        advance: data.read_u8()?,
+    })
+}
+
+/// Reads an index subtable covering `first..=last`.
+fn read_index_subtable(
+    data: &mut FontDataStream,
+    first_glyph_index: u16,
+    last_glyph_index: u16,
+) -> Result<IndexSubTable, Error> {
+    if last_glyph_index < first_glyph_index {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    let index_format = data.read_u16()?;
+    let image_format = data.read_u16()?;
+    let image_data_offset = data.read_u32()?;
+    let count = (last_glyph_index - first_glyph_index) as usize + 1;
+    let data = match index_format {
+        1 => IndexSubTableData::Format1(
+            (0..=count)
+                .map(|_| data.read_u32())
+                .collect::<Result<_, _>>()?,
+        ),
+        2 => IndexSubTableData::Format2 {
+            image_size: data.read_u32()?,
+            metrics: read_big_metrics(data)?,
+        },
+        3 => IndexSubTableData::Format3(
+            (0..=count)
+                .map(|_| data.read_u16())
+                .collect::<Result<_, _>>()?,
+        ),
+        4 => {
+            let num_glyphs = data.read_u32()? as usize;
+            let mut pairs = Vec::with_capacity(num_glyphs.min(count) + 1);
+            for _ in 0..=num_glyphs {
+                pairs.push((data.read_u16()?, data.read_u16()?));
+            }
+            IndexSubTableData::Format4(pairs)
+        }
+        5 => {
+            let image_size = data.read_u32()?;
+            let metrics = read_big_metrics(data)?;
+            let num_glyphs = data.read_u32()? as usize;
+            let glyph_ids = (0..num_glyphs)
+                .map(|_| data.read_u16())
+                .collect::<Result<Vec<_>, _>>()?;
+            IndexSubTableData::Format5 {
+                image_size,
+                metrics,
+                glyph_ids,
+            }
+        }
+        _ => return Err(Error::Io(IoError::InvalidData)),
+    };
+    Ok(IndexSubTable {
+        first_glyph_index,
+        last_glyph_index,
+        image_format,
+        image_data_offset,
+        data,
+    })
+}
+
+/// Reads an `EBLC` or `CBLC` table with one of the given major versions.
+pub(crate) fn read_location_table(
+    data: &mut FontDataStream,
+    major_versions: &[u16],
+    color: bool,
+) -> Result<EblcTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if !major_versions.contains(&major_version) {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let num_sizes = data.read_u32()?;
+
+    let mut sizes = Vec::with_capacity(num_sizes.min(256) as usize);
+    for _ in 0..num_sizes {
+        let array_offset = data.read_u32()? as usize;
+        let _index_tables_size = data.read_u32()?;
+        let subtable_count = data.read_u32()?;
+        let color_ref = data.read_u32()?;
+        let hori = read_line_metrics(data)?;
+        let vert = read_line_metrics(data)?;
+        let start_glyph_index = data.read_u16()?;
+        let end_glyph_index = data.read_u16()?;
+        let ppem_x = data.read_u8()?;
+        let ppem_y = data.read_u8()?;
+        let bit_depth = data.read_u8()?;
+        let flags = data.read_i8()?;
+
+        let mut array = data.substream(array_offset)?;
+        let mut index_subtables = Vec::with_capacity(subtable_count.min(1024) as usize);
+        for _ in 0..subtable_count {
+            let first = array.read_u16()?;
+            let last = array.read_u16()?;
+            let offset = array.read_u32()? as usize;
+            let mut subtable = data.substream(array_offset + offset)?;
+            index_subtables.push(read_index_subtable(&mut subtable, first, last)?);
+        }
+        sizes.push(BitmapSize {
+            color_ref,
+            hori,
+            vert,
+            start_glyph_index,
+            end_glyph_index,
+            ppem_x,
+            ppem_y,
+            bit_depth,
+            flags,
+            index_subtables,
+        });
+    }
+    Ok(EblcTable {
+        major_version,
+        minor_version,
+        sizes,
+        color,
+    })
+}
+
+/// Extracts the `EBLC` table from the provided data stream.
+pub fn read_eblc(data: &mut FontDataStream) -> Result<EblcTable, Error> {
+    read_location_table(data, &[2], false)
+}
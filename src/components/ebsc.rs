@@ -0,0 +1,73 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `EBSC` (Embedded Bitmap Scaling) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+use super::eblc::{SbitLineMetrics, read_line_metrics};
+
+/// A size without a strike of its own, drawn by scaling another strike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BitmapScale {
+    /// Horizontal line metrics at the target size.
+    pub hori: SbitLineMetrics,
+    /// Vertical line metrics at the target size.
+    pub vert: SbitLineMetrics,
+    /// Target horizontal pixels per em.
+    pub ppem_x: u8,
+    /// Target vertical pixels per em.
+    pub ppem_y: u8,
+    /// Horizontal pixels per em of the strike to scale.
+    pub substitute_ppem_x: u8,
+    /// Vertical pixels per em of the strike to scale.
+    pub substitute_ppem_y: u8,
+}
+
+/// Embedded bitmap scaling table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EbscTable {
+    /// Major version (2).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Scaled sizes.
+    pub scales: Vec<BitmapScale>,
+}
+
+impl EbscTable {
+    /// Returns the scaling record for the vertical ppem `ppem`.
+    pub fn scale(&self, ppem: u8) -> Option<&BitmapScale> {
+        self.scales.iter().find(|scale| scale.ppem_y == ppem)
+    }
+}
+
+/// Extracts the `EBSC` table from the provided data stream.
+pub fn read_ebsc(data: &mut FontDataStream) -> Result<EbscTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 2 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let num_sizes = data.read_u32()?;
+    let mut scales = Vec::with_capacity(num_sizes.min(256) as usize);
+    for _ in 0..num_sizes {
+        scales.push(BitmapScale {
+            hori: read_line_metrics(data)?,
+            vert: read_line_metrics(data)?,
+            ppem_x: data.read_u8()?,
+            ppem_y: data.read_u8()?,
+            substitute_ppem_x: data.read_u8()?,
+            substitute_ppem_y: data.read_u8()?,
+        });
+    }
+    Ok(EbscTable {
+        major_version,
+        minor_version,
+        scales,
+    })
+}
@@ -0,0 +1,101 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `feat` (Feature Name) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Feature flag: the settings are mutually exclusive.
+const FEATURE_EXCLUSIVE: u16 = 0x8000;
+/// Feature flag: the low byte is the index of the default setting.
+const FEATURE_DEFAULT_INDEX: u16 = 0x4000;
+
+/// Feature name table: the AAT features and settings a font supports.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FeatTable {
+    /// Table version as a 16.16 value (0x00010000).
+    pub version: u32,
+    /// Features, sorted by type.
+    pub features: Vec<FeatureName>,
+}
+
+/// A feature and its settings.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FeatureName {
+    /// Feature type.
+    pub feature_type: u16,
+    /// Settings of the feature.
+    pub settings: Vec<FeatureSettingName>,
+    /// Whether exactly one setting is on at a time.
+    pub exclusive: bool,
+    /// Index of the default setting; the first setting when `None`.
+    pub default_index: Option<u8>,
+    /// `name` table id of the feature's name.
+    pub name_id: u16,
+}
+
+/// A feature setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FeatureSettingName {
+    /// Setting value.
+    pub setting: u16,
+    /// `name` table id of the setting's name.
+    pub name_id: u16,
+}
+
+impl FeatTable {
+    /// Returns a feature by type.
+    pub fn feature(&self, feature_type: u16) -> Option<&FeatureName> {
+        self.features
+            .iter()
+            .find(|feature| feature.feature_type == feature_type)
+    }
+}
+
+impl FeatureName {
+    /// Returns the setting in effect when none is requested.
+    pub fn default_setting(&self) -> Option<u16> {
+        self.settings
+            .get(self.default_index.unwrap_or(0) as usize)
+            .map(|setting| setting.setting)
+    }
+}
+
+/// Extracts the `feat` table from the provided data stream.
+pub fn read_feat(data: &mut FontDataStream) -> Result<FeatTable, Error> {
+    let version = data.read_u32()?;
+    if version >> 16 != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version)));
+    }
+    let count = data.read_u16()?;
+    data.skip(6);
+    let mut features = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let feature_type = data.read_u16()?;
+        let setting_count = data.read_u16()?;
+        let offset = data.read_u32()? as usize;
+        let flags = data.read_u16()?;
+        let name_id = data.read_u16()?;
+        let mut settings_stream = data.substream(offset)?;
+        let settings = (0..setting_count)
+            .map(|_| {
+                Ok(FeatureSettingName {
+                    setting: settings_stream.read_u16()?,
+                    name_id: settings_stream.read_u16()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        features.push(FeatureName {
+            feature_type,
+            settings,
+            exclusive: flags & FEATURE_EXCLUSIVE != 0,
+            default_index: (flags & FEATURE_DEFAULT_INDEX != 0).then_some(flags as u8),
+            name_id,
+        });
+    }
+    Ok(FeatTable { version, features })
+}
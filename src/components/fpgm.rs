@@ -0,0 +1,24 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `fpgm` (Font Program) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+
+/// Font program: TrueType instructions run once when the font is loaded, defining the functions used by the other programs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FpgmTable {
+    /// Instruction bytes.
+    pub instructions: Vec<u8>,
+}
+
+/// Extracts the `fpgm` table from the provided data stream.
+pub fn read_fpgm(data: &mut FontDataStream) -> Result<FpgmTable, Error> {
+    Ok(FpgmTable {
+        instructions: data.remaining_bytes().to_vec(),
+    })
+}
@@ -0,0 +1,212 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `fvar` (Font Variations) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::avar::AvarTable;
+
+/// Axis flag: the axis should not be exposed directly in user interfaces.
+pub const HIDDEN_AXIS: u16 = 0x0001;
+
+/// A variation axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariationAxis {
+    /// Axis tag, e.g. `wght`.
+    pub tag: [u8; 4],
+    /// Minimum user coordinate.
+    pub min_value: f32,
+    /// Default user coordinate.
+    pub default_value: f32,
+    /// Maximum user coordinate.
+    pub max_value: f32,
+    /// Axis flags (`HIDDEN_AXIS`).
+    pub flags: u16,
+    /// Name ID of the axis name.
+    pub axis_name_id: u16,
+}
+
+impl VariationAxis {
+    /// Returns whether the axis should be hidden from users.
+    pub fn is_hidden(&self) -> bool {
+        self.flags & HIDDEN_AXIS != 0
+    }
+
+    /// Maps a user coordinate to the normalized range -1..=1 with the default
+    /// normalization (before `avar`), clamping to the axis range.
+    pub fn normalize(&self, value: f32) -> f32 {
+        let value = value.clamp(self.min_value, self.max_value);
+        let normalized = if value < self.default_value {
+            -(self.default_value - value) / (self.default_value - self.min_value)
+        } else if value > self.default_value {
+            (value - self.default_value) / (self.max_value - self.default_value)
+        } else {
+            0.0
+        };
+        to_f2dot14(normalized)
+    }
+}
+
+/// A named instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedInstance {
+    /// Name ID of the subfamily name.
+    pub subfamily_name_id: u16,
+    /// Reserved flags.
+    pub flags: u16,
+    /// User coordinates, one per axis.
+    pub coordinates: Vec<f32>,
+    /// Name ID of the PostScript name, if recorded.
+    pub post_script_name_id: Option<u16>,
+}
+
+/// Font variations table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FvarTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Variation axes; their order defines the order of coordinate arrays.
+    pub axes: Vec<VariationAxis>,
+    /// Named instances.
+    pub instances: Vec<NamedInstance>,
+}
+
+impl FvarTable {
+    /// Returns the index of the axis with `tag`.
+    pub fn axis_index(&self, tag: &[u8; 4]) -> Option<usize> {
+        self.axes.iter().position(|axis| &axis.tag == tag)
+    }
+
+    /// Returns the default user coordinates of the axes.
+    pub fn default_coords(&self) -> Vec<f32> {
+        self.axes.iter().map(|axis| axis.default_value).collect()
+    }
+
+    /// Converts user coordinates, one per axis in `fvar` order, to normalized
+    /// coordinates: default normalization, then the `avar` mapping if the font
+    /// has one. Missing coordinates take the axis default; values outside an
+    /// axis range are clamped.
+    pub fn normalize(&self, user_coords: &[f32], avar: Option<&AvarTable>) -> Vec<f32> {
+        let coords: Vec<f32> = self
+            .axes
+            .iter()
+            .enumerate()
+            .map(|(i, axis)| {
+                axis.normalize(user_coords.get(i).copied().unwrap_or(axis.default_value))
+            })
+            .collect();
+        match avar {
+            Some(avar) => avar.map_coords(&coords),
+            None => coords,
+        }
+    }
+}
+
+/// Rounds a normalized coordinate to the nearest `F2Dot14` value, as the
+/// specification requires between normalization steps.
+pub fn to_f2dot14(value: f32) -> f32 {
+    (value * 16384.0).round() / 16384.0
+}
+
+/// Extracts the `fvar` table from the provided data stream.
+pub fn read_fvar(data: &mut FontDataStream) -> Result<FvarTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let axes_offset = data.read_u16()? as usize;
+    data.skip(2); // reserved
+    let axis_count = data.read_u16()? as usize;
+    let axis_size = data.read_u16()? as usize;
+    let instance_count = data.read_u16()? as usize;
+    let instance_size = data.read_u16()? as usize;
+    if axis_size < 20 || instance_size < 4 + axis_count * 4 {
+        return Err(Error::InvalidFormat);
+    }
+
+    let mut axes = Vec::with_capacity(axis_count);
+    for i in 0..axis_count {
+        let mut record = data.substream(axes_offset + i * axis_size)?;
+        axes.push(VariationAxis {
+            tag: record.read_tag()?,
+            min_value: record.read_fixed()?,
+            default_value: record.read_fixed()?,
+            max_value: record.read_fixed()?,
+            flags: record.read_u16()?,
+            axis_name_id: record.read_u16()?,
+        });
+    }
+
+    let instances_offset = axes_offset + axis_count * axis_size;
+    let mut instances = Vec::with_capacity(instance_count);
+    for i in 0..instance_count {
+        let mut record = data.substream(instances_offset + i * instance_size)?;
+        let subfamily_name_id = record.read_u16()?;
+        let flags = record.read_u16()?;
+        let coordinates = (0..axis_count)
+            .map(|_| record.read_fixed())
+            .collect::<Result<Vec<_>, _>>()?;
+        let post_script_name_id = if instance_size >= 6 + axis_count * 4 {
+            Some(record.read_u16()?)
+        } else {
+            None
+        };
+        instances.push(NamedInstance {
+            subfamily_name_id,
+            flags,
+            coordinates,
+            post_script_name_id,
+        });
+    }
+    Ok(FvarTable {
+        major_version,
+        minor_version,
+        axes,
+        instances,
+    })
+}
+
+/// Serializes the `fvar` table. PostScript name IDs are written when any
+/// instance has one; the others get 0xFFFF.
+pub fn write_fvar(writer: &mut FontDataWriter, fvar: &FvarTable) {
+    let with_post_script_names = fvar
+        .instances
+        .iter()
+        .any(|i| i.post_script_name_id.is_some());
+    let instance_size = 4 + fvar.axes.len() * 4 + if with_post_script_names { 2 } else { 0 };
+    writer.write_u16(fvar.major_version);
+    writer.write_u16(fvar.minor_version);
+    writer.write_u16(16);
+    writer.write_u16(2);
+    writer.write_u16(fvar.axes.len() as u16);
+    writer.write_u16(20);
+    writer.write_u16(fvar.instances.len() as u16);
+    writer.write_u16(instance_size as u16);
+    for axis in &fvar.axes {
+        writer.write_tag(axis.tag);
+        writer.write_fixed(axis.min_value);
+        writer.write_fixed(axis.default_value);
+        writer.write_fixed(axis.max_value);
+        writer.write_u16(axis.flags);
+        writer.write_u16(axis.axis_name_id);
+    }
+    for instance in &fvar.instances {
+        writer.write_u16(instance.subfamily_name_id);
+        writer.write_u16(instance.flags);
+        for &coordinate in &instance.coordinates {
+            writer.write_fixed(coordinate);
+        }
+        if with_post_script_names {
+            writer.write_u16(instance.post_script_name_id.unwrap_or(0xFFFF));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `gasp` (Grid-fitting and Scan-conversion Procedure) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Use grid-fitting (hinting).
+pub const GASP_GRIDFIT: u16 = 0x0001;
+/// Use grayscale rendering.
+pub const GASP_DOGRAY: u16 = 0x0002;
+/// Use grid-fitting with ClearType symmetric smoothing (version 1).
+pub const GASP_SYMMETRIC_GRIDFIT: u16 = 0x0004;
+/// Use smoothing along multiple axes with ClearType (version 1).
+pub const GASP_SYMMETRIC_SMOOTHING: u16 = 0x0008;
+
+/// Rendering behavior for the sizes up to `range_max_ppem`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GaspRange {
+    /// Upper limit of the range, in pixels per em, inclusive.
+    pub range_max_ppem: u16,
+    /// Combination of the `GASP_*` flags.
+    pub range_gasp_behavior: u16,
+}
+
+/// Grid-fitting and scan-conversion procedure table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GaspTable {
+    /// Table version (0 or 1).
+    pub version: u16,
+    /// Ranges sorted by `range_max_ppem`; the last one ends at 0xFFFF.
+    pub ranges: Vec<GaspRange>,
+}
+
+impl GaspTable {
+    /// Returns the `GASP_*` flags for `ppem` pixels per em. Sizes beyond the
+    /// last range have no flags.
+    pub fn behavior(&self, ppem: u16) -> u16 {
+        self.ranges
+            .iter()
+            .find(|range| ppem <= range.range_max_ppem)
+            .map_or(0, |range| range.range_gasp_behavior)
+    }
+}
+
+/// Extracts the `gasp` table from the provided data stream.
+pub fn read_gasp(data: &mut FontDataStream) -> Result<GaspTable, Error> {
+    let version = data.read_u16()?;
+    if version > 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let num_ranges = data.read_u16()?;
+    let mut ranges = Vec::with_capacity(num_ranges as usize);
+    for _ in 0..num_ranges {
+        let range = GaspRange {
+            range_max_ppem: data.read_u16()?,
+            range_gasp_behavior: data.read_u16()?,
+        };
+        if ranges
+            .last()
+            .is_some_and(|last: &GaspRange| last.range_max_ppem >= range.range_max_ppem)
+        {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        ranges.push(range);
+    }
+    Ok(GaspTable { version, ranges })
+}
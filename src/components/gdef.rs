@@ -0,0 +1,302 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `GDEF` (Glyph Definition) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::{FontDataWriter, serialize};
+
+use super::layout::{
+    ClassDef, Coverage, DeviceTable, class_def_bytes, coverage_bytes, device_bytes, read_at,
+    read_class_def, read_coverage, read_device, read_optional_at, write_child16,
+    write_optional_child16,
+};
+use super::variation::{ItemVariationStore, read_item_variation_store, write_item_variation_store};
+
+/// Glyph class: base glyph (single character, spacing glyph).
+pub const BASE_GLYPH: u16 = 1;
+/// Glyph class: ligature glyph (multiple characters, spacing glyph).
+pub const LIGATURE_GLYPH: u16 = 2;
+/// Glyph class: mark glyph (non-spacing combining glyph).
+pub const MARK_GLYPH: u16 = 3;
+/// Glyph class: component glyph (part of a single character).
+pub const COMPONENT_GLYPH: u16 = 4;
+
+/// A ligature caret position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CaretValue {
+    /// Format 1: X or Y coordinate in font units.
+    Coordinate(i16),
+    /// Format 2: index of a contour point of the ligature glyph.
+    PointIndex(u16),
+    /// Format 3: coordinate adjusted by a device or variation index table.
+    CoordinateDevice(i16, DeviceTable),
+}
+
+/// Glyph definition table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GdefTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0, 2 or 3).
+    pub minor_version: u16,
+    /// Glyph classes (`BASE_GLYPH`, `LIGATURE_GLYPH`, ...).
+    pub glyph_class_def: Option<ClassDef>,
+    /// Attachment point indices of each glyph.
+    pub attach_list: Option<BTreeMap<u16, Vec<u16>>>,
+    /// Caret values of each ligature glyph.
+    pub lig_caret_list: Option<BTreeMap<u16, Vec<CaretValue>>>,
+    /// Mark attachment classes.
+    pub mark_attach_class_def: Option<ClassDef>,
+    /// Mark glyph sets referenced by lookup mark filtering (version 1.2+).
+    pub mark_glyph_sets: Option<Vec<Coverage>>,
+    /// Item variation store referenced by variation index tables (version 1.3).
+    pub item_var_store: Option<ItemVariationStore>,
+}
+
+impl GdefTable {
+    /// Returns the variation delta of a device table at normalized
+    /// coordinates `coords`: the delta of its item for variation index
+    /// tables, 0 for hinting device tables or without a store.
+    pub fn variation_delta(&self, device: &DeviceTable, coords: &[f32]) -> f32 {
+        match (device, &self.item_var_store) {
+            (
+                DeviceTable::VariationIndex {
+                    delta_set_outer_index,
+                    delta_set_inner_index,
+                },
+                Some(store),
+            ) => store.delta(*delta_set_outer_index, *delta_set_inner_index, coords),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Extracts the `GDEF` table from the provided data stream.
+pub fn read_gdef(data: &mut FontDataStream) -> Result<GdefTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let glyph_class_def_offset = data.read_u16()? as usize;
+    let attach_list_offset = data.read_u16()? as usize;
+    let lig_caret_list_offset = data.read_u16()? as usize;
+    let mark_attach_class_def_offset = data.read_u16()? as usize;
+    let mark_glyph_sets_offset = if minor_version >= 2 {
+        data.read_u16()? as usize
+    } else {
+        0
+    };
+    let item_var_store_offset = if minor_version >= 3 {
+        data.read_u32()? as usize
+    } else {
+        0
+    };
+
+    let attach_list = read_optional_at(data, attach_list_offset, |list| {
+        let coverage_offset = list.read_u16()? as usize;
+        let coverage = read_at(list, coverage_offset, read_coverage)?;
+        let count = list.read_u16()?;
+        let mut points = BTreeMap::new();
+        for i in 0..count as usize {
+            let offset = list.read_u16()? as usize;
+            let indices = read_at(list, offset, |d| {
+                let count = d.read_u16()?;
+                (0..count)
+                    .map(|_| d.read_u16())
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+            if let Some(&glyph) = coverage.glyphs.get(i) {
+                points.insert(glyph, indices);
+            }
+        }
+        Ok(points)
+    })?;
+
+    let lig_caret_list = read_optional_at(data, lig_caret_list_offset, |list| {
+        let coverage_offset = list.read_u16()? as usize;
+        let coverage = read_at(list, coverage_offset, read_coverage)?;
+        let count = list.read_u16()?;
+        let mut carets = BTreeMap::new();
+        for i in 0..count as usize {
+            let offset = list.read_u16()? as usize;
+            let values = read_at(list, offset, |lig| {
+                let count = lig.read_u16()?;
+                (0..count)
+                    .map(|_| {
+                        let offset = lig.read_u16()? as usize;
+                        read_at(lig, offset, read_caret_value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+            if let Some(&glyph) = coverage.glyphs.get(i) {
+                carets.insert(glyph, values);
+            }
+        }
+        Ok(carets)
+    })?;
+
+    let mark_glyph_sets = read_optional_at(data, mark_glyph_sets_offset, |sets| {
+        let format = sets.read_u16()?;
+        if format != 1 {
+            return Err(Error::InvalidFormat);
+        }
+        let count = sets.read_u16()?;
+        (0..count)
+            .map(|_| {
+                let offset = sets.read_u32()? as usize;
+                read_at(sets, offset, read_coverage)
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    Ok(GdefTable {
+        major_version,
+        minor_version,
+        glyph_class_def: read_optional_at(data, glyph_class_def_offset, read_class_def)?,
+        attach_list,
+        lig_caret_list,
+        mark_attach_class_def: read_optional_at(
+            data,
+            mark_attach_class_def_offset,
+            read_class_def,
+        )?,
+        mark_glyph_sets,
+        item_var_store: read_optional_at(data, item_var_store_offset, read_item_variation_store)?,
+    })
+}
+
+/// Extracts a caret value table.
+pub fn read_caret_value(data: &mut FontDataStream) -> Result<CaretValue, Error> {
+    match data.read_u16()? {
+        1 => Ok(CaretValue::Coordinate(data.read_i16()?)),
+        2 => Ok(CaretValue::PointIndex(data.read_u16()?)),
+        3 => {
+            let coordinate = data.read_i16()?;
+            let offset = data.read_u16()? as usize;
+            Ok(CaretValue::CoordinateDevice(
+                coordinate,
+                read_at(data, offset, read_device)?,
+            ))
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+fn caret_value_bytes(caret: &CaretValue) -> Result<Vec<u8>, Error> {
+    serialize(|w| {
+        match caret {
+            CaretValue::Coordinate(coordinate) => {
+                w.write_u16(1);
+                w.write_i16(*coordinate);
+            }
+            CaretValue::PointIndex(index) => {
+                w.write_u16(2);
+                w.write_u16(*index);
+            }
+            CaretValue::CoordinateDevice(coordinate, device) => {
+                w.write_u16(3);
+                w.write_i16(*coordinate);
+                write_child16(w, 0, device_bytes(device));
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Serializes the `GDEF` table. The version is raised to cover the optional
+/// fields present.
+pub fn write_gdef(writer: &mut FontDataWriter, gdef: &GdefTable) -> Result<(), Error> {
+    let base = writer.position();
+    let minor_version = if gdef.item_var_store.is_some() {
+        3
+    } else if gdef.mark_glyph_sets.is_some() {
+        gdef.minor_version.max(2)
+    } else {
+        gdef.minor_version
+    };
+    writer.write_u16(gdef.major_version);
+    writer.write_u16(minor_version);
+    write_optional_child16(
+        writer,
+        base,
+        gdef.glyph_class_def.as_ref().map(class_def_bytes),
+    );
+
+    let attach_list = match &gdef.attach_list {
+        Some(points) => Some(serialize(|w| {
+            write_child16(w, 0, coverage_bytes(&Coverage::new(points.keys().copied())));
+            w.write_u16(points.len() as u16);
+            for indices in points.values() {
+                let mut point = FontDataWriter::new();
+                point.write_u16(indices.len() as u16);
+                for &index in indices {
+                    point.write_u16(index);
+                }
+                write_child16(w, 0, point.as_bytes().to_vec());
+            }
+            Ok(())
+        })?),
+        None => None,
+    };
+    write_optional_child16(writer, base, attach_list);
+
+    let lig_caret_list = match &gdef.lig_caret_list {
+        Some(carets) => Some(serialize(|w| {
+            write_child16(w, 0, coverage_bytes(&Coverage::new(carets.keys().copied())));
+            w.write_u16(carets.len() as u16);
+            for values in carets.values() {
+                let lig = serialize(|lig| {
+                    lig.write_u16(values.len() as u16);
+                    for value in values {
+                        write_child16(lig, 0, caret_value_bytes(value)?);
+                    }
+                    Ok(())
+                })?;
+                write_child16(w, 0, lig);
+            }
+            Ok(())
+        })?),
+        None => None,
+    };
+    write_optional_child16(writer, base, lig_caret_list);
+    write_optional_child16(
+        writer,
+        base,
+        gdef.mark_attach_class_def.as_ref().map(class_def_bytes),
+    );
+
+    if minor_version >= 2 {
+        let sets = match &gdef.mark_glyph_sets {
+            Some(sets) => Some(serialize(|w| {
+                w.write_u16(1);
+                w.write_u16(sets.len() as u16);
+                for coverage in sets {
+                    let placeholder = w.reserve_offset32(0);
+                    w.defer_subtable(placeholder, coverage_bytes(coverage));
+                }
+                Ok(())
+            })?),
+            None => None,
+        };
+        write_optional_child16(writer, base, sets);
+    }
+    if minor_version >= 3 {
+        match &gdef.item_var_store {
+            Some(store) => {
+                let placeholder = writer.reserve_offset32(base);
+                let bytes = serialize(|w| write_item_variation_store(w, store))?;
+                writer.defer_subtable(placeholder, bytes);
+            }
+            None => writer.write_u32(0),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,641 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `glyf` (Glyph Data) table representation and utilities for the Aurora Font Library.
+//! This module decodes TrueType simple and composite glyphs and serializes them back.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::loca::LocaTable;
+
+/// Simple glyph flag: point is on the curve.
+pub const ON_CURVE_POINT: u8 = 0x01;
+/// Simple glyph flag: x coordinate is one byte.
+pub const X_SHORT_VECTOR: u8 = 0x02;
+/// Simple glyph flag: y coordinate is one byte.
+pub const Y_SHORT_VECTOR: u8 = 0x04;
+/// Simple glyph flag: the next byte is a repeat count for this flag.
+pub const REPEAT_FLAG: u8 = 0x08;
+/// Simple glyph flag: x is positive (short) or unchanged (long).
+pub const X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR: u8 = 0x10;
+/// Simple glyph flag: y is positive (short) or unchanged (long).
+pub const Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR: u8 = 0x20;
+/// Simple glyph flag: contours may overlap (first flag only).
+pub const OVERLAP_SIMPLE: u8 = 0x40;
+
+/// Component flag: arguments are 16-bit.
+pub const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+/// Component flag: arguments are x/y offsets rather than point numbers.
+pub const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+/// Component flag: round the offset to the grid.
+pub const ROUND_XY_TO_GRID: u16 = 0x0004;
+/// Component flag: a single uniform scale follows.
+pub const WE_HAVE_A_SCALE: u16 = 0x0008;
+/// Component flag: more components follow.
+pub const MORE_COMPONENTS: u16 = 0x0020;
+/// Component flag: separate x and y scales follow.
+pub const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+/// Component flag: a 2x2 transformation follows.
+pub const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+/// Component flag: instructions follow the last component.
+pub const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+/// Component flag: use this component's metrics for the composite.
+pub const USE_MY_METRICS: u16 = 0x0200;
+/// Component flag: components may overlap.
+pub const OVERLAP_COMPOUND: u16 = 0x0400;
+/// Component flag: the offset is scaled by the component transform.
+pub const SCALED_COMPONENT_OFFSET: u16 = 0x0800;
+/// Component flag: the offset is not scaled by the component transform.
+pub const UNSCALED_COMPONENT_OFFSET: u16 = 0x1000;
+
+/// Glyph header shared by simple and composite glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GlyphHeader {
+    /// Number of contours; negative for composite glyphs.
+    pub number_of_contours: i16,
+    /// Minimum x of the glyph bounding box.
+    pub x_min: i16,
+    /// Minimum y of the glyph bounding box.
+    pub y_min: i16,
+    /// Maximum x of the glyph bounding box.
+    pub x_max: i16,
+    /// Maximum y of the glyph bounding box.
+    pub y_max: i16,
+}
+
+/// A point of a simple glyph outline, in absolute font units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GlyphPoint {
+    /// Absolute x coordinate.
+    pub x: i16,
+    /// Absolute y coordinate.
+    pub y: i16,
+    /// Whether the point is on the curve (otherwise a quadratic control point).
+    pub on_curve: bool,
+}
+
+/// A glyph described by its own contours.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SimpleGlyph {
+    /// Glyph header.
+    pub header: GlyphHeader,
+    /// Index of the last point of each contour.
+    pub end_points: Vec<u16>,
+    /// TrueType hinting instructions.
+    pub instructions: Vec<u8>,
+    /// Outline points.
+    pub points: Vec<GlyphPoint>,
+    /// Whether `OVERLAP_SIMPLE` is set on the first flag.
+    pub overlap: bool,
+}
+
+impl SimpleGlyph {
+    /// Computes the bounding box of the points as `(x_min, y_min, x_max, y_max)`.
+    pub fn compute_bounds(&self) -> (i16, i16, i16, i16) {
+        compute_bounds(self.points.iter().map(|p| (p.x, p.y)))
+    }
+
+    /// Returns the points of each contour.
+    pub fn contours(&self) -> impl Iterator<Item = &[GlyphPoint]> {
+        let mut start = 0usize;
+        self.end_points.iter().map(move |&end| {
+            let end = (end as usize + 1).clamp(start, self.points.len());
+            let contour = &self.points[start..end];
+            start = end;
+            contour
+        })
+    }
+}
+
+/// Transformation applied to a composite glyph component.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ComponentTransform {
+    /// No scaling.
+    #[default]
+    None,
+    /// Uniform scale.
+    Scale(f32),
+    /// Separate x and y scales.
+    XYScale { x_scale: f32, y_scale: f32 },
+    /// Full 2x2 matrix.
+    TwoByTwo { xx: f32, yx: f32, xy: f32, yy: f32 },
+}
+
+impl ComponentTransform {
+    /// Returns the matrix `[xx, yx, xy, yy]` of the transform.
+    pub fn matrix(&self) -> [f32; 4] {
+        match *self {
+            ComponentTransform::None => [1.0, 0.0, 0.0, 1.0],
+            ComponentTransform::Scale(s) => [s, 0.0, 0.0, s],
+            ComponentTransform::XYScale { x_scale, y_scale } => [x_scale, 0.0, 0.0, y_scale],
+            ComponentTransform::TwoByTwo { xx, yx, xy, yy } => [xx, yx, xy, yy],
+        }
+    }
+}
+
+/// A component reference of a composite glyph.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlyphComponent {
+    /// Component flags as stored in the font.
+    pub flags: u16,
+    /// Glyph id of the referenced glyph.
+    pub glyph_index: u16,
+    /// First argument: x offset or parent point number.
+    pub arg1: i32,
+    /// Second argument: y offset or child point number.
+    pub arg2: i32,
+    /// Transformation of the component.
+    pub transform: ComponentTransform,
+}
+
+impl GlyphComponent {
+    /// Returns whether the arguments are x/y offsets rather than point numbers.
+    pub fn args_are_xy_values(&self) -> bool {
+        self.flags & ARGS_ARE_XY_VALUES != 0
+    }
+}
+
+/// A glyph assembled from references to other glyphs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CompositeGlyph {
+    /// Glyph header.
+    pub header: GlyphHeader,
+    /// Components in drawing order.
+    pub components: Vec<GlyphComponent>,
+    /// TrueType hinting instructions for the whole composite.
+    pub instructions: Vec<u8>,
+}
+
+impl CompositeGlyph {
+    /// Returns whether the glyph carries an instruction block.
+    ///
+    /// This is also true for an empty block announced by `WE_HAVE_INSTRUCTIONS`
+    /// on the last component, so such glyphs round-trip unchanged.
+    pub fn has_instructions(&self) -> bool {
+        !self.instructions.is_empty()
+            || self
+                .components
+                .last()
+                .is_some_and(|c| c.flags & WE_HAVE_INSTRUCTIONS != 0)
+    }
+}
+
+/// A decoded `glyf` entry.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Glyph {
+    /// Glyph without outline data (e.g. space).
+    #[default]
+    Empty,
+    /// Glyph with its own contours.
+    Simple(SimpleGlyph),
+    /// Glyph built from other glyphs.
+    Composite(CompositeGlyph),
+}
+
+impl Glyph {
+    /// Returns the glyph header, or `None` for empty glyphs.
+    pub fn header(&self) -> Option<&GlyphHeader> {
+        match self {
+            Glyph::Empty => None,
+            Glyph::Simple(g) => Some(&g.header),
+            Glyph::Composite(g) => Some(&g.header),
+        }
+    }
+
+    /// Returns the glyph header mutably, or `None` for empty glyphs.
+    pub fn header_mut(&mut self) -> Option<&mut GlyphHeader> {
+        match self {
+            Glyph::Empty => None,
+            Glyph::Simple(g) => Some(&mut g.header),
+            Glyph::Composite(g) => Some(&mut g.header),
+        }
+    }
+
+    /// Returns the hinting instructions of the glyph.
+    pub fn instructions(&self) -> &[u8] {
+        match self {
+            Glyph::Empty => &[],
+            Glyph::Simple(g) => &g.instructions,
+            Glyph::Composite(g) => &g.instructions,
+        }
+    }
+
+    /// Returns the glyph ids referenced by a composite glyph.
+    pub fn component_glyph_ids(&self) -> Vec<u16> {
+        match self {
+            Glyph::Composite(g) => g.components.iter().map(|c| c.glyph_index).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns whether the glyph is a composite.
+    pub fn is_composite(&self) -> bool {
+        matches!(self, Glyph::Composite(_))
+    }
+}
+
+/// Glyph data table representation.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlyfTable {
+    /// Decoded glyphs indexed by glyph id.
+    pub glyphs: Vec<Glyph>,
+}
+
+impl GlyfTable {
+    /// Returns a glyph by id.
+    pub fn glyph(&self, glyph_id: u16) -> Option<&Glyph> {
+        self.glyphs.get(glyph_id as usize)
+    }
+
+    /// Returns the number of glyphs.
+    pub fn num_glyphs(&self) -> usize {
+        self.glyphs.len()
+    }
+}
+
+/// Computes `(x_min, y_min, x_max, y_max)` for a set of points, or zeros if empty.
+pub fn compute_bounds(points: impl Iterator<Item = (i16, i16)>) -> (i16, i16, i16, i16) {
+    let mut bounds: Option<(i16, i16, i16, i16)> = None;
+    for (x, y) in points {
+        bounds = Some(match bounds {
+            None => (x, y, x, y),
+            Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+        });
+    }
+    bounds.unwrap_or((0, 0, 0, 0))
+}
+
+/// Extracts every glyph of the `glyf` table using the offsets from `loca`.
+pub fn read_glyf(data: &mut FontDataStream, loca: &LocaTable) -> Result<GlyfTable, Error> {
+    let mut glyphs = Vec::with_capacity(loca.num_glyphs());
+    for glyph_id in 0..loca.num_glyphs() {
+        let range =
+            loca.glyph_range(glyph_id as u16)
+                .ok_or(Error::Io(IoError::InvalidGlyphIndex {
+                    index: glyph_id as u32,
+                }))?;
+        let bytes = data.slice_range(range)?;
+        glyphs.push(read_glyph(&mut FontDataStream::new(bytes))?);
+    }
+    Ok(GlyfTable { glyphs })
+}
+
+/// Decodes a single glyph. An empty stream yields `Glyph::Empty`.
+pub fn read_glyph(data: &mut FontDataStream) -> Result<Glyph, Error> {
+    if data.is_empty() {
+        return Ok(Glyph::Empty);
+    }
+    let header = GlyphHeader {
+        number_of_contours: data.read_i16()?,
+        x_min: data.read_i16()?,
+        y_min: data.read_i16()?,
+        x_max: data.read_i16()?,
+        y_max: data.read_i16()?,
+    };
+    match header.number_of_contours {
+        0 => Ok(Glyph::Empty),
+        n if n > 0 => read_simple_glyph(data, header).map(Glyph::Simple),
+        _ => read_composite_glyph(data, header).map(Glyph::Composite),
+    }
+}
+
+/// Decodes the body of a simple glyph.
+fn read_simple_glyph(data: &mut FontDataStream, header: GlyphHeader) -> Result<SimpleGlyph, Error> {
+    let mut end_points = Vec::with_capacity(header.number_of_contours as usize);
+    for _ in 0..header.number_of_contours {
+        end_points.push(data.read_u16()?);
+    }
+    let num_points = end_points.last().map_or(0, |&e| e as usize + 1);
+    let instruction_length = data.read_u16()? as usize;
+    let instructions = data.read_bytes(instruction_length)?.to_vec();
+
+    let mut flags = Vec::with_capacity(num_points);
+    while flags.len() < num_points {
+        let flag = data.read_u8()?;
+        flags.push(flag);
+        if flag & REPEAT_FLAG != 0 {
+            let count = data.read_u8()? as usize;
+            flags.extend(std::iter::repeat_n(flag, count));
+        }
+    }
+    flags.truncate(num_points);
+
+    let xs = read_coordinates(
+        data,
+        &flags,
+        X_SHORT_VECTOR,
+        X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR,
+    )?;
+    let ys = read_coordinates(
+        data,
+        &flags,
+        Y_SHORT_VECTOR,
+        Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR,
+    )?;
+    let points = flags
+        .iter()
+        .zip(xs.into_iter().zip(ys))
+        .map(|(&flag, (x, y))| GlyphPoint {
+            x,
+            y,
+            on_curve: flag & ON_CURVE_POINT != 0,
+        })
+        .collect();
+
+    Ok(SimpleGlyph {
+        header,
+        end_points,
+        instructions,
+        points,
+        overlap: flags.first().is_some_and(|f| f & OVERLAP_SIMPLE != 0),
+    })
+}
+
+/// Decodes one coordinate array of a simple glyph into absolute values.
+fn read_coordinates(
+    data: &mut FontDataStream,
+    flags: &[u8],
+    short_flag: u8,
+    same_flag: u8,
+) -> Result<Vec<i16>, Error> {
+    let mut values = Vec::with_capacity(flags.len());
+    let mut value = 0i16;
+    for &flag in flags {
+        let delta = if flag & short_flag != 0 {
+            let magnitude = data.read_u8()? as i16;
+            if flag & same_flag != 0 {
+                magnitude
+            } else {
+                -magnitude
+            }
+        } else if flag & same_flag != 0 {
+            0
+        } else {
+            data.read_i16()?
+        };
+        value = value.wrapping_add(delta);
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Decodes the body of a composite glyph.
+fn read_composite_glyph(
+    data: &mut FontDataStream,
+    header: GlyphHeader,
+) -> Result<CompositeGlyph, Error> {
+    let mut components = Vec::new();
+    let mut have_instructions = false;
+    loop {
+        let flags = data.read_u16()?;
+        let glyph_index = data.read_u16()?;
+        let (arg1, arg2) = match (
+            flags & ARG_1_AND_2_ARE_WORDS != 0,
+            flags & ARGS_ARE_XY_VALUES != 0,
+        ) {
+            (true, true) => (data.read_i16()? as i32, data.read_i16()? as i32),
+            (true, false) => (data.read_u16()? as i32, data.read_u16()? as i32),
+            (false, true) => (data.read_i8()? as i32, data.read_i8()? as i32),
+            (false, false) => (data.read_u8()? as i32, data.read_u8()? as i32),
+        };
+        let transform = if flags & WE_HAVE_A_SCALE != 0 {
+            ComponentTransform::Scale(data.read_f2dot14()?)
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            ComponentTransform::XYScale {
+                x_scale: data.read_f2dot14()?,
+                y_scale: data.read_f2dot14()?,
+            }
+        } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            ComponentTransform::TwoByTwo {
+                xx: data.read_f2dot14()?,
+                yx: data.read_f2dot14()?,
+                xy: data.read_f2dot14()?,
+                yy: data.read_f2dot14()?,
+            }
+        } else {
+            ComponentTransform::None
+        };
+        have_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+        components.push(GlyphComponent {
+            flags,
+            glyph_index,
+            arg1,
+            arg2,
+            transform,
+        });
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+    let instructions = if have_instructions {
+        let length = data.read_u16()? as usize;
+        data.read_bytes(length)?.to_vec()
+    } else {
+        Vec::new()
+    };
+    Ok(CompositeGlyph {
+        header,
+        components,
+        instructions,
+    })
+}
+
+/// Serializes a single glyph. Empty glyphs produce no bytes.
+pub fn write_glyph(writer: &mut FontDataWriter, glyph: &Glyph) {
+    match glyph {
+        Glyph::Empty => {}
+        Glyph::Simple(g) => write_simple_glyph(writer, g),
+        Glyph::Composite(g) => {
+            write_glyph_header(writer, &g.header);
+            write_components(writer, &g.components, g.has_instructions());
+            if g.has_instructions() {
+                writer.write_u16(g.instructions.len() as u16);
+                writer.write_bytes(&g.instructions);
+            }
+        }
+    }
+}
+
+/// Writes a glyph header.
+fn write_glyph_header(writer: &mut FontDataWriter, header: &GlyphHeader) {
+    writer.write_i16(header.number_of_contours);
+    writer.write_i16(header.x_min);
+    writer.write_i16(header.y_min);
+    writer.write_i16(header.x_max);
+    writer.write_i16(header.y_max);
+}
+
+/// Serializes a simple glyph, choosing the smallest flag and coordinate encoding.
+fn write_simple_glyph(writer: &mut FontDataWriter, glyph: &SimpleGlyph) {
+    write_glyph_header(writer, &glyph.header);
+    for &end in &glyph.end_points {
+        writer.write_u16(end);
+    }
+    writer.write_u16(glyph.instructions.len() as u16);
+    writer.write_bytes(&glyph.instructions);
+
+    let mut flags = Vec::with_capacity(glyph.points.len());
+    let mut xs = FontDataWriter::new();
+    let mut ys = FontDataWriter::new();
+    let (mut last_x, mut last_y) = (0i16, 0i16);
+    for (i, point) in glyph.points.iter().enumerate() {
+        let mut flag = if point.on_curve { ON_CURVE_POINT } else { 0 };
+        if i == 0 && glyph.overlap {
+            flag |= OVERLAP_SIMPLE;
+        }
+        let dx = point.x.wrapping_sub(last_x);
+        let dy = point.y.wrapping_sub(last_y);
+        flag |= encode_coordinate(
+            &mut xs,
+            dx,
+            X_SHORT_VECTOR,
+            X_IS_SAME_OR_POSITIVE_X_SHORT_VECTOR,
+        );
+        flag |= encode_coordinate(
+            &mut ys,
+            dy,
+            Y_SHORT_VECTOR,
+            Y_IS_SAME_OR_POSITIVE_Y_SHORT_VECTOR,
+        );
+        flags.push(flag);
+        last_x = point.x;
+        last_y = point.y;
+    }
+
+    let mut i = 0;
+    while i < flags.len() {
+        let flag = flags[i];
+        let run = flags[i + 1..]
+            .iter()
+            .take(255)
+            .take_while(|&&f| f == flag)
+            .count();
+        if run > 1 {
+            writer.write_u8(flag | REPEAT_FLAG);
+            writer.write_u8(run as u8);
+            i += run + 1;
+        } else {
+            writer.write_u8(flag);
+            i += 1;
+        }
+    }
+    writer.write_bytes(xs.as_bytes());
+    writer.write_bytes(ys.as_bytes());
+}
+
+/// Writes a coordinate delta and returns the flag bits describing it.
+fn encode_coordinate(out: &mut FontDataWriter, delta: i16, short_flag: u8, same_flag: u8) -> u8 {
+    if delta == 0 {
+        same_flag
+    } else if (-255..=255).contains(&delta) {
+        out.write_u8(delta.unsigned_abs() as u8);
+        if delta > 0 {
+            short_flag | same_flag
+        } else {
+            short_flag
+        }
+    } else {
+        out.write_i16(delta);
+        0
+    }
+}
+
+/// Serializes composite components, re-deriving the size flags from the values.
+fn write_components(
+    writer: &mut FontDataWriter,
+    components: &[GlyphComponent],
+    instructions: bool,
+) {
+    for (i, component) in components.iter().enumerate() {
+        let mut flags = component.flags
+            & !(ARG_1_AND_2_ARE_WORDS
+                | WE_HAVE_A_SCALE
+                | WE_HAVE_AN_X_AND_Y_SCALE
+                | WE_HAVE_A_TWO_BY_TWO
+                | MORE_COMPONENTS
+                | WE_HAVE_INSTRUCTIONS);
+        let xy = component.flags & ARGS_ARE_XY_VALUES != 0;
+        let words = if xy {
+            !(-128..=127).contains(&component.arg1) || !(-128..=127).contains(&component.arg2)
+        } else {
+            component.arg1 > 255 || component.arg2 > 255
+        };
+        if words {
+            flags |= ARG_1_AND_2_ARE_WORDS;
+        }
+        flags |= match component.transform {
+            ComponentTransform::None => 0,
+            ComponentTransform::Scale(_) => WE_HAVE_A_SCALE,
+            ComponentTransform::XYScale { .. } => WE_HAVE_AN_X_AND_Y_SCALE,
+            ComponentTransform::TwoByTwo { .. } => WE_HAVE_A_TWO_BY_TWO,
+        };
+        if i + 1 < components.len() {
+            flags |= MORE_COMPONENTS;
+        } else if instructions {
+            flags |= WE_HAVE_INSTRUCTIONS;
+        }
+
+        writer.write_u16(flags);
+        writer.write_u16(component.glyph_index);
+        match (words, xy) {
+            (true, true) => {
+                writer.write_i16(component.arg1 as i16);
+                writer.write_i16(component.arg2 as i16);
+            }
+            (true, false) => {
+                writer.write_u16(component.arg1 as u16);
+                writer.write_u16(component.arg2 as u16);
+            }
+            (false, true) => {
+                writer.write_i8(component.arg1 as i8);
+                writer.write_i8(component.arg2 as i8);
+            }
+            (false, false) => {
+                writer.write_u8(component.arg1 as u8);
+                writer.write_u8(component.arg2 as u8);
+            }
+        }
+        match component.transform {
+            ComponentTransform::None => {}
+            ComponentTransform::Scale(s) => writer.write_f2dot14(s),
+            ComponentTransform::XYScale { x_scale, y_scale } => {
+                writer.write_f2dot14(x_scale);
+                writer.write_f2dot14(y_scale);
+            }
+            ComponentTransform::TwoByTwo { xx, yx, xy, yy } => {
+                writer.write_f2dot14(xx);
+                writer.write_f2dot14(yx);
+                writer.write_f2dot14(xy);
+                writer.write_f2dot14(yy);
+            }
+        }
+    }
+}
+
+/// Serializes the components of a composite glyph without header or instructions.
+///
+/// This is the layout used by the WOFF2 composite stream.
+pub fn write_composite_components(writer: &mut FontDataWriter, glyph: &CompositeGlyph) {
+    write_components(writer, &glyph.components, glyph.has_instructions());
+}
+
+/// Serializes the `glyf` table and returns the matching `loca` offsets.
+///
+/// Each glyph is padded to a 4-byte boundary.
+pub fn write_glyf(writer: &mut FontDataWriter, glyf: &GlyfTable) -> LocaTable {
+    let start = writer.position();
+    let mut offsets = Vec::with_capacity(glyf.glyphs.len() + 1);
+    for glyph in &glyf.glyphs {
+        offsets.push((writer.position() - start) as u32);
+        write_glyph(writer, glyph);
+        let padding = (4 - (writer.position() - start) % 4) % 4;
+        writer.write_zeros(padding);
+    }
+    offsets.push((writer.position() - start) as u32);
+    LocaTable { offsets }
+}
@@ -0,0 +1,762 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `GPOS` (Glyph Positioning) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::{FontDataWriter, serialize};
+
+use super::layout::{
+    ChainedSequenceContext, ClassDef, Coverage, DeviceTable, LayoutTable, SequenceContext,
+    class_def_bytes, coverage_bytes, device_bytes, read_at, read_chained_sequence_context,
+    read_class_def, read_coverage, read_device, read_layout_table, read_optional_at,
+    read_sequence_context, write_chained_sequence_context, write_child16, write_layout_table,
+    write_optional_child16, write_sequence_context,
+};
+
+/// Lookup type of extension positioning subtables.
+pub const GPOS_EXTENSION_TYPE: u16 = 9;
+
+/// Value format flag: horizontal placement adjustment present.
+pub const X_PLACEMENT: u16 = 0x0001;
+/// Value format flag: vertical placement adjustment present.
+pub const Y_PLACEMENT: u16 = 0x0002;
+/// Value format flag: horizontal advance adjustment present.
+pub const X_ADVANCE: u16 = 0x0004;
+/// Value format flag: vertical advance adjustment present.
+pub const Y_ADVANCE: u16 = 0x0008;
+/// Value format flag: device table for the horizontal placement present.
+pub const X_PLACEMENT_DEVICE: u16 = 0x0010;
+/// Value format flag: device table for the vertical placement present.
+pub const Y_PLACEMENT_DEVICE: u16 = 0x0020;
+/// Value format flag: device table for the horizontal advance present.
+pub const X_ADVANCE_DEVICE: u16 = 0x0040;
+/// Value format flag: device table for the vertical advance present.
+pub const Y_ADVANCE_DEVICE: u16 = 0x0080;
+
+/// Glyph positioning table.
+pub type GposTable = LayoutTable<PositioningSubtable>;
+
+/// Positioning adjustments of a glyph. Fields absent from the value format are zero/`None`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ValueRecord {
+    /// Horizontal placement adjustment.
+    pub x_placement: i16,
+    /// Vertical placement adjustment.
+    pub y_placement: i16,
+    /// Horizontal advance adjustment.
+    pub x_advance: i16,
+    /// Vertical advance adjustment.
+    pub y_advance: i16,
+    /// Device table for `x_placement`.
+    pub x_placement_device: Option<DeviceTable>,
+    /// Device table for `y_placement`.
+    pub y_placement_device: Option<DeviceTable>,
+    /// Device table for `x_advance`.
+    pub x_advance_device: Option<DeviceTable>,
+    /// Device table for `y_advance`.
+    pub y_advance_device: Option<DeviceTable>,
+}
+
+impl ValueRecord {
+    /// Returns the smallest value format that can hold the record.
+    pub fn format(&self) -> u16 {
+        let mut format = 0;
+        for (present, flag) in [
+            (self.x_placement != 0, X_PLACEMENT),
+            (self.y_placement != 0, Y_PLACEMENT),
+            (self.x_advance != 0, X_ADVANCE),
+            (self.y_advance != 0, Y_ADVANCE),
+            (self.x_placement_device.is_some(), X_PLACEMENT_DEVICE),
+            (self.y_placement_device.is_some(), Y_PLACEMENT_DEVICE),
+            (self.x_advance_device.is_some(), X_ADVANCE_DEVICE),
+            (self.y_advance_device.is_some(), Y_ADVANCE_DEVICE),
+        ] {
+            if present {
+                format |= flag;
+            }
+        }
+        format
+    }
+}
+
+/// An attachment point.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Anchor {
+    /// Horizontal position in font units.
+    pub x_coordinate: i16,
+    /// Vertical position in font units.
+    pub y_coordinate: i16,
+    /// Contour point index refining the position when hinting (format 2).
+    pub anchor_point: Option<u16>,
+    /// Device table for `x_coordinate` (format 3).
+    pub x_device: Option<DeviceTable>,
+    /// Device table for `y_coordinate` (format 3).
+    pub y_device: Option<DeviceTable>,
+}
+
+/// A mark glyph's class and anchor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MarkRecord {
+    /// Mark class.
+    pub mark_class: u16,
+    /// Attachment point of the mark.
+    pub mark_anchor: Anchor,
+}
+
+/// Cursive attachment points of a glyph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct EntryExitRecord {
+    /// Entry point.
+    pub entry_anchor: Option<Anchor>,
+    /// Exit point.
+    pub exit_anchor: Option<Anchor>,
+}
+
+/// Adjustments of a glyph pair, keyed by the first glyph in `PositioningSubtable::Pair`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PairValueRecord {
+    /// Second glyph of the pair.
+    pub second_glyph: u16,
+    /// Adjustment of the first glyph.
+    pub value_record1: ValueRecord,
+    /// Adjustment of the second glyph.
+    pub value_record2: ValueRecord,
+}
+
+/// A `GPOS` lookup subtable. Extension subtables are unwrapped on reading.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PositioningSubtable {
+    /// Type 1: adjust single glyphs.
+    Single {
+        /// Fields stored for every value record.
+        value_format: u16,
+        /// Adjustment of each covered glyph.
+        values: BTreeMap<u16, ValueRecord>,
+    },
+    /// Type 2, format 1: adjust individual glyph pairs.
+    Pair {
+        /// Fields stored for the first glyph.
+        value_format1: u16,
+        /// Fields stored for the second glyph.
+        value_format2: u16,
+        /// Pairs keyed by their first glyph.
+        pair_sets: BTreeMap<u16, Vec<PairValueRecord>>,
+    },
+    /// Type 2, format 2: adjust pairs of glyph classes.
+    PairClass {
+        /// Fields stored for the first glyph.
+        value_format1: u16,
+        /// Fields stored for the second glyph.
+        value_format2: u16,
+        /// First glyphs the subtable applies to.
+        coverage: Coverage,
+        /// Classes of the first glyphs.
+        class_def1: ClassDef,
+        /// Classes of the second glyphs.
+        class_def2: ClassDef,
+        /// Adjustments indexed by first class, then second class.
+        class1_records: Vec<Vec<(ValueRecord, ValueRecord)>>,
+    },
+    /// Type 3: cursive attachment.
+    Cursive(BTreeMap<u16, EntryExitRecord>),
+    /// Type 4: attach marks to bases.
+    MarkToBase {
+        /// Number of mark classes.
+        mark_class_count: u16,
+        /// Covered marks.
+        marks: BTreeMap<u16, MarkRecord>,
+        /// Base anchors indexed by mark class.
+        bases: BTreeMap<u16, Vec<Option<Anchor>>>,
+    },
+    /// Type 5: attach marks to ligature components.
+    MarkToLigature {
+        /// Number of mark classes.
+        mark_class_count: u16,
+        /// Covered marks.
+        marks: BTreeMap<u16, MarkRecord>,
+        /// Anchors indexed by component, then mark class.
+        ligatures: BTreeMap<u16, Vec<Vec<Option<Anchor>>>>,
+    },
+    /// Type 6: attach marks to other marks.
+    MarkToMark {
+        /// Number of mark classes.
+        mark_class_count: u16,
+        /// Covered attaching marks.
+        marks: BTreeMap<u16, MarkRecord>,
+        /// Anchors of the base marks indexed by mark class.
+        mark2s: BTreeMap<u16, Vec<Option<Anchor>>>,
+    },
+    /// Type 7: contextual positioning.
+    Context(SequenceContext),
+    /// Type 8: chained contextual positioning.
+    ChainContext(ChainedSequenceContext),
+}
+
+impl PositioningSubtable {
+    /// Returns the lookup type of the subtable.
+    pub fn lookup_type(&self) -> u16 {
+        match self {
+            PositioningSubtable::Single { .. } => 1,
+            PositioningSubtable::Pair { .. } | PositioningSubtable::PairClass { .. } => 2,
+            PositioningSubtable::Cursive(_) => 3,
+            PositioningSubtable::MarkToBase { .. } => 4,
+            PositioningSubtable::MarkToLigature { .. } => 5,
+            PositioningSubtable::MarkToMark { .. } => 6,
+            PositioningSubtable::Context(_) => 7,
+            PositioningSubtable::ChainContext(_) => 8,
+        }
+    }
+}
+
+/// Extracts the `GPOS` table from the provided data stream.
+pub fn read_gpos(data: &mut FontDataStream) -> Result<GposTable, Error> {
+    read_layout_table(data, GPOS_EXTENSION_TYPE, read_positioning_subtable)
+}
+
+/// Serializes the `GPOS` table.
+pub fn write_gpos(writer: &mut FontDataWriter, gpos: &GposTable) -> Result<(), Error> {
+    write_layout_table(
+        writer,
+        gpos,
+        GPOS_EXTENSION_TYPE,
+        write_positioning_subtable,
+    )
+}
+
+/// Reads a value record; device offsets are measured from `base`.
+pub fn read_value_record(
+    data: &mut FontDataStream,
+    format: u16,
+    base: &FontDataStream,
+) -> Result<ValueRecord, Error> {
+    let mut value = |flag: u16| -> Result<i16, Error> {
+        if format & flag != 0 {
+            data.read_i16()
+        } else {
+            Ok(0)
+        }
+    };
+    let x_placement = value(X_PLACEMENT)?;
+    let y_placement = value(Y_PLACEMENT)?;
+    let x_advance = value(X_ADVANCE)?;
+    let y_advance = value(Y_ADVANCE)?;
+    let mut device = |flag: u16| -> Result<Option<DeviceTable>, Error> {
+        if format & flag == 0 {
+            return Ok(None);
+        }
+        let offset = data.read_u16()? as usize;
+        read_optional_at(base, offset, read_device)
+    };
+    Ok(ValueRecord {
+        x_placement,
+        y_placement,
+        x_advance,
+        y_advance,
+        x_placement_device: device(X_PLACEMENT_DEVICE)?,
+        y_placement_device: device(Y_PLACEMENT_DEVICE)?,
+        x_advance_device: device(X_ADVANCE_DEVICE)?,
+        y_advance_device: device(Y_ADVANCE_DEVICE)?,
+    })
+}
+
+/// Writes a value record; device tables are deferred relative to `base`.
+pub fn write_value_record(
+    writer: &mut FontDataWriter,
+    value: &ValueRecord,
+    format: u16,
+    base: usize,
+) {
+    for (field, flag) in [
+        (value.x_placement, X_PLACEMENT),
+        (value.y_placement, Y_PLACEMENT),
+        (value.x_advance, X_ADVANCE),
+        (value.y_advance, Y_ADVANCE),
+    ] {
+        if format & flag != 0 {
+            writer.write_i16(field);
+        }
+    }
+    for (device, flag) in [
+        (&value.x_placement_device, X_PLACEMENT_DEVICE),
+        (&value.y_placement_device, Y_PLACEMENT_DEVICE),
+        (&value.x_advance_device, X_ADVANCE_DEVICE),
+        (&value.y_advance_device, Y_ADVANCE_DEVICE),
+    ] {
+        if format & flag != 0 {
+            write_optional_child16(writer, base, device.as_ref().map(device_bytes));
+        }
+    }
+}
+
+/// Extracts an anchor table.
+pub fn read_anchor(data: &mut FontDataStream) -> Result<Anchor, Error> {
+    let format = data.read_u16()?;
+    let x_coordinate = data.read_i16()?;
+    let y_coordinate = data.read_i16()?;
+    let mut anchor = Anchor {
+        x_coordinate,
+        y_coordinate,
+        ..Default::default()
+    };
+    match format {
+        1 => {}
+        2 => anchor.anchor_point = Some(data.read_u16()?),
+        3 => {
+            let x_offset = data.read_u16()? as usize;
+            let y_offset = data.read_u16()? as usize;
+            anchor.x_device = read_optional_at(data, x_offset, read_device)?;
+            anchor.y_device = read_optional_at(data, y_offset, read_device)?;
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    Ok(anchor)
+}
+
+/// Serializes an anchor table in the smallest format that holds it.
+pub fn write_anchor(writer: &mut FontDataWriter, anchor: &Anchor) {
+    let base = writer.position();
+    let format = if anchor.x_device.is_some() || anchor.y_device.is_some() {
+        3
+    } else if anchor.anchor_point.is_some() {
+        2
+    } else {
+        1
+    };
+    writer.write_u16(format);
+    writer.write_i16(anchor.x_coordinate);
+    writer.write_i16(anchor.y_coordinate);
+    match format {
+        2 => writer.write_u16(anchor.anchor_point.unwrap_or(0)),
+        3 => {
+            write_optional_child16(writer, base, anchor.x_device.as_ref().map(device_bytes));
+            write_optional_child16(writer, base, anchor.y_device.as_ref().map(device_bytes));
+        }
+        _ => {}
+    }
+}
+
+fn anchor_bytes(anchor: &Anchor) -> Result<Vec<u8>, Error> {
+    serialize(|w| {
+        write_anchor(w, anchor);
+        Ok(())
+    })
+}
+
+fn read_optional_anchor(
+    data: &mut FontDataStream,
+    base: &FontDataStream,
+) -> Result<Option<Anchor>, Error> {
+    let offset = data.read_u16()? as usize;
+    read_optional_at(base, offset, read_anchor)
+}
+
+fn write_optional_anchor(
+    writer: &mut FontDataWriter,
+    base: usize,
+    anchor: &Option<Anchor>,
+) -> Result<(), Error> {
+    let bytes = match anchor {
+        Some(anchor) => Some(anchor_bytes(anchor)?),
+        None => None,
+    };
+    write_optional_child16(writer, base, bytes);
+    Ok(())
+}
+
+fn read_mark_array(
+    data: &mut FontDataStream,
+    coverage: &Coverage,
+) -> Result<BTreeMap<u16, MarkRecord>, Error> {
+    let count = data.read_u16()?;
+    let mut marks = BTreeMap::new();
+    for i in 0..count as usize {
+        let mark_class = data.read_u16()?;
+        let offset = data.read_u16()? as usize;
+        let mark_anchor = read_at(data, offset, read_anchor)?;
+        if let Some(&glyph) = coverage.glyphs.get(i) {
+            marks.insert(
+                glyph,
+                MarkRecord {
+                    mark_class,
+                    mark_anchor,
+                },
+            );
+        }
+    }
+    Ok(marks)
+}
+
+fn mark_array_bytes(marks: &BTreeMap<u16, MarkRecord>) -> Result<Vec<u8>, Error> {
+    serialize(|w| {
+        w.write_u16(marks.len() as u16);
+        for mark in marks.values() {
+            w.write_u16(mark.mark_class);
+            write_child16(w, 0, anchor_bytes(&mark.mark_anchor)?);
+        }
+        Ok(())
+    })
+}
+
+/// Reads a base, mark2 or component array: one row of `class_count` anchors per record.
+fn read_anchor_matrix(
+    data: &mut FontDataStream,
+    class_count: u16,
+) -> Result<Vec<Vec<Option<Anchor>>>, Error> {
+    let base = data.substream(0)?;
+    let count = data.read_u16()?;
+    (0..count)
+        .map(|_| {
+            (0..class_count)
+                .map(|_| read_optional_anchor(data, &base))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect()
+}
+
+fn anchor_matrix_bytes(rows: &[&Vec<Option<Anchor>>]) -> Result<Vec<u8>, Error> {
+    serialize(|w| {
+        w.write_u16(rows.len() as u16);
+        for row in rows {
+            for anchor in row.iter() {
+                write_optional_anchor(w, 0, anchor)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Extracts a single `GPOS` subtable of the given lookup type.
+pub fn read_positioning_subtable(
+    data: &mut FontDataStream,
+    lookup_type: u16,
+) -> Result<PositioningSubtable, Error> {
+    if lookup_type == 7 {
+        return read_sequence_context(data).map(PositioningSubtable::Context);
+    }
+    if lookup_type == 8 {
+        return read_chained_sequence_context(data).map(PositioningSubtable::ChainContext);
+    }
+
+    let base = data.substream(0)?;
+    let format = data.read_u16()?;
+    let coverage_offset = data.read_u16()? as usize;
+    let coverage = read_at(data, coverage_offset, read_coverage)?;
+    match (lookup_type, format) {
+        (1, 1) => {
+            let value_format = data.read_u16()?;
+            let value = read_value_record(data, value_format, &base)?;
+            Ok(PositioningSubtable::Single {
+                value_format,
+                values: coverage
+                    .glyphs
+                    .iter()
+                    .map(|&g| (g, value.clone()))
+                    .collect(),
+            })
+        }
+        (1, 2) => {
+            let value_format = data.read_u16()?;
+            let count = data.read_u16()?;
+            let mut values = BTreeMap::new();
+            for i in 0..count as usize {
+                let value = read_value_record(data, value_format, &base)?;
+                if let Some(&glyph) = coverage.glyphs.get(i) {
+                    values.insert(glyph, value);
+                }
+            }
+            Ok(PositioningSubtable::Single {
+                value_format,
+                values,
+            })
+        }
+        (2, 1) => {
+            let value_format1 = data.read_u16()?;
+            let value_format2 = data.read_u16()?;
+            let count = data.read_u16()?;
+            let mut pair_sets = BTreeMap::new();
+            for i in 0..count as usize {
+                let offset = data.read_u16()? as usize;
+                let pairs = read_at(data, offset, |set| {
+                    let count = set.read_u16()?;
+                    (0..count)
+                        .map(|_| {
+                            Ok(PairValueRecord {
+                                second_glyph: set.read_u16()?,
+                                value_record1: read_value_record(set, value_format1, &base)?,
+                                value_record2: read_value_record(set, value_format2, &base)?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                })?;
+                if let Some(&glyph) = coverage.glyphs.get(i) {
+                    pair_sets.insert(glyph, pairs);
+                }
+            }
+            Ok(PositioningSubtable::Pair {
+                value_format1,
+                value_format2,
+                pair_sets,
+            })
+        }
+        (2, 2) => {
+            let value_format1 = data.read_u16()?;
+            let value_format2 = data.read_u16()?;
+            let class_def1_offset = data.read_u16()? as usize;
+            let class_def2_offset = data.read_u16()? as usize;
+            let class1_count = data.read_u16()?;
+            let class2_count = data.read_u16()?;
+            let mut class1_records = Vec::with_capacity(class1_count as usize);
+            for _ in 0..class1_count {
+                let row = (0..class2_count)
+                    .map(|_| {
+                        Ok((
+                            read_value_record(data, value_format1, &base)?,
+                            read_value_record(data, value_format2, &base)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                class1_records.push(row);
+            }
+            Ok(PositioningSubtable::PairClass {
+                value_format1,
+                value_format2,
+                coverage,
+                class_def1: read_optional_at(&base, class_def1_offset, read_class_def)?
+                    .unwrap_or_default(),
+                class_def2: read_optional_at(&base, class_def2_offset, read_class_def)?
+                    .unwrap_or_default(),
+                class1_records,
+            })
+        }
+        (3, 1) => {
+            let count = data.read_u16()?;
+            let mut records = BTreeMap::new();
+            for i in 0..count as usize {
+                let record = EntryExitRecord {
+                    entry_anchor: read_optional_anchor(data, &base)?,
+                    exit_anchor: read_optional_anchor(data, &base)?,
+                };
+                if let Some(&glyph) = coverage.glyphs.get(i) {
+                    records.insert(glyph, record);
+                }
+            }
+            Ok(PositioningSubtable::Cursive(records))
+        }
+        (4..=6, 1) => {
+            // The first coverage offset read above is the mark coverage.
+            let base_coverage_offset = data.read_u16()? as usize;
+            let mark_class_count = data.read_u16()?;
+            let mark_array_offset = data.read_u16()? as usize;
+            let base_array_offset = data.read_u16()? as usize;
+            let base_coverage = read_at(&base, base_coverage_offset, read_coverage)?;
+            let marks = read_at(&base, mark_array_offset, |d| read_mark_array(d, &coverage))?;
+            match lookup_type {
+                5 => {
+                    let ligatures = read_at(&base, base_array_offset, |array| {
+                        let count = array.read_u16()?;
+                        let mut ligatures = BTreeMap::new();
+                        for i in 0..count as usize {
+                            let offset = array.read_u16()? as usize;
+                            let components = read_at(array, offset, |attach| {
+                                read_anchor_matrix(attach, mark_class_count)
+                            })?;
+                            if let Some(&glyph) = base_coverage.glyphs.get(i) {
+                                ligatures.insert(glyph, components);
+                            }
+                        }
+                        Ok(ligatures)
+                    })?;
+                    Ok(PositioningSubtable::MarkToLigature {
+                        mark_class_count,
+                        marks,
+                        ligatures,
+                    })
+                }
+                _ => {
+                    let rows = read_at(&base, base_array_offset, |d| {
+                        read_anchor_matrix(d, mark_class_count)
+                    })?;
+                    let rows: BTreeMap<_, _> =
+                        base_coverage.glyphs.iter().copied().zip(rows).collect();
+                    Ok(if lookup_type == 4 {
+                        PositioningSubtable::MarkToBase {
+                            mark_class_count,
+                            marks,
+                            bases: rows,
+                        }
+                    } else {
+                        PositioningSubtable::MarkToMark {
+                            mark_class_count,
+                            marks,
+                            mark2s: rows,
+                        }
+                    })
+                }
+            }
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+/// Serializes a single `GPOS` subtable.
+pub fn write_positioning_subtable(
+    writer: &mut FontDataWriter,
+    subtable: &PositioningSubtable,
+) -> Result<(), Error> {
+    match subtable {
+        PositioningSubtable::Single {
+            value_format,
+            values,
+        } => {
+            let coverage = coverage_bytes(&Coverage::new(values.keys().copied()));
+            let mut records = values.values();
+            let first = records.next().cloned().unwrap_or_default();
+            if records.all(|v| *v == first) {
+                writer.write_u16(1);
+                write_child16(writer, 0, coverage);
+                writer.write_u16(*value_format);
+                write_value_record(writer, &first, *value_format, 0);
+            } else {
+                writer.write_u16(2);
+                write_child16(writer, 0, coverage);
+                writer.write_u16(*value_format);
+                writer.write_u16(values.len() as u16);
+                for value in values.values() {
+                    write_value_record(writer, value, *value_format, 0);
+                }
+            }
+        }
+        PositioningSubtable::Pair {
+            value_format1,
+            value_format2,
+            pair_sets,
+        } => {
+            writer.write_u16(1);
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(pair_sets.keys().copied())),
+            );
+            writer.write_u16(*value_format1);
+            writer.write_u16(*value_format2);
+            writer.write_u16(pair_sets.len() as u16);
+            // Device offsets in pair sets are measured from the subtable, so
+            // the sets are written inline rather than as shared subtables.
+            let placeholders: Vec<_> = pair_sets
+                .keys()
+                .map(|_| writer.reserve_offset16(0))
+                .collect();
+            for (pairs, placeholder) in pair_sets.values().zip(placeholders) {
+                writer.patch_offset_here(placeholder)?;
+                writer.write_u16(pairs.len() as u16);
+                for pair in pairs {
+                    writer.write_u16(pair.second_glyph);
+                    write_value_record(writer, &pair.value_record1, *value_format1, 0);
+                    write_value_record(writer, &pair.value_record2, *value_format2, 0);
+                }
+            }
+        }
+        PositioningSubtable::PairClass {
+            value_format1,
+            value_format2,
+            coverage,
+            class_def1,
+            class_def2,
+            class1_records,
+        } => {
+            writer.write_u16(2);
+            write_child16(writer, 0, coverage_bytes(coverage));
+            writer.write_u16(*value_format1);
+            writer.write_u16(*value_format2);
+            write_child16(writer, 0, class_def_bytes(class_def1));
+            write_child16(writer, 0, class_def_bytes(class_def2));
+            writer.write_u16(class1_records.len() as u16);
+            writer.write_u16(class1_records.first().map_or(0, |r| r.len()) as u16);
+            for row in class1_records {
+                for (value1, value2) in row {
+                    write_value_record(writer, value1, *value_format1, 0);
+                    write_value_record(writer, value2, *value_format2, 0);
+                }
+            }
+        }
+        PositioningSubtable::Cursive(records) => {
+            writer.write_u16(1);
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(records.keys().copied())),
+            );
+            writer.write_u16(records.len() as u16);
+            for record in records.values() {
+                write_optional_anchor(writer, 0, &record.entry_anchor)?;
+                write_optional_anchor(writer, 0, &record.exit_anchor)?;
+            }
+        }
+        PositioningSubtable::MarkToBase {
+            mark_class_count,
+            marks,
+            bases: rows,
+        }
+        | PositioningSubtable::MarkToMark {
+            mark_class_count,
+            marks,
+            mark2s: rows,
+        } => {
+            writer.write_u16(1);
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(marks.keys().copied())),
+            );
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(rows.keys().copied())),
+            );
+            writer.write_u16(*mark_class_count);
+            write_child16(writer, 0, mark_array_bytes(marks)?);
+            let rows: Vec<_> = rows.values().collect();
+            write_child16(writer, 0, anchor_matrix_bytes(&rows)?);
+        }
+        PositioningSubtable::MarkToLigature {
+            mark_class_count,
+            marks,
+            ligatures,
+        } => {
+            writer.write_u16(1);
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(marks.keys().copied())),
+            );
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(ligatures.keys().copied())),
+            );
+            writer.write_u16(*mark_class_count);
+            write_child16(writer, 0, mark_array_bytes(marks)?);
+            let array = serialize(|w| {
+                w.write_u16(ligatures.len() as u16);
+                for components in ligatures.values() {
+                    let rows: Vec<_> = components.iter().collect();
+                    write_child16(w, 0, anchor_matrix_bytes(&rows)?);
+                }
+                Ok(())
+            })?;
+            write_child16(writer, 0, array);
+        }
+        PositioningSubtable::Context(context) => write_sequence_context(writer, context)?,
+        PositioningSubtable::ChainContext(context) => {
+            write_chained_sequence_context(writer, context)?
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,308 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `GSUB` (Glyph Substitution) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::{FontDataWriter, serialize};
+
+use super::layout::{
+    ChainedSequenceContext, Coverage, LayoutTable, SequenceContext, coverage_bytes, read_at,
+    read_chained_sequence_context, read_coverage, read_layout_table, read_sequence_context,
+    write_chained_sequence_context, write_child16, write_layout_table, write_sequence_context,
+};
+
+/// Lookup type of extension substitution subtables.
+pub const GSUB_EXTENSION_TYPE: u16 = 7;
+
+/// Glyph substitution table.
+pub type GsubTable = LayoutTable<SubstitutionSubtable>;
+
+/// A ligature and the components following its first glyph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ligature {
+    /// Glyph id of the ligature.
+    pub ligature_glyph: u16,
+    /// Component glyphs after the first (covered) one.
+    pub component_glyph_ids: Vec<u16>,
+}
+
+/// Reverse chaining contextual single substitution (lookup type 8).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ReverseChainSingleSubst {
+    /// Glyphs to substitute.
+    pub coverage: Coverage,
+    /// Coverage of each backtrack position, nearest first.
+    pub backtrack_coverages: Vec<Coverage>,
+    /// Coverage of each lookahead position.
+    pub lookahead_coverages: Vec<Coverage>,
+    /// Substitutes in coverage order.
+    pub substitute_glyph_ids: Vec<u16>,
+}
+
+/// A `GSUB` lookup subtable. Extension subtables are unwrapped on reading.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubstitutionSubtable {
+    /// Type 1: replace one glyph with another.
+    Single(BTreeMap<u16, u16>),
+    /// Type 2: replace one glyph with a sequence.
+    Multiple(BTreeMap<u16, Vec<u16>>),
+    /// Type 3: replace one glyph with one of several alternates.
+    Alternate(BTreeMap<u16, Vec<u16>>),
+    /// Type 4: replace a sequence with a ligature, keyed by the first glyph.
+    Ligature(BTreeMap<u16, Vec<Ligature>>),
+    /// Type 5: contextual substitution.
+    Context(SequenceContext),
+    /// Type 6: chained contextual substitution.
+    ChainContext(ChainedSequenceContext),
+    /// Type 8: reverse chaining contextual single substitution.
+    ReverseChainSingle(ReverseChainSingleSubst),
+}
+
+impl SubstitutionSubtable {
+    /// Returns the lookup type of the subtable.
+    pub fn lookup_type(&self) -> u16 {
+        match self {
+            SubstitutionSubtable::Single(_) => 1,
+            SubstitutionSubtable::Multiple(_) => 2,
+            SubstitutionSubtable::Alternate(_) => 3,
+            SubstitutionSubtable::Ligature(_) => 4,
+            SubstitutionSubtable::Context(_) => 5,
+            SubstitutionSubtable::ChainContext(_) => 6,
+            SubstitutionSubtable::ReverseChainSingle(_) => 8,
+        }
+    }
+}
+
+/// Extracts the `GSUB` table from the provided data stream.
+pub fn read_gsub(data: &mut FontDataStream) -> Result<GsubTable, Error> {
+    read_layout_table(data, GSUB_EXTENSION_TYPE, read_substitution_subtable)
+}
+
+/// Serializes the `GSUB` table.
+pub fn write_gsub(writer: &mut FontDataWriter, gsub: &GsubTable) -> Result<(), Error> {
+    write_layout_table(
+        writer,
+        gsub,
+        GSUB_EXTENSION_TYPE,
+        write_substitution_subtable,
+    )
+}
+
+/// Reads a glyph array behind each offset of an offset array, keyed by coverage.
+fn read_sequences(
+    data: &mut FontDataStream,
+    coverage: &Coverage,
+) -> Result<BTreeMap<u16, Vec<u16>>, Error> {
+    let count = data.read_u16()?;
+    let mut map = BTreeMap::new();
+    for i in 0..count as usize {
+        let offset = data.read_u16()? as usize;
+        let glyphs = read_at(data, offset, |d| {
+            let count = d.read_u16()?;
+            (0..count)
+                .map(|_| d.read_u16())
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        if let Some(&glyph) = coverage.glyphs.get(i) {
+            map.insert(glyph, glyphs);
+        }
+    }
+    Ok(map)
+}
+
+fn write_sequences(writer: &mut FontDataWriter, map: &BTreeMap<u16, Vec<u16>>) {
+    writer.write_u16(1);
+    write_child16(
+        writer,
+        0,
+        coverage_bytes(&Coverage::new(map.keys().copied())),
+    );
+    writer.write_u16(map.len() as u16);
+    for glyphs in map.values() {
+        let mut sequence = FontDataWriter::new();
+        sequence.write_u16(glyphs.len() as u16);
+        for &glyph in glyphs {
+            sequence.write_u16(glyph);
+        }
+        write_child16(writer, 0, sequence.as_bytes().to_vec());
+    }
+}
+
+/// Extracts a single `GSUB` subtable of the given lookup type.
+pub fn read_substitution_subtable(
+    data: &mut FontDataStream,
+    lookup_type: u16,
+) -> Result<SubstitutionSubtable, Error> {
+    if lookup_type == 5 {
+        return read_sequence_context(data).map(SubstitutionSubtable::Context);
+    }
+    if lookup_type == 6 {
+        return read_chained_sequence_context(data).map(SubstitutionSubtable::ChainContext);
+    }
+
+    let format = data.read_u16()?;
+    if lookup_type == 8 {
+        let base = data.substream(0)?;
+        let coverage_offset = data.read_u16()? as usize;
+        let read_coverages = |data: &mut FontDataStream| -> Result<Vec<Coverage>, Error> {
+            let count = data.read_u16()?;
+            (0..count)
+                .map(|_| {
+                    let offset = data.read_u16()? as usize;
+                    read_at(&base, offset, read_coverage)
+                })
+                .collect()
+        };
+        let backtrack_coverages = read_coverages(data)?;
+        let lookahead_coverages = read_coverages(data)?;
+        let count = data.read_u16()?;
+        return Ok(SubstitutionSubtable::ReverseChainSingle(
+            ReverseChainSingleSubst {
+                coverage: read_at(data, coverage_offset, read_coverage)?,
+                backtrack_coverages,
+                lookahead_coverages,
+                substitute_glyph_ids: (0..count)
+                    .map(|_| data.read_u16())
+                    .collect::<Result<_, _>>()?,
+            },
+        ));
+    }
+
+    let coverage_offset = data.read_u16()? as usize;
+    let coverage = read_at(data, coverage_offset, read_coverage)?;
+    match (lookup_type, format) {
+        (1, 1) => {
+            let delta = data.read_i16()?;
+            Ok(SubstitutionSubtable::Single(
+                coverage
+                    .glyphs
+                    .iter()
+                    .map(|&g| (g, g.wrapping_add(delta as u16)))
+                    .collect(),
+            ))
+        }
+        (1, 2) => {
+            let count = data.read_u16()?;
+            let mut map = BTreeMap::new();
+            for i in 0..count as usize {
+                let substitute = data.read_u16()?;
+                if let Some(&glyph) = coverage.glyphs.get(i) {
+                    map.insert(glyph, substitute);
+                }
+            }
+            Ok(SubstitutionSubtable::Single(map))
+        }
+        (2, 1) => read_sequences(data, &coverage).map(SubstitutionSubtable::Multiple),
+        (3, 1) => read_sequences(data, &coverage).map(SubstitutionSubtable::Alternate),
+        (4, 1) => {
+            let count = data.read_u16()?;
+            let mut map = BTreeMap::new();
+            for i in 0..count as usize {
+                let offset = data.read_u16()? as usize;
+                let ligatures = read_at(data, offset, |set| {
+                    let count = set.read_u16()?;
+                    let mut ligatures = Vec::with_capacity(count as usize);
+                    for _ in 0..count {
+                        let offset = set.read_u16()? as usize;
+                        ligatures.push(read_at(set, offset, |lig| {
+                            let ligature_glyph = lig.read_u16()?;
+                            let count = lig.read_u16()?.saturating_sub(1);
+                            Ok(Ligature {
+                                ligature_glyph,
+                                component_glyph_ids: (0..count)
+                                    .map(|_| lig.read_u16())
+                                    .collect::<Result<_, _>>()?,
+                            })
+                        })?);
+                    }
+                    Ok(ligatures)
+                })?;
+                if let Some(&glyph) = coverage.glyphs.get(i) {
+                    map.insert(glyph, ligatures);
+                }
+            }
+            Ok(SubstitutionSubtable::Ligature(map))
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+/// Serializes a single `GSUB` subtable.
+pub fn write_substitution_subtable(
+    writer: &mut FontDataWriter,
+    subtable: &SubstitutionSubtable,
+) -> Result<(), Error> {
+    match subtable {
+        SubstitutionSubtable::Single(map) => {
+            let coverage = coverage_bytes(&Coverage::new(map.keys().copied()));
+            let mut deltas = map.iter().map(|(&g, &s)| s.wrapping_sub(g));
+            let first = deltas.next().unwrap_or(0);
+            if deltas.all(|d| d == first) {
+                writer.write_u16(1);
+                write_child16(writer, 0, coverage);
+                writer.write_u16(first);
+            } else {
+                writer.write_u16(2);
+                write_child16(writer, 0, coverage);
+                writer.write_u16(map.len() as u16);
+                for &substitute in map.values() {
+                    writer.write_u16(substitute);
+                }
+            }
+        }
+        SubstitutionSubtable::Multiple(map) | SubstitutionSubtable::Alternate(map) => {
+            write_sequences(writer, map);
+        }
+        SubstitutionSubtable::Ligature(map) => {
+            writer.write_u16(1);
+            write_child16(
+                writer,
+                0,
+                coverage_bytes(&Coverage::new(map.keys().copied())),
+            );
+            writer.write_u16(map.len() as u16);
+            for ligatures in map.values() {
+                let set = serialize(|set| {
+                    set.write_u16(ligatures.len() as u16);
+                    for ligature in ligatures {
+                        let mut lig = FontDataWriter::new();
+                        lig.write_u16(ligature.ligature_glyph);
+                        lig.write_u16(ligature.component_glyph_ids.len() as u16 + 1);
+                        for &component in &ligature.component_glyph_ids {
+                            lig.write_u16(component);
+                        }
+                        write_child16(set, 0, lig.as_bytes().to_vec());
+                    }
+                    Ok(())
+                })?;
+                write_child16(writer, 0, set);
+            }
+        }
+        SubstitutionSubtable::Context(context) => write_sequence_context(writer, context)?,
+        SubstitutionSubtable::ChainContext(context) => {
+            write_chained_sequence_context(writer, context)?
+        }
+        SubstitutionSubtable::ReverseChainSingle(subst) => {
+            writer.write_u16(1);
+            write_child16(writer, 0, coverage_bytes(&subst.coverage));
+            for coverages in [&subst.backtrack_coverages, &subst.lookahead_coverages] {
+                writer.write_u16(coverages.len() as u16);
+                for coverage in coverages {
+                    write_child16(writer, 0, coverage_bytes(coverage));
+                }
+            }
+            writer.write_u16(subst.substitute_glyph_ids.len() as u16);
+            for &glyph in &subst.substitute_glyph_ids {
+                writer.write_u16(glyph);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,435 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `gvar` (Glyph Variations) table representation and utilities for the Aurora Font Library.
+
+use std::collections::HashMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::glyf::Glyph;
+use super::hmtx::HmtxTable;
+use super::variation::{TupleVariation, read_tuple_variations, write_tuple_variations};
+
+/// Number of phantom points appended to every glyph's points: left and right
+/// side bearing points, then top and bottom origin points.
+pub const PHANTOM_POINT_COUNT: usize = 4;
+
+/// Glyph variations table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GvarTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Number of `fvar` axes.
+    pub axis_count: u16,
+    /// Tuple variations of each glyph; shared tuples are resolved into the
+    /// peaks of the variations.
+    pub glyph_variations: Vec<Vec<TupleVariation>>,
+}
+
+impl GvarTable {
+    /// Returns the tuple variations of a glyph (empty for glyphs without variations).
+    pub fn variations(&self, glyph_id: u16) -> &[TupleVariation] {
+        self.glyph_variations
+            .get(glyph_id as usize)
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Extracts the `gvar` table from the provided data stream.
+pub fn read_gvar(data: &mut FontDataStream) -> Result<GvarTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let axis_count = data.read_u16()?;
+    let shared_tuple_count = data.read_u16()?;
+    let shared_tuples_offset = data.read_u32()? as usize;
+    let glyph_count = data.read_u16()? as usize;
+    let flags = data.read_u16()?;
+    let array_offset = data.read_u32()? as usize;
+    let offsets = (0..=glyph_count)
+        .map(|_| match flags & 1 {
+            0 => data.read_u16().map(|offset| offset as usize * 2),
+            _ => data.read_u32().map(|offset| offset as usize),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut shared = data.substream(shared_tuples_offset)?;
+    let shared_tuples = (0..shared_tuple_count)
+        .map(|_| {
+            (0..axis_count)
+                .map(|_| shared.read_f2dot14())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut glyph_variations = Vec::with_capacity(glyph_count);
+    for range in offsets.windows(2) {
+        if range[1] <= range[0] {
+            glyph_variations.push(Vec::new());
+            continue;
+        }
+        let glyph_data = data.slice_at(array_offset + range[0], range[1] - range[0])?;
+        let base = FontDataStream::new(glyph_data);
+        glyph_variations.push(read_tuple_variations(
+            &mut base.substream(0)?,
+            &base,
+            axis_count as usize,
+            &shared_tuples,
+            2,
+        )?);
+    }
+    Ok(GvarTable {
+        major_version,
+        minor_version,
+        axis_count,
+        glyph_variations,
+    })
+}
+
+/// Serializes the `gvar` table. Peaks used by more than one variation are
+/// stored as shared tuples.
+pub fn write_gvar(writer: &mut FontDataWriter, gvar: &GvarTable) -> Result<(), Error> {
+    let mut usage: HashMap<Vec<i16>, (usize, &Vec<f32>)> = HashMap::new();
+    for tuple in gvar.glyph_variations.iter().flatten() {
+        let key = tuple
+            .peak
+            .iter()
+            .map(|&c| (c * 16384.0).round() as i16)
+            .collect();
+        usage.entry(key).or_insert((0, &tuple.peak)).0 += 1;
+    }
+    let mut shared: Vec<(Vec<i16>, usize, &Vec<f32>)> = usage
+        .into_iter()
+        .filter(|(_, (count, _))| *count > 1)
+        .map(|(key, (count, peak))| (key, count, peak))
+        .collect();
+    shared.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    shared.truncate(0x0FFF);
+    let shared_tuples: Vec<Vec<f32>> = shared
+        .into_iter()
+        .map(|(_, _, peak)| peak.clone())
+        .collect();
+
+    let mut glyph_data = Vec::with_capacity(gvar.glyph_variations.len());
+    for tuples in &gvar.glyph_variations {
+        let mut data = FontDataWriter::new();
+        if !tuples.is_empty() {
+            write_tuple_variations(&mut data, 0, tuples, &shared_tuples)?;
+            data.align(2);
+        }
+        glyph_data.push(data.into_bytes()?);
+    }
+    let total: usize = glyph_data.iter().map(Vec::len).sum();
+    let long_offsets = total > 0x1FFFE;
+
+    let offsets_size = (glyph_data.len() + 1) * if long_offsets { 4 } else { 2 };
+    let shared_tuples_offset = 20 + offsets_size;
+    let array_offset = shared_tuples_offset + shared_tuples.len() * gvar.axis_count as usize * 2;
+    writer.write_u16(gvar.major_version);
+    writer.write_u16(gvar.minor_version);
+    writer.write_u16(gvar.axis_count);
+    writer.write_u16(shared_tuples.len() as u16);
+    writer.write_u32(shared_tuples_offset as u32);
+    writer.write_u16(glyph_data.len() as u16);
+    writer.write_u16(long_offsets as u16);
+    writer.write_u32(array_offset as u32);
+    let mut offset = 0;
+    for data in std::iter::once(&Vec::new()).chain(&glyph_data) {
+        offset += data.len();
+        if long_offsets {
+            writer.write_u32(offset as u32);
+        } else {
+            writer.write_u16((offset / 2) as u16);
+        }
+    }
+    for peak in &shared_tuples {
+        for &coord in peak {
+            writer.write_f2dot14(coord);
+        }
+    }
+    for data in &glyph_data {
+        writer.write_bytes(data);
+    }
+    Ok(())
+}
+
+/// Infers the deltas of untouched points from their touched neighbours in
+/// the same contour (IUP). Points after the last contour, such as phantom
+/// points, are left unchanged.
+///
+/// # Arguments
+/// * `points` - Original point coordinates.
+/// * `end_points` - Index of the last point of each contour.
+/// * `touched` - Whether each point has an explicit delta.
+/// * `deltas` - Deltas of each point; untouched entries are overwritten.
+pub fn interpolate_untouched(
+    points: &[(f32, f32)],
+    end_points: &[u16],
+    touched: &[bool],
+    deltas: &mut [(f32, f32)],
+) {
+    let mut start = 0;
+    for &end in end_points {
+        let end = end as usize;
+        if end >= points.len() || end < start {
+            break;
+        }
+        let contour: Vec<usize> = (start..=end).filter(|&i| touched[i]).collect();
+        if !contour.is_empty() && contour.len() <= end - start {
+            for (k, &first) in contour.iter().enumerate() {
+                let next = contour[(k + 1) % contour.len()];
+                // Untouched points between `first` and `next`, wrapping around.
+                let mut i = if first == end { start } else { first + 1 };
+                while i != next {
+                    deltas[i] = (
+                        interpolate(
+                            points[i].0,
+                            points[first].0,
+                            points[next].0,
+                            deltas[first].0,
+                            deltas[next].0,
+                        ),
+                        interpolate(
+                            points[i].1,
+                            points[first].1,
+                            points[next].1,
+                            deltas[first].1,
+                            deltas[next].1,
+                        ),
+                    );
+                    i = if i == end { start } else { i + 1 };
+                }
+            }
+        }
+        start = end + 1;
+    }
+}
+
+/// Interpolates the delta of coordinate `c` from the neighbouring touched
+/// coordinates `c1` and `c2` and their deltas.
+fn interpolate(c: f32, c1: f32, c2: f32, d1: f32, d2: f32) -> f32 {
+    if c1 == c2 {
+        return if d1 == d2 { d1 } else { 0.0 };
+    }
+    let (c1, d1, c2, d2) = if c1 < c2 {
+        (c1, d1, c2, d2)
+    } else {
+        (c2, d2, c1, d1)
+    };
+    if c <= c1 {
+        d1
+    } else if c >= c2 {
+        d2
+    } else {
+        d1 + (c - c1) * (d2 - d1) / (c2 - c1)
+    }
+}
+
+/// Returns the delta of every point for one tuple variation, inferring the
+/// deltas of untouched points.
+///
+/// # Arguments
+/// * `tuple` - The tuple variation.
+/// * `points` - Original coordinates of the glyph points followed by its
+///   phantom points.
+/// * `end_points` - Contour end points of a simple glyph; empty for composite
+///   glyphs, whose untouched points keep a zero delta.
+pub fn tuple_deltas(
+    tuple: &TupleVariation,
+    points: &[(f32, f32)],
+    end_points: &[u16],
+) -> Vec<(f32, f32)> {
+    let mut deltas = vec![(0.0, 0.0); points.len()];
+    let (Some(x), Some(y)) = (tuple.deltas.first(), tuple.deltas.get(1)) else {
+        return deltas;
+    };
+    match &tuple.point_numbers {
+        None => {
+            for (delta, (&dx, &dy)) in deltas.iter_mut().zip(x.iter().zip(y)) {
+                *delta = (dx as f32, dy as f32);
+            }
+        }
+        Some(point_numbers) => {
+            let mut touched = vec![false; points.len()];
+            for (&point, (&dx, &dy)) in point_numbers.iter().zip(x.iter().zip(y)) {
+                if let Some(delta) = deltas.get_mut(point as usize) {
+                    *delta = (dx as f32, dy as f32);
+                    touched[point as usize] = true;
+                }
+            }
+            interpolate_untouched(points, end_points, &touched, &mut deltas);
+        }
+    }
+    deltas
+}
+
+/// Returns the accumulated delta of every point at normalized coordinates `coords`.
+pub fn glyph_deltas(
+    variations: &[TupleVariation],
+    coords: &[f32],
+    points: &[(f32, f32)],
+    end_points: &[u16],
+) -> Vec<(f32, f32)> {
+    let mut total = vec![(0.0, 0.0); points.len()];
+    for tuple in variations {
+        let scalar = tuple.scalar(coords);
+        if scalar == 0.0 {
+            continue;
+        }
+        for (sum, delta) in total
+            .iter_mut()
+            .zip(tuple_deltas(tuple, points, end_points))
+        {
+            sum.0 += delta.0 * scalar;
+            sum.1 += delta.1 * scalar;
+        }
+    }
+    total
+}
+
+/// Returns the variable points of a glyph: outline points of a simple glyph
+/// or component offsets of a composite glyph, without phantom points.
+/// Components attached by point matching contribute `(0, 0)`.
+pub fn glyph_points(glyph: &Glyph) -> Vec<(f32, f32)> {
+    match glyph {
+        Glyph::Empty => Vec::new(),
+        Glyph::Simple(simple) => simple
+            .points
+            .iter()
+            .map(|p| (p.x as f32, p.y as f32))
+            .collect(),
+        Glyph::Composite(composite) => composite
+            .components
+            .iter()
+            .map(|c| match c.args_are_xy_values() {
+                true => (c.arg1 as f32, c.arg2 as f32),
+                false => (0.0, 0.0),
+            })
+            .collect(),
+    }
+}
+
+/// Returns the phantom points of a glyph from its metrics. The vertical
+/// phantom points are at 0 without `vmtx`.
+pub fn phantom_points(
+    glyph: &Glyph,
+    hmtx: &HmtxTable,
+    vmtx: Option<&HmtxTable>,
+    glyph_id: u16,
+) -> [(f32, f32); PHANTOM_POINT_COUNT] {
+    let (x_min, y_max) = glyph.header().map_or((0, 0), |h| (h.x_min, h.y_max));
+    let left = (x_min - hmtx.lsb(glyph_id)) as f32;
+    let right = left + hmtx.advance_width(glyph_id) as f32;
+    let (top, bottom) = match vmtx {
+        Some(vmtx) => {
+            let top = (y_max + vmtx.lsb(glyph_id)) as f32;
+            (top, top - vmtx.advance_width(glyph_id) as f32)
+        }
+        None => (0.0, 0.0),
+    };
+    [(left, 0.0), (right, 0.0), (0.0, top), (0.0, bottom)]
+}
+
+/// Sets the variable points of a glyph, rounding to font units. Extra points
+/// are ignored.
+pub fn set_glyph_points(glyph: &mut Glyph, points: &[(f32, f32)]) {
+    match glyph {
+        Glyph::Empty => {}
+        Glyph::Simple(simple) => {
+            for (point, &(x, y)) in simple.points.iter_mut().zip(points) {
+                point.x = x.round() as i16;
+                point.y = y.round() as i16;
+            }
+        }
+        Glyph::Composite(composite) => {
+            for (component, &(x, y)) in composite.components.iter_mut().zip(points) {
+                if component.args_are_xy_values() {
+                    component.arg1 = x.round() as i32;
+                    component.arg2 = y.round() as i32;
+                }
+            }
+        }
+    }
+}
+
+/// A glyph at a location of the design space.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariedGlyph {
+    /// The glyph with varied points or component offsets. The bounds of a
+    /// simple glyph are recomputed; those of a composite glyph are kept, as
+    /// they depend on its varied components.
+    pub glyph: Glyph,
+    /// Varied phantom points, unrounded.
+    pub phantom_points: [(f32, f32); PHANTOM_POINT_COUNT],
+}
+
+impl VariedGlyph {
+    /// Returns the varied advance width.
+    pub fn advance_width(&self) -> f32 {
+        self.phantom_points[1].0 - self.phantom_points[0].0
+    }
+
+    /// Returns the varied advance height (0 without `vmtx`).
+    pub fn advance_height(&self) -> f32 {
+        self.phantom_points[2].1 - self.phantom_points[3].1
+    }
+
+    /// Returns the horizontal origin; the outline is drawn relative to it.
+    pub fn origin(&self) -> f32 {
+        self.phantom_points[0].0
+    }
+}
+
+/// Applies the variations of a glyph at normalized coordinates `coords`,
+/// inferring untouched points and varying the phantom points.
+///
+/// # Arguments
+/// * `glyph` - The default glyph from `glyf`.
+/// * `gvar` - Glyph variations.
+/// * `hmtx` - Horizontal metrics, for the horizontal phantom points.
+/// * `vmtx` - Vertical metrics, for the vertical phantom points.
+/// * `glyph_id` - The glyph id.
+/// * `coords` - Normalized coordinates, one per `fvar` axis.
+pub fn vary_glyph(
+    glyph: &Glyph,
+    gvar: &GvarTable,
+    hmtx: &HmtxTable,
+    vmtx: Option<&HmtxTable>,
+    glyph_id: u16,
+    coords: &[f32],
+) -> VariedGlyph {
+    let mut points = glyph_points(glyph);
+    points.extend(phantom_points(glyph, hmtx, vmtx, glyph_id));
+    let end_points: &[u16] = match glyph {
+        Glyph::Simple(simple) => &simple.end_points,
+        _ => &[],
+    };
+    let deltas = glyph_deltas(gvar.variations(glyph_id), coords, &points, end_points);
+    for (point, delta) in points.iter_mut().zip(deltas) {
+        point.0 += delta.0;
+        point.1 += delta.1;
+    }
+    let mut glyph = glyph.clone();
+    set_glyph_points(&mut glyph, &points);
+    if let Glyph::Simple(simple) = &mut glyph {
+        let bounds = simple.compute_bounds();
+        let header = &mut simple.header;
+        (header.x_min, header.y_min, header.x_max, header.y_max) = bounds;
+    }
+    let mut phantom_points = [(0.0, 0.0); PHANTOM_POINT_COUNT];
+    phantom_points.copy_from_slice(&points[points.len() - PHANTOM_POINT_COUNT..]);
+    VariedGlyph {
+        glyph,
+        phantom_points,
+    }
+}
@@ -0,0 +1,68 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `hdmx` (Horizontal Device Metrics) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Hinted advance widths of every glyph at one size.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeviceRecord {
+    /// Pixels per em the widths were computed for.
+    pub pixel_size: u8,
+    /// Largest of the widths.
+    pub max_width: u8,
+    /// Advance width of each glyph, in pixels.
+    pub widths: Vec<u8>,
+}
+
+/// Horizontal device metrics table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HdmxTable {
+    /// Table version (0).
+    pub version: u16,
+    /// Device records, sorted by size.
+    pub records: Vec<DeviceRecord>,
+}
+
+impl HdmxTable {
+    /// Returns the record for `ppem` pixels per em.
+    pub fn record(&self, ppem: u16) -> Option<&DeviceRecord> {
+        self.records
+            .iter()
+            .find(|record| record.pixel_size as u16 == ppem)
+    }
+
+    /// Returns the hinted advance width of `glyph_id` at `ppem`, in pixels.
+    pub fn advance_width(&self, ppem: u16, glyph_id: u16) -> Option<u8> {
+        self.record(ppem)?.widths.get(glyph_id as usize).copied()
+    }
+}
+
+/// Extracts the `hdmx` table from the provided data stream. `num_glyphs` is
+/// the glyph count from `maxp`.
+pub fn read_hdmx(data: &mut FontDataStream, num_glyphs: u16) -> Result<HdmxTable, Error> {
+    let version = data.read_u16()?;
+    if version != 0 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let num_records = data.read_u16()?;
+    let record_size = data.read_u32()? as usize;
+    if record_size < 2 + num_glyphs as usize {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    let mut records = Vec::with_capacity(num_records as usize);
+    for _ in 0..num_records {
+        let record = data.read_bytes(record_size)?;
+        records.push(DeviceRecord {
+            pixel_size: record[0],
+            max_width: record[1],
+            widths: record[2..2 + num_glyphs as usize].to_vec(),
+        });
+    }
+    Ok(HdmxTable { version, records })
+}
@@ -0,0 +1,110 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `head` (Font Header) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Font header table representation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadTable {
+    /// Major version of the table (1).
+    pub major_version: u16,
+    /// Minor version of the table (0).
+    pub minor_version: u16,
+    /// Font revision set by the font manufacturer.
+    pub font_revision: f32,
+    /// Checksum adjustment for the whole font.
+    pub checksum_adjustment: u32,
+    /// Magic number (0x5F0F3CF5).
+    pub magic_number: u32,
+    /// Header flags.
+    pub flags: u16,
+    /// Units per em (16..=16384).
+    pub units_per_em: u16,
+    /// Creation time in seconds since 1904-01-01.
+    pub created: i64,
+    /// Modification time in seconds since 1904-01-01.
+    pub modified: i64,
+    /// Minimum x of all glyph bounding boxes.
+    pub x_min: i16,
+    /// Minimum y of all glyph bounding boxes.
+    pub y_min: i16,
+    /// Maximum x of all glyph bounding boxes.
+    pub x_max: i16,
+    /// Maximum y of all glyph bounding boxes.
+    pub y_max: i16,
+    /// Mac style bits (bold, italic, ...).
+    pub mac_style: u16,
+    /// Smallest readable size in pixels.
+    pub lowest_rec_ppem: u16,
+    /// Deprecated font direction hint.
+    pub font_direction_hint: i16,
+    /// 0 for short (Offset16) `loca` offsets, 1 for long (Offset32).
+    pub index_to_loc_format: i16,
+    /// Glyph data format (0).
+    pub glyph_data_format: i16,
+}
+
+/// Magic number stored in every `head` table.
+pub const HEAD_MAGIC_NUMBER: u32 = 0x5F0F3CF5;
+
+impl HeadTable {
+    /// Returns whether the `loca` table uses 32-bit offsets.
+    pub fn has_long_loca(&self) -> bool {
+        self.index_to_loc_format != 0
+    }
+}
+
+/// Extracts the `head` table from the provided data stream.
+pub fn read_head(data: &mut FontDataStream) -> Result<HeadTable, Error> {
+    Ok(HeadTable {
+        major_version: data.read_u16()?,
+        minor_version: data.read_u16()?,
+        font_revision: data.read_fixed()?,
+        checksum_adjustment: data.read_u32()?,
+        magic_number: data.read_u32()?,
+        flags: data.read_u16()?,
+        units_per_em: data.read_u16()?,
+        created: ((data.read_u32()? as i64) << 32) | data.read_u32()? as i64,
+        modified: ((data.read_u32()? as i64) << 32) | data.read_u32()? as i64,
+        x_min: data.read_i16()?,
+        y_min: data.read_i16()?,
+        x_max: data.read_i16()?,
+        y_max: data.read_i16()?,
+        mac_style: data.read_u16()?,
+        lowest_rec_ppem: data.read_u16()?,
+        font_direction_hint: data.read_i16()?,
+        index_to_loc_format: data.read_i16()?,
+        glyph_data_format: data.read_i16()?,
+    })
+}
+
+/// Serializes the `head` table.
+pub fn write_head(writer: &mut FontDataWriter, head: &HeadTable) {
+    writer.write_u16(head.major_version);
+    writer.write_u16(head.minor_version);
+    writer.write_fixed(head.font_revision);
+    writer.write_u32(head.checksum_adjustment);
+    writer.write_u32(head.magic_number);
+    writer.write_u16(head.flags);
+    writer.write_u16(head.units_per_em);
+    writer.write_u32((head.created >> 32) as u32);
+    writer.write_u32(head.created as u32);
+    writer.write_u32((head.modified >> 32) as u32);
+    writer.write_u32(head.modified as u32);
+    writer.write_i16(head.x_min);
+    writer.write_i16(head.y_min);
+    writer.write_i16(head.x_max);
+    writer.write_i16(head.y_max);
+    writer.write_u16(head.mac_style);
+    writer.write_u16(head.lowest_rec_ppem);
+    writer.write_i16(head.font_direction_hint);
+    writer.write_i16(head.index_to_loc_format);
+    writer.write_i16(head.glyph_data_format);
+}
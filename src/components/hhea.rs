@@ -0,0 +1,98 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `hhea` (Horizontal Header) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Horizontal header table representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HheaTable {
+    /// Major version of the table (1).
+    pub major_version: u16,
+    /// Minor version of the table (0).
+    pub minor_version: u16,
+    /// Typographic ascent.
+    pub ascender: i16,
+    /// Typographic descent.
+    pub descender: i16,
+    /// Typographic line gap.
+    pub line_gap: i16,
+    /// Maximum advance width in `hmtx`.
+    pub advance_width_max: u16,
+    /// Minimum left side bearing in `hmtx`.
+    pub min_left_side_bearing: i16,
+    /// Minimum right side bearing.
+    pub min_right_side_bearing: i16,
+    /// Maximum extent (`lsb + (xMax - xMin)`).
+    pub x_max_extent: i16,
+    /// Rise of the caret slope.
+    pub caret_slope_rise: i16,
+    /// Run of the caret slope.
+    pub caret_slope_run: i16,
+    /// Caret offset for slanted fonts.
+    pub caret_offset: i16,
+    /// Metric data format (0).
+    pub metric_data_format: i16,
+    /// Number of entries in the `hMetrics` array of `hmtx`.
+    pub number_of_h_metrics: u16,
+}
+
+/// Extracts the `hhea` table from the provided data stream.
+pub fn read_hhea(data: &mut FontDataStream) -> Result<HheaTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    let ascender = data.read_i16()?;
+    let descender = data.read_i16()?;
+    let line_gap = data.read_i16()?;
+    let advance_width_max = data.read_u16()?;
+    let min_left_side_bearing = data.read_i16()?;
+    let min_right_side_bearing = data.read_i16()?;
+    let x_max_extent = data.read_i16()?;
+    let caret_slope_rise = data.read_i16()?;
+    let caret_slope_run = data.read_i16()?;
+    let caret_offset = data.read_i16()?;
+    data.skip(8); // reserved
+    let metric_data_format = data.read_i16()?;
+    let number_of_h_metrics = data.read_u16()?;
+    Ok(HheaTable {
+        major_version,
+        minor_version,
+        ascender,
+        descender,
+        line_gap,
+        advance_width_max,
+        min_left_side_bearing,
+        min_right_side_bearing,
+        x_max_extent,
+        caret_slope_rise,
+        caret_slope_run,
+        caret_offset,
+        metric_data_format,
+        number_of_h_metrics,
+    })
+}
+
+/// Serializes the `hhea` table.
+pub fn write_hhea(writer: &mut FontDataWriter, hhea: &HheaTable) {
+    writer.write_u16(hhea.major_version);
+    writer.write_u16(hhea.minor_version);
+    writer.write_i16(hhea.ascender);
+    writer.write_i16(hhea.descender);
+    writer.write_i16(hhea.line_gap);
+    writer.write_u16(hhea.advance_width_max);
+    writer.write_i16(hhea.min_left_side_bearing);
+    writer.write_i16(hhea.min_right_side_bearing);
+    writer.write_i16(hhea.x_max_extent);
+    writer.write_i16(hhea.caret_slope_rise);
+    writer.write_i16(hhea.caret_slope_run);
+    writer.write_i16(hhea.caret_offset);
+    writer.write_zeros(8);
+    writer.write_i16(hhea.metric_data_format);
+    writer.write_u16(hhea.number_of_h_metrics);
+}
@@ -0,0 +1,97 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `hmtx` (Horizontal Metrics) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Advance width and left side bearing of a single glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LongHorMetric {
+    /// Advance width in font units.
+    pub advance_width: u16,
+    /// Left side bearing in font units.
+    pub lsb: i16,
+}
+
+/// Horizontal metrics table representation.
+///
+/// The first `h_metrics.len()` glyphs have their own advance; the remaining
+/// glyphs repeat the last advance and only store a side bearing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct HmtxTable {
+    /// Paired advance widths and side bearings (`hhea.numberOfHMetrics` entries).
+    pub h_metrics: Vec<LongHorMetric>,
+    /// Side bearings for the remaining glyphs.
+    pub left_side_bearings: Vec<i16>,
+}
+
+impl HmtxTable {
+    /// Returns the number of glyphs covered by the table.
+    pub fn num_glyphs(&self) -> usize {
+        self.h_metrics.len() + self.left_side_bearings.len()
+    }
+
+    /// Returns the metrics of a glyph, or `None` if the glyph is out of range.
+    pub fn metric(&self, glyph_id: u16) -> Option<LongHorMetric> {
+        let index = glyph_id as usize;
+        if let Some(metric) = self.h_metrics.get(index) {
+            return Some(*metric);
+        }
+        let lsb = *self.left_side_bearings.get(index - self.h_metrics.len())?;
+        let advance_width = self.h_metrics.last().map_or(0, |m| m.advance_width);
+        Some(LongHorMetric { advance_width, lsb })
+    }
+
+    /// Returns the advance width of a glyph, or 0 if the glyph is out of range.
+    pub fn advance_width(&self, glyph_id: u16) -> u16 {
+        self.metric(glyph_id).map_or(0, |m| m.advance_width)
+    }
+
+    /// Returns the left side bearing of a glyph, or 0 if the glyph is out of range.
+    pub fn lsb(&self, glyph_id: u16) -> i16 {
+        self.metric(glyph_id).map_or(0, |m| m.lsb)
+    }
+}
+
+/// Extracts the `hmtx` table from the provided data stream.
+///
+/// `number_of_h_metrics` comes from `hhea` and `num_glyphs` from `maxp`.
+pub fn read_hmtx(
+    data: &mut FontDataStream,
+    number_of_h_metrics: u16,
+    num_glyphs: u16,
+) -> Result<HmtxTable, Error> {
+    let mut h_metrics = Vec::with_capacity(number_of_h_metrics as usize);
+    for _ in 0..number_of_h_metrics {
+        h_metrics.push(LongHorMetric {
+            advance_width: data.read_u16()?,
+            lsb: data.read_i16()?,
+        });
+    }
+    let remaining = num_glyphs.saturating_sub(number_of_h_metrics);
+    let mut left_side_bearings = Vec::with_capacity(remaining as usize);
+    for _ in 0..remaining {
+        left_side_bearings.push(data.read_i16()?);
+    }
+    Ok(HmtxTable {
+        h_metrics,
+        left_side_bearings,
+    })
+}
+
+/// Serializes the `hmtx` table.
+pub fn write_hmtx(writer: &mut FontDataWriter, hmtx: &HmtxTable) {
+    for metric in &hmtx.h_metrics {
+        writer.write_u16(metric.advance_width);
+        writer.write_i16(metric.lsb);
+    }
+    for lsb in &hmtx.left_side_bearings {
+        writer.write_i16(*lsb);
+    }
+}
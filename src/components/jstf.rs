@@ -0,0 +1,368 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `JSTF` (Justification) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::tags::script::ScriptTag;
+
+use super::gpos::{GPOS_EXTENSION_TYPE, PositioningSubtable, read_positioning_subtable};
+use super::layout::{Lookup, read_at, read_lookup_list, read_optional_at};
+
+/// Justification table: lookups that shrink or extend lines, per script and
+/// language system.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JstfTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Scripts in tag order.
+    pub scripts: Vec<JstfScriptRecord>,
+}
+
+/// A script with its tag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JstfScriptRecord {
+    /// OpenType script tag.
+    pub script_tag: [u8; 4],
+    /// Justification data of the script.
+    pub script: JstfScript,
+}
+
+/// Justification data of a script.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JstfScript {
+    /// Glyphs that may be inserted to extend a line, such as the Arabic
+    /// kashida.
+    pub extender_glyphs: Vec<u16>,
+    /// Priorities used when no language system matches.
+    pub default_lang_sys: Option<JstfLangSys>,
+    /// Language systems in tag order.
+    pub lang_sys_records: Vec<JstfLangSysRecord>,
+}
+
+/// A language system with its tag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JstfLangSysRecord {
+    /// OpenType language system tag.
+    pub lang_sys_tag: [u8; 4],
+    /// Justification priorities of the language system.
+    pub lang_sys: JstfLangSys,
+}
+
+/// Justification priorities of a language system.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JstfLangSys {
+    /// Priorities, most preferred first.
+    pub priorities: Vec<JstfPriority>,
+}
+
+/// One justification step, for shrinking and for extending a line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JstfPriority {
+    /// Modifications that shrink a line.
+    pub shrinkage: JstfModifications,
+    /// Modifications that extend a line.
+    pub extension: JstfModifications,
+}
+
+/// Lookups to switch on and off, and the limit of the adjustment, for one
+/// direction of a priority. Empty lists are stored as null offsets.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JstfModifications {
+    /// `GSUB` lookup indices to enable.
+    pub gsub_enable: Vec<u16>,
+    /// `GSUB` lookup indices to disable.
+    pub gsub_disable: Vec<u16>,
+    /// `GPOS` lookup indices to enable.
+    pub gpos_enable: Vec<u16>,
+    /// `GPOS` lookup indices to disable.
+    pub gpos_disable: Vec<u16>,
+    /// `GPOS` lookups giving the largest adjustment allowed per glyph.
+    pub max: Vec<Lookup<PositioningSubtable>>,
+}
+
+/// Direction in which a line has to change to reach its target width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JustificationMode {
+    /// The line is too wide.
+    Shrink,
+    /// The line is too narrow.
+    Extend,
+}
+
+/// A priority suggested by [`JstfLangSys::suggest`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct JustificationStep<'a> {
+    /// Index of the priority in the language system.
+    pub priority_index: usize,
+    /// Lookups to switch on and off for the mode.
+    pub modifications: &'a JstfModifications,
+    /// Largest width change the priority's `max` lookups allow for the line,
+    /// in font units, or `None` when the priority sets no limit.
+    pub max_adjustment: Option<f32>,
+}
+
+/// Priorities to apply to bring a line to its target width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Justification<'a> {
+    /// Whether the line is shrunk or extended.
+    pub mode: JustificationMode,
+    /// Priorities in application order. Apply them one at a time, shaping
+    /// and measuring the line after each, and stop once it fits.
+    pub steps: Vec<JustificationStep<'a>>,
+    /// Width, in font units, that the limited steps cannot account for.
+    /// Steps without a limit may still cover it.
+    pub remaining: f32,
+}
+
+impl JstfModifications {
+    /// Returns `true` if the modifications change nothing.
+    pub fn is_empty(&self) -> bool {
+        self.gsub_enable.is_empty()
+            && self.gsub_disable.is_empty()
+            && self.gpos_enable.is_empty()
+            && self.gpos_disable.is_empty()
+            && self.max.is_empty()
+    }
+
+    /// Returns the total horizontal advance change the `max` lookups allow
+    /// for `glyphs`, in font units. Only single adjustment subtables carry
+    /// limits; within a lookup the first subtable covering a glyph applies.
+    pub fn max_advance(&self, glyphs: &[u16]) -> i32 {
+        let mut total = 0;
+        for &glyph_id in glyphs {
+            for lookup in &self.max {
+                let value = lookup.subtables.iter().find_map(|subtable| match subtable {
+                    PositioningSubtable::Single { values, .. } => values.get(&glyph_id),
+                    _ => None,
+                });
+                if let Some(value) = value {
+                    total += value.x_advance as i32;
+                }
+            }
+        }
+        total
+    }
+}
+
+impl JstfPriority {
+    /// Returns the modifications for a justification mode.
+    pub fn modifications(&self, mode: JustificationMode) -> &JstfModifications {
+        match mode {
+            JustificationMode::Shrink => &self.shrinkage,
+            JustificationMode::Extend => &self.extension,
+        }
+    }
+}
+
+impl JstfLangSys {
+    /// Suggests the priorities that bring a shaped line of `glyphs`, measuring
+    /// `line_width`, to `target_width` (both in font units).
+    ///
+    /// Priorities are taken in order, skipping those that change nothing for
+    /// the mode, until their limits cover the difference. Returns `None` when
+    /// the line already fits.
+    pub fn suggest(
+        &self,
+        glyphs: &[u16],
+        line_width: f32,
+        target_width: f32,
+    ) -> Option<Justification<'_>> {
+        let difference = target_width - line_width;
+        if difference == 0.0 {
+            return None;
+        }
+        let mode = if difference > 0.0 {
+            JustificationMode::Extend
+        } else {
+            JustificationMode::Shrink
+        };
+        let mut remaining = difference.abs();
+        let mut steps = Vec::new();
+        for (priority_index, priority) in self.priorities.iter().enumerate() {
+            if remaining <= 0.0 {
+                break;
+            }
+            let modifications = priority.modifications(mode);
+            if modifications.is_empty() {
+                continue;
+            }
+            let max_adjustment = (!modifications.max.is_empty()).then(|| {
+                // Shrinking limits are negative advances; keep the magnitude
+                // in the direction of the mode.
+                let advance = modifications.max_advance(glyphs) as f32;
+                match mode {
+                    JustificationMode::Shrink => (-advance).max(0.0),
+                    JustificationMode::Extend => advance.max(0.0),
+                }
+            });
+            if let Some(adjustment) = max_adjustment {
+                remaining -= adjustment;
+            }
+            steps.push(JustificationStep {
+                priority_index,
+                modifications,
+                max_adjustment,
+            });
+        }
+        Some(Justification {
+            mode,
+            steps,
+            remaining: remaining.max(0.0),
+        })
+    }
+}
+
+impl JstfScript {
+    /// Returns the priorities of a language system, or the script's default
+    /// priorities when the language has none or is `None`.
+    pub fn lang_sys(&self, language: Option<[u8; 4]>) -> Option<&JstfLangSys> {
+        language
+            .and_then(|language| {
+                self.lang_sys_records
+                    .iter()
+                    .find(|record| record.lang_sys_tag == language)
+            })
+            .map(|record| &record.lang_sys)
+            .or(self.default_lang_sys.as_ref())
+    }
+
+    /// Returns `true` if the glyph may be inserted to extend a line.
+    pub fn is_extender(&self, glyph_id: u16) -> bool {
+        self.extender_glyphs.contains(&glyph_id)
+    }
+}
+
+impl JstfTable {
+    /// Returns the justification data of a script.
+    pub fn script(&self, script: ScriptTag) -> Option<&JstfScript> {
+        self.scripts
+            .iter()
+            .find(|record| record.script_tag == script.tag())
+            .map(|record| &record.script)
+    }
+
+    /// Suggests priorities for a shaped line in a script and language; see
+    /// [`JstfLangSys::suggest`].
+    pub fn suggest(
+        &self,
+        script: ScriptTag,
+        language: Option<[u8; 4]>,
+        glyphs: &[u16],
+        line_width: f32,
+        target_width: f32,
+    ) -> Option<Justification<'_>> {
+        self.script(script)?
+            .lang_sys(language)?
+            .suggest(glyphs, line_width, target_width)
+    }
+}
+
+/// Extracts the `JSTF` table from the provided data stream.
+pub fn read_jstf(data: &mut FontDataStream) -> Result<JstfTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let count = data.read_u16()?;
+    let scripts = (0..count)
+        .map(|_| {
+            let script_tag = data.read_tag()?;
+            let offset = data.read_u16()? as usize;
+            Ok(JstfScriptRecord {
+                script_tag,
+                script: read_at(data, offset, read_jstf_script)?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(JstfTable {
+        major_version,
+        minor_version,
+        scripts,
+    })
+}
+
+fn read_jstf_script(data: &mut FontDataStream) -> Result<JstfScript, Error> {
+    let base = data.substream(0)?;
+    let extender_offset = data.read_u16()? as usize;
+    let default_offset = data.read_u16()? as usize;
+    let count = data.read_u16()?;
+    let lang_sys_records = (0..count)
+        .map(|_| {
+            let lang_sys_tag = data.read_tag()?;
+            let offset = data.read_u16()? as usize;
+            Ok(JstfLangSysRecord {
+                lang_sys_tag,
+                lang_sys: read_at(&base, offset, read_jstf_lang_sys)?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(JstfScript {
+        extender_glyphs: read_optional_at(&base, extender_offset, read_index_list)?
+            .unwrap_or_default(),
+        default_lang_sys: read_optional_at(&base, default_offset, read_jstf_lang_sys)?,
+        lang_sys_records,
+    })
+}
+
+fn read_jstf_lang_sys(data: &mut FontDataStream) -> Result<JstfLangSys, Error> {
+    let base = data.substream(0)?;
+    let count = data.read_u16()?;
+    let priorities = (0..count)
+        .map(|_| {
+            let offset = data.read_u16()? as usize;
+            read_at(&base, offset, read_jstf_priority)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(JstfLangSys { priorities })
+}
+
+fn read_jstf_priority(data: &mut FontDataStream) -> Result<JstfPriority, Error> {
+    let base = data.substream(0)?;
+    Ok(JstfPriority {
+        shrinkage: read_modifications(data, &base)?,
+        extension: read_modifications(data, &base)?,
+    })
+}
+
+/// Reads the five offsets of one direction of a priority, measured from
+/// `base`.
+fn read_modifications<'a>(
+    data: &mut FontDataStream<'a>,
+    base: &FontDataStream<'a>,
+) -> Result<JstfModifications, Error> {
+    let mut list = || -> Result<Vec<u16>, Error> {
+        let offset = data.read_u16()? as usize;
+        Ok(read_optional_at(base, offset, read_index_list)?.unwrap_or_default())
+    };
+    let gsub_enable = list()?;
+    let gsub_disable = list()?;
+    let gpos_enable = list()?;
+    let gpos_disable = list()?;
+    let max_offset = data.read_u16()? as usize;
+    let max = read_optional_at(base, max_offset, |max| {
+        read_lookup_list(max, GPOS_EXTENSION_TYPE, &read_positioning_subtable)
+    })?
+    .unwrap_or_default();
+    Ok(JstfModifications {
+        gsub_enable,
+        gsub_disable,
+        gpos_enable,
+        gpos_disable,
+        max,
+    })
+}
+
+/// Reads a count followed by that many 16-bit values: extender glyphs and
+/// lookup index lists share this layout.
+fn read_index_list(data: &mut FontDataStream) -> Result<Vec<u16>, Error> {
+    let count = data.read_u16()?;
+    (0..count).map(|_| data.read_u16()).collect()
+}
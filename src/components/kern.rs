@@ -0,0 +1,551 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `kern` (Kerning) table representation and utilities for the Aurora Font Library.
+//!
+//! Both the Microsoft version 0 table (formats 0 and 2) and the Apple version
+//! 1.0 table (formats 0 to 3) are supported; they differ only in their
+//! headers, so one representation covers both.
+
+use std::collections::BTreeMap;
+
+use crate::components::aat::{
+    CLASS_END_OF_TEXT, CLASS_OUT_OF_BOUNDS, ENTRY_DONT_ADVANCE, MAX_DONT_ADVANCE,
+};
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Version of the Microsoft `kern` table.
+pub const KERN_VERSION_MICROSOFT: u32 = 0;
+/// Version of the Apple `kern` table (1.0 as 16.16 fixed).
+pub const KERN_VERSION_APPLE: u32 = 0x0001_0000;
+
+/// Contextual kerning entry flag: push the current glyph on the kerning stack.
+const ENTRY_PUSH: u16 = 0x8000;
+/// Contextual kerning entry mask: offset of the entry's value list.
+const ENTRY_VALUE_OFFSET: u16 = 0x3FFF;
+/// Deepest the contextual kerning stack can grow.
+const KERNING_STACK_DEPTH: usize = 8;
+
+/// Kerning table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KernTable {
+    /// `KERN_VERSION_MICROSOFT` or `KERN_VERSION_APPLE`.
+    pub version: u32,
+    /// Subtables, applied in order.
+    pub subtables: Vec<KernSubtable>,
+}
+
+/// One `kern` subtable and its coverage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KernSubtable {
+    /// Whether the subtable applies to horizontal text (vertical otherwise).
+    pub horizontal: bool,
+    /// Whether the values are minimum values rather than kerning values
+    /// (Microsoft only).
+    pub minimum: bool,
+    /// Whether the values move glyphs perpendicular to the text direction.
+    pub cross_stream: bool,
+    /// Whether the values replace those accumulated so far instead of adding
+    /// to them (Microsoft only).
+    pub replace: bool,
+    /// Whether the values are variations for the tuple `tuple_index` (Apple
+    /// only).
+    pub variation: bool,
+    /// Index of the variation tuple the values belong to (Apple only).
+    pub tuple_index: u16,
+    /// Kerning data.
+    pub data: KernData,
+}
+
+/// The kerning data of a subtable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KernData {
+    /// Format 0: values of individual glyph pairs.
+    Pairs(BTreeMap<(u16, u16), i16>),
+    /// Format 1: contextual kerning driven by a state machine (Apple only).
+    Contextual(KernStateTable),
+    /// Format 2: a two-dimensional array indexed by glyph classes.
+    Classes(KernClassTable),
+    /// Format 3: a compact array of values indexed by glyph classes (Apple
+    /// only).
+    Indices(KernIndexTable),
+}
+
+impl KernData {
+    /// Returns the subtable format the data is written as.
+    pub fn format(&self) -> u8 {
+        match self {
+            KernData::Pairs(_) => 0,
+            KernData::Contextual(_) => 1,
+            KernData::Classes(_) => 2,
+            KernData::Indices(_) => 3,
+        }
+    }
+}
+
+/// Format 1 state machine.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KernStateTable {
+    /// Number of glyph classes, including the four predefined ones.
+    pub class_count: u16,
+    /// First glyph in `classes`.
+    pub first_glyph: u16,
+    /// Class of each glyph from `first_glyph`. Other glyphs are out of bounds
+    /// (class 1).
+    pub classes: Vec<u8>,
+    /// Entry index for each class, per state. State 0 starts the run.
+    pub states: Vec<Vec<u8>>,
+    /// State transitions and their actions.
+    pub entries: Vec<KernStateEntry>,
+}
+
+/// A format 1 state transition.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KernStateEntry {
+    /// State to move to.
+    pub new_state: u16,
+    /// Whether the current glyph is pushed on the kerning stack.
+    pub push: bool,
+    /// Whether the current glyph is processed again in the new state.
+    pub dont_advance: bool,
+    /// Values applied to the glyphs popped from the stack, most recently
+    /// pushed first. Empty for entries without an action.
+    pub values: Vec<i16>,
+}
+
+/// Format 2 class-based kerning.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KernClassTable {
+    /// First glyph in `left_classes`.
+    pub left_first_glyph: u16,
+    /// Row of `values` for each left glyph from `left_first_glyph`. Other
+    /// left glyphs are not kerned.
+    pub left_classes: Vec<u16>,
+    /// First glyph in `right_classes`.
+    pub right_first_glyph: u16,
+    /// Column of `values` for each right glyph from `right_first_glyph`.
+    /// Other right glyphs use column 0.
+    pub right_classes: Vec<u16>,
+    /// Kerning values indexed by left and then right class; every row has the
+    /// same length.
+    pub values: Vec<Vec<i16>>,
+}
+
+/// Format 3 indexed kerning.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KernIndexTable {
+    /// Distinct kerning values.
+    pub values: Vec<i16>,
+    /// Left class of each glyph.
+    pub left_classes: Vec<u8>,
+    /// Right class of each glyph; as long as `left_classes`.
+    pub right_classes: Vec<u8>,
+    /// Number of left classes.
+    pub left_class_count: u8,
+    /// Number of right classes.
+    pub right_class_count: u8,
+    /// Index into `values` for each left and right class pair, by row.
+    pub indices: Vec<u8>,
+}
+
+impl KernTable {
+    /// Returns the horizontal kerning between two adjacent glyphs, in font
+    /// units.
+    ///
+    /// Only subtables that kern pairs along horizontal text contribute:
+    /// vertical, cross-stream, minimum, variation and contextual subtables
+    /// are skipped.
+    pub fn kerning(&self, left_glyph: u16, right_glyph: u16) -> i16 {
+        self.subtables
+            .iter()
+            .filter(|subtable| {
+                subtable.horizontal
+                    && !subtable.cross_stream
+                    && !subtable.minimum
+                    && !subtable.variation
+            })
+            .fold(0i16, |total, subtable| {
+                match subtable.pair_value(left_glyph, right_glyph) {
+                    Some(value) if subtable.replace => value,
+                    Some(value) => total.saturating_add(value),
+                    None => total,
+                }
+            })
+    }
+}
+
+impl KernSubtable {
+    /// Returns the value of a glyph pair, or `None` for contextual subtables
+    /// and pairs missing from a format 0 list.
+    pub fn pair_value(&self, left_glyph: u16, right_glyph: u16) -> Option<i16> {
+        match &self.data {
+            KernData::Pairs(pairs) => pairs.get(&(left_glyph, right_glyph)).copied(),
+            KernData::Contextual(_) => None,
+            KernData::Classes(classes) => Some(classes.value(left_glyph, right_glyph)),
+            KernData::Indices(indices) => Some(indices.value(left_glyph, right_glyph)),
+        }
+    }
+}
+
+impl KernClassTable {
+    /// Returns the kerning between two glyphs.
+    pub fn value(&self, left_glyph: u16, right_glyph: u16) -> i16 {
+        let Some(&row) = left_glyph
+            .checked_sub(self.left_first_glyph)
+            .and_then(|index| self.left_classes.get(index as usize))
+        else {
+            return 0;
+        };
+        let column = right_glyph
+            .checked_sub(self.right_first_glyph)
+            .and_then(|index| self.right_classes.get(index as usize))
+            .copied()
+            .unwrap_or(0);
+        self.values
+            .get(row as usize)
+            .and_then(|row| row.get(column as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl KernIndexTable {
+    /// Returns the kerning between two glyphs.
+    pub fn value(&self, left_glyph: u16, right_glyph: u16) -> i16 {
+        let (Some(&left), Some(&right)) = (
+            self.left_classes.get(left_glyph as usize),
+            self.right_classes.get(right_glyph as usize),
+        ) else {
+            return 0;
+        };
+        let index = left as usize * self.right_class_count as usize + right as usize;
+        self.indices
+            .get(index)
+            .and_then(|&index| self.values.get(index as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl KernStateTable {
+    /// Returns the class of a glyph.
+    pub fn class(&self, glyph_id: u16) -> u8 {
+        glyph_id
+            .checked_sub(self.first_glyph)
+            .and_then(|index| self.classes.get(index as usize))
+            .copied()
+            .unwrap_or(CLASS_OUT_OF_BOUNDS as u8)
+    }
+
+    /// Runs the state machine over a glyph run and returns the kerning of
+    /// each glyph, in font units.
+    pub fn apply(&self, glyphs: &[u16]) -> Vec<i16> {
+        let mut adjustments = vec![0i16; glyphs.len()];
+        let mut stack: Vec<usize> = Vec::with_capacity(KERNING_STACK_DEPTH);
+        let mut state = 0usize;
+        let mut index = 0;
+        let mut repeats = 0;
+        // The position one past the last glyph is the end of text.
+        while index <= glyphs.len() {
+            let class = glyphs
+                .get(index)
+                .map_or(CLASS_END_OF_TEXT as u8, |&glyph| self.class(glyph));
+            let Some(entry) = self
+                .states
+                .get(state)
+                .and_then(|row| row.get(class as usize))
+                .and_then(|&entry| self.entries.get(entry as usize))
+            else {
+                break;
+            };
+            if entry.push && index < glyphs.len() {
+                if stack.len() == KERNING_STACK_DEPTH {
+                    stack.remove(0);
+                }
+                stack.push(index);
+            }
+            if !entry.values.is_empty() {
+                for &value in &entry.values {
+                    let Some(glyph) = stack.pop() else {
+                        break;
+                    };
+                    adjustments[glyph] = adjustments[glyph].saturating_add(value);
+                }
+                stack.clear();
+            }
+            state = entry.new_state as usize;
+            if entry.dont_advance && repeats < MAX_DONT_ADVANCE && index < glyphs.len() {
+                repeats += 1;
+            } else {
+                index += 1;
+                repeats = 0;
+            }
+        }
+        adjustments
+    }
+}
+
+/// Extracts the `kern` table from the provided data stream.
+pub fn read_kern(data: &mut FontDataStream) -> Result<KernTable, Error> {
+    let major = data.read_u16()?;
+    let (version, count) = match major {
+        0 => (KERN_VERSION_MICROSOFT, data.read_u16()? as u32),
+        1 => {
+            let minor = data.read_u16()?;
+            if minor != 0 {
+                return Err(Error::Io(IoError::UnsupportedVersion(
+                    (major as u32) << 16 | minor as u32,
+                )));
+            }
+            (KERN_VERSION_APPLE, data.read_u32()?)
+        }
+        _ => return Err(Error::Io(IoError::UnsupportedVersion(major as u32))),
+    };
+    let apple = version == KERN_VERSION_APPLE;
+    let mut subtables = Vec::new();
+    for _ in 0..count {
+        let start = data.position();
+        let subtable = data.substream(start)?;
+        let (subtable, length) = read_kern_subtable(subtable, apple)?;
+        subtables.push(subtable);
+        data.seek(start + length);
+    }
+    Ok(KernTable { version, subtables })
+}
+
+/// Reads one subtable and returns it with its length in bytes.
+fn read_kern_subtable(
+    mut data: FontDataStream,
+    apple: bool,
+) -> Result<(KernSubtable, usize), Error> {
+    let (mut length, format, mut subtable) = if apple {
+        let length = data.read_u32()? as usize;
+        let coverage = data.read_u16()?;
+        let tuple_index = data.read_u16()?;
+        let subtable = KernSubtable {
+            horizontal: coverage & 0x8000 == 0,
+            minimum: false,
+            cross_stream: coverage & 0x4000 != 0,
+            replace: false,
+            variation: coverage & 0x2000 != 0,
+            tuple_index,
+            data: KernData::Pairs(BTreeMap::new()),
+        };
+        (length, coverage & 0xFF, subtable)
+    } else {
+        let _version = data.read_u16()?;
+        let length = data.read_u16()? as usize;
+        let coverage = data.read_u16()?;
+        let subtable = KernSubtable {
+            horizontal: coverage & 0x01 != 0,
+            minimum: coverage & 0x02 != 0,
+            cross_stream: coverage & 0x04 != 0,
+            replace: coverage & 0x08 != 0,
+            variation: false,
+            tuple_index: 0,
+            data: KernData::Pairs(BTreeMap::new()),
+        };
+        (length, coverage >> 8, subtable)
+    };
+    let header_size = data.position();
+    subtable.data = match format {
+        0 => {
+            let count = data.read_u16()?;
+            data.skip(6);
+            let mut pairs = BTreeMap::new();
+            for _ in 0..count {
+                let left = data.read_u16()?;
+                let right = data.read_u16()?;
+                pairs.insert((left, right), data.read_i16()?);
+            }
+            // Large Microsoft pair lists overflow the 16-bit length; only a
+            // declared length equal to the truncated size is overridden.
+            let size = data.position();
+            if !apple && size > u16::MAX as usize && size & 0xFFFF == length {
+                length = size;
+            }
+            KernData::Pairs(pairs)
+        }
+        1 => KernData::Contextual(read_state_table(&data.substream(header_size)?)?),
+        2 => KernData::Classes(read_class_table(&mut data)?),
+        3 => KernData::Indices(read_index_table(&mut data)?),
+        _ => return Err(Error::InvalidFormat),
+    };
+    if length < header_size {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    Ok((subtable, length))
+}
+
+/// Reads a format 1 state table. Offsets are measured from its start, just
+/// after the subtable header.
+fn read_state_table(data: &FontDataStream) -> Result<KernStateTable, Error> {
+    let class_count = data.read_at_u16(0)?;
+    let class_table = data.read_at_u16(2)? as usize;
+    let state_array = data.read_at_u16(4)? as usize;
+    let entry_table = data.read_at_u16(6)? as usize;
+    let first_glyph = data.read_at_u16(class_table)?;
+    let glyph_count = data.read_at_u16(class_table + 2)? as usize;
+    let classes = data.slice_at(class_table + 4, glyph_count)?.to_vec();
+
+    // The number of states and entries is not recorded, so both are grown
+    // until every reachable state and entry has been read.
+    let row_size = class_count as usize;
+    let mut states: Vec<Vec<u8>> = Vec::new();
+    let mut entries = Vec::new();
+    let mut state_count = 1;
+    let mut entry_count = 0;
+    while states.len() < state_count || entries.len() < entry_count {
+        while states.len() < state_count {
+            let row = data
+                .slice_at(state_array + states.len() * row_size, row_size)?
+                .to_vec();
+            if let Some(&max) = row.iter().max() {
+                entry_count = entry_count.max(max as usize + 1);
+            }
+            states.push(row);
+        }
+        while entries.len() < entry_count {
+            let offset = entry_table + entries.len() * 4;
+            let new_state = data.read_at_u16(offset)? as usize;
+            let flags = data.read_at_u16(offset + 2)?;
+            let state = new_state
+                .checked_sub(state_array)
+                .filter(|&state| row_size != 0 && state.is_multiple_of(row_size))
+                .map(|state| state / row_size)
+                .ok_or(Error::Io(IoError::InvalidData))?;
+            state_count = state_count.max(state + 1);
+            let value_offset = (flags & ENTRY_VALUE_OFFSET) as usize;
+            let mut values = Vec::new();
+            if value_offset != 0 {
+                // The last value of a list has its lowest bit set.
+                for index in 0..KERNING_STACK_DEPTH {
+                    let value = data.read_at_u16(value_offset + index * 2)?;
+                    values.push((value & !1) as i16);
+                    if value & 1 != 0 {
+                        break;
+                    }
+                }
+            }
+            entries.push(KernStateEntry {
+                new_state: state as u16,
+                push: flags & ENTRY_PUSH != 0,
+                dont_advance: flags & ENTRY_DONT_ADVANCE != 0,
+                values,
+            });
+        }
+    }
+    Ok(KernStateTable {
+        class_count,
+        first_glyph,
+        classes,
+        states,
+        entries,
+    })
+}
+
+/// Reads a format 2 class table. Offsets are measured from the subtable
+/// start.
+fn read_class_table(data: &mut FontDataStream) -> Result<KernClassTable, Error> {
+    let row_width = data.read_u16()? as usize;
+    let left_table = data.read_u16()? as usize;
+    let right_table = data.read_u16()? as usize;
+    let array = data.read_u16()? as usize;
+    let read_classes = |offset: usize| -> Result<(u16, Vec<u16>), Error> {
+        let mut table = data.substream(offset)?;
+        let first_glyph = table.read_u16()?;
+        let count = table.read_u16()?;
+        let classes = (0..count)
+            .map(|_| table.read_u16())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((first_glyph, classes))
+    };
+    let (left_first_glyph, left_offsets) = read_classes(left_table)?;
+    let (right_first_glyph, right_offsets) = read_classes(right_table)?;
+
+    // Left classes are offsets of rows from the subtable start, right
+    // classes offsets within a row.
+    let columns = row_width / 2;
+    let mut left_classes = Vec::with_capacity(left_offsets.len());
+    let mut outside = false;
+    for offset in left_offsets {
+        let offset = offset as usize;
+        if offset < array || row_width == 0 {
+            outside = true;
+            left_classes.push(None);
+        } else if !(offset - array).is_multiple_of(row_width) {
+            return Err(Error::Io(IoError::InvalidData));
+        } else {
+            left_classes.push(Some(((offset - array) / row_width) as u16));
+        }
+    }
+    let rows = left_classes
+        .iter()
+        .flatten()
+        .max()
+        .map_or(0, |&row| row as usize + 1);
+    let right_classes = right_offsets
+        .into_iter()
+        .map(|offset| {
+            let offset = offset as usize;
+            if !offset.is_multiple_of(2) || (offset != 0 && offset >= row_width) {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            Ok((offset / 2) as u16)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut values = Vec::with_capacity(rows + outside as usize);
+    let mut array = data.substream(array)?;
+    for _ in 0..rows {
+        values.push(
+            (0..columns)
+                .map(|_| array.read_i16())
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+    }
+    // Glyphs whose offset precedes the array are not kerned; they share an
+    // extra row of zeros.
+    if outside {
+        values.push(vec![0; columns]);
+    }
+    let left_classes = left_classes
+        .into_iter()
+        .map(|row| row.unwrap_or(rows as u16))
+        .collect();
+    Ok(KernClassTable {
+        left_first_glyph,
+        left_classes,
+        right_first_glyph,
+        right_classes,
+        values,
+    })
+}
+
+/// Reads a format 3 index table.
+fn read_index_table(data: &mut FontDataStream) -> Result<KernIndexTable, Error> {
+    let glyph_count = data.read_u16()? as usize;
+    let value_count = data.read_u8()?;
+    let left_class_count = data.read_u8()?;
+    let right_class_count = data.read_u8()?;
+    let _flags = data.read_u8()?;
+    let values = (0..value_count)
+        .map(|_| data.read_i16())
+        .collect::<Result<Vec<_>, _>>()?;
+    let left_classes = data.read_bytes(glyph_count)?.to_vec();
+    let right_classes = data.read_bytes(glyph_count)?.to_vec();
+    let indices = data
+        .read_bytes(left_class_count as usize * right_class_count as usize)?
+        .to_vec();
+    Ok(KernIndexTable {
+        values,
+        left_classes,
+        right_classes,
+        left_class_count,
+        right_class_count,
+        indices,
+    })
+}
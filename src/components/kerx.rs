@@ -0,0 +1,522 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `kerx` (Extended Kerning) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::components::aat::{
+    AatLookup, StateTable, read_lookup, read_state_table,
+};
+use crate::components::ankr::AnkrTable;
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Contextual entry flag: push the current glyph on the kerning stack.
+pub const KERX_ENTRY_PUSH: u16 = 0x8000;
+/// Contextual entry flag: clear the kerning stack.
+pub const KERX_ENTRY_RESET: u16 = 0x2000;
+/// Attachment entry flag: mark the current glyph.
+pub const KERX_ENTRY_MARK: u16 = 0x8000;
+
+/// Index meaning no kerning values or attachment action.
+const NO_INDEX: u16 = 0xFFFF;
+/// Deepest the contextual kerning stack can grow.
+const KERNING_STACK_DEPTH: usize = 8;
+/// Cross-stream value resetting the cross-stream shift to zero.
+const CROSS_STREAM_RESET: i16 = -0x8000;
+
+/// Extended kerning table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KerxTable {
+    /// Table version (2, 3 or 4).
+    pub version: u16,
+    /// Subtables, applied in order.
+    pub subtables: Vec<KerxSubtable>,
+}
+
+/// One `kerx` subtable and its coverage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KerxSubtable {
+    /// Whether the subtable applies to vertical text.
+    pub vertical: bool,
+    /// Whether the values move glyphs perpendicular to the text direction.
+    pub cross_stream: bool,
+    /// Whether the values are variations.
+    pub variation: bool,
+    /// Number of variation tuples per value; 0 without variations.
+    pub tuple_count: u32,
+    /// Kerning data.
+    pub data: KerxData,
+}
+
+/// The kerning data of a `kerx` subtable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KerxData {
+    /// Format 0: values of individual glyph pairs.
+    Pairs(BTreeMap<(u16, u16), i16>),
+    /// Format 1: contextual kerning driven by a state machine.
+    Contextual(KerxContextualSubtable),
+    /// Format 2: a two-dimensional array indexed by glyph classes.
+    Classes(KerxClassTable),
+    /// Format 4: attachment of glyphs to marked glyphs.
+    Attachment(KerxAttachmentSubtable),
+    /// Format 6: a two-dimensional array indexed by glyph classes, with
+    /// room for variations.
+    IndexedClasses(KerxClassTable),
+}
+
+impl KerxData {
+    /// Returns the subtable format the data is written as.
+    pub fn format(&self) -> u8 {
+        match self {
+            KerxData::Pairs(_) => 0,
+            KerxData::Contextual(_) => 1,
+            KerxData::Classes(_) => 2,
+            KerxData::Attachment(_) => 4,
+            KerxData::IndexedClasses(_) => 6,
+        }
+    }
+}
+
+/// Format 1 contextual kerning.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KerxContextualSubtable {
+    /// State machine; entries give the index of their first value, or
+    /// `0xFFFF` for none.
+    pub machine: StateTable<u16>,
+    /// Value lists applied to the glyphs popped from the stack, most
+    /// recently pushed first. The last value of a list has its lowest bit
+    /// set.
+    pub values: Vec<i16>,
+}
+
+/// Class-based kerning (formats 2 and 6).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KerxClassTable {
+    /// Row of `values` for each left glyph; other glyphs use row 0.
+    pub left_classes: AatLookup,
+    /// Column of `values` for each right glyph; other glyphs use column 0.
+    pub right_classes: AatLookup,
+    /// Kerning values indexed by left and then right class; every row has the
+    /// same length.
+    pub values: Vec<Vec<i16>>,
+}
+
+/// Format 4 attachment.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KerxAttachmentSubtable {
+    /// State machine; entries give the index of their action, or `0xFFFF`
+    /// for none.
+    pub machine: StateTable<u16>,
+    /// Actions attaching the current glyph to the marked one.
+    pub actions: AttachmentActions,
+}
+
+/// How format 4 actions locate the points to align.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttachmentActions {
+    /// Outline point indices `(marked, current)`.
+    ControlPoints(Vec<(u16, u16)>),
+    /// `ankr` anchor indices `(marked, current)`.
+    Anchors(Vec<(u16, u16)>),
+    /// Coordinates `(marked_x, marked_y, current_x, current_y)` in font units.
+    Coordinates(Vec<(i16, i16, i16, i16)>),
+}
+
+impl Default for AttachmentActions {
+    fn default() -> Self {
+        AttachmentActions::Anchors(Vec::new())
+    }
+}
+
+/// Position adjustment of one glyph, in font units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KerxPosition {
+    /// Change of the glyph's advance.
+    pub x_advance: i16,
+    /// Horizontal shift of the glyph.
+    pub x_offset: i16,
+    /// Vertical shift of the glyph.
+    pub y_offset: i16,
+    /// Glyph this one is attached to; its offsets are then measured from that
+    /// glyph's origin.
+    pub attached_to: Option<usize>,
+}
+
+impl KerxClassTable {
+    /// Returns the kerning between two glyphs.
+    pub fn value(&self, left_glyph: u16, right_glyph: u16) -> i16 {
+        let row = self.left_classes.get(left_glyph).unwrap_or(0);
+        let column = self.right_classes.get(right_glyph).unwrap_or(0);
+        self.values
+            .get(row as usize)
+            .and_then(|row| row.get(column as usize))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl KerxSubtable {
+    /// Returns the value of a glyph pair, or `None` for subtables that do not
+    /// kern pairs and pairs missing from a format 0 list.
+    pub fn pair_value(&self, left_glyph: u16, right_glyph: u16) -> Option<i16> {
+        match &self.data {
+            KerxData::Pairs(pairs) => pairs.get(&(left_glyph, right_glyph)).copied(),
+            KerxData::Classes(classes) | KerxData::IndexedClasses(classes) => {
+                Some(classes.value(left_glyph, right_glyph))
+            }
+            KerxData::Contextual(_) | KerxData::Attachment(_) => None,
+        }
+    }
+}
+
+impl KerxTable {
+    /// Returns the horizontal kerning between two adjacent glyphs, in font
+    /// units, from the subtables that kern pairs along horizontal text.
+    pub fn kerning(&self, left_glyph: u16, right_glyph: u16) -> i16 {
+        self.subtables
+            .iter()
+            .filter(|subtable| !subtable.vertical && !subtable.cross_stream && !subtable.variation)
+            .filter_map(|subtable| subtable.pair_value(left_glyph, right_glyph))
+            .fold(0i16, i16::saturating_add)
+    }
+
+    /// Applies the horizontal subtables to a glyph run and returns the
+    /// adjustment of each glyph.
+    ///
+    /// Attachments by anchor need `ankr`; attachments by outline point are
+    /// not applied.
+    pub fn apply(&self, glyphs: &[u16], ankr: Option<&AnkrTable>) -> Vec<KerxPosition> {
+        let mut positions = vec![KerxPosition::default(); glyphs.len()];
+        for subtable in &self.subtables {
+            if subtable.vertical || subtable.variation {
+                continue;
+            }
+            match &subtable.data {
+                KerxData::Contextual(contextual) => {
+                    contextual.apply(glyphs, subtable.cross_stream, &mut positions)
+                }
+                KerxData::Attachment(attachment) => attachment.apply(glyphs, ankr, &mut positions),
+                _ => {
+                    for (index, pair) in glyphs.windows(2).enumerate() {
+                        let value = subtable.pair_value(pair[0], pair[1]).unwrap_or(0);
+                        if subtable.cross_stream {
+                            let position = &mut positions[index + 1];
+                            position.y_offset = position.y_offset.saturating_add(value);
+                        } else {
+                            let position = &mut positions[index];
+                            position.x_advance = position.x_advance.saturating_add(value);
+                        }
+                    }
+                }
+            }
+        }
+        positions
+    }
+}
+
+impl KerxContextualSubtable {
+    fn apply(&self, glyphs: &[u16], cross_stream: bool, positions: &mut [KerxPosition]) {
+        let mut stack: Vec<usize> = Vec::with_capacity(KERNING_STACK_DEPTH);
+        let mut buffer = glyphs.to_vec();
+        self.machine.drive(&mut buffer, |entry, glyphs, index| {
+            if entry.flags & KERX_ENTRY_RESET != 0 {
+                stack.clear();
+            }
+            if entry.flags & KERX_ENTRY_PUSH != 0 && index < glyphs.len() {
+                if stack.len() == KERNING_STACK_DEPTH {
+                    stack.remove(0);
+                }
+                stack.push(index);
+            }
+            if entry.data != NO_INDEX && !stack.is_empty() {
+                for &value in self.values.iter().skip(entry.data as usize) {
+                    let Some(glyph) = stack.pop() else {
+                        break;
+                    };
+                    let kerning = value & !1;
+                    let position = &mut positions[glyph];
+                    if !cross_stream {
+                        position.x_advance = position.x_advance.saturating_add(kerning);
+                        position.x_offset = position.x_offset.saturating_add(kerning);
+                    } else if kerning == CROSS_STREAM_RESET {
+                        position.y_offset = 0;
+                    } else {
+                        position.y_offset = position.y_offset.saturating_add(kerning);
+                    }
+                    if value & 1 != 0 {
+                        break;
+                    }
+                }
+            }
+            index
+        });
+    }
+}
+
+impl KerxAttachmentSubtable {
+    fn apply(&self, glyphs: &[u16], ankr: Option<&AnkrTable>, positions: &mut [KerxPosition]) {
+        let mut mark: Option<usize> = None;
+        let mut buffer = glyphs.to_vec();
+        self.machine.drive(&mut buffer, |entry, glyphs, index| {
+            if entry.data != NO_INDEX
+                && index < glyphs.len()
+                && let Some(marked) = mark
+                && let Some(((mark_x, mark_y), (current_x, current_y))) =
+                    self.points(entry.data, glyphs[marked], glyphs[index], ankr)
+            {
+                positions[index] = KerxPosition {
+                    x_offset: mark_x.saturating_sub(current_x),
+                    y_offset: mark_y.saturating_sub(current_y),
+                    attached_to: Some(marked),
+                    ..positions[index]
+                };
+            }
+            if entry.flags & KERX_ENTRY_MARK != 0 && index < glyphs.len() {
+                mark = Some(index);
+            }
+            index
+        });
+    }
+
+    /// Returns the points of the marked and current glyphs an action aligns.
+    fn points(
+        &self,
+        action: u16,
+        marked: u16,
+        current: u16,
+        ankr: Option<&AnkrTable>,
+    ) -> Option<((i16, i16), (i16, i16))> {
+        match &self.actions {
+            AttachmentActions::ControlPoints(_) => None,
+            AttachmentActions::Anchors(actions) => {
+                let (marked_anchor, current_anchor) = *actions.get(action as usize)?;
+                let ankr = ankr?;
+                Some((
+                    ankr.anchor(marked, marked_anchor)?,
+                    ankr.anchor(current, current_anchor)?,
+                ))
+            }
+            AttachmentActions::Coordinates(actions) => {
+                let (mark_x, mark_y, current_x, current_y) = *actions.get(action as usize)?;
+                Some(((mark_x, mark_y), (current_x, current_y)))
+            }
+        }
+    }
+}
+
+/// Extracts the `kerx` table from the provided data stream. `num_glyphs` is
+/// the glyph count from `maxp`.
+pub fn read_kerx(data: &mut FontDataStream, num_glyphs: u16) -> Result<KerxTable, Error> {
+    let version = data.read_u16()?;
+    if !(2..=4).contains(&version) {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let _padding = data.read_u16()?;
+    let count = data.read_u32()?;
+    let mut subtables = Vec::new();
+    for _ in 0..count {
+        let start = data.position();
+        let length = data.read_u32()? as usize;
+        let coverage = data.read_u32()?;
+        let tuple_count = data.read_u32()?;
+        if length < 12 {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let subtable = data.substream(start)?;
+        subtables.push(KerxSubtable {
+            vertical: coverage & 0x8000_0000 != 0,
+            cross_stream: coverage & 0x4000_0000 != 0,
+            variation: coverage & 0x2000_0000 != 0,
+            tuple_count,
+            data: read_kerx_data(&subtable, (coverage & 0xFF) as u8, tuple_count, num_glyphs)?,
+        });
+        data.seek(start + length);
+    }
+    Ok(KerxTable { version, subtables })
+}
+
+/// Reads the data of a subtable from a stream starting at its header.
+fn read_kerx_data(
+    subtable: &FontDataStream,
+    format: u8,
+    tuple_count: u32,
+    num_glyphs: u16,
+) -> Result<KerxData, Error> {
+    // Values of variation subtables come in tuples; only the first is kept.
+    let stride = tuple_count.max(1) as usize;
+    let mut data = subtable.substream(12)?;
+    Ok(match format {
+        0 => {
+            let count = data.read_u32()?;
+            data.skip(12);
+            let mut pairs = BTreeMap::new();
+            for _ in 0..count {
+                let left = data.read_u16()?;
+                let right = data.read_u16()?;
+                pairs.insert((left, right), data.read_i16()?);
+            }
+            KerxData::Pairs(pairs)
+        }
+        1 => {
+            let machine = read_state_table(&data, num_glyphs, |data| data.read_u16())?;
+            let table = data.substream(data.read_at_u32(16)? as usize)?;
+            // Each list runs to the first value with its lowest bit set.
+            let mut count = 0;
+            for entry in machine
+                .entries
+                .iter()
+                .filter(|entry| entry.data != NO_INDEX)
+            {
+                let mut index = entry.data as usize;
+                for _ in 0..KERNING_STACK_DEPTH {
+                    let value = table.read_at_u16(index * stride * 2)?;
+                    index += 1;
+                    if value & 1 != 0 {
+                        break;
+                    }
+                }
+                count = count.max(index);
+            }
+            let values = (0..count)
+                .map(|index| Ok(table.read_at_u16(index * stride * 2)? as i16))
+                .collect::<Result<Vec<_>, Error>>()?;
+            KerxData::Contextual(KerxContextualSubtable { machine, values })
+        }
+        2 => {
+            let row_width = data.read_u32()? as usize;
+            let left = read_lookup(
+                &mut subtable.substream(data.read_u32()? as usize)?,
+                num_glyphs,
+            )?;
+            let right = read_lookup(
+                &mut subtable.substream(data.read_u32()? as usize)?,
+                num_glyphs,
+            )?;
+            let array = subtable.substream(data.read_u32()? as usize)?;
+            // Classes are byte offsets of rows in the array and of values in a
+            // row.
+            if row_width == 0 || !row_width.is_multiple_of(2) {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            let mut left_classes = BTreeMap::new();
+            for (glyph, offset) in left.values {
+                if !(offset as usize).is_multiple_of(row_width) {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                left_classes.insert(glyph, (offset as usize / row_width) as u16);
+            }
+            let mut right_classes = BTreeMap::new();
+            for (glyph, offset) in right.values {
+                if offset % 2 != 0 || offset as usize >= row_width {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                right_classes.insert(glyph, offset / 2);
+            }
+            let rows = left_classes
+                .values()
+                .max()
+                .map_or(1, |&max| max as usize + 1);
+            let values = read_rows(&array, rows, row_width / 2)?;
+            KerxData::Classes(KerxClassTable {
+                left_classes: AatLookup {
+                    values: left_classes,
+                },
+                right_classes: AatLookup {
+                    values: right_classes,
+                },
+                values,
+            })
+        }
+        4 => {
+            let machine = read_state_table(&data, num_glyphs, |data| data.read_u16())?;
+            let flags = data.read_at_u32(16)?;
+            let mut actions = data.substream((flags & 0x00FF_FFFF) as usize)?;
+            let count = machine
+                .entries
+                .iter()
+                .filter(|entry| entry.data != NO_INDEX)
+                .map(|entry| entry.data as usize + 1)
+                .max()
+                .unwrap_or(0);
+            let mut pairs = || {
+                (0..count)
+                    .map(|_| Ok((actions.read_u16()?, actions.read_u16()?)))
+                    .collect::<Result<Vec<_>, Error>>()
+            };
+            let actions = match flags >> 30 {
+                0 => AttachmentActions::ControlPoints(pairs()?),
+                1 => AttachmentActions::Anchors(pairs()?),
+                2 => AttachmentActions::Coordinates(
+                    (0..count)
+                        .map(|_| {
+                            Ok((
+                                actions.read_i16()?,
+                                actions.read_i16()?,
+                                actions.read_i16()?,
+                                actions.read_i16()?,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?,
+                ),
+                _ => return Err(Error::InvalidFormat),
+            };
+            KerxData::Attachment(KerxAttachmentSubtable { machine, actions })
+        }
+        6 => {
+            let flags = data.read_u32()?;
+            // Long values need 32-bit lookups.
+            if flags & 1 != 0 {
+                return Err(Error::InvalidFormat);
+            }
+            let rows = data.read_u16()? as usize;
+            let columns = data.read_u16()? as usize;
+            let left = read_lookup(
+                &mut subtable.substream(data.read_u32()? as usize)?,
+                num_glyphs,
+            )?;
+            let right = read_lookup(
+                &mut subtable.substream(data.read_u32()? as usize)?,
+                num_glyphs,
+            )?;
+            let array = subtable.substream(data.read_u32()? as usize)?;
+            // Row classes are indices of the rows' first values.
+            if columns == 0 {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            let mut left_classes = BTreeMap::new();
+            for (glyph, index) in left.values {
+                if !(index as usize).is_multiple_of(columns) || index as usize / columns >= rows {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                left_classes.insert(glyph, (index as usize / columns) as u16);
+            }
+            if right
+                .values
+                .values()
+                .any(|&column| column as usize >= columns)
+            {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            KerxData::IndexedClasses(KerxClassTable {
+                left_classes: AatLookup {
+                    values: left_classes,
+                },
+                right_classes: right,
+                values: read_rows(&array, rows, columns)?,
+            })
+        }
+        _ => return Err(Error::InvalidFormat),
+    })
+}
+
+fn read_rows(array: &FontDataStream, rows: usize, columns: usize) -> Result<Vec<Vec<i16>>, Error> {
+    let mut array = array.substream(0)?;
+    (0..rows)
+        .map(|_| (0..columns).map(|_| array.read_i16()).collect())
+        .collect()
+}
@@ -0,0 +1,1371 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Common OpenType layout structures for the Aurora Font Library.
+//! Coverage and class definition tables, device tables, script/feature/lookup
+//! lists and sequence contexts are shared by `GSUB`, `GPOS`, `GDEF` and the
+//! other layout tables, so they are parsed and serialized here.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::{FontDataWriter, serialize};
+
+/// Lookup flag: the text is written right to left (cursive attachment only).
+pub const RIGHT_TO_LEFT: u16 = 0x0001;
+/// Lookup flag: skip base glyphs.
+pub const IGNORE_BASE_GLYPHS: u16 = 0x0002;
+/// Lookup flag: skip ligatures.
+pub const IGNORE_LIGATURES: u16 = 0x0004;
+/// Lookup flag: skip combining marks.
+pub const IGNORE_MARKS: u16 = 0x0008;
+/// Lookup flag: a mark filtering set index follows the subtable offsets.
+pub const USE_MARK_FILTERING_SET: u16 = 0x0010;
+/// Lookup flag mask: only marks of this attachment class are processed.
+pub const MARK_ATTACHMENT_CLASS_FILTER: u16 = 0xFF00;
+
+/// Coverage table: the sorted set of glyphs a subtable applies to.
+///
+/// The coverage index of a glyph is its position in `glyphs`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Coverage {
+    /// Covered glyph ids in ascending order.
+    pub glyphs: Vec<u16>,
+}
+
+impl Coverage {
+    /// Creates a coverage table from glyph ids in any order.
+    pub fn new(glyphs: impl IntoIterator<Item = u16>) -> Self {
+        let mut glyphs: Vec<u16> = glyphs.into_iter().collect();
+        glyphs.sort_unstable();
+        glyphs.dedup();
+        Coverage { glyphs }
+    }
+
+    /// Returns the coverage index of a glyph.
+    pub fn index_of(&self, glyph_id: u16) -> Option<usize> {
+        self.glyphs.binary_search(&glyph_id).ok()
+    }
+
+    /// Returns whether the glyph is covered.
+    pub fn contains(&self, glyph_id: u16) -> bool {
+        self.index_of(glyph_id).is_some()
+    }
+
+    /// Returns the number of covered glyphs.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns whether no glyph is covered.
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
+
+/// Class definition table assigning glyphs to classes.
+///
+/// Glyphs that are not listed belong to class 0.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ClassDef {
+    /// Class of every glyph with a non-zero class.
+    pub classes: BTreeMap<u16, u16>,
+}
+
+impl ClassDef {
+    /// Returns the class of a glyph.
+    pub fn class(&self, glyph_id: u16) -> u16 {
+        self.classes.get(&glyph_id).copied().unwrap_or(0)
+    }
+
+    /// Returns the highest class value in use.
+    pub fn max_class(&self) -> u16 {
+        self.classes.values().copied().max().unwrap_or(0)
+    }
+}
+
+/// Device or variation index table referenced from value records, anchors and
+/// caret values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceTable {
+    /// Formats 1-3: pixel adjustments for a range of ppem sizes.
+    Hinting {
+        /// Smallest ppem size with a delta.
+        start_size: u16,
+        /// Largest ppem size with a delta.
+        end_size: u16,
+        /// Packing of the deltas (1: 2-bit, 2: 4-bit, 3: 8-bit signed values).
+        delta_format: u16,
+        /// Packed delta words.
+        delta_values: Vec<u16>,
+    },
+    /// Format 0x8000: index of a delta set in the `GDEF` item variation store.
+    VariationIndex {
+        /// Outer (item variation data) index.
+        delta_set_outer_index: u16,
+        /// Inner (row) index.
+        delta_set_inner_index: u16,
+    },
+}
+
+impl DeviceTable {
+    /// Returns the pixel adjustment for a ppem size (0 for variation indices).
+    pub fn delta(&self, ppem: u16) -> i16 {
+        let DeviceTable::Hinting {
+            start_size,
+            end_size,
+            delta_format,
+            delta_values,
+        } = self
+        else {
+            return 0;
+        };
+        if ppem < *start_size || ppem > *end_size || !(1..=3).contains(delta_format) {
+            return 0;
+        }
+        let bits = 1usize << delta_format;
+        let per_word = 16 / bits;
+        let index = (ppem - start_size) as usize;
+        let Some(word) = delta_values.get(index / per_word) else {
+            return 0;
+        };
+        let shift = 16 - bits * (index % per_word + 1);
+        let raw = (word >> shift) & ((1 << bits) - 1);
+        // Sign-extend the packed value.
+        ((raw << (16 - bits)) as i16) >> (16 - bits)
+    }
+}
+
+/// Language system: the features enabled for a script/language pair.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LangSys {
+    /// Index of a required feature, or 0xFFFF when there is none.
+    pub required_feature_index: u16,
+    /// Indices into the feature list.
+    pub feature_indices: Vec<u16>,
+}
+
+/// A language system with its tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LangSysRecord {
+    /// OpenType language system tag.
+    pub lang_sys_tag: [u8; 4],
+    /// Language system table.
+    pub lang_sys: LangSys,
+}
+
+/// Script table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Script {
+    /// Default language system, used when no language matches.
+    pub default_lang_sys: Option<LangSys>,
+    /// Language specific systems in tag order.
+    pub lang_sys_records: Vec<LangSysRecord>,
+}
+
+/// A script with its tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScriptRecord {
+    /// OpenType script tag.
+    pub script_tag: [u8; 4],
+    /// Script table.
+    pub script: Script,
+}
+
+/// Feature table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Feature {
+    /// Raw feature parameters (`size`, `ssXX` and `cvXX` features).
+    pub feature_params: Option<Vec<u8>>,
+    /// Indices into the lookup list.
+    pub lookup_list_indices: Vec<u16>,
+}
+
+/// A feature with its tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureRecord {
+    /// OpenType feature tag.
+    pub feature_tag: [u8; 4],
+    /// Feature table.
+    pub feature: Feature,
+}
+
+/// A lookup: a list of subtables of the same type.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Lookup<T> {
+    /// Lookup type. Extension lookups are unwrapped, so this is never the
+    /// extension type.
+    pub lookup_type: u16,
+    /// Lookup flags (`RIGHT_TO_LEFT`, `IGNORE_MARKS`, ...).
+    pub lookup_flag: u16,
+    /// Subtables, tried in order.
+    pub subtables: Vec<T>,
+    /// Index into `GDEF` mark glyph sets when `USE_MARK_FILTERING_SET` is set.
+    pub mark_filtering_set: Option<u16>,
+}
+
+/// A feature variation condition (format 1: axis range).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Condition {
+    /// Index of the axis in `fvar`.
+    pub axis_index: u16,
+    /// Minimum normalized axis value.
+    pub filter_range_min_value: f32,
+    /// Maximum normalized axis value.
+    pub filter_range_max_value: f32,
+}
+
+/// Alternate feature tables applied when every condition matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureVariationRecord {
+    /// Conditions that must all hold.
+    pub conditions: Vec<Condition>,
+    /// Feature index and the feature table replacing it.
+    pub substitutions: Vec<(u16, Feature)>,
+}
+
+/// Feature variations of a `GSUB` or `GPOS` table (version 1.1).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureVariations {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Records, evaluated in order; the first match wins.
+    pub records: Vec<FeatureVariationRecord>,
+}
+
+/// Common structure of the `GSUB` and `GPOS` tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutTable<T> {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0 or 1).
+    pub minor_version: u16,
+    /// Scripts in tag order.
+    pub scripts: Vec<ScriptRecord>,
+    /// Features, referenced by index from language systems.
+    pub features: Vec<FeatureRecord>,
+    /// Lookups, referenced by index from features and sequence contexts.
+    pub lookups: Vec<Lookup<T>>,
+    /// Feature variations (version 1.1 only).
+    pub feature_variations: Option<FeatureVariations>,
+}
+
+/// A nested lookup applied at a position of a matched sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SequenceLookupRecord {
+    /// Index into the matched input sequence.
+    pub sequence_index: u16,
+    /// Index of the lookup to apply.
+    pub lookup_list_index: u16,
+}
+
+/// A rule of a glyph or class based sequence context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SequenceRule {
+    /// Glyphs (or classes) following the first, covered input glyph.
+    pub input_sequence: Vec<u16>,
+    /// Lookups applied when the rule matches.
+    pub seq_lookup_records: Vec<SequenceLookupRecord>,
+}
+
+/// Contextual substitution/positioning subtable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceContext {
+    /// Format 1: rules on glyph sequences, one rule set per covered glyph.
+    Glyphs {
+        /// First glyphs of the input sequences.
+        coverage: Coverage,
+        /// Rule sets in coverage order.
+        rule_sets: Vec<Vec<SequenceRule>>,
+    },
+    /// Format 2: rules on class sequences, one rule set per class.
+    Classes {
+        /// First glyphs of the input sequences.
+        coverage: Coverage,
+        /// Classes of the input glyphs.
+        class_def: ClassDef,
+        /// Rule sets indexed by the class of the first glyph.
+        rule_sets: Vec<Vec<SequenceRule>>,
+    },
+    /// Format 3: a single rule with one coverage per input position.
+    Coverages {
+        /// Coverage of each input position.
+        coverages: Vec<Coverage>,
+        /// Lookups applied when the sequence matches.
+        seq_lookup_records: Vec<SequenceLookupRecord>,
+    },
+}
+
+/// A rule of a glyph or class based chained sequence context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ChainedSequenceRule {
+    /// Backtrack glyphs (or classes), nearest first.
+    pub backtrack_sequence: Vec<u16>,
+    /// Glyphs (or classes) following the first, covered input glyph.
+    pub input_sequence: Vec<u16>,
+    /// Lookahead glyphs (or classes).
+    pub lookahead_sequence: Vec<u16>,
+    /// Lookups applied when the rule matches.
+    pub seq_lookup_records: Vec<SequenceLookupRecord>,
+}
+
+/// Chained contextual substitution/positioning subtable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChainedSequenceContext {
+    /// Format 1: rules on glyph sequences, one rule set per covered glyph.
+    Glyphs {
+        /// First glyphs of the input sequences.
+        coverage: Coverage,
+        /// Rule sets in coverage order.
+        rule_sets: Vec<Vec<ChainedSequenceRule>>,
+    },
+    /// Format 2: rules on class sequences, one rule set per input class.
+    Classes {
+        /// First glyphs of the input sequences.
+        coverage: Coverage,
+        /// Classes of the backtrack glyphs.
+        backtrack_class_def: ClassDef,
+        /// Classes of the input glyphs.
+        input_class_def: ClassDef,
+        /// Classes of the lookahead glyphs.
+        lookahead_class_def: ClassDef,
+        /// Rule sets indexed by the class of the first glyph.
+        rule_sets: Vec<Vec<ChainedSequenceRule>>,
+    },
+    /// Format 3: a single rule with one coverage per position.
+    Coverages {
+        /// Coverage of each backtrack position, nearest first.
+        backtrack_coverages: Vec<Coverage>,
+        /// Coverage of each input position.
+        input_coverages: Vec<Coverage>,
+        /// Coverage of each lookahead position.
+        lookahead_coverages: Vec<Coverage>,
+        /// Lookups applied when the sequence matches.
+        seq_lookup_records: Vec<SequenceLookupRecord>,
+    },
+}
+
+/// Reads the structure at `offset` from the start of `data`.
+pub(crate) fn read_at<'a, T>(
+    data: &FontDataStream<'a>,
+    offset: usize,
+    read: impl FnOnce(&mut FontDataStream<'a>) -> Result<T, Error>,
+) -> Result<T, Error> {
+    read(&mut data.substream(offset)?)
+}
+
+/// Reads the structure at `offset` from the start of `data`, or `None` for a
+/// null offset.
+pub(crate) fn read_optional_at<'a, T>(
+    data: &FontDataStream<'a>,
+    offset: usize,
+    read: impl FnOnce(&mut FontDataStream<'a>) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    if offset == 0 {
+        Ok(None)
+    } else {
+        read_at(data, offset, read).map(Some)
+    }
+}
+
+/// Writes an `Offset16` measured from `base` to a deferred subtable.
+pub(crate) fn write_child16(writer: &mut FontDataWriter, base: usize, subtable: Vec<u8>) {
+    let placeholder = writer.reserve_offset16(base);
+    writer.defer_subtable(placeholder, subtable);
+}
+
+/// Writes an `Offset16` to an optional deferred subtable (0 for `None`).
+pub(crate) fn write_optional_child16(
+    writer: &mut FontDataWriter,
+    base: usize,
+    subtable: Option<Vec<u8>>,
+) {
+    match subtable {
+        Some(subtable) => write_child16(writer, base, subtable),
+        None => writer.write_u16(0),
+    }
+}
+
+fn read_u16_array(data: &mut FontDataStream, count: usize) -> Result<Vec<u16>, Error> {
+    (0..count).map(|_| data.read_u16()).collect()
+}
+
+fn write_u16_array(writer: &mut FontDataWriter, values: &[u16]) {
+    for &value in values {
+        writer.write_u16(value);
+    }
+}
+
+/// Extracts a coverage table.
+pub fn read_coverage(data: &mut FontDataStream) -> Result<Coverage, Error> {
+    let format = data.read_u16()?;
+    let mut glyphs = Vec::new();
+    match format {
+        1 => {
+            let count = data.read_u16()?;
+            glyphs = read_u16_array(data, count as usize)?;
+        }
+        2 => {
+            let count = data.read_u16()?;
+            for _ in 0..count {
+                let start = data.read_u16()?;
+                let end = data.read_u16()?;
+                data.skip(2); // startCoverageIndex
+                glyphs.extend(start..=end);
+            }
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    Ok(Coverage::new(glyphs))
+}
+
+/// Serializes a coverage table in whichever format is smaller.
+pub fn write_coverage(writer: &mut FontDataWriter, coverage: &Coverage) {
+    let ranges = glyph_ranges(coverage.glyphs.iter().map(|&g| (g, 0)));
+    if ranges.len() * 3 < coverage.glyphs.len() {
+        writer.write_u16(2);
+        writer.write_u16(ranges.len() as u16);
+        let mut index = 0u16;
+        for (start, end, _) in ranges {
+            writer.write_u16(start);
+            writer.write_u16(end);
+            writer.write_u16(index);
+            index += end - start + 1;
+        }
+    } else {
+        writer.write_u16(1);
+        writer.write_u16(coverage.glyphs.len() as u16);
+        write_u16_array(writer, &coverage.glyphs);
+    }
+}
+
+/// Groups sorted `(glyph, value)` pairs into ranges of consecutive glyphs
+/// sharing a value.
+fn glyph_ranges(items: impl Iterator<Item = (u16, u16)>) -> Vec<(u16, u16, u16)> {
+    let mut ranges: Vec<(u16, u16, u16)> = Vec::new();
+    for (glyph, value) in items {
+        match ranges.last_mut() {
+            Some((_, end, v)) if *end as u32 + 1 == glyph as u32 && *v == value => *end = glyph,
+            _ => ranges.push((glyph, glyph, value)),
+        }
+    }
+    ranges
+}
+
+/// Extracts a class definition table.
+pub fn read_class_def(data: &mut FontDataStream) -> Result<ClassDef, Error> {
+    let format = data.read_u16()?;
+    let mut classes = BTreeMap::new();
+    match format {
+        1 => {
+            let start = data.read_u16()?;
+            let count = data.read_u16()?;
+            for i in 0..count {
+                let class = data.read_u16()?;
+                if class != 0 {
+                    classes.insert(start.wrapping_add(i), class);
+                }
+            }
+        }
+        2 => {
+            let count = data.read_u16()?;
+            for _ in 0..count {
+                let start = data.read_u16()?;
+                let end = data.read_u16()?;
+                let class = data.read_u16()?;
+                if class != 0 {
+                    for glyph in start..=end {
+                        classes.insert(glyph, class);
+                    }
+                }
+            }
+        }
+        _ => return Err(Error::InvalidFormat),
+    }
+    Ok(ClassDef { classes })
+}
+
+/// Serializes a class definition table in whichever format is smaller.
+pub fn write_class_def(writer: &mut FontDataWriter, class_def: &ClassDef) {
+    let ranges = glyph_ranges(class_def.classes.iter().map(|(&g, &c)| (g, c)));
+    let first = class_def.classes.keys().next().copied().unwrap_or(0);
+    let last = class_def.classes.keys().next_back().copied().unwrap_or(0);
+    let span = if class_def.classes.is_empty() {
+        0
+    } else {
+        (last - first) as usize + 1
+    };
+    if !class_def.classes.is_empty() && span < ranges.len() * 3 {
+        writer.write_u16(1);
+        writer.write_u16(first);
+        writer.write_u16(span as u16);
+        for glyph in first..=last {
+            writer.write_u16(class_def.class(glyph));
+        }
+    } else {
+        writer.write_u16(2);
+        writer.write_u16(ranges.len() as u16);
+        for (start, end, class) in ranges {
+            writer.write_u16(start);
+            writer.write_u16(end);
+            writer.write_u16(class);
+        }
+    }
+}
+
+/// Extracts a device or variation index table.
+///
+/// Unknown delta formats yield a device table without deltas.
+pub fn read_device(data: &mut FontDataStream) -> Result<DeviceTable, Error> {
+    let start_size = data.read_u16()?;
+    let end_size = data.read_u16()?;
+    let delta_format = data.read_u16()?;
+    if delta_format == 0x8000 {
+        return Ok(DeviceTable::VariationIndex {
+            delta_set_outer_index: start_size,
+            delta_set_inner_index: end_size,
+        });
+    }
+    let words = if (1..=3).contains(&delta_format) && end_size >= start_size {
+        let count = (end_size - start_size) as usize + 1;
+        (count << delta_format).div_ceil(16)
+    } else {
+        0
+    };
+    Ok(DeviceTable::Hinting {
+        start_size,
+        end_size,
+        delta_format,
+        delta_values: read_u16_array(data, words)?,
+    })
+}
+
+/// Serializes a device or variation index table.
+pub fn write_device(writer: &mut FontDataWriter, device: &DeviceTable) {
+    match device {
+        DeviceTable::Hinting {
+            start_size,
+            end_size,
+            delta_format,
+            delta_values,
+        } => {
+            writer.write_u16(*start_size);
+            writer.write_u16(*end_size);
+            writer.write_u16(*delta_format);
+            write_u16_array(writer, delta_values);
+        }
+        DeviceTable::VariationIndex {
+            delta_set_outer_index,
+            delta_set_inner_index,
+        } => {
+            writer.write_u16(*delta_set_outer_index);
+            writer.write_u16(*delta_set_inner_index);
+            writer.write_u16(0x8000);
+        }
+    }
+}
+
+/// Serializes a device table into its own buffer.
+pub(crate) fn device_bytes(device: &DeviceTable) -> Vec<u8> {
+    let mut writer = FontDataWriter::new();
+    write_device(&mut writer, device);
+    writer.as_bytes().to_vec()
+}
+
+fn read_lang_sys(data: &mut FontDataStream) -> Result<LangSys, Error> {
+    data.skip(2); // lookupOrderOffset
+    let required_feature_index = data.read_u16()?;
+    let count = data.read_u16()?;
+    Ok(LangSys {
+        required_feature_index,
+        feature_indices: read_u16_array(data, count as usize)?,
+    })
+}
+
+fn write_lang_sys(writer: &mut FontDataWriter, lang_sys: &LangSys) {
+    writer.write_u16(0);
+    writer.write_u16(lang_sys.required_feature_index);
+    writer.write_u16(lang_sys.feature_indices.len() as u16);
+    write_u16_array(writer, &lang_sys.feature_indices);
+}
+
+fn read_script(data: &mut FontDataStream) -> Result<Script, Error> {
+    let default_offset = data.read_u16()?;
+    let default_lang_sys = read_optional_at(data, default_offset as usize, read_lang_sys)?;
+    let count = data.read_u16()?;
+    let mut lang_sys_records = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let lang_sys_tag = data.read_tag()?;
+        let offset = data.read_u16()?;
+        lang_sys_records.push(LangSysRecord {
+            lang_sys_tag,
+            lang_sys: read_at(data, offset as usize, read_lang_sys)?,
+        });
+    }
+    Ok(Script {
+        default_lang_sys,
+        lang_sys_records,
+    })
+}
+
+fn write_script(writer: &mut FontDataWriter, script: &Script) -> Result<(), Error> {
+    let default = match &script.default_lang_sys {
+        Some(lang_sys) => Some(serialize(|w| {
+            write_lang_sys(w, lang_sys);
+            Ok(())
+        })?),
+        None => None,
+    };
+    write_optional_child16(writer, 0, default);
+    writer.write_u16(script.lang_sys_records.len() as u16);
+    for record in &script.lang_sys_records {
+        writer.write_tag(record.lang_sys_tag);
+        let bytes = serialize(|w| {
+            write_lang_sys(w, &record.lang_sys);
+            Ok(())
+        })?;
+        write_child16(writer, 0, bytes);
+    }
+    Ok(())
+}
+
+/// Extracts a script list.
+pub fn read_script_list(data: &mut FontDataStream) -> Result<Vec<ScriptRecord>, Error> {
+    let count = data.read_u16()?;
+    let mut scripts = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let script_tag = data.read_tag()?;
+        let offset = data.read_u16()?;
+        scripts.push(ScriptRecord {
+            script_tag,
+            script: read_at(data, offset as usize, read_script)?,
+        });
+    }
+    Ok(scripts)
+}
+
+/// Serializes a script list.
+pub fn write_script_list(
+    writer: &mut FontDataWriter,
+    scripts: &[ScriptRecord],
+) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(scripts.len() as u16);
+    for record in scripts {
+        writer.write_tag(record.script_tag);
+        let bytes = serialize(|w| write_script(w, &record.script))?;
+        write_child16(writer, base, bytes);
+    }
+    Ok(())
+}
+
+/// Returns the length of the feature parameters of a feature, if its tag
+/// defines any.
+fn feature_params_len(tag: &[u8; 4], params: &FontDataStream) -> Result<Option<usize>, Error> {
+    Ok(match tag {
+        b"size" => Some(10),
+        [b's', b's', a, b] if a.is_ascii_digit() && b.is_ascii_digit() => Some(4),
+        [b'c', b'v', a, b] if a.is_ascii_digit() && b.is_ascii_digit() => {
+            Some(14 + 3 * params.read_at_u16(12)? as usize)
+        }
+        _ => None,
+    })
+}
+
+fn read_feature(data: &mut FontDataStream, tag: &[u8; 4]) -> Result<Feature, Error> {
+    let params_offset = data.read_u16()? as usize;
+    let count = data.read_u16()?;
+    let lookup_list_indices = read_u16_array(data, count as usize)?;
+    let mut feature_params = None;
+    if params_offset != 0 {
+        let params = data.substream(params_offset)?;
+        if let Some(len) = feature_params_len(tag, &params)? {
+            feature_params = Some(params.slice_at(0, len)?.to_vec());
+        }
+    }
+    Ok(Feature {
+        feature_params,
+        lookup_list_indices,
+    })
+}
+
+fn write_feature(writer: &mut FontDataWriter, feature: &Feature) {
+    let base = writer.position();
+    write_optional_child16(writer, base, feature.feature_params.clone());
+    writer.write_u16(feature.lookup_list_indices.len() as u16);
+    write_u16_array(writer, &feature.lookup_list_indices);
+}
+
+/// Extracts a feature list.
+pub fn read_feature_list(data: &mut FontDataStream) -> Result<Vec<FeatureRecord>, Error> {
+    let count = data.read_u16()?;
+    let mut features = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let feature_tag = data.read_tag()?;
+        let offset = data.read_u16()?;
+        let feature = read_at(data, offset as usize, |d| read_feature(d, &feature_tag))?;
+        features.push(FeatureRecord {
+            feature_tag,
+            feature,
+        });
+    }
+    Ok(features)
+}
+
+/// Serializes a feature list.
+pub fn write_feature_list(
+    writer: &mut FontDataWriter,
+    features: &[FeatureRecord],
+) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(features.len() as u16);
+    for record in features {
+        writer.write_tag(record.feature_tag);
+        let bytes = serialize(|w| {
+            write_feature(w, &record.feature);
+            Ok(())
+        })?;
+        write_child16(writer, base, bytes);
+    }
+    Ok(())
+}
+
+fn read_condition_set(data: &mut FontDataStream) -> Result<Vec<Condition>, Error> {
+    let count = data.read_u16()?;
+    let mut conditions = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let offset = data.read_u32()?;
+        conditions.push(read_at(data, offset as usize, |c| {
+            if c.read_u16()? != 1 {
+                return Err(Error::InvalidFormat);
+            }
+            Ok(Condition {
+                axis_index: c.read_u16()?,
+                filter_range_min_value: c.read_f2dot14()?,
+                filter_range_max_value: c.read_f2dot14()?,
+            })
+        })?);
+    }
+    Ok(conditions)
+}
+
+fn read_feature_table_substitution(
+    data: &mut FontDataStream,
+) -> Result<Vec<(u16, Feature)>, Error> {
+    data.skip(4); // version
+    let count = data.read_u16()?;
+    let mut substitutions = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let feature_index = data.read_u16()?;
+        let offset = data.read_u32()?;
+        let feature = read_at(data, offset as usize, |d| read_feature(d, &[0; 4]))?;
+        substitutions.push((feature_index, feature));
+    }
+    Ok(substitutions)
+}
+
+/// Extracts a feature variations table.
+pub fn read_feature_variations(data: &mut FontDataStream) -> Result<FeatureVariations, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    let count = data.read_u32()?;
+    let mut records = Vec::with_capacity(count.min(0x1000) as usize);
+    for _ in 0..count {
+        let condition_offset = data.read_u32()? as usize;
+        let substitution_offset = data.read_u32()? as usize;
+        records.push(FeatureVariationRecord {
+            conditions: read_optional_at(data, condition_offset, read_condition_set)?
+                .unwrap_or_default(),
+            substitutions: read_optional_at(
+                data,
+                substitution_offset,
+                read_feature_table_substitution,
+            )?
+            .unwrap_or_default(),
+        });
+    }
+    Ok(FeatureVariations {
+        major_version,
+        minor_version,
+        records,
+    })
+}
+
+/// Serializes a feature variations table.
+fn write_feature_variations(
+    writer: &mut FontDataWriter,
+    variations: &FeatureVariations,
+) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(variations.major_version);
+    writer.write_u16(variations.minor_version);
+    writer.write_u32(variations.records.len() as u32);
+    for record in &variations.records {
+        let conditions = serialize(|w| {
+            w.write_u16(record.conditions.len() as u16);
+            for condition in &record.conditions {
+                let placeholder = w.reserve_offset32(0);
+                let mut c = FontDataWriter::new();
+                c.write_u16(1);
+                c.write_u16(condition.axis_index);
+                c.write_f2dot14(condition.filter_range_min_value);
+                c.write_f2dot14(condition.filter_range_max_value);
+                w.defer_subtable(placeholder, c.into_bytes()?);
+            }
+            Ok(())
+        })?;
+        let substitutions = serialize(|w| {
+            w.write_u16(1);
+            w.write_u16(0);
+            w.write_u16(record.substitutions.len() as u16);
+            for (index, feature) in &record.substitutions {
+                w.write_u16(*index);
+                let placeholder = w.reserve_offset32(0);
+                let bytes = serialize(|f| {
+                    write_feature(f, feature);
+                    Ok(())
+                })?;
+                w.defer_subtable(placeholder, bytes);
+            }
+            Ok(())
+        })?;
+        let placeholder = writer.reserve_offset32(base);
+        writer.defer_subtable(placeholder, conditions);
+        let placeholder = writer.reserve_offset32(base);
+        writer.defer_subtable(placeholder, substitutions);
+    }
+    Ok(())
+}
+
+fn read_seq_lookup_records(
+    data: &mut FontDataStream,
+    count: u16,
+) -> Result<Vec<SequenceLookupRecord>, Error> {
+    (0..count)
+        .map(|_| {
+            Ok(SequenceLookupRecord {
+                sequence_index: data.read_u16()?,
+                lookup_list_index: data.read_u16()?,
+            })
+        })
+        .collect()
+}
+
+fn write_seq_lookup_records(writer: &mut FontDataWriter, records: &[SequenceLookupRecord]) {
+    for record in records {
+        writer.write_u16(record.sequence_index);
+        writer.write_u16(record.lookup_list_index);
+    }
+}
+
+/// Reads an array of offsets to rule sets; null offsets give empty sets.
+fn read_rule_sets<'a, T>(
+    data: &mut FontDataStream<'a>,
+    read_rule: impl Fn(&mut FontDataStream<'a>) -> Result<T, Error>,
+) -> Result<Vec<Vec<T>>, Error> {
+    let count = data.read_u16()?;
+    let mut rule_sets = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let offset = data.read_u16()? as usize;
+        let rules = read_optional_at(data, offset, |set| {
+            let count = set.read_u16()?;
+            let mut rules = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let offset = set.read_u16()? as usize;
+                rules.push(read_at(set, offset, &read_rule)?);
+            }
+            Ok(rules)
+        })?;
+        rule_sets.push(rules.unwrap_or_default());
+    }
+    Ok(rule_sets)
+}
+
+/// Writes an array of offsets to rule sets; empty sets get null offsets.
+fn write_rule_sets<T>(
+    writer: &mut FontDataWriter,
+    rule_sets: &[Vec<T>],
+    write_rule: impl Fn(&mut FontDataWriter, &T),
+) -> Result<(), Error> {
+    writer.write_u16(rule_sets.len() as u16);
+    for rules in rule_sets {
+        if rules.is_empty() {
+            writer.write_u16(0);
+            continue;
+        }
+        let bytes = serialize(|set| {
+            set.write_u16(rules.len() as u16);
+            for rule in rules {
+                let bytes = serialize(|w| {
+                    write_rule(w, rule);
+                    Ok(())
+                })?;
+                write_child16(set, 0, bytes);
+            }
+            Ok(())
+        })?;
+        write_child16(writer, 0, bytes);
+    }
+    Ok(())
+}
+
+fn read_coverages<'a>(
+    data: &mut FontDataStream<'a>,
+    count: u16,
+    base: &FontDataStream<'a>,
+) -> Result<Vec<Coverage>, Error> {
+    (0..count)
+        .map(|_| {
+            let offset = data.read_u16()? as usize;
+            read_at(base, offset, read_coverage)
+        })
+        .collect()
+}
+
+fn write_coverages(writer: &mut FontDataWriter, coverages: &[Coverage]) {
+    writer.write_u16(coverages.len() as u16);
+    for coverage in coverages {
+        write_child16(writer, 0, coverage_bytes(coverage));
+    }
+}
+
+/// Serializes a coverage table into its own buffer.
+pub(crate) fn coverage_bytes(coverage: &Coverage) -> Vec<u8> {
+    let mut writer = FontDataWriter::new();
+    write_coverage(&mut writer, coverage);
+    writer.as_bytes().to_vec()
+}
+
+/// Serializes a class definition table into its own buffer.
+pub(crate) fn class_def_bytes(class_def: &ClassDef) -> Vec<u8> {
+    let mut writer = FontDataWriter::new();
+    write_class_def(&mut writer, class_def);
+    writer.as_bytes().to_vec()
+}
+
+fn read_sequence_rule(data: &mut FontDataStream) -> Result<SequenceRule, Error> {
+    let glyph_count = data.read_u16()?;
+    let seq_lookup_count = data.read_u16()?;
+    Ok(SequenceRule {
+        input_sequence: read_u16_array(data, glyph_count.saturating_sub(1) as usize)?,
+        seq_lookup_records: read_seq_lookup_records(data, seq_lookup_count)?,
+    })
+}
+
+fn write_sequence_rule(writer: &mut FontDataWriter, rule: &SequenceRule) {
+    writer.write_u16(rule.input_sequence.len() as u16 + 1);
+    writer.write_u16(rule.seq_lookup_records.len() as u16);
+    write_u16_array(writer, &rule.input_sequence);
+    write_seq_lookup_records(writer, &rule.seq_lookup_records);
+}
+
+/// Extracts a sequence context subtable.
+pub fn read_sequence_context(data: &mut FontDataStream) -> Result<SequenceContext, Error> {
+    let format = data.read_u16()?;
+    match format {
+        1 => {
+            let coverage_offset = data.read_u16()? as usize;
+            Ok(SequenceContext::Glyphs {
+                coverage: read_at(data, coverage_offset, read_coverage)?,
+                rule_sets: read_rule_sets(data, read_sequence_rule)?,
+            })
+        }
+        2 => {
+            let coverage_offset = data.read_u16()? as usize;
+            let class_def_offset = data.read_u16()? as usize;
+            Ok(SequenceContext::Classes {
+                coverage: read_at(data, coverage_offset, read_coverage)?,
+                class_def: read_optional_at(data, class_def_offset, read_class_def)?
+                    .unwrap_or_default(),
+                rule_sets: read_rule_sets(data, read_sequence_rule)?,
+            })
+        }
+        3 => {
+            let glyph_count = data.read_u16()?;
+            let seq_lookup_count = data.read_u16()?;
+            let base = data.substream(0)?;
+            Ok(SequenceContext::Coverages {
+                coverages: read_coverages(data, glyph_count, &base)?,
+                seq_lookup_records: read_seq_lookup_records(data, seq_lookup_count)?,
+            })
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+/// Serializes a sequence context subtable.
+pub fn write_sequence_context(
+    writer: &mut FontDataWriter,
+    context: &SequenceContext,
+) -> Result<(), Error> {
+    match context {
+        SequenceContext::Glyphs {
+            coverage,
+            rule_sets,
+        } => {
+            writer.write_u16(1);
+            write_child16(writer, 0, coverage_bytes(coverage));
+            write_rule_sets(writer, rule_sets, write_sequence_rule)
+        }
+        SequenceContext::Classes {
+            coverage,
+            class_def,
+            rule_sets,
+        } => {
+            writer.write_u16(2);
+            write_child16(writer, 0, coverage_bytes(coverage));
+            write_child16(writer, 0, class_def_bytes(class_def));
+            write_rule_sets(writer, rule_sets, write_sequence_rule)
+        }
+        SequenceContext::Coverages {
+            coverages,
+            seq_lookup_records,
+        } => {
+            writer.write_u16(3);
+            writer.write_u16(coverages.len() as u16);
+            writer.write_u16(seq_lookup_records.len() as u16);
+            for coverage in coverages {
+                write_child16(writer, 0, coverage_bytes(coverage));
+            }
+            write_seq_lookup_records(writer, seq_lookup_records);
+            Ok(())
+        }
+    }
+}
+
+fn read_chained_sequence_rule(data: &mut FontDataStream) -> Result<ChainedSequenceRule, Error> {
+    let backtrack_count = data.read_u16()?;
+    let backtrack_sequence = read_u16_array(data, backtrack_count as usize)?;
+    let input_count = data.read_u16()?;
+    let input_sequence = read_u16_array(data, input_count.saturating_sub(1) as usize)?;
+    let lookahead_count = data.read_u16()?;
+    let lookahead_sequence = read_u16_array(data, lookahead_count as usize)?;
+    let seq_lookup_count = data.read_u16()?;
+    Ok(ChainedSequenceRule {
+        backtrack_sequence,
+        input_sequence,
+        lookahead_sequence,
+        seq_lookup_records: read_seq_lookup_records(data, seq_lookup_count)?,
+    })
+}
+
+fn write_chained_sequence_rule(writer: &mut FontDataWriter, rule: &ChainedSequenceRule) {
+    writer.write_u16(rule.backtrack_sequence.len() as u16);
+    write_u16_array(writer, &rule.backtrack_sequence);
+    writer.write_u16(rule.input_sequence.len() as u16 + 1);
+    write_u16_array(writer, &rule.input_sequence);
+    writer.write_u16(rule.lookahead_sequence.len() as u16);
+    write_u16_array(writer, &rule.lookahead_sequence);
+    writer.write_u16(rule.seq_lookup_records.len() as u16);
+    write_seq_lookup_records(writer, &rule.seq_lookup_records);
+}
+
+/// Extracts a chained sequence context subtable.
+pub fn read_chained_sequence_context(
+    data: &mut FontDataStream,
+) -> Result<ChainedSequenceContext, Error> {
+    let format = data.read_u16()?;
+    match format {
+        1 => {
+            let coverage_offset = data.read_u16()? as usize;
+            Ok(ChainedSequenceContext::Glyphs {
+                coverage: read_at(data, coverage_offset, read_coverage)?,
+                rule_sets: read_rule_sets(data, read_chained_sequence_rule)?,
+            })
+        }
+        2 => {
+            let coverage_offset = data.read_u16()? as usize;
+            let backtrack_offset = data.read_u16()? as usize;
+            let input_offset = data.read_u16()? as usize;
+            let lookahead_offset = data.read_u16()? as usize;
+            Ok(ChainedSequenceContext::Classes {
+                coverage: read_at(data, coverage_offset, read_coverage)?,
+                backtrack_class_def: read_optional_at(data, backtrack_offset, read_class_def)?
+                    .unwrap_or_default(),
+                input_class_def: read_optional_at(data, input_offset, read_class_def)?
+                    .unwrap_or_default(),
+                lookahead_class_def: read_optional_at(data, lookahead_offset, read_class_def)?
+                    .unwrap_or_default(),
+                rule_sets: read_rule_sets(data, read_chained_sequence_rule)?,
+            })
+        }
+        3 => {
+            let base = data.substream(0)?;
+            let backtrack_count = data.read_u16()?;
+            let backtrack_coverages = read_coverages(data, backtrack_count, &base)?;
+            let input_count = data.read_u16()?;
+            let input_coverages = read_coverages(data, input_count, &base)?;
+            let lookahead_count = data.read_u16()?;
+            let lookahead_coverages = read_coverages(data, lookahead_count, &base)?;
+            let seq_lookup_count = data.read_u16()?;
+            Ok(ChainedSequenceContext::Coverages {
+                backtrack_coverages,
+                input_coverages,
+                lookahead_coverages,
+                seq_lookup_records: read_seq_lookup_records(data, seq_lookup_count)?,
+            })
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+/// Serializes a chained sequence context subtable.
+pub fn write_chained_sequence_context(
+    writer: &mut FontDataWriter,
+    context: &ChainedSequenceContext,
+) -> Result<(), Error> {
+    match context {
+        ChainedSequenceContext::Glyphs {
+            coverage,
+            rule_sets,
+        } => {
+            writer.write_u16(1);
+            write_child16(writer, 0, coverage_bytes(coverage));
+            write_rule_sets(writer, rule_sets, write_chained_sequence_rule)
+        }
+        ChainedSequenceContext::Classes {
+            coverage,
+            backtrack_class_def,
+            input_class_def,
+            lookahead_class_def,
+            rule_sets,
+        } => {
+            writer.write_u16(2);
+            write_child16(writer, 0, coverage_bytes(coverage));
+            write_child16(writer, 0, class_def_bytes(backtrack_class_def));
+            write_child16(writer, 0, class_def_bytes(input_class_def));
+            write_child16(writer, 0, class_def_bytes(lookahead_class_def));
+            write_rule_sets(writer, rule_sets, write_chained_sequence_rule)
+        }
+        ChainedSequenceContext::Coverages {
+            backtrack_coverages,
+            input_coverages,
+            lookahead_coverages,
+            seq_lookup_records,
+        } => {
+            writer.write_u16(3);
+            write_coverages(writer, backtrack_coverages);
+            write_coverages(writer, input_coverages);
+            write_coverages(writer, lookahead_coverages);
+            writer.write_u16(seq_lookup_records.len() as u16);
+            write_seq_lookup_records(writer, seq_lookup_records);
+            Ok(())
+        }
+    }
+}
+
+fn read_lookup<'a, T>(
+    data: &mut FontDataStream<'a>,
+    extension_type: u16,
+    read_subtable: &impl Fn(&mut FontDataStream<'a>, u16) -> Result<T, Error>,
+) -> Result<Lookup<T>, Error> {
+    let lookup_type = data.read_u16()?;
+    let lookup_flag = data.read_u16()?;
+    let count = data.read_u16()?;
+    let offsets = read_u16_array(data, count as usize)?;
+    let mark_filtering_set = if lookup_flag & USE_MARK_FILTERING_SET != 0 {
+        Some(data.read_u16()?)
+    } else {
+        None
+    };
+    let mut actual_type = lookup_type;
+    let mut subtables = Vec::with_capacity(offsets.len());
+    for offset in offsets {
+        let mut subtable = data.substream(offset as usize)?;
+        if lookup_type == extension_type {
+            // Extension subtables wrap the real subtable behind an Offset32.
+            subtable.skip(2);
+            actual_type = subtable.read_u16()?;
+            let inner_offset = subtable.read_u32()? as usize;
+            subtables.push(read_at(&subtable, inner_offset, |d| {
+                read_subtable(d, actual_type)
+            })?);
+        } else {
+            subtables.push(read_subtable(&mut subtable, lookup_type)?);
+        }
+    }
+    Ok(Lookup {
+        lookup_type: actual_type,
+        lookup_flag,
+        subtables,
+        mark_filtering_set,
+    })
+}
+
+/// Extracts a lookup list: a count and `Offset16`s to lookups measured from
+/// the start of the list. `GSUB` and `GPOS` share it, and so does `JSTF`.
+pub(crate) fn read_lookup_list<'a, T>(
+    list: &mut FontDataStream<'a>,
+    extension_type: u16,
+    read_subtable: &impl Fn(&mut FontDataStream<'a>, u16) -> Result<T, Error>,
+) -> Result<Vec<Lookup<T>>, Error> {
+    let count = list.read_u16()?;
+    let mut lookups = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let offset = list.read_u16()? as usize;
+        lookups.push(read_at(list, offset, |d| {
+            read_lookup(d, extension_type, read_subtable)
+        })?);
+    }
+    Ok(lookups)
+}
+
+/// Extracts the common structure of a `GSUB` or `GPOS` table.
+///
+/// `read_subtable` receives a stream positioned at a subtable and its lookup
+/// type; extension subtables of type `extension_type` are unwrapped first.
+pub(crate) fn read_layout_table<'a, T>(
+    data: &mut FontDataStream<'a>,
+    extension_type: u16,
+    read_subtable: impl Fn(&mut FontDataStream<'a>, u16) -> Result<T, Error>,
+) -> Result<LayoutTable<T>, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let script_offset = data.read_u16()? as usize;
+    let feature_offset = data.read_u16()? as usize;
+    let lookup_offset = data.read_u16()? as usize;
+    let variations_offset = if minor_version >= 1 {
+        data.read_u32()? as usize
+    } else {
+        0
+    };
+
+    let scripts = read_optional_at(data, script_offset, read_script_list)?.unwrap_or_default();
+    let features = read_optional_at(data, feature_offset, read_feature_list)?.unwrap_or_default();
+    let lookups = read_optional_at(data, lookup_offset, |list| {
+        read_lookup_list(list, extension_type, &read_subtable)
+    })?
+    .unwrap_or_default();
+    let feature_variations = read_optional_at(data, variations_offset, read_feature_variations)?;
+
+    Ok(LayoutTable {
+        major_version,
+        minor_version,
+        scripts,
+        features,
+        lookups,
+        feature_variations,
+    })
+}
+
+/// Builds the lookup list. Without extensions every offset is 16-bit and may
+/// overflow; with extensions each subtable is reached through an Offset32.
+fn write_lookup_list<T>(
+    lookups: &[Lookup<T>],
+    subtables: &[Vec<Vec<u8>>],
+    extension_type: Option<u16>,
+) -> Result<Vec<u8>, Error> {
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(lookups.len() as u16);
+    let placeholders: Vec<_> = lookups.iter().map(|_| writer.reserve_offset16(0)).collect();
+    for ((lookup, bytes), placeholder) in lookups.iter().zip(subtables).zip(placeholders) {
+        writer.patch_offset_here(placeholder)?;
+        let base = writer.position();
+        writer.write_u16(extension_type.unwrap_or(lookup.lookup_type));
+        let mut flag = lookup.lookup_flag & !USE_MARK_FILTERING_SET;
+        if lookup.mark_filtering_set.is_some() {
+            flag |= USE_MARK_FILTERING_SET;
+        }
+        writer.write_u16(flag);
+        writer.write_u16(bytes.len() as u16);
+        let offsets: Vec<_> = bytes
+            .iter()
+            .map(|_| writer.reserve_offset16(base))
+            .collect();
+        if let Some(set) = lookup.mark_filtering_set {
+            writer.write_u16(set);
+        }
+        for (subtable, offset) in bytes.iter().zip(offsets) {
+            writer.patch_offset_here(offset)?;
+            if extension_type.is_some() {
+                let start = writer.position();
+                writer.write_u16(1);
+                writer.write_u16(lookup.lookup_type);
+                let placeholder = writer.reserve_offset32(start);
+                writer.defer_subtable(placeholder, subtable.clone());
+            } else {
+                writer.write_bytes(subtable);
+            }
+        }
+    }
+    writer.into_bytes()
+}
+
+/// Serializes a lookup list into its own buffer.
+///
+/// Lookups are wrapped in extension subtables (`extension_type`) only when
+/// the list does not fit 16-bit offsets.
+pub(crate) fn lookup_list_bytes<T>(
+    lookups: &[Lookup<T>],
+    extension_type: u16,
+    write_subtable: impl Fn(&mut FontDataWriter, &T) -> Result<(), Error>,
+) -> Result<Vec<u8>, Error> {
+    let subtables = lookups
+        .iter()
+        .map(|lookup| {
+            lookup
+                .subtables
+                .iter()
+                .map(|subtable| serialize(|w| write_subtable(w, subtable)))
+                .collect::<Result<Vec<_>, Error>>()
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    match write_lookup_list(lookups, &subtables, None) {
+        Err(Error::Io(IoError::OffsetOverflow { .. })) => {
+            write_lookup_list(lookups, &subtables, Some(extension_type))
+        }
+        result => result,
+    }
+}
+
+/// Serializes the common structure of a `GSUB` or `GPOS` table.
+///
+/// Lookups are wrapped in extension subtables (`extension_type`) only when
+/// the lookup list does not fit 16-bit offsets.
+pub(crate) fn write_layout_table<T>(
+    writer: &mut FontDataWriter,
+    table: &LayoutTable<T>,
+    extension_type: u16,
+    write_subtable: impl Fn(&mut FontDataWriter, &T) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let lookup_list = lookup_list_bytes(&table.lookups, extension_type, write_subtable)?;
+
+    let base = writer.position();
+    let has_variations = table.feature_variations.is_some();
+    writer.write_u16(table.major_version);
+    writer.write_u16(if has_variations {
+        table.minor_version.max(1)
+    } else {
+        0
+    });
+    let script_placeholder = writer.reserve_offset16(base);
+    let feature_placeholder = writer.reserve_offset16(base);
+    let lookup_placeholder = writer.reserve_offset16(base);
+    let variations_placeholder = has_variations.then(|| writer.reserve_offset32(base));
+
+    writer.patch_offset_here(script_placeholder)?;
+    let bytes = serialize(|w| write_script_list(w, &table.scripts))?;
+    writer.write_bytes(&bytes);
+    writer.patch_offset_here(feature_placeholder)?;
+    let bytes = serialize(|w| write_feature_list(w, &table.features))?;
+    writer.write_bytes(&bytes);
+    writer.patch_offset_here(lookup_placeholder)?;
+    writer.write_bytes(&lookup_list);
+    if let (Some(placeholder), Some(variations)) =
+        (variations_placeholder, &table.feature_variations)
+    {
+        writer.patch_offset_here(placeholder)?;
+        let bytes = serialize(|w| write_feature_variations(w, variations))?;
+        writer.write_bytes(&bytes);
+    }
+    Ok(())
+}
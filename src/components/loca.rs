@@ -0,0 +1,87 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `loca` (Index to Location) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Index to location table representation.
+///
+/// Offsets are stored as byte offsets into `glyf` regardless of the on-disk
+/// format; there is one more offset than there are glyphs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct LocaTable {
+    /// Byte offsets of each glyph in `glyf`, plus the end offset.
+    pub offsets: Vec<u32>,
+}
+
+impl LocaTable {
+    /// Returns the number of glyphs described by the table.
+    pub fn num_glyphs(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns the byte range of a glyph in `glyf`.
+    pub fn glyph_range(&self, glyph_id: u16) -> Option<std::ops::Range<usize>> {
+        let index = glyph_id as usize;
+        let start = *self.offsets.get(index)? as usize;
+        let end = *self.offsets.get(index + 1)? as usize;
+        if start <= end { Some(start..end) } else { None }
+    }
+
+    /// Returns whether the offsets can be stored in the short format.
+    pub fn fits_short_format(&self) -> bool {
+        self.offsets
+            .iter()
+            .all(|&o| o % 2 == 0 && o / 2 <= u16::MAX as u32)
+    }
+}
+
+/// Extracts the `loca` table from the provided data stream.
+///
+/// `long_format` comes from `head.indexToLocFormat` and `num_glyphs` from `maxp`.
+pub fn read_loca(
+    data: &mut FontDataStream,
+    long_format: bool,
+    num_glyphs: u16,
+) -> Result<LocaTable, Error> {
+    let count = num_glyphs as usize + 1;
+    let mut offsets = Vec::with_capacity(count);
+    for _ in 0..count {
+        offsets.push(if long_format {
+            data.read_u32()?
+        } else {
+            data.read_u16()? as u32 * 2
+        });
+    }
+    Ok(LocaTable { offsets })
+}
+
+/// Serializes the `loca` table in the short or long format.
+///
+/// Returns `IoError::OffsetOverflow` when the short format is requested but an
+/// offset is odd or too large.
+pub fn write_loca(
+    writer: &mut FontDataWriter,
+    loca: &LocaTable,
+    long_format: bool,
+) -> Result<(), Error> {
+    for &offset in &loca.offsets {
+        if long_format {
+            writer.write_u32(offset);
+        } else if offset % 2 == 0 && offset / 2 <= u16::MAX as u32 {
+            writer.write_u16((offset / 2) as u16);
+        } else {
+            return Err(Error::Io(IoError::OffsetOverflow {
+                position: writer.position(),
+                offset: offset as usize,
+            }));
+        }
+    }
+    Ok(())
+}
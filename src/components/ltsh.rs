@@ -0,0 +1,45 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `LTSH` (Linear Threshold) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Linear threshold table: the size from which each glyph's hinted advance
+/// scales linearly with the font size.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LtshTable {
+    /// Table version (0).
+    pub version: u16,
+    /// Pixels per em from which each glyph scales linearly; 1 means at every
+    /// size.
+    pub y_pels: Vec<u8>,
+}
+
+impl LtshTable {
+    /// Returns whether the hinted advance of `glyph_id` scales linearly at
+    /// `ppem`, so that it need not be hinted to compute layout. Glyphs
+    /// beyond the table never do.
+    pub fn is_linear(&self, glyph_id: u16, ppem: u16) -> bool {
+        self.y_pels
+            .get(glyph_id as usize)
+            .is_some_and(|&threshold| threshold != 0 && ppem >= threshold as u16)
+    }
+}
+
+/// Extracts the `LTSH` table from the provided data stream.
+pub fn read_ltsh(data: &mut FontDataStream) -> Result<LtshTable, Error> {
+    let version = data.read_u16()?;
+    if version != 0 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let num_glyphs = data.read_u16()?;
+    Ok(LtshTable {
+        version,
+        y_pels: data.read_bytes(num_glyphs as usize)?.to_vec(),
+    })
+}
@@ -0,0 +1,705 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `MATH` (Mathematical Typesetting) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+use super::layout::{Coverage, DeviceTable, read_at, read_coverage, read_device, read_optional_at};
+
+/// Glyph part flag: the part can be repeated to stretch the assembly.
+const EXTENDER_FLAG: u16 = 0x0001;
+/// Most times the extenders of an assembly are repeated.
+const MAX_EXTENDER_REPEATS: usize = 1024;
+
+/// Mathematical typesetting table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Global layout constants.
+    pub constants: Option<MathConstants>,
+    /// Per-glyph layout information.
+    pub glyph_info: Option<MathGlyphInfo>,
+    /// Size variants and assemblies of stretchable glyphs.
+    pub variants: Option<MathVariants>,
+}
+
+/// A value in font units with an optional device table adjustment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MathValueRecord {
+    /// Value in font units.
+    pub value: i16,
+    /// Device or variation index table adjusting the value.
+    pub device: Option<DeviceTable>,
+}
+
+impl MathValueRecord {
+    /// Creates a record without a device table.
+    pub fn new(value: i16) -> Self {
+        MathValueRecord {
+            value,
+            device: None,
+        }
+    }
+
+    /// Returns the value adjusted by the device table at a ppem size.
+    pub fn value_at(&self, ppem: u16) -> i32 {
+        self.value as i32
+            + self
+                .device
+                .as_ref()
+                .map_or(0, |device| device.delta(ppem) as i32)
+    }
+}
+
+/// Layout constants of the `MathConstants` table, in the order they are
+/// stored. Values are in font units unless noted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MathConstants {
+    /// Scale of the first script level, in percent.
+    pub script_percent_scale_down: i16,
+    /// Scale of the second script level, in percent.
+    pub script_script_percent_scale_down: i16,
+    /// Minimum height of a delimited subformula for delimiters to grow.
+    pub delimited_sub_formula_min_height: u16,
+    /// Minimum height of display-style n-ary operators.
+    pub display_operator_min_height: u16,
+    /// White space between consecutive lines of math.
+    pub math_leading: MathValueRecord,
+    /// Height of the math axis above the baseline.
+    pub axis_height: MathValueRecord,
+    /// Largest base height that needs no accent shift.
+    pub accent_base_height: MathValueRecord,
+    /// Largest base height that needs no flattened accent.
+    pub flattened_accent_base_height: MathValueRecord,
+    /// Standard shift down of subscripts.
+    pub subscript_shift_down: MathValueRecord,
+    /// Largest height of a subscript's top.
+    pub subscript_top_max: MathValueRecord,
+    /// Smallest drop of a subscript's baseline below the base's bottom.
+    pub subscript_baseline_drop_min: MathValueRecord,
+    /// Standard shift up of superscripts.
+    pub superscript_shift_up: MathValueRecord,
+    /// Standard shift up of superscripts in cramped style.
+    pub superscript_shift_up_cramped: MathValueRecord,
+    /// Smallest height of a superscript's bottom.
+    pub superscript_bottom_min: MathValueRecord,
+    /// Largest drop of a superscript's baseline below the base's top.
+    pub superscript_baseline_drop_max: MathValueRecord,
+    /// Smallest gap between a subscript and a superscript.
+    pub sub_superscript_gap_min: MathValueRecord,
+    /// Largest height of a superscript's bottom when a subscript is present.
+    pub superscript_bottom_max_with_subscript: MathValueRecord,
+    /// Extra white space after scripts.
+    pub space_after_script: MathValueRecord,
+    /// Smallest gap between an upper limit and its operator.
+    pub upper_limit_gap_min: MathValueRecord,
+    /// Smallest rise of an upper limit's baseline above the operator's top.
+    pub upper_limit_baseline_rise_min: MathValueRecord,
+    /// Smallest gap between a lower limit and its operator.
+    pub lower_limit_gap_min: MathValueRecord,
+    /// Smallest drop of a lower limit's baseline below the operator's bottom.
+    pub lower_limit_baseline_drop_min: MathValueRecord,
+    /// Standard shift up of the top element of a stack.
+    pub stack_top_shift_up: MathValueRecord,
+    /// Standard shift up of the top element of a stack in display style.
+    pub stack_top_display_style_shift_up: MathValueRecord,
+    /// Standard shift down of the bottom element of a stack.
+    pub stack_bottom_shift_down: MathValueRecord,
+    /// Standard shift down of the bottom element of a stack in display
+    /// style.
+    pub stack_bottom_display_style_shift_down: MathValueRecord,
+    /// Smallest gap between the elements of a stack.
+    pub stack_gap_min: MathValueRecord,
+    /// Smallest gap between the elements of a stack in display style.
+    pub stack_display_style_gap_min: MathValueRecord,
+    /// Standard shift up of the top element of a stretch stack.
+    pub stretch_stack_top_shift_up: MathValueRecord,
+    /// Standard shift down of the bottom element of a stretch stack.
+    pub stretch_stack_bottom_shift_down: MathValueRecord,
+    /// Smallest gap between a stretched glyph and the element above it.
+    pub stretch_stack_gap_above_min: MathValueRecord,
+    /// Smallest gap between a stretched glyph and the element below it.
+    pub stretch_stack_gap_below_min: MathValueRecord,
+    /// Standard shift up of a fraction's numerator.
+    pub fraction_numerator_shift_up: MathValueRecord,
+    /// Standard shift up of a fraction's numerator in display style.
+    pub fraction_numerator_display_style_shift_up: MathValueRecord,
+    /// Standard shift down of a fraction's denominator.
+    pub fraction_denominator_shift_down: MathValueRecord,
+    /// Standard shift down of a fraction's denominator in display style.
+    pub fraction_denominator_display_style_shift_down: MathValueRecord,
+    /// Smallest gap between a numerator and the fraction bar.
+    pub fraction_numerator_gap_min: MathValueRecord,
+    /// Smallest gap between a numerator and the fraction bar in display
+    /// style.
+    pub fraction_num_display_style_gap_min: MathValueRecord,
+    /// Thickness of the fraction bar.
+    pub fraction_rule_thickness: MathValueRecord,
+    /// Smallest gap between the fraction bar and a denominator.
+    pub fraction_denominator_gap_min: MathValueRecord,
+    /// Smallest gap between the fraction bar and a denominator in display
+    /// style.
+    pub fraction_denom_display_style_gap_min: MathValueRecord,
+    /// Horizontal gap between the parts of a skewed fraction.
+    pub skewed_fraction_horizontal_gap: MathValueRecord,
+    /// Vertical gap between the parts of a skewed fraction.
+    pub skewed_fraction_vertical_gap: MathValueRecord,
+    /// Gap between an overbar and the base's top.
+    pub overbar_vertical_gap: MathValueRecord,
+    /// Thickness of an overbar.
+    pub overbar_rule_thickness: MathValueRecord,
+    /// White space above an overbar.
+    pub overbar_extra_ascender: MathValueRecord,
+    /// Gap between an underbar and the base's bottom.
+    pub underbar_vertical_gap: MathValueRecord,
+    /// Thickness of an underbar.
+    pub underbar_rule_thickness: MathValueRecord,
+    /// White space below an underbar.
+    pub underbar_extra_descender: MathValueRecord,
+    /// Gap between a radical's bar and its radicand.
+    pub radical_vertical_gap: MathValueRecord,
+    /// Gap between a radical's bar and its radicand in display style.
+    pub radical_display_style_vertical_gap: MathValueRecord,
+    /// Thickness of a radical's bar.
+    pub radical_rule_thickness: MathValueRecord,
+    /// White space above a radical's bar.
+    pub radical_extra_ascender: MathValueRecord,
+    /// Horizontal space before a radical's degree.
+    pub radical_kern_before_degree: MathValueRecord,
+    /// Horizontal space after a radical's degree.
+    pub radical_kern_after_degree: MathValueRecord,
+    /// Rise of a radical degree's bottom, in percent of the radical's height.
+    pub radical_degree_bottom_raise_percent: i16,
+}
+
+/// Per-glyph information of the `MathGlyphInfo` table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathGlyphInfo {
+    /// Italic correction of each glyph that has one.
+    pub italics_corrections: BTreeMap<u16, MathValueRecord>,
+    /// Horizontal position accents attach to, for each glyph that has one.
+    pub top_accent_attachments: BTreeMap<u16, MathValueRecord>,
+    /// Glyphs that are extended shapes, such as stretched delimiters.
+    pub extended_shapes: Coverage,
+    /// Cut-in kerning of each glyph that has any.
+    pub kern_info: BTreeMap<u16, MathKernInfo>,
+}
+
+/// Cut-in kerning at the four corners of a glyph.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathKernInfo {
+    /// Kerning at the top right corner.
+    pub top_right: Option<MathKern>,
+    /// Kerning at the top left corner.
+    pub top_left: Option<MathKern>,
+    /// Kerning at the bottom right corner.
+    pub bottom_right: Option<MathKern>,
+    /// Kerning at the bottom left corner.
+    pub bottom_left: Option<MathKern>,
+}
+
+/// Kerning values for ranges of heights.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathKern {
+    /// Heights separating the ranges, in increasing order.
+    pub correction_heights: Vec<MathValueRecord>,
+    /// Kerning of each range; one more than there are heights.
+    pub kern_values: Vec<MathValueRecord>,
+}
+
+impl MathKern {
+    /// Returns the kerning at a height, in font units.
+    pub fn kern(&self, height: i16) -> i16 {
+        let index = self
+            .correction_heights
+            .iter()
+            .position(|correction| height < correction.value)
+            .unwrap_or(self.correction_heights.len());
+        self.kern_values
+            .get(index)
+            .or(self.kern_values.last())
+            .map_or(0, |kern| kern.value)
+    }
+}
+
+/// Size variants and assemblies of the `MathVariants` table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathVariants {
+    /// Smallest overlap between connected parts of an assembly.
+    pub min_connector_overlap: u16,
+    /// Constructions of glyphs that grow vertically.
+    pub vertical: BTreeMap<u16, MathGlyphConstruction>,
+    /// Constructions of glyphs that grow horizontally.
+    pub horizontal: BTreeMap<u16, MathGlyphConstruction>,
+}
+
+/// The ways to draw a glyph at larger sizes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathGlyphConstruction {
+    /// Assembly used when no variant is large enough.
+    pub assembly: Option<GlyphAssembly>,
+    /// Ready-made variants in increasing size, usually starting with the
+    /// glyph itself.
+    pub variants: Vec<MathGlyphVariant>,
+}
+
+/// A ready-made size variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MathGlyphVariant {
+    /// Variant glyph id.
+    pub glyph_id: u16,
+    /// Size of the variant along the stretch direction.
+    pub advance_measurement: u16,
+}
+
+/// A glyph built from overlapping parts.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GlyphAssembly {
+    /// Italic correction of the assembled glyph.
+    pub italics_correction: MathValueRecord,
+    /// Parts from bottom to top, or left to right.
+    pub parts: Vec<GlyphPart>,
+}
+
+/// A part of a glyph assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GlyphPart {
+    /// Part glyph id.
+    pub glyph_id: u16,
+    /// Length of the connector at the start of the part.
+    pub start_connector_length: u16,
+    /// Length of the connector at the end of the part.
+    pub end_connector_length: u16,
+    /// Size of the part along the stretch direction.
+    pub full_advance: u16,
+    /// Whether the part can be repeated.
+    pub extender: bool,
+}
+
+/// A glyph placed along the stretch direction of a stretched glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StretchedGlyph {
+    /// Glyph id.
+    pub glyph_id: u16,
+    /// Distance of the glyph's origin from the start of the stretched
+    /// glyph, upwards or rightwards.
+    pub offset: i32,
+}
+
+/// A glyph stretched to a size: a single variant or an assembly of parts.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MathStretch {
+    /// Glyphs to draw, from the start of the stretch direction.
+    pub glyphs: Vec<StretchedGlyph>,
+    /// Size reached along the stretch direction.
+    pub size: i32,
+    /// Italic correction of the result, for assemblies.
+    pub italics_correction: i16,
+}
+
+impl MathTable {
+    /// Returns the italic correction of a glyph.
+    pub fn italics_correction(&self, glyph_id: u16) -> Option<i16> {
+        let info = self.glyph_info.as_ref()?;
+        info.italics_corrections.get(&glyph_id).map(|v| v.value)
+    }
+
+    /// Returns the horizontal accent attachment position of a glyph.
+    pub fn top_accent_attachment(&self, glyph_id: u16) -> Option<i16> {
+        let info = self.glyph_info.as_ref()?;
+        info.top_accent_attachments.get(&glyph_id).map(|v| v.value)
+    }
+
+    /// Returns whether a glyph is an extended shape.
+    pub fn is_extended_shape(&self, glyph_id: u16) -> bool {
+        self.glyph_info
+            .as_ref()
+            .is_some_and(|info| info.extended_shapes.contains(glyph_id))
+    }
+
+    /// Returns the glyphs drawing `glyph_id` stretched to at least `target`
+    /// font units, vertically or horizontally; see
+    /// [`MathVariants::stretch`].
+    pub fn stretch(&self, glyph_id: u16, target: i32, vertical: bool) -> Option<MathStretch> {
+        self.variants.as_ref()?.stretch(glyph_id, target, vertical)
+    }
+}
+
+impl MathVariants {
+    /// Returns the glyphs drawing `glyph_id` stretched to at least `target`
+    /// font units, vertically or horizontally.
+    ///
+    /// The smallest variant reaching the target is used. Otherwise the
+    /// glyph's assembly is built with as few extender repeats as possible and
+    /// its overlaps spread to hit the target exactly when they can. Without
+    /// an assembly the largest variant is used. Returns `None` for glyphs
+    /// without a construction in that direction.
+    pub fn stretch(&self, glyph_id: u16, target: i32, vertical: bool) -> Option<MathStretch> {
+        let constructions = if vertical {
+            &self.vertical
+        } else {
+            &self.horizontal
+        };
+        let construction = constructions.get(&glyph_id)?;
+        let variant = |variant: &MathGlyphVariant| MathStretch {
+            glyphs: vec![StretchedGlyph {
+                glyph_id: variant.glyph_id,
+                offset: 0,
+            }],
+            size: variant.advance_measurement as i32,
+            italics_correction: 0,
+        };
+        if let Some(fit) = construction
+            .variants
+            .iter()
+            .find(|v| v.advance_measurement as i32 >= target)
+        {
+            return Some(variant(fit));
+        }
+        if let Some(assembly) = &construction.assembly
+            && let Some(stretch) = assembly.assemble(target, self.min_connector_overlap)
+        {
+            return Some(stretch);
+        }
+        construction.variants.last().map(variant)
+    }
+}
+
+impl GlyphAssembly {
+    /// Builds the assembly at `target` font units, or the nearest size it
+    /// can reach. Returns `None` for assemblies without parts.
+    pub fn assemble(&self, target: i32, min_connector_overlap: u16) -> Option<MathStretch> {
+        if self.parts.is_empty() {
+            return None;
+        }
+        let min_overlap = min_connector_overlap as i64;
+        let has_extenders = self.parts.iter().any(|part| part.extender);
+        // Non-extenders appear once; extenders appear `repeats` times.
+        let expand = |repeats: usize| -> Vec<GlyphPart> {
+            self.parts
+                .iter()
+                .flat_map(|part| {
+                    let count = if part.extender { repeats } else { 1 };
+                    std::iter::repeat_n(*part, count)
+                })
+                .collect()
+        };
+        // Overlap limits of each joint: the shorter connector, but at least
+        // the font's minimum.
+        let limits = |parts: &[GlyphPart]| -> Vec<i64> {
+            parts
+                .windows(2)
+                .map(|pair| {
+                    let connector = pair[0]
+                        .end_connector_length
+                        .min(pair[1].start_connector_length);
+                    (connector as i64).max(min_overlap)
+                })
+                .collect()
+        };
+        let total = |parts: &[GlyphPart]| -> i64 {
+            parts.iter().map(|part| part.full_advance as i64).sum()
+        };
+
+        let mut repeats = if has_extenders { 1 } else { 0 };
+        let mut parts = expand(repeats);
+        while has_extenders
+            && repeats < MAX_EXTENDER_REPEATS
+            && (parts.is_empty()
+                || total(&parts) - min_overlap * (parts.len() as i64 - 1) < target as i64)
+        {
+            repeats += 1;
+            parts = expand(repeats);
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        let limits = limits(&parts);
+        let advance = total(&parts);
+        let largest = advance - min_overlap * limits.len() as i64;
+        let smallest = advance - limits.iter().sum::<i64>();
+        let size = (target as i64).clamp(smallest, largest);
+        // Spread the overlap beyond the minimum over the joints in proportion
+        // to how much each can take.
+        let mut extra = largest - size;
+        let mut capacity: i64 = limits.iter().map(|limit| limit - min_overlap).sum();
+        let mut glyphs = Vec::with_capacity(parts.len());
+        let mut offset = 0i64;
+        for (index, part) in parts.iter().enumerate() {
+            glyphs.push(StretchedGlyph {
+                glyph_id: part.glyph_id,
+                offset: offset as i32,
+            });
+            let Some(&limit) = limits.get(index) else {
+                break;
+            };
+            let room = limit - min_overlap;
+            let share = if capacity > 0 {
+                extra * room / capacity
+            } else {
+                0
+            };
+            extra -= share;
+            capacity -= room;
+            offset += part.full_advance as i64 - min_overlap - share;
+        }
+        Some(MathStretch {
+            glyphs,
+            size: size as i32,
+            italics_correction: self.italics_correction.value,
+        })
+    }
+}
+
+/// Extracts the `MATH` table from the provided data stream.
+pub fn read_math(data: &mut FontDataStream) -> Result<MathTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let constants_offset = data.read_u16()? as usize;
+    let glyph_info_offset = data.read_u16()? as usize;
+    let variants_offset = data.read_u16()? as usize;
+    Ok(MathTable {
+        major_version,
+        minor_version,
+        constants: read_optional_at(data, constants_offset, read_math_constants)?,
+        glyph_info: read_optional_at(data, glyph_info_offset, read_math_glyph_info)?,
+        variants: read_optional_at(data, variants_offset, read_math_variants)?,
+    })
+}
+
+/// Reads a value record; its device offset is measured from `base`.
+fn read_math_value(
+    data: &mut FontDataStream,
+    base: &FontDataStream,
+) -> Result<MathValueRecord, Error> {
+    let value = data.read_i16()?;
+    let offset = data.read_u16()? as usize;
+    Ok(MathValueRecord {
+        value,
+        device: read_optional_at(base, offset, read_device)?,
+    })
+}
+
+fn read_math_constants(data: &mut FontDataStream) -> Result<MathConstants, Error> {
+    let base = data.substream(0)?;
+    let script_percent_scale_down = data.read_i16()?;
+    let script_script_percent_scale_down = data.read_i16()?;
+    let delimited_sub_formula_min_height = data.read_u16()?;
+    let display_operator_min_height = data.read_u16()?;
+    let mut record = || read_math_value(data, &base);
+    let mut constants = MathConstants {
+        script_percent_scale_down,
+        script_script_percent_scale_down,
+        delimited_sub_formula_min_height,
+        display_operator_min_height,
+        math_leading: record()?,
+        axis_height: record()?,
+        accent_base_height: record()?,
+        flattened_accent_base_height: record()?,
+        subscript_shift_down: record()?,
+        subscript_top_max: record()?,
+        subscript_baseline_drop_min: record()?,
+        superscript_shift_up: record()?,
+        superscript_shift_up_cramped: record()?,
+        superscript_bottom_min: record()?,
+        superscript_baseline_drop_max: record()?,
+        sub_superscript_gap_min: record()?,
+        superscript_bottom_max_with_subscript: record()?,
+        space_after_script: record()?,
+        upper_limit_gap_min: record()?,
+        upper_limit_baseline_rise_min: record()?,
+        lower_limit_gap_min: record()?,
+        lower_limit_baseline_drop_min: record()?,
+        stack_top_shift_up: record()?,
+        stack_top_display_style_shift_up: record()?,
+        stack_bottom_shift_down: record()?,
+        stack_bottom_display_style_shift_down: record()?,
+        stack_gap_min: record()?,
+        stack_display_style_gap_min: record()?,
+        stretch_stack_top_shift_up: record()?,
+        stretch_stack_bottom_shift_down: record()?,
+        stretch_stack_gap_above_min: record()?,
+        stretch_stack_gap_below_min: record()?,
+        fraction_numerator_shift_up: record()?,
+        fraction_numerator_display_style_shift_up: record()?,
+        fraction_denominator_shift_down: record()?,
+        fraction_denominator_display_style_shift_down: record()?,
+        fraction_numerator_gap_min: record()?,
+        fraction_num_display_style_gap_min: record()?,
+        fraction_rule_thickness: record()?,
+        fraction_denominator_gap_min: record()?,
+        fraction_denom_display_style_gap_min: record()?,
+        skewed_fraction_horizontal_gap: record()?,
+        skewed_fraction_vertical_gap: record()?,
+        overbar_vertical_gap: record()?,
+        overbar_rule_thickness: record()?,
+        overbar_extra_ascender: record()?,
+        underbar_vertical_gap: record()?,
+        underbar_rule_thickness: record()?,
+        underbar_extra_descender: record()?,
+        radical_vertical_gap: record()?,
+        radical_display_style_vertical_gap: record()?,
+        radical_rule_thickness: record()?,
+        radical_extra_ascender: record()?,
+        radical_kern_before_degree: record()?,
+        radical_kern_after_degree: record()?,
+        radical_degree_bottom_raise_percent: 0,
+    };
+    constants.radical_degree_bottom_raise_percent = data.read_i16()?;
+    Ok(constants)
+}
+
+/// Reads a coverage offset and one value record per covered glyph.
+fn read_glyph_values(data: &mut FontDataStream) -> Result<BTreeMap<u16, MathValueRecord>, Error> {
+    let base = data.substream(0)?;
+    let coverage_offset = data.read_u16()? as usize;
+    let coverage = read_at(&base, coverage_offset, read_coverage)?;
+    let count = data.read_u16()?;
+    let mut values = BTreeMap::new();
+    for index in 0..count as usize {
+        let value = read_math_value(data, &base)?;
+        if let Some(&glyph) = coverage.glyphs.get(index) {
+            values.insert(glyph, value);
+        }
+    }
+    Ok(values)
+}
+
+fn read_math_glyph_info(data: &mut FontDataStream) -> Result<MathGlyphInfo, Error> {
+    let italics_offset = data.read_u16()? as usize;
+    let accents_offset = data.read_u16()? as usize;
+    let shapes_offset = data.read_u16()? as usize;
+    let kern_offset = data.read_u16()? as usize;
+
+    let kern_info = read_optional_at(data, kern_offset, |info| {
+        let base = info.substream(0)?;
+        let coverage_offset = info.read_u16()? as usize;
+        let coverage = read_at(&base, coverage_offset, read_coverage)?;
+        let count = info.read_u16()?;
+        let mut kerns = BTreeMap::new();
+        for index in 0..count as usize {
+            let mut corner = || -> Result<Option<MathKern>, Error> {
+                let offset = info.read_u16()? as usize;
+                read_optional_at(&base, offset, read_math_kern)
+            };
+            let record = MathKernInfo {
+                top_right: corner()?,
+                top_left: corner()?,
+                bottom_right: corner()?,
+                bottom_left: corner()?,
+            };
+            if let Some(&glyph) = coverage.glyphs.get(index) {
+                kerns.insert(glyph, record);
+            }
+        }
+        Ok(kerns)
+    })?;
+
+    Ok(MathGlyphInfo {
+        italics_corrections: read_optional_at(data, italics_offset, read_glyph_values)?
+            .unwrap_or_default(),
+        top_accent_attachments: read_optional_at(data, accents_offset, read_glyph_values)?
+            .unwrap_or_default(),
+        extended_shapes: read_optional_at(data, shapes_offset, read_coverage)?.unwrap_or_default(),
+        kern_info: kern_info.unwrap_or_default(),
+    })
+}
+
+fn read_math_kern(data: &mut FontDataStream) -> Result<MathKern, Error> {
+    let base = data.substream(0)?;
+    let count = data.read_u16()? as usize;
+    let correction_heights = (0..count)
+        .map(|_| read_math_value(data, &base))
+        .collect::<Result<Vec<_>, _>>()?;
+    let kern_values = (0..=count)
+        .map(|_| read_math_value(data, &base))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(MathKern {
+        correction_heights,
+        kern_values,
+    })
+}
+
+fn read_math_variants(data: &mut FontDataStream) -> Result<MathVariants, Error> {
+    let base = data.substream(0)?;
+    let min_connector_overlap = data.read_u16()?;
+    let vertical_coverage = data.read_u16()? as usize;
+    let horizontal_coverage = data.read_u16()? as usize;
+    let vertical_count = data.read_u16()?;
+    let horizontal_count = data.read_u16()?;
+    let mut constructions = |coverage_offset: usize, count: u16| {
+        let coverage = read_optional_at(&base, coverage_offset, read_coverage)?.unwrap_or_default();
+        let mut constructions = BTreeMap::new();
+        for index in 0..count as usize {
+            let offset = data.read_u16()? as usize;
+            let construction = read_at(&base, offset, read_glyph_construction)?;
+            if let Some(&glyph) = coverage.glyphs.get(index) {
+                constructions.insert(glyph, construction);
+            }
+        }
+        Ok::<_, Error>(constructions)
+    };
+    let vertical = constructions(vertical_coverage, vertical_count)?;
+    let horizontal = constructions(horizontal_coverage, horizontal_count)?;
+    Ok(MathVariants {
+        min_connector_overlap,
+        vertical,
+        horizontal,
+    })
+}
+
+fn read_glyph_construction(data: &mut FontDataStream) -> Result<MathGlyphConstruction, Error> {
+    let assembly_offset = data.read_u16()? as usize;
+    let count = data.read_u16()?;
+    let variants = (0..count)
+        .map(|_| {
+            Ok(MathGlyphVariant {
+                glyph_id: data.read_u16()?,
+                advance_measurement: data.read_u16()?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(MathGlyphConstruction {
+        assembly: read_optional_at(data, assembly_offset, read_glyph_assembly)?,
+        variants,
+    })
+}
+
+fn read_glyph_assembly(data: &mut FontDataStream) -> Result<GlyphAssembly, Error> {
+    let base = data.substream(0)?;
+    let italics_correction = read_math_value(data, &base)?;
+    let count = data.read_u16()?;
+    let parts = (0..count)
+        .map(|_| {
+            let glyph_id = data.read_u16()?;
+            let start_connector_length = data.read_u16()?;
+            let end_connector_length = data.read_u16()?;
+            let full_advance = data.read_u16()?;
+            let flags = data.read_u16()?;
+            Ok(GlyphPart {
+                glyph_id,
+                start_connector_length,
+                end_connector_length,
+                full_advance,
+                extender: flags & EXTENDER_FLAG != 0,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(GlyphAssembly {
+        italics_correction,
+        parts,
+    })
+}
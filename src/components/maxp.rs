@@ -0,0 +1,111 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `maxp` (Maximum Profile) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Maximum profile table representation.
+///
+/// Version 0.5 (CFF outlines) only carries `num_glyphs`; version 1.0
+/// (TrueType outlines) adds the limits in `truetype`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MaxpTable {
+    /// Table version as a 16.16 number (0x00005000 or 0x00010000).
+    pub version: u32,
+    /// Number of glyphs in the font.
+    pub num_glyphs: u16,
+    /// TrueType limits, present in version 1.0.
+    pub truetype: Option<MaxpTrueType>,
+}
+
+/// Version 1.0 fields of the `maxp` table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MaxpTrueType {
+    /// Maximum points in a simple glyph.
+    pub max_points: u16,
+    /// Maximum contours in a simple glyph.
+    pub max_contours: u16,
+    /// Maximum points in a composite glyph.
+    pub max_composite_points: u16,
+    /// Maximum contours in a composite glyph.
+    pub max_composite_contours: u16,
+    /// 1 if instructions do not use the twilight zone, 2 otherwise.
+    pub max_zones: u16,
+    /// Maximum points used in the twilight zone.
+    pub max_twilight_points: u16,
+    /// Number of storage area locations.
+    pub max_storage: u16,
+    /// Number of function definitions.
+    pub max_function_defs: u16,
+    /// Number of instruction definitions.
+    pub max_instruction_defs: u16,
+    /// Maximum stack depth.
+    pub max_stack_elements: u16,
+    /// Maximum byte count for glyph instructions.
+    pub max_size_of_instructions: u16,
+    /// Maximum number of components referenced at the top level of a composite.
+    pub max_component_elements: u16,
+    /// Maximum levels of recursion in composites.
+    pub max_component_depth: u16,
+}
+
+/// Extracts the `maxp` table from the provided data stream.
+pub fn read_maxp(data: &mut FontDataStream) -> Result<MaxpTable, Error> {
+    let version = data.read_u32()?;
+    let num_glyphs = data.read_u16()?;
+    let truetype = match version {
+        0x0000_5000 => None,
+        0x0001_0000 => Some(MaxpTrueType {
+            max_points: data.read_u16()?,
+            max_contours: data.read_u16()?,
+            max_composite_points: data.read_u16()?,
+            max_composite_contours: data.read_u16()?,
+            max_zones: data.read_u16()?,
+            max_twilight_points: data.read_u16()?,
+            max_storage: data.read_u16()?,
+            max_function_defs: data.read_u16()?,
+            max_instruction_defs: data.read_u16()?,
+            max_stack_elements: data.read_u16()?,
+            max_size_of_instructions: data.read_u16()?,
+            max_component_elements: data.read_u16()?,
+            max_component_depth: data.read_u16()?,
+        }),
+        v => return Err(Error::Io(IoError::UnsupportedVersion(v))),
+    };
+    Ok(MaxpTable {
+        version,
+        num_glyphs,
+        truetype,
+    })
+}
+
+/// Serializes the `maxp` table.
+pub fn write_maxp(writer: &mut FontDataWriter, maxp: &MaxpTable) {
+    writer.write_u32(maxp.version);
+    writer.write_u16(maxp.num_glyphs);
+    if let Some(tt) = &maxp.truetype {
+        for value in [
+            tt.max_points,
+            tt.max_contours,
+            tt.max_composite_points,
+            tt.max_composite_contours,
+            tt.max_zones,
+            tt.max_twilight_points,
+            tt.max_storage,
+            tt.max_function_defs,
+            tt.max_instruction_defs,
+            tt.max_stack_elements,
+            tt.max_size_of_instructions,
+            tt.max_component_elements,
+            tt.max_component_depth,
+        ] {
+            writer.write_u16(value);
+        }
+    }
+}
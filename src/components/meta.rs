@@ -0,0 +1,115 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `meta` (Metadata) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Metadata tag: languages the font was designed for.
+pub const META_DESIGN_LANGUAGES: [u8; 4] = *b"dlng";
+/// Metadata tag: languages the font supports.
+pub const META_SUPPORTED_LANGUAGES: [u8; 4] = *b"slng";
+
+/// Metadata table: tagged data maps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MetaTable {
+    /// Table version (1).
+    pub version: u32,
+    /// Table flags (0).
+    pub flags: u32,
+    /// Data maps in file order.
+    pub data_maps: Vec<MetaDataMap>,
+}
+
+/// A metadata entry with its tag.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaDataMap {
+    /// Metadata tag (`dlng`, `slng`, `appl`, `bild`, ...).
+    pub tag: [u8; 4],
+    /// Metadata value.
+    pub data: MetaData,
+}
+
+/// Value of a metadata entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MetaData {
+    /// `dlng` and `slng`: ScriptLangTags such as `Latn` or `zh-Hant`, in
+    /// file order.
+    Languages(Vec<String>),
+    /// Any other tag, such as the vendor-defined `appl` and `bild`: the raw
+    /// bytes.
+    Opaque(Vec<u8>),
+}
+
+impl MetaTable {
+    /// Returns the value of the first data map with `tag`.
+    pub fn get(&self, tag: [u8; 4]) -> Option<&MetaData> {
+        self.data_maps
+            .iter()
+            .find(|map| map.tag == tag)
+            .map(|map| &map.data)
+    }
+
+    /// Returns the ScriptLangTags of the `dlng` entry, or an empty slice.
+    pub fn design_languages(&self) -> &[String] {
+        self.languages(META_DESIGN_LANGUAGES)
+    }
+
+    /// Returns the ScriptLangTags of the `slng` entry, or an empty slice.
+    pub fn supported_languages(&self) -> &[String] {
+        self.languages(META_SUPPORTED_LANGUAGES)
+    }
+
+    fn languages(&self, tag: [u8; 4]) -> &[String] {
+        match self.get(tag) {
+            Some(MetaData::Languages(languages)) => languages,
+            _ => &[],
+        }
+    }
+}
+
+/// Extracts the `meta` table from the provided data stream.
+pub fn read_meta(data: &mut FontDataStream) -> Result<MetaTable, Error> {
+    let version = data.read_u32()?;
+    if version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version)));
+    }
+    let flags = data.read_u32()?;
+    data.skip(4);
+    let count = data.read_u32()?;
+    let data_maps = (0..count)
+        .map(|_| {
+            let tag = data.read_tag()?;
+            let offset = data.read_u32()? as usize;
+            let length = data.read_u32()? as usize;
+            let bytes = data.slice_at(offset, length)?;
+            let data = if tag == META_DESIGN_LANGUAGES || tag == META_SUPPORTED_LANGUAGES {
+                MetaData::Languages(parse_languages(bytes)?)
+            } else {
+                MetaData::Opaque(bytes.to_vec())
+            };
+            Ok(MetaDataMap { tag, data })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(MetaTable {
+        version,
+        flags,
+        data_maps,
+    })
+}
+
+/// Splits a comma-separated ScriptLangTag list, dropping surrounding spaces
+/// and empty items.
+fn parse_languages(bytes: &[u8]) -> Result<Vec<String>, Error> {
+    let text = std::str::from_utf8(bytes).map_err(|_| Error::Io(IoError::InvalidData))?;
+    Ok(text
+        .split(',')
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+        .map(str::to_string)
+        .collect())
+}
@@ -0,0 +1,63 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Font table (component) representations. Each module parses one SNFT table
+//! from a `FontDataStream` positioned at the start of the table data.
+
+pub mod aat;
+pub mod ankr;
+pub mod avar;
+pub mod base;
+pub mod cbdt;
+pub mod cblc;
+pub mod cff1;
+pub mod cff2;
+pub mod cmap;
+pub mod colr;
+pub mod cpal;
+pub mod cvar;
+pub mod cvt;
+pub mod device;
+pub mod dsig;
+pub mod ebdt;
+pub mod eblc;
+pub mod ebsc;
+pub mod feat;
+pub mod fpgm;
+pub mod fvar;
+pub mod gasp;
+pub mod gdef;
+pub mod glyf;
+pub mod gpos;
+pub mod gsub;
+pub mod gvar;
+pub mod hdmx;
+pub mod head;
+pub mod hhea;
+pub mod hmtx;
+pub mod hvar;
+pub mod jstf;
+pub mod kern;
+pub mod kerx;
+pub mod layout;
+pub mod loca;
+pub mod ltsh;
+pub mod math;
+pub mod maxp;
+pub mod meta;
+pub mod morx;
+pub mod mvar;
+pub mod name;
+pub mod pclt;
+pub mod post;
+pub mod prep;
+pub mod sbix;
+pub mod stat;
+pub mod svg;
+pub mod trak;
+pub mod variation;
+pub mod vdmx;
+pub mod vvar;
@@ -0,0 +1,629 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `morx` (Extended Glyph Metamorphosis) table representation and utilities for the Aurora Font Library.
+
+use crate::components::aat::{
+    AatLookup, DELETED_GLYPH, ENTRY_DONT_ADVANCE, MAX_LEN_FACTOR, MAX_LEN_MIN, StateTable,
+    read_lookup, read_state_table,
+};
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Rearrangement entry flag: the current glyph starts the marked range.
+pub const REARRANGEMENT_MARK_FIRST: u16 = 0x8000;
+/// Rearrangement entry flag: the current glyph ends the marked range.
+pub const REARRANGEMENT_MARK_LAST: u16 = 0x2000;
+/// Rearrangement entry mask: how the marked range is rearranged.
+pub const REARRANGEMENT_VERB: u16 = 0x000F;
+/// Contextual and insertion entry flag: mark the current glyph.
+pub const ENTRY_SET_MARK: u16 = 0x8000;
+/// Ligature entry flag: push the current glyph on the component stack.
+pub const LIGATURE_SET_COMPONENT: u16 = 0x8000;
+/// Ligature entry flag: run the entry's ligature actions.
+pub const LIGATURE_PERFORM_ACTION: u16 = 0x2000;
+/// Ligature action flag: the last action of the entry.
+pub const LIGATURE_ACTION_LAST: u32 = 0x8000_0000;
+/// Ligature action flag: store the ligature in place of the popped glyph.
+pub const LIGATURE_ACTION_STORE: u32 = 0x4000_0000;
+/// Ligature action mask: signed offset added to the popped glyph id to index
+/// the components.
+pub const LIGATURE_ACTION_OFFSET: u32 = 0x3FFF_FFFF;
+/// Insertion entry flag: insert before the current glyph instead of after.
+pub const INSERTION_CURRENT_BEFORE: u16 = 0x0800;
+/// Insertion entry flag: insert before the marked glyph instead of after.
+pub const INSERTION_MARKED_BEFORE: u16 = 0x0400;
+/// Insertion entry mask: number of glyphs inserted at the current glyph.
+pub const INSERTION_CURRENT_COUNT: u16 = 0x03E0;
+/// Insertion entry mask: number of glyphs inserted at the marked glyph.
+pub const INSERTION_MARKED_COUNT: u16 = 0x001F;
+
+/// Deepest the ligature component stack can grow.
+const LIGATURE_STACK_DEPTH: usize = 64;
+/// Index meaning no substitution or insertion.
+const NO_INDEX: u16 = 0xFFFF;
+
+/// Extended glyph metamorphosis table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MorxTable {
+    /// Table version (2 or 3).
+    pub version: u16,
+    /// Chains, applied in order.
+    pub chains: Vec<MorxChain>,
+}
+
+/// A chain of subtables sharing one set of feature flags.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MorxChain {
+    /// Subtable flags enabled when no feature is requested.
+    pub default_flags: u32,
+    /// Feature settings and the flags they change.
+    pub features: Vec<MorxFeature>,
+    /// Subtables, applied in order.
+    pub subtables: Vec<MorxSubtable>,
+}
+
+/// A feature setting and the subtable flags it turns on and off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MorxFeature {
+    /// Feature type, as in `feat`.
+    pub feature_type: u16,
+    /// Feature setting, as in `feat`.
+    pub feature_setting: u16,
+    /// Flags turned on by the setting.
+    pub enable_flags: u32,
+    /// Flags kept by the setting; the others are turned off.
+    pub disable_flags: u32,
+}
+
+/// One `morx` subtable and its coverage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MorxSubtable {
+    /// Whether the subtable applies only to vertical text.
+    pub vertical: bool,
+    /// Whether the glyphs are processed from last to first.
+    pub descending: bool,
+    /// Whether the subtable applies to both horizontal and vertical text.
+    pub both_orientations: bool,
+    /// Whether `descending` refers to logical rather than layout order.
+    pub logical_order: bool,
+    /// Chain flags any of which enables the subtable.
+    pub sub_feature_flags: u32,
+    /// Subtable data.
+    pub data: MorxData,
+}
+
+/// The data of a `morx` subtable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MorxData {
+    /// Type 0: reorders a marked range of glyphs.
+    Rearrangement(StateTable<()>),
+    /// Type 1: substitutes the current and marked glyphs.
+    Contextual(ContextualSubtable),
+    /// Type 2: joins the glyphs on a component stack into ligatures.
+    Ligature(LigatureSubtable),
+    /// Type 4: substitutes every glyph independently.
+    Noncontextual(AatLookup),
+    /// Type 5: inserts glyphs at the current and marked glyphs.
+    Insertion(InsertionSubtable),
+}
+
+impl MorxData {
+    /// Returns the subtable type the data is written as.
+    pub fn subtable_type(&self) -> u8 {
+        match self {
+            MorxData::Rearrangement(_) => 0,
+            MorxData::Contextual(_) => 1,
+            MorxData::Ligature(_) => 2,
+            MorxData::Noncontextual(_) => 4,
+            MorxData::Insertion(_) => 5,
+        }
+    }
+}
+
+/// Substitution indices of a contextual entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ContextualEntry {
+    /// Substitution applied to the marked glyph, or `0xFFFF` for none.
+    pub mark_index: u16,
+    /// Substitution applied to the current glyph, or `0xFFFF` for none.
+    pub current_index: u16,
+}
+
+/// Type 1 contextual substitution.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContextualSubtable {
+    /// State machine; entries name substitutions.
+    pub machine: StateTable<ContextualEntry>,
+    /// Substitutions indexed by the entries.
+    pub substitutions: Vec<AatLookup>,
+}
+
+/// Type 2 ligature substitution.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LigatureSubtable {
+    /// State machine; entries give the index of their first action.
+    pub machine: StateTable<u16>,
+    /// Ligature actions: flags and a component offset.
+    pub actions: Vec<u32>,
+    /// Amounts accumulated into the ligature index, indexed by glyph id plus
+    /// action offset.
+    pub components: Vec<u16>,
+    /// Ligature glyphs indexed by the accumulated component amounts.
+    pub ligatures: Vec<u16>,
+}
+
+/// Insertion indices of an insertion entry; counts are in the entry flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct InsertionEntry {
+    /// First glyph inserted at the current glyph, or `0xFFFF` for none.
+    pub current_insert_index: u16,
+    /// First glyph inserted at the marked glyph, or `0xFFFF` for none.
+    pub marked_insert_index: u16,
+}
+
+/// Type 5 glyph insertion.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InsertionSubtable {
+    /// State machine; entries name insertions.
+    pub machine: StateTable<InsertionEntry>,
+    /// Glyphs inserted by the entries.
+    pub glyphs: Vec<u16>,
+}
+
+impl MorxChain {
+    /// Returns the chain flags for a set of `(feature_type, feature_setting)`
+    /// pairs; settings the chain does not know are ignored.
+    pub fn flags(&self, features: &[(u16, u16)]) -> u32 {
+        self.features
+            .iter()
+            .filter(|feature| features.contains(&(feature.feature_type, feature.feature_setting)))
+            .fold(self.default_flags, |flags, feature| {
+                (flags & feature.disable_flags) | feature.enable_flags
+            })
+    }
+}
+
+impl MorxTable {
+    /// Applies the table to a run of horizontal, left-to-right glyphs.
+    ///
+    /// `features` lists the requested `(feature_type, feature_setting)`
+    /// pairs on top of each chain's defaults. Glyphs deleted by ligatures are
+    /// removed once every chain has run.
+    pub fn apply(&self, glyphs: &mut Vec<u16>, features: &[(u16, u16)]) {
+        for chain in &self.chains {
+            let flags = chain.flags(features);
+            for subtable in &chain.subtables {
+                if subtable.sub_feature_flags & flags == 0
+                    || (subtable.vertical && !subtable.both_orientations)
+                {
+                    continue;
+                }
+                if subtable.descending {
+                    glyphs.reverse();
+                }
+                subtable.data.apply(glyphs);
+                if subtable.descending {
+                    glyphs.reverse();
+                }
+            }
+        }
+        glyphs.retain(|&glyph| glyph != DELETED_GLYPH);
+    }
+}
+
+impl MorxData {
+    /// Applies the subtable to a glyph run, leaving deleted glyphs as
+    /// `DELETED_GLYPH`.
+    pub fn apply(&self, glyphs: &mut Vec<u16>) {
+        match self {
+            MorxData::Rearrangement(machine) => apply_rearrangement(machine, glyphs),
+            MorxData::Contextual(subtable) => subtable.apply(glyphs),
+            MorxData::Ligature(subtable) => subtable.apply(glyphs),
+            MorxData::Noncontextual(lookup) => {
+                for glyph in glyphs.iter_mut().filter(|glyph| **glyph != DELETED_GLYPH) {
+                    if let Some(substitute) = lookup.get(*glyph) {
+                        *glyph = substitute;
+                    }
+                }
+            }
+            MorxData::Insertion(subtable) => subtable.apply(glyphs),
+        }
+    }
+}
+
+fn apply_rearrangement(machine: &StateTable<()>, glyphs: &mut Vec<u16>) {
+    let mut start = 0;
+    let mut end = 0;
+    machine.drive(glyphs, |entry, glyphs, index| {
+        if entry.flags & REARRANGEMENT_MARK_FIRST != 0 {
+            start = index;
+        }
+        if entry.flags & REARRANGEMENT_MARK_LAST != 0 {
+            end = (index + 1).min(glyphs.len());
+        }
+        let verb = entry.flags & REARRANGEMENT_VERB;
+        if verb != 0 && start < end {
+            rearrange(&mut glyphs[start..end], verb);
+        }
+        index
+    });
+}
+
+/// Rearranges a marked range. Each verb moves up to two glyphs from the
+/// start (A, B) and the end (C, D) of the range to the other side, possibly
+/// swapping them; x is everything in between.
+fn rearrange(range: &mut [u16], verb: u16) {
+    // Glyphs moved from the start and the end; 3 means two, swapped.
+    const MOVES: [(usize, usize); 16] = [
+        (0, 0), // no change
+        (1, 0), // Ax => xA
+        (0, 1), // xD => Dx
+        (1, 1), // AxD => DxA
+        (2, 0), // ABx => xAB
+        (3, 0), // ABx => xBA
+        (0, 2), // xCD => CDx
+        (0, 3), // xCD => DCx
+        (1, 2), // AxCD => CDxA
+        (1, 3), // AxCD => DCxA
+        (2, 1), // ABxD => DxAB
+        (3, 1), // ABxD => DxBA
+        (2, 2), // ABxCD => CDxAB
+        (3, 2), // ABxCD => CDxBA
+        (2, 3), // ABxCD => DCxAB
+        (3, 3), // ABxCD => DCxBA
+    ];
+    let (left, right) = MOVES[verb as usize & 0xF];
+    let (left_count, right_count) = (left.min(2), right.min(2));
+    if range.len() < left_count + right_count {
+        return;
+    }
+    let mut start = range[..left_count].to_vec();
+    let mut end = range[range.len() - right_count..].to_vec();
+    if left == 3 {
+        start.reverse();
+    }
+    if right == 3 {
+        end.reverse();
+    }
+    let middle = range[left_count..range.len() - right_count].to_vec();
+    let rearranged: Vec<u16> = end.into_iter().chain(middle).chain(start).collect();
+    range.copy_from_slice(&rearranged);
+}
+
+impl ContextualSubtable {
+    /// Applies the substitutions to a glyph run.
+    ///
+    /// As in CoreText, the mark starts at the first glyph, and nothing is
+    /// substituted at the end of the run unless a mark was set.
+    pub fn apply(&self, glyphs: &mut Vec<u16>) {
+        let substitute = |glyphs: &mut Vec<u16>, position: usize, index: u16| {
+            let Some(lookup) = self.substitutions.get(index as usize) else {
+                return;
+            };
+            if let Some(substitute) = glyphs.get(position).and_then(|&g| lookup.get(g)) {
+                glyphs[position] = substitute;
+            }
+        };
+        let mut mark = 0;
+        let mut mark_set = false;
+        self.machine.drive(glyphs, |entry, glyphs, index| {
+            if glyphs.is_empty() || (index >= glyphs.len() && !mark_set) {
+                return index;
+            }
+            if entry.data.mark_index != NO_INDEX {
+                substitute(glyphs, mark, entry.data.mark_index);
+            }
+            // At the end of the run the last glyph is current.
+            let current = index.min(glyphs.len() - 1);
+            if entry.data.current_index != NO_INDEX {
+                substitute(glyphs, current, entry.data.current_index);
+            }
+            if entry.flags & ENTRY_SET_MARK != 0 {
+                mark = current;
+                mark_set = true;
+            }
+            index
+        });
+    }
+}
+
+impl LigatureSubtable {
+    /// Forms the ligatures of a glyph run. Components merged into a ligature
+    /// are replaced by `DELETED_GLYPH`.
+    pub fn apply(&self, glyphs: &mut Vec<u16>) {
+        let mut stack: Vec<usize> = Vec::new();
+        self.machine.drive(glyphs, |entry, glyphs, index| {
+            if entry.flags & LIGATURE_SET_COMPONENT != 0 && index < glyphs.len() {
+                // A glyph processed again is only pushed once.
+                if stack.last() == Some(&index) {
+                    stack.pop();
+                }
+                if stack.len() == LIGATURE_STACK_DEPTH {
+                    stack.remove(0);
+                }
+                stack.push(index);
+            }
+            if entry.flags & LIGATURE_PERFORM_ACTION != 0 {
+                self.perform_actions(entry.data, glyphs, &mut stack);
+            }
+            index
+        });
+    }
+
+    fn perform_actions(&self, first_action: u16, glyphs: &mut [u16], stack: &mut Vec<usize>) {
+        let mut cursor = stack.len();
+        let mut ligature_index: u32 = 0;
+        for action in self.actions.iter().skip(first_action as usize) {
+            if cursor == 0 {
+                stack.clear();
+                break;
+            }
+            cursor -= 1;
+            let position = stack[cursor];
+            // Sign-extend the 30-bit offset.
+            let offset = (((action & LIGATURE_ACTION_OFFSET) << 2) as i32) >> 2;
+            let component = glyphs[position] as i64 + offset as i64;
+            let Some(&amount) = usize::try_from(component)
+                .ok()
+                .and_then(|index| self.components.get(index))
+            else {
+                break;
+            };
+            ligature_index = ligature_index.wrapping_add(amount as u32);
+            if action & (LIGATURE_ACTION_STORE | LIGATURE_ACTION_LAST) != 0 {
+                let Some(&ligature) = self.ligatures.get(ligature_index as usize) else {
+                    break;
+                };
+                glyphs[position] = ligature;
+                // The components above the ligature are merged into it.
+                for &component in &stack[cursor + 1..] {
+                    glyphs[component] = DELETED_GLYPH;
+                }
+                stack.truncate(cursor + 1);
+            }
+            if action & LIGATURE_ACTION_LAST != 0 {
+                break;
+            }
+        }
+    }
+}
+
+impl InsertionSubtable {
+    /// Inserts glyphs into a glyph run.
+    ///
+    /// Follows the placement of HarfBuzz and CoreText: glyphs inserted at the
+    /// mark are stepped over, the mark is set between the two insertions, and
+    /// glyphs inserted at the current glyph are processed next only when the
+    /// entry does not advance. The run never grows beyond a multiple of its
+    /// initial length.
+    pub fn apply(&self, glyphs: &mut Vec<u16>) {
+        let max_len = (glyphs.len() * MAX_LEN_FACTOR).max(MAX_LEN_MIN);
+        let mut mark: Option<usize> = None;
+        self.machine.drive(glyphs, |entry, glyphs, mut index| {
+            let flags = entry.flags;
+            if entry.data.marked_insert_index != NO_INDEX
+                && let Some(marked) = mark
+            {
+                let count = (flags & INSERTION_MARKED_COUNT) as usize;
+                let position = if flags & INSERTION_MARKED_BEFORE == 0 && marked < glyphs.len() {
+                    marked + 1
+                } else {
+                    marked
+                };
+                let first = entry.data.marked_insert_index;
+                index += self.insert(glyphs, position, first, count, max_len);
+            }
+            if flags & ENTRY_SET_MARK != 0 {
+                mark = Some(index);
+            }
+            if entry.data.current_insert_index != NO_INDEX {
+                let count = ((flags & INSERTION_CURRENT_COUNT) >> 5) as usize;
+                let position = if flags & INSERTION_CURRENT_BEFORE == 0 && index < glyphs.len() {
+                    index + 1
+                } else {
+                    index
+                };
+                let first = entry.data.current_insert_index;
+                let inserted = self.insert(glyphs, position, first, count, max_len);
+                // Without `DontAdvance` the inserted glyphs are stepped over;
+                // with it, processing resumes at the current position, which
+                // holds the first inserted glyph when inserting before.
+                if flags & ENTRY_DONT_ADVANCE == 0 {
+                    index += inserted;
+                }
+            }
+            index
+        });
+    }
+
+    /// Inserts `count` glyphs starting at `first` at `position`, unless the
+    /// run would grow beyond `max_len`. Returns the number inserted.
+    fn insert(
+        &self,
+        glyphs: &mut Vec<u16>,
+        position: usize,
+        first: u16,
+        count: usize,
+        max_len: usize,
+    ) -> usize {
+        let Some(inserted) = self.glyphs.get(first as usize..first as usize + count) else {
+            return 0;
+        };
+        if glyphs.len() + inserted.len() > max_len {
+            return 0;
+        }
+        let position = position.min(glyphs.len());
+        glyphs.splice(position..position, inserted.iter().copied());
+        inserted.len()
+    }
+}
+
+/// Extracts the `morx` table from the provided data stream. `num_glyphs` is
+/// the glyph count from `maxp`.
+pub fn read_morx(data: &mut FontDataStream, num_glyphs: u16) -> Result<MorxTable, Error> {
+    let version = data.read_u16()?;
+    if !(2..=3).contains(&version) {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let _unused = data.read_u16()?;
+    let chain_count = data.read_u32()?;
+    let mut chains = Vec::new();
+    for _ in 0..chain_count {
+        let start = data.position();
+        let default_flags = data.read_u32()?;
+        let length = data.read_u32()? as usize;
+        let feature_count = data.read_u32()?;
+        let subtable_count = data.read_u32()?;
+        let mut features = Vec::new();
+        for _ in 0..feature_count {
+            features.push(MorxFeature {
+                feature_type: data.read_u16()?,
+                feature_setting: data.read_u16()?,
+                enable_flags: data.read_u32()?,
+                disable_flags: data.read_u32()?,
+            });
+        }
+        let mut subtables = Vec::new();
+        for _ in 0..subtable_count {
+            let subtable_start = data.position();
+            let subtable_length = data.read_u32()? as usize;
+            let coverage = data.read_u32()?;
+            let sub_feature_flags = data.read_u32()?;
+            if subtable_length < 12 {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            let body = data.substream(subtable_start + 12)?;
+            let body_length = subtable_length - 12;
+            subtables.push(MorxSubtable {
+                vertical: coverage & 0x8000_0000 != 0,
+                descending: coverage & 0x4000_0000 != 0,
+                both_orientations: coverage & 0x2000_0000 != 0,
+                logical_order: coverage & 0x1000_0000 != 0,
+                sub_feature_flags,
+                data: read_morx_data(body, body_length, (coverage & 0xFF) as u8, num_glyphs)?,
+            });
+            data.seek(subtable_start + subtable_length);
+        }
+        chains.push(MorxChain {
+            default_flags,
+            features,
+            subtables,
+        });
+        data.seek(start + length);
+    }
+    Ok(MorxTable { version, chains })
+}
+
+fn read_morx_data(
+    data: FontDataStream,
+    length: usize,
+    subtable_type: u8,
+    num_glyphs: u16,
+) -> Result<MorxData, Error> {
+    let index = |data: &mut FontDataStream| data.read_u16();
+    Ok(match subtable_type {
+        0 => MorxData::Rearrangement(read_state_table(&data, num_glyphs, |_| Ok(()))?),
+        1 => {
+            let machine = read_state_table(&data, num_glyphs, |data| {
+                Ok(ContextualEntry {
+                    mark_index: index(data)?,
+                    current_index: index(data)?,
+                })
+            })?;
+            let table = data.read_at_u32(16)? as usize;
+            let count = machine
+                .entries
+                .iter()
+                .flat_map(|entry| [entry.data.mark_index, entry.data.current_index])
+                .filter(|&index| index != NO_INDEX)
+                .max()
+                .map_or(0, |max| max as usize + 1);
+            let table_stream = data.substream(table)?;
+            let substitutions = (0..count)
+                .map(|index| {
+                    let offset = table_stream.read_at_u32(index * 4)? as usize;
+                    read_lookup(&mut table_stream.substream(offset)?, num_glyphs)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            MorxData::Contextual(ContextualSubtable {
+                machine,
+                substitutions,
+            })
+        }
+        2 => {
+            let machine = read_state_table(&data, num_glyphs, index)?;
+            let offsets = [
+                data.read_at_u32(16)? as usize,
+                data.read_at_u32(20)? as usize,
+                data.read_at_u32(24)? as usize,
+            ];
+            // The arrays' lengths are not recorded; each runs to the next
+            // array or to the end of the subtable.
+            let extent = |offset: usize| {
+                offsets
+                    .iter()
+                    .copied()
+                    .chain([length])
+                    .filter(|&other| other > offset)
+                    .min()
+                    .unwrap_or(offset)
+                    - offset
+            };
+            let mut actions = data.substream(offsets[0])?;
+            let mut components = data.substream(offsets[1])?;
+            let mut ligatures = data.substream(offsets[2])?;
+            MorxData::Ligature(LigatureSubtable {
+                machine,
+                actions: (0..extent(offsets[0]) / 4)
+                    .map(|_| actions.read_u32())
+                    .collect::<Result<_, _>>()?,
+                components: (0..extent(offsets[1]) / 2)
+                    .map(|_| components.read_u16())
+                    .collect::<Result<_, _>>()?,
+                ligatures: (0..extent(offsets[2]) / 2)
+                    .map(|_| ligatures.read_u16())
+                    .collect::<Result<_, _>>()?,
+            })
+        }
+        4 => MorxData::Noncontextual(read_lookup(&mut data.substream(0)?, num_glyphs)?),
+        5 => {
+            let machine = read_state_table(&data, num_glyphs, |data| {
+                Ok(InsertionEntry {
+                    current_insert_index: index(data)?,
+                    marked_insert_index: index(data)?,
+                })
+            })?;
+            let count = machine
+                .entries
+                .iter()
+                .flat_map(|entry| {
+                    [
+                        (
+                            entry.data.current_insert_index,
+                            (entry.flags & INSERTION_CURRENT_COUNT) >> 5,
+                        ),
+                        (
+                            entry.data.marked_insert_index,
+                            entry.flags & INSERTION_MARKED_COUNT,
+                        ),
+                    ]
+                })
+                .filter(|&(index, _)| index != NO_INDEX)
+                .map(|(index, count)| index as usize + count as usize)
+                .max()
+                .unwrap_or(0);
+            let mut glyphs = data.substream(data.read_at_u32(16)? as usize)?;
+            MorxData::Insertion(InsertionSubtable {
+                machine,
+                glyphs: (0..count)
+                    .map(|_| glyphs.read_u16())
+                    .collect::<Result<_, _>>()?,
+            })
+        }
+        _ => return Err(Error::InvalidFormat),
+    })
+}
@@ -0,0 +1,114 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `MVAR` (Metrics Variations) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::layout::read_optional_at;
+use super::variation::{ItemVariationStore, read_item_variation_store, write_item_variation_store};
+
+/// A metric with variation data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetricValueRecord {
+    /// Tag of the metric, e.g. `hasc` for `OS/2.sTypoAscender`.
+    pub tag: [u8; 4],
+    /// Outer index of the delta-set item.
+    pub outer: u16,
+    /// Inner index of the delta-set item.
+    pub inner: u16,
+}
+
+/// Metrics variations table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MvarTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Value records sorted by tag.
+    pub records: Vec<MetricValueRecord>,
+    /// Deltas of the metrics.
+    pub store: Option<ItemVariationStore>,
+}
+
+impl MvarTable {
+    /// Returns the delta of the metric `tag` at normalized coordinates `coords`.
+    pub fn delta(&self, tag: &[u8; 4], coords: &[f32]) -> Option<f32> {
+        let record = self.records.iter().find(|record| &record.tag == tag)?;
+        Some(
+            self.store
+                .as_ref()?
+                .delta(record.outer, record.inner, coords),
+        )
+    }
+}
+
+/// Extracts the `MVAR` table from the provided data stream.
+pub fn read_mvar(data: &mut FontDataStream) -> Result<MvarTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    data.skip(2); // reserved
+    let record_size = data.read_u16()? as usize;
+    let record_count = data.read_u16()? as usize;
+    let store_offset = data.read_u16()? as usize;
+    if record_count > 0 && record_size < 8 {
+        return Err(Error::InvalidFormat);
+    }
+    let mut records = Vec::with_capacity(record_count);
+    for i in 0..record_count {
+        let mut record = data.substream(12 + i * record_size)?;
+        records.push(MetricValueRecord {
+            tag: record.read_tag()?,
+            outer: record.read_u16()?,
+            inner: record.read_u16()?,
+        });
+    }
+    Ok(MvarTable {
+        major_version,
+        minor_version,
+        records,
+        store: read_optional_at(data, store_offset, read_item_variation_store)?,
+    })
+}
+
+/// Serializes the `MVAR` table. Records are sorted by tag.
+pub fn write_mvar(writer: &mut FontDataWriter, mvar: &MvarTable) -> Result<(), Error> {
+    let base = writer.position();
+    let mut records = mvar.records.clone();
+    records.sort_by_key(|record| record.tag);
+    writer.write_u16(mvar.major_version);
+    writer.write_u16(mvar.minor_version);
+    writer.write_u16(0);
+    writer.write_u16(8);
+    writer.write_u16(records.len() as u16);
+    let store = match &mvar.store {
+        Some(store) => {
+            let placeholder = writer.reserve_offset16(base);
+            let mut bytes = FontDataWriter::new();
+            write_item_variation_store(&mut bytes, store)?;
+            Some((placeholder, bytes.into_bytes()?))
+        }
+        None => {
+            writer.write_u16(0);
+            None
+        }
+    };
+    for record in &records {
+        writer.write_tag(record.tag);
+        writer.write_u16(record.outer);
+        writer.write_u16(record.inner);
+    }
+    if let Some((placeholder, bytes)) = store {
+        writer.defer_subtable(placeholder, bytes);
+    }
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `name` (Naming) table representation and utilities for the Aurora Font Library.
+
+use std::collections::HashMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// Name ID: copyright notice.
+pub const COPYRIGHT_NOTICE: u16 = 0;
+/// Name ID: font family name.
+pub const FAMILY_NAME: u16 = 1;
+/// Name ID: font subfamily name.
+pub const SUBFAMILY_NAME: u16 = 2;
+/// Name ID: unique font identifier.
+pub const UNIQUE_ID: u16 = 3;
+/// Name ID: full font name.
+pub const FULL_NAME: u16 = 4;
+/// Name ID: version string.
+pub const VERSION_STRING: u16 = 5;
+/// Name ID: PostScript name.
+pub const POSTSCRIPT_NAME: u16 = 6;
+/// Name ID: typographic family name.
+pub const TYPOGRAPHIC_FAMILY_NAME: u16 = 16;
+/// Name ID: typographic subfamily name.
+pub const TYPOGRAPHIC_SUBFAMILY_NAME: u16 = 17;
+/// Name ID: variations PostScript name prefix.
+pub const VARIATIONS_POSTSCRIPT_NAME_PREFIX: u16 = 25;
+
+/// Windows language ID for English (United States).
+pub const LANGUAGE_EN_US: u16 = 0x0409;
+
+/// Mac OS Roman code points for bytes 0x80..=0xFF.
+const MAC_ROMAN: [char; 128] = [
+    'Ä', 'Å', 'Ç', 'É', 'Ñ', 'Ö', 'Ü', 'á', 'à', 'â', 'ä', 'ã', 'å', 'ç', 'é', 'è', //
+    'ê', 'ë', 'í', 'ì', 'î', 'ï', 'ñ', 'ó', 'ò', 'ô', 'ö', 'õ', 'ú', 'ù', 'û', 'ü', //
+    '†', '°', '¢', '£', '§', '•', '¶', 'ß', '®', '©', '™', '´', '¨', '≠', 'Æ', 'Ø', //
+    '∞', '±', '≤', '≥', '¥', 'µ', '∂', '∑', '∏', 'π', '∫', 'ª', 'º', 'Ω', 'æ', 'ø', //
+    '¿', '¡', '¬', '√', 'ƒ', '≈', '∆', '«', '»', '…', '\u{A0}', 'À', 'Ã', 'Õ', 'Œ', 'œ', //
+    '–', '—', '“', '”', '‘', '’', '÷', '◊', 'ÿ', 'Ÿ', '⁄', '€', '‹', '›', 'ﬁ', 'ﬂ', //
+    '‡', '·', '‚', '„', '‰', 'Â', 'Ê', 'Á', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', 'Ó', 'Ô', //
+    '\u{F8FF}', 'Ò', 'Ú', 'Û', 'Ù', 'ı', 'ˆ', '˜', '¯', '˘', '˙', '˚', '¸', '˝', '˛', 'ˇ', //
+];
+
+/// Naming table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NameTable {
+    /// Table version (0 or 1).
+    pub version: u16,
+    /// Name records in file order.
+    pub records: Vec<NameRecord>,
+    /// IETF BCP 47 language tags referenced by language IDs `0x8000..` (version 1).
+    pub lang_tags: Vec<String>,
+}
+
+/// A single name string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NameRecord {
+    /// Platform ID.
+    pub platform_id: u16,
+    /// Platform-specific encoding ID.
+    pub encoding_id: u16,
+    /// Language ID.
+    pub language_id: u16,
+    /// Name ID.
+    pub name_id: u16,
+    /// Encoded string bytes.
+    pub value: Vec<u8>,
+}
+
+impl NameRecord {
+    /// Creates a Windows Unicode BMP (3, 1) record encoded as UTF-16BE.
+    pub fn windows(name_id: u16, language_id: u16, value: &str) -> Self {
+        NameRecord {
+            platform_id: 3,
+            encoding_id: 1,
+            language_id,
+            name_id,
+            value: value.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        }
+    }
+
+    /// Returns true if the value is UTF-16BE encoded (Unicode and Windows platforms).
+    pub fn is_unicode(&self) -> bool {
+        self.platform_id == 0 || self.platform_id == 3
+    }
+
+    /// Decodes the value, or `None` for encodings other than UTF-16BE and Mac Roman.
+    pub fn decode(&self) -> Option<String> {
+        if self.is_unicode() {
+            let units: Vec<u16> = self
+                .value
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        } else if self.platform_id == 1 && self.encoding_id == 0 {
+            Some(
+                self.value
+                    .iter()
+                    .map(|&b| {
+                        if b < 0x80 {
+                            b as char
+                        } else {
+                            MAC_ROMAN[b as usize - 0x80]
+                        }
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl NameTable {
+    /// Returns the decoded string for `name_id`, preferring Windows English,
+    /// then any Unicode record, then Macintosh.
+    pub fn get(&self, name_id: u16) -> Option<String> {
+        let candidates = || self.records.iter().filter(|r| r.name_id == name_id);
+        candidates()
+            .find(|r| r.platform_id == 3 && r.language_id == LANGUAGE_EN_US)
+            .or_else(|| candidates().find(|r| r.is_unicode()))
+            .or_else(|| candidates().find(|r| r.platform_id == 1))
+            .and_then(NameRecord::decode)
+    }
+
+    /// Replaces every Windows record of `name_id` with `value`, adding an
+    /// English (United States) record if there was none.
+    pub fn set(&mut self, name_id: u16, value: &str) {
+        let mut found = false;
+        for record in &mut self.records {
+            if record.name_id == name_id && record.platform_id == 3 {
+                *record = NameRecord::windows(name_id, record.language_id, value);
+                found = true;
+            }
+        }
+        if !found {
+            self.records
+                .push(NameRecord::windows(name_id, LANGUAGE_EN_US, value));
+        }
+    }
+}
+
+/// Extracts the `name` table from the provided data stream.
+pub fn read_name(data: &mut FontDataStream) -> Result<NameTable, Error> {
+    let version = data.read_u16()?;
+    if version > 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let count = data.read_u16()?;
+    let storage_offset = data.read_u16()? as usize;
+    let mut records = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let platform_id = data.read_u16()?;
+        let encoding_id = data.read_u16()?;
+        let language_id = data.read_u16()?;
+        let name_id = data.read_u16()?;
+        let length = data.read_u16()? as usize;
+        let offset = data.read_u16()? as usize;
+        records.push(NameRecord {
+            platform_id,
+            encoding_id,
+            language_id,
+            name_id,
+            value: data.slice_at(storage_offset + offset, length)?.to_vec(),
+        });
+    }
+    let mut lang_tags = Vec::new();
+    if version == 1 {
+        let count = data.read_u16()?;
+        for _ in 0..count {
+            let length = data.read_u16()? as usize;
+            let offset = data.read_u16()? as usize;
+            let record = NameRecord {
+                platform_id: 0,
+                encoding_id: 0,
+                language_id: 0,
+                name_id: 0,
+                value: data.slice_at(storage_offset + offset, length)?.to_vec(),
+            };
+            lang_tags.push(record.decode().unwrap_or_default());
+        }
+    }
+    Ok(NameTable {
+        version,
+        records,
+        lang_tags,
+    })
+}
+
+/// Serializes the `name` table. Records are sorted as the specification
+/// requires and identical strings share storage.
+pub fn write_name(writer: &mut FontDataWriter, name: &NameTable) -> Result<(), Error> {
+    let mut records: Vec<_> = name.records.iter().collect();
+    records.sort_by_key(|r| (r.platform_id, r.encoding_id, r.language_id, r.name_id));
+    let version = if name.lang_tags.is_empty() { 0 } else { 1 };
+
+    let mut storage = Vec::new();
+    let mut offsets: HashMap<Vec<u8>, usize> = HashMap::new();
+    let mut store = |value: &[u8]| -> Result<u16, Error> {
+        let offset = *offsets.entry(value.to_vec()).or_insert_with(|| {
+            storage.extend_from_slice(value);
+            storage.len() - value.len()
+        });
+        u16::try_from(offset).map_err(|_| {
+            Error::Io(IoError::OffsetOverflow {
+                position: 0,
+                offset,
+            })
+        })
+    };
+
+    let header_len = 6
+        + records.len() * 12
+        + if version == 1 {
+            2 + name.lang_tags.len() * 4
+        } else {
+            0
+        };
+    writer.write_u16(version);
+    writer.write_u16(records.len() as u16);
+    writer.write_u16(header_len as u16);
+    for record in &records {
+        writer.write_u16(record.platform_id);
+        writer.write_u16(record.encoding_id);
+        writer.write_u16(record.language_id);
+        writer.write_u16(record.name_id);
+        writer.write_u16(record.value.len() as u16);
+        writer.write_u16(store(&record.value)?);
+    }
+    if version == 1 {
+        writer.write_u16(name.lang_tags.len() as u16);
+        for tag in &name.lang_tags {
+            let value: Vec<u8> = tag.encode_utf16().flat_map(u16::to_be_bytes).collect();
+            writer.write_u16(value.len() as u16);
+            writer.write_u16(store(&value)?);
+        }
+    }
+    writer.write_bytes(&storage);
+    Ok(())
+}
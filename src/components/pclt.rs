@@ -0,0 +1,99 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `PCLT` (PCL 5) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// PCL 5 table: font selection data for HP printers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PcltTable {
+    /// Major version of the table (1).
+    pub major_version: u16,
+    /// Minor version of the table (0).
+    pub minor_version: u16,
+    /// Font number assigned by the vendor; bit 31 marks a native font.
+    pub font_number: u32,
+    /// Width of the space glyph, in font units.
+    pub pitch: u16,
+    /// Height of the lowercase x, in font units.
+    pub x_height: u16,
+    /// Posture, width and structure bits.
+    pub style: u16,
+    /// Vendor code and typeface family.
+    pub type_family: u16,
+    /// Height of the uppercase letters, in font units.
+    pub cap_height: u16,
+    /// PCL symbol set.
+    pub symbol_set: u16,
+    /// Typeface name, ASCII padded with spaces.
+    pub typeface: [u8; 16],
+    /// Bit field of the character sets the font covers.
+    pub character_complement: [u8; 8],
+    /// Six-character file name for the font, ASCII.
+    pub file_name: [u8; 6],
+    /// Stroke weight, from -7 (ultra thin) to 7 (ultra black).
+    pub stroke_weight: i8,
+    /// Width type, from -5 (ultra compressed) to 5 (ultra expanded).
+    pub width_type: i8,
+    /// Serif style bits.
+    pub serif_style: u8,
+}
+
+impl PcltTable {
+    /// Returns the typeface name without trailing padding.
+    pub fn typeface_name(&self) -> String {
+        String::from_utf8_lossy(&self.typeface)
+            .trim_end_matches([' ', '\0'])
+            .to_string()
+    }
+}
+
+/// Extracts the `PCLT` table from the provided data stream.
+pub fn read_pclt(data: &mut FontDataStream) -> Result<PcltTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let font_number = data.read_u32()?;
+    let pitch = data.read_u16()?;
+    let x_height = data.read_u16()?;
+    let style = data.read_u16()?;
+    let type_family = data.read_u16()?;
+    let cap_height = data.read_u16()?;
+    let symbol_set = data.read_u16()?;
+    let typeface = read_array(data)?;
+    let character_complement = read_array(data)?;
+    let file_name = read_array(data)?;
+    let stroke_weight = data.read_i8()?;
+    let width_type = data.read_i8()?;
+    let serif_style = data.read_u8()?;
+    Ok(PcltTable {
+        major_version,
+        minor_version,
+        font_number,
+        pitch,
+        x_height,
+        style,
+        type_family,
+        cap_height,
+        symbol_set,
+        typeface,
+        character_complement,
+        file_name,
+        stroke_weight,
+        width_type,
+        serif_style,
+    })
+}
+
+fn read_array<const N: usize>(data: &mut FontDataStream) -> Result<[u8; N], Error> {
+    let mut array = [0; N];
+    array.copy_from_slice(data.read_bytes(N)?);
+    Ok(array)
+}
@@ -0,0 +1,417 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `post` (PostScript) table representation and utilities for the Aurora Font Library.
+
+use std::collections::HashMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// The 258 standard Macintosh glyph names indexed by version 2 name indices.
+pub const MAC_GLYPH_NAMES: [&str; 258] = [
+    ".notdef",
+    ".null",
+    "nonmarkingreturn",
+    "space",
+    "exclam",
+    "quotedbl",
+    "numbersign",
+    "dollar",
+    "percent",
+    "ampersand",
+    "quotesingle",
+    "parenleft",
+    "parenright",
+    "asterisk",
+    "plus",
+    "comma",
+    "hyphen",
+    "period",
+    "slash",
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "colon",
+    "semicolon",
+    "less",
+    "equal",
+    "greater",
+    "question",
+    "at",
+    "A",
+    "B",
+    "C",
+    "D",
+    "E",
+    "F",
+    "G",
+    "H",
+    "I",
+    "J",
+    "K",
+    "L",
+    "M",
+    "N",
+    "O",
+    "P",
+    "Q",
+    "R",
+    "S",
+    "T",
+    "U",
+    "V",
+    "W",
+    "X",
+    "Y",
+    "Z",
+    "bracketleft",
+    "backslash",
+    "bracketright",
+    "asciicircum",
+    "underscore",
+    "grave",
+    "a",
+    "b",
+    "c",
+    "d",
+    "e",
+    "f",
+    "g",
+    "h",
+    "i",
+    "j",
+    "k",
+    "l",
+    "m",
+    "n",
+    "o",
+    "p",
+    "q",
+    "r",
+    "s",
+    "t",
+    "u",
+    "v",
+    "w",
+    "x",
+    "y",
+    "z",
+    "braceleft",
+    "bar",
+    "braceright",
+    "asciitilde",
+    "Adieresis",
+    "Aring",
+    "Ccedilla",
+    "Eacute",
+    "Ntilde",
+    "Odieresis",
+    "Udieresis",
+    "aacute",
+    "agrave",
+    "acircumflex",
+    "adieresis",
+    "atilde",
+    "aring",
+    "ccedilla",
+    "eacute",
+    "egrave",
+    "ecircumflex",
+    "edieresis",
+    "iacute",
+    "igrave",
+    "icircumflex",
+    "idieresis",
+    "ntilde",
+    "oacute",
+    "ograve",
+    "ocircumflex",
+    "odieresis",
+    "otilde",
+    "uacute",
+    "ugrave",
+    "ucircumflex",
+    "udieresis",
+    "dagger",
+    "degree",
+    "cent",
+    "sterling",
+    "section",
+    "bullet",
+    "paragraph",
+    "germandbls",
+    "registered",
+    "copyright",
+    "trademark",
+    "acute",
+    "dieresis",
+    "notequal",
+    "AE",
+    "Oslash",
+    "infinity",
+    "plusminus",
+    "lessequal",
+    "greaterequal",
+    "yen",
+    "mu",
+    "partialdiff",
+    "summation",
+    "product",
+    "pi",
+    "integral",
+    "ordfeminine",
+    "ordmasculine",
+    "Omega",
+    "ae",
+    "oslash",
+    "questiondown",
+    "exclamdown",
+    "logicalnot",
+    "radical",
+    "florin",
+    "approxequal",
+    "Delta",
+    "guillemotleft",
+    "guillemotright",
+    "ellipsis",
+    "nonbreakingspace",
+    "Agrave",
+    "Atilde",
+    "Otilde",
+    "OE",
+    "oe",
+    "endash",
+    "emdash",
+    "quotedblleft",
+    "quotedblright",
+    "quoteleft",
+    "quoteright",
+    "divide",
+    "lozenge",
+    "ydieresis",
+    "Ydieresis",
+    "fraction",
+    "currency",
+    "guilsinglleft",
+    "guilsinglright",
+    "fi",
+    "fl",
+    "daggerdbl",
+    "periodcentered",
+    "quotesinglbase",
+    "quotedblbase",
+    "perthousand",
+    "Acircumflex",
+    "Ecircumflex",
+    "Aacute",
+    "Edieresis",
+    "Egrave",
+    "Iacute",
+    "Icircumflex",
+    "Idieresis",
+    "Igrave",
+    "Oacute",
+    "Ocircumflex",
+    "apple",
+    "Ograve",
+    "Uacute",
+    "Ucircumflex",
+    "Ugrave",
+    "dotlessi",
+    "circumflex",
+    "tilde",
+    "macron",
+    "breve",
+    "dotaccent",
+    "ring",
+    "cedilla",
+    "hungarumlaut",
+    "ogonek",
+    "caron",
+    "Lslash",
+    "lslash",
+    "Scaron",
+    "scaron",
+    "Zcaron",
+    "zcaron",
+    "brokenbar",
+    "Eth",
+    "eth",
+    "Yacute",
+    "yacute",
+    "Thorn",
+    "thorn",
+    "minus",
+    "multiply",
+    "onesuperior",
+    "twosuperior",
+    "threesuperior",
+    "onehalf",
+    "onequarter",
+    "threequarters",
+    "franc",
+    "Gbreve",
+    "gbreve",
+    "Idotaccent",
+    "Scedilla",
+    "scedilla",
+    "Cacute",
+    "cacute",
+    "Ccaron",
+    "ccaron",
+    "dcroat",
+];
+
+/// PostScript table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostTable {
+    /// Table version as a 16.16 value (0x00010000, 0x00020000, 0x00025000 or 0x00030000).
+    pub version: u32,
+    /// Italic angle in counter-clockwise degrees from the vertical.
+    pub italic_angle: f32,
+    /// Suggested y coordinate of the top of the underline.
+    pub underline_position: i16,
+    /// Suggested underline thickness.
+    pub underline_thickness: i16,
+    /// Non-zero if the font is monospaced.
+    pub is_fixed_pitch: u32,
+    /// Minimum memory usage when downloaded as a Type 42 font.
+    pub min_mem_type42: u32,
+    /// Maximum memory usage when downloaded as a Type 42 font.
+    pub max_mem_type42: u32,
+    /// Minimum memory usage when downloaded as a Type 1 font.
+    pub min_mem_type1: u32,
+    /// Maximum memory usage when downloaded as a Type 1 font.
+    pub max_mem_type1: u32,
+    /// Glyph names for versions 1, 2 and 2.5; `None` otherwise.
+    pub glyph_names: Option<Vec<String>>,
+}
+
+impl PostTable {
+    /// Returns the name of a glyph, if the table stores names.
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<&str> {
+        self.glyph_names
+            .as_ref()?
+            .get(glyph_id as usize)
+            .map(String::as_str)
+    }
+}
+
+/// Extracts the `post` table from the provided data stream.
+pub fn read_post(data: &mut FontDataStream) -> Result<PostTable, Error> {
+    let version = data.read_u32()?;
+    let mut post = PostTable {
+        version,
+        italic_angle: data.read_fixed()?,
+        underline_position: data.read_i16()?,
+        underline_thickness: data.read_i16()?,
+        is_fixed_pitch: data.read_u32()?,
+        min_mem_type42: data.read_u32()?,
+        max_mem_type42: data.read_u32()?,
+        min_mem_type1: data.read_u32()?,
+        max_mem_type1: data.read_u32()?,
+        glyph_names: None,
+    };
+    match version {
+        0x00010000 => {
+            post.glyph_names = Some(MAC_GLYPH_NAMES.iter().map(|n| n.to_string()).collect());
+        }
+        0x00020000 => {
+            let count = data.read_u16()?;
+            let indices = (0..count)
+                .map(|_| data.read_u16())
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut strings = Vec::new();
+            while !data.is_eof() {
+                let length = data.read_u8()? as usize;
+                let bytes = data.read_bytes(length)?;
+                strings.push(bytes.iter().map(|&b| b as char).collect::<String>());
+            }
+            let names = indices
+                .iter()
+                .map(|&index| match index as usize {
+                    i if i < 258 => Ok(MAC_GLYPH_NAMES[i].to_string()),
+                    i => strings
+                        .get(i - 258)
+                        .cloned()
+                        .ok_or(Error::Io(IoError::InvalidData)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            post.glyph_names = Some(names);
+        }
+        0x00025000 => {
+            let count = data.read_u16()?;
+            let names = (0..count)
+                .map(|glyph| {
+                    let index = glyph as i32 + data.read_i8()? as i32;
+                    MAC_GLYPH_NAMES
+                        .get(index as usize)
+                        .map(|n| n.to_string())
+                        .ok_or(Error::Io(IoError::InvalidData))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            post.glyph_names = Some(names);
+        }
+        _ => {}
+    }
+    Ok(post)
+}
+
+/// Serializes the `post` table. Glyph names are written as version 2.0 and
+/// a table without names as version 3.0.
+pub fn write_post(writer: &mut FontDataWriter, post: &PostTable) -> Result<(), Error> {
+    writer.write_u32(if post.glyph_names.is_some() {
+        0x00020000
+    } else {
+        0x00030000
+    });
+    writer.write_fixed(post.italic_angle);
+    writer.write_i16(post.underline_position);
+    writer.write_i16(post.underline_thickness);
+    writer.write_u32(post.is_fixed_pitch);
+    writer.write_u32(post.min_mem_type42);
+    writer.write_u32(post.max_mem_type42);
+    writer.write_u32(post.min_mem_type1);
+    writer.write_u32(post.max_mem_type1);
+    let Some(names) = &post.glyph_names else {
+        return Ok(());
+    };
+
+    let standard: HashMap<&str, u16> = MAC_GLYPH_NAMES
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| (n, i as u16))
+        .collect();
+    let mut custom: HashMap<&str, u16> = HashMap::new();
+    let mut strings = FontDataWriter::new();
+    writer.write_u16(names.len() as u16);
+    for name in names {
+        let index = match standard.get(name.as_str()) {
+            Some(&index) => index,
+            None => {
+                let next = 258 + custom.len() as u16;
+                *custom.entry(name.as_str()).or_insert_with(|| {
+                    let bytes: Vec<u8> = name.chars().map(|c| c as u8).collect();
+                    strings.write_u8(bytes.len().min(255) as u8);
+                    strings.write_bytes(&bytes[..bytes.len().min(255)]);
+                    next
+                })
+            }
+        };
+        writer.write_u16(index);
+    }
+    writer.write_bytes(strings.as_bytes());
+    Ok(())
+}
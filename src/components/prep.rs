@@ -0,0 +1,24 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `prep` (Control Value Program) table representation and utilities for the Aurora Font Library.
+
+use crate::error::Error;
+use crate::io::stream::FontDataStream;
+
+/// Control value program: TrueType instructions run whenever the size or transform changes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrepTable {
+    /// Instruction bytes.
+    pub instructions: Vec<u8>,
+}
+
+/// Extracts the `prep` table from the provided data stream.
+pub fn read_prep(data: &mut FontDataStream) -> Result<PrepTable, Error> {
+    Ok(PrepTable {
+        instructions: data.remaining_bytes().to_vec(),
+    })
+}
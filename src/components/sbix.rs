@@ -0,0 +1,170 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `sbix` (Standard Bitmap Graphics) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// PNG image data.
+pub const GRAPHIC_TYPE_PNG: [u8; 4] = *b"png ";
+/// JPEG image data.
+pub const GRAPHIC_TYPE_JPG: [u8; 4] = *b"jpg ";
+/// TIFF image data.
+pub const GRAPHIC_TYPE_TIFF: [u8; 4] = *b"tiff";
+/// The data is the big-endian glyph id of another glyph in the same strike.
+pub const GRAPHIC_TYPE_DUPE: [u8; 4] = *b"dupe";
+/// Mask applied to the glyph outline (reserved by Apple).
+pub const GRAPHIC_TYPE_MASK: [u8; 4] = *b"mask";
+/// Horizontally mirrored copy of another glyph (reserved by Apple).
+pub const GRAPHIC_TYPE_FLIP: [u8; 4] = *b"flip";
+
+/// Draw outlines in addition to the bitmaps.
+pub const DRAW_OUTLINES: u16 = 0x0002;
+
+/// Bitmap data of one glyph in a strike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbixGlyph {
+    /// Horizontal offset of the image from the glyph origin, in pixels.
+    pub origin_offset_x: i16,
+    /// Vertical offset of the image from the glyph origin, in pixels.
+    pub origin_offset_y: i16,
+    /// Format of `data`, one of the `GRAPHIC_TYPE_*` tags.
+    pub graphic_type: [u8; 4],
+    /// Image data.
+    pub data: Vec<u8>,
+}
+
+/// A set of bitmaps drawn for one size.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SbixStrike {
+    /// Pixels per em the strike was designed for.
+    pub ppem: u16,
+    /// Pixel density the strike was designed for.
+    pub ppi: u16,
+    /// Glyph bitmaps by glyph id; `None` for glyphs without one.
+    pub glyphs: Vec<Option<SbixGlyph>>,
+}
+
+/// A glyph bitmap resolved by [`SbixTable::bitmap_glyph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SbixBitmap<'a> {
+    /// Pixels per em of the strike the bitmap comes from.
+    pub ppem: u16,
+    /// Pixel density of the strike the bitmap comes from.
+    pub ppi: u16,
+    /// Bitmap data, never of type `dupe`.
+    pub glyph: &'a SbixGlyph,
+}
+
+/// Standard bitmap graphics table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SbixTable {
+    /// Table version (1).
+    pub version: u16,
+    /// Table flags; bit 0 is always set.
+    pub flags: u16,
+    /// Bitmap strikes.
+    pub strikes: Vec<SbixStrike>,
+}
+
+impl SbixTable {
+    /// Returns the strike best suited for rendering at `ppem`: the smallest
+    /// strike at least as large, or the largest one when all are smaller.
+    pub fn strike_for_ppem(&self, ppem: u16) -> Option<&SbixStrike> {
+        let larger = self
+            .strikes
+            .iter()
+            .filter(|strike| strike.ppem >= ppem)
+            .min_by_key(|strike| strike.ppem);
+        larger.or_else(|| self.strikes.iter().max_by_key(|strike| strike.ppem))
+    }
+
+    /// Returns the bitmap of `glyph_id` from the strike best suited for
+    /// `ppem`, following `dupe` records to the glyph holding the image.
+    ///
+    /// Returns `IoError::InvalidData` for `dupe` records that are truncated,
+    /// point outside the strike, or form a cycle.
+    pub fn bitmap_glyph(&self, glyph_id: u16, ppem: u16) -> Result<Option<SbixBitmap<'_>>, Error> {
+        let Some(strike) = self.strike_for_ppem(ppem) else {
+            return Ok(None);
+        };
+        let mut current = glyph_id;
+        for _ in 0..=strike.glyphs.len() {
+            let Some(glyph) = strike.glyphs.get(current as usize).and_then(Option::as_ref) else {
+                return Ok(None);
+            };
+            if glyph.graphic_type != GRAPHIC_TYPE_DUPE {
+                return Ok(Some(SbixBitmap {
+                    ppem: strike.ppem,
+                    ppi: strike.ppi,
+                    glyph,
+                }));
+            }
+            let target = glyph
+                .data
+                .first_chunk::<2>()
+                .ok_or(Error::Io(IoError::InvalidData))?;
+            current = u16::from_be_bytes(*target);
+            if current as usize >= strike.glyphs.len() {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+        }
+        Err(Error::Io(IoError::InvalidData))
+    }
+}
+
+/// Extracts the `sbix` table from the provided data stream. `num_glyphs` is
+/// the glyph count from `maxp`.
+pub fn read_sbix(data: &mut FontDataStream, num_glyphs: u16) -> Result<SbixTable, Error> {
+    let version = data.read_u16()?;
+    if version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let flags = data.read_u16()?;
+    let strike_count = data.read_u32()?;
+
+    let mut strikes = Vec::with_capacity(strike_count.min(256) as usize);
+    for _ in 0..strike_count {
+        let strike_offset = data.read_u32()? as usize;
+        let mut strike = data.substream(strike_offset)?;
+        let ppem = strike.read_u16()?;
+        let ppi = strike.read_u16()?;
+        let mut offsets = Vec::with_capacity(num_glyphs as usize + 1);
+        for _ in 0..=num_glyphs {
+            offsets.push(strike.read_u32()? as usize);
+        }
+
+        let mut glyphs = Vec::with_capacity(num_glyphs as usize);
+        for window in offsets.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            if end < start {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            if end == start {
+                glyphs.push(None);
+                continue;
+            }
+            // Each record holds two origin offsets and a graphic type before the image.
+            if end - start < 8 {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            let mut record = strike.substream(start)?;
+            glyphs.push(Some(SbixGlyph {
+                origin_offset_x: record.read_i16()?,
+                origin_offset_y: record.read_i16()?,
+                graphic_type: record.read_tag()?,
+                data: strike.slice_at(start + 8, end - start - 8)?.to_vec(),
+            }));
+        }
+        strikes.push(SbixStrike { ppem, ppi, glyphs });
+    }
+    Ok(SbixTable {
+        version,
+        flags,
+        strikes,
+    })
+}
@@ -0,0 +1,367 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `STAT` (Style Attributes) table representation and utilities for the Aurora Font Library.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+use super::name::{NameTable, SUBFAMILY_NAME};
+
+/// Axis value flag: the value describes fonts of the family released before
+/// this one.
+pub const OLDER_SIBLING_FONT_ATTRIBUTE: u16 = 0x0001;
+/// Axis value flag: the name may be omitted from composed style names, as
+/// for "Regular".
+pub const ELIDABLE_AXIS_VALUE_NAME: u16 = 0x0002;
+
+/// A design axis of the font family.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AxisRecord {
+    /// Axis tag, e.g. `wght`.
+    pub tag: [u8; 4],
+    /// Name ID of the axis name.
+    pub axis_name_id: u16,
+    /// Position of the axis in composed names, lowest first.
+    pub axis_ordering: u16,
+}
+
+/// A named value or range of values on one or more design axes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AxisValue {
+    /// Format 1: a single value.
+    Value {
+        /// Index into the design axes.
+        axis_index: u16,
+        /// Flags (`OLDER_SIBLING_FONT_ATTRIBUTE`, `ELIDABLE_AXIS_VALUE_NAME`).
+        flags: u16,
+        /// Name ID of the value name.
+        value_name_id: u16,
+        /// Value in user coordinates.
+        value: f32,
+    },
+    /// Format 2: a range of values with a nominal value.
+    Range {
+        /// Index into the design axes.
+        axis_index: u16,
+        /// Flags.
+        flags: u16,
+        /// Name ID of the value name.
+        value_name_id: u16,
+        /// Nominal value in user coordinates.
+        nominal_value: f32,
+        /// Start of the range.
+        range_min_value: f32,
+        /// End of the range.
+        range_max_value: f32,
+    },
+    /// Format 3: a single value with a style-linked value, e.g. Regular and Bold.
+    Linked {
+        /// Index into the design axes.
+        axis_index: u16,
+        /// Flags.
+        flags: u16,
+        /// Name ID of the value name.
+        value_name_id: u16,
+        /// Value in user coordinates.
+        value: f32,
+        /// Value of the style-linked counterpart.
+        linked_value: f32,
+    },
+    /// Format 4: a combination of values on several axes.
+    Location {
+        /// Flags.
+        flags: u16,
+        /// Name ID of the value name.
+        value_name_id: u16,
+        /// `(axis_index, value)` pairs.
+        values: Vec<(u16, f32)>,
+    },
+}
+
+impl AxisValue {
+    /// Returns the flags of the axis value.
+    pub fn flags(&self) -> u16 {
+        match self {
+            AxisValue::Value { flags, .. }
+            | AxisValue::Range { flags, .. }
+            | AxisValue::Linked { flags, .. }
+            | AxisValue::Location { flags, .. } => *flags,
+        }
+    }
+
+    /// Returns the name ID of the value name.
+    pub fn value_name_id(&self) -> u16 {
+        match self {
+            AxisValue::Value { value_name_id, .. }
+            | AxisValue::Range { value_name_id, .. }
+            | AxisValue::Linked { value_name_id, .. }
+            | AxisValue::Location { value_name_id, .. } => *value_name_id,
+        }
+    }
+
+    /// Returns whether the name may be omitted from composed style names.
+    pub fn is_elidable(&self) -> bool {
+        self.flags() & ELIDABLE_AXIS_VALUE_NAME != 0
+    }
+
+    /// Returns the design axes the value applies to.
+    pub fn axis_indices(&self) -> Vec<u16> {
+        match self {
+            AxisValue::Value { axis_index, .. }
+            | AxisValue::Range { axis_index, .. }
+            | AxisValue::Linked { axis_index, .. } => vec![*axis_index],
+            AxisValue::Location { values, .. } => values.iter().map(|&(axis, _)| axis).collect(),
+        }
+    }
+}
+
+/// Style attributes table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StatTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0, 1 or 2).
+    pub minor_version: u16,
+    /// Design axes of the family.
+    pub design_axes: Vec<AxisRecord>,
+    /// Axis values.
+    pub axis_values: Vec<AxisValue>,
+    /// Name ID used when every part of a style name is elided (version 1.1+).
+    pub elided_fallback_name_id: Option<u16>,
+}
+
+impl StatTable {
+    /// Returns the index of the design axis with `tag`.
+    pub fn axis_index(&self, tag: &[u8; 4]) -> Option<usize> {
+        self.design_axes.iter().position(|axis| &axis.tag == tag)
+    }
+
+    /// Returns the axis values naming a location, at most one per design axis,
+    /// in axis ordering.
+    ///
+    /// Format 4 values matching the location exactly are preferred; the other
+    /// axes take a format 1 or 3 value equal to their coordinate, else a
+    /// format 2 range containing it. Axes missing from `location` are
+    /// skipped, as are values flagged `OLDER_SIBLING_FONT_ATTRIBUTE`.
+    ///
+    /// # Arguments
+    /// * `location` - User coordinates by axis tag.
+    pub fn axis_values_at(&self, location: &BTreeMap<[u8; 4], f32>) -> Vec<&AxisValue> {
+        let coordinate = |axis_index: u16| {
+            self.design_axes
+                .get(axis_index as usize)
+                .and_then(|axis| location.get(&axis.tag))
+                .copied()
+        };
+        // Values kept for fonts released before this one do not describe it.
+        let current = || {
+            self.axis_values
+                .iter()
+                .filter(|value| value.flags() & OLDER_SIBLING_FONT_ATTRIBUTE == 0)
+        };
+        let mut chosen: BTreeMap<u16, &AxisValue> = BTreeMap::new();
+
+        // Combinations covering the most axes first.
+        let mut locations: Vec<&AxisValue> = current()
+            .filter(|value| match value {
+                AxisValue::Location { values, .. } => {
+                    values.iter().all(|&(axis, v)| coordinate(axis) == Some(v))
+                }
+                _ => false,
+            })
+            .collect();
+        locations.sort_by_key(|value| std::cmp::Reverse(value.axis_indices().len()));
+        for value in locations {
+            let axes = value.axis_indices();
+            if axes.iter().any(|axis| chosen.contains_key(axis)) {
+                continue;
+            }
+            for axis in axes {
+                chosen.insert(axis, value);
+            }
+        }
+
+        for axis_index in 0..self.design_axes.len() as u16 {
+            if chosen.contains_key(&axis_index) {
+                continue;
+            }
+            let Some(coordinate) = coordinate(axis_index) else {
+                continue;
+            };
+            let exact = current().find(|value| match value {
+                AxisValue::Value {
+                    axis_index: axis,
+                    value,
+                    ..
+                }
+                | AxisValue::Linked {
+                    axis_index: axis,
+                    value,
+                    ..
+                } => *axis == axis_index && *value == coordinate,
+                _ => false,
+            });
+            let range = || {
+                current().find(|value| match value {
+                    AxisValue::Range {
+                        axis_index: axis,
+                        range_min_value,
+                        range_max_value,
+                        ..
+                    } => {
+                        *axis == axis_index
+                            && *range_min_value <= coordinate
+                            && coordinate <= *range_max_value
+                    }
+                    _ => false,
+                })
+            };
+            if let Some(value) = exact.or_else(range) {
+                chosen.insert(axis_index, value);
+            }
+        }
+
+        let mut values: Vec<(u16, &AxisValue)> = Vec::new();
+        for (&axis_index, &value) in &chosen {
+            let ordering = self.design_axes[axis_index as usize].axis_ordering;
+            match values.iter_mut().find(|(_, v)| std::ptr::eq(*v, value)) {
+                Some(entry) => entry.0 = entry.0.min(ordering),
+                None => values.push((ordering, value)),
+            }
+        }
+        values.sort_by_key(|&(ordering, _)| ordering);
+        values.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Composes the style name of a location, such as "Bold Condensed
+    /// Italic", from the names of its axis values. Elidable names are
+    /// omitted; when every name is elided the elided fallback name is used,
+    /// or the subfamily name ID 2 before version 1.1.
+    ///
+    /// # Arguments
+    /// * `location` - User coordinates by axis tag.
+    /// * `name` - The `name` table holding the value names.
+    ///
+    /// Returns `None` if a name ID is missing from `name`.
+    pub fn style_name(
+        &self,
+        location: &BTreeMap<[u8; 4], f32>,
+        name: &NameTable,
+    ) -> Option<String> {
+        let parts = self
+            .axis_values_at(location)
+            .into_iter()
+            .filter(|value| !value.is_elidable())
+            .map(|value| name.get(value.value_name_id()))
+            .collect::<Option<Vec<String>>>()?;
+        if parts.is_empty() {
+            return name.get(self.elided_fallback_name_id.unwrap_or(SUBFAMILY_NAME));
+        }
+        Some(parts.join(" "))
+    }
+}
+
+/// Extracts an axis value table.
+pub fn read_axis_value(data: &mut FontDataStream) -> Result<AxisValue, Error> {
+    let format = data.read_u16()?;
+    match format {
+        1..=3 => {
+            let axis_index = data.read_u16()?;
+            let flags = data.read_u16()?;
+            let value_name_id = data.read_u16()?;
+            let value = data.read_fixed()?;
+            Ok(match format {
+                1 => AxisValue::Value {
+                    axis_index,
+                    flags,
+                    value_name_id,
+                    value,
+                },
+                2 => AxisValue::Range {
+                    axis_index,
+                    flags,
+                    value_name_id,
+                    nominal_value: value,
+                    range_min_value: data.read_fixed()?,
+                    range_max_value: data.read_fixed()?,
+                },
+                _ => AxisValue::Linked {
+                    axis_index,
+                    flags,
+                    value_name_id,
+                    value,
+                    linked_value: data.read_fixed()?,
+                },
+            })
+        }
+        4 => {
+            let axis_count = data.read_u16()?;
+            let flags = data.read_u16()?;
+            let value_name_id = data.read_u16()?;
+            let values = (0..axis_count)
+                .map(|_| Ok((data.read_u16()?, data.read_fixed()?)))
+                .collect::<Result<Vec<_>, Error>>()?;
+            Ok(AxisValue::Location {
+                flags,
+                value_name_id,
+                values,
+            })
+        }
+        _ => Err(Error::InvalidFormat),
+    }
+}
+
+/// Extracts the `STAT` table from the provided data stream.
+pub fn read_stat(data: &mut FontDataStream) -> Result<StatTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let design_axis_size = data.read_u16()? as usize;
+    let design_axis_count = data.read_u16()? as usize;
+    let design_axes_offset = data.read_u32()? as usize;
+    let axis_value_count = data.read_u16()? as usize;
+    let axis_values_offset = data.read_u32()? as usize;
+    let elided_fallback_name_id = if minor_version >= 1 {
+        Some(data.read_u16()?)
+    } else {
+        None
+    };
+    if design_axis_count > 0 && design_axis_size < 8 {
+        return Err(Error::InvalidFormat);
+    }
+
+    let mut design_axes = Vec::with_capacity(design_axis_count);
+    for i in 0..design_axis_count {
+        let mut record = data.substream(design_axes_offset + i * design_axis_size)?;
+        design_axes.push(AxisRecord {
+            tag: record.read_tag()?,
+            axis_name_id: record.read_u16()?,
+            axis_ordering: record.read_u16()?,
+        });
+    }
+
+    let mut axis_values = Vec::with_capacity(axis_value_count);
+    if axis_value_count > 0 {
+        let mut offsets = data.substream(axis_values_offset)?;
+        let array = offsets.substream(0)?;
+        for _ in 0..axis_value_count {
+            let offset = offsets.read_u16()? as usize;
+            axis_values.push(read_axis_value(&mut array.substream(offset)?)?);
+        }
+    }
+    Ok(StatTable {
+        major_version,
+        minor_version,
+        design_axes,
+        axis_values,
+        elided_fallback_name_id,
+    })
+}
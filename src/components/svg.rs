@@ -0,0 +1,138 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `SVG ` (Scalable Vector Graphics) table representation and utilities for the Aurora Font Library.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::decompress::{gunzip, is_gzip};
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Largest ratio between the uncompressed and stored size of a document;
+/// larger documents are rejected rather than inflated without bound.
+const MAX_DOCUMENT_EXPANSION: usize = 256;
+
+/// A range of glyphs drawn by one SVG document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SvgDocumentRecord {
+    /// First glyph of the range.
+    pub start_glyph_id: u16,
+    /// Last glyph of the range, inclusive.
+    pub end_glyph_id: u16,
+    /// Index into the documents of the table.
+    pub document_index: usize,
+}
+
+/// The SVG document of a glyph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgGlyph<'a> {
+    /// Uncompressed document bytes (UTF-8 XML).
+    pub document: Cow<'a, [u8]>,
+    /// Id of the element drawing the glyph, `glyph<N>`.
+    pub element_id: String,
+}
+
+/// SVG glyph table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SvgTable {
+    /// Table version (0).
+    pub version: u16,
+    /// Document index records, sorted by glyph.
+    pub records: Vec<SvgDocumentRecord>,
+    /// Distinct documents as stored, possibly gzip-compressed.
+    pub documents: Vec<Vec<u8>>,
+}
+
+impl SvgTable {
+    /// Returns the record of the range holding `glyph_id`.
+    pub fn record(&self, glyph_id: u16) -> Option<&SvgDocumentRecord> {
+        let index = self
+            .records
+            .partition_point(|record| record.end_glyph_id < glyph_id);
+        self.records
+            .get(index)
+            .filter(|record| record.start_glyph_id <= glyph_id)
+    }
+
+    /// Returns the uncompressed SVG document drawing `glyph_id` and the id of
+    /// the glyph element within it, or `None` for glyphs without SVG.
+    ///
+    /// Returns `IoError::InvalidData` if a gzip-compressed document is corrupt
+    /// or inflates to more than 256 times its stored size.
+    pub fn glyph_document(&self, glyph_id: u16) -> Result<Option<SvgGlyph<'_>>, Error> {
+        let Some(record) = self.record(glyph_id) else {
+            return Ok(None);
+        };
+        let stored = self
+            .documents
+            .get(record.document_index)
+            .ok_or(Error::Io(IoError::InvalidData))?;
+        let document = if is_gzip(stored) {
+            Cow::Owned(gunzip(
+                stored,
+                stored.len().saturating_mul(MAX_DOCUMENT_EXPANSION),
+            )?)
+        } else {
+            Cow::Borrowed(stored.as_slice())
+        };
+        Ok(Some(SvgGlyph {
+            document,
+            element_id: format!("glyph{glyph_id}"),
+        }))
+    }
+}
+
+/// Extracts the `SVG ` table from the provided data stream. Records pointing
+/// at the same document bytes share one document.
+pub fn read_svg(data: &mut FontDataStream) -> Result<SvgTable, Error> {
+    let version = data.read_u16()?;
+    if version != 0 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let list_offset = data.read_u32()? as usize;
+    let mut list = data.substream(list_offset)?;
+    let count = list.read_u16()?;
+
+    let mut records = Vec::with_capacity(count as usize);
+    let mut documents = Vec::new();
+    // Document index of each location and of each distinct document.
+    let mut by_location: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut by_bytes: HashMap<&[u8], usize> = HashMap::new();
+    for _ in 0..count {
+        let start_glyph_id = list.read_u16()?;
+        let end_glyph_id = list.read_u16()?;
+        let offset = list.read_u32()? as usize;
+        let length = list.read_u32()? as usize;
+        if end_glyph_id < start_glyph_id {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let document_index = match by_location.get(&(offset, length)) {
+            Some(&index) => index,
+            None => {
+                let bytes = data.slice_at(list_offset + offset, length)?;
+                let index = *by_bytes.entry(bytes).or_insert_with(|| {
+                    documents.push(bytes.to_vec());
+                    documents.len() - 1
+                });
+                by_location.insert((offset, length), index);
+                index
+            }
+        };
+        records.push(SvgDocumentRecord {
+            start_glyph_id,
+            end_glyph_id,
+            document_index,
+        });
+    }
+    records.sort_by_key(|record| record.start_glyph_id);
+    Ok(SvgTable {
+        version,
+        records,
+        documents,
+    })
+}
@@ -0,0 +1,141 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `trak` (Tracking) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Tracking table: size-dependent spacing added between glyphs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TrakTable {
+    /// Table version as a 16.16 value (0x00010000).
+    pub version: u32,
+    /// Table format (0).
+    pub format: u16,
+    /// Tracking for horizontal text.
+    pub horizontal: Option<TrackData>,
+    /// Tracking for vertical text.
+    pub vertical: Option<TrackData>,
+}
+
+/// Tracks for one text direction.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TrackData {
+    /// Point sizes the values are given for, in increasing order.
+    pub sizes: Vec<f32>,
+    /// Tracks, each with one value per size.
+    pub tracks: Vec<Track>,
+}
+
+/// One track: the spacing at each size for one tightness.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Track {
+    /// Tightness; 0 is normal, negative tighter and positive looser.
+    pub track: f32,
+    /// `name` table id of the track's name.
+    pub name_id: u16,
+    /// Spacing at each size, in font units.
+    pub values: Vec<i16>,
+}
+
+impl TrackData {
+    /// Returns the spacing of a track at `size` points, in font units,
+    /// interpolating linearly between sizes and extrapolating beyond them.
+    /// Tracks the table lacks have no spacing.
+    pub fn tracking(&self, track: f32, size: f32) -> f32 {
+        let Some(values) = self
+            .tracks
+            .iter()
+            .find(|entry| entry.track == track)
+            .map(|entry| &entry.values)
+        else {
+            return 0.0;
+        };
+        let count = self.sizes.len().min(values.len());
+        match count {
+            0 => 0.0,
+            1 => values[0] as f32,
+            _ => {
+                // The pair of sizes around `size`, or the first or last pair.
+                let upper = self.sizes[..count]
+                    .iter()
+                    .position(|&s| s >= size)
+                    .unwrap_or(count - 1)
+                    .max(1);
+                let (s0, s1) = (self.sizes[upper - 1], self.sizes[upper]);
+                let (v0, v1) = (values[upper - 1] as f32, values[upper] as f32);
+                if s1 == s0 {
+                    return v0;
+                }
+                v0 + (size - s0) / (s1 - s0) * (v1 - v0)
+            }
+        }
+    }
+}
+
+impl TrakTable {
+    /// Returns the normal horizontal tracking at `size` points, in font
+    /// units.
+    pub fn tracking(&self, size: f32) -> f32 {
+        self.horizontal
+            .as_ref()
+            .map_or(0.0, |data| data.tracking(0.0, size))
+    }
+}
+
+/// Extracts the `trak` table from the provided data stream.
+pub fn read_trak(data: &mut FontDataStream) -> Result<TrakTable, Error> {
+    let version = data.read_u32()?;
+    if version >> 16 != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version)));
+    }
+    let format = data.read_u16()?;
+    if format != 0 {
+        return Err(Error::InvalidFormat);
+    }
+    let horizontal = data.read_u16()? as usize;
+    let vertical = data.read_u16()? as usize;
+    let table = data.substream(0)?;
+    let read = |offset: usize| {
+        (offset != 0)
+            .then(|| read_track_data(&table, offset))
+            .transpose()
+    };
+    Ok(TrakTable {
+        version,
+        format,
+        horizontal: read(horizontal)?,
+        vertical: read(vertical)?,
+    })
+}
+
+/// Reads track data at `offset`; all its offsets are measured from the table
+/// start.
+fn read_track_data(table: &FontDataStream, offset: usize) -> Result<TrackData, Error> {
+    let mut data = table.substream(offset)?;
+    let track_count = data.read_u16()?;
+    let size_count = data.read_u16()?;
+    let mut sizes_stream = table.substream(data.read_u32()? as usize)?;
+    let sizes = (0..size_count)
+        .map(|_| sizes_stream.read_fixed())
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut tracks = Vec::with_capacity(track_count as usize);
+    for _ in 0..track_count {
+        let track = data.read_fixed()?;
+        let name_id = data.read_u16()?;
+        let mut values_stream = table.substream(data.read_u16()? as usize)?;
+        let values = (0..size_count)
+            .map(|_| values_stream.read_i16())
+            .collect::<Result<Vec<_>, _>>()?;
+        tracks.push(Track {
+            track,
+            name_id,
+            values,
+        });
+    }
+    Ok(TrackData { sizes, tracks })
+}
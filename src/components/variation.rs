@@ -0,0 +1,659 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Common OpenType font variation structures for the Aurora Font Library.
+//! The item variation store and delta-set index maps are shared by `HVAR`,
+//! `VVAR`, `MVAR`, `GDEF` and `CFF2`, and the tuple variation store is shared
+//! by `gvar` and `cvar`, so they are parsed and serialized here.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+/// The span of a variation region along one axis, in normalized coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RegionAxis {
+    /// Start of the region.
+    pub start: f32,
+    /// Peak of the region, where the scalar is 1.
+    pub peak: f32,
+    /// End of the region.
+    pub end: f32,
+}
+
+impl RegionAxis {
+    /// Creates a region axis from its start, peak and end.
+    pub fn new(start: f32, peak: f32, end: f32) -> Self {
+        RegionAxis { start, peak, end }
+    }
+
+    /// Returns the scalar of this axis at the normalized coordinate `coord`.
+    ///
+    /// Axes with a zero peak or an invalid range do not constrain the region.
+    pub fn scalar(&self, coord: f32) -> f32 {
+        let RegionAxis { start, peak, end } = *self;
+        if start > peak || peak > end || (start < 0.0 && end > 0.0) || peak == 0.0 {
+            return 1.0;
+        }
+        if coord == peak {
+            1.0
+        } else if coord <= start || coord >= end {
+            0.0
+        } else if coord < peak {
+            (coord - start) / (peak - start)
+        } else {
+            (end - coord) / (end - peak)
+        }
+    }
+}
+
+/// A variation region: one `RegionAxis` per `fvar` axis.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VariationRegion {
+    /// Region spans in `fvar` axis order.
+    pub axes: Vec<RegionAxis>,
+}
+
+impl VariationRegion {
+    /// Returns the scalar of the region at normalized coordinates `coords`.
+    /// Missing coordinates are taken as the default (0).
+    pub fn scalar(&self, coords: &[f32]) -> f32 {
+        let mut product = 1.0;
+        for (i, axis) in self.axes.iter().enumerate() {
+            product *= axis.scalar(coords.get(i).copied().unwrap_or(0.0));
+            if product == 0.0 {
+                break;
+            }
+        }
+        product
+    }
+}
+
+/// Delta sets of items sharing the same regions.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ItemVariationData {
+    /// Indices into the store's region list, one per delta column.
+    pub region_indexes: Vec<u16>,
+    /// One row of deltas per item, one delta per region index.
+    pub delta_sets: Vec<Vec<i32>>,
+}
+
+/// Item variation store.
+///
+/// Items are addressed by an outer index selecting the `ItemVariationData`
+/// and an inner index selecting the row.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ItemVariationStore {
+    /// Store format (1).
+    pub format: u16,
+    /// Number of axes of every region.
+    pub axis_count: u16,
+    /// Variation regions referenced by the item variation data.
+    pub regions: Vec<VariationRegion>,
+    /// Item variation data subtables.
+    pub data: Vec<ItemVariationData>,
+}
+
+impl ItemVariationStore {
+    /// Returns the scalar of every region at normalized coordinates `coords`.
+    pub fn region_scalars(&self, coords: &[f32]) -> Vec<f32> {
+        self.regions
+            .iter()
+            .map(|region| region.scalar(coords))
+            .collect()
+    }
+
+    /// Returns the interpolated delta of an item.
+    pub fn delta(&self, outer: u16, inner: u16, coords: &[f32]) -> f32 {
+        self.delta_with_scalars(outer, inner, &self.region_scalars(coords))
+    }
+
+    /// Returns the interpolated delta of an item from precomputed region scalars.
+    pub fn delta_with_scalars(&self, outer: u16, inner: u16, scalars: &[f32]) -> f32 {
+        let Some(data) = self.data.get(outer as usize) else {
+            return 0.0;
+        };
+        let Some(row) = data.delta_sets.get(inner as usize) else {
+            return 0.0;
+        };
+        row.iter()
+            .zip(&data.region_indexes)
+            .map(|(&delta, &region)| {
+                delta as f32 * scalars.get(region as usize).copied().unwrap_or(0.0)
+            })
+            .sum()
+    }
+}
+
+/// Maps glyph ids or other indices to `(outer, inner)` item indices.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeltaSetIndexMap {
+    /// `(outer, inner)` index of each entry.
+    pub entries: Vec<(u16, u16)>,
+}
+
+impl DeltaSetIndexMap {
+    /// Returns the item of `index`; indices past the end use the last entry.
+    pub fn get(&self, index: u32) -> Option<(u16, u16)> {
+        self.entries
+            .get(index as usize)
+            .or(self.entries.last())
+            .copied()
+    }
+}
+
+/// Extracts an item variation store.
+pub fn read_item_variation_store(data: &mut FontDataStream) -> Result<ItemVariationStore, Error> {
+    let format = data.read_u16()?;
+    if format != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(format as u32)));
+    }
+    let region_list_offset = data.read_u32()? as usize;
+    let data_count = data.read_u16()?;
+
+    let mut axis_count = 0;
+    let mut regions = Vec::new();
+    if region_list_offset != 0 {
+        let mut list = data.substream(region_list_offset)?;
+        axis_count = list.read_u16()?;
+        let region_count = list.read_u16()?;
+        for _ in 0..region_count {
+            let axes = (0..axis_count)
+                .map(|_| {
+                    Ok(RegionAxis {
+                        start: list.read_f2dot14()?,
+                        peak: list.read_f2dot14()?,
+                        end: list.read_f2dot14()?,
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            regions.push(VariationRegion { axes });
+        }
+    }
+
+    let mut item_data = Vec::with_capacity(data_count as usize);
+    for _ in 0..data_count {
+        let offset = data.read_u32()? as usize;
+        if offset == 0 {
+            item_data.push(ItemVariationData::default());
+            continue;
+        }
+        let mut subtable = data.substream(offset)?;
+        let item_count = subtable.read_u16()?;
+        let word_delta_count = subtable.read_u16()?;
+        let region_index_count = subtable.read_u16()? as usize;
+        let long_words = word_delta_count & 0x8000 != 0;
+        let word_count = (word_delta_count & 0x7FFF) as usize;
+        let region_indexes = (0..region_index_count)
+            .map(|_| subtable.read_u16())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut delta_sets = Vec::with_capacity(item_count as usize);
+        for _ in 0..item_count {
+            let row = (0..region_index_count)
+                .map(|column| match (long_words, column < word_count) {
+                    (true, true) => subtable.read_i32(),
+                    (true, false) | (false, true) => subtable.read_i16().map(i32::from),
+                    (false, false) => subtable.read_i8().map(i32::from),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            delta_sets.push(row);
+        }
+        item_data.push(ItemVariationData {
+            region_indexes,
+            delta_sets,
+        });
+    }
+    Ok(ItemVariationStore {
+        format,
+        axis_count,
+        regions,
+        data: item_data,
+    })
+}
+
+/// Serializes an item variation store. Delta columns are reordered so that
+/// wide columns come first and each column uses the smallest size.
+pub fn write_item_variation_store(
+    writer: &mut FontDataWriter,
+    store: &ItemVariationStore,
+) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(1);
+    let region_list = writer.reserve_offset32(base);
+    writer.write_u16(store.data.len() as u16);
+    let data_offsets: Vec<_> = store
+        .data
+        .iter()
+        .map(|_| writer.reserve_offset32(base))
+        .collect();
+
+    writer.patch_offset_here(region_list)?;
+    writer.write_u16(store.axis_count);
+    writer.write_u16(store.regions.len() as u16);
+    for region in &store.regions {
+        for axis in &region.axes {
+            writer.write_f2dot14(axis.start);
+            writer.write_f2dot14(axis.peak);
+            writer.write_f2dot14(axis.end);
+        }
+    }
+
+    for (data, placeholder) in store.data.iter().zip(data_offsets) {
+        writer.patch_offset_here(placeholder)?;
+        // 0: byte, 1: word, 2: long.
+        let width = |column: usize| {
+            data.delta_sets
+                .iter()
+                .map(|row| match row[column] {
+                    -128..=127 => 0,
+                    -32768..=32767 => 1,
+                    _ => 2,
+                })
+                .max()
+                .unwrap_or(0)
+        };
+        let mut columns: Vec<(usize, u8)> = (0..data.region_indexes.len())
+            .map(|c| (c, width(c)))
+            .collect();
+        columns.sort_by_key(|&(column, width)| (std::cmp::Reverse(width), column));
+        let long_words = columns.iter().any(|&(_, width)| width == 2);
+        let wide = if long_words { 2 } else { 1 };
+        let word_count = columns.iter().filter(|&&(_, width)| width >= wide).count();
+
+        writer.write_u16(data.delta_sets.len() as u16);
+        writer.write_u16(word_count as u16 | if long_words { 0x8000 } else { 0 });
+        writer.write_u16(columns.len() as u16);
+        for &(column, _) in &columns {
+            writer.write_u16(data.region_indexes[column]);
+        }
+        for row in &data.delta_sets {
+            for (i, &(column, _)) in columns.iter().enumerate() {
+                match (long_words, i < word_count) {
+                    (true, true) => writer.write_i32(row[column]),
+                    (true, false) | (false, true) => writer.write_i16(row[column] as i16),
+                    (false, false) => writer.write_i8(row[column] as i8),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Extracts a delta-set index map (format 0 or 1).
+pub fn read_delta_set_index_map(data: &mut FontDataStream) -> Result<DeltaSetIndexMap, Error> {
+    let format = data.read_u8()?;
+    let entry_format = data.read_u8()?;
+    let map_count = match format {
+        0 => data.read_u16()? as u32,
+        1 => data.read_u32()?,
+        _ => return Err(Error::InvalidFormat),
+    };
+    let inner_bits = (entry_format & 0x0F) as u32 + 1;
+    let entry_size = ((entry_format >> 4) & 0x03) as usize + 1;
+    let entries = (0..map_count)
+        .map(|_| {
+            let mut entry = 0u32;
+            for _ in 0..entry_size {
+                entry = (entry << 8) | data.read_u8()? as u32;
+            }
+            Ok((
+                (entry >> inner_bits) as u16,
+                (entry & ((1 << inner_bits) - 1)) as u16,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(DeltaSetIndexMap { entries })
+}
+
+/// Serializes a delta-set index map with the smallest entry format.
+pub fn write_delta_set_index_map(writer: &mut FontDataWriter, map: &DeltaSetIndexMap) {
+    let max_outer = map
+        .entries
+        .iter()
+        .map(|&(outer, _)| outer)
+        .max()
+        .unwrap_or(0) as u32;
+    let max_inner = map
+        .entries
+        .iter()
+        .map(|&(_, inner)| inner)
+        .max()
+        .unwrap_or(0) as u32;
+    let inner_bits = (32 - max_inner.leading_zeros()).max(1);
+    let total_bits = inner_bits + (32 - max_outer.leading_zeros());
+    let entry_size = total_bits.div_ceil(8).max(1);
+    if map.entries.len() > u16::MAX as usize {
+        writer.write_u8(1);
+        writer.write_u8((((entry_size - 1) << 4) | (inner_bits - 1)) as u8);
+        writer.write_u32(map.entries.len() as u32);
+    } else {
+        writer.write_u8(0);
+        writer.write_u8((((entry_size - 1) << 4) | (inner_bits - 1)) as u8);
+        writer.write_u16(map.entries.len() as u16);
+    }
+    for &(outer, inner) in &map.entries {
+        let entry = ((outer as u32) << inner_bits) | inner as u32;
+        for i in (0..entry_size).rev() {
+            writer.write_u8((entry >> (8 * i)) as u8);
+        }
+    }
+}
+
+/// Tuple index flag: the peak tuple is embedded in the header.
+const EMBEDDED_PEAK_TUPLE: u16 = 0x8000;
+/// Tuple index flag: start and end tuples follow the peak.
+const INTERMEDIATE_REGION: u16 = 0x4000;
+/// Tuple index flag: the serialized data starts with point numbers.
+const PRIVATE_POINT_NUMBERS: u16 = 0x2000;
+/// Tuple index mask: index into the shared tuples.
+const TUPLE_INDEX_MASK: u16 = 0x0FFF;
+/// Tuple variation count flag: shared point numbers precede the tuple data.
+const SHARED_POINT_NUMBERS: u16 = 0x8000;
+/// Tuple variation count mask.
+const COUNT_MASK: u16 = 0x0FFF;
+
+/// A set of deltas of a tuple variation store (`gvar` or `cvar`), applying
+/// within one region of the design space.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TupleVariation {
+    /// Peak of the region, one normalized coordinate per axis.
+    pub peak: Vec<f32>,
+    /// Explicit start and end of the region; otherwise each axis spans from 0
+    /// to its peak.
+    pub intermediate: Option<(Vec<f32>, Vec<f32>)>,
+    /// Numbers of the points with deltas, or `None` for every point.
+    pub point_numbers: Option<Vec<u16>>,
+    /// Deltas per dimension (x and y for `gvar`, one for `cvar`), each with one
+    /// delta per point number.
+    pub deltas: Vec<Vec<i32>>,
+}
+
+impl TupleVariation {
+    /// Returns the span of axis `axis` of the region.
+    pub fn region_axis(&self, axis: usize) -> RegionAxis {
+        let peak = self.peak.get(axis).copied().unwrap_or(0.0);
+        match &self.intermediate {
+            Some((start, end)) => RegionAxis::new(
+                start.get(axis).copied().unwrap_or(0.0),
+                peak,
+                end.get(axis).copied().unwrap_or(0.0),
+            ),
+            None => RegionAxis::new(peak.min(0.0), peak, peak.max(0.0)),
+        }
+    }
+
+    /// Returns the scalar of the tuple at normalized coordinates `coords`.
+    pub fn scalar(&self, coords: &[f32]) -> f32 {
+        let mut product = 1.0;
+        for axis in 0..self.peak.len() {
+            product *= self
+                .region_axis(axis)
+                .scalar(coords.get(axis).copied().unwrap_or(0.0));
+            if product == 0.0 {
+                break;
+            }
+        }
+        product
+    }
+}
+
+fn read_packed_point_numbers(data: &mut FontDataStream) -> Result<Option<Vec<u16>>, Error> {
+    let first = data.read_u8()? as usize;
+    let count = if first & 0x80 != 0 {
+        ((first & 0x7F) << 8) | data.read_u8()? as usize
+    } else {
+        first
+    };
+    if count == 0 {
+        return Ok(None);
+    }
+    let mut points = Vec::with_capacity(count);
+    let mut point = 0u16;
+    while points.len() < count {
+        let control = data.read_u8()?;
+        let run = (control & 0x7F) as usize + 1;
+        for _ in 0..run.min(count - points.len()) {
+            let delta = if control & 0x80 != 0 {
+                data.read_u16()?
+            } else {
+                data.read_u8()? as u16
+            };
+            point = point.wrapping_add(delta);
+            points.push(point);
+        }
+    }
+    Ok(Some(points))
+}
+
+fn write_packed_point_numbers(writer: &mut FontDataWriter, points: Option<&[u16]>) {
+    let points = points.unwrap_or_default();
+    if points.len() < 0x80 {
+        writer.write_u8(points.len() as u8);
+    } else {
+        writer.write_u16(points.len() as u16 | 0x8000);
+    }
+    let mut previous = 0u16;
+    let deltas: Vec<u16> = points
+        .iter()
+        .map(|&point| {
+            let delta = point.wrapping_sub(previous);
+            previous = point;
+            delta
+        })
+        .collect();
+    let mut i = 0;
+    while i < deltas.len() {
+        let words = deltas[i] > 0xFF;
+        let run = deltas[i..]
+            .iter()
+            .take(128)
+            .take_while(|&&delta| (delta > 0xFF) == words)
+            .count();
+        writer.write_u8((run - 1) as u8 | if words { 0x80 } else { 0 });
+        for &delta in &deltas[i..i + run] {
+            if words {
+                writer.write_u16(delta);
+            } else {
+                writer.write_u8(delta as u8);
+            }
+        }
+        i += run;
+    }
+}
+
+/// Reads packed deltas until `count` values are read, or until the end of
+/// the stream when `count` is `None`.
+fn read_packed_deltas(data: &mut FontDataStream, count: Option<usize>) -> Result<Vec<i32>, Error> {
+    let mut deltas = Vec::with_capacity(count.unwrap_or(0));
+    while count.map_or(!data.is_eof(), |count| deltas.len() < count) {
+        let control = data.read_u8()?;
+        let run = (control & 0x3F) as usize + 1;
+        for _ in 0..run {
+            deltas.push(match control & 0xC0 {
+                0x80 => 0,
+                0x40 => data.read_i16()? as i32,
+                0xC0 => data.read_i32()?,
+                _ => data.read_i8()? as i32,
+            });
+        }
+    }
+    Ok(deltas)
+}
+
+fn write_packed_deltas(writer: &mut FontDataWriter, deltas: &[i32]) {
+    // 0: zero, 1: byte, 2: word, 3: long.
+    let class = |delta: i32| match delta {
+        0 => 0,
+        -128..=127 => 1,
+        -32768..=32767 => 2,
+        _ => 3,
+    };
+    let mut i = 0;
+    while i < deltas.len() {
+        let kind = class(deltas[i]);
+        let run = deltas[i..]
+            .iter()
+            .take(64)
+            .take_while(|&&delta| class(delta) == kind)
+            .count();
+        writer.write_u8((run - 1) as u8 | [0x80, 0x00, 0x40, 0xC0][kind]);
+        for &delta in &deltas[i..i + run] {
+            match kind {
+                1 => writer.write_i8(delta as i8),
+                2 => writer.write_i16(delta as i16),
+                3 => writer.write_i32(delta),
+                _ => {}
+            }
+        }
+        i += run;
+    }
+}
+
+/// Extracts the tuple variations of a `gvar` glyph or of `cvar`.
+///
+/// # Arguments
+/// * `data` - Stream positioned at the tuple variation count; offsets are
+///   measured from `data_base`.
+/// * `data_base` - Stream positioned at the start of the structure holding
+///   the serialized data offset.
+/// * `axis_count` - Number of `fvar` axes.
+/// * `shared_tuples` - Peak tuples referenced by index (`gvar` only).
+/// * `dimensions` - Number of deltas per point (2 for `gvar`, 1 for `cvar`).
+pub fn read_tuple_variations(
+    data: &mut FontDataStream,
+    data_base: &FontDataStream,
+    axis_count: usize,
+    shared_tuples: &[Vec<f32>],
+    dimensions: usize,
+) -> Result<Vec<TupleVariation>, Error> {
+    let count_and_flags = data.read_u16()?;
+    let data_offset = data.read_u16()? as usize;
+    let read_tuple = |data: &mut FontDataStream| {
+        (0..axis_count)
+            .map(|_| data.read_f2dot14())
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let mut headers = Vec::new();
+    for _ in 0..count_and_flags & COUNT_MASK {
+        let size = data.read_u16()? as usize;
+        let tuple_index = data.read_u16()?;
+        let peak = if tuple_index & EMBEDDED_PEAK_TUPLE != 0 {
+            read_tuple(data)?
+        } else {
+            shared_tuples
+                .get((tuple_index & TUPLE_INDEX_MASK) as usize)
+                .cloned()
+                .ok_or(Error::Io(IoError::InvalidData))?
+        };
+        let intermediate = if tuple_index & INTERMEDIATE_REGION != 0 {
+            Some((read_tuple(data)?, read_tuple(data)?))
+        } else {
+            None
+        };
+        headers.push((size, tuple_index, peak, intermediate));
+    }
+
+    let mut serialized = data_base.substream(data_offset)?;
+    let shared_points = if count_and_flags & SHARED_POINT_NUMBERS != 0 {
+        read_packed_point_numbers(&mut serialized)?
+    } else {
+        None
+    };
+    let mut tuples = Vec::with_capacity(headers.len());
+    for (size, tuple_index, peak, intermediate) in headers {
+        let bytes = serialized.read_bytes(size)?;
+        let mut tuple_data = FontDataStream::new(bytes);
+        let point_numbers = if tuple_index & PRIVATE_POINT_NUMBERS != 0 {
+            read_packed_point_numbers(&mut tuple_data)?
+        } else {
+            shared_points.clone()
+        };
+        let deltas = match &point_numbers {
+            Some(points) => (0..dimensions)
+                .map(|_| read_packed_deltas(&mut tuple_data, Some(points.len())))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => {
+                // Deltas for every point: the point count is implied by the
+                // data size, each dimension holding the same number of deltas.
+                let all = read_packed_deltas(&mut tuple_data, None)?;
+                let per_dimension = all.len() / dimensions.max(1);
+                all.chunks(per_dimension.max(1))
+                    .take(dimensions)
+                    .map(<[i32]>::to_vec)
+                    .collect()
+            }
+        };
+        tuples.push(TupleVariation {
+            peak,
+            intermediate,
+            point_numbers,
+            deltas,
+        });
+    }
+    Ok(tuples)
+}
+
+/// Serializes tuple variations with private point numbers. Peaks found in
+/// `shared_tuples` are referenced by index, the others are embedded.
+///
+/// The serialized data follows the headers directly; the data offset is
+/// measured from `data_base`, the writer position of the enclosing structure.
+pub fn write_tuple_variations(
+    writer: &mut FontDataWriter,
+    data_base: usize,
+    tuples: &[TupleVariation],
+    shared_tuples: &[Vec<f32>],
+) -> Result<(), Error> {
+    let serialized: Vec<Vec<u8>> = tuples
+        .iter()
+        .map(|tuple| {
+            let mut data = FontDataWriter::new();
+            write_packed_point_numbers(&mut data, tuple.point_numbers.as_deref());
+            for deltas in &tuple.deltas {
+                write_packed_deltas(&mut data, deltas);
+            }
+            data.as_bytes().to_vec()
+        })
+        .collect();
+
+    writer.write_u16(tuples.len() as u16);
+    let data_offset = writer.reserve_offset16(data_base);
+    for (tuple, data) in tuples.iter().zip(&serialized) {
+        let size = u16::try_from(data.len()).map_err(|_| {
+            Error::Io(IoError::OffsetOverflow {
+                position: writer.position(),
+                offset: data.len(),
+            })
+        })?;
+        writer.write_u16(size);
+        let shared = shared_tuples.iter().position(|peak| peak == &tuple.peak);
+        let mut tuple_index = PRIVATE_POINT_NUMBERS
+            | match shared {
+                Some(index) => index as u16,
+                None => EMBEDDED_PEAK_TUPLE,
+            };
+        if tuple.intermediate.is_some() {
+            tuple_index |= INTERMEDIATE_REGION;
+        }
+        writer.write_u16(tuple_index);
+        if shared.is_none() {
+            for &coord in &tuple.peak {
+                writer.write_f2dot14(coord);
+            }
+        }
+        if let Some((start, end)) = &tuple.intermediate {
+            for &coord in start.iter().chain(end) {
+                writer.write_f2dot14(coord);
+            }
+        }
+    }
+    writer.patch_offset_here(data_offset)?;
+    for data in &serialized {
+        writer.write_bytes(data);
+    }
+    Ok(())
+}
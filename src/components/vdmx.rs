@@ -0,0 +1,155 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `VDMX` (Vertical Device Metrics) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+
+/// Hinted vertical extremes of the font at one size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VdmxEntry {
+    /// Pixels per em the extremes were computed for.
+    pub y_pel_height: u16,
+    /// Highest pixel row reached by any glyph.
+    pub y_max: i16,
+    /// Lowest pixel row reached by any glyph.
+    pub y_min: i16,
+}
+
+/// Vertical extremes for a range of sizes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct VdmxGroup {
+    /// Smallest size of the group.
+    pub start_size: u8,
+    /// Largest size of the group.
+    pub end_size: u8,
+    /// Entries sorted by size.
+    pub entries: Vec<VdmxEntry>,
+}
+
+/// A range of device aspect ratios and the group describing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VdmxRatio {
+    /// Character set (version 0: 0 for all glyphs, 1 for Windows ANSI).
+    pub char_set: u8,
+    /// Horizontal part of the ratio; 0 with the other parts 0 matches any
+    /// device.
+    pub x_ratio: u8,
+    /// Smallest vertical part of the ratio.
+    pub y_start_ratio: u8,
+    /// Largest vertical part of the ratio.
+    pub y_end_ratio: u8,
+    /// Index of the group in `VdmxTable::groups`.
+    pub group_index: usize,
+}
+
+impl VdmxRatio {
+    /// Returns whether a device with pixels `x_resolution` wide and
+    /// `y_resolution` tall falls in the range.
+    pub fn matches(&self, x_resolution: u16, y_resolution: u16) -> bool {
+        if self.x_ratio == 0 {
+            return self.y_start_ratio == 0 && self.y_end_ratio == 0;
+        }
+        // y / x scaled to x_ratio, compared without division.
+        let y = y_resolution as u32 * self.x_ratio as u32;
+        let x = x_resolution as u32;
+        self.y_start_ratio as u32 * x <= y && y <= self.y_end_ratio as u32 * x
+    }
+}
+
+/// Vertical device metrics table.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VdmxTable {
+    /// Table version (0 or 1).
+    pub version: u16,
+    /// Ratio ranges in priority order.
+    pub ratios: Vec<VdmxRatio>,
+    /// Distinct groups referenced by the ratios.
+    pub groups: Vec<VdmxGroup>,
+}
+
+impl VdmxTable {
+    /// Returns the hinted `(y_max, y_min)` at `ppem` from the first ratio
+    /// range matching the device aspect ratio.
+    pub fn bounds(&self, ppem: u16, x_resolution: u16, y_resolution: u16) -> Option<(i16, i16)> {
+        let ratio = self
+            .ratios
+            .iter()
+            .find(|ratio| ratio.matches(x_resolution, y_resolution))?;
+        let group = self.groups.get(ratio.group_index)?;
+        group
+            .entries
+            .iter()
+            .find(|entry| entry.y_pel_height == ppem)
+            .map(|entry| (entry.y_max, entry.y_min))
+    }
+}
+
+/// Extracts the `VDMX` table from the provided data stream.
+pub fn read_vdmx(data: &mut FontDataStream) -> Result<VdmxTable, Error> {
+    let version = data.read_u16()?;
+    if version > 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(version as u32)));
+    }
+    let _num_groups = data.read_u16()?;
+    let num_ratios = data.read_u16()?;
+    let mut ranges = Vec::with_capacity(num_ratios as usize);
+    for _ in 0..num_ratios {
+        ranges.push((
+            data.read_u8()?,
+            data.read_u8()?,
+            data.read_u8()?,
+            data.read_u8()?,
+        ));
+    }
+    let mut ratios = Vec::with_capacity(num_ratios as usize);
+    let mut groups = Vec::new();
+    // Groups shared by several ratios are read once.
+    let mut group_offsets: Vec<u16> = Vec::new();
+    for (char_set, x_ratio, y_start_ratio, y_end_ratio) in ranges {
+        let offset = data.read_u16()?;
+        let group_index = match group_offsets.iter().position(|&o| o == offset) {
+            Some(index) => index,
+            None => {
+                groups.push(read_group(&mut data.substream(offset as usize)?)?);
+                group_offsets.push(offset);
+                groups.len() - 1
+            }
+        };
+        ratios.push(VdmxRatio {
+            char_set,
+            x_ratio,
+            y_start_ratio,
+            y_end_ratio,
+            group_index,
+        });
+    }
+    Ok(VdmxTable {
+        version,
+        ratios,
+        groups,
+    })
+}
+
+fn read_group(data: &mut FontDataStream) -> Result<VdmxGroup, Error> {
+    let num_entries = data.read_u16()?;
+    let start_size = data.read_u8()?;
+    let end_size = data.read_u8()?;
+    let mut entries = Vec::with_capacity(num_entries as usize);
+    for _ in 0..num_entries {
+        entries.push(VdmxEntry {
+            y_pel_height: data.read_u16()?,
+            y_max: data.read_i16()?,
+            y_min: data.read_i16()?,
+        });
+    }
+    Ok(VdmxGroup {
+        start_size,
+        end_size,
+        entries,
+    })
+}
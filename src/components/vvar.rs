@@ -0,0 +1,117 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `VVAR` (Vertical Metrics Variations) table representation and utilities for the Aurora Font Library.
+
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::layout::read_optional_at;
+use super::variation::{
+    DeltaSetIndexMap, ItemVariationStore, read_delta_set_index_map, read_item_variation_store,
+    write_delta_set_index_map, write_item_variation_store,
+};
+
+/// Vertical metrics variations table.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VvarTable {
+    /// Major version (1).
+    pub major_version: u16,
+    /// Minor version (0).
+    pub minor_version: u16,
+    /// Deltas of the metrics.
+    pub store: ItemVariationStore,
+    /// Advance height items by glyph id; without it the item of glyph `g` is `(0, g)`.
+    pub advance_map: Option<DeltaSetIndexMap>,
+    /// Top side bearing items by glyph id.
+    pub tsb_map: Option<DeltaSetIndexMap>,
+    /// Bottom side bearing items by glyph id.
+    pub bsb_map: Option<DeltaSetIndexMap>,
+    /// Vertical origin (`VORG`) items by glyph id.
+    pub vorg_map: Option<DeltaSetIndexMap>,
+}
+
+impl VvarTable {
+    /// Returns the advance height delta of a glyph at normalized coordinates `coords`.
+    pub fn advance_delta(&self, glyph_id: u16, coords: &[f32]) -> f32 {
+        let (outer, inner) = match &self.advance_map {
+            Some(map) => map.get(glyph_id as u32).unwrap_or((0, glyph_id)),
+            None => (0, glyph_id),
+        };
+        self.store.delta(outer, inner, coords)
+    }
+
+    /// Returns the top side bearing delta of a glyph, if the table has one.
+    pub fn tsb_delta(&self, glyph_id: u16, coords: &[f32]) -> Option<f32> {
+        let (outer, inner) = self.tsb_map.as_ref()?.get(glyph_id as u32)?;
+        Some(self.store.delta(outer, inner, coords))
+    }
+
+    /// Returns the bottom side bearing delta of a glyph, if the table has one.
+    pub fn bsb_delta(&self, glyph_id: u16, coords: &[f32]) -> Option<f32> {
+        let (outer, inner) = self.bsb_map.as_ref()?.get(glyph_id as u32)?;
+        Some(self.store.delta(outer, inner, coords))
+    }
+
+    /// Returns the vertical origin delta of a glyph, if the table has one.
+    pub fn vorg_delta(&self, glyph_id: u16, coords: &[f32]) -> Option<f32> {
+        let (outer, inner) = self.vorg_map.as_ref()?.get(glyph_id as u32)?;
+        Some(self.store.delta(outer, inner, coords))
+    }
+}
+
+/// Extracts the `VVAR` table from the provided data stream.
+pub fn read_vvar(data: &mut FontDataStream) -> Result<VvarTable, Error> {
+    let major_version = data.read_u16()?;
+    let minor_version = data.read_u16()?;
+    if major_version != 1 {
+        return Err(Error::Io(IoError::UnsupportedVersion(major_version as u32)));
+    }
+    let store_offset = data.read_u32()? as usize;
+    let advance_offset = data.read_u32()? as usize;
+    let tsb_offset = data.read_u32()? as usize;
+    let bsb_offset = data.read_u32()? as usize;
+    let vorg_offset = data.read_u32()? as usize;
+    Ok(VvarTable {
+        major_version,
+        minor_version,
+        store: read_optional_at(data, store_offset, read_item_variation_store)?.unwrap_or_default(),
+        advance_map: read_optional_at(data, advance_offset, read_delta_set_index_map)?,
+        tsb_map: read_optional_at(data, tsb_offset, read_delta_set_index_map)?,
+        bsb_map: read_optional_at(data, bsb_offset, read_delta_set_index_map)?,
+        vorg_map: read_optional_at(data, vorg_offset, read_delta_set_index_map)?,
+    })
+}
+
+/// Serializes the `VVAR` table.
+pub fn write_vvar(writer: &mut FontDataWriter, vvar: &VvarTable) -> Result<(), Error> {
+    let base = writer.position();
+    writer.write_u16(vvar.major_version);
+    writer.write_u16(vvar.minor_version);
+    let store = writer.reserve_offset32(base);
+    let mut maps = Vec::new();
+    for map in [
+        &vvar.advance_map,
+        &vvar.tsb_map,
+        &vvar.bsb_map,
+        &vvar.vorg_map,
+    ] {
+        match map {
+            Some(map) => maps.push((writer.reserve_offset32(base), map)),
+            None => writer.write_u32(0),
+        }
+    }
+    let mut bytes = FontDataWriter::new();
+    write_item_variation_store(&mut bytes, &vvar.store)?;
+    writer.defer_subtable(store, bytes.into_bytes()?);
+    for (placeholder, map) in maps {
+        let mut bytes = FontDataWriter::new();
+        write_delta_set_index_map(&mut bytes, map);
+        writer.defer_subtable(placeholder, bytes.into_bytes()?);
+    }
+    Ok(())
+}
@@ -0,0 +1,455 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Brotli (RFC 7932) compression for the Aurora Font Library.
+//!
+//! This is a basic-quality encoder: hash-chain LZ77 matching over a 4 MiB
+//! window, one block type per category, a single literal and distance prefix
+//! code per meta-block and no static dictionary references. Meta-blocks that
+//! would not shrink are stored uncompressed.
+
+use super::deflate::{BitWriter, MatchFinder, NICE_MATCH, canonical_codes, huffman_lengths};
+
+/// Window size exponent written to the stream header.
+const WBITS: u32 = 22;
+/// Largest backward distance allowed by the window.
+const MAX_DISTANCE: usize = (1 << WBITS) - 16;
+/// Shortest match worth emitting as a copy.
+const MIN_COPY: usize = 4;
+/// Longest match searched for.
+const MAX_COPY: usize = 1024;
+/// Maximum number of hash chain entries inspected per position.
+const MAX_CHAIN: usize = 32;
+/// Maximum number of input bytes covered by one meta-block.
+const META_BLOCK_SIZE: usize = 1 << 20;
+
+/// Size of the literal alphabet.
+const LITERAL_ALPHABET: usize = 256;
+/// Size of the insert-and-copy length alphabet.
+const COMMAND_ALPHABET: usize = 704;
+/// Size of the distance alphabet with NPOSTFIX = 0 and NDIRECT = 0.
+const DISTANCE_ALPHABET: usize = 64;
+
+/// `(extra_bits, base)` for insert length codes 0..=23.
+const INSERT_CODES: [(u32, u32); 24] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (0, 5),
+    (1, 6),
+    (1, 8),
+    (2, 10),
+    (2, 14),
+    (3, 18),
+    (3, 26),
+    (4, 34),
+    (4, 50),
+    (5, 66),
+    (5, 98),
+    (6, 130),
+    (7, 194),
+    (8, 322),
+    (9, 578),
+    (10, 1090),
+    (12, 2114),
+    (14, 6210),
+    (24, 22594),
+];
+/// `(extra_bits, base)` for copy length codes 0..=23.
+const COPY_CODES: [(u32, u32); 24] = [
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (0, 5),
+    (0, 6),
+    (0, 7),
+    (0, 8),
+    (0, 9),
+    (1, 10),
+    (1, 12),
+    (2, 14),
+    (2, 18),
+    (3, 22),
+    (3, 30),
+    (4, 38),
+    (4, 54),
+    (5, 70),
+    (5, 102),
+    (6, 134),
+    (7, 198),
+    (8, 326),
+    (9, 582),
+    (10, 1094),
+    (24, 2118),
+];
+/// Order in which code length code lengths are transmitted.
+const CODE_LENGTH_ORDER: [usize; 18] =
+    [1, 2, 3, 4, 0, 5, 17, 6, 16, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+/// Compresses `data` into a Brotli stream.
+pub fn brotli_compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    // WBITS in 18..=24 is written as a set bit followed by WBITS - 17 in 3 bits.
+    writer.write_bits(1, 1);
+    writer.write_bits(WBITS - 17, 3);
+
+    let commands = find_commands(data);
+    let mut start = 0usize;
+    let mut consumed = 0usize;
+    while start < commands.len() {
+        let mut end = start;
+        let mut len = 0usize;
+        while end < commands.len() && (end == start || len + commands[end].len() <= META_BLOCK_SIZE)
+        {
+            len += commands[end].len();
+            end += 1;
+            if commands[end - 1].copy_len == 0 {
+                // A command without a copy can only end a meta-block.
+                break;
+            }
+        }
+        write_meta_block(
+            &mut writer,
+            &commands[start..end],
+            &data[consumed..consumed + len],
+        );
+        consumed += len;
+        start = end;
+    }
+
+    // ISLAST, ISLASTEMPTY.
+    writer.write_bits(1, 1);
+    writer.write_bits(1, 1);
+    writer.finish()
+}
+
+/// An insert-and-copy command: `insert` literals followed by a backward copy.
+#[derive(Debug, Clone)]
+struct Command {
+    literals: std::ops::Range<usize>,
+    copy_len: usize,
+    distance: usize,
+}
+
+impl Command {
+    /// Number of output bytes the command produces.
+    fn len(&self) -> usize {
+        self.literals.len() + self.copy_len
+    }
+}
+
+/// Runs LZ77 over `data` and groups the result into Brotli commands.
+fn find_commands(data: &[u8]) -> Vec<Command> {
+    let mut finder = MatchFinder::new(data.len(), MAX_DISTANCE, MAX_COPY, MAX_CHAIN);
+    let mut commands = Vec::new();
+    let mut literal_start = 0usize;
+    let mut pos = 0usize;
+    while pos < data.len() {
+        let current = finder.longest_match(data, pos).filter(|m| m.0 >= MIN_COPY);
+        finder.insert(data, pos);
+        match current {
+            Some((len, distance)) => {
+                if len < NICE_MATCH
+                    && finder
+                        .longest_match(data, pos + 1)
+                        .is_some_and(|n| n.0 > len)
+                {
+                    pos += 1;
+                    continue;
+                }
+                commands.push(Command {
+                    literals: literal_start..pos,
+                    copy_len: len,
+                    distance,
+                });
+                for p in pos + 1..pos + len {
+                    finder.insert(data, p);
+                }
+                pos += len;
+                literal_start = pos;
+            }
+            None => pos += 1,
+        }
+        if pos - literal_start >= META_BLOCK_SIZE {
+            commands.push(Command {
+                literals: literal_start..pos,
+                copy_len: 0,
+                distance: 0,
+            });
+            literal_start = pos;
+        }
+    }
+    if literal_start < data.len() {
+        commands.push(Command {
+            literals: literal_start..data.len(),
+            copy_len: 0,
+            distance: 0,
+        });
+    }
+    commands
+}
+
+/// Returns `(code, extra_bits, extra_value)` for an insert length.
+fn insert_code(len: usize) -> (usize, u32, u32) {
+    let len = len as u32;
+    let code = INSERT_CODES
+        .iter()
+        .rposition(|&(_, base)| base <= len)
+        .unwrap_or(0);
+    (code, INSERT_CODES[code].0, len - INSERT_CODES[code].1)
+}
+
+/// Returns `(code, extra_bits, extra_value)` for a copy length.
+fn copy_code(len: usize) -> (usize, u32, u32) {
+    let len = len.max(2) as u32;
+    let code = COPY_CODES
+        .iter()
+        .rposition(|&(_, base)| base <= len)
+        .unwrap_or(0);
+    (code, COPY_CODES[code].0, len - COPY_CODES[code].1)
+}
+
+/// Combines insert and copy length codes into an insert-and-copy symbol that
+/// is followed by an explicit distance code.
+fn command_symbol(insert: usize, copy: usize) -> usize {
+    let cell = match (insert >> 3, copy >> 3) {
+        (0, 0) => 128,
+        (0, 1) => 192,
+        (1, 0) => 256,
+        (1, 1) => 320,
+        (0, 2) => 384,
+        (2, 0) => 448,
+        (1, 2) => 512,
+        (2, 1) => 576,
+        _ => 640,
+    };
+    cell + ((insert & 7) << 3) + (copy & 7)
+}
+
+/// Returns `(code, extra_bits, extra_value)` for a backward distance, using
+/// NPOSTFIX = 0 and NDIRECT = 0.
+fn distance_code(distance: usize) -> (usize, u32, u32) {
+    let value = distance as u32 + 3;
+    let nbits = 31 - value.leading_zeros();
+    let extra_bits = nbits - 1;
+    let lcode = (value >> extra_bits) & 1;
+    let code = 16 + 2 * (extra_bits as usize - 1) + lcode as usize;
+    (code, extra_bits, value & ((1 << extra_bits) - 1))
+}
+
+/// A prefix code ready to be written: code lengths and canonical codes.
+struct PrefixCode {
+    lengths: Vec<u8>,
+    codes: Vec<u16>,
+    symbols: Vec<usize>,
+}
+
+impl PrefixCode {
+    /// Builds a prefix code from symbol frequencies.
+    fn new(freqs: &[u32]) -> Self {
+        let symbols: Vec<usize> = (0..freqs.len()).filter(|&i| freqs[i] > 0).collect();
+        let mut lengths = huffman_lengths(freqs, 15);
+        if symbols.len() == 1 {
+            // A single-symbol simple prefix code is decoded with zero bits.
+            lengths[symbols[0]] = 0;
+        }
+        let codes = canonical_codes(&lengths);
+        PrefixCode {
+            lengths,
+            codes,
+            symbols,
+        }
+    }
+
+    /// Writes a symbol.
+    fn write(&self, writer: &mut BitWriter, symbol: usize) {
+        if self.lengths[symbol] > 0 {
+            writer.write_code(self.codes[symbol], self.lengths[symbol]);
+        }
+    }
+
+    /// Writes the code description for an alphabet of `alphabet_size` symbols.
+    fn write_description(&self, writer: &mut BitWriter, alphabet_size: usize) {
+        match self.symbols.len() {
+            0 | 1 => {
+                // Simple prefix code (HSKIP = 1) with a single symbol.
+                let alphabet_bits = usize::BITS - (alphabet_size - 1).leading_zeros();
+                writer.write_bits(1, 2);
+                writer.write_bits(0, 2);
+                writer.write_bits(
+                    self.symbols.first().copied().unwrap_or(0) as u32,
+                    alphabet_bits,
+                );
+            }
+            _ => write_complex_code(writer, &self.lengths),
+        }
+    }
+}
+
+/// Writes a complex prefix code description for the given code lengths.
+fn write_complex_code(writer: &mut BitWriter, lengths: &[u8]) {
+    let last = lengths.iter().rposition(|&l| l > 0).unwrap_or(0);
+    let rle = run_length_encode(&lengths[..=last]);
+
+    let mut cl_freq = [0u32; 18];
+    for &(symbol, _) in &rle {
+        cl_freq[symbol as usize] += 1;
+    }
+    let mut cl_lengths = huffman_lengths(&cl_freq, 5);
+    let used = cl_lengths.iter().filter(|&&l| l > 0).count();
+    if used == 1 {
+        // A lone code length symbol is decoded with zero bits.
+        cl_lengths.iter_mut().for_each(|l| *l = 0);
+        cl_lengths[rle[0].0 as usize] = 1;
+    }
+    let cl_codes = canonical_codes(&cl_lengths);
+
+    // HSKIP = 0, then code length code lengths until the code is complete.
+    writer.write_bits(0, 2);
+    let mut space = 32i32;
+    for &symbol in &CODE_LENGTH_ORDER {
+        let len = cl_lengths[symbol];
+        match len {
+            0 => writer.write_bits(0, 2),
+            1 => writer.write_bits(7, 4),
+            2 => writer.write_bits(3, 3),
+            3 => writer.write_bits(2, 2),
+            4 => writer.write_bits(1, 2),
+            _ => writer.write_bits(15, 4),
+        }
+        if len > 0 {
+            space -= 32 >> len;
+            if space <= 0 {
+                break;
+            }
+        }
+    }
+
+    for &(symbol, extra) in &rle {
+        if used > 1 {
+            writer.write_code(cl_codes[symbol as usize], cl_lengths[symbol as usize]);
+        }
+        match symbol {
+            16 => writer.write_bits(extra as u32, 2),
+            17 => writer.write_bits(extra as u32, 3),
+            _ => {}
+        }
+    }
+}
+
+/// Run-length encodes symbol code lengths with repeat codes 16 and 17.
+///
+/// Consecutive repeat codes of the same kind have a multiplicative meaning in
+/// Brotli, so runs are split with a literal length between repeat codes.
+fn run_length_encode(lengths: &[u8]) -> Vec<(u8, u8)> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < lengths.len() {
+        let value = lengths[i];
+        let run = lengths[i..].iter().take_while(|&&l| l == value).count();
+        i += run;
+        let mut remaining = run;
+        if value != 0 {
+            out.push((value, 0));
+            remaining -= 1;
+        }
+        let (code, max) = if value == 0 { (17, 10) } else { (16, 6) };
+        while remaining > 0 {
+            if remaining >= 3 {
+                let n = remaining.min(max);
+                out.push((code, (n - 3) as u8));
+                remaining -= n;
+                if remaining > 0 {
+                    out.push((value, 0));
+                    remaining -= 1;
+                }
+            } else {
+                out.extend(std::iter::repeat_n((value, 0), remaining));
+                remaining = 0;
+            }
+        }
+    }
+    out
+}
+
+/// Writes the meta-block header fields `MNIBBLES` and `MLEN - 1`.
+fn write_meta_block_length(writer: &mut BitWriter, len: usize) {
+    let value = len as u32 - 1;
+    let bits = 32 - value.leading_zeros();
+    let nibbles = u32::max(4, bits.div_ceil(4));
+    writer.write_bits(nibbles - 4, 2);
+    writer.write_bits(value, nibbles * 4);
+}
+
+/// Encodes one meta-block covering `commands`, whose output is `raw`.
+fn write_meta_block(writer: &mut BitWriter, commands: &[Command], raw: &[u8]) {
+    let mut literal_freq = vec![0u32; LITERAL_ALPHABET];
+    let mut command_freq = vec![0u32; COMMAND_ALPHABET];
+    let mut distance_freq = vec![0u32; DISTANCE_ALPHABET];
+    let offset = commands[0].literals.start;
+    for command in commands {
+        for &b in &raw[command.literals.start - offset..command.literals.end - offset] {
+            literal_freq[b as usize] += 1;
+        }
+        let symbol = command_symbol(
+            insert_code(command.literals.len()).0,
+            copy_code(command.copy_len).0,
+        );
+        command_freq[symbol] += 1;
+        if command.copy_len > 0 {
+            distance_freq[distance_code(command.distance).0] += 1;
+        }
+    }
+    let literal_code = PrefixCode::new(&literal_freq);
+    let command_code = PrefixCode::new(&command_freq);
+    let distance_code_table = PrefixCode::new(&distance_freq);
+
+    let mut body = BitWriter::default();
+    literal_code.write_description(&mut body, LITERAL_ALPHABET);
+    command_code.write_description(&mut body, COMMAND_ALPHABET);
+    distance_code_table.write_description(&mut body, DISTANCE_ALPHABET);
+    for command in commands {
+        let (ic, ix, iv) = insert_code(command.literals.len());
+        let (cc, cx, cv) = copy_code(command.copy_len);
+        let symbol = command_symbol(ic, cc);
+        command_code.write(&mut body, symbol);
+        body.write_bits(iv, ix);
+        body.write_bits(cv, cx);
+        for &b in &raw[command.literals.start - offset..command.literals.end - offset] {
+            literal_code.write(&mut body, b as usize);
+        }
+        if command.copy_len > 0 {
+            let (dc, dx, dv) = distance_code(command.distance);
+            distance_code_table.write(&mut body, dc);
+            body.write_bits(dv, dx);
+        }
+    }
+
+    // ISLAST = 0, then the length; the caller terminates the stream separately.
+    writer.write_bits(0, 1);
+    write_meta_block_length(writer, raw.len());
+    if body.bit_len() > raw.len() * 8 {
+        // ISUNCOMPRESSED = 1, pad to a byte boundary, then the raw bytes.
+        writer.write_bits(1, 1);
+        writer.align_to_byte();
+        writer.write_bytes(raw);
+        return;
+    }
+    writer.write_bits(0, 1);
+    // NBLTYPESL, NBLTYPESI, NBLTYPESD = 1; NPOSTFIX = 0; NDIRECT = 0.
+    writer.write_bits(0, 1);
+    writer.write_bits(0, 1);
+    writer.write_bits(0, 1);
+    writer.write_bits(0, 2);
+    writer.write_bits(0, 4);
+    // Context mode for the literal block type (LSB6), NTREESL = 1, NTREESD = 1.
+    writer.write_bits(0, 2);
+    writer.write_bits(0, 1);
+    writer.write_bits(0, 1);
+    writer.append(body);
+}
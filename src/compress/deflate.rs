@@ -0,0 +1,567 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! DEFLATE (RFC 1951) and zlib (RFC 1950) compression for the Aurora Font Library.
+//!
+//! The encoder uses hash-chain LZ77 matching with one step of lazy evaluation
+//! and emits dynamic Huffman blocks, falling back to stored blocks whenever a
+//! block would not shrink. It favours simplicity over the last few percent of
+//! compression ratio.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Size of the LZ77 sliding window.
+const WINDOW_SIZE: usize = 32 * 1024;
+/// Shortest match DEFLATE can encode.
+const MIN_MATCH: usize = 3;
+/// Longest match DEFLATE can encode.
+const MAX_MATCH: usize = 258;
+/// Number of hash buckets used by the match finder.
+const HASH_SIZE: usize = 1 << 15;
+/// Maximum number of chain entries inspected per position.
+const MAX_CHAIN: usize = 128;
+/// Match length considered good enough to stop searching the hash chain.
+pub(crate) const NICE_MATCH: usize = 128;
+/// Maximum number of LZ77 symbols per block.
+const BLOCK_SYMBOLS: usize = 1 << 14;
+
+/// Base lengths for length codes 257..=285.
+pub(crate) const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+/// Extra bits for length codes 257..=285.
+pub(crate) const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// Base distances for distance codes 0..=29.
+pub(crate) const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits for distance codes 0..=29.
+pub(crate) const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Transmission order of the code length code lengths.
+pub(crate) const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Compresses `data` into a raw DEFLATE stream.
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    let symbols = find_matches(data);
+
+    if symbols.is_empty() {
+        // A single empty fixed-Huffman block: BFINAL, BTYPE=01, end-of-block.
+        writer.write_bits(1, 1);
+        writer.write_bits(1, 2);
+        writer.write_bits(0, 7);
+        return writer.finish();
+    }
+
+    let mut consumed = 0usize;
+    let blocks: Vec<&[Symbol]> = symbols.chunks(BLOCK_SYMBOLS).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i + 1 == blocks.len();
+        let raw_len: usize = block.iter().map(|s| s.input_len()).sum();
+        let raw = &data[consumed..consumed + raw_len];
+        consumed += raw_len;
+        write_block(&mut writer, block, raw, last);
+    }
+    writer.finish()
+}
+
+/// Compresses `data` into a zlib stream (DEFLATE with zlib header and Adler-32 trailer).
+pub fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    // CMF: deflate, 32K window. FLG: default level, check bits make the header a multiple of 31.
+    let mut out = vec![0x78, 0x9C];
+    out.extend_from_slice(&deflate(data));
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Computes the Adler-32 checksum of `data`.
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 is the largest block for which `b` cannot overflow before reduction.
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+/// An LZ77 symbol: a literal byte or a back-reference.
+#[derive(Debug, Clone, Copy)]
+enum Symbol {
+    Literal(u8),
+    Match { length: u16, distance: u16 },
+}
+
+impl Symbol {
+    /// Number of input bytes the symbol covers.
+    fn input_len(&self) -> usize {
+        match self {
+            Symbol::Literal(_) => 1,
+            Symbol::Match { length, .. } => *length as usize,
+        }
+    }
+}
+
+/// Hashes the three bytes starting at `pos`.
+fn hash3(data: &[u8], pos: usize) -> usize {
+    let v = ((data[pos] as u32) << 16) | ((data[pos + 1] as u32) << 8) | data[pos + 2] as u32;
+    (v.wrapping_mul(0x9E37_79B1) >> 17) as usize & (HASH_SIZE - 1)
+}
+
+/// Hash-chain match finder over the whole input.
+///
+/// Shared by the DEFLATE and Brotli encoders, which differ only in window size
+/// and match length limits.
+pub(crate) struct MatchFinder {
+    head: Vec<usize>,
+    prev: Vec<usize>,
+    window: usize,
+    max_match: usize,
+    max_chain: usize,
+}
+
+impl MatchFinder {
+    /// Creates a match finder for an input of `len` bytes.
+    ///
+    /// At most `max_chain` earlier positions are inspected per lookup.
+    pub(crate) fn new(len: usize, window: usize, max_match: usize, max_chain: usize) -> Self {
+        MatchFinder {
+            head: vec![usize::MAX; HASH_SIZE],
+            prev: vec![usize::MAX; len],
+            window,
+            max_match,
+            max_chain,
+        }
+    }
+
+    /// Records `pos` in the hash chains.
+    pub(crate) fn insert(&mut self, data: &[u8], pos: usize) {
+        if pos + MIN_MATCH <= data.len() {
+            let h = hash3(data, pos);
+            self.prev[pos] = self.head[h];
+            self.head[h] = pos;
+        }
+    }
+
+    /// Returns the longest match `(length, distance)` for `pos`, if any.
+    pub(crate) fn longest_match(&self, data: &[u8], pos: usize) -> Option<(usize, usize)> {
+        if pos + MIN_MATCH > data.len() {
+            return None;
+        }
+        let max_len = usize::min(self.max_match, data.len() - pos);
+        let mut candidate = self.head[hash3(data, pos)];
+        let mut best: Option<(usize, usize)> = None;
+        let mut chain = 0;
+        while candidate != usize::MAX && chain < self.max_chain {
+            if candidate >= pos {
+                candidate = self.prev[candidate];
+                continue;
+            }
+            let distance = pos - candidate;
+            if distance > self.window {
+                break;
+            }
+            let best_len = best.map_or(MIN_MATCH - 1, |b| b.0);
+            if data[candidate + best_len] == data[pos + best_len] {
+                let len = data[candidate..]
+                    .iter()
+                    .zip(&data[pos..pos + max_len])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if len > best_len {
+                    best = Some((len, distance));
+                    if len >= max_len.min(NICE_MATCH) {
+                        break;
+                    }
+                }
+            }
+            candidate = self.prev[candidate];
+            chain += 1;
+        }
+        best.filter(|b| b.0 >= MIN_MATCH)
+    }
+}
+
+/// Runs LZ77 over `data` with one step of lazy matching.
+fn find_matches(data: &[u8]) -> Vec<Symbol> {
+    let mut finder = MatchFinder::new(data.len(), WINDOW_SIZE, MAX_MATCH, MAX_CHAIN);
+    let mut symbols = Vec::with_capacity(data.len() / 2);
+    let mut pos = 0usize;
+    while pos < data.len() {
+        let current = finder.longest_match(data, pos);
+        finder.insert(data, pos);
+        match current {
+            Some((len, dist)) => {
+                // Lazy evaluation: prefer a literal if the next position matches longer.
+                if len < NICE_MATCH
+                    && finder
+                        .longest_match(data, pos + 1)
+                        .is_some_and(|n| n.0 > len)
+                {
+                    symbols.push(Symbol::Literal(data[pos]));
+                    pos += 1;
+                    continue;
+                }
+                symbols.push(Symbol::Match {
+                    length: len as u16,
+                    distance: dist as u16,
+                });
+                for p in pos + 1..pos + len {
+                    finder.insert(data, p);
+                }
+                pos += len;
+            }
+            None => {
+                symbols.push(Symbol::Literal(data[pos]));
+                pos += 1;
+            }
+        }
+    }
+    symbols
+}
+
+/// Returns `(code, extra_bits, extra_value)` for a match length.
+fn length_code(length: u16) -> (usize, u8, u16) {
+    let idx = LENGTH_BASE.iter().rposition(|&b| b <= length).unwrap_or(0);
+    (257 + idx, LENGTH_EXTRA[idx], length - LENGTH_BASE[idx])
+}
+
+/// Returns `(code, extra_bits, extra_value)` for a match distance.
+fn distance_code(distance: u16) -> (usize, u8, u16) {
+    let idx = DIST_BASE.iter().rposition(|&b| b <= distance).unwrap_or(0);
+    (idx, DIST_EXTRA[idx], distance - DIST_BASE[idx])
+}
+
+/// Encodes one block, choosing between a dynamic Huffman and a stored block.
+fn write_block(writer: &mut BitWriter, symbols: &[Symbol], raw: &[u8], last: bool) {
+    let mut lit_freq = [0u32; 286];
+    let mut dist_freq = [0u32; 30];
+    for symbol in symbols {
+        match *symbol {
+            Symbol::Literal(b) => lit_freq[b as usize] += 1,
+            Symbol::Match { length, distance } => {
+                lit_freq[length_code(length).0] += 1;
+                dist_freq[distance_code(distance).0] += 1;
+            }
+        }
+    }
+    lit_freq[256] = 1;
+
+    let lit_lengths = huffman_lengths(&lit_freq, 15);
+    let mut dist_lengths = huffman_lengths(&dist_freq, 15);
+    if dist_lengths.iter().all(|&l| l == 0) {
+        // At least one distance code must be described.
+        dist_lengths[0] = 1;
+    }
+
+    let hlit = usize::max(
+        257,
+        lit_lengths.iter().rposition(|&l| l > 0).unwrap_or(0) + 1,
+    );
+    let hdist = usize::max(
+        1,
+        dist_lengths.iter().rposition(|&l| l > 0).unwrap_or(0) + 1,
+    );
+    let mut all_lengths = lit_lengths[..hlit].to_vec();
+    all_lengths.extend_from_slice(&dist_lengths[..hdist]);
+    let rle = run_length_encode(&all_lengths);
+
+    let mut cl_freq = [0u32; 19];
+    for &(sym, _) in &rle {
+        cl_freq[sym as usize] += 1;
+    }
+    let cl_lengths = huffman_lengths(&cl_freq, 7);
+    let hclen = usize::max(
+        4,
+        CODE_LENGTH_ORDER
+            .iter()
+            .rposition(|&i| cl_lengths[i] > 0)
+            .unwrap_or(0)
+            + 1,
+    );
+
+    // Estimate the dynamic block size in bits to compare with a stored block.
+    let mut bits = 3 + 5 + 5 + 4 + 3 * hclen as u64;
+    for &(sym, _) in &rle {
+        bits += cl_lengths[sym as usize] as u64
+            + match sym {
+                16 => 2,
+                17 => 3,
+                18 => 7,
+                _ => 0,
+            };
+    }
+    for symbol in symbols {
+        bits += match *symbol {
+            Symbol::Literal(b) => lit_lengths[b as usize] as u64,
+            Symbol::Match { length, distance } => {
+                let (lc, lx, _) = length_code(length);
+                let (dc, dx, _) = distance_code(distance);
+                (lit_lengths[lc] + dist_lengths[dc]) as u64 + (lx + dx) as u64
+            }
+        };
+    }
+    bits += lit_lengths[256] as u64;
+    let stored_bits = (raw.len() as u64 + 5) * 8 + 7;
+    if bits >= stored_bits {
+        write_stored(writer, raw, last);
+        return;
+    }
+
+    writer.write_bits(last as u32, 1);
+    writer.write_bits(2, 2);
+    writer.write_bits((hlit - 257) as u32, 5);
+    writer.write_bits((hdist - 1) as u32, 5);
+    writer.write_bits((hclen - 4) as u32, 4);
+    for &i in &CODE_LENGTH_ORDER[..hclen] {
+        writer.write_bits(cl_lengths[i] as u32, 3);
+    }
+    let cl_codes = canonical_codes(&cl_lengths);
+    for &(sym, extra) in &rle {
+        writer.write_code(cl_codes[sym as usize], cl_lengths[sym as usize]);
+        match sym {
+            16 => writer.write_bits(extra as u32, 2),
+            17 => writer.write_bits(extra as u32, 3),
+            18 => writer.write_bits(extra as u32, 7),
+            _ => {}
+        }
+    }
+
+    let lit_codes = canonical_codes(&lit_lengths);
+    let dist_codes = canonical_codes(&dist_lengths);
+    for symbol in symbols {
+        match *symbol {
+            Symbol::Literal(b) => {
+                writer.write_code(lit_codes[b as usize], lit_lengths[b as usize]);
+            }
+            Symbol::Match { length, distance } => {
+                let (lc, lx, lv) = length_code(length);
+                writer.write_code(lit_codes[lc], lit_lengths[lc]);
+                writer.write_bits(lv as u32, lx as u32);
+                let (dc, dx, dv) = distance_code(distance);
+                writer.write_code(dist_codes[dc], dist_lengths[dc]);
+                writer.write_bits(dv as u32, dx as u32);
+            }
+        }
+    }
+    writer.write_code(lit_codes[256], lit_lengths[256]);
+}
+
+/// Writes `raw` as one or more stored blocks.
+fn write_stored(writer: &mut BitWriter, raw: &[u8], last: bool) {
+    let chunks: Vec<&[u8]> = if raw.is_empty() {
+        vec![raw]
+    } else {
+        raw.chunks(0xFFFF).collect()
+    };
+    for (i, chunk) in chunks.iter().enumerate() {
+        let final_chunk = last && i + 1 == chunks.len();
+        writer.write_bits(final_chunk as u32, 1);
+        writer.write_bits(0, 2);
+        writer.align_to_byte();
+        let len = chunk.len() as u16;
+        writer.write_bytes(&len.to_le_bytes());
+        writer.write_bytes(&(!len).to_le_bytes());
+        writer.write_bytes(chunk);
+    }
+}
+
+/// Run-length encodes code lengths using DEFLATE symbols 16, 17 and 18.
+///
+/// Returns `(symbol, extra_value)` pairs.
+fn run_length_encode(lengths: &[u8]) -> Vec<(u8, u8)> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < lengths.len() {
+        let value = lengths[i];
+        let run = lengths[i..].iter().take_while(|&&l| l == value).count();
+        if value == 0 && run >= 3 {
+            let mut remaining = run;
+            while remaining >= 11 {
+                let n = remaining.min(138);
+                out.push((18, (n - 11) as u8));
+                remaining -= n;
+            }
+            if remaining >= 3 {
+                out.push((17, (remaining - 3) as u8));
+                remaining = 0;
+            }
+            out.extend(std::iter::repeat_n((0, 0), remaining));
+        } else if value != 0 && run >= 4 {
+            out.push((value, 0));
+            let mut remaining = run - 1;
+            while remaining >= 3 {
+                let n = remaining.min(6);
+                out.push((16, (n - 3) as u8));
+                remaining -= n;
+            }
+            out.extend(std::iter::repeat_n((value, 0), remaining));
+        } else {
+            out.extend(std::iter::repeat_n((value, 0), run));
+        }
+        i += run;
+    }
+    out
+}
+
+/// Computes Huffman code lengths for `freqs`, limited to `max_len` bits.
+///
+/// Symbols with zero frequency get length zero. When the optimal tree is too
+/// deep, frequencies are flattened and the tree rebuilt until it fits.
+pub(crate) fn huffman_lengths(freqs: &[u32], max_len: u8) -> Vec<u8> {
+    let mut lengths = vec![0u8; freqs.len()];
+    let used: Vec<usize> = (0..freqs.len()).filter(|&i| freqs[i] > 0).collect();
+    match used.len() {
+        0 => return lengths,
+        1 => {
+            lengths[used[0]] = 1;
+            return lengths;
+        }
+        _ => {}
+    }
+
+    let mut weights: Vec<u64> = freqs.iter().map(|&f| f as u64).collect();
+    loop {
+        // Nodes 0..n are leaves; internal nodes are appended with their parent links.
+        let mut parent: Vec<usize> = vec![usize::MAX; freqs.len()];
+        let mut heap = BinaryHeap::new();
+        for &i in &used {
+            heap.push(Reverse((weights[i], i)));
+        }
+        while heap.len() > 1 {
+            let Reverse((w1, a)) = heap.pop().unwrap();
+            let Reverse((w2, b)) = heap.pop().unwrap();
+            let node = parent.len();
+            parent.push(usize::MAX);
+            parent[a] = node;
+            parent[b] = node;
+            heap.push(Reverse((w1 + w2, node)));
+        }
+
+        let mut depth = vec![0u8; parent.len()];
+        for node in (0..parent.len()).rev() {
+            if parent[node] != usize::MAX {
+                depth[node] = depth[parent[node]] + 1;
+            }
+        }
+        let max = used.iter().map(|&i| depth[i]).max().unwrap_or(0);
+        if max <= max_len {
+            for &i in &used {
+                lengths[i] = depth[i];
+            }
+            return lengths;
+        }
+        for &i in &used {
+            weights[i] = (weights[i] >> 1) | 1;
+        }
+    }
+}
+
+/// Assigns canonical Huffman codes from code lengths (RFC 1951 section 3.2.2).
+pub(crate) fn canonical_codes(lengths: &[u8]) -> Vec<u16> {
+    let max = lengths.iter().copied().max().unwrap_or(0) as usize;
+    let mut bl_count = vec![0u16; max + 1];
+    for &l in lengths {
+        if l > 0 {
+            bl_count[l as usize] += 1;
+        }
+    }
+    let mut next_code = vec![0u16; max + 2];
+    let mut code = 0u16;
+    for bits in 1..=max {
+        code = (code + bl_count[bits - 1]) << 1;
+        next_code[bits] = code;
+    }
+    lengths
+        .iter()
+        .map(|&l| {
+            if l == 0 {
+                0
+            } else {
+                let c = next_code[l as usize];
+                next_code[l as usize] += 1;
+                c
+            }
+        })
+        .collect()
+}
+
+/// LSB-first bit writer used by DEFLATE (and Brotli).
+#[derive(Debug, Default)]
+pub(crate) struct BitWriter {
+    out: Vec<u8>,
+    bit_buf: u64,
+    bit_count: u32,
+}
+
+impl BitWriter {
+    /// Writes the low `count` bits of `value`, least significant bit first.
+    pub(crate) fn write_bits(&mut self, value: u32, count: u32) {
+        if count == 0 {
+            return;
+        }
+        self.bit_buf |= ((value as u64) & ((1u64 << count) - 1)) << self.bit_count;
+        self.bit_count += count;
+        while self.bit_count >= 8 {
+            self.out.push(self.bit_buf as u8);
+            self.bit_buf >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    /// Writes a Huffman code, which is transmitted most significant bit first.
+    pub(crate) fn write_code(&mut self, code: u16, length: u8) {
+        let reversed = code.reverse_bits() >> (16 - length as u32);
+        self.write_bits(reversed as u32, length as u32);
+    }
+
+    /// Pads with zero bits up to the next byte boundary.
+    pub(crate) fn align_to_byte(&mut self) {
+        if self.bit_count > 0 {
+            self.write_bits(0, 8 - self.bit_count);
+        }
+    }
+
+    /// Writes whole bytes. The writer must be byte aligned.
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        debug_assert_eq!(self.bit_count, 0);
+        self.out.extend_from_slice(bytes);
+    }
+
+    /// Returns the number of bits written so far.
+    pub(crate) fn bit_len(&self) -> usize {
+        self.out.len() * 8 + self.bit_count as usize
+    }
+
+    /// Appends everything written to `other`, which need not be byte aligned.
+    pub(crate) fn append(&mut self, other: BitWriter) {
+        for &byte in &other.out {
+            self.write_bits(byte as u32, 8);
+        }
+        self.write_bits(other.bit_buf as u32, other.bit_count);
+    }
+
+    /// Flushes any partial byte and returns the output.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        self.align_to_byte();
+        self.out
+    }
+}
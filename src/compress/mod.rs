@@ -0,0 +1,14 @@
+//! Aurora Font Library
+//! 
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Compression codecs used by the font encoders. Implemented in-crate to keep
+//! the dependency list minimal.
+
+pub mod brotli;
+pub mod deflate;
+
+pub use brotli::*;
+pub use deflate::*;
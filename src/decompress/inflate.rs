@@ -0,0 +1,365 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! DEFLATE (RFC 1951), zlib (RFC 1950) and gzip (RFC 1952) decompression for
+//! the Aurora Font Library.
+//!
+//! Huffman codes are decoded one bit at a time against canonical code counts,
+//! which keeps the decoder small; font payloads are rarely large enough for
+//! table-driven decoding to matter.
+
+use crate::compress::deflate::{
+    CODE_LENGTH_ORDER, DIST_BASE, DIST_EXTRA, LENGTH_BASE, LENGTH_EXTRA, adler32,
+};
+use crate::error::{Error, IoError};
+
+/// Longest Huffman code DEFLATE allows.
+const MAX_CODE_LENGTH: usize = 15;
+/// gzip member magic bytes.
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+/// gzip header flags.
+const FHCRC: u8 = 0x02;
+const FEXTRA: u8 = 0x04;
+const FNAME: u8 = 0x08;
+const FCOMMENT: u8 = 0x10;
+
+/// Reads bits least significant first.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    bit_buf: u32,
+    bit_count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            position: 0,
+            bit_buf: 0,
+            bit_count: 0,
+        }
+    }
+
+    /// Reads `count` bits (at most 16).
+    fn bits(&mut self, count: u32) -> Result<u32, Error> {
+        while self.bit_count < count {
+            let byte = *self
+                .data
+                .get(self.position)
+                .ok_or(Error::Io(IoError::OutOfBounds {
+                    requested: 1,
+                    available: 0,
+                }))?;
+            self.position += 1;
+            self.bit_buf |= (byte as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let value = self.bit_buf & ((1u32 << count) - 1);
+        self.bit_buf >>= count;
+        self.bit_count -= count;
+        Ok(value)
+    }
+
+    /// Drops the bits left in the current byte.
+    fn align_to_byte(&mut self) {
+        self.bit_buf = 0;
+        self.bit_count = 0;
+    }
+
+    /// Reads whole bytes after `align_to_byte`.
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + length)
+            .ok_or(Error::Io(IoError::OutOfBounds {
+                requested: length,
+                available: self.data.len() - self.position,
+            }))?;
+        self.position += length;
+        Ok(bytes)
+    }
+}
+
+/// A canonical Huffman code: the number of codes of each length and the
+/// symbols ordered by code.
+struct Huffman {
+    counts: [u16; MAX_CODE_LENGTH + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Builds the code of the given code lengths (0 for unused symbols).
+    /// Incomplete codes are accepted; over-subscribed ones are not.
+    fn new(lengths: &[u8]) -> Result<Self, Error> {
+        let mut counts = [0u16; MAX_CODE_LENGTH + 1];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - count as i32;
+            if left < 0 {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+        }
+        let mut offsets = [0u16; MAX_CODE_LENGTH + 2];
+        for length in 1..=MAX_CODE_LENGTH {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0; offsets[MAX_CODE_LENGTH + 1] as usize];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+        Ok(Huffman { counts, symbols })
+    }
+
+    /// Decodes one symbol; codes are transmitted most significant bit first.
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, Error> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for &count in &self.counts[1..] {
+            code |= reader.bits(1)? as i32;
+            let count = count as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(Error::Io(IoError::InvalidData))
+    }
+}
+
+/// Returns the fixed literal/length and distance codes.
+fn fixed_codes() -> Result<(Huffman, Huffman), Error> {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?))
+}
+
+/// Reads the code lengths of a dynamic block and returns its codes.
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), Error> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match code_length_code.decode(reader)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths.last().ok_or(Error::Io(IoError::InvalidData))?;
+                (previous, 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            _ => (0, 11 + reader.bits(7)? as usize),
+        };
+        if lengths.len() + repeat > literal_count + distance_count {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+    if lengths[256] == 0 {
+        // A block without an end-of-block code cannot terminate.
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    Ok((
+        Huffman::new(&lengths[..literal_count])?,
+        Huffman::new(&lengths[literal_count..])?,
+    ))
+}
+
+/// Decodes the symbols of a compressed block up to its end-of-block code.
+/// Fails once `out` would grow past `max_len` bytes.
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+    max_len: usize,
+) -> Result<(), Error> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+        match symbol {
+            0..=255 => {
+                if out.len() >= max_len {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                out.push(symbol as u8);
+            }
+            256 => return Ok(()),
+            257..=285 => {
+                let code = symbol - 257;
+                let length =
+                    LENGTH_BASE[code] as usize + reader.bits(LENGTH_EXTRA[code] as u32)? as usize;
+                let code = distances.decode(reader)? as usize;
+                if code >= DIST_BASE.len() {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                let distance =
+                    DIST_BASE[code] as usize + reader.bits(DIST_EXTRA[code] as u32)? as usize;
+                if distance > out.len() || out.len() + length > max_len {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                let start = out.len() - distance;
+                for i in 0..length {
+                    out.push(out[start + i]);
+                }
+            }
+            _ => return Err(Error::Io(IoError::InvalidData)),
+        }
+    }
+}
+
+/// Decompresses a raw DEFLATE stream of at most `max_len` output bytes and
+/// returns the output with the number of input bytes consumed.
+fn inflate_stream(data: &[u8], max_len: usize) -> Result<(Vec<u8>, usize), Error> {
+    let mut reader = BitReader::new(data);
+    let mut out = Vec::new();
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let header = reader.bytes(4)?;
+                let length = u16::from_le_bytes([header[0], header[1]]);
+                let complement = u16::from_le_bytes([header[2], header[3]]);
+                if length != !complement || out.len() + length as usize > max_len {
+                    return Err(Error::Io(IoError::InvalidData));
+                }
+                out.extend_from_slice(reader.bytes(length as usize)?);
+            }
+            1 => {
+                let (literals, distances) = fixed_codes()?;
+                inflate_block(&mut reader, &mut out, &literals, &distances, max_len)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &literals, &distances, max_len)?;
+            }
+            _ => return Err(Error::Io(IoError::InvalidData)),
+        }
+        if last {
+            return Ok((out, reader.position));
+        }
+    }
+}
+
+/// Decompresses a raw DEFLATE stream.
+///
+/// Returns `IoError::InvalidData` if the output would exceed `max_len` bytes.
+pub fn inflate(data: &[u8], max_len: usize) -> Result<Vec<u8>, Error> {
+    inflate_stream(data, max_len).map(|(out, _)| out)
+}
+
+/// Decompresses a zlib stream and verifies its Adler-32 checksum.
+///
+/// Returns `IoError::InvalidData` if the output would exceed `max_len` bytes.
+pub fn zlib_decompress(data: &[u8], max_len: usize) -> Result<Vec<u8>, Error> {
+    let (&[cmf, flg], body) = data
+        .split_first_chunk::<2>()
+        .ok_or(Error::Io(IoError::InvalidData))?;
+    if cmf & 0x0F != 8 || !(((cmf as u16) << 8) | flg as u16).is_multiple_of(31) || flg & 0x20 != 0
+    {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    let (out, consumed) = inflate_stream(body, max_len)?;
+    let checksum = body
+        .get(consumed..consumed + 4)
+        .ok_or(Error::Io(IoError::InvalidData))?;
+    if adler32(&out).to_be_bytes() != checksum {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+    Ok(out)
+}
+
+/// Returns whether `data` starts with a gzip member header.
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&GZIP_MAGIC)
+}
+
+/// Decompresses the first member of a gzip file and verifies its CRC-32 and
+/// length.
+///
+/// Returns `IoError::InvalidData` if the output would exceed `max_len` bytes.
+pub fn gunzip(data: &[u8], max_len: usize) -> Result<Vec<u8>, Error> {
+    let invalid = || Error::Io(IoError::InvalidData);
+    if !is_gzip(data) || data.get(2) != Some(&8) {
+        return Err(invalid());
+    }
+    let flags = *data.get(3).ok_or_else(invalid)?;
+    // Magic, method, flags, modification time, extra flags and OS.
+    let mut position = 10;
+    if flags & FEXTRA != 0 {
+        let length = data.get(position..position + 2).ok_or_else(invalid)?;
+        position += 2 + u16::from_le_bytes([length[0], length[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = data
+                .get(position..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or_else(invalid)?;
+            position += end + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        position += 2;
+    }
+    let body = data.get(position..).ok_or_else(invalid)?;
+    let (out, consumed) = inflate_stream(body, max_len)?;
+    let trailer = body.get(consumed..consumed + 8).ok_or_else(invalid)?;
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if crc != crc32(&out) || size != out.len() as u32 {
+        return Err(invalid());
+    }
+    Ok(out)
+}
+
+/// CRC-32 (IEEE 802.3) lookup table.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC-32 checksum used by gzip.
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
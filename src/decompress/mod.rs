@@ -1,5 +1,12 @@
 //! Aurora Font Library
-//! 
+//!
 //! Author: Colton McGraw <github.com/ColtMcG1>
 //! License: Apache-2.0
-//! Date: January 2026
\ No newline at end of file
+//! Date: January 2026
+//!
+//! Decompression codecs used by the font decoders. Implemented in-crate to
+//! keep the dependency list minimal.
+
+pub mod inflate;
+
+pub use inflate::*;
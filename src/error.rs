@@ -41,6 +41,8 @@ pub enum IoError {
     InvalidUtf8 { offset: usize },
     /// Glyph index out of range.
     InvalidGlyphIndex { index: u32 },
+    /// An offset field at `position` could not hold the required `offset`.
+    OffsetOverflow { position: usize, offset: usize },
     /// Generic invalid data placeholder.
     InvalidData,
 }
@@ -75,6 +77,7 @@ impl fmt::Display for IoError {
             IoError::TruncatedTable { table, expected_len, found_len } => write!(f, "truncated table {}{}{}{}: expected {}, found {}", table[0] as char, table[1] as char, table[2] as char, table[3] as char, expected_len, found_len),
             IoError::InvalidUtf8 { offset } => write!(f, "invalid utf8 at offset {}", offset),
             IoError::InvalidGlyphIndex { index } => write!(f, "invalid glyph index {}", index),
+            IoError::OffsetOverflow { position, offset } => write!(f, "offset {} does not fit in field at {}", offset, position),
             IoError::InvalidData => write!(f, "invalid data"),
         }
     }
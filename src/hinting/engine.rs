@@ -0,0 +1,1482 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! The TrueType bytecode interpreter: instruction decoding, control flow,
+//! function and instruction definitions, and every instruction of the
+//! TrueType instruction set.
+
+use crate::error::{Error, IoError};
+
+use super::HintingMode;
+use super::state::{
+    GraphicsState, Point, RoundMode, TOUCHED_X, TOUCHED_Y, X_AXIS, Y_AXIS, Zone, mul_div, mul_fix,
+    mul14, normalize,
+};
+
+/// Maximum nesting of function calls.
+const MAX_CALL_DEPTH: usize = 64;
+/// Maximum number of instructions a program may execute, which stops
+/// endless loops.
+const MAX_INSTRUCTIONS: usize = 1_000_000;
+
+/// The twilight zone.
+const TWILIGHT: usize = 0;
+/// The glyph zone.
+const GLYPH: usize = 1;
+
+/// A program the interpreter runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Program {
+    /// `fpgm`, run once per size before `prep`.
+    Font,
+    /// `prep`, run whenever the size changes.
+    ControlValue,
+    /// Instructions of the glyph being hinted.
+    Glyph,
+}
+
+/// A function or instruction definition: the instructions between `FDEF`
+/// or `IDEF` and the matching `ENDF`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Definition {
+    program: Program,
+    start: usize,
+    /// Position of the `ENDF` instruction.
+    end: usize,
+}
+
+/// Position in a program.
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    program: Program,
+    pc: usize,
+    end: usize,
+}
+
+/// An active function call.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    definition: Definition,
+    caller: Cursor,
+    /// Remaining iterations of a `LOOPCALL`.
+    remaining: i32,
+}
+
+/// Returns the error raised by invalid instructions.
+fn invalid() -> Error {
+    Error::Io(IoError::InvalidData)
+}
+
+/// Converts a stack value to an index; negative values never index anything.
+fn index(value: i32) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+/// Projects `v` on the unit vector `u`.
+fn dot(v: Point, u: Point) -> i32 {
+    let sum = v.x as i64 * u.x as i64 + v.y as i64 * u.y as i64;
+    ((sum + 0x2000) >> 14) as i32
+}
+
+/// Returns the length of the instruction at `pc`, push data included.
+fn instruction_length(code: &[u8], pc: usize) -> Result<usize, Error> {
+    let opcode = code[pc];
+    let length = match opcode {
+        0x40 => 2 + *code.get(pc + 1).ok_or_else(invalid)? as usize,
+        0x41 => 2 + 2 * *code.get(pc + 1).ok_or_else(invalid)? as usize,
+        0xB0..=0xB7 => 2 + (opcode - 0xB0) as usize,
+        0xB8..=0xBF => 3 + 2 * (opcode - 0xB8) as usize,
+        _ => 1,
+    };
+    if pc + length > code.len() {
+        return Err(invalid());
+    }
+    Ok(length)
+}
+
+/// State of the TrueType virtual machine.
+#[derive(Debug, Clone)]
+pub(crate) struct Engine {
+    pub mode: HintingMode,
+    pub fpgm: Vec<u8>,
+    pub prep: Vec<u8>,
+    pub glyph: Vec<u8>,
+    pub functions: Vec<Option<Definition>>,
+    pub instruction_defs: Vec<Option<Definition>>,
+    pub max_instruction_defs: usize,
+    pub stack: Vec<i32>,
+    pub stack_limit: usize,
+    pub storage: Vec<i32>,
+    /// Scaled control values in 26.6 pixels.
+    pub cvt: Vec<i32>,
+    /// The twilight and glyph zones.
+    pub zones: [Zone; 2],
+    pub gs: GraphicsState,
+    pub ppem: i32,
+    /// Font units to 26.6 pixels, in 16.16.
+    pub scale: i32,
+    /// Normalized variation coordinates in 2.14.
+    pub coords: Vec<i32>,
+    pub is_composite: bool,
+    /// v40 mode with the font not declaring native ClearType support: x
+    /// movements are ignored and y movements stop after `IUP` ran on both axes.
+    pub backward_compatibility: bool,
+    pub iup_x_called: bool,
+    pub iup_y_called: bool,
+    /// The program started by `execute`.
+    program: Program,
+}
+
+impl Engine {
+    /// Returns an engine with empty programs and zones.
+    pub(crate) fn new(mode: HintingMode) -> Engine {
+        Engine {
+            mode,
+            fpgm: Vec::new(),
+            prep: Vec::new(),
+            glyph: Vec::new(),
+            functions: Vec::new(),
+            instruction_defs: vec![None; 256],
+            max_instruction_defs: 0,
+            stack: Vec::new(),
+            stack_limit: 0,
+            storage: Vec::new(),
+            cvt: Vec::new(),
+            zones: [Zone::default(), Zone::default()],
+            gs: GraphicsState::default(),
+            ppem: 0,
+            scale: 0,
+            coords: Vec::new(),
+            is_composite: false,
+            backward_compatibility: false,
+            iup_x_called: false,
+            iup_y_called: false,
+            program: Program::Font,
+        }
+    }
+
+    fn code(&self, program: Program) -> &[u8] {
+        match program {
+            Program::Font => &self.fpgm,
+            Program::ControlValue => &self.prep,
+            Program::Glyph => &self.glyph,
+        }
+    }
+
+    fn pop(&mut self) -> Result<i32, Error> {
+        self.stack.pop().ok_or_else(invalid)
+    }
+
+    fn push(&mut self, value: i32) -> Result<(), Error> {
+        if self.stack.len() >= self.stack_limit {
+            return Err(invalid());
+        }
+        self.stack.push(value);
+        Ok(())
+    }
+
+    /// Returns `point` if it exists in `zone`. Instructions referencing
+    /// missing points are skipped rather than failing, as fonts routinely
+    /// touch glyph points from `prep`, where the glyph zone is empty.
+    fn point(&self, zone: usize, point: i32) -> Option<usize> {
+        let point = index(point);
+        (point < self.zones[zone].len()).then_some(point)
+    }
+
+    /// Projects `a - b` on the projection vector.
+    fn project(&self, a: Point, b: Point) -> i32 {
+        dot(a.sub(b), self.gs.projection_vector)
+    }
+
+    /// Projects `a - b` on the dual projection vector.
+    fn dual_project(&self, a: Point, b: Point) -> i32 {
+        dot(a.sub(b), self.gs.dual_vector)
+    }
+
+    /// Returns the projection of the freedom vector on the projection vector.
+    fn f_dot_p(&self) -> i32 {
+        let (fv, pv) = (self.gs.freedom_vector, self.gs.projection_vector);
+        let value = ((fv.x as i64 * pv.x as i64 + fv.y as i64 * pv.y as i64) >> 14) as i32;
+        // Nearly perpendicular vectors would make moves explode.
+        if value.abs() < 0x400 { 0x4000 } else { value }
+    }
+
+    /// Returns the displacement along the freedom vector that moves a point
+    /// by `distance` along the projection vector.
+    fn freedom_displacement(&self, distance: i32) -> (i32, i32) {
+        let f_dot_p = self.f_dot_p();
+        let fv = self.gs.freedom_vector;
+        (
+            mul_div(distance, fv.x, f_dot_p),
+            mul_div(distance, fv.y, f_dot_p),
+        )
+    }
+
+    /// Moves a point by `(dx, dy)`, restricted by the backward compatibility
+    /// mode, and marks it touched along the freedom vector if `touch`.
+    fn shift_point(&mut self, zone: usize, point: usize, dx: i32, dy: i32, touch: bool) {
+        let fv = self.gs.freedom_vector;
+        let compatible = self.backward_compatibility;
+        let post_iup = compatible && self.iup_x_called && self.iup_y_called;
+        let zone = &mut self.zones[zone];
+        if fv.x != 0 {
+            if !compatible {
+                zone.current[point].x = zone.current[point].x.wrapping_add(dx);
+            }
+            if touch {
+                zone.flags[point] |= TOUCHED_X;
+            }
+        }
+        if fv.y != 0 {
+            if !post_iup {
+                zone.current[point].y = zone.current[point].y.wrapping_add(dy);
+            }
+            if touch {
+                zone.flags[point] |= TOUCHED_Y;
+            }
+        }
+    }
+
+    /// Moves a point by `distance` along the projection vector, in the
+    /// direction of the freedom vector, and touches it.
+    fn move_point(&mut self, zone: usize, point: usize, distance: i32) {
+        let (dx, dy) = self.freedom_displacement(distance);
+        self.shift_point(zone, point, dx, dy, true);
+    }
+
+    /// Moves the original position of a point by `distance`.
+    fn move_original(&mut self, zone: usize, point: usize, distance: i32) {
+        let (dx, dy) = self.freedom_displacement(distance);
+        let original = &mut self.zones[zone].original[point];
+        original.x = original.x.wrapping_add(dx);
+        original.y = original.y.wrapping_add(dy);
+    }
+
+    /// Returns the vector from the point `p2` of `zp2` to the point `p1` of
+    /// `zp1`, rotated counter-clockwise if `perpendicular`. `None` when a point
+    /// is missing.
+    fn line_vector(&self, p1: i32, p2: i32, perpendicular: bool, original: bool) -> Option<Point> {
+        let (zp1, zp2) = (self.gs.zp1, self.gs.zp2);
+        let p1 = self.point(zp1, p1)?;
+        let p2 = self.point(zp2, p2)?;
+        let (a, b) = if original {
+            (self.zones[zp1].original[p1], self.zones[zp2].original[p2])
+        } else {
+            (self.zones[zp1].current[p1], self.zones[zp2].current[p2])
+        };
+        let (mut dx, mut dy) = (a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y));
+        if dx == 0 && dy == 0 {
+            return Some(X_AXIS);
+        }
+        if perpendicular {
+            (dx, dy) = (dy.wrapping_neg(), dx);
+        }
+        Some(normalize(dx, dy))
+    }
+
+    /// Runs a program to completion.
+    pub(crate) fn execute(&mut self, program: Program) -> Result<(), Error> {
+        self.program = program;
+        self.stack.clear();
+        self.gs.loop_count = 1;
+        self.backward_compatibility =
+            self.mode == HintingMode::V40 && self.gs.instruct_control & 4 == 0;
+        self.iup_x_called = false;
+        self.iup_y_called = false;
+        let mut cursor = Cursor {
+            program,
+            pc: 0,
+            end: self.code(program).len(),
+        };
+        let mut calls: Vec<Frame> = Vec::new();
+        let mut executed = 0usize;
+        loop {
+            if cursor.pc >= cursor.end {
+                // Functions end at their `ENDF`; running past it means a bad jump.
+                return if calls.is_empty() {
+                    Ok(())
+                } else {
+                    Err(invalid())
+                };
+            }
+            executed += 1;
+            if executed > MAX_INSTRUCTIONS {
+                return Err(invalid());
+            }
+
+            let code = self.code(cursor.program);
+            let opcode = code[cursor.pc];
+            let mut next = cursor.pc + instruction_length(code, cursor.pc)?;
+            match opcode {
+                // SVTCA, SPVTCA, SFVTCA
+                0x00..=0x05 => {
+                    let axis = if opcode & 1 != 0 { X_AXIS } else { Y_AXIS };
+                    if opcode <= 0x03 {
+                        self.gs.projection_vector = axis;
+                        self.gs.dual_vector = axis;
+                    }
+                    if opcode <= 0x01 || opcode >= 0x04 {
+                        self.gs.freedom_vector = axis;
+                    }
+                }
+                // SPVTL, SFVTL
+                0x06..=0x09 => {
+                    let p2 = self.pop()?;
+                    let p1 = self.pop()?;
+                    if let Some(vector) = self.line_vector(p1, p2, opcode & 1 != 0, false) {
+                        if opcode <= 0x07 {
+                            self.gs.projection_vector = vector;
+                            self.gs.dual_vector = vector;
+                        } else {
+                            self.gs.freedom_vector = vector;
+                        }
+                    }
+                }
+                // SPVFS, SFVFS
+                0x0A | 0x0B => {
+                    let y = self.pop()? as i16 as i32;
+                    let x = self.pop()? as i16 as i32;
+                    let vector = normalize(x, y);
+                    if opcode == 0x0A {
+                        self.gs.projection_vector = vector;
+                        self.gs.dual_vector = vector;
+                    } else {
+                        self.gs.freedom_vector = vector;
+                    }
+                }
+                // GPV, GFV
+                0x0C | 0x0D => {
+                    let vector = if opcode == 0x0C {
+                        self.gs.projection_vector
+                    } else {
+                        self.gs.freedom_vector
+                    };
+                    self.push(vector.x)?;
+                    self.push(vector.y)?;
+                }
+                // SFVTPV
+                0x0E => self.gs.freedom_vector = self.gs.projection_vector,
+                0x0F => self.isect()?,
+                // SRP0, SRP1, SRP2
+                0x10 => self.gs.rp0 = index(self.pop()?),
+                0x11 => self.gs.rp1 = index(self.pop()?),
+                0x12 => self.gs.rp2 = index(self.pop()?),
+                // SZP0, SZP1, SZP2, SZPS
+                0x13..=0x16 => {
+                    let zone = match self.pop()? {
+                        0 => TWILIGHT,
+                        1 => GLYPH,
+                        _ => return Err(invalid()),
+                    };
+                    match opcode {
+                        0x13 => self.gs.zp0 = zone,
+                        0x14 => self.gs.zp1 = zone,
+                        0x15 => self.gs.zp2 = zone,
+                        _ => {
+                            self.gs.zp0 = zone;
+                            self.gs.zp1 = zone;
+                            self.gs.zp2 = zone;
+                        }
+                    }
+                }
+                // SLOOP
+                0x17 => {
+                    let count = self.pop()?;
+                    if count < 0 {
+                        return Err(invalid());
+                    }
+                    self.gs.loop_count = count.min(0xFFFF);
+                }
+                0x18 => self.gs.round_mode = RoundMode::Grid,
+                0x19 => self.gs.round_mode = RoundMode::HalfGrid,
+                // SMD
+                0x1A => self.gs.min_distance = self.pop()?,
+                // ELSE: the taken branch ends here.
+                0x1B => next = self.skip_branch(cursor, false)?,
+                // JMPR
+                0x1C => {
+                    let offset = self.pop()?;
+                    next = Self::jump(cursor, offset)?;
+                }
+                // SCVTCI, SSWCI, SSW
+                0x1D => self.gs.control_value_cut_in = self.pop()?,
+                0x1E => self.gs.single_width_cut_in = self.pop()?,
+                0x1F => {
+                    let value = self.pop()?;
+                    self.gs.single_width_value = mul_fix(value, self.scale);
+                }
+                // DUP
+                0x20 => {
+                    let value = *self.stack.last().ok_or_else(invalid)?;
+                    self.push(value)?;
+                }
+                // POP
+                0x21 => {
+                    self.pop()?;
+                }
+                // CLEAR
+                0x22 => self.stack.clear(),
+                // SWAP
+                0x23 => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.push(b)?;
+                    self.push(a)?;
+                }
+                // DEPTH
+                0x24 => self.push(self.stack.len() as i32)?,
+                // CINDEX, MINDEX
+                0x25 | 0x26 => {
+                    let k = index(self.pop()?);
+                    if k == 0 || k > self.stack.len() {
+                        return Err(invalid());
+                    }
+                    let position = self.stack.len() - k;
+                    let value = if opcode == 0x25 {
+                        self.stack[position]
+                    } else {
+                        self.stack.remove(position)
+                    };
+                    self.push(value)?;
+                }
+                0x27 => self.align_points()?,
+                // UTP
+                0x29 => {
+                    let point = self.pop()?;
+                    if let Some(point) = self.point(self.gs.zp0, point) {
+                        let fv = self.gs.freedom_vector;
+                        let flags = &mut self.zones[self.gs.zp0].flags[point];
+                        if fv.x != 0 {
+                            *flags &= !TOUCHED_X;
+                        }
+                        if fv.y != 0 {
+                            *flags &= !TOUCHED_Y;
+                        }
+                    }
+                }
+                // LOOPCALL, CALL
+                0x2A | 0x2B => {
+                    let function = index(self.pop()?);
+                    let count = if opcode == 0x2A { self.pop()? } else { 1 };
+                    let definition = self
+                        .functions
+                        .get(function)
+                        .copied()
+                        .flatten()
+                        .ok_or_else(invalid)?;
+                    if count > 0 {
+                        cursor = Self::call(&mut calls, definition, cursor, next, count)?;
+                        continue;
+                    }
+                }
+                // FDEF, IDEF
+                0x2C | 0x89 => {
+                    let number = index(self.pop()?);
+                    let end = self.find_endf(cursor)?;
+                    let definition = Definition {
+                        program: cursor.program,
+                        start: next,
+                        end,
+                    };
+                    if opcode == 0x2C {
+                        *self.functions.get_mut(number).ok_or_else(invalid)? = Some(definition);
+                    } else {
+                        let defined = self.instruction_defs.iter().flatten().count();
+                        let slot = self.instruction_defs.get_mut(number).ok_or_else(invalid)?;
+                        if slot.is_none() && defined >= self.max_instruction_defs {
+                            return Err(invalid());
+                        }
+                        *slot = Some(definition);
+                    }
+                    next = end + 1;
+                }
+                // ENDF
+                0x2D => {
+                    let frame = calls.last_mut().ok_or_else(invalid)?;
+                    frame.remaining -= 1;
+                    if frame.remaining > 0 {
+                        cursor.pc = frame.definition.start;
+                    } else {
+                        cursor = frame.caller;
+                        calls.pop();
+                    }
+                    continue;
+                }
+                // MDAP
+                0x2E | 0x2F => {
+                    let point = self.pop()?;
+                    if let Some(point) = self.point(self.gs.zp0, point) {
+                        let distance = if opcode & 1 != 0 {
+                            let current = dot(
+                                self.zones[self.gs.zp0].current[point],
+                                self.gs.projection_vector,
+                            );
+                            self.gs.round_mode.round(current).wrapping_sub(current)
+                        } else {
+                            0
+                        };
+                        self.move_point(self.gs.zp0, point, distance);
+                        self.gs.rp0 = point;
+                        self.gs.rp1 = point;
+                    }
+                }
+                // IUP
+                0x30 | 0x31 => self.iup(opcode & 1 != 0),
+                // SHP
+                0x32 | 0x33 => {
+                    let displacement = self.reference_displacement(opcode & 1 != 0);
+                    for _ in 0..self.gs.loop_count {
+                        let point = self.pop()?;
+                        if let (Some((dx, dy, _, _)), Some(point)) =
+                            (displacement, self.point(self.gs.zp2, point))
+                        {
+                            self.shift_point(self.gs.zp2, point, dx, dy, true);
+                        }
+                    }
+                    self.gs.loop_count = 1;
+                }
+                // SHC
+                0x34 | 0x35 => {
+                    let contour = index(self.pop()?);
+                    let zp2 = self.gs.zp2;
+                    let ends = &self.zones[zp2].contour_ends;
+                    if let (Some(displacement), Some(&end)) = (
+                        self.reference_displacement(opcode & 1 != 0),
+                        ends.get(contour),
+                    ) {
+                        let start = match contour {
+                            0 => 0,
+                            _ => ends[contour - 1] + 1,
+                        };
+                        let (dx, dy, zone, reference) = displacement;
+                        for point in start..=end.min(self.zones[zp2].len().saturating_sub(1)) {
+                            if zone != zp2 || point != reference {
+                                self.shift_point(zp2, point, dx, dy, true);
+                            }
+                        }
+                    }
+                }
+                // SHZ
+                0x36 | 0x37 => {
+                    let zp = match self.pop()? {
+                        0 => TWILIGHT,
+                        1 => GLYPH,
+                        _ => return Err(invalid()),
+                    };
+                    if let Some((dx, dy, zone, reference)) =
+                        self.reference_displacement(opcode & 1 != 0)
+                    {
+                        // Phantom points follow the last contour and are not shifted.
+                        let limit = match zp {
+                            TWILIGHT => self.zones[zp].len(),
+                            _ => self.zones[zp].contour_ends.last().map_or(0, |&end| end + 1),
+                        };
+                        for point in 0..limit.min(self.zones[zp].len()) {
+                            if zone != zp || point != reference {
+                                self.shift_point(zp, point, dx, dy, false);
+                            }
+                        }
+                    }
+                }
+                // SHPIX
+                0x38 => {
+                    let amount = self.pop()?;
+                    let fv = self.gs.freedom_vector;
+                    let (dx, dy) = (mul14(amount, fv.x), mul14(amount, fv.y));
+                    for _ in 0..self.gs.loop_count {
+                        let point = self.pop()?;
+                        let Some(point) = self.point(self.gs.zp2, point) else {
+                            continue;
+                        };
+                        if self.backward_compatibility {
+                            // Only vertical moves before IUP, of touched points or
+                            // in composites.
+                            let touched_y = self.zones[self.gs.zp2].flags[point] & TOUCHED_Y != 0;
+                            if !(self.iup_x_called && self.iup_y_called)
+                                && ((self.is_composite && fv.y != 0) || touched_y)
+                            {
+                                self.shift_point(self.gs.zp2, point, 0, dy, true);
+                            }
+                        } else {
+                            self.shift_point(self.gs.zp2, point, dx, dy, true);
+                        }
+                    }
+                    self.gs.loop_count = 1;
+                }
+                0x39 => self.interpolate_points()?,
+                // MSIRP
+                0x3A | 0x3B => {
+                    let distance = self.pop()?;
+                    let point = self.pop()?;
+                    let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                    if let (Some(point), Some(rp0)) =
+                        (self.point(zp1, point), self.point(zp0, self.gs.rp0 as i32))
+                    {
+                        if zp1 == TWILIGHT {
+                            self.zones[zp1].original[point] = self.zones[zp0].original[rp0];
+                            self.move_original(zp1, point, distance);
+                            self.zones[zp1].current[point] = self.zones[zp1].original[point];
+                        }
+                        let current = self
+                            .project(self.zones[zp1].current[point], self.zones[zp0].current[rp0]);
+                        self.move_point(zp1, point, distance.wrapping_sub(current));
+                        self.gs.rp1 = self.gs.rp0;
+                        self.gs.rp2 = point;
+                        if opcode & 1 != 0 {
+                            self.gs.rp0 = point;
+                        }
+                    }
+                }
+                // ALIGNRP
+                0x3C => {
+                    let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                    let rp0 = self.point(zp0, self.gs.rp0 as i32);
+                    for _ in 0..self.gs.loop_count {
+                        let point = self.pop()?;
+                        if let (Some(point), Some(rp0)) = (self.point(zp1, point), rp0) {
+                            let distance = self.project(
+                                self.zones[zp1].current[point],
+                                self.zones[zp0].current[rp0],
+                            );
+                            self.move_point(zp1, point, distance.wrapping_neg());
+                        }
+                    }
+                    self.gs.loop_count = 1;
+                }
+                0x3D => self.gs.round_mode = RoundMode::DoubleGrid,
+                // MIAP
+                0x3E | 0x3F => {
+                    let cvt = index(self.pop()?);
+                    let point = self.pop()?;
+                    let zp0 = self.gs.zp0;
+                    if let (Some(point), Some(&cvt)) = (self.point(zp0, point), self.cvt.get(cvt)) {
+                        let mut distance = cvt;
+                        if zp0 == TWILIGHT {
+                            let fv = self.gs.freedom_vector;
+                            let position = Point::new(mul14(distance, fv.x), mul14(distance, fv.y));
+                            self.zones[zp0].original[point] = position;
+                            self.zones[zp0].current[point] = position;
+                        }
+                        let current =
+                            dot(self.zones[zp0].current[point], self.gs.projection_vector);
+                        if opcode & 1 != 0 {
+                            if (distance.wrapping_sub(current)).abs() > self.gs.control_value_cut_in
+                            {
+                                distance = current;
+                            }
+                            distance = self.gs.round_mode.round(distance);
+                        }
+                        self.move_point(zp0, point, distance.wrapping_sub(current));
+                        self.gs.rp0 = point;
+                        self.gs.rp1 = point;
+                    }
+                }
+                // NPUSHB, NPUSHW, PUSHB, PUSHW
+                0x40 | 0x41 | 0xB0..=0xBF => {
+                    let (count, words, data) = match opcode {
+                        0x40 => (code[cursor.pc + 1] as usize, false, cursor.pc + 2),
+                        0x41 => (code[cursor.pc + 1] as usize, true, cursor.pc + 2),
+                        0xB0..=0xB7 => ((opcode - 0xAF) as usize, false, cursor.pc + 1),
+                        _ => ((opcode - 0xB7) as usize, true, cursor.pc + 1),
+                    };
+                    for i in 0..count {
+                        let code = self.code(cursor.program);
+                        let value = if words {
+                            i16::from_be_bytes([code[data + 2 * i], code[data + 2 * i + 1]]) as i32
+                        } else {
+                            code[data + i] as i32
+                        };
+                        self.push(value)?;
+                    }
+                }
+                // WS
+                0x42 => {
+                    let value = self.pop()?;
+                    let location = index(self.pop()?);
+                    if let Some(slot) = self.storage.get_mut(location) {
+                        *slot = value;
+                    }
+                }
+                // RS
+                0x43 => {
+                    let location = index(self.pop()?);
+                    let value = self.storage.get(location).copied().unwrap_or(0);
+                    self.push(value)?;
+                }
+                // WCVTP, WCVTF
+                0x44 | 0x70 => {
+                    let mut value = self.pop()?;
+                    let location = index(self.pop()?);
+                    if opcode == 0x70 {
+                        value = mul_fix(value, self.scale);
+                    }
+                    if let Some(slot) = self.cvt.get_mut(location) {
+                        *slot = value;
+                    }
+                }
+                // RCVT
+                0x45 => {
+                    let location = index(self.pop()?);
+                    let value = self.cvt.get(location).copied().unwrap_or(0);
+                    self.push(value)?;
+                }
+                // GC
+                0x46 | 0x47 => {
+                    let point = self.pop()?;
+                    let zp2 = self.gs.zp2;
+                    let value = match self.point(zp2, point) {
+                        Some(point) if opcode & 1 != 0 => {
+                            dot(self.zones[zp2].original[point], self.gs.dual_vector)
+                        }
+                        Some(point) => {
+                            dot(self.zones[zp2].current[point], self.gs.projection_vector)
+                        }
+                        None => 0,
+                    };
+                    self.push(value)?;
+                }
+                // SCFS
+                0x48 => {
+                    let value = self.pop()?;
+                    let point = self.pop()?;
+                    let zp2 = self.gs.zp2;
+                    if let Some(point) = self.point(zp2, point) {
+                        let current =
+                            dot(self.zones[zp2].current[point], self.gs.projection_vector);
+                        self.move_point(zp2, point, value.wrapping_sub(current));
+                        if zp2 == TWILIGHT {
+                            self.zones[zp2].original[point] = self.zones[zp2].current[point];
+                        }
+                    }
+                }
+                // MD
+                0x49 | 0x4A => {
+                    let p2 = self.pop()?;
+                    let p1 = self.pop()?;
+                    let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+                    let distance = match (self.point(zp0, p1), self.point(zp1, p2)) {
+                        (Some(p1), Some(p2)) if opcode & 1 != 0 => {
+                            self.project(self.zones[zp0].current[p1], self.zones[zp1].current[p2])
+                        }
+                        (Some(p1), Some(p2)) => self.dual_project(
+                            self.zones[zp0].original[p1],
+                            self.zones[zp1].original[p2],
+                        ),
+                        _ => 0,
+                    };
+                    self.push(distance)?;
+                }
+                // MPPEM
+                0x4B => self.push(self.ppem)?,
+                // MPS: v40 reports the ppem like Windows; v35 the point size at 72 dpi.
+                0x4C => {
+                    let size = match self.mode {
+                        HintingMode::V40 => self.ppem,
+                        HintingMode::V35 => self.ppem << 6,
+                    };
+                    self.push(size)?;
+                }
+                0x4D => self.gs.auto_flip = true,
+                0x4E => self.gs.auto_flip = false,
+                // DEBUG, SANGW, AA
+                0x4F | 0x7E | 0x7F => {
+                    self.pop()?;
+                }
+                // LT, LTEQ, GT, GTEQ, EQ, NEQ, AND, OR
+                0x50..=0x55 | 0x5A | 0x5B => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let result = match opcode {
+                        0x50 => a < b,
+                        0x51 => a <= b,
+                        0x52 => a > b,
+                        0x53 => a >= b,
+                        0x54 => a == b,
+                        0x55 => a != b,
+                        0x5A => a != 0 && b != 0,
+                        _ => a != 0 || b != 0,
+                    };
+                    self.push(result as i32)?;
+                }
+                // ODD, EVEN
+                0x56 | 0x57 => {
+                    let value = self.gs.round_mode.round(self.pop()?) & 127;
+                    let result = if opcode == 0x56 {
+                        value == 64
+                    } else {
+                        value == 0
+                    };
+                    self.push(result as i32)?;
+                }
+                // IF
+                0x58 => {
+                    if self.pop()? == 0 {
+                        next = self.skip_branch(cursor, true)?;
+                    }
+                }
+                // EIF
+                0x59 => {}
+                // NOT
+                0x5C => {
+                    let value = self.pop()?;
+                    self.push((value == 0) as i32)?;
+                }
+                // DELTAP1, DELTAP2, DELTAP3
+                0x5D | 0x71 | 0x72 => self.delta_points(opcode)?,
+                // SDB, SDS
+                0x5E => self.gs.delta_base = self.pop()?,
+                0x5F => {
+                    let shift = self.pop()?;
+                    if !(0..=6).contains(&shift) {
+                        return Err(invalid());
+                    }
+                    self.gs.delta_shift = shift;
+                }
+                // ADD, SUB, DIV, MUL, MAX, MIN
+                0x60..=0x63 | 0x8B | 0x8C => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let result = match opcode {
+                        0x60 => a.wrapping_add(b),
+                        0x61 => a.wrapping_sub(b),
+                        0x62 => {
+                            if b == 0 {
+                                return Err(invalid());
+                            }
+                            (a as i64 * 64 / b as i64).clamp(i32::MIN as i64, i32::MAX as i64)
+                                as i32
+                        }
+                        0x63 => mul_div(a, b, 64),
+                        0x8B => a.max(b),
+                        _ => a.min(b),
+                    };
+                    self.push(result)?;
+                }
+                // ABS, NEG, FLOOR, CEILING
+                0x64..=0x67 => {
+                    let value = self.pop()?;
+                    let result = match opcode {
+                        0x64 => value.wrapping_abs(),
+                        0x65 => value.wrapping_neg(),
+                        0x66 => value & !63,
+                        _ => value.wrapping_add(63) & !63,
+                    };
+                    self.push(result)?;
+                }
+                // ROUND; no engine compensation applies to any distance type.
+                0x68..=0x6B => {
+                    let value = self.pop()?;
+                    self.push(self.gs.round_mode.round(value))?;
+                }
+                // NROUND
+                0x6C..=0x6F => {}
+                // DELTAC1, DELTAC2, DELTAC3
+                0x73..=0x75 => self.delta_cvt(opcode)?,
+                // SROUND, S45ROUND
+                0x76 | 0x77 => {
+                    let selector = self.pop()?;
+                    let grid_period = if opcode == 0x76 { 64 } else { 45 };
+                    self.gs.round_mode = RoundMode::parse_super(selector, grid_period);
+                }
+                // JROT, JROF
+                0x78 | 0x79 => {
+                    let condition = self.pop()?;
+                    let offset = self.pop()?;
+                    if (condition != 0) == (opcode == 0x78) {
+                        next = Self::jump(cursor, offset)?;
+                    }
+                }
+                0x7A => self.gs.round_mode = RoundMode::Off,
+                0x7C => self.gs.round_mode = RoundMode::UpToGrid,
+                0x7D => self.gs.round_mode = RoundMode::DownToGrid,
+                // FLIPPT
+                0x80 => {
+                    for _ in 0..self.gs.loop_count {
+                        let point = self.pop()?;
+                        if let Some(point) = self.point(GLYPH, point) {
+                            let on_curve = &mut self.zones[GLYPH].on_curve[point];
+                            *on_curve = !*on_curve;
+                        }
+                    }
+                    self.gs.loop_count = 1;
+                }
+                // FLIPRGON, FLIPRGOFF
+                0x81 | 0x82 => {
+                    let high = index(self.pop()?);
+                    let low = index(self.pop()?);
+                    let zone = &mut self.zones[GLYPH];
+                    if low <= high && high < zone.len() {
+                        zone.on_curve[low..=high].fill(opcode == 0x81);
+                    }
+                }
+                // SCANCTRL
+                0x85 => self.gs.scan_control = self.pop()? & 0xFF != 0,
+                // SDPVTL
+                0x86 | 0x87 => {
+                    let p2 = self.pop()?;
+                    let p1 = self.pop()?;
+                    let perpendicular = opcode & 1 != 0;
+                    if let (Some(dual), Some(projection)) = (
+                        self.line_vector(p1, p2, perpendicular, true),
+                        self.line_vector(p1, p2, perpendicular, false),
+                    ) {
+                        self.gs.dual_vector = dual;
+                        self.gs.projection_vector = projection;
+                    }
+                }
+                // GETINFO
+                0x88 => {
+                    let selector = self.pop()?;
+                    self.push(self.info(selector))?;
+                }
+                // ROLL
+                0x8A => {
+                    let c = self.pop()?;
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.push(b)?;
+                    self.push(c)?;
+                    self.push(a)?;
+                }
+                // SCANTYPE
+                0x8D => {
+                    self.pop()?;
+                }
+                // INSTCTRL
+                0x8E => {
+                    let selector = self.pop()?;
+                    let value = self.pop()?;
+                    if !(1..=3).contains(&selector) {
+                        return Err(invalid());
+                    }
+                    // Only the control value program may change instruction control.
+                    if self.program == Program::ControlValue {
+                        let bit = 1u8 << (selector - 1);
+                        self.gs.instruct_control &= !bit;
+                        if value != 0 {
+                            self.gs.instruct_control |= bit;
+                        }
+                    }
+                }
+                // GETVARIATION
+                0x91 if !self.coords.is_empty() => {
+                    for i in 0..self.coords.len() {
+                        self.push(self.coords[i])?;
+                    }
+                }
+                // GETDATA
+                0x92 => self.push(17)?,
+                // MDRP
+                0xC0..=0xDF => self.move_direct_relative(opcode)?,
+                // MIRP
+                0xE0..=0xFF => self.move_indirect_relative(opcode)?,
+                // Unassigned opcodes run their instruction definition.
+                _ => {
+                    let definition = self.instruction_defs[opcode as usize].ok_or_else(invalid)?;
+                    cursor = Self::call(&mut calls, definition, cursor, next, 1)?;
+                    continue;
+                }
+            }
+            cursor.pc = next;
+        }
+    }
+
+    /// Enters a function, returning the cursor at its first instruction.
+    fn call(
+        calls: &mut Vec<Frame>,
+        definition: Definition,
+        cursor: Cursor,
+        return_pc: usize,
+        count: i32,
+    ) -> Result<Cursor, Error> {
+        if calls.len() >= MAX_CALL_DEPTH {
+            return Err(invalid());
+        }
+        calls.push(Frame {
+            definition,
+            caller: Cursor {
+                pc: return_pc,
+                ..cursor
+            },
+            remaining: count,
+        });
+        Ok(Cursor {
+            program: definition.program,
+            pc: definition.start,
+            end: definition.end + 1,
+        })
+    }
+
+    /// Returns the target of a jump by `offset` bytes from the jump instruction.
+    fn jump(cursor: Cursor, offset: i32) -> Result<usize, Error> {
+        let target = cursor.pc as i64 + offset as i64;
+        if target < 0 || target > cursor.end as i64 {
+            return Err(invalid());
+        }
+        Ok(target as usize)
+    }
+
+    /// Returns the position of the `ENDF` closing the definition at the cursor.
+    fn find_endf(&self, cursor: Cursor) -> Result<usize, Error> {
+        let code = self.code(cursor.program);
+        let mut pc = cursor.pc + instruction_length(code, cursor.pc)?;
+        while pc < cursor.end {
+            match code[pc] {
+                0x2D => return Ok(pc),
+                // Definitions do not nest.
+                0x2C | 0x89 => return Err(invalid()),
+                _ => pc += instruction_length(code, pc)?,
+            }
+        }
+        Err(invalid())
+    }
+
+    /// Skips the branch starting at the cursor, returning the position after
+    /// the matching `EIF`, or after the matching `ELSE` if `stop_at_else`.
+    fn skip_branch(&self, cursor: Cursor, stop_at_else: bool) -> Result<usize, Error> {
+        let code = self.code(cursor.program);
+        let mut pc = cursor.pc + instruction_length(code, cursor.pc)?;
+        let mut depth = 1;
+        while pc < cursor.end {
+            let length = instruction_length(code, pc)?;
+            match code[pc] {
+                0x58 => depth += 1,
+                0x1B if depth == 1 && stop_at_else => return Ok(pc + length),
+                0x59 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(pc + length);
+                    }
+                }
+                _ => {}
+            }
+            pc += length;
+        }
+        Err(invalid())
+    }
+
+    /// Answers `GETINFO`.
+    fn info(&self, selector: i32) -> i32 {
+        let mut result = 0;
+        let v40 = self.mode == HintingMode::V40;
+        if selector & 0x01 != 0 {
+            result = if v40 { 40 } else { 35 };
+        }
+        if selector & 0x08 != 0 && !self.coords.is_empty() {
+            result |= 1 << 10;
+        }
+        if selector & 0x20 != 0 && !v40 {
+            // Grayscale rendering.
+            result |= 1 << 12;
+        }
+        if v40 {
+            if selector & 0x40 != 0 {
+                // ClearType hinting.
+                result |= 1 << 13;
+            }
+            if selector & 0x400 != 0 {
+                // Subpixel positioning.
+                result |= 1 << 17;
+            }
+            if selector & 0x800 != 0 {
+                // Symmetrical smoothing.
+                result |= 1 << 18;
+            }
+        }
+        result
+    }
+
+    /// Returns how far the reference point of `SHP`, `SHC` and `SHZ` moved,
+    /// as a displacement along the freedom vector, with its zone and index.
+    fn reference_displacement(&self, use_rp1: bool) -> Option<(i32, i32, usize, usize)> {
+        let (zone, point) = if use_rp1 {
+            (self.gs.zp0, self.gs.rp1)
+        } else {
+            (self.gs.zp1, self.gs.rp2)
+        };
+        let point = self.point(zone, point as i32)?;
+        let distance = self.project(
+            self.zones[zone].current[point],
+            self.zones[zone].original[point],
+        );
+        let (dx, dy) = self.freedom_displacement(distance);
+        Some((dx, dy, zone, point))
+    }
+
+    /// `ISECT`: moves a point to the intersection of two lines.
+    fn isect(&mut self) -> Result<(), Error> {
+        let b1 = self.pop()?;
+        let b0 = self.pop()?;
+        let a1 = self.pop()?;
+        let a0 = self.pop()?;
+        let point = self.pop()?;
+        let (zp0, zp1, zp2) = (self.gs.zp0, self.gs.zp1, self.gs.zp2);
+        let (Some(point), Some(a0), Some(a1), Some(b0), Some(b1)) = (
+            self.point(zp2, point),
+            self.point(zp1, a0),
+            self.point(zp1, a1),
+            self.point(zp0, b0),
+            self.point(zp0, b1),
+        ) else {
+            return Ok(());
+        };
+        let (pa0, pa1) = (self.zones[zp1].current[a0], self.zones[zp1].current[a1]);
+        let (pb0, pb1) = (self.zones[zp0].current[b0], self.zones[zp0].current[b1]);
+        let (dbx, dby) = (pb1.x - pb0.x, pb1.y - pb0.y);
+        let (dax, day) = (pa1.x - pa0.x, pa1.y - pa0.y);
+        let (dx, dy) = (pb0.x - pa0.x, pb0.y - pa0.y);
+        let discriminant = mul_div(dax, -dby, 0x40) + mul_div(day, dbx, 0x40);
+        let dot_product = mul_div(dax, dbx, 0x40) + mul_div(day, dby, 0x40);
+        // Lines closer than about 3 degrees to parallel meet at the middle.
+        let position = if 19 * discriminant.abs() > dot_product.abs() {
+            let value = mul_div(dx, -dby, 0x40) + mul_div(dy, dbx, 0x40);
+            Point::new(
+                pa0.x + mul_div(value, dax, discriminant),
+                pa0.y + mul_div(value, day, discriminant),
+            )
+        } else {
+            Point::new(
+                (pa0.x + pa1.x + pb0.x + pb1.x) / 4,
+                (pa0.y + pa1.y + pb0.y + pb1.y) / 4,
+            )
+        };
+        let zone = &mut self.zones[zp2];
+        zone.current[point] = position;
+        zone.flags[point] |= TOUCHED_X | TOUCHED_Y;
+        Ok(())
+    }
+
+    /// `ALIGNPTS`: moves two points halfway towards each other.
+    fn align_points(&mut self) -> Result<(), Error> {
+        let p2 = self.pop()?;
+        let p1 = self.pop()?;
+        let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+        if let (Some(p1), Some(p2)) = (self.point(zp1, p1), self.point(zp0, p2)) {
+            let distance =
+                self.project(self.zones[zp0].current[p2], self.zones[zp1].current[p1]) / 2;
+            self.move_point(zp1, p1, distance);
+            self.move_point(zp0, p2, distance.wrapping_neg());
+        }
+        Ok(())
+    }
+
+    /// `IP`: keeps points at their original relative position between the
+    /// reference points 1 and 2.
+    fn interpolate_points(&mut self) -> Result<(), Error> {
+        let (zp0, zp1, zp2) = (self.gs.zp0, self.gs.zp1, self.gs.zp2);
+        let references = (
+            self.point(zp0, self.gs.rp1 as i32),
+            self.point(zp1, self.gs.rp2 as i32),
+        );
+        let (original_base, current_base, original_range, current_range) = match references {
+            (Some(rp1), Some(rp2)) => {
+                let (original, current) =
+                    (self.zones[zp0].original[rp1], self.zones[zp0].current[rp1]);
+                (
+                    original,
+                    current,
+                    self.dual_project(self.zones[zp1].original[rp2], original),
+                    self.project(self.zones[zp1].current[rp2], current),
+                )
+            }
+            _ => (Point::default(), Point::default(), 0, 0),
+        };
+        for _ in 0..self.gs.loop_count {
+            let point = self.pop()?;
+            let Some(point) = self.point(zp2, point) else {
+                continue;
+            };
+            if references.0.is_none() || references.1.is_none() {
+                continue;
+            }
+            let original = self.dual_project(self.zones[zp2].original[point], original_base);
+            let current = self.project(self.zones[zp2].current[point], current_base);
+            let target = match (original, original_range) {
+                (0, _) => 0,
+                (original, 0) => original,
+                (original, range) => mul_div(original, current_range, range),
+            };
+            self.move_point(zp2, point, target.wrapping_sub(current));
+        }
+        self.gs.loop_count = 1;
+        Ok(())
+    }
+
+    /// `IUP`: interpolates the points of each contour not touched along the
+    /// axis between the touched points around them.
+    fn iup(&mut self, x_axis: bool) {
+        if self.backward_compatibility {
+            // Only the first IUP on each axis runs.
+            if self.iup_x_called && self.iup_y_called {
+                return;
+            }
+            if x_axis {
+                self.iup_x_called = true;
+            } else {
+                self.iup_y_called = true;
+            }
+        }
+        let flag = if x_axis { TOUCHED_X } else { TOUCHED_Y };
+        let zone = &mut self.zones[GLYPH];
+        let coordinate = |p: Point| if x_axis { p.x } else { p.y };
+        let mut start = 0;
+        for contour in 0..zone.contour_ends.len() {
+            let end = zone.contour_ends[contour];
+            if end < start || end >= zone.len() {
+                break;
+            }
+            let touched: Vec<usize> = (start..=end)
+                .filter(|&point| zone.flags[point] & flag != 0)
+                .collect();
+            match touched[..] {
+                [] => {}
+                [only] => {
+                    let delta = coordinate(zone.current[only]) - coordinate(zone.original[only]);
+                    for point in (start..=end).filter(|&point| point != only) {
+                        let value = coordinate(zone.original[point]) + delta;
+                        set_coordinate(&mut zone.current[point], x_axis, value);
+                    }
+                }
+                _ => {
+                    for (i, &first) in touched.iter().enumerate() {
+                        let second = touched[(i + 1) % touched.len()];
+                        // Points strictly between the pair, wrapping around the contour.
+                        let mut point = first;
+                        loop {
+                            point = if point == end { start } else { point + 1 };
+                            if point == second {
+                                break;
+                            }
+                            interpolate(zone, x_axis, point, first, second);
+                        }
+                    }
+                }
+            }
+            start = end + 1;
+        }
+    }
+
+    /// `DELTAP1`-`DELTAP3`: moves points at one size.
+    fn delta_points(&mut self, opcode: u8) -> Result<(), Error> {
+        let count = self.pop()?;
+        if count < 0 {
+            return Err(invalid());
+        }
+        let range = match opcode {
+            0x5D => 0,
+            0x71 => 16,
+            _ => 32,
+        };
+        let zp0 = self.gs.zp0;
+        for _ in 0..count {
+            let point = self.pop()?;
+            let argument = self.pop()?;
+            let Some(point) = self.point(zp0, point) else {
+                continue;
+            };
+            let Some(distance) = self.delta_distance(argument, range) else {
+                continue;
+            };
+            if self.backward_compatibility {
+                let touched_y = self.zones[zp0].flags[point] & TOUCHED_Y != 0;
+                if !(self.iup_x_called && self.iup_y_called)
+                    && ((self.is_composite && self.gs.freedom_vector.y != 0) || touched_y)
+                {
+                    self.move_point(zp0, point, distance);
+                }
+            } else {
+                self.move_point(zp0, point, distance);
+            }
+        }
+        Ok(())
+    }
+
+    /// `DELTAC1`-`DELTAC3`: changes control values at one size.
+    fn delta_cvt(&mut self, opcode: u8) -> Result<(), Error> {
+        let count = self.pop()?;
+        if count < 0 {
+            return Err(invalid());
+        }
+        let range = (opcode - 0x73) as i32 * 16;
+        for _ in 0..count {
+            let location = index(self.pop()?);
+            let argument = self.pop()?;
+            if let Some(distance) = self.delta_distance(argument, range)
+                && let Some(value) = self.cvt.get_mut(location)
+            {
+                *value = value.wrapping_add(distance);
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes a delta argument: the distance if it applies at the current
+    /// size.
+    fn delta_distance(&self, argument: i32, range: i32) -> Option<i32> {
+        let ppem = ((argument & 0xF0) >> 4) + self.gs.delta_base + range;
+        if ppem != self.ppem {
+            return None;
+        }
+        let mut steps = (argument & 0x0F) - 8;
+        if steps >= 0 {
+            steps += 1;
+        }
+        Some(steps * 64 / (1 << self.gs.delta_shift))
+    }
+
+    /// `MDRP`: keeps a point at its original distance from reference point 0.
+    fn move_direct_relative(&mut self, opcode: u8) -> Result<(), Error> {
+        let point = self.pop()?;
+        let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+        let (Some(point), Some(rp0)) =
+            (self.point(zp1, point), self.point(zp0, self.gs.rp0 as i32))
+        else {
+            return Ok(());
+        };
+        let mut original = self.dual_project(
+            self.zones[zp1].original[point],
+            self.zones[zp0].original[rp0],
+        );
+        let single_width = self.gs.single_width_value;
+        if (original - single_width).abs() < self.gs.single_width_cut_in {
+            original = if original >= 0 {
+                single_width
+            } else {
+                -single_width
+            };
+        }
+        let mut distance = if opcode & 0x04 != 0 {
+            self.gs.round_mode.round(original)
+        } else {
+            original
+        };
+        if opcode & 0x08 != 0 {
+            distance = self.keep_min_distance(original, distance);
+        }
+        let current = self.project(self.zones[zp1].current[point], self.zones[zp0].current[rp0]);
+        self.move_point(zp1, point, distance.wrapping_sub(current));
+        self.gs.rp1 = self.gs.rp0;
+        self.gs.rp2 = point;
+        if opcode & 0x10 != 0 {
+            self.gs.rp0 = point;
+        }
+        Ok(())
+    }
+
+    /// `MIRP`: moves a point to a control value distance from reference point 0.
+    fn move_indirect_relative(&mut self, opcode: u8) -> Result<(), Error> {
+        let cvt = self.pop()?;
+        let point = self.pop()?;
+        let (zp0, zp1) = (self.gs.zp0, self.gs.zp1);
+        let (Some(point), Some(rp0)) =
+            (self.point(zp1, point), self.point(zp0, self.gs.rp0 as i32))
+        else {
+            return Ok(());
+        };
+        // -1 selects a zero distance.
+        let mut cvt_distance = match cvt {
+            -1 => 0,
+            cvt => *self.cvt.get(index(cvt)).ok_or_else(invalid)?,
+        };
+        let single_width = self.gs.single_width_value;
+        if (cvt_distance - single_width).abs() < self.gs.single_width_cut_in {
+            cvt_distance = if cvt_distance >= 0 {
+                single_width
+            } else {
+                -single_width
+            };
+        }
+        if zp1 == TWILIGHT {
+            let fv = self.gs.freedom_vector;
+            let base = self.zones[zp0].original[rp0];
+            let position = Point::new(
+                base.x.wrapping_add(mul14(cvt_distance, fv.x)),
+                base.y.wrapping_add(mul14(cvt_distance, fv.y)),
+            );
+            self.zones[zp1].original[point] = position;
+            self.zones[zp1].current[point] = position;
+        }
+        let original = self.dual_project(
+            self.zones[zp1].original[point],
+            self.zones[zp0].original[rp0],
+        );
+        let current = self.project(self.zones[zp1].current[point], self.zones[zp0].current[rp0]);
+        if self.gs.auto_flip && (original ^ cvt_distance) < 0 {
+            cvt_distance = cvt_distance.wrapping_neg();
+        }
+        let mut distance = if opcode & 0x04 != 0 {
+            if zp0 == zp1
+                && (cvt_distance.wrapping_sub(original)).abs() > self.gs.control_value_cut_in
+            {
+                cvt_distance = original;
+            }
+            self.gs.round_mode.round(cvt_distance)
+        } else {
+            cvt_distance
+        };
+        if opcode & 0x08 != 0 {
+            distance = self.keep_min_distance(original, distance);
+        }
+        self.move_point(zp1, point, distance.wrapping_sub(current));
+        self.gs.rp1 = self.gs.rp0;
+        self.gs.rp2 = point;
+        if opcode & 0x10 != 0 {
+            self.gs.rp0 = point;
+        }
+        Ok(())
+    }
+
+    /// Applies the minimum distance in the direction of `original`.
+    fn keep_min_distance(&self, original: i32, distance: i32) -> i32 {
+        let min_distance = self.gs.min_distance;
+        if original >= 0 {
+            distance.max(min_distance)
+        } else {
+            distance.min(min_distance.wrapping_neg())
+        }
+    }
+}
+
+/// Sets the x or y coordinate of a point.
+fn set_coordinate(point: &mut Point, x_axis: bool, value: i32) {
+    if x_axis {
+        point.x = value;
+    } else {
+        point.y = value;
+    }
+}
+
+/// Moves an untouched point of the glyph zone in proportion to the touched
+/// points `first` and `second` along one axis.
+fn interpolate(zone: &mut Zone, x_axis: bool, point: usize, first: usize, second: usize) {
+    let coordinate = |p: Point| if x_axis { p.x } else { p.y };
+    let (mut original1, mut current1) = (
+        coordinate(zone.original[first]),
+        coordinate(zone.current[first]),
+    );
+    let (mut original2, mut current2) = (
+        coordinate(zone.original[second]),
+        coordinate(zone.current[second]),
+    );
+    if original1 > original2 {
+        std::mem::swap(&mut original1, &mut original2);
+        std::mem::swap(&mut current1, &mut current2);
+    }
+    let original = coordinate(zone.original[point]);
+    let value = if original <= original1 {
+        original + (current1 - original1)
+    } else if original >= original2 {
+        original + (current2 - original2)
+    } else {
+        current1
+            + mul_div(
+                original - original1,
+                current2 - current1,
+                original2 - original1,
+            )
+    };
+    set_coordinate(&mut zone.current[point], x_axis, value);
+}
@@ -0,0 +1,421 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! TrueType hinting. The font program (`fpgm`) runs once, the control value
+//! program (`prep`) whenever the size changes, and glyph instructions from
+//! `glyf` fit each outline to the pixel grid. The interpreter follows the
+//! FreeType semantics, including its v40 mode where the horizontal direction
+//! is left unhinted for ClearType-style rendering.
+//!
+//! Execution is bounded by the `maxp` limits: stack depth, storage, function
+//! and instruction definitions, twilight points, instruction size and
+//! component depth.
+
+mod engine;
+mod state;
+
+use crate::components::glyf::{
+    GlyfTable, Glyph, ROUND_XY_TO_GRID, SCALED_COMPONENT_OFFSET, USE_MY_METRICS,
+};
+use crate::components::gvar::{PHANTOM_POINT_COUNT, phantom_points};
+use crate::components::hmtx::HmtxTable;
+use crate::components::maxp::MaxpTrueType;
+use crate::error::{Error, IoError};
+use crate::outline::{PathCommand, contour_path};
+
+use engine::{Engine, Program};
+use state::{GraphicsState, Point, Zone, mul_fix};
+
+/// Stack slack beyond `maxStackElements`; many fonts understate their needs.
+const STACK_SLACK: usize = 32;
+
+/// Interpreter version, which decides how horizontal movements are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HintingMode {
+    /// Classic hinting in both directions.
+    V35,
+    /// Vertical-only hinting for subpixel rendering. Fonts that do not declare
+    /// native ClearType support through `INSTCTRL` run in a backward
+    /// compatibility mode that ignores horizontal movements.
+    #[default]
+    V40,
+}
+
+/// A hinted outline point in 26.6 fixed-point pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HintedPoint {
+    /// Horizontal position.
+    pub x: i32,
+    /// Vertical position.
+    pub y: i32,
+    /// Whether the point is on the curve.
+    pub on_curve: bool,
+}
+
+/// A hinted glyph outline.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HintedGlyph {
+    /// Outline points, with composite components resolved.
+    pub points: Vec<HintedPoint>,
+    /// Index of the last point of each contour.
+    pub end_points: Vec<u16>,
+    /// Hinted phantom points: left and right side bearing, top and bottom.
+    pub phantoms: [(i32, i32); PHANTOM_POINT_COUNT],
+}
+
+impl HintedGlyph {
+    /// Returns the hinted advance width in 26.6 pixels.
+    pub fn advance_width(&self) -> i32 {
+        self.phantoms[1].0 - self.phantoms[0].0
+    }
+
+    /// Returns the outline in pixels.
+    pub fn path(&self) -> Vec<PathCommand> {
+        let points: Vec<(f32, f32, bool)> = self
+            .points
+            .iter()
+            .map(|p| (p.x as f32 / 64.0, p.y as f32 / 64.0, p.on_curve))
+            .collect();
+        let end_points: Vec<usize> = self.end_points.iter().map(|&end| end as usize).collect();
+        contour_path(&points, &end_points)
+    }
+}
+
+/// Interpreter state after `prep` ran, restored before each glyph.
+#[derive(Debug, Clone)]
+struct SizeState {
+    gs: GraphicsState,
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    twilight: Zone,
+}
+
+/// Hints TrueType glyphs at one size.
+#[derive(Debug, Clone)]
+pub struct Hinter {
+    engine: Engine,
+    units_per_em: u16,
+    /// Control values in font units.
+    cvt: Vec<f32>,
+    twilight_points: usize,
+    max_instructions: usize,
+    max_component_depth: usize,
+    /// Storage written by `fpgm`.
+    font_storage: Vec<i32>,
+    size: Option<SizeState>,
+}
+
+/// Returns the error raised by malformed instructions and glyphs.
+fn invalid() -> Error {
+    Error::Io(IoError::InvalidData)
+}
+
+impl Hinter {
+    /// Creates a hinter and runs the font program. `cvt` holds the control
+    /// values in font units; variable fonts pass `CvtTable::cvt_at` for the
+    /// instance.
+    pub fn new(
+        fpgm: &[u8],
+        prep: &[u8],
+        cvt: &[f32],
+        maxp: &MaxpTrueType,
+        units_per_em: u16,
+        mode: HintingMode,
+    ) -> Result<Hinter, Error> {
+        if units_per_em == 0 {
+            return Err(invalid());
+        }
+        let mut engine = Engine::new(mode);
+        engine.fpgm = fpgm.to_vec();
+        engine.prep = prep.to_vec();
+        engine.functions = vec![None; maxp.max_function_defs as usize];
+        engine.max_instruction_defs = maxp.max_instruction_defs as usize;
+        engine.stack_limit = maxp.max_stack_elements as usize + STACK_SLACK;
+        engine.storage = vec![0; maxp.max_storage as usize];
+        // Twilight points are followed by the four phantom points of the zone.
+        let twilight_points = maxp.max_twilight_points as usize + PHANTOM_POINT_COUNT;
+        engine.zones[0] = Zone::twilight(twilight_points);
+        engine.execute(Program::Font)?;
+        Ok(Hinter {
+            font_storage: engine.storage.clone(),
+            engine,
+            units_per_em,
+            cvt: cvt.to_vec(),
+            twilight_points,
+            max_instructions: maxp.max_size_of_instructions as usize,
+            max_component_depth: maxp.max_component_depth.max(1) as usize,
+            size: None,
+        })
+    }
+
+    /// Sets the normalized variation coordinates reported to `GETVARIATION`.
+    pub fn with_coords(mut self, coords: &[f32]) -> Hinter {
+        self.engine.coords = coords
+            .iter()
+            .map(|&c| (c.clamp(-1.0, 1.0) * 16384.0).round() as i32)
+            .collect();
+        self
+    }
+
+    /// Returns the current size in pixels per em, 0 before `set_ppem`.
+    pub fn ppem(&self) -> u16 {
+        self.engine.ppem as u16
+    }
+
+    /// Scales the control values to `ppem` pixels per em and runs the
+    /// control value program.
+    pub fn set_ppem(&mut self, ppem: u16) -> Result<(), Error> {
+        if ppem == 0 {
+            return Err(invalid());
+        }
+        let engine = &mut self.engine;
+        // Font units to 26.6 pixels, in 16.16.
+        engine.scale = i32::try_from(((ppem as i64) << 22) / self.units_per_em as i64)
+            .map_err(|_| invalid())?;
+        engine.ppem = ppem as i32;
+        let factor = ppem as f64 * 64.0 / self.units_per_em as f64;
+        engine.cvt = self
+            .cvt
+            .iter()
+            .map(|&value| (value as f64 * factor).round() as i32)
+            .collect();
+        engine.storage = self.font_storage.clone();
+        engine.zones = [Zone::twilight(self.twilight_points), Zone::default()];
+        engine.gs = GraphicsState::default();
+        engine.is_composite = false;
+        self.size = None;
+        engine.execute(Program::ControlValue)?;
+
+        // Only the settings `prep` may change for glyphs carry over; glyph
+        // programs start on the x axis with the reference points and zone
+        // pointers at their defaults, as in FreeType.
+        let defaults = GraphicsState::default();
+        let gs = GraphicsState {
+            dual_vector: defaults.dual_vector,
+            freedom_vector: defaults.freedom_vector,
+            projection_vector: defaults.projection_vector,
+            loop_count: defaults.loop_count,
+            rp0: defaults.rp0,
+            rp1: defaults.rp1,
+            rp2: defaults.rp2,
+            zp0: defaults.zp0,
+            zp1: defaults.zp1,
+            zp2: defaults.zp2,
+            ..engine.gs
+        };
+        self.size = Some(SizeState {
+            gs,
+            cvt: engine.cvt.clone(),
+            storage: engine.storage.clone(),
+            twilight: engine.zones[0].clone(),
+        });
+        Ok(())
+    }
+
+    /// Hints a glyph at the current size. Variable fonts pass outlines
+    /// already instanced to the coordinates.
+    pub fn hint_glyph(
+        &mut self,
+        glyf: &GlyfTable,
+        hmtx: &HmtxTable,
+        glyph_id: u16,
+    ) -> Result<HintedGlyph, Error> {
+        if self.size.is_none() {
+            return Err(invalid());
+        }
+        let outline = self.load(glyf, hmtx, glyph_id, 0)?;
+        let points = outline.points[..outline.points.len() - PHANTOM_POINT_COUNT]
+            .iter()
+            .zip(&outline.on_curve)
+            .map(|(point, &on_curve)| HintedPoint {
+                x: point.x,
+                y: point.y,
+                on_curve,
+            })
+            .collect();
+        let phantoms = outline.points[outline.points.len() - PHANTOM_POINT_COUNT..]
+            .iter()
+            .map(|point| (point.x, point.y));
+        let mut phantom_array = [(0, 0); PHANTOM_POINT_COUNT];
+        for (slot, phantom) in phantom_array.iter_mut().zip(phantoms) {
+            *slot = phantom;
+        }
+        Ok(HintedGlyph {
+            points,
+            end_points: outline.contour_ends.iter().map(|&end| end as u16).collect(),
+            phantoms: phantom_array,
+        })
+    }
+
+    /// Loads and hints a glyph, returning its points followed by its phantom
+    /// points.
+    fn load(
+        &mut self,
+        glyf: &GlyfTable,
+        hmtx: &HmtxTable,
+        glyph_id: u16,
+        depth: usize,
+    ) -> Result<Outline, Error> {
+        let glyph = glyf.glyph(glyph_id).ok_or_else(invalid)?;
+        let scale = self.engine.scale;
+        let mut phantoms = phantom_points(glyph, hmtx, None, glyph_id).map(|(x, y)| {
+            Point::new(
+                mul_fix(x.round() as i32, scale),
+                mul_fix(y.round() as i32, scale),
+            )
+        });
+        match glyph {
+            Glyph::Empty => self.hint(Outline::default(), phantoms, &[], false),
+            Glyph::Simple(simple) => {
+                let outline = Outline {
+                    points: simple
+                        .points
+                        .iter()
+                        .map(|p| Point::new(mul_fix(p.x as i32, scale), mul_fix(p.y as i32, scale)))
+                        .collect(),
+                    on_curve: simple.points.iter().map(|p| p.on_curve).collect(),
+                    contour_ends: simple.end_points.iter().map(|&end| end as usize).collect(),
+                };
+                self.hint(outline, phantoms, &simple.instructions, false)
+            }
+            Glyph::Composite(composite) => {
+                if depth >= self.max_component_depth {
+                    return Err(invalid());
+                }
+                let mut outline = Outline::default();
+                for component in &composite.components {
+                    let mut child = self.load(glyf, hmtx, component.glyph_index, depth + 1)?;
+                    let child_phantoms = child
+                        .points
+                        .split_off(child.points.len() - PHANTOM_POINT_COUNT);
+                    let [xx, yx, xy, yy] = component.transform.matrix();
+                    let transform = |p: Point| {
+                        let (x, y) = (p.x as f32, p.y as f32);
+                        Point::new(
+                            (xx * x + xy * y).round() as i32,
+                            (yx * x + yy * y).round() as i32,
+                        )
+                    };
+                    let transformed = component.transform.matrix() != [1.0, 0.0, 0.0, 1.0];
+                    if transformed {
+                        child.points.iter_mut().for_each(|p| *p = transform(*p));
+                    }
+                    let offset = if component.args_are_xy_values() {
+                        let mut offset = Point::new(
+                            mul_fix(component.arg1, scale),
+                            mul_fix(component.arg2, scale),
+                        );
+                        if transformed && component.flags & SCALED_COMPONENT_OFFSET != 0 {
+                            offset = transform(offset);
+                        }
+                        if component.flags & ROUND_XY_TO_GRID != 0 {
+                            // v40 leaves horizontal positions unhinted.
+                            if self.engine.mode == HintingMode::V35 {
+                                offset.x = (offset.x + 32) & !63;
+                            }
+                            offset.y = (offset.y + 32) & !63;
+                        }
+                        offset
+                    } else {
+                        // Point matching: the child point lands on the parent point.
+                        let parent = outline.points.get(component.arg1 as usize);
+                        let anchor = child.points.get(component.arg2 as usize);
+                        let (parent, anchor) = parent.zip(anchor).ok_or_else(invalid)?;
+                        parent.sub(*anchor)
+                    };
+                    if component.flags & USE_MY_METRICS != 0 {
+                        phantoms.copy_from_slice(&child_phantoms);
+                    }
+                    let base = outline.points.len();
+                    outline.points.extend(
+                        child
+                            .points
+                            .iter()
+                            .map(|p| Point::new(p.x + offset.x, p.y + offset.y)),
+                    );
+                    outline.on_curve.extend(child.on_curve);
+                    outline
+                        .contour_ends
+                        .extend(child.contour_ends.iter().map(|end| end + base));
+                }
+                self.hint(outline, phantoms, &composite.instructions, true)
+            }
+        }
+    }
+
+    /// Runs glyph instructions over an outline. Returns the hinted points
+    /// followed by the phantom points.
+    fn hint(
+        &mut self,
+        mut outline: Outline,
+        phantoms: [Point; PHANTOM_POINT_COUNT],
+        instructions: &[u8],
+        is_composite: bool,
+    ) -> Result<Outline, Error> {
+        if instructions.len() > self.max_instructions {
+            return Err(invalid());
+        }
+        let original_phantoms = phantoms;
+        let mut phantoms = phantoms;
+        // The advance and vertical metrics snap to whole pixels.
+        phantoms[0].x = (phantoms[0].x + 32) & !63;
+        phantoms[1].x = (phantoms[1].x + 32) & !63;
+        phantoms[2].y = (phantoms[2].y + 32) & !63;
+        phantoms[3].y = (phantoms[3].y + 32) & !63;
+
+        let size = self.size.as_ref().ok_or_else(invalid)?;
+        if instructions.is_empty() || size.gs.instruct_control & 1 != 0 {
+            outline.points.extend(phantoms);
+            return Ok(outline);
+        }
+
+        let engine = &mut self.engine;
+        engine.gs = if size.gs.instruct_control & 2 != 0 {
+            GraphicsState {
+                instruct_control: size.gs.instruct_control,
+                ..GraphicsState::default()
+            }
+        } else {
+            size.gs
+        };
+        engine.cvt.clone_from(&size.cvt);
+        engine.storage.clone_from(&size.storage);
+        engine.zones[0] = size.twilight.clone();
+
+        let mut original = outline.points.clone();
+        original.extend(original_phantoms);
+        let mut current = outline.points;
+        current.extend(phantoms);
+        let mut on_curve = outline.on_curve;
+        on_curve.extend([true; PHANTOM_POINT_COUNT]);
+        // Composites are hinted from their already hinted components.
+        let mut zone = Zone::glyph(current, on_curve, outline.contour_ends);
+        if !is_composite {
+            zone.original = original;
+        }
+        engine.zones[1] = zone;
+        engine.glyph = instructions.to_vec();
+        engine.is_composite = is_composite;
+        engine.execute(Program::Glyph)?;
+
+        let zone = std::mem::take(&mut engine.zones[1]);
+        let mut on_curve = zone.on_curve;
+        on_curve.truncate(on_curve.len() - PHANTOM_POINT_COUNT);
+        Ok(Outline {
+            points: zone.current,
+            on_curve,
+            contour_ends: zone.contour_ends,
+        })
+    }
+}
+
+/// Points of a glyph being loaded, in 26.6 pixels.
+#[derive(Debug, Clone, Default)]
+struct Outline {
+    points: Vec<Point>,
+    on_curve: Vec<bool>,
+    contour_ends: Vec<usize>,
+}
@@ -0,0 +1,286 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! State of the TrueType virtual machine: points and zones, the graphics
+//! state and rounding. Coordinates are 26.6 fixed-point pixels and vectors
+//! are 2.14 fixed-point unit vectors.
+
+/// The point was moved along the x axis.
+pub(crate) const TOUCHED_X: u8 = 0x01;
+/// The point was moved along the y axis.
+pub(crate) const TOUCHED_Y: u8 = 0x02;
+
+/// A point in 26.6 pixels, or a vector in 2.14.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub(crate) const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub(crate) fn sub(self, other: Point) -> Point {
+        Point::new(self.x.wrapping_sub(other.x), self.y.wrapping_sub(other.y))
+    }
+}
+
+/// The unit vector along the x axis.
+pub(crate) const X_AXIS: Point = Point::new(0x4000, 0);
+/// The unit vector along the y axis.
+pub(crate) const Y_AXIS: Point = Point::new(0, 0x4000);
+
+/// A set of points the instructions operate on: the twilight zone (0) or the
+/// glyph zone (1).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Zone {
+    /// Positions before hinting.
+    pub original: Vec<Point>,
+    /// Positions being hinted.
+    pub current: Vec<Point>,
+    /// `TOUCHED_X` and `TOUCHED_Y` flags.
+    pub flags: Vec<u8>,
+    /// Whether each point is on the curve.
+    pub on_curve: Vec<bool>,
+    /// Index of the last point of each contour.
+    pub contour_ends: Vec<usize>,
+}
+
+impl Zone {
+    /// Returns a zone of `count` points at the origin.
+    pub(crate) fn twilight(count: usize) -> Zone {
+        Zone {
+            original: vec![Point::default(); count],
+            current: vec![Point::default(); count],
+            flags: vec![0; count],
+            on_curve: vec![true; count],
+            contour_ends: Vec::new(),
+        }
+    }
+
+    /// Returns a zone holding `points` as both original and current positions.
+    pub(crate) fn glyph(points: Vec<Point>, on_curve: Vec<bool>, contour_ends: Vec<usize>) -> Zone {
+        Zone {
+            original: points.clone(),
+            flags: vec![0; points.len()],
+            current: points,
+            on_curve,
+            contour_ends,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.current.len()
+    }
+}
+
+/// Rounding applied to distances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RoundMode {
+    Grid,
+    HalfGrid,
+    DoubleGrid,
+    DownToGrid,
+    UpToGrid,
+    Off,
+    /// `SROUND` and `S45ROUND` rounding.
+    Super {
+        period: i32,
+        phase: i32,
+        threshold: i32,
+    },
+}
+
+impl RoundMode {
+    /// Parses the `SROUND` selector; `grid_period` is one pixel for
+    /// `SROUND` and `sqrt(2)/2` pixels for `S45ROUND`.
+    pub(crate) fn parse_super(selector: i32, grid_period: i32) -> RoundMode {
+        let period = match (selector >> 6) & 3 {
+            0 => grid_period / 2,
+            2 => grid_period * 2,
+            _ => grid_period,
+        };
+        let phase = match (selector >> 4) & 3 {
+            0 => 0,
+            1 => period / 4,
+            2 => period / 2,
+            _ => period * 3 / 4,
+        };
+        let threshold = match selector & 0x0F {
+            0 => period - 1,
+            n => (n - 4) * period / 8,
+        };
+        RoundMode::Super {
+            period,
+            phase,
+            threshold,
+        }
+    }
+
+    /// Rounds a 26.6 distance. The sign of the distance is kept; values that
+    /// would change sign become zero.
+    pub(crate) fn round(self, distance: i32) -> i32 {
+        let d = distance;
+        match self {
+            RoundMode::Grid => {
+                if d >= 0 {
+                    (d.saturating_add(32) & !63).max(0)
+                } else {
+                    (-(d.saturating_neg().saturating_add(32) & !63)).min(0)
+                }
+            }
+            RoundMode::HalfGrid => {
+                if d >= 0 {
+                    ((d & !63).saturating_add(32)).max(0)
+                } else {
+                    (-((d.saturating_neg() & !63).saturating_add(32))).min(0)
+                }
+            }
+            RoundMode::DoubleGrid => {
+                if d >= 0 {
+                    (d.saturating_add(16) & !31).max(0)
+                } else {
+                    (-(d.saturating_neg().saturating_add(16) & !31)).min(0)
+                }
+            }
+            RoundMode::DownToGrid => {
+                if d >= 0 {
+                    d & !63
+                } else {
+                    -(d.saturating_neg() & !63)
+                }
+            }
+            RoundMode::UpToGrid => {
+                if d >= 0 {
+                    d.saturating_add(63) & !63
+                } else {
+                    -(d.saturating_neg().saturating_add(63) & !63)
+                }
+            }
+            RoundMode::Off => d,
+            RoundMode::Super {
+                period,
+                phase,
+                threshold,
+            } => {
+                let (d, period, phase, threshold) = (
+                    d as i64,
+                    period.max(1) as i64,
+                    phase as i64,
+                    threshold as i64,
+                );
+                let value = if d >= 0 {
+                    let value = (d - phase + threshold).div_euclid(period) * period + phase;
+                    if value < 0 { phase } else { value }
+                } else {
+                    let value = -((-d - phase + threshold).div_euclid(period) * period) - phase;
+                    if value > 0 { -phase } else { value }
+                };
+                value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+            }
+        }
+    }
+}
+
+/// Graphics state of the virtual machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GraphicsState {
+    pub auto_flip: bool,
+    pub control_value_cut_in: i32,
+    pub delta_base: i32,
+    pub delta_shift: i32,
+    pub dual_vector: Point,
+    pub freedom_vector: Point,
+    pub projection_vector: Point,
+    pub instruct_control: u8,
+    pub loop_count: i32,
+    pub min_distance: i32,
+    pub round_mode: RoundMode,
+    pub rp0: usize,
+    pub rp1: usize,
+    pub rp2: usize,
+    pub scan_control: bool,
+    pub single_width_cut_in: i32,
+    pub single_width_value: i32,
+    pub zp0: usize,
+    pub zp1: usize,
+    pub zp2: usize,
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        GraphicsState {
+            auto_flip: true,
+            // 17/16 pixel.
+            control_value_cut_in: 68,
+            delta_base: 9,
+            delta_shift: 3,
+            dual_vector: X_AXIS,
+            freedom_vector: X_AXIS,
+            projection_vector: X_AXIS,
+            instruct_control: 0,
+            loop_count: 1,
+            min_distance: 64,
+            round_mode: RoundMode::Grid,
+            rp0: 0,
+            rp1: 0,
+            rp2: 0,
+            scan_control: false,
+            single_width_cut_in: 0,
+            single_width_value: 0,
+            zp0: 1,
+            zp1: 1,
+            zp2: 1,
+        }
+    }
+}
+
+/// Computes `a * b / c` rounded to nearest, saturating on division by zero.
+pub(crate) fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+    if c == 0 {
+        return if (a as i64 * b as i64) < 0 {
+            i32::MIN
+        } else {
+            i32::MAX
+        };
+    }
+    let (product, divisor) = (a as i64 * b as i64, c as i64);
+    let quotient = (product.abs() + divisor.abs() / 2) / divisor.abs();
+    let rounded = if (product < 0) != (divisor < 0) {
+        -quotient
+    } else {
+        quotient
+    };
+    rounded.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// Multiplies a 26.6 value by a 2.14 factor.
+pub(crate) fn mul14(a: i32, b: i32) -> i32 {
+    let product = a as i64 * b as i64;
+    ((product + 0x2000 * product.signum()) / 0x4000) as i32
+}
+
+/// Multiplies a value by a 16.16 factor.
+pub(crate) fn mul_fix(a: i32, b: i32) -> i32 {
+    let product = a as i64 * b as i64;
+    ((product + 0x8000 * product.signum()) / 0x10000).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// Returns `(x, y)` scaled to a 2.14 unit vector, or the x axis for a zero
+/// vector.
+pub(crate) fn normalize(x: i32, y: i32) -> Point {
+    if x == 0 && y == 0 {
+        return X_AXIS;
+    }
+    let (x, y) = (x as f64, y as f64);
+    let length = (x * x + y * y).sqrt();
+    Point::new(
+        (x * 16384.0 / length).round() as i32,
+        (y * 16384.0 / length).round() as i32,
+    )
+}
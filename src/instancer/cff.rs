@@ -0,0 +1,258 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Instancing of `CFF2` outlines. Charstrings are flattened and their blends
+//! rewritten against the instanced variation store; subroutines are dropped.
+
+use crate::components::cff1::{CharStringToken, Operand, write_char_string};
+use crate::components::cff2::{
+    Blend, CS_BLEND, Cff2Table, flatten_char_string, rewrite_char_string_blends,
+    rewrite_dict_blends,
+};
+use crate::components::variation::{ItemVariationData, ItemVariationStore};
+use crate::error::Error;
+use crate::io::writer::FontDataWriter;
+use crate::outline::{PathCommand, char_string_path};
+
+use super::solver::{NormalizedLimit, StoreRebase, remaining_axis_count};
+
+/// Result of instancing a `CFF2` table.
+pub(crate) struct InstancedCff2 {
+    /// The instanced table.
+    pub cff2: Cff2Table,
+    /// Bounding box of every glyph at the default of the instanced font, or
+    /// `None` for glyphs without contours.
+    pub bounds: Vec<Option<(i16, i16, i16, i16)>>,
+}
+
+/// Rebased store of a `CFF2` table: item variation data keep their indices
+/// and hold no rows, since the deltas live in the charstrings.
+struct Cff2Rebase {
+    rebase: StoreRebase,
+    store: Option<ItemVariationStore>,
+}
+
+impl Cff2Rebase {
+    fn new(cff2: &Cff2Table, limits: &[NormalizedLimit]) -> Option<Self> {
+        let store = cff2.variation_store.as_ref()?;
+        let rebase = StoreRebase::new(store, limits);
+        let data: Vec<ItemVariationData> = store
+            .data
+            .iter()
+            .map(|data| ItemVariationData {
+                region_indexes: rebase.rebase_rows(&data.region_indexes, &[]).0,
+                delta_sets: Vec::new(),
+            })
+            .collect();
+        let varies = data.iter().any(|data| !data.region_indexes.is_empty());
+        let store = varies.then(|| ItemVariationStore {
+            format: store.format,
+            axis_count: remaining_axis_count(limits) as u16,
+            regions: rebase.regions.clone(),
+            data,
+        });
+        Some(Cff2Rebase { rebase, store })
+    }
+
+    /// Returns the rebased defaults of a blend, followed by its rebased deltas
+    /// when the instanced store still varies.
+    fn rebase_blend(&self, cff2: &Cff2Table, blend: &Blend) -> (Vec<f64>, Option<Vec<f64>>) {
+        let region_indexes = cff2
+            .variation_store
+            .as_ref()
+            .and_then(|store| store.data.get(blend.vsindex as usize))
+            .map_or(&[][..], |data| data.region_indexes.as_slice());
+        let rows: Vec<Vec<f64>> = blend.deltas.iter().map(|row| row.to_vec()).collect();
+        let (columns, rows, defaults) = self.rebase.rebase_rows(region_indexes, &rows);
+        let values: Vec<f64> = blend
+            .defaults
+            .iter()
+            .zip(defaults.iter().chain(std::iter::repeat(&0.0)))
+            .map(|(value, delta)| to_fixed(value + delta))
+            .collect();
+        if self.store.is_none() || columns.is_empty() {
+            return (values, None);
+        }
+        (
+            values,
+            Some(rows.into_iter().flatten().map(to_fixed).collect()),
+        )
+    }
+}
+
+/// Rounds a value to the 16.16 precision of charstring and DICT numbers.
+fn to_fixed(value: f64) -> f64 {
+    (value * 65536.0).round() / 65536.0
+}
+
+fn operand(value: f64) -> Operand {
+    match value.fract() == 0.0 {
+        true => Operand::Integer(value as i32),
+        false => Operand::Real(value),
+    }
+}
+
+/// Instances a `CFF2` table.
+pub(crate) fn instance_cff2(
+    cff2: &Cff2Table,
+    limits: &[NormalizedLimit],
+) -> Result<InstancedCff2, Error> {
+    let rebase = Cff2Rebase::new(cff2, limits);
+    let varies = rebase.as_ref().is_some_and(|r| r.store.is_some());
+
+    let mut char_strings = Vec::with_capacity(cff2.char_strings.len());
+    for glyph_id in 0..cff2.char_strings.len() as u16 {
+        let tokens = flatten_char_string(cff2, glyph_id)?;
+        let tokens = rewrite_char_string_blends(
+            cff2,
+            &tokens,
+            cff2.default_vsindex(glyph_id),
+            varies,
+            |blend| {
+                let (values, deltas) = match &rebase {
+                    Some(rebase) => rebase.rebase_blend(cff2, &blend),
+                    None => (blend.defaults.to_vec(), None),
+                };
+                let mut tokens: Vec<CharStringToken> =
+                    values.iter().map(|&v| CharStringToken::Number(v)).collect();
+                if let Some(deltas) = deltas {
+                    tokens.extend(deltas.into_iter().map(CharStringToken::Number));
+                    tokens.push(CharStringToken::Number(values.len() as f64));
+                    tokens.push(CharStringToken::Operator(CS_BLEND));
+                }
+                tokens
+            },
+        );
+        char_strings.push(tokens);
+    }
+
+    let mut instanced = cff2.clone();
+    for fd in &mut instanced.fd_array {
+        fd.private.dict = rewrite_dict_blends(cff2, &fd.private.dict, varies, |blend| {
+            let (values, deltas) = match &rebase {
+                Some(rebase) => rebase.rebase_blend(cff2, &blend),
+                None => (blend.defaults.to_vec(), None),
+            };
+            let mut operands: Vec<Operand> = values.iter().map(|&v| operand(v)).collect();
+            let is_blend = deltas.is_some();
+            if let Some(deltas) = deltas {
+                operands.extend(deltas.into_iter().map(operand));
+                operands.push(Operand::Integer(values.len() as i32));
+            }
+            (operands, is_blend)
+        });
+        fd.private.local_subrs.clear();
+    }
+    instanced.global_subrs.clear();
+    instanced.variation_store = rebase.and_then(|r| r.store);
+
+    let mut bounds = Vec::with_capacity(char_strings.len());
+    instanced.char_strings.clear();
+    for (glyph_id, tokens) in char_strings.iter().enumerate() {
+        let defaults = rewrite_char_string_blends(
+            &instanced,
+            tokens,
+            instanced.default_vsindex(glyph_id as u16),
+            false,
+            |blend| {
+                blend
+                    .defaults
+                    .iter()
+                    .map(|&v| CharStringToken::Number(v))
+                    .collect()
+            },
+        );
+        bounds.push(char_string_bounds(&defaults));
+        let mut writer = FontDataWriter::new();
+        write_char_string(&mut writer, tokens);
+        instanced.char_strings.push(writer.as_bytes().to_vec());
+    }
+    Ok(InstancedCff2 {
+        cff2: instanced,
+        bounds,
+    })
+}
+
+/// Bounding box accumulated from path points and curve extrema.
+#[derive(Default)]
+struct Bounds {
+    bounds: Option<(f64, f64, f64, f64)>,
+}
+
+impl Bounds {
+    fn add(&mut self, (x, y): (f64, f64)) {
+        self.bounds = Some(match self.bounds {
+            None => (x, y, x, y),
+            Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+        });
+    }
+
+    fn add_curve(&mut self, p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64)) {
+        self.add(p3);
+        let point = |t: f64| {
+            let u = 1.0 - t;
+            let blend = |a: f64, b: f64, c: f64, d: f64| {
+                u * u * u * a + 3.0 * u * u * t * b + 3.0 * u * t * t * c + t * t * t * d
+            };
+            (blend(p0.0, p1.0, p2.0, p3.0), blend(p0.1, p1.1, p2.1, p3.1))
+        };
+        for (a, b, c, d) in [(p0.0, p1.0, p2.0, p3.0), (p0.1, p1.1, p2.1, p3.1)] {
+            // Roots of the derivative, a quadratic in t.
+            let qa = -a + 3.0 * b - 3.0 * c + d;
+            let qb = 2.0 * (a - 2.0 * b + c);
+            let qc = b - a;
+            let roots = if qa.abs() < 1e-12 {
+                if qb.abs() < 1e-12 {
+                    vec![]
+                } else {
+                    vec![-qc / qb]
+                }
+            } else {
+                let discriminant = qb * qb - 4.0 * qa * qc;
+                if discriminant < 0.0 {
+                    vec![]
+                } else {
+                    let root = discriminant.sqrt();
+                    vec![(-qb + root) / (2.0 * qa), (-qb - root) / (2.0 * qa)]
+                }
+            };
+            for t in roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0) {
+                self.add(point(t));
+            }
+        }
+    }
+}
+
+/// Computes the bounding box of a flattened, blend-free `CFF2` charstring.
+fn char_string_bounds(tokens: &[CharStringToken]) -> Option<(i16, i16, i16, i16)> {
+    let mut bounds = Bounds::default();
+    let mut current = (0.0, 0.0);
+    for command in char_string_path(tokens) {
+        match command {
+            PathCommand::MoveTo(x, y) => current = (x as f64, y as f64),
+            PathCommand::LineTo(x, y) => {
+                bounds.add(current);
+                current = (x as f64, y as f64);
+                bounds.add(current);
+            }
+            PathCommand::CurveTo(x1, y1, x2, y2, x, y) => {
+                let p3 = (x as f64, y as f64);
+                bounds.add(current);
+                bounds.add_curve(current, (x1 as f64, y1 as f64), (x2 as f64, y2 as f64), p3);
+                current = p3;
+            }
+            PathCommand::QuadTo(..) | PathCommand::Close => {}
+        }
+    }
+    bounds.bounds.map(|(x0, y0, x1, y1)| {
+        (
+            x0.floor() as i16,
+            y0.floor() as i16,
+            x1.ceil() as i16,
+            y1.ceil() as i16,
+        )
+    })
+}
@@ -0,0 +1,146 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Instancing of TrueType outlines: `gvar` deltas are applied to `glyf`
+//! points, composite offsets and phantom points, and bounding boxes are
+//! recomputed.
+
+use crate::components::glyf::{GlyfTable, Glyph, compute_bounds};
+use crate::components::gvar::{
+    GvarTable, PHANTOM_POINT_COUNT, glyph_points, phantom_points, set_glyph_points, tuple_deltas,
+};
+use crate::components::hmtx::HmtxTable;
+
+use super::solver::{NormalizedLimit, instance_tuples, remaining_axis_count};
+
+/// Maximum component nesting depth followed when resolving composite bounds.
+const MAX_COMPONENT_DEPTH: usize = 16;
+
+/// Result of instancing `glyf` and `gvar`.
+pub(crate) struct InstancedGlyf {
+    /// Glyphs with the default deltas applied and recomputed bounds.
+    pub glyf: GlyfTable,
+    /// Variations over the remaining axes, if any axis remains.
+    pub gvar: Option<GvarTable>,
+    /// Phantom points of every glyph after instancing, in the order left
+    /// side, right side, top and bottom.
+    pub phantoms: Vec<[(f32, f32); PHANTOM_POINT_COUNT]>,
+}
+
+/// Returns the outline points of a glyph with its components resolved.
+pub(crate) fn resolved_points(glyf: &GlyfTable, glyph_id: u16, depth: usize) -> Vec<(f32, f32)> {
+    match glyf.glyph(glyph_id) {
+        Some(Glyph::Simple(simple)) => simple
+            .points
+            .iter()
+            .map(|p| (p.x as f32, p.y as f32))
+            .collect(),
+        Some(Glyph::Composite(composite)) if depth < MAX_COMPONENT_DEPTH => {
+            let mut points: Vec<(f32, f32)> = Vec::new();
+            for component in &composite.components {
+                let [xx, yx, xy, yy] = component.transform.matrix();
+                let child: Vec<(f32, f32)> =
+                    resolved_points(glyf, component.glyph_index, depth + 1)
+                        .into_iter()
+                        .map(|(x, y)| (xx * x + xy * y, yx * x + yy * y))
+                        .collect();
+                let (dx, dy) = if component.args_are_xy_values() {
+                    (component.arg1 as f32, component.arg2 as f32)
+                } else {
+                    // Point matching: the child point lands on the parent point.
+                    let parent = points
+                        .get(component.arg1 as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    let anchor = child
+                        .get(component.arg2 as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    (parent.0 - anchor.0, parent.1 - anchor.1)
+                };
+                points.extend(child.into_iter().map(|(x, y)| (x + dx, y + dy)));
+            }
+            points
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Instances `glyf` with `gvar`.
+///
+/// # Arguments
+/// * `glyf` - Glyph outlines.
+/// * `gvar` - Glyph variations.
+/// * `hmtx` - Horizontal metrics, used for the horizontal phantom points.
+/// * `vmtx` - Vertical metrics, used for the vertical phantom points.
+/// * `limits` - Normalized limit of every `fvar` axis.
+pub(crate) fn instance_glyf(
+    glyf: &GlyfTable,
+    gvar: &GvarTable,
+    hmtx: &HmtxTable,
+    vmtx: Option<&HmtxTable>,
+    limits: &[NormalizedLimit],
+) -> InstancedGlyf {
+    let mut glyphs = Vec::with_capacity(glyf.glyphs.len());
+    let mut glyph_variations = Vec::with_capacity(glyf.glyphs.len());
+    let mut phantoms = Vec::with_capacity(glyf.glyphs.len());
+    for (glyph_id, glyph) in glyf.glyphs.iter().enumerate() {
+        let glyph_id = glyph_id as u16;
+        let mut points = glyph_points(glyph);
+        points.extend(phantom_points(glyph, hmtx, vmtx, glyph_id));
+        let end_points: &[u16] = match glyph {
+            Glyph::Simple(simple) => &simple.end_points,
+            _ => &[],
+        };
+        let dense: Vec<_> = gvar
+            .variations(glyph_id)
+            .iter()
+            .map(|tuple| {
+                let (x, y) = tuple_deltas(tuple, &points, end_points).into_iter().unzip();
+                (tuple, vec![x, y])
+            })
+            .collect();
+        let (defaults, tuples) = instance_tuples(&dense, limits, 2, points.len());
+        for (i, point) in points.iter_mut().enumerate() {
+            point.0 += defaults[0][i];
+            point.1 += defaults[1][i];
+        }
+        let mut glyph = glyph.clone();
+        set_glyph_points(&mut glyph, &points);
+        let mut phantom = [(0.0, 0.0); PHANTOM_POINT_COUNT];
+        phantom.copy_from_slice(&points[points.len() - PHANTOM_POINT_COUNT..]);
+        glyphs.push(glyph);
+        glyph_variations.push(tuples);
+        phantoms.push(phantom);
+    }
+
+    let mut instanced = GlyfTable { glyphs };
+    // Composite bounds depend on the instanced components.
+    for glyph_id in 0..instanced.glyphs.len() {
+        let points = resolved_points(&instanced, glyph_id as u16, 0);
+        let bounds = compute_bounds(
+            points
+                .iter()
+                .map(|&(x, y)| (x.round() as i16, y.round() as i16)),
+        );
+        if let Some(header) = instanced.glyphs[glyph_id].header_mut() {
+            (header.x_min, header.y_min, header.x_max, header.y_max) = bounds;
+        }
+    }
+
+    let axis_count = remaining_axis_count(limits);
+    let gvar = (axis_count > 0).then_some(GvarTable {
+        major_version: gvar.major_version,
+        minor_version: gvar.minor_version,
+        axis_count: axis_count as u16,
+        glyph_variations,
+    });
+    InstancedGlyf {
+        glyf: instanced,
+        gvar,
+        phantoms,
+    }
+}
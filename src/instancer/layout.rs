@@ -0,0 +1,181 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Instancing of `GPOS` values and `GDEF` ligature carets: the delta fixed by
+//! the pinned axes is added to every value with a variation index table, and
+//! the tables are removed when no axis remains.
+
+use crate::components::gdef::{CaretValue, GdefTable};
+use crate::components::gpos::{Anchor, GposTable, PositioningSubtable, ValueRecord};
+use crate::components::layout::DeviceTable;
+
+use super::solver::InstancedStore;
+
+/// Value format bits of the value fields, as opposed to device offsets.
+const VALUE_FIELDS: u16 = 0x000F;
+
+/// Adds the default delta of a variation index table to `value`, removing the
+/// table unless `keep` is set.
+fn fold(value: &mut i16, device: &mut Option<DeviceTable>, store: &InstancedStore, keep: bool) {
+    let Some(DeviceTable::VariationIndex {
+        delta_set_outer_index,
+        delta_set_inner_index,
+    }) = *device
+    else {
+        return;
+    };
+    let delta = store.default_delta(delta_set_outer_index, delta_set_inner_index);
+    *value = (*value as f32 + delta)
+        .round()
+        .clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    if !keep {
+        *device = None;
+    }
+}
+
+fn fold_value_record(record: &mut ValueRecord, store: &InstancedStore, keep: bool) {
+    fold(
+        &mut record.x_placement,
+        &mut record.x_placement_device,
+        store,
+        keep,
+    );
+    fold(
+        &mut record.y_placement,
+        &mut record.y_placement_device,
+        store,
+        keep,
+    );
+    fold(
+        &mut record.x_advance,
+        &mut record.x_advance_device,
+        store,
+        keep,
+    );
+    fold(
+        &mut record.y_advance,
+        &mut record.y_advance_device,
+        store,
+        keep,
+    );
+}
+
+fn fold_anchor(anchor: &mut Anchor, store: &InstancedStore, keep: bool) {
+    fold(&mut anchor.x_coordinate, &mut anchor.x_device, store, keep);
+    fold(&mut anchor.y_coordinate, &mut anchor.y_device, store, keep);
+}
+
+/// Returns a value format holding the value fields of `format` and every
+/// field used by `records`.
+fn merged_format<'a>(format: u16, records: impl Iterator<Item = &'a ValueRecord>) -> u16 {
+    records.fold(format & VALUE_FIELDS, |format, record| {
+        format | record.format()
+    })
+}
+
+/// Folds the default deltas into the values and anchors of every `GPOS`
+/// lookup.
+pub(crate) fn instance_gpos(gpos: &mut GposTable, store: &InstancedStore, keep: bool) {
+    for lookup in &mut gpos.lookups {
+        for subtable in &mut lookup.subtables {
+            match subtable {
+                PositioningSubtable::Single {
+                    value_format,
+                    values,
+                } => {
+                    for record in values.values_mut() {
+                        fold_value_record(record, store, keep);
+                    }
+                    *value_format = merged_format(*value_format, values.values());
+                }
+                PositioningSubtable::Pair {
+                    value_format1,
+                    value_format2,
+                    pair_sets,
+                } => {
+                    for pair in pair_sets.values_mut().flatten() {
+                        fold_value_record(&mut pair.value_record1, store, keep);
+                        fold_value_record(&mut pair.value_record2, store, keep);
+                    }
+                    let pairs = || pair_sets.values().flatten();
+                    *value_format1 =
+                        merged_format(*value_format1, pairs().map(|p| &p.value_record1));
+                    *value_format2 =
+                        merged_format(*value_format2, pairs().map(|p| &p.value_record2));
+                }
+                PositioningSubtable::PairClass {
+                    value_format1,
+                    value_format2,
+                    class1_records,
+                    ..
+                } => {
+                    for (record1, record2) in class1_records.iter_mut().flatten() {
+                        fold_value_record(record1, store, keep);
+                        fold_value_record(record2, store, keep);
+                    }
+                    let records = || class1_records.iter().flatten();
+                    *value_format1 = merged_format(*value_format1, records().map(|r| &r.0));
+                    *value_format2 = merged_format(*value_format2, records().map(|r| &r.1));
+                }
+                PositioningSubtable::Cursive(records) => {
+                    for record in records.values_mut() {
+                        for anchor in [&mut record.entry_anchor, &mut record.exit_anchor]
+                            .into_iter()
+                            .flatten()
+                        {
+                            fold_anchor(anchor, store, keep);
+                        }
+                    }
+                }
+                PositioningSubtable::MarkToBase { marks, bases, .. } => {
+                    for mark in marks.values_mut() {
+                        fold_anchor(&mut mark.mark_anchor, store, keep);
+                    }
+                    for anchor in bases.values_mut().flatten().flatten() {
+                        fold_anchor(anchor, store, keep);
+                    }
+                }
+                PositioningSubtable::MarkToLigature {
+                    marks, ligatures, ..
+                } => {
+                    for mark in marks.values_mut() {
+                        fold_anchor(&mut mark.mark_anchor, store, keep);
+                    }
+                    for anchor in ligatures.values_mut().flatten().flatten().flatten() {
+                        fold_anchor(anchor, store, keep);
+                    }
+                }
+                PositioningSubtable::MarkToMark { marks, mark2s, .. } => {
+                    for mark in marks.values_mut() {
+                        fold_anchor(&mut mark.mark_anchor, store, keep);
+                    }
+                    for anchor in mark2s.values_mut().flatten().flatten() {
+                        fold_anchor(anchor, store, keep);
+                    }
+                }
+                PositioningSubtable::Context(_) | PositioningSubtable::ChainContext(_) => {}
+            }
+        }
+    }
+}
+
+/// Folds the default deltas into the ligature caret coordinates of `GDEF`.
+pub(crate) fn instance_carets(gdef: &mut GdefTable, store: &InstancedStore, keep: bool) {
+    let Some(carets) = &mut gdef.lig_caret_list else {
+        return;
+    };
+    for caret in carets.values_mut().flatten() {
+        let CaretValue::CoordinateDevice(coordinate, device) = caret else {
+            continue;
+        };
+        let (mut coordinate, mut device) = (*coordinate, Some(device.clone()));
+        fold(&mut coordinate, &mut device, store, keep);
+        *caret = match device {
+            Some(device) => CaretValue::CoordinateDevice(coordinate, device),
+            None => CaretValue::Coordinate(coordinate),
+        };
+    }
+}
@@ -0,0 +1,863 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Variable font instancing. Axes are pinned to a user coordinate, which
+//! removes them, or limited to a narrower user range. When every axis is
+//! pinned the result is a static font without variation tables; otherwise
+//! the variation tables are rewritten over the remaining axes.
+//!
+//! `gvar` deltas are applied to `glyf` outlines and phantom points, blends to
+//! `CFF2` charstrings and Private DICTs, `HVAR` and `VVAR` deltas to advances
+//! and `MVAR` deltas to the font-wide metrics. `GSUB` and `GPOS` feature
+//! variations are resolved, and `GDEF` deltas are folded into the `GPOS`
+//! values and ligature carets that reference them. `cvar` deltas are applied to
+//! the control values of `cvt `. `STAT` axis values outside the limits are
+//! removed, and `DSIG` is dropped.
+
+mod cff;
+mod glyf;
+mod layout;
+mod solver;
+
+use std::collections::BTreeMap;
+
+use crate::common::snft::{SnftTable, write_snft};
+use crate::components::avar::{AvarTable, read_avar, write_avar};
+use crate::components::cff2::{read_cff2, write_cff2};
+use crate::components::colr::read_colr;
+use crate::components::cvar::{CvarTable, cvt_deltas, read_cvar, write_cvar};
+use crate::components::cvt::{read_cvt, write_cvt};
+use crate::components::fvar::{FvarTable, read_fvar, to_f2dot14, write_fvar};
+use crate::components::gdef::{read_gdef, write_gdef};
+use crate::components::glyf::{GlyfTable, read_glyf, write_glyf};
+use crate::components::gpos::{read_gpos, write_gpos};
+use crate::components::gsub::{read_gsub, write_gsub};
+use crate::components::gvar::{read_gvar, write_gvar};
+use crate::components::head::{HeadTable, read_head, write_head};
+use crate::components::hhea::{read_hhea, write_hhea};
+use crate::components::hmtx::{HmtxTable, LongHorMetric, read_hmtx, write_hmtx};
+use crate::components::hvar::{HvarTable, read_hvar, write_hvar};
+use crate::components::layout::{FeatureVariations, LayoutTable};
+use crate::components::loca::{read_loca, write_loca};
+use crate::components::maxp::read_maxp;
+use crate::components::mvar::{MvarTable, read_mvar, write_mvar};
+use crate::components::stat::{AxisValue, StatTable, read_stat};
+use crate::components::vvar::{VvarTable, read_vvar, write_vvar};
+use crate::error::{Error, IoError};
+use crate::io::writer::{FontDataWriter, serialize};
+
+use solver::{InstancedStore, NormalizedLimit, instance_store, instance_tuples};
+
+/// Variation tables removed from a static instance.
+const VARIATION_TABLES: [[u8; 4]; 7] = [
+    *b"HVAR", *b"MVAR", *b"VVAR", *b"avar", *b"cvar", *b"fvar", *b"gvar",
+];
+
+/// The digital signature, which no longer matches the instance.
+const SIGNATURE_TABLE: [u8; 4] = *b"DSIG";
+
+/// Location of each `MVAR` value tag: table and byte offset of the field.
+const MVAR_FIELDS: [([u8; 4], [u8; 4], usize); 28] = [
+    (*b"hasc", *b"OS/2", 68),
+    (*b"hdsc", *b"OS/2", 70),
+    (*b"hlgp", *b"OS/2", 72),
+    (*b"hcla", *b"OS/2", 74),
+    (*b"hcld", *b"OS/2", 76),
+    (*b"xhgt", *b"OS/2", 86),
+    (*b"cpht", *b"OS/2", 88),
+    (*b"sbxs", *b"OS/2", 10),
+    (*b"sbys", *b"OS/2", 12),
+    (*b"sbxo", *b"OS/2", 14),
+    (*b"sbyo", *b"OS/2", 16),
+    (*b"spxs", *b"OS/2", 18),
+    (*b"spys", *b"OS/2", 20),
+    (*b"spxo", *b"OS/2", 22),
+    (*b"spyo", *b"OS/2", 24),
+    (*b"strs", *b"OS/2", 26),
+    (*b"stro", *b"OS/2", 28),
+    (*b"hcrs", *b"hhea", 18),
+    (*b"hcrn", *b"hhea", 20),
+    (*b"hcof", *b"hhea", 22),
+    (*b"vasc", *b"vhea", 4),
+    (*b"vdsc", *b"vhea", 6),
+    (*b"vlgp", *b"vhea", 8),
+    (*b"vcrs", *b"vhea", 18),
+    (*b"vcrn", *b"vhea", 20),
+    (*b"vcof", *b"vhea", 22),
+    (*b"undo", *b"post", 8),
+    (*b"unds", *b"post", 10),
+];
+
+/// `wdth` percentages of the `OS/2` width classes 1 to 9.
+const WIDTH_CLASSES: [f32; 9] = [50.0, 62.5, 75.0, 87.5, 100.0, 112.5, 125.0, 150.0, 200.0];
+
+/// Limit applied to one axis, in user coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisLimit {
+    /// Fix the axis at a value and remove it.
+    Pin(f32),
+    /// Keep the axis, restricted to `min..=max`. The range must contain the
+    /// axis default.
+    Range(f32, f32),
+}
+
+/// Options controlling instancing.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InstanceOptions {
+    /// Limits by axis tag; axes without a limit keep their full range.
+    pub axes: BTreeMap<[u8; 4], AxisLimit>,
+}
+
+/// Axis limits resolved against `fvar` and `avar`.
+struct Limits {
+    /// Normalized limit of every `fvar` axis.
+    normalized: Vec<NormalizedLimit>,
+    /// User limit of every axis: the pinned value or the clamped range.
+    user: Vec<AxisLimit>,
+}
+
+impl Limits {
+    fn new(
+        fvar: &FvarTable,
+        avar: Option<&AvarTable>,
+        options: &InstanceOptions,
+    ) -> Result<Self, Error> {
+        if let Some(tag) = options
+            .axes
+            .keys()
+            .find(|tag| fvar.axis_index(tag).is_none())
+        {
+            return Err(Error::Io(IoError::InvalidTag(*tag)));
+        }
+        let mut normalized = Vec::with_capacity(fvar.axes.len());
+        let mut user = Vec::with_capacity(fvar.axes.len());
+        for (i, axis) in fvar.axes.iter().enumerate() {
+            let normalize = |value: f32| {
+                let value = axis.normalize(value);
+                avar.map_or(value, |avar| avar.map(i, value))
+            };
+            let limit = options
+                .axes
+                .get(&axis.tag)
+                .copied()
+                .unwrap_or(AxisLimit::Range(axis.min_value, axis.max_value));
+            match limit {
+                AxisLimit::Pin(value) => {
+                    let value = value.clamp(axis.min_value, axis.max_value);
+                    normalized.push(NormalizedLimit::Pin(normalize(value)));
+                    user.push(AxisLimit::Pin(value));
+                }
+                AxisLimit::Range(min, max) => {
+                    let (min, max) = (min.max(axis.min_value), max.min(axis.max_value));
+                    if min > axis.default_value || max < axis.default_value {
+                        return Err(Error::Io(IoError::InvalidData));
+                    }
+                    if min == max {
+                        normalized.push(NormalizedLimit::Pin(0.0));
+                        user.push(AxisLimit::Pin(min));
+                    } else {
+                        normalized.push(NormalizedLimit::Range(normalize(min), normalize(max)));
+                        user.push(AxisLimit::Range(min, max));
+                    }
+                }
+            }
+        }
+        // `avar` version 2 mixes the axes, so only a complete location can
+        // be normalized.
+        if let Some(avar) = avar.filter(|avar| avar.has_cross_axis_mapping()) {
+            let coords: Option<Vec<f32>> = user
+                .iter()
+                .map(|limit| match limit {
+                    AxisLimit::Pin(value) => Some(*value),
+                    AxisLimit::Range(..) => None,
+                })
+                .collect();
+            let Some(coords) = coords else {
+                return Err(Error::Io(IoError::UnsupportedVersion(
+                    avar.major_version as u32,
+                )));
+            };
+            normalized = fvar
+                .normalize(&coords, Some(avar))
+                .into_iter()
+                .map(NormalizedLimit::Pin)
+                .collect();
+        }
+        Ok(Limits { normalized, user })
+    }
+
+    fn is_static(&self) -> bool {
+        self.user
+            .iter()
+            .all(|limit| matches!(limit, AxisLimit::Pin(_)))
+    }
+
+    /// Returns the pinned user value of the axis with `tag`.
+    fn pinned(&self, fvar: &FvarTable, tag: &[u8; 4]) -> Option<f32> {
+        match self.user.get(fvar.axis_index(tag)?)? {
+            AxisLimit::Pin(value) => Some(*value),
+            AxisLimit::Range(..) => None,
+        }
+    }
+
+    /// Returns the index of each `fvar` axis among the remaining axes.
+    fn remaining_indices(&self) -> Vec<Option<u16>> {
+        let mut next = 0;
+        self.user
+            .iter()
+            .map(|limit| match limit {
+                AxisLimit::Pin(_) => None,
+                AxisLimit::Range(..) => {
+                    next += 1;
+                    Some(next - 1)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rewrites `fvar` over the remaining axes. Named instances outside the new
+/// design space are removed.
+fn instance_fvar(fvar: &FvarTable, limits: &Limits) -> FvarTable {
+    let mut axes = Vec::new();
+    for (axis, limit) in fvar.axes.iter().zip(&limits.user) {
+        if let AxisLimit::Range(min, max) = *limit {
+            let mut axis = axis.clone();
+            (axis.min_value, axis.max_value) = (min, max);
+            axes.push(axis);
+        }
+    }
+    let instances = fvar
+        .instances
+        .iter()
+        .filter(|instance| {
+            instance
+                .coordinates
+                .iter()
+                .zip(&limits.user)
+                .all(|(&c, limit)| match *limit {
+                    AxisLimit::Pin(value) => c == value,
+                    AxisLimit::Range(min, max) => (min..=max).contains(&c),
+                })
+        })
+        .map(|instance| {
+            let mut instance = instance.clone();
+            instance.coordinates = instance
+                .coordinates
+                .iter()
+                .zip(&limits.user)
+                .filter(|(_, limit)| matches!(limit, AxisLimit::Range(..)))
+                .map(|(&c, _)| c)
+                .collect();
+            instance
+        })
+        .collect();
+    FvarTable {
+        major_version: fvar.major_version,
+        minor_version: fvar.minor_version,
+        axes,
+        instances,
+    }
+}
+
+/// Rewrites `avar` for the remaining axes so that the new normalized range
+/// of each limited axis maps onto its limited original range. Returns `None`
+/// when every map is the identity.
+fn instance_avar(fvar: &FvarTable, avar: Option<&AvarTable>, limits: &Limits) -> Option<AvarTable> {
+    let mut segment_maps = Vec::new();
+    for (i, (axis, limit)) in fvar.axes.iter().zip(&limits.user).enumerate() {
+        let (AxisLimit::Range(min, max), NormalizedLimit::Range(low, high)) =
+            (*limit, limits.normalized[i])
+        else {
+            continue;
+        };
+        // Default-normalized limits before `avar`.
+        let (from_low, from_high) = (axis.normalize(min), axis.normalize(max));
+        let original = avar
+            .and_then(|avar| avar.segment_maps.get(i))
+            .filter(|m| m.len() >= 3);
+        let mut map = vec![(-1.0, -1.0), (0.0, 0.0), (1.0, 1.0)];
+        if let Some(original) = original {
+            for &(from, to) in original {
+                if from < 0.0 && from > from_low && low < 0.0 {
+                    map.push((to_f2dot14(from / -from_low), to_f2dot14(to / -low)));
+                } else if from > 0.0 && from < from_high && high > 0.0 {
+                    map.push((to_f2dot14(from / from_high), to_f2dot14(to / high)));
+                }
+            }
+        }
+        map.sort_by(|a, b| a.0.total_cmp(&b.0));
+        map.dedup_by(|a, b| a.0 == b.0);
+        segment_maps.push(map);
+    }
+    let identity = segment_maps
+        .iter()
+        .all(|map| map.iter().all(|(from, to)| from == to));
+    (!identity).then_some(AvarTable {
+        major_version: 1,
+        minor_version: 0,
+        segment_maps,
+        axis_index_map: None,
+        variation_store: None,
+    })
+}
+
+/// Resolves feature variations: records whose conditions fail are removed,
+/// conditions on pinned axes are dropped and the others are renormalized.
+/// In a static instance the first matching record replaces its features.
+fn instance_feature_variations<T>(table: &mut LayoutTable<T>, limits: &Limits) {
+    let Some(variations) = table.feature_variations.take() else {
+        return;
+    };
+    let indices = limits.remaining_indices();
+    let mut records = Vec::new();
+    for mut record in variations.records {
+        let mut matches = true;
+        let mut conditions = Vec::new();
+        for mut condition in record.conditions {
+            let axis = condition.axis_index as usize;
+            let (min, max) = (
+                condition.filter_range_min_value,
+                condition.filter_range_max_value,
+            );
+            match limits.normalized.get(axis) {
+                Some(NormalizedLimit::Pin(value)) => matches &= (min..=max).contains(value),
+                Some(&NormalizedLimit::Range(low, high)) => {
+                    let (min, max) = (min.max(low), max.min(high));
+                    if min > max {
+                        matches = false;
+                        continue;
+                    }
+                    let rescale = |v: f32| match v < 0.0 {
+                        true => to_f2dot14(v / -low).max(-1.0),
+                        false if high > 0.0 => to_f2dot14(v / high).min(1.0),
+                        false => 0.0,
+                    };
+                    condition.axis_index = indices[axis].unwrap_or(0);
+                    condition.filter_range_min_value = rescale(min);
+                    condition.filter_range_max_value = rescale(max);
+                    conditions.push(condition);
+                }
+                None => matches = false,
+            }
+        }
+        if !matches {
+            continue;
+        }
+        record.conditions = conditions;
+        let always = record.conditions.is_empty();
+        records.push(record);
+        // Later records can never be reached.
+        if always {
+            break;
+        }
+    }
+
+    if limits.is_static() {
+        if let Some(record) = records.into_iter().next() {
+            for (index, feature) in record.substitutions {
+                if let Some(target) = table.features.get_mut(index as usize) {
+                    target.feature = feature;
+                }
+            }
+        }
+        table.minor_version = 0;
+    } else if !records.is_empty() {
+        table.feature_variations = Some(FeatureVariations {
+            records,
+            ..variations
+        });
+    } else {
+        table.minor_version = 0;
+    }
+}
+
+/// Interpolates the `OS/2` width class of a `wdth` percentage.
+fn width_class(width: f32) -> u16 {
+    let width = width.clamp(WIDTH_CLASSES[0], WIDTH_CLASSES[8]);
+    let upper = WIDTH_CLASSES.iter().position(|&w| w >= width).unwrap_or(8);
+    if upper == 0 {
+        return 1;
+    }
+    let (w0, w1) = (WIDTH_CLASSES[upper - 1], WIDTH_CLASSES[upper]);
+    (upper as f32 + (width - w0) / (w1 - w0)).round() as u16
+}
+
+/// Adds a delta to a big-endian 16-bit field.
+fn add_to_field(data: &mut [u8], offset: usize, delta: f32) {
+    if let Some(field) = data.get_mut(offset..offset + 2) {
+        let value = i16::from_be_bytes([field[0], field[1]]) as f32 + delta;
+        field.copy_from_slice(&(value.round() as i16).to_be_bytes());
+    }
+}
+
+fn set_field(data: &mut [u8], offset: usize, value: &[u8]) {
+    if let Some(field) = data.get_mut(offset..offset + value.len()) {
+        field.copy_from_slice(value);
+    }
+}
+
+/// Builds metrics from explicit advances and side bearings, dropping repeated
+/// trailing advances.
+fn pack_metrics(metrics: Vec<LongHorMetric>) -> HmtxTable {
+    let last_advance = metrics.last().map_or(0, |m| m.advance_width);
+    let long_count = metrics
+        .iter()
+        .rposition(|m| m.advance_width != last_advance)
+        .map_or(1, |i| i + 2)
+        .min(metrics.len());
+    HmtxTable {
+        left_side_bearings: metrics[long_count..].iter().map(|m| m.lsb).collect(),
+        h_metrics: metrics[..long_count].to_vec(),
+    }
+}
+
+/// Instances a variable font.
+///
+/// # Arguments
+/// * `snft` - The table directory of the font.
+/// * `source` - The complete source font bytes the directory refers to.
+/// * `options` - The axis limits, in user coordinates.
+///
+/// Pinned values are clamped to the axis range. The result is a complete SNFT
+/// font; it is static when every axis is pinned.
+///
+/// # Errors
+/// * Returns `Error` if the font has no `fvar` table, if an option names an
+///   unknown axis, if a range excludes the axis default, if an axis is kept
+///   in a font with an `avar` version 2 variation store, or if a required
+///   table is missing or cannot be parsed.
+/// * Returns `IoError::InvalidTag` naming `COLR` if the font has variable
+///   color glyphs.
+pub fn instantiate_font(
+    snft: &SnftTable,
+    source: &[u8],
+    options: &InstanceOptions,
+) -> Result<Vec<u8>, Error> {
+    let table = |tag: &[u8; 4]| snft.table_stream(tag, source);
+    let optional = |tag: &[u8; 4]| snft.has_table(tag).then(|| table(tag)).transpose();
+    if !snft.has_table(b"fvar") {
+        return Err(Error::Io(IoError::InvalidTag(*b"fvar")));
+    }
+    let fvar = read_fvar(&mut table(b"fvar")?)?;
+    let avar = optional(b"avar")?
+        .map(|mut data| read_avar(&mut data))
+        .transpose()?;
+    let limits = Limits::new(&fvar, avar.as_ref(), options)?;
+    let is_static = limits.is_static();
+    // Variable `COLR` paints hold their deltas in place, which are not
+    // rewritten.
+    if let Some(mut data) = optional(b"COLR")?
+        && read_colr(&mut data)?.item_variation_store.is_some()
+    {
+        return Err(Error::Io(IoError::InvalidTag(*b"COLR")));
+    }
+
+    let num_glyphs = read_maxp(&mut table(b"maxp")?)?.num_glyphs;
+    let mut head = read_head(&mut table(b"head")?)?;
+    let mut hhea = read_hhea(&mut table(b"hhea")?)?;
+    let hmtx = read_hmtx(&mut table(b"hmtx")?, hhea.number_of_h_metrics, num_glyphs)?;
+    let vmtx = match (optional(b"vhea")?, optional(b"vmtx")?) {
+        (Some(vhea), Some(mut vmtx)) => {
+            Some(read_hmtx(&mut vmtx, vhea.read_at_u16(34)?, num_glyphs)?)
+        }
+        _ => None,
+    };
+    let hvar = optional(b"HVAR")?
+        .map(|mut data| read_hvar(&mut data))
+        .transpose()?;
+    let hvar_instance = hvar
+        .as_ref()
+        .map(|hvar| instance_store(&hvar.store, &limits.normalized));
+    let vvar = optional(b"VVAR")?
+        .map(|mut data| read_vvar(&mut data))
+        .transpose()?;
+    let vvar_instance = vvar
+        .as_ref()
+        .map(|vvar| instance_store(&vvar.store, &limits.normalized));
+
+    let mut tables: Vec<([u8; 4], Vec<u8>)> = Vec::new();
+    // Bounding box of every glyph after instancing, when known.
+    let mut bounds: Vec<Option<(i16, i16, i16, i16)>> = vec![None; num_glyphs as usize];
+    let mut h_metrics: Vec<LongHorMetric> = (0..num_glyphs)
+        .map(|g| hmtx.metric(g).unwrap_or_default())
+        .collect();
+    let mut v_metrics: Option<Vec<LongHorMetric>> = vmtx.as_ref().map(|vmtx| {
+        (0..num_glyphs)
+            .map(|g| vmtx.metric(g).unwrap_or_default())
+            .collect()
+    });
+
+    // Advances follow `HVAR` and `VVAR` unless `gvar` phantom points provide them.
+    if let (Some(hvar), Some(instanced)) = (&hvar, &hvar_instance) {
+        for (glyph_id, metric) in h_metrics.iter_mut().enumerate() {
+            let (outer, inner) = match &hvar.advance_map {
+                Some(map) => map.get(glyph_id as u32).unwrap_or((0, 0)),
+                None => (0, glyph_id as u16),
+            };
+            let advance = metric.advance_width as f32 + instanced.default_delta(outer, inner);
+            metric.advance_width = advance.round().max(0.0) as u16;
+        }
+    }
+    if let (Some(vvar), Some(instanced), Some(v_metrics)) = (&vvar, &vvar_instance, &mut v_metrics)
+    {
+        for (glyph_id, metric) in v_metrics.iter_mut().enumerate() {
+            let (outer, inner) = match &vvar.advance_map {
+                Some(map) => map.get(glyph_id as u32).unwrap_or((0, 0)),
+                None => (0, glyph_id as u16),
+            };
+            let advance = metric.advance_width as f32 + instanced.default_delta(outer, inner);
+            metric.advance_width = advance.round().max(0.0) as u16;
+        }
+    }
+
+    if snft.has_table(b"glyf") {
+        let loca = read_loca(&mut table(b"loca")?, head.has_long_loca(), num_glyphs)?;
+        let glyf = read_glyf(&mut table(b"glyf")?, &loca)?;
+        let glyf = match optional(b"gvar")? {
+            Some(mut data) => {
+                let gvar = read_gvar(&mut data)?;
+                let instanced =
+                    glyf::instance_glyf(&glyf, &gvar, &hmtx, vmtx.as_ref(), &limits.normalized);
+                for (glyph_id, [left, right, top, bottom]) in instanced.phantoms.iter().enumerate()
+                {
+                    let glyph = &instanced.glyf.glyphs[glyph_id];
+                    let (x_min, y_max) = glyph.header().map_or((0, 0), |h| (h.x_min, h.y_max));
+                    h_metrics[glyph_id] = LongHorMetric {
+                        advance_width: (right.0 - left.0).round().max(0.0) as u16,
+                        lsb: (x_min as f32 - left.0).round() as i16,
+                    };
+                    if let Some(v_metrics) = &mut v_metrics {
+                        v_metrics[glyph_id] = LongHorMetric {
+                            advance_width: (top.1 - bottom.1).round().max(0.0) as u16,
+                            lsb: (top.1 - y_max as f32).round() as i16,
+                        };
+                    }
+                }
+                if let Some(gvar) = &instanced.gvar {
+                    tables.push((*b"gvar", serialize(|w| write_gvar(w, gvar))?));
+                }
+                instanced.glyf
+            }
+            None => glyf,
+        };
+        for (glyph_id, glyph) in glyf.glyphs.iter().enumerate() {
+            bounds[glyph_id] = glyph.header().map(|h| (h.x_min, h.y_min, h.x_max, h.y_max));
+        }
+        write_glyf_tables(&glyf, &mut tables, &mut head)?;
+    }
+
+    // Control values take the `cvar` deltas fixed by the pinned axes.
+    if let (Some(mut cvt_data), Some(mut cvar_data)) = (optional(b"cvt ")?, optional(b"cvar")?) {
+        let mut cvt = read_cvt(&mut cvt_data)?;
+        let cvar = read_cvar(&mut cvar_data, fvar.axes.len())?;
+        let count = cvt.values.len();
+        let dense: Vec<_> = cvar
+            .variations
+            .iter()
+            .map(|tuple| (tuple, vec![cvt_deltas(tuple, count)]))
+            .collect();
+        let (defaults, variations) = instance_tuples(&dense, &limits.normalized, 1, count);
+        for (value, delta) in cvt.values.iter_mut().zip(&defaults[0]) {
+            *value = (*value as f32 + delta)
+                .round()
+                .clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+        tables.push((
+            *b"cvt ",
+            serialize(|w| {
+                write_cvt(w, &cvt);
+                Ok(())
+            })?,
+        ));
+        if !is_static && !variations.is_empty() {
+            let cvar = CvarTable { variations, ..cvar };
+            tables.push((*b"cvar", serialize(|w| write_cvar(w, &cvar))?));
+        }
+    }
+
+    if snft.has_table(b"CFF2") {
+        let cff2 = read_cff2(&mut table(b"CFF2")?)?;
+        let instanced = cff::instance_cff2(&cff2, &limits.normalized)?;
+        for (glyph_id, glyph_bounds) in instanced.bounds.iter().enumerate().take(bounds.len()) {
+            bounds[glyph_id] = *glyph_bounds;
+            h_metrics[glyph_id].lsb = glyph_bounds.map_or(0, |b| b.0);
+        }
+        tables.push((*b"CFF2", serialize(|w| write_cff2(w, &instanced.cff2))?));
+    }
+
+    // Font-wide bounds and horizontal extrema.
+    let mut font_bounds: Option<(i16, i16, i16, i16)> = None;
+    hhea.advance_width_max = h_metrics.iter().map(|m| m.advance_width).max().unwrap_or(0);
+    let (mut min_lsb, mut min_rsb, mut max_extent) = (i16::MAX, i16::MAX, i16::MIN);
+    for (metric, glyph_bounds) in h_metrics.iter().zip(&bounds) {
+        let Some((x_min, y_min, x_max, y_max)) = *glyph_bounds else {
+            continue;
+        };
+        font_bounds = Some(match font_bounds {
+            None => (x_min, y_min, x_max, y_max),
+            Some((a, b, c, d)) => (a.min(x_min), b.min(y_min), c.max(x_max), d.max(y_max)),
+        });
+        let width = x_max as i32 - x_min as i32;
+        min_lsb = min_lsb.min(metric.lsb);
+        min_rsb = min_rsb.min((metric.advance_width as i32 - metric.lsb as i32 - width) as i16);
+        max_extent = max_extent.max((metric.lsb as i32 + width) as i16);
+    }
+    if let Some((x_min, y_min, x_max, y_max)) = font_bounds {
+        (head.x_min, head.y_min, head.x_max, head.y_max) = (x_min, y_min, x_max, y_max);
+        (
+            hhea.min_left_side_bearing,
+            hhea.min_right_side_bearing,
+            hhea.x_max_extent,
+        ) = (min_lsb, min_rsb, max_extent);
+    }
+    let hmtx = pack_metrics(h_metrics);
+    hhea.number_of_h_metrics = hmtx.h_metrics.len() as u16;
+    tables.push((
+        *b"hmtx",
+        serialize(|w| {
+            write_hmtx(w, &hmtx);
+            Ok(())
+        })?,
+    ));
+    tables.push((
+        *b"hhea",
+        serialize(|w| {
+            write_hhea(w, &hhea);
+            Ok(())
+        })?,
+    ));
+    if let (Some(v_metrics), Some(vhea)) = (v_metrics, optional(b"vhea")?) {
+        let vmtx = pack_metrics(v_metrics);
+        let mut vhea = vhea.remaining_bytes().to_vec();
+        set_field(&mut vhea, 34, &(vmtx.h_metrics.len() as u16).to_be_bytes());
+        tables.push((*b"vhea", vhea));
+        tables.push((
+            *b"vmtx",
+            serialize(|w| {
+                write_hmtx(w, &vmtx);
+                Ok(())
+            })?,
+        ));
+    }
+    tables.push((
+        *b"head",
+        serialize(|w| {
+            write_head(w, &head);
+            Ok(())
+        })?,
+    ));
+
+    if let Some(mut data) = optional(b"GSUB")? {
+        let mut gsub = read_gsub(&mut data)?;
+        instance_feature_variations(&mut gsub, &limits);
+        tables.push((*b"GSUB", serialize(|w| write_gsub(w, &gsub))?));
+    }
+    // The `GDEF` deltas fixed by the pinned axes are folded into the values
+    // referencing them; variation index tables stay while the store varies.
+    let mut gdef = optional(b"GDEF")?
+        .map(|mut data| read_gdef(&mut data))
+        .transpose()?;
+    let gdef_instance = gdef
+        .as_mut()
+        .and_then(|gdef| gdef.item_var_store.take())
+        .map(|store| instance_store(&store, &limits.normalized));
+    let keep_devices = gdef_instance.as_ref().is_some_and(InstancedStore::varies);
+    if let Some(mut data) = optional(b"GPOS")? {
+        let mut gpos = read_gpos(&mut data)?;
+        instance_feature_variations(&mut gpos, &limits);
+        if let Some(instanced) = &gdef_instance {
+            layout::instance_gpos(&mut gpos, instanced, keep_devices);
+        }
+        tables.push((*b"GPOS", serialize(|w| write_gpos(w, &gpos))?));
+    }
+    if let Some(mut gdef) = gdef {
+        if let Some(instanced) = gdef_instance {
+            layout::instance_carets(&mut gdef, &instanced, keep_devices);
+            gdef.item_var_store = keep_devices.then_some(instanced.store);
+        }
+        tables.push((*b"GDEF", serialize(|w| write_gdef(w, &gdef))?));
+    }
+
+    if !is_static {
+        tables.push((
+            *b"fvar",
+            serialize(|w| {
+                write_fvar(w, &instance_fvar(&fvar, &limits));
+                Ok(())
+            })?,
+        ));
+        if let Some(avar) = instance_avar(&fvar, avar.as_ref(), &limits) {
+            tables.push((*b"avar", serialize(|w| write_avar(w, &avar))?));
+        }
+        if let (Some(hvar), Some(instanced)) = (&hvar, &hvar_instance)
+            && instanced.varies()
+        {
+            let hvar = HvarTable {
+                store: instanced.store.clone(),
+                ..hvar.clone()
+            };
+            tables.push((*b"HVAR", serialize(|w| write_hvar(w, &hvar))?));
+        }
+        if let (Some(vvar), Some(instanced)) = (&vvar, &vvar_instance)
+            && instanced.varies()
+        {
+            let vvar = VvarTable {
+                store: instanced.store.clone(),
+                ..vvar.clone()
+            };
+            tables.push((*b"VVAR", serialize(|w| write_vvar(w, &vvar))?));
+        }
+    }
+
+    // Remaining tables, copied or patched.
+    for entry in snft.all_tables() {
+        let tag = entry.tag;
+        if tables.iter().any(|(t, _)| *t == tag)
+            || VARIATION_TABLES.contains(&tag)
+            || tag == SIGNATURE_TABLE
+        {
+            continue;
+        }
+        let mut data = table(&tag)?.remaining_bytes().to_vec();
+        if tag == *b"STAT" {
+            let stat = read_stat(&mut table(&tag)?)?;
+            prune_stat(&mut data, &stat, &fvar, &limits)?;
+        }
+        tables.push((tag, data));
+    }
+
+    if let Some(mut data) = optional(b"MVAR")? {
+        let mvar = read_mvar(&mut data)?;
+        if let Some(store) = &mvar.store {
+            let instanced = instance_store(store, &limits.normalized);
+            apply_mvar(&mvar, &instanced, &mut tables);
+            if !is_static && instanced.varies() {
+                let mvar = MvarTable {
+                    store: Some(instanced.store),
+                    ..mvar
+                };
+                tables.push((*b"MVAR", serialize(|w| write_mvar(w, &mvar))?));
+            }
+        }
+    }
+
+    // Style fields of the pinned registered axes.
+    if let Some((_, os2)) = tables.iter_mut().find(|(tag, _)| tag == b"OS/2") {
+        if let Some(weight) = limits.pinned(&fvar, b"wght") {
+            set_field(
+                os2,
+                4,
+                &(weight.clamp(1.0, 1000.0).round() as u16).to_be_bytes(),
+            );
+        }
+        if let Some(width) = limits.pinned(&fvar, b"wdth") {
+            set_field(os2, 6, &width_class(width).to_be_bytes());
+        }
+    }
+    if let (Some(slant), Some((_, post))) = (
+        limits.pinned(&fvar, b"slnt"),
+        tables.iter_mut().find(|(tag, _)| tag == b"post"),
+    ) {
+        let angle = (slant.clamp(-90.0, 90.0) * 65536.0).round() as i32;
+        set_field(post, 4, &angle.to_be_bytes());
+    }
+
+    tables.sort_by_key(|(tag, _)| *tag);
+    let mut writer = FontDataWriter::new();
+    write_snft(&mut writer, snft.header.version, &tables)?;
+    writer.into_bytes()
+}
+
+/// Removes the `STAT` axis values outside the limits of their `fvar` axes.
+/// The design axes are kept, since they also order the names of the pinned
+/// values.
+fn prune_stat(
+    data: &mut [u8],
+    stat: &StatTable,
+    fvar: &FvarTable,
+    limits: &Limits,
+) -> Result<(), Error> {
+    let limit = |axis_index: u16| {
+        let tag = stat.design_axes.get(axis_index as usize)?.tag;
+        limits.user.get(fvar.axis_index(&tag)?).copied()
+    };
+    let contains = |axis_index: u16, value: f32| match limit(axis_index) {
+        Some(AxisLimit::Pin(pinned)) => value == pinned,
+        Some(AxisLimit::Range(min, max)) => min <= value && value <= max,
+        None => true,
+    };
+    let kept: Vec<bool> = stat
+        .axis_values
+        .iter()
+        .map(|value| match value {
+            AxisValue::Value {
+                axis_index, value, ..
+            }
+            | AxisValue::Linked {
+                axis_index, value, ..
+            } => contains(*axis_index, *value),
+            AxisValue::Range {
+                axis_index,
+                range_min_value,
+                range_max_value,
+                ..
+            } => match limit(*axis_index) {
+                Some(AxisLimit::Pin(pinned)) => {
+                    *range_min_value <= pinned && pinned <= *range_max_value
+                }
+                Some(AxisLimit::Range(min, max)) => {
+                    *range_min_value <= max && min <= *range_max_value
+                }
+                None => true,
+            },
+            AxisValue::Location { values, .. } => values
+                .iter()
+                .all(|&(axis_index, value)| contains(axis_index, value)),
+        })
+        .collect();
+
+    // The axis value offsets are relative to the start of their array, so
+    // the kept ones are moved forward unchanged.
+    let array = u32::from_be_bytes(data[14..18].try_into().unwrap()) as usize;
+    let mut count = 0;
+    for (i, _) in kept.iter().enumerate().filter(|(_, kept)| **kept) {
+        data.copy_within(array + i * 2..array + i * 2 + 2, array + count * 2);
+        count += 1;
+    }
+    set_field(data, 12, &(count as u16).to_be_bytes());
+    Ok(())
+}
+
+/// Serializes instanced `glyf` and `loca` and updates the `loca` format.
+fn write_glyf_tables(
+    glyf: &GlyfTable,
+    tables: &mut Vec<([u8; 4], Vec<u8>)>,
+    head: &mut HeadTable,
+) -> Result<(), Error> {
+    let mut writer = FontDataWriter::new();
+    let loca = write_glyf(&mut writer, glyf);
+    tables.push((*b"glyf", writer.into_bytes()?));
+    let long = !loca.fits_short_format();
+    tables.push((*b"loca", serialize(|w| write_loca(w, &loca, long))?));
+    head.index_to_loc_format = long as i16;
+    Ok(())
+}
+
+/// Adds the default `MVAR` deltas to the fields they vary.
+fn apply_mvar(mvar: &MvarTable, instanced: &InstancedStore, tables: &mut [([u8; 4], Vec<u8>)]) {
+    for record in &mvar.records {
+        let delta = instanced.default_delta(record.outer, record.inner);
+        if delta == 0.0 {
+            continue;
+        }
+        for (_, table_tag, offset) in MVAR_FIELDS.iter().filter(|(tag, ..)| *tag == record.tag) {
+            if let Some((_, data)) = tables.iter_mut().find(|(tag, _)| tag == table_tag) {
+                add_to_field(data, *offset, delta);
+            }
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Rebasing of variation regions onto limited axes. A region scalar over the
+//! original design space is rewritten as a sum of scaled region scalars over
+//! the new, renormalized design space; parts that no longer vary fold into
+//! the default.
+
+use std::collections::HashMap;
+
+use crate::components::fvar::to_f2dot14;
+use crate::components::variation::{
+    ItemVariationData, ItemVariationStore, RegionAxis, TupleVariation, VariationRegion,
+};
+
+/// Limit of one axis in normalized coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NormalizedLimit {
+    /// The axis is removed, fixed at this coordinate.
+    Pin(f32),
+    /// The axis is kept, restricted to `min..=max` (with `min <= 0 <= max`),
+    /// which becomes the new -1..=1.
+    Range(f32, f32),
+}
+
+impl NormalizedLimit {
+    fn is_pinned(&self) -> bool {
+        matches!(self, NormalizedLimit::Pin(_))
+    }
+}
+
+/// A scaled region of the instanced design space. `None` is the default.
+pub(crate) type Piece = (f32, Option<Vec<RegionAxis>>);
+
+/// Rebases one axis span onto the range `min..=max`.
+fn rebase_axis(axis: RegionAxis, min: f32, max: f32) -> Vec<(f32, RegionAxis)> {
+    let RegionAxis { start, peak, end } = axis;
+    if peak == 0.0 || start > peak || peak > end || (start < 0.0 && end > 0.0) {
+        return vec![(1.0, RegionAxis::default())];
+    }
+    if peak < 0.0 {
+        return rebase_axis(RegionAxis::new(-end, -peak, -start), -max, -min)
+            .into_iter()
+            .map(|(factor, a)| (factor, RegionAxis::new(-a.end, -a.peak, -a.start)))
+            .collect();
+    }
+    let limit = max;
+    if start >= limit {
+        return Vec::new();
+    }
+    let scale = |value: f32| to_f2dot14(value / limit);
+    if end <= limit {
+        return vec![(1.0, RegionAxis::new(scale(start), scale(peak), scale(end)))];
+    }
+    if peak <= limit {
+        // The falling edge is cut at the limit, where the scalar is still
+        // `factor`: a second region ramping up to the new maximum restores it.
+        let mut pieces = vec![(1.0, RegionAxis::new(scale(start), scale(peak), 1.0))];
+        let factor = (end - limit) / (end - peak);
+        if peak < limit && factor != 0.0 {
+            pieces.push((factor, RegionAxis::new(scale(peak), 1.0, 1.0)));
+        }
+        return pieces;
+    }
+    // Only the rising edge remains, reaching `factor` at the limit.
+    let factor = (limit - start) / (peak - start);
+    vec![(factor, RegionAxis::new(scale(start), 1.0, 1.0))]
+}
+
+/// Rebases a region given by one span per original axis. The returned regions
+/// have one span per unpinned axis; regions that no longer vary are `None`.
+pub(crate) fn rebase_region(axes: &[RegionAxis], limits: &[NormalizedLimit]) -> Vec<Piece> {
+    let mut pieces: Vec<(f32, Vec<RegionAxis>)> = vec![(1.0, Vec::new())];
+    for (i, limit) in limits.iter().enumerate() {
+        let axis = axes.get(i).copied().unwrap_or_default();
+        match *limit {
+            NormalizedLimit::Pin(value) => {
+                let scalar = axis.scalar(value);
+                if scalar == 0.0 {
+                    return Vec::new();
+                }
+                for piece in &mut pieces {
+                    piece.0 *= scalar;
+                }
+            }
+            NormalizedLimit::Range(min, max) => {
+                let spans = rebase_axis(axis, min, max);
+                pieces = pieces
+                    .iter()
+                    .flat_map(|(factor, region)| {
+                        spans.iter().map(move |&(scale, span)| {
+                            let mut region = region.clone();
+                            region.push(span);
+                            (factor * scale, region)
+                        })
+                    })
+                    .collect();
+            }
+        }
+    }
+    pieces
+        .into_iter()
+        .map(|(factor, region)| {
+            let varies = region.iter().any(|axis| axis.peak != 0.0);
+            (factor, varies.then_some(region))
+        })
+        .collect()
+}
+
+/// Returns the number of axes left by `limits`.
+pub(crate) fn remaining_axis_count(limits: &[NormalizedLimit]) -> usize {
+    limits.iter().filter(|limit| !limit.is_pinned()).count()
+}
+
+fn region_key(axes: &[RegionAxis]) -> Vec<[i16; 3]> {
+    let quantize = |value: f32| (value * 16384.0).round() as i16;
+    axes.iter()
+        .map(|axis| {
+            [
+                quantize(axis.start),
+                quantize(axis.peak),
+                quantize(axis.end),
+            ]
+        })
+        .collect()
+}
+
+/// Result of instancing an item variation store.
+pub(crate) struct InstancedStore {
+    /// The store over the remaining axes, with the same outer and inner
+    /// indices as the original.
+    pub store: ItemVariationStore,
+    /// Delta folded into the default value of every item.
+    pub defaults: Vec<Vec<f32>>,
+}
+
+impl InstancedStore {
+    /// Returns the default delta of an item.
+    pub fn default_delta(&self, outer: u16, inner: u16) -> f32 {
+        self.defaults
+            .get(outer as usize)
+            .and_then(|rows| rows.get(inner as usize))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Returns whether the store still has regions.
+    pub fn varies(&self) -> bool {
+        !self.store.regions.is_empty()
+    }
+}
+
+/// Per-region rebasing of a store: each original region becomes scaled
+/// pieces over the new region list.
+pub(crate) struct StoreRebase {
+    /// Pieces of each original region; `None` targets the default.
+    pub pieces: Vec<Vec<(f32, Option<u16>)>>,
+    /// Regions of the instanced store.
+    pub regions: Vec<VariationRegion>,
+}
+
+impl StoreRebase {
+    pub fn new(store: &ItemVariationStore, limits: &[NormalizedLimit]) -> Self {
+        let mut regions = Vec::new();
+        let mut index: HashMap<Vec<[i16; 3]>, u16> = HashMap::new();
+        let pieces = store
+            .regions
+            .iter()
+            .map(|region| {
+                rebase_region(&region.axes, limits)
+                    .into_iter()
+                    .map(|(factor, axes)| {
+                        let target = axes.map(|axes| {
+                            *index.entry(region_key(&axes)).or_insert_with(|| {
+                                regions.push(VariationRegion { axes });
+                                (regions.len() - 1) as u16
+                            })
+                        });
+                        (factor, target)
+                    })
+                    .collect()
+            })
+            .collect();
+        StoreRebase { pieces, regions }
+    }
+
+    /// Rebases the delta rows of item variation data `data`. Returns the new
+    /// region indexes, the new rows and the default delta of each row.
+    pub fn rebase_rows(
+        &self,
+        region_indexes: &[u16],
+        rows: &[Vec<f64>],
+    ) -> (Vec<u16>, Vec<Vec<f64>>, Vec<f64>) {
+        let mut columns: Vec<u16> = Vec::new();
+        for &region in region_indexes {
+            for &(_, target) in self
+                .pieces
+                .get(region as usize)
+                .map_or(&[][..], Vec::as_slice)
+            {
+                if let Some(target) = target
+                    && !columns.contains(&target)
+                {
+                    columns.push(target);
+                }
+            }
+        }
+        let mut new_rows = Vec::with_capacity(rows.len());
+        let mut defaults = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut new_row = vec![0.0; columns.len()];
+            let mut default = 0.0;
+            for (&delta, &region) in row.iter().zip(region_indexes) {
+                for &(factor, target) in self
+                    .pieces
+                    .get(region as usize)
+                    .map_or(&[][..], Vec::as_slice)
+                {
+                    let value = delta * factor as f64;
+                    match target.and_then(|t| columns.iter().position(|&c| c == t)) {
+                        Some(column) => new_row[column] += value,
+                        None => default += value,
+                    }
+                }
+            }
+            new_rows.push(new_row);
+            defaults.push(default);
+        }
+        (columns, new_rows, defaults)
+    }
+}
+
+/// Instances an item variation store. Item indices are preserved so that
+/// references from other tables stay valid.
+pub(crate) fn instance_store(
+    store: &ItemVariationStore,
+    limits: &[NormalizedLimit],
+) -> InstancedStore {
+    let rebase = StoreRebase::new(store, limits);
+    let mut data = Vec::with_capacity(store.data.len());
+    let mut defaults = Vec::with_capacity(store.data.len());
+    for subtable in &store.data {
+        let rows: Vec<Vec<f64>> = subtable
+            .delta_sets
+            .iter()
+            .map(|row| row.iter().map(|&d| d as f64).collect())
+            .collect();
+        let (columns, rows, row_defaults) = rebase.rebase_rows(&subtable.region_indexes, &rows);
+        let rows: Vec<Vec<i32>> = rows
+            .iter()
+            .map(|row| row.iter().map(|d| d.round() as i32).collect())
+            .collect();
+        // Drop columns left without deltas.
+        let used: Vec<usize> = (0..columns.len())
+            .filter(|&c| rows.iter().any(|row| row[c] != 0))
+            .collect();
+        data.push(ItemVariationData {
+            region_indexes: used.iter().map(|&c| columns[c]).collect(),
+            delta_sets: rows
+                .iter()
+                .map(|row| used.iter().map(|&c| row[c]).collect())
+                .collect(),
+        });
+        defaults.push(row_defaults.into_iter().map(|d| d as f32).collect());
+    }
+
+    // Keep only the regions still referenced.
+    let mut remap = vec![None; rebase.regions.len()];
+    let mut regions = Vec::new();
+    for subtable in &mut data {
+        for region in &mut subtable.region_indexes {
+            let index = *remap[*region as usize].get_or_insert_with(|| {
+                regions.push(rebase.regions[*region as usize].clone());
+                (regions.len() - 1) as u16
+            });
+            *region = index;
+        }
+    }
+    InstancedStore {
+        store: ItemVariationStore {
+            format: store.format,
+            axis_count: remaining_axis_count(limits) as u16,
+            regions,
+            data,
+        },
+        defaults,
+    }
+}
+
+/// Instances tuple variations given with dense deltas (one value per point
+/// and dimension). Returns the delta folded into the default of every point
+/// and the tuples over the remaining axes, with deltas for every point.
+pub(crate) fn instance_tuples(
+    tuples: &[(&TupleVariation, Vec<Vec<f32>>)],
+    limits: &[NormalizedLimit],
+    dimensions: usize,
+    point_count: usize,
+) -> (Vec<Vec<f32>>, Vec<TupleVariation>) {
+    let mut defaults = vec![vec![0.0; point_count]; dimensions];
+    let mut merged: Vec<(Vec<RegionAxis>, Vec<Vec<f32>>)> = Vec::new();
+    let mut index: HashMap<Vec<[i16; 3]>, usize> = HashMap::new();
+    for (tuple, deltas) in tuples {
+        let axes: Vec<RegionAxis> = (0..limits.len())
+            .map(|axis| tuple.region_axis(axis))
+            .collect();
+        for (factor, region) in rebase_region(&axes, limits) {
+            let target = match region {
+                None => &mut defaults,
+                Some(region) => {
+                    let slot = *index.entry(region_key(&region)).or_insert_with(|| {
+                        merged.push((region, vec![vec![0.0; point_count]; dimensions]));
+                        merged.len() - 1
+                    });
+                    &mut merged[slot].1
+                }
+            };
+            for (sum, values) in target.iter_mut().zip(deltas) {
+                for (s, v) in sum.iter_mut().zip(values) {
+                    *s += v * factor;
+                }
+            }
+        }
+    }
+    let tuples = merged
+        .into_iter()
+        .filter_map(|(region, deltas)| {
+            let deltas: Vec<Vec<i32>> = deltas
+                .iter()
+                .map(|values| values.iter().map(|v| v.round() as i32).collect())
+                .collect();
+            if deltas.iter().flatten().all(|&d| d == 0) {
+                return None;
+            }
+            let implicit = region
+                .iter()
+                .all(|a| a.start == a.peak.min(0.0) && a.end == a.peak.max(0.0));
+            Some(TupleVariation {
+                peak: region.iter().map(|a| a.peak).collect(),
+                intermediate: (!implicit).then(|| {
+                    (
+                        region.iter().map(|a| a.start).collect(),
+                        region.iter().map(|a| a.end).collect(),
+                    )
+                }),
+                point_numbers: None,
+                deltas,
+            })
+        })
+        .collect();
+    (defaults, tuples)
+}
@@ -2,6 +2,8 @@
 
 pub mod endian;
 pub mod stream;
+pub mod writer;
 
 pub use endian::*;
-pub use stream::*;
\ No newline at end of file
+pub use stream::*;
+pub use writer::*;
\ No newline at end of file
@@ -112,6 +112,17 @@ impl<'a> FontDataStream<'a> {
         }
     }
 
+    /// Reads a 24-bit unsigned integer from the stream. Advances the position by 3 bytes.
+    pub fn read_u24(&mut self) -> Result<u32, Error> {
+        let bytes = self.read_bytes(3)?;
+        Ok(match self.endianness {
+            endian::ByteOrder::BigEndian => u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]),
+            endian::ByteOrder::LittleEndian => {
+                u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0])
+            }
+        })
+    }
+
     /// Reads a signed 8-bit integer from the stream. Advances the position by 1 byte.
     pub fn read_i8(&mut self) -> Result<i8, Error> {
         self.read_u8().map(|v| v as i8)
@@ -125,6 +136,15 @@ impl<'a> FontDataStream<'a> {
         self.read_u32().map(|v| v as i32)
     }
 
+    /// Reads a 16.16 `Fixed` value from the stream. Advances the position by 4 bytes.
+    pub fn read_fixed(&mut self) -> Result<f32, Error> {
+        self.read_i32().map(|v| (v as f64 / 65536.0) as f32)
+    }
+    /// Reads a 2.14 `F2Dot14` value from the stream. Advances the position by 2 bytes.
+    pub fn read_f2dot14(&mut self) -> Result<f32, Error> {
+        self.read_i16().map(|v| v as f32 / 16384.0)
+    }
+
     /// Skips a specified number of bytes in the stream.
     pub fn skip(&mut self, bytes: usize) {
         self.position = usize::min(self.position + bytes, self.data.len());
@@ -302,6 +322,23 @@ impl<'a> FontDataStream<'a> {
         }))
     }
 
+    /// Returns a new stream over the data starting at the absolute `offset`.
+    ///
+    /// The new stream keeps the byte order and starts at position 0, which makes
+    /// it convenient for following offsets measured from a subtable's start.
+    /// Returns `IoError::InvalidOffset` if `offset` lies past the end of the data.
+    pub fn substream(&self, offset: usize) -> Result<FontDataStream<'a>, Error> {
+        let data = self
+            .data
+            .get(offset..)
+            .ok_or(Error::Io(IoError::InvalidOffset { offset }))?;
+        Ok(FontDataStream {
+            data,
+            position: 0,
+            endianness: self.endianness,
+        })
+    }
+
     /// Reads a four-byte tag (common in font tables) and advances the position.
     pub fn read_tag(&mut self) -> Result<[u8; 4], Error> {
         let bytes = self.read_bytes(4)?;
@@ -0,0 +1,375 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Font data writing utilities for the Aurora Font Library. This module provides
+//! the growable counterpart to `FontDataStream`, used by table compilers to
+//! serialize font data, patch offsets and pack shared subtables.
+
+use std::collections::HashMap;
+
+use super::endian;
+use crate::error::{Error, IoError};
+
+/// Width of an offset field reserved in a `FontDataWriter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OffsetWidth {
+    /// `Offset16` field (2 bytes).
+    Offset16,
+    /// `Offset24` field (3 bytes).
+    Offset24,
+    /// `Offset32` field (4 bytes).
+    Offset32,
+}
+
+impl OffsetWidth {
+    /// Returns the size of the field in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            OffsetWidth::Offset16 => 2,
+            OffsetWidth::Offset24 => 3,
+            OffsetWidth::Offset32 => 4,
+        }
+    }
+
+    /// Returns the largest value the field can hold.
+    pub fn max_value(self) -> usize {
+        match self {
+            OffsetWidth::Offset16 => 0xFFFF,
+            OffsetWidth::Offset24 => 0xFF_FFFF,
+            OffsetWidth::Offset32 => 0xFFFF_FFFF,
+        }
+    }
+}
+
+/// A reserved offset field waiting to be patched.
+///
+/// Offsets in OpenType tables are relative to the start of the structure that
+/// contains them, so every placeholder remembers the `base` position the final
+/// offset is computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OffsetPlaceholder {
+    /// Position of the offset field in the output.
+    pub position: usize,
+    /// Position the offset is measured from.
+    pub base: usize,
+    /// Width of the offset field.
+    pub width: OffsetWidth,
+}
+
+/// # Font Data Writer
+///
+/// A growable buffer for serializing font data.
+///
+/// It mirrors `FontDataStream`: every `read_*` primitive has a `write_*`
+/// counterpart honoring the configured byte order.
+///
+/// Offsets to subtables that are not written yet can be reserved with
+/// `reserve_offset16`/`reserve_offset32` and patched later, either manually via
+/// `patch_offset` or by queuing the subtable with `defer_subtable`. Deferred
+/// subtables are packed by `flush_subtables`, which writes identical subtables
+/// only once and points every referencing offset at the shared copy.
+///
+/// # Fields
+///
+/// - `data` is the output buffer.
+/// - `endianness` specifies the byte order for multi-byte writes.
+/// - `deferred` holds subtables queued by `defer_subtable`.
+#[derive(Debug, Default)]
+pub struct FontDataWriter {
+    /// The output buffer.
+    data: Vec<u8>,
+    /// The endianness for multi-byte writes.
+    endianness: endian::ByteOrder,
+    /// Subtables waiting to be packed, with the offsets referencing them.
+    deferred: Vec<(OffsetPlaceholder, Vec<u8>)>,
+}
+
+impl FontDataWriter {
+    /// # FontDataWriter
+    ///
+    /// Creates a new, empty FontDataWriter.
+    ///
+    /// **Endianness** defaults to Big Endian. See `with_endianness` to change it.
+    pub fn new() -> Self {
+        FontDataWriter {
+            data: Vec::new(),
+            endianness: endian::ByteOrder::BigEndian,
+            deferred: Vec::new(),
+        }
+    }
+
+    /// Creates a new FontDataWriter with room for `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        FontDataWriter {
+            data: Vec::with_capacity(capacity),
+            endianness: endian::ByteOrder::BigEndian,
+            deferred: Vec::new(),
+        }
+    }
+
+    /// Sets the endianness for the writer.
+    pub fn with_endianness(mut self, endianness: endian::ByteOrder) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Writes a single byte.
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    /// Writes a 16-bit unsigned integer.
+    pub fn write_u16(&mut self, value: u16) {
+        match self.endianness {
+            endian::ByteOrder::BigEndian => self.data.extend_from_slice(&value.to_be_bytes()),
+            endian::ByteOrder::LittleEndian => self.data.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    /// Writes a 24-bit unsigned integer. The upper 8 bits of `value` are ignored.
+    pub fn write_u24(&mut self, value: u32) {
+        let bytes = value.to_be_bytes();
+        match self.endianness {
+            endian::ByteOrder::BigEndian => self.data.extend_from_slice(&bytes[1..]),
+            endian::ByteOrder::LittleEndian => {
+                self.data.extend_from_slice(&[bytes[3], bytes[2], bytes[1]])
+            }
+        }
+    }
+
+    /// Writes a 32-bit unsigned integer.
+    pub fn write_u32(&mut self, value: u32) {
+        match self.endianness {
+            endian::ByteOrder::BigEndian => self.data.extend_from_slice(&value.to_be_bytes()),
+            endian::ByteOrder::LittleEndian => self.data.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+
+    /// Writes a signed 8-bit integer.
+    pub fn write_i8(&mut self, value: i8) {
+        self.write_u8(value as u8);
+    }
+    /// Writes a signed 16-bit integer.
+    pub fn write_i16(&mut self, value: i16) {
+        self.write_u16(value as u16);
+    }
+    /// Writes a signed 32-bit integer.
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    /// Writes a 16.16 `Fixed` value, rounding to the nearest representable value.
+    pub fn write_fixed(&mut self, value: f32) {
+        self.write_i32((value as f64 * 65536.0).round() as i32);
+    }
+
+    /// Writes a 2.14 `F2Dot14` value, rounding to the nearest representable value.
+    ///
+    /// Values outside the representable range (-2.0..2.0) are clamped.
+    pub fn write_f2dot14(&mut self, value: f32) {
+        let raw = (value as f64 * 16384.0).round();
+        self.write_i16(raw.clamp(i16::MIN as f64, i16::MAX as f64) as i16);
+    }
+
+    /// Writes a four-byte tag.
+    pub fn write_tag(&mut self, tag: [u8; 4]) {
+        self.data.extend_from_slice(&tag);
+    }
+
+    /// Writes a raw byte slice.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Writes `count` zero bytes.
+    pub fn write_zeros(&mut self, count: usize) {
+        self.data.resize(self.data.len() + count, 0);
+    }
+
+    /// Pads the output with zero bytes until its length is a multiple of `alignment`.
+    pub fn align(&mut self, alignment: usize) {
+        if alignment > 1 {
+            let padding = (alignment - self.data.len() % alignment) % alignment;
+            self.write_zeros(padding);
+        }
+    }
+
+    /// Returns the current write position (the length of the output).
+    pub fn position(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the current length of the output.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether nothing has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the bytes written so far.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Overwrites a 16-bit unsigned integer at an absolute offset.
+    pub fn write_at_u16(&mut self, offset: usize, value: u16) -> Result<(), Error> {
+        let bytes = match self.endianness {
+            endian::ByteOrder::BigEndian => value.to_be_bytes(),
+            endian::ByteOrder::LittleEndian => value.to_le_bytes(),
+        };
+        self.write_at_bytes(offset, &bytes)
+    }
+
+    /// Overwrites a 32-bit unsigned integer at an absolute offset.
+    pub fn write_at_u32(&mut self, offset: usize, value: u32) -> Result<(), Error> {
+        let bytes = match self.endianness {
+            endian::ByteOrder::BigEndian => value.to_be_bytes(),
+            endian::ByteOrder::LittleEndian => value.to_le_bytes(),
+        };
+        self.write_at_bytes(offset, &bytes)
+    }
+
+    /// Overwrites bytes at an absolute offset. The range must already be written.
+    pub fn write_at_bytes(&mut self, offset: usize, bytes: &[u8]) -> Result<(), Error> {
+        let len = self.data.len();
+        match self.data.get_mut(offset..offset + bytes.len()) {
+            Some(target) => {
+                target.copy_from_slice(bytes);
+                Ok(())
+            }
+            None => Err(Error::Io(IoError::OutOfBounds {
+                requested: bytes.len(),
+                available: len.saturating_sub(offset),
+            })),
+        }
+    }
+
+    /// Reserves an `Offset16` field measured from `base` and writes zero into it.
+    pub fn reserve_offset16(&mut self, base: usize) -> OffsetPlaceholder {
+        self.reserve_offset(base, OffsetWidth::Offset16)
+    }
+
+    /// Reserves an `Offset24` field measured from `base` and writes zero into it.
+    pub fn reserve_offset24(&mut self, base: usize) -> OffsetPlaceholder {
+        self.reserve_offset(base, OffsetWidth::Offset24)
+    }
+
+    /// Reserves an `Offset32` field measured from `base` and writes zero into it.
+    pub fn reserve_offset32(&mut self, base: usize) -> OffsetPlaceholder {
+        self.reserve_offset(base, OffsetWidth::Offset32)
+    }
+
+    /// Reserves an offset field of the given width measured from `base`.
+    pub fn reserve_offset(&mut self, base: usize, width: OffsetWidth) -> OffsetPlaceholder {
+        let position = self.data.len();
+        self.write_zeros(width.size());
+        OffsetPlaceholder {
+            position,
+            base,
+            width,
+        }
+    }
+
+    /// Patches a reserved offset so it points at the absolute position `target`.
+    ///
+    /// Returns `IoError::OffsetOverflow` if the target lies before the
+    /// placeholder's base or is too far away to fit in the field.
+    pub fn patch_offset(
+        &mut self,
+        placeholder: OffsetPlaceholder,
+        target: usize,
+    ) -> Result<(), Error> {
+        let value = target
+            .checked_sub(placeholder.base)
+            .filter(|v| *v <= placeholder.width.max_value())
+            .ok_or(Error::Io(IoError::OffsetOverflow {
+                position: placeholder.position,
+                offset: target.wrapping_sub(placeholder.base),
+            }))?;
+        let bytes = (value as u32).to_be_bytes();
+        let mut field = bytes[4 - placeholder.width.size()..].to_vec();
+        if self.endianness.is_little() {
+            field.reverse();
+        }
+        self.write_at_bytes(placeholder.position, &field)
+    }
+
+    /// Patches a reserved offset so it points at the current write position.
+    pub fn patch_offset_here(&mut self, placeholder: OffsetPlaceholder) -> Result<(), Error> {
+        let target = self.position();
+        self.patch_offset(placeholder, target)
+    }
+
+    /// Queues a fully serialized subtable to be written by `flush_subtables`.
+    ///
+    /// The subtable's own offsets must already be resolved (e.g. by serializing
+    /// it with a separate writer and calling `into_bytes`), since it may be
+    /// shared with other placeholders that queue identical bytes.
+    pub fn defer_subtable(&mut self, placeholder: OffsetPlaceholder, subtable: Vec<u8>) {
+        self.deferred.push((placeholder, subtable));
+    }
+
+    /// Returns the number of subtables waiting to be packed.
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+
+    /// Writes every deferred subtable at the end of the output and patches the
+    /// offsets referencing them.
+    ///
+    /// Identical subtables are written once and shared. Subtables are written in
+    /// the order they were first queued, each aligned to `alignment` bytes
+    /// (use `1` for no alignment).
+    pub fn flush_subtables(&mut self, alignment: usize) -> Result<(), Error> {
+        let deferred = std::mem::take(&mut self.deferred);
+        let mut written: HashMap<&[u8], usize> = HashMap::new();
+        for (placeholder, subtable) in &deferred {
+            let target = match written.get(subtable.as_slice()) {
+                Some(&position) => position,
+                None => {
+                    self.align(alignment);
+                    let position = self.position();
+                    self.write_bytes(subtable);
+                    written.insert(subtable.as_slice(), position);
+                    position
+                }
+            };
+            self.patch_offset(*placeholder, target)?;
+        }
+        Ok(())
+    }
+
+    /// Packs any deferred subtables and returns the finished output.
+    pub fn into_bytes(mut self) -> Result<Vec<u8>, Error> {
+        if !self.deferred.is_empty() {
+            self.flush_subtables(1)?;
+        }
+        Ok(self.data)
+    }
+}
+
+/// Serializes a structure with its own writer, packing its subtables.
+pub(crate) fn serialize(
+    write: impl FnOnce(&mut FontDataWriter) -> Result<(), Error>,
+) -> Result<Vec<u8>, Error> {
+    let mut writer = FontDataWriter::new();
+    write(&mut writer)?;
+    writer.into_bytes()
+}
+
+impl std::io::Write for FontDataWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -7,9 +7,19 @@
 //! This library provides functionality for parsing and manipulating font files.
 
 pub mod common;
+pub mod components;
+pub mod compress;
+pub mod decompress;
 pub mod error;
+pub mod hinting;
+pub mod instancer;
 pub mod io;
+pub mod outline;
+#[cfg(feature = "raster")]
+pub mod raster;
+pub mod subset;
+pub mod tags;
 
 /// Interface for high-level font data conversion and manipulation (opt-in).
 #[cfg(feature = "full")]
-pub mod font;
\ No newline at end of file
+pub mod optional;
\ No newline at end of file
@@ -34,10 +34,10 @@ impl FontSink for Font {
         Ok(())
     }
 
-    // fn consume_svg(&mut self, svg_data: &[u8]) -> Result<(), Self::Err> {
-    //     self.svg_data = Some(svg_data.to_vec());
-    //     Ok(())
-    // }
+    fn consume_svg(&mut self, svg_data: &[u8]) -> Result<(), Self::Err> {
+        self.svg_data = Some(svg_data.to_vec());
+        Ok(())
+    }
 
     fn finish(self) -> Result<Self::Output, Self::Err> {
         Ok(self)
@@ -64,7 +64,7 @@ where
         FontFileHeader::WOFF | FontFileHeader::WOF2 => return Err(Error::InvalidFormat),
         FontFileHeader::SVG => {
             let bytes = stream.slice_range(0..stream.len())?;
-            //sink.consume_svg(bytes).map_err(|e| e.into())?;
+            sink.consume_svg(bytes).map_err(|e| e.into())?;
         }
         _ => {
             let snft = extract_snft_tables_from_stream(stream)?;
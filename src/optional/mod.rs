@@ -6,7 +6,9 @@
 
 
 pub mod builtin;
+pub mod render;
 pub mod sink;
 
 pub use builtin::*;
+pub use render::*;
 pub use sink::*;
\ No newline at end of file
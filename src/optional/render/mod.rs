@@ -0,0 +1,617 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Text run rendering into RGBA images. Each glyph is drawn from the best
+//! source the font has: a `COLR` color glyph, an `sbix` image, a `CBDT` or
+//! `EBDT` bitmap, and finally its `glyf`, `CFF ` or `CFF2` outline. The
+//! font's device metrics decide when outlines are hinted and when they are
+//! anti-aliased.
+
+mod paint;
+mod png;
+
+use crate::common::snft::read_snft;
+use crate::components::cbdt::{CbdtTable, read_cbdt};
+use crate::components::cblc::{CblcTable, read_cblc};
+use crate::components::cff1::{CffTable, read_cff};
+use crate::components::cff2::{Cff2Table, read_cff2};
+use crate::components::colr::{ColrTable, CompositeMode, Transform, read_colr};
+use crate::components::cpal::{Color, CpalTable, read_cpal};
+use crate::components::cvt::read_cvt;
+use crate::components::device::{DeviceMetrics, RenderingHints};
+use crate::components::ebdt::{BitmapData, EbdtTable, read_ebdt};
+use crate::components::eblc::{EblcTable, read_eblc};
+use crate::components::ebsc::{EbscTable, read_ebsc};
+use crate::components::fpgm::read_fpgm;
+use crate::components::gasp::read_gasp;
+use crate::components::glyf::{GlyfTable, read_glyf};
+use crate::components::hdmx::read_hdmx;
+use crate::components::head::read_head;
+use crate::components::hhea::read_hhea;
+use crate::components::hmtx::{HmtxTable, read_hmtx};
+use crate::components::loca::read_loca;
+use crate::components::ltsh::read_ltsh;
+use crate::components::maxp::read_maxp;
+use crate::components::prep::read_prep;
+use crate::components::sbix::{GRAPHIC_TYPE_PNG, SbixTable, read_sbix};
+use crate::components::vdmx::read_vdmx;
+use crate::error::Error;
+use crate::hinting::{Hinter, HintingMode};
+use crate::io::stream::FontDataStream;
+use crate::outline::{PathCommand, cff_path, cff2_path, glyf_path};
+use crate::raster::{Bitmap, RasterOptions, render};
+
+use paint::{Pixel, RegionPainter, composite, premultiply};
+use png::{PngImage, decode_png, encode_png};
+
+/// An image of straight-alpha RGBA pixels, rows from the top.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RgbaImage {
+    /// Width in pixels.
+    pub width: usize,
+    /// Height in pixels.
+    pub height: usize,
+    /// Four bytes per pixel: red, green, blue and alpha.
+    pub data: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Creates a transparent image.
+    pub fn new(width: usize, height: usize) -> Self {
+        RgbaImage {
+            width,
+            height,
+            data: vec![0; width * height * 4],
+        }
+    }
+
+    /// Sets every pixel to `color`.
+    pub fn fill(&mut self, color: Color) {
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.red, color.green, color.blue, color.alpha]);
+        }
+    }
+
+    /// Returns the color of a pixel.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = (y * self.width + x) * 4;
+        let p = &self.data[offset..offset + 4];
+        Some(Color::rgba(p[0], p[1], p[2], p[3]))
+    }
+
+    /// Encodes the image as a PNG file.
+    pub fn to_png(&self) -> Vec<u8> {
+        encode_png(self.width, self.height, &self.data)
+    }
+
+    /// Composites a premultiplied pixel over the pixel at `x`, `y`; pixels
+    /// outside the image are ignored.
+    fn blend(&mut self, x: i64, y: i64, source: Pixel) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 || source[3] <= 0.0 {
+            return;
+        }
+        let offset = (y as usize * self.width + x as usize) * 4;
+        let p = &mut self.data[offset..offset + 4];
+        let dest = premultiply(Color::rgba(p[0], p[1], p[2], p[3]));
+        let out = composite(CompositeMode::SrcOver, source, dest);
+        let alpha = out[3];
+        if alpha <= 0.0 {
+            p.copy_from_slice(&[0; 4]);
+            return;
+        }
+        for i in 0..3 {
+            p[i] = (out[i] / alpha * 255.0 + 0.5).min(255.0) as u8;
+        }
+        p[3] = (alpha * 255.0 + 0.5).min(255.0) as u8;
+    }
+}
+
+/// A glyph of a shaped run, as produced by a text shaper. Values are in font
+/// units with y up.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ShapedGlyph {
+    /// Glyph to draw.
+    pub glyph_id: u16,
+    /// Horizontal pen movement after the glyph.
+    pub x_advance: f32,
+    /// Vertical pen movement after the glyph.
+    pub y_advance: f32,
+    /// Horizontal offset of the glyph from the pen.
+    pub x_offset: f32,
+    /// Vertical offset of the glyph from the pen.
+    pub y_offset: f32,
+}
+
+/// Options for [`TextRenderer::render_run`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// Font size in pixels per em.
+    pub size: f32,
+    /// Text color, also the foreground color of color glyphs.
+    pub color: Color,
+    /// `CPAL` palette of color glyphs; 0 is the default palette.
+    pub palette_index: usize,
+    /// Whether TrueType outlines are hinted at sizes where `gasp` asks for
+    /// grid-fitting. Hinting needs a whole-pixel size.
+    pub hinting: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            size: 16.0,
+            color: Color::rgba(0, 0, 0, 255),
+            palette_index: 0,
+            hinting: true,
+        }
+    }
+}
+
+/// Outline table of a font.
+enum Outlines {
+    None,
+    Glyf(GlyfTable),
+    Cff(CffTable),
+    Cff2(Cff2Table),
+}
+
+/// Renders shaped glyph runs of one font into RGBA images.
+pub struct TextRenderer {
+    units_per_em: u16,
+    outlines: Outlines,
+    hmtx: HmtxTable,
+    hinter: Option<Hinter>,
+    device: DeviceMetrics,
+    colr: Option<(ColrTable, CpalTable)>,
+    sbix: Option<SbixTable>,
+    cbdt: Option<(CblcTable, CbdtTable)>,
+    ebdt: Option<(EblcTable, EbdtTable, Option<EbscTable>)>,
+}
+
+impl TextRenderer {
+    /// Loads the tables needed for rendering from an SFNT font.
+    ///
+    /// # Errors
+    /// * Returns `Error` if `head`, `maxp`, `hhea` or `hmtx` is missing, or if
+    ///   a table used for rendering cannot be parsed. A font whose `fpgm` or
+    ///   `prep` program fails is rendered unhinted, and bitmap tables that
+    ///   cannot be parsed (`sbix`, `CBLC`/`CBDT`, `EBLC`/`EBDT`/`EBSC`) are
+    ///   ignored.
+    pub fn new(data: &[u8]) -> Result<Self, Error> {
+        let snft = read_snft(&mut FontDataStream::new(data))?;
+        let table = |tag: &[u8; 4]| snft.table_stream(tag, data);
+        let optional = |tag: &[u8; 4]| snft.has_table(tag).then(|| table(tag)).transpose();
+
+        let head = read_head(&mut table(b"head")?)?;
+        let maxp = read_maxp(&mut table(b"maxp")?)?;
+        let hhea = read_hhea(&mut table(b"hhea")?)?;
+        let hmtx = read_hmtx(
+            &mut table(b"hmtx")?,
+            hhea.number_of_h_metrics,
+            maxp.num_glyphs,
+        )?;
+
+        let outlines = if let Some(mut glyf) = optional(b"glyf")? {
+            let loca = read_loca(&mut table(b"loca")?, head.has_long_loca(), maxp.num_glyphs)?;
+            Outlines::Glyf(read_glyf(&mut glyf, &loca)?)
+        } else if let Some(mut cff) = optional(b"CFF ")? {
+            Outlines::Cff(read_cff(&mut cff)?)
+        } else if let Some(mut cff2) = optional(b"CFF2")? {
+            Outlines::Cff2(read_cff2(&mut cff2)?)
+        } else {
+            Outlines::None
+        };
+
+        let hinter = match (&outlines, &maxp.truetype) {
+            (Outlines::Glyf(_), Some(limits)) => {
+                let fpgm = optional(b"fpgm")?
+                    .map(|mut d| read_fpgm(&mut d))
+                    .transpose()?;
+                let prep = optional(b"prep")?
+                    .map(|mut d| read_prep(&mut d))
+                    .transpose()?;
+                let cvt = optional(b"cvt ")?
+                    .map(|mut d| read_cvt(&mut d))
+                    .transpose()?;
+                let cvt: Vec<f32> = cvt.map_or(Vec::new(), |cvt| {
+                    cvt.values.iter().map(|&v| v as f32).collect()
+                });
+                Hinter::new(
+                    fpgm.as_ref().map_or(&[][..], |t| &t.instructions),
+                    prep.as_ref().map_or(&[][..], |t| &t.instructions),
+                    &cvt,
+                    limits,
+                    head.units_per_em,
+                    HintingMode::V40,
+                )
+                .ok()
+            }
+            _ => None,
+        };
+
+        let colr = match optional(b"COLR")? {
+            Some(mut colr) => {
+                let cpal = optional(b"CPAL")?
+                    .map(|mut d| read_cpal(&mut d))
+                    .transpose()?
+                    .unwrap_or_default();
+                Some((read_colr(&mut colr)?, cpal))
+            }
+            None => None,
+        };
+        let sbix = optional(b"sbix")
+            .ok()
+            .flatten()
+            .and_then(|mut d| read_sbix(&mut d, maxp.num_glyphs).ok());
+        let cbdt = match (optional(b"CBLC"), optional(b"CBDT")) {
+            (Ok(Some(mut cblc)), Ok(Some(mut cbdt))) => {
+                read_cblc(&mut cblc).ok().zip(read_cbdt(&mut cbdt).ok())
+            }
+            _ => None,
+        };
+        let ebdt = match (optional(b"EBLC"), optional(b"EBDT")) {
+            (Ok(Some(mut eblc)), Ok(Some(mut ebdt))) => {
+                let ebsc = optional(b"EBSC")
+                    .ok()
+                    .flatten()
+                    .and_then(|mut d| read_ebsc(&mut d).ok());
+                read_eblc(&mut eblc)
+                    .ok()
+                    .zip(read_ebdt(&mut ebdt).ok())
+                    .map(|(eblc, ebdt)| (eblc, ebdt, ebsc))
+            }
+            _ => None,
+        };
+        let device = DeviceMetrics {
+            gasp: optional(b"gasp")?
+                .map(|mut d| read_gasp(&mut d))
+                .transpose()?,
+            hdmx: optional(b"hdmx")?
+                .map(|mut d| read_hdmx(&mut d, maxp.num_glyphs))
+                .transpose()?,
+            vdmx: optional(b"VDMX")?
+                .map(|mut d| read_vdmx(&mut d))
+                .transpose()?,
+            ltsh: optional(b"LTSH")?
+                .map(|mut d| read_ltsh(&mut d))
+                .transpose()?,
+        };
+
+        Ok(TextRenderer {
+            units_per_em: head.units_per_em.max(1),
+            outlines,
+            hmtx,
+            hinter,
+            device,
+            colr,
+            sbix,
+            cbdt,
+            ebdt,
+        })
+    }
+
+    /// Returns the outline of a glyph in font units.
+    pub fn outline(&self, glyph_id: u16) -> Result<Vec<PathCommand>, Error> {
+        match &self.outlines {
+            Outlines::None => Ok(Vec::new()),
+            Outlines::Glyf(glyf) => glyf_path(glyf, glyph_id),
+            Outlines::Cff(cff) => cff_path(cff, glyph_id),
+            Outlines::Cff2(cff2) => cff2_path(cff2, glyph_id, &[]),
+        }
+    }
+
+    /// Returns the font's rendering hints at `size` pixels per em.
+    pub fn rendering_hints(&self, size: f32) -> RenderingHints {
+        self.device
+            .rendering_hints(size.round().clamp(0.0, u16::MAX as f32) as u16)
+    }
+
+    /// Draws a shaped run over `image`.
+    ///
+    /// # Arguments
+    /// * `image` - The image to draw on.
+    /// * `glyphs` - The glyphs of the run, in visual order.
+    /// * `origin` - Pen position of the first glyph on the baseline, in
+    ///   image pixels with y down.
+    /// * `options` - Size, color and hinting.
+    ///
+    /// Returns the pen position after the last glyph.
+    pub fn render_run(
+        &mut self,
+        image: &mut RgbaImage,
+        glyphs: &[ShapedGlyph],
+        origin: (f32, f32),
+        options: &RenderOptions,
+    ) -> Result<(f32, f32), Error> {
+        let scale = options.size / self.units_per_em as f32;
+        let hints = self.rendering_hints(options.size);
+        let ppem = hints.ppem;
+        let hinted = options.hinting
+            && hints.gridfit
+            && ppem as f32 == options.size
+            && self
+                .hinter
+                .as_mut()
+                .is_some_and(|hinter| hinter.set_ppem(ppem).is_ok());
+        let style = GlyphStyle {
+            scale,
+            ppem,
+            color: options.color,
+            palette_index: options.palette_index,
+            hinted,
+            antialias: hints.grayscale,
+        };
+
+        let mut pen = origin;
+        for glyph in glyphs {
+            let position = (
+                pen.0 + glyph.x_offset * scale,
+                pen.1 - glyph.y_offset * scale,
+            );
+            self.render_glyph(image, glyph.glyph_id, position, &style)?;
+            pen = (
+                pen.0 + glyph.x_advance * scale,
+                pen.1 - glyph.y_advance * scale,
+            );
+        }
+        Ok(pen)
+    }
+
+    /// Draws one glyph with its origin at `position`.
+    fn render_glyph(
+        &mut self,
+        image: &mut RgbaImage,
+        glyph_id: u16,
+        position: (f32, f32),
+        style: &GlyphStyle,
+    ) -> Result<(), Error> {
+        if self.render_color_glyph(image, glyph_id, position, style)? {
+            return Ok(());
+        }
+        if let Some((bitmap, left, top, scale)) = self.bitmap_glyph(glyph_id, style)? {
+            draw_image(image, &bitmap, position.0 + left, position.1 + top, scale);
+            return Ok(());
+        }
+
+        let hinted = match (&self.outlines, self.hinter.as_mut()) {
+            (Outlines::Glyf(glyf), Some(hinter)) if style.hinted => {
+                hinter.hint_glyph(glyf, &self.hmtx, glyph_id).ok()
+            }
+            _ => None,
+        };
+        let (path, options) = match hinted {
+            // Hinted outlines are in pixels and keep the pen on the pixel grid.
+            Some(glyph) => (
+                glyph.path(),
+                RasterOptions {
+                    offset: (position.0.round(), -position.1.round()),
+                    ..Default::default()
+                },
+            ),
+            None => (
+                self.outline(glyph_id)?,
+                RasterOptions {
+                    scale: style.scale,
+                    offset: (position.0, -position.1),
+                    ..Default::default()
+                },
+            ),
+        };
+        let coverage = render(&path, &options)?;
+        draw_coverage(image, &coverage, style.color, style.antialias);
+        Ok(())
+    }
+
+    /// Draws a `COLR` glyph; returns `false` if the glyph has no color.
+    fn render_color_glyph(
+        &self,
+        image: &mut RgbaImage,
+        glyph_id: u16,
+        position: (f32, f32),
+        style: &GlyphStyle,
+    ) -> Result<bool, Error> {
+        let Some((colr, cpal)) = &self.colr else {
+            return Ok(false);
+        };
+        if colr.layers(glyph_id).is_none() && !colr.base_glyph_paints.contains_key(&glyph_id) {
+            return Ok(false);
+        }
+        // Paint within the clip box when there is one, else over the image.
+        let (mut x0, mut y0) = (0.0f32, 0.0f32);
+        let (mut x1, mut y1) = (image.width as f32, image.height as f32);
+        if let Some(clip) = colr.clip_box(glyph_id) {
+            x0 = x0.max(position.0 + clip.x_min as f32 * style.scale).floor();
+            x1 = x1.min(position.0 + clip.x_max as f32 * style.scale).ceil();
+            y0 = y0.max(position.1 - clip.y_max as f32 * style.scale).floor();
+            y1 = y1.min(position.1 - clip.y_min as f32 * style.scale).ceil();
+        }
+        if x1 <= x0 || y1 <= y0 {
+            return Ok(true);
+        }
+        let (width, height) = ((x1 - x0) as usize, (y1 - y0) as usize);
+        let transform = Transform {
+            xx: style.scale,
+            yy: -style.scale,
+            dx: position.0 - x0,
+            dy: position.1 - y0,
+            ..Transform::IDENTITY
+        };
+        let outline = |glyph_id: u16| self.outline(glyph_id);
+        let mut painter = RegionPainter::new(width, height, transform, &outline);
+        colr.paint_glyph(
+            glyph_id,
+            cpal,
+            style.palette_index,
+            style.color,
+            &[],
+            &mut painter,
+        )?;
+        if let Some(error) = painter.error.take() {
+            return Err(error);
+        }
+        for (i, pixel) in painter.finish().into_iter().enumerate() {
+            let (x, y) = ((i % width) as i64, (i / width) as i64);
+            image.blend(x0 as i64 + x, y0 as i64 + y, pixel);
+        }
+        Ok(true)
+    }
+
+    /// Returns the bitmap image of a glyph with the offset of its top-left
+    /// corner from the glyph origin and its scale, from `sbix`, `CBDT` or
+    /// `EBDT` in that order. Bitmaps in formats other than PNG and raw pixels
+    /// are skipped.
+    fn bitmap_glyph(
+        &self,
+        glyph_id: u16,
+        style: &GlyphStyle,
+    ) -> Result<Option<(PngImage, f32, f32, f32)>, Error> {
+        let size = style.scale * self.units_per_em as f32;
+        if let Some(sbix) = &self.sbix
+            && let Some(bitmap) = sbix.bitmap_glyph(glyph_id, style.ppem)?
+            && bitmap.glyph.graphic_type == GRAPHIC_TYPE_PNG
+            && bitmap.ppem > 0
+        {
+            let image = decode_png(&bitmap.glyph.data)?;
+            let scale = size / bitmap.ppem as f32;
+            let left = bitmap.glyph.origin_offset_x as f32 * scale;
+            let top = -(bitmap.glyph.origin_offset_y as f32 + image.height as f32) * scale;
+            return Ok(Some((image, left, top, scale)));
+        }
+        let Ok(ppem) = u8::try_from(style.ppem) else {
+            return Ok(None);
+        };
+        let tables = [
+            self.cbdt.as_ref().map(|(cblc, cbdt)| (cblc, cbdt, None)),
+            self.ebdt
+                .as_ref()
+                .map(|(eblc, ebdt, ebsc)| (eblc, ebdt, ebsc.as_ref())),
+        ];
+        for (location, data, scaling) in tables.into_iter().flatten() {
+            let Some(glyph) = data.bitmap_glyph(location, scaling, glyph_id, ppem)? else {
+                continue;
+            };
+            let metrics = glyph.metrics;
+            let (width, height) = (metrics.width as usize, metrics.height as usize);
+            let image = match glyph.data {
+                BitmapData::Png(png) => decode_png(&png)?,
+                BitmapData::Pixels(pixels) if glyph.bit_depth == 32 => PngImage {
+                    width,
+                    height,
+                    data: pixels
+                        .chunks_exact(4)
+                        .flat_map(|bgra| {
+                            let unpremultiply = |c: u8| match bgra[3] {
+                                0 => 0,
+                                a => (c as u32 * 255 / a as u32).min(255) as u8,
+                            };
+                            [
+                                unpremultiply(bgra[2]),
+                                unpremultiply(bgra[1]),
+                                unpremultiply(bgra[0]),
+                                bgra[3],
+                            ]
+                        })
+                        .collect(),
+                },
+                BitmapData::Pixels(pixels) => {
+                    let max = ((1u32 << glyph.bit_depth.clamp(1, 8)) - 1).max(1);
+                    let color = style.color;
+                    PngImage {
+                        width,
+                        height,
+                        data: pixels
+                            .iter()
+                            .flat_map(|&value| {
+                                let alpha = (value as u32).min(max) * color.alpha as u32 / max;
+                                [color.red, color.green, color.blue, alpha as u8]
+                            })
+                            .collect(),
+                    }
+                }
+            };
+            if image.data.len() < image.width * image.height * 4 {
+                continue;
+            }
+            let scale = glyph.scale * size / ppem.max(1) as f32;
+            let left = metrics.hori_bearing_x as f32 * scale;
+            let top = -(metrics.hori_bearing_y as f32) * scale;
+            return Ok(Some((image, left, top, scale)));
+        }
+        Ok(None)
+    }
+}
+
+/// How the glyphs of a run are drawn.
+struct GlyphStyle {
+    /// Pixels per font unit.
+    scale: f32,
+    /// Size rounded to whole pixels per em.
+    ppem: u16,
+    color: Color,
+    palette_index: usize,
+    hinted: bool,
+    antialias: bool,
+}
+
+/// Draws coverage in `color`; without anti-aliasing, pixels at least half
+/// covered are filled and the rest left untouched.
+fn draw_coverage(image: &mut RgbaImage, bitmap: &Bitmap, color: Color, antialias: bool) {
+    let color = premultiply(color);
+    for (row, line) in bitmap.data.chunks_exact(bitmap.width.max(1)).enumerate() {
+        for (column, &coverage) in line.iter().enumerate() {
+            let coverage = match antialias {
+                true => coverage as f32 / 255.0,
+                false if coverage >= 128 => 1.0,
+                false => 0.0,
+            };
+            let x = bitmap.left as i64 + column as i64;
+            let y = row as i64 - bitmap.top as i64;
+            image.blend(x, y, color.map(|c| c * coverage));
+        }
+    }
+}
+
+/// Draws an image scaled by `scale` with its top-left corner at `left`,
+/// `top`, sampling it bilinearly.
+fn draw_image(image: &mut RgbaImage, source: &PngImage, left: f32, top: f32, scale: f32) {
+    if scale <= 0.0 || !scale.is_finite() {
+        return;
+    }
+    let texel = |x: i64, y: i64| -> Pixel {
+        if x < 0 || y < 0 || x >= source.width as i64 || y >= source.height as i64 {
+            return [0.0; 4];
+        }
+        let offset = (y as usize * source.width + x as usize) * 4;
+        let p = &source.data[offset..offset + 4];
+        premultiply(Color::rgba(p[0], p[1], p[2], p[3]))
+    };
+    let right = left + source.width as f32 * scale;
+    let bottom = top + source.height as f32 * scale;
+    for y in top.floor() as i64..bottom.ceil() as i64 {
+        for x in left.floor() as i64..right.ceil() as i64 {
+            let u = (x as f32 + 0.5 - left) / scale - 0.5;
+            let v = (y as f32 + 0.5 - top) / scale - 0.5;
+            let (x0, y0) = (u.floor(), v.floor());
+            let (fx, fy) = (u - x0, v - y0);
+            let (x0, y0) = (x0 as i64, y0 as i64);
+            let (a, b, c, d) = (
+                texel(x0, y0),
+                texel(x0 + 1, y0),
+                texel(x0, y0 + 1),
+                texel(x0 + 1, y0 + 1),
+            );
+            let pixel: Pixel = std::array::from_fn(|i| {
+                let top = a[i] + (b[i] - a[i]) * fx;
+                let bottom = c[i] + (d[i] - c[i]) * fx;
+                top + (bottom - top) * fy
+            });
+            image.blend(x, y, pixel);
+        }
+    }
+}
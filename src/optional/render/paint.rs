@@ -0,0 +1,429 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Rasterizing `ColorPainter` for `COLR` glyphs. Clips are coverage masks,
+//! layers are premultiplied RGBA buffers over a region of the target image.
+
+use crate::components::colr::{
+    Brush, ClipShape, ColorPainter, CompositeMode, Extend, GradientStop, Transform,
+};
+use crate::components::cpal::Color;
+use crate::error::Error;
+use crate::outline::PathCommand;
+use crate::raster::{FillRule, Rasterizer};
+
+/// Premultiplied RGBA with components in `0..=1`.
+pub(crate) type Pixel = [f32; 4];
+
+/// Paints a color glyph into a rectangle of the target image.
+pub(crate) struct RegionPainter<'a> {
+    width: usize,
+    height: usize,
+    /// Returns the outline of a glyph in font units.
+    outline: &'a dyn Fn(u16) -> Result<Vec<PathCommand>, Error>,
+    transforms: Vec<Transform>,
+    clips: Vec<Vec<f32>>,
+    layers: Vec<(Vec<Pixel>, CompositeMode)>,
+    /// First error met while painting; the trait methods cannot return one.
+    pub error: Option<Error>,
+}
+
+impl<'a> RegionPainter<'a> {
+    /// Creates a painter for a `width` by `height` region. `transform` maps
+    /// font units to region pixels, with y down.
+    pub fn new(
+        width: usize,
+        height: usize,
+        transform: Transform,
+        outline: &'a dyn Fn(u16) -> Result<Vec<PathCommand>, Error>,
+    ) -> Self {
+        RegionPainter {
+            width,
+            height,
+            outline,
+            transforms: vec![transform],
+            clips: vec![vec![1.0; width * height]],
+            layers: vec![(vec![[0.0; 4]; width * height], CompositeMode::SrcOver)],
+            error: None,
+        }
+    }
+
+    /// Returns the painted pixels.
+    pub fn finish(mut self) -> Vec<Pixel> {
+        self.layers.swap_remove(0).0
+    }
+
+    fn transform(&self) -> Transform {
+        self.transforms.last().copied().unwrap_or_default()
+    }
+
+    fn clip_path(&mut self, clip: ClipShape) -> Vec<PathCommand> {
+        match clip {
+            ClipShape::Glyph(glyph_id) => match (self.outline)(glyph_id) {
+                Ok(path) => path,
+                Err(error) => {
+                    self.error.get_or_insert(error);
+                    Vec::new()
+                }
+            },
+            ClipShape::Box {
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            } => vec![
+                PathCommand::MoveTo(x_min, y_min),
+                PathCommand::LineTo(x_max, y_min),
+                PathCommand::LineTo(x_max, y_max),
+                PathCommand::LineTo(x_min, y_max),
+                PathCommand::Close,
+            ],
+        }
+    }
+}
+
+impl ColorPainter for RegionPainter<'_> {
+    fn push_transform(&mut self, transform: Transform) {
+        let combined = self.transform().multiply(&transform);
+        self.transforms.push(combined);
+    }
+
+    fn pop_transform(&mut self) {
+        if self.transforms.len() > 1 {
+            self.transforms.pop();
+        }
+    }
+
+    fn push_clip(&mut self, clip: ClipShape) {
+        let transform = self.transform();
+        let path: Vec<PathCommand> = self
+            .clip_path(clip)
+            .into_iter()
+            .map(|command| command.map(|x, y| transform.apply((x, y))))
+            .collect();
+        let mut rasterizer = Rasterizer::new(self.width, self.height);
+        rasterizer.draw_path(&path);
+        let coverage = rasterizer.coverage(FillRule::NonZero);
+        let current = self.clips.last().map(Vec::as_slice).unwrap_or_default();
+        let mask = current
+            .iter()
+            .zip(coverage)
+            .map(|(&m, c)| m * c as f32 / 255.0)
+            .collect();
+        self.clips.push(mask);
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clips.len() > 1 {
+            self.clips.pop();
+        }
+    }
+
+    fn fill(&mut self, brush: Brush) {
+        let Some(inverse) = invert(&self.transform()) else {
+            return;
+        };
+        let (Some(mask), Some((layer, _))) = (self.clips.last(), self.layers.last_mut()) else {
+            return;
+        };
+        for (i, (pixel, &m)) in layer.iter_mut().zip(mask).enumerate() {
+            if m <= 0.0 {
+                continue;
+            }
+            let center = ((i % self.width) as f32 + 0.5, (i / self.width) as f32 + 0.5);
+            let color = brush_color(&brush, inverse.apply(center));
+            let source = color.map(|c| c * m);
+            *pixel = composite(CompositeMode::SrcOver, source, *pixel);
+        }
+    }
+
+    fn push_layer(&mut self, mode: CompositeMode) {
+        self.layers
+            .push((vec![[0.0; 4]; self.width * self.height], mode));
+    }
+
+    fn pop_layer(&mut self) {
+        if self.layers.len() < 2 {
+            return;
+        }
+        let (source, mode) = self.layers.pop().unwrap();
+        let (below, _) = self.layers.last_mut().unwrap();
+        for (dest, source) in below.iter_mut().zip(source) {
+            *dest = composite(mode, source, *dest);
+        }
+    }
+}
+
+/// Returns the inverse of an invertible transform.
+fn invert(t: &Transform) -> Option<Transform> {
+    let det = t.xx * t.yy - t.xy * t.yx;
+    if det.abs() < 1e-12 || !det.is_finite() {
+        return None;
+    }
+    let (xx, yx, xy, yy) = (t.yy / det, -t.yx / det, -t.xy / det, t.xx / det);
+    Some(Transform {
+        xx,
+        yx,
+        xy,
+        yy,
+        dx: -(xx * t.dx + xy * t.dy),
+        dy: -(yx * t.dx + yy * t.dy),
+    })
+}
+
+/// Converts a straight-alpha color to a premultiplied pixel.
+pub(crate) fn premultiply(color: Color) -> Pixel {
+    let alpha = color.alpha as f32 / 255.0;
+    [
+        color.red as f32 / 255.0 * alpha,
+        color.green as f32 / 255.0 * alpha,
+        color.blue as f32 / 255.0 * alpha,
+        alpha,
+    ]
+}
+
+/// Returns the color of a brush at a point in brush coordinates.
+fn brush_color(brush: &Brush, (x, y): (f32, f32)) -> Pixel {
+    match brush {
+        Brush::Solid(color) => premultiply(*color),
+        Brush::LinearGradient {
+            p0,
+            p1,
+            stops,
+            extend,
+        } => {
+            let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+            let length = dx * dx + dy * dy;
+            let t = if length > 0.0 {
+                ((x - p0.0) * dx + (y - p0.1) * dy) / length
+            } else {
+                0.0
+            };
+            stop_color(stops, *extend, t)
+        }
+        Brush::RadialGradient {
+            c0,
+            r0,
+            c1,
+            r1,
+            stops,
+            extend,
+        } => match radial_offset((x, y), *c0, *r0, *c1, *r1) {
+            Some(t) => stop_color(stops, *extend, t),
+            None => [0.0; 4],
+        },
+        Brush::SweepGradient {
+            center,
+            start_angle,
+            end_angle,
+            stops,
+            extend,
+        } => {
+            let angle = (y - center.1)
+                .atan2(x - center.0)
+                .to_degrees()
+                .rem_euclid(360.0);
+            let span = end_angle - start_angle;
+            let t = if span.abs() > f32::EPSILON {
+                (angle - start_angle) / span
+            } else if angle < *start_angle {
+                0.0
+            } else {
+                1.0
+            };
+            stop_color(stops, *extend, t)
+        }
+    }
+}
+
+/// Returns the largest offset `t` whose circle, interpolated between the
+/// two circles, passes through `p` with a non-negative radius.
+fn radial_offset(p: (f32, f32), c0: (f32, f32), r0: f32, c1: (f32, f32), r1: f32) -> Option<f32> {
+    let (cdx, cdy, dr) = (c1.0 - c0.0, c1.1 - c0.1, r1 - r0);
+    let (pdx, pdy) = (p.0 - c0.0, p.1 - c0.1);
+    let a = cdx * cdx + cdy * cdy - dr * dr;
+    let b = pdx * cdx + pdy * cdy + r0 * dr;
+    let c = pdx * pdx + pdy * pdy - r0 * r0;
+    let valid = |t: f32| r0 + t * dr >= 0.0;
+    if a.abs() < 1e-6 {
+        let t = c / (2.0 * b);
+        return (b.abs() > 1e-6 && valid(t)).then_some(t);
+    }
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let (t1, t2) = ((b + root) / a, (b - root) / a);
+    let (high, low) = (t1.max(t2), t1.min(t2));
+    [high, low].into_iter().find(|&t| valid(t))
+}
+
+/// Returns the premultiplied color of a color line at offset `t`.
+fn stop_color(stops: &[GradientStop], extend: Extend, t: f32) -> Pixel {
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return [0.0; 4];
+    };
+    let (start, span) = (first.offset, last.offset - first.offset);
+    let t = if span > 0.0 {
+        match extend {
+            Extend::Pad => t,
+            Extend::Repeat => start + (t - start).rem_euclid(span),
+            Extend::Reflect => {
+                let u = (t - start).rem_euclid(2.0 * span);
+                start + if u > span { 2.0 * span - u } else { u }
+            }
+        }
+    } else {
+        t
+    };
+    if t <= first.offset {
+        return premultiply(first.color);
+    }
+    for pair in stops.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if t <= b.offset {
+            let f = if b.offset > a.offset {
+                (t - a.offset) / (b.offset - a.offset)
+            } else {
+                1.0
+            };
+            let (ca, cb) = (premultiply(a.color), premultiply(b.color));
+            return std::array::from_fn(|i| ca[i] + (cb[i] - ca[i]) * f);
+        }
+    }
+    premultiply(last.color)
+}
+
+/// Composites premultiplied `source` onto `dest`.
+pub(crate) fn composite(mode: CompositeMode, source: Pixel, dest: Pixel) -> Pixel {
+    let (sa, da) = (source[3], dest[3]);
+    let porter_duff = |fs: f32, fd: f32| -> Pixel {
+        std::array::from_fn(|i| (source[i] * fs + dest[i] * fd).min(1.0))
+    };
+    match mode {
+        CompositeMode::Clear => [0.0; 4],
+        CompositeMode::Src => source,
+        CompositeMode::Dest => dest,
+        CompositeMode::SrcOver => porter_duff(1.0, 1.0 - sa),
+        CompositeMode::DestOver => porter_duff(1.0 - da, 1.0),
+        CompositeMode::SrcIn => porter_duff(da, 0.0),
+        CompositeMode::DestIn => porter_duff(0.0, sa),
+        CompositeMode::SrcOut => porter_duff(1.0 - da, 0.0),
+        CompositeMode::DestOut => porter_duff(0.0, 1.0 - sa),
+        CompositeMode::SrcAtop => porter_duff(da, 1.0 - sa),
+        CompositeMode::DestAtop => porter_duff(1.0 - da, sa),
+        CompositeMode::Xor => porter_duff(1.0 - da, 1.0 - sa),
+        CompositeMode::Plus => porter_duff(1.0, 1.0),
+        _ => blend(mode, source, dest),
+    }
+}
+
+/// Applies a separable or non-separable blend mode, as defined by the W3C
+/// Compositing and Blending specification.
+fn blend(mode: CompositeMode, source: Pixel, dest: Pixel) -> Pixel {
+    let (sa, da) = (source[3], dest[3]);
+    let unpremultiply = |p: Pixel| -> [f32; 3] {
+        match p[3] {
+            0.0 => [0.0; 3],
+            a => [p[0] / a, p[1] / a, p[2] / a],
+        }
+    };
+    let (cs, cb) = (unpremultiply(source), unpremultiply(dest));
+    let separable =
+        |f: fn(f32, f32) -> f32| -> [f32; 3] { std::array::from_fn(|i| f(cb[i], cs[i])) };
+    let mixed = match mode {
+        CompositeMode::Multiply => separable(|b, s| b * s),
+        CompositeMode::Screen => separable(screen),
+        CompositeMode::Overlay => separable(|b, s| hard_light(s, b)),
+        CompositeMode::Darken => separable(f32::min),
+        CompositeMode::Lighten => separable(f32::max),
+        CompositeMode::ColorDodge => separable(|b, s| match (b, s) {
+            (0.0, _) => 0.0,
+            (_, 1.0) => 1.0,
+            _ => (b / (1.0 - s)).min(1.0),
+        }),
+        CompositeMode::ColorBurn => separable(|b, s| match (b, s) {
+            (1.0, _) => 1.0,
+            (_, 0.0) => 0.0,
+            _ => 1.0 - ((1.0 - b) / s).min(1.0),
+        }),
+        CompositeMode::HardLight => separable(hard_light),
+        CompositeMode::SoftLight => separable(|b, s| {
+            if s <= 0.5 {
+                b - (1.0 - 2.0 * s) * b * (1.0 - b)
+            } else {
+                let d = if b <= 0.25 {
+                    ((16.0 * b - 12.0) * b + 4.0) * b
+                } else {
+                    b.sqrt()
+                };
+                b + (2.0 * s - 1.0) * (d - b)
+            }
+        }),
+        CompositeMode::Difference => separable(|b, s| (b - s).abs()),
+        CompositeMode::Exclusion => separable(|b, s| b + s - 2.0 * b * s),
+        CompositeMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+        CompositeMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+        CompositeMode::Color => set_lum(cs, lum(cb)),
+        CompositeMode::Luminosity => set_lum(cb, lum(cs)),
+        _ => cs,
+    };
+    let alpha = sa + da * (1.0 - sa);
+    let mut out = [0.0, 0.0, 0.0, alpha];
+    for i in 0..3 {
+        out[i] = source[i] * (1.0 - da) + dest[i] * (1.0 - sa) + sa * da * mixed[i];
+    }
+    out
+}
+
+fn screen(b: f32, s: f32) -> f32 {
+    b + s - b * s
+}
+
+fn hard_light(b: f32, s: f32) -> f32 {
+    if s <= 0.5 {
+        b * 2.0 * s
+    } else {
+        screen(b, 2.0 * s - 1.0)
+    }
+}
+
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    let c = c.map(|v| v + d);
+    let l = lum(c);
+    let (n, x) = (c[0].min(c[1]).min(c[2]), c[0].max(c[1]).max(c[2]));
+    c.map(|v| {
+        let v = if n < 0.0 {
+            l + (v - l) * l / (l - n)
+        } else {
+            v
+        };
+        if x > 1.0 {
+            l + (v - l) * (1.0 - l) / (x - l)
+        } else {
+            v
+        }
+    })
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let (min, max) = (c[0].min(c[1]).min(c[2]), c[0].max(c[1]).max(c[2]));
+    if max > min {
+        c.map(|v| (v - min) * s / (max - min))
+    } else {
+        [0.0; 3]
+    }
+}
@@ -0,0 +1,324 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Minimal PNG codec for embedded bitmap glyphs and rendered previews.
+
+use crate::compress::zlib_compress;
+use crate::decompress::{crc32, zlib_decompress};
+use crate::error::{Error, IoError};
+
+/// PNG file signature.
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Largest image decoded, in pixels.
+const MAX_PIXELS: usize = 1 << 24;
+
+/// Column start, row start, column step and row step of the Adam7 passes.
+const ADAM7: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+/// A decoded image of straight-alpha RGBA pixels, rows from the top.
+pub(crate) struct PngImage {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+/// Image header fields.
+struct Header {
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    color_type: u8,
+    interlaced: bool,
+}
+
+impl Header {
+    fn channels(&self) -> usize {
+        match self.color_type {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        }
+    }
+
+    fn bits_per_pixel(&self) -> usize {
+        self.channels() * self.bit_depth as usize
+    }
+
+    /// Bytes of one filtered row of `width` pixels, filter byte excluded.
+    fn row_bytes(&self, width: usize) -> usize {
+        (width * self.bits_per_pixel()).div_ceil(8)
+    }
+}
+
+/// Decodes a PNG file. Every standard color type and bit depth is supported,
+/// interlaced images included; 16-bit samples are reduced to 8 bits.
+pub(crate) fn decode_png(data: &[u8]) -> Result<PngImage, Error> {
+    let invalid = || Error::Io(IoError::InvalidData);
+    let mut rest = data.strip_prefix(&SIGNATURE).ok_or(Error::InvalidFormat)?;
+    let mut header: Option<Header> = None;
+    let mut palette: Vec<[u8; 4]> = Vec::new();
+    let mut transparent: Option<[u16; 3]> = None;
+    let mut compressed = Vec::new();
+    loop {
+        let (length, after) = rest.split_first_chunk::<4>().ok_or_else(invalid)?;
+        let length = u32::from_be_bytes(*length) as usize;
+        let (kind, after) = after.split_first_chunk::<4>().ok_or_else(invalid)?;
+        let body = after.get(..length).ok_or_else(invalid)?;
+        rest = after.get(length + 4..).ok_or_else(invalid)?;
+        match kind {
+            b"IHDR" => {
+                let field = |i: usize| -> Result<usize, Error> {
+                    let bytes = body.get(i..i + 4).ok_or_else(invalid)?;
+                    Ok(u32::from_be_bytes(bytes.try_into().unwrap()) as usize)
+                };
+                let parsed = Header {
+                    width: field(0)?,
+                    height: field(4)?,
+                    bit_depth: *body.get(8).ok_or_else(invalid)?,
+                    color_type: *body.get(9).ok_or_else(invalid)?,
+                    interlaced: body.get(12) == Some(&1),
+                };
+                let depth_valid = match parsed.color_type {
+                    0 => matches!(parsed.bit_depth, 1 | 2 | 4 | 8 | 16),
+                    3 => matches!(parsed.bit_depth, 1 | 2 | 4 | 8),
+                    2 | 4 | 6 => matches!(parsed.bit_depth, 8 | 16),
+                    _ => false,
+                };
+                if !depth_valid
+                    || parsed.width == 0
+                    || parsed.height == 0
+                    || parsed.width.saturating_mul(parsed.height) > MAX_PIXELS
+                {
+                    return Err(Error::InvalidFormat);
+                }
+                header = Some(parsed);
+            }
+            b"PLTE" => {
+                palette = body
+                    .chunks_exact(3)
+                    .map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                    .collect();
+            }
+            b"tRNS" => {
+                let sample = |i: usize| {
+                    body.get(i..i + 2)
+                        .map_or(0, |b| u16::from_be_bytes([b[0], b[1]]))
+                };
+                match header.as_ref().map(|h| h.color_type) {
+                    Some(3) => {
+                        for (entry, &alpha) in palette.iter_mut().zip(body) {
+                            entry[3] = alpha;
+                        }
+                    }
+                    Some(0) => transparent = Some([sample(0); 3]),
+                    Some(2) => transparent = Some([sample(0), sample(2), sample(4)]),
+                    _ => {}
+                }
+            }
+            b"IDAT" => compressed.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+    }
+    let header = header.ok_or_else(invalid)?;
+    if header.color_type == 3 && palette.is_empty() {
+        return Err(invalid());
+    }
+    let passes: &[(usize, usize, usize, usize)] = if header.interlaced {
+        &ADAM7
+    } else {
+        &[(0, 0, 1, 1)]
+    };
+    // Columns and filtered size of each pass.
+    let sizes: Vec<(usize, usize)> = passes
+        .iter()
+        .map(|&(x0, y0, dx, dy)| {
+            let columns = header.width.saturating_sub(x0).div_ceil(dx);
+            let rows = header.height.saturating_sub(y0).div_ceil(dy);
+            let size = if columns == 0 || rows == 0 {
+                0
+            } else {
+                (header.row_bytes(columns) + 1) * rows
+            };
+            (columns, size)
+        })
+        .collect();
+    let filtered = zlib_decompress(&compressed, sizes.iter().map(|size| size.1).sum())?;
+
+    let mut image = PngImage {
+        width: header.width,
+        height: header.height,
+        data: vec![0; header.width * header.height * 4],
+    };
+    let mut position = 0;
+    for (&(x0, y0, dx, dy), &(columns, size)) in passes.iter().zip(&sizes) {
+        if size == 0 {
+            continue;
+        }
+        let stride = header.row_bytes(columns);
+        let pass = filtered
+            .get(position..position + size)
+            .ok_or_else(invalid)?;
+        position += size;
+        let raw = unfilter(pass, stride, header.bits_per_pixel().div_ceil(8))?;
+        for (row, line) in raw.chunks_exact(stride).enumerate() {
+            for column in 0..columns {
+                let pixel = read_pixel(&header, line, column, &palette, transparent);
+                let (x, y) = (x0 + column * dx, y0 + row * dy);
+                let offset = (y * header.width + x) * 4;
+                image.data[offset..offset + 4].copy_from_slice(&pixel);
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// Reverses the per-row filters of a pass.
+fn unfilter(data: &[u8], stride: usize, pixel_bytes: usize) -> Result<Vec<u8>, Error> {
+    let mut out = vec![0u8; data.len() / (stride + 1) * stride];
+    for (row, line) in data.chunks_exact(stride + 1).enumerate() {
+        let (filter, line) = (line[0], &line[1..]);
+        let (previous, current) = out.split_at_mut(row * stride);
+        let up = previous.get(previous.len().saturating_sub(stride)..);
+        let current = &mut current[..stride];
+        for i in 0..stride {
+            let a = if i >= pixel_bytes {
+                current[i - pixel_bytes]
+            } else {
+                0
+            };
+            let b = up.filter(|_| row > 0).map_or(0, |up| up[i]);
+            let c = if i >= pixel_bytes {
+                up.filter(|_| row > 0).map_or(0, |up| up[i - pixel_bytes])
+            } else {
+                0
+            };
+            let predictor = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(Error::Io(IoError::InvalidData)),
+            };
+            current[i] = line[i].wrapping_add(predictor);
+        }
+    }
+    Ok(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// Converts the pixel at `column` of an unfiltered row to RGBA.
+fn read_pixel(
+    header: &Header,
+    line: &[u8],
+    column: usize,
+    palette: &[[u8; 4]],
+    transparent: Option<[u16; 3]>,
+) -> [u8; 4] {
+    let depth = header.bit_depth as usize;
+    // Samples as stored, and scaled to 8 bits.
+    let sample = |index: usize| -> (u16, u8) {
+        match depth {
+            16 => {
+                let value = u16::from_be_bytes([line[index * 2], line[index * 2 + 1]]);
+                (value, (value >> 8) as u8)
+            }
+            8 => (line[index] as u16, line[index]),
+            _ => {
+                let bit = index * depth;
+                let value = (line[bit / 8] >> (8 - depth - bit % 8)) & ((1 << depth) - 1);
+                (
+                    value as u16,
+                    (value as u32 * 255 / ((1 << depth) - 1)) as u8,
+                )
+            }
+        }
+    };
+    let channels = header.channels();
+    match header.color_type {
+        3 => {
+            let (index, _) = sample(column);
+            palette.get(index as usize).copied().unwrap_or([0; 4])
+        }
+        0 | 4 => {
+            let (raw, gray) = sample(column * channels);
+            let alpha = match header.color_type {
+                4 => sample(column * channels + 1).1,
+                _ if transparent.is_some_and(|t| t[0] == raw) => 0,
+                _ => 255,
+            };
+            [gray, gray, gray, alpha]
+        }
+        _ => {
+            let (r, g, b) = (
+                sample(column * channels),
+                sample(column * channels + 1),
+                sample(column * channels + 2),
+            );
+            let alpha = match header.color_type {
+                6 => sample(column * channels + 3).1,
+                _ if transparent == Some([r.0, g.0, b.0]) => 0,
+                _ => 255,
+            };
+            [r.1, g.1, b.1, alpha]
+        }
+    }
+}
+
+/// Encodes straight-alpha RGBA pixels as an 8-bit RGBA PNG file.
+pub(crate) fn encode_png(width: usize, height: usize, data: &[u8]) -> Vec<u8> {
+    let mut filtered = Vec::with_capacity((width * 4 + 1) * height);
+    for row in data.chunks_exact(width * 4).take(height) {
+        filtered.push(0);
+        filtered.extend_from_slice(row);
+    }
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits, RGBA, deflate, adaptive filtering, no interlace.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut out = SIGNATURE.to_vec();
+    for (kind, body) in [
+        (b"IHDR", header),
+        (b"IDAT", zlib_compress(&filtered)),
+        (b"IEND", Vec::new()),
+    ] {
+        out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        let start = out.len();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(&body);
+        let crc = crc32(&out[start..]);
+        out.extend_from_slice(&crc.to_be_bytes());
+    }
+    out
+}
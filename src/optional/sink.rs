@@ -41,8 +41,12 @@ pub trait FontSink {
     fn consume_snft(&mut self, snft: crate::common::snft::SnftTable) -> Result<(), Self::Err>;
 
     /// Consume SVG font data. The slice is only valid for the duration of the
-    /// call; implementors should copy it if they need to retain it.
-    //fn consume_svg(&mut self, svg_data: &[u8]) -> Result<(), Self::Err>;
+    /// call; implementors should copy it if they need to retain it. The
+    /// default implementation ignores the data, so sinks that only handle
+    /// SNFT fonts need not implement it.
+    fn consume_svg(&mut self, _svg_data: &[u8]) -> Result<(), Self::Err> {
+        Ok(())
+    }
 
     /// Finalize the sink and produce its output value.
     fn finish(self) -> Result<Self::Output, Self::Err>;
@@ -0,0 +1,308 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Type 2 charstring outlines for `CFF ` and `CFF2`.
+
+use crate::components::cff1::{
+    CS_ENDCHAR, CffTable, CharStringToken, flatten_char_string, standard_encoding_sid,
+};
+use crate::components::cff2::{self, Cff2Table, rewrite_char_string_blends};
+use crate::error::{Error, IoError};
+
+use super::PathCommand;
+
+/// Builds path commands from charstring operators.
+#[derive(Default)]
+struct PathBuilder {
+    commands: Vec<PathCommand>,
+    current: (f64, f64),
+    open: bool,
+    /// Whether the optional advance width operand was consumed.
+    width_parsed: bool,
+    /// `adx`, `ady`, base and accent codes of a `seac`-style `endchar`.
+    seac: Option<(f64, f64, u8, u8)>,
+}
+
+impl PathBuilder {
+    fn move_to(&mut self, dx: f64, dy: f64) {
+        if self.open {
+            self.commands.push(PathCommand::Close);
+        }
+        self.current = (self.current.0 + dx, self.current.1 + dy);
+        let (x, y) = self.current;
+        self.commands.push(PathCommand::MoveTo(x as f32, y as f32));
+        self.open = true;
+    }
+
+    /// Starts a contour at the current point if drawing without `moveto`.
+    fn ensure_open(&mut self) {
+        if !self.open {
+            self.move_to(0.0, 0.0);
+        }
+    }
+
+    fn line(&mut self, dx: f64, dy: f64) {
+        self.ensure_open();
+        self.current = (self.current.0 + dx, self.current.1 + dy);
+        let (x, y) = self.current;
+        self.commands.push(PathCommand::LineTo(x as f32, y as f32));
+    }
+
+    fn curve(&mut self, d: [f64; 6]) {
+        self.ensure_open();
+        let p0 = self.current;
+        let p1 = (p0.0 + d[0], p0.1 + d[1]);
+        let p2 = (p1.0 + d[2], p1.1 + d[3]);
+        let p3 = (p2.0 + d[4], p2.1 + d[5]);
+        self.commands.push(PathCommand::CurveTo(
+            p1.0 as f32,
+            p1.1 as f32,
+            p2.0 as f32,
+            p2.1 as f32,
+            p3.0 as f32,
+            p3.1 as f32,
+        ));
+        self.current = p3;
+    }
+
+    /// Returns the operands of the first stack-clearing operator without the
+    /// advance width that may precede them.
+    fn strip_width<'a>(&mut self, operator: u16, stack: &'a [f64]) -> &'a [f64] {
+        if self.width_parsed {
+            return stack;
+        }
+        let has_width = match operator {
+            // Stems and masks take pairs.
+            1 | 3 | 18 | 19 | 20 | 23 => stack.len() % 2 == 1,
+            21 => stack.len() > 2,
+            4 | 22 => stack.len() > 1,
+            CS_ENDCHAR => stack.len() == 1 || stack.len() == 5,
+            _ => return stack,
+        };
+        self.width_parsed = true;
+        if has_width { &stack[1..] } else { stack }
+    }
+
+    /// Runs a path or hint operator on its operands.
+    fn operator(&mut self, operator: u16, stack: &[f64]) {
+        let s = self.strip_width(operator, stack);
+        match operator {
+            // rmoveto, hmoveto, vmoveto.
+            21 if s.len() >= 2 => self.move_to(s[s.len() - 2], s[s.len() - 1]),
+            22 if !s.is_empty() => self.move_to(s[s.len() - 1], 0.0),
+            4 if !s.is_empty() => self.move_to(0.0, s[s.len() - 1]),
+            // rlineto.
+            5 => {
+                for pair in s.chunks_exact(2) {
+                    self.line(pair[0], pair[1]);
+                }
+            }
+            // hlineto, vlineto: alternating directions.
+            6 | 7 => {
+                for (i, &d) in s.iter().enumerate() {
+                    let horizontal = (i % 2 == 0) == (operator == 6);
+                    let (dx, dy) = if horizontal { (d, 0.0) } else { (0.0, d) };
+                    self.line(dx, dy);
+                }
+            }
+            // rrcurveto.
+            8 => {
+                for c in s.chunks_exact(6) {
+                    self.curve([c[0], c[1], c[2], c[3], c[4], c[5]]);
+                }
+            }
+            // rcurveline.
+            24 if s.len() >= 8 => {
+                let curves = (s.len() - 2) / 6;
+                for c in s[..curves * 6].chunks_exact(6) {
+                    self.curve([c[0], c[1], c[2], c[3], c[4], c[5]]);
+                }
+                self.line(s[s.len() - 2], s[s.len() - 1]);
+            }
+            // rlinecurve.
+            25 if s.len() >= 8 => {
+                let lines = s.len() - 6;
+                for pair in s[..lines].chunks_exact(2) {
+                    self.line(pair[0], pair[1]);
+                }
+                let c = &s[lines..];
+                self.curve([c[0], c[1], c[2], c[3], c[4], c[5]]);
+            }
+            // vvcurveto, hhcurveto.
+            26 | 27 => {
+                let (first, rest) = if s.len() % 4 == 1 {
+                    (s[0], &s[1..])
+                } else {
+                    (0.0, s)
+                };
+                for (i, c) in rest.chunks_exact(4).enumerate() {
+                    let extra = if i == 0 { first } else { 0.0 };
+                    let d = match operator {
+                        26 => [extra, c[0], c[1], c[2], 0.0, c[3]],
+                        _ => [c[0], extra, c[1], c[2], c[3], 0.0],
+                    };
+                    self.curve(d);
+                }
+            }
+            // vhcurveto, hvcurveto.
+            30 | 31 => {
+                let count = s.len() / 4;
+                let last = if s.len() % 4 == 1 {
+                    s[s.len() - 1]
+                } else {
+                    0.0
+                };
+                for (i, c) in s.chunks_exact(4).enumerate() {
+                    let horizontal = (i % 2 == 0) == (operator == 31);
+                    let extra = if i + 1 == count { last } else { 0.0 };
+                    let d = match horizontal {
+                        true => [c[0], 0.0, c[1], c[2], extra, c[3]],
+                        false => [0.0, c[0], c[1], c[2], c[3], extra],
+                    };
+                    self.curve(d);
+                }
+            }
+            // hflex.
+            0x0C22 if s.len() >= 7 => {
+                let y = self.current.1;
+                self.curve([s[0], 0.0, s[1], s[2], s[3], 0.0]);
+                let back = y - self.current.1;
+                self.curve([s[4], 0.0, s[5], back, s[6], 0.0]);
+            }
+            // flex.
+            0x0C23 if s.len() >= 12 => {
+                self.curve([s[0], s[1], s[2], s[3], s[4], s[5]]);
+                self.curve([s[6], s[7], s[8], s[9], s[10], s[11]]);
+            }
+            // hflex1.
+            0x0C24 if s.len() >= 9 => {
+                let y = self.current.1;
+                self.curve([s[0], s[1], s[2], s[3], s[4], 0.0]);
+                let back = y - self.current.1 - s[7];
+                self.curve([s[5], 0.0, s[6], s[7], s[8], back]);
+            }
+            // flex1.
+            0x0C25 if s.len() >= 11 => {
+                let start = self.current;
+                let dx: f64 = s[0] + s[2] + s[4] + s[6] + s[8];
+                let dy: f64 = s[1] + s[3] + s[5] + s[7] + s[9];
+                self.curve([s[0], s[1], s[2], s[3], s[4], s[5]]);
+                let (last_x, last_y) = if dx.abs() > dy.abs() {
+                    (s[10], start.1 - self.current.1 - s[7] - s[9])
+                } else {
+                    (start.0 - self.current.0 - s[6] - s[8], s[10])
+                };
+                self.curve([s[6], s[7], s[8], s[9], last_x, last_y]);
+            }
+            CS_ENDCHAR if s.len() >= 4 => {
+                let n = s.len();
+                self.seac = Some((s[n - 4], s[n - 3], s[n - 2] as u8, s[n - 1] as u8));
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Vec<PathCommand> {
+        if self.open {
+            self.commands.push(PathCommand::Close);
+        }
+        self.commands
+    }
+}
+
+/// Interprets flattened, blend-free charstring tokens.
+fn interpret(tokens: &[CharStringToken]) -> PathBuilder {
+    let mut builder = PathBuilder::default();
+    let mut stack: Vec<f64> = Vec::new();
+    for token in tokens {
+        match token {
+            CharStringToken::Number(value) => stack.push(*value),
+            CharStringToken::Operator(operator) | CharStringToken::Mask(operator, _) => {
+                builder.operator(*operator, &stack);
+                stack.clear();
+                if *operator == CS_ENDCHAR {
+                    break;
+                }
+            }
+        }
+    }
+    builder
+}
+
+/// Returns the outline drawn by flattened charstring tokens without
+/// subroutine calls or blends.
+pub fn char_string_path(tokens: &[CharStringToken]) -> Vec<PathCommand> {
+    interpret(tokens).finish()
+}
+
+/// Returns the outline of a `CFF ` glyph. Accented glyphs built with the
+/// `seac` form of `endchar` include their base and accent.
+pub fn cff_path(cff: &CffTable, glyph_id: u16) -> Result<Vec<PathCommand>, Error> {
+    let builder = interpret(&flatten_char_string(cff, glyph_id)?);
+    let seac = builder.seac;
+    let mut commands = builder.finish();
+    if let Some((adx, ady, base, accent)) = seac {
+        let component = |code: u8| {
+            cff.glyph_for_charset_id(standard_encoding_sid(code))
+                .filter(|_| !cff.is_cid())
+                .ok_or(Error::Io(IoError::InvalidData))
+                .and_then(|glyph| flatten_char_string(cff, glyph))
+                .map(|tokens| char_string_path(&tokens))
+        };
+        commands.extend(component(base)?);
+        let (dx, dy) = (adx as f32, ady as f32);
+        commands.extend(
+            component(accent)?
+                .into_iter()
+                .map(|command| command.map(|x, y| (x + dx, y + dy))),
+        );
+    }
+    Ok(commands)
+}
+
+/// Returns the outline of a `CFF2` glyph at normalized variation coordinates
+/// `coords`; an empty slice selects the default instance.
+pub fn cff2_path(
+    cff2: &Cff2Table,
+    glyph_id: u16,
+    coords: &[f32],
+) -> Result<Vec<PathCommand>, Error> {
+    let tokens = cff2::flatten_char_string(cff2, glyph_id)?;
+    let scalars = cff2
+        .variation_store
+        .as_ref()
+        .map(|store| store.region_scalars(coords))
+        .unwrap_or_default();
+    let tokens = rewrite_char_string_blends(
+        cff2,
+        &tokens,
+        cff2.default_vsindex(glyph_id),
+        false,
+        |blend| {
+            let regions = cff2
+                .variation_store
+                .as_ref()
+                .and_then(|store| store.data.get(blend.vsindex as usize))
+                .map_or(&[][..], |data| data.region_indexes.as_slice());
+            blend
+                .defaults
+                .iter()
+                .zip(blend.deltas.iter().chain(std::iter::repeat(&&[][..])))
+                .map(|(&default, deltas)| {
+                    let delta: f64 = deltas
+                        .iter()
+                        .zip(regions)
+                        .map(|(&delta, &region)| {
+                            delta * scalars.get(region as usize).copied().unwrap_or(0.0) as f64
+                        })
+                        .sum();
+                    CharStringToken::Number(default + delta)
+                })
+                .collect()
+        },
+    );
+    Ok(char_string_path(&tokens))
+}
@@ -0,0 +1,188 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Glyph outlines as path commands. TrueType contours from `glyf` become
+//! lines and quadratic curves, and Type 2 charstrings from `CFF ` and `CFF2`
+//! become lines and cubic curves. Coordinates are in font units with y up.
+
+mod cff;
+
+pub use cff::{cff_path, cff2_path, char_string_path};
+
+use crate::components::glyf::{GlyfTable, Glyph, SCALED_COMPONENT_OFFSET};
+use crate::error::{Error, IoError};
+
+/// Maximum component nesting depth followed when resolving composites.
+const MAX_COMPONENT_DEPTH: usize = 16;
+
+/// A contour point with its on-curve flag.
+type ContourPoint = (f32, f32, bool);
+
+/// A drawing command of a glyph outline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    /// Starts a contour at a point.
+    MoveTo(f32, f32),
+    /// Draws a line to a point.
+    LineTo(f32, f32),
+    /// Draws a quadratic curve through a control point to a point.
+    QuadTo(f32, f32, f32, f32),
+    /// Draws a cubic curve through two control points to a point.
+    CurveTo(f32, f32, f32, f32, f32, f32),
+    /// Closes the contour with a line back to its start.
+    Close,
+}
+
+impl PathCommand {
+    /// Returns the command with every point mapped by `f`.
+    pub fn map(self, mut f: impl FnMut(f32, f32) -> (f32, f32)) -> PathCommand {
+        match self {
+            PathCommand::MoveTo(x, y) => {
+                let (x, y) = f(x, y);
+                PathCommand::MoveTo(x, y)
+            }
+            PathCommand::LineTo(x, y) => {
+                let (x, y) = f(x, y);
+                PathCommand::LineTo(x, y)
+            }
+            PathCommand::QuadTo(x1, y1, x, y) => {
+                let ((x1, y1), (x, y)) = (f(x1, y1), f(x, y));
+                PathCommand::QuadTo(x1, y1, x, y)
+            }
+            PathCommand::CurveTo(x1, y1, x2, y2, x, y) => {
+                let ((x1, y1), (x2, y2), (x, y)) = (f(x1, y1), f(x2, y2), f(x, y));
+                PathCommand::CurveTo(x1, y1, x2, y2, x, y)
+            }
+            PathCommand::Close => PathCommand::Close,
+        }
+    }
+}
+
+/// Converts TrueType contours to path commands. Consecutive off-curve points
+/// imply an on-curve point halfway between them.
+pub fn contour_path(points: &[(f32, f32, bool)], end_points: &[usize]) -> Vec<PathCommand> {
+    let mut commands = Vec::new();
+    let mut start = 0;
+    for &end in end_points {
+        if end < start || end >= points.len() {
+            break;
+        }
+        let contour = &points[start..=end];
+        start = end + 1;
+        let midpoint = |a: ContourPoint, b: ContourPoint| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        // Start at the first on-curve point, or between the last and first
+        // points when the contour has none.
+        let (first, rest): ((f32, f32), Vec<ContourPoint>) = match contour.iter().position(|p| p.2)
+        {
+            Some(i) => (
+                (contour[i].0, contour[i].1),
+                contour[i + 1..]
+                    .iter()
+                    .chain(&contour[..i])
+                    .copied()
+                    .collect(),
+            ),
+            None => (
+                midpoint(contour[contour.len() - 1], contour[0]),
+                contour.to_vec(),
+            ),
+        };
+        commands.push(PathCommand::MoveTo(first.0, first.1));
+        let mut control: Option<ContourPoint> = None;
+        for point in rest {
+            match (point.2, control) {
+                (true, Some(c)) => commands.push(PathCommand::QuadTo(c.0, c.1, point.0, point.1)),
+                (true, None) => commands.push(PathCommand::LineTo(point.0, point.1)),
+                (false, Some(c)) => {
+                    let (x, y) = midpoint(c, point);
+                    commands.push(PathCommand::QuadTo(c.0, c.1, x, y));
+                }
+                (false, None) => {}
+            }
+            control = (!point.2).then_some(point);
+        }
+        if let Some(c) = control {
+            commands.push(PathCommand::QuadTo(c.0, c.1, first.0, first.1));
+        }
+        commands.push(PathCommand::Close);
+    }
+    commands
+}
+
+/// Returns the outline of a `glyf` glyph with its components resolved.
+pub fn glyf_path(glyf: &GlyfTable, glyph_id: u16) -> Result<Vec<PathCommand>, Error> {
+    let mut points = Vec::new();
+    let mut end_points = Vec::new();
+    resolve_glyph(glyf, glyph_id, 0, &mut points, &mut end_points)?;
+    Ok(contour_path(&points, &end_points))
+}
+
+/// Appends the points and contour ends of a glyph, resolving components.
+fn resolve_glyph(
+    glyf: &GlyfTable,
+    glyph_id: u16,
+    depth: usize,
+    points: &mut Vec<(f32, f32, bool)>,
+    end_points: &mut Vec<usize>,
+) -> Result<(), Error> {
+    let glyph = glyf
+        .glyph(glyph_id)
+        .ok_or(Error::Io(IoError::InvalidGlyphIndex {
+            index: glyph_id as u32,
+        }))?;
+    match glyph {
+        Glyph::Empty => {}
+        Glyph::Simple(simple) => {
+            let base = points.len();
+            points.extend(
+                simple
+                    .points
+                    .iter()
+                    .map(|p| (p.x as f32, p.y as f32, p.on_curve)),
+            );
+            end_points.extend(simple.end_points.iter().map(|&end| base + end as usize));
+        }
+        Glyph::Composite(composite) => {
+            if depth >= MAX_COMPONENT_DEPTH {
+                return Err(Error::Io(IoError::InvalidData));
+            }
+            for component in &composite.components {
+                let (mut child, mut child_ends) = (Vec::new(), Vec::new());
+                resolve_glyph(
+                    glyf,
+                    component.glyph_index,
+                    depth + 1,
+                    &mut child,
+                    &mut child_ends,
+                )?;
+                let [xx, yx, xy, yy] = component.transform.matrix();
+                let transform = |x: f32, y: f32| (xx * x + xy * y, yx * x + yy * y);
+                for point in &mut child {
+                    (point.0, point.1) = transform(point.0, point.1);
+                }
+                let (dx, dy) = if component.args_are_xy_values() {
+                    let (x, y) = (component.arg1 as f32, component.arg2 as f32);
+                    if component.flags & SCALED_COMPONENT_OFFSET != 0 {
+                        transform(x, y)
+                    } else {
+                        (x, y)
+                    }
+                } else {
+                    // Point matching: the child point lands on the parent point.
+                    let parent = points.get(component.arg1 as usize);
+                    let anchor = child.get(component.arg2 as usize);
+                    let (parent, anchor) =
+                        parent.zip(anchor).ok_or(Error::Io(IoError::InvalidData))?;
+                    (parent.0 - anchor.0, parent.1 - anchor.1)
+                };
+                let base = points.len();
+                points.extend(child.into_iter().map(|(x, y, on)| (x + dx, y + dy, on)));
+                end_points.extend(child_ends.into_iter().map(|end| base + end));
+            }
+        }
+    }
+    Ok(())
+}
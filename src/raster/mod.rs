@@ -0,0 +1,404 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Anti-aliased scanline rasterizer (opt-in). Outlines are flattened to
+//! lines whose exact signed area is accumulated per pixel, then each row is
+//! integrated into 8-bit coverage under the non-zero or even-odd fill rule.
+//! The LCD mode renders at three times the horizontal resolution and
+//! applies a five-tap filter to reduce color fringes.
+
+use crate::error::{Error, IoError};
+use crate::outline::PathCommand;
+
+/// Maximum distance in pixels between a curve and its flattened lines.
+const TOLERANCE: f32 = 0.1;
+/// Maximum number of lines a curve is flattened into.
+const MAX_CURVE_SEGMENTS: usize = 128;
+/// Largest bitmap `render` allocates, in samples.
+const MAX_SAMPLES: usize = 1 << 26;
+/// LCD filter weights over five subpixels, summing to 256.
+const LCD_FILTER: [u32; 5] = [8, 77, 86, 77, 8];
+
+/// Rule deciding which areas enclosed by contours are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Areas with a non-zero winding number, as TrueType and CFF outlines expect.
+    #[default]
+    NonZero,
+    /// Areas enclosed an odd number of times.
+    EvenOdd,
+}
+
+/// Pixel layout of a rendered bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// One coverage byte per pixel.
+    #[default]
+    Gray,
+    /// Three coverage bytes per pixel, for horizontal RGB subpixels.
+    Lcd,
+}
+
+/// Options of `render`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterOptions {
+    /// Pixels per outline unit, e.g. `ppem / units_per_em`.
+    pub scale: f32,
+    /// Offset in pixels added after scaling; fractional offsets position
+    /// glyphs between pixels.
+    pub offset: (f32, f32),
+    /// Fill rule.
+    pub fill_rule: FillRule,
+    /// Pixel layout.
+    pub mode: RenderMode,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        RasterOptions {
+            scale: 1.0,
+            offset: (0.0, 0.0),
+            fill_rule: FillRule::NonZero,
+            mode: RenderMode::Gray,
+        }
+    }
+}
+
+/// A rendered coverage bitmap.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bitmap {
+    /// Width in pixels.
+    pub width: usize,
+    /// Height in pixels.
+    pub height: usize,
+    /// Position of the left column relative to the origin, in pixels.
+    pub left: i32,
+    /// Position of the top row above the baseline, in pixels.
+    pub top: i32,
+    /// Pixel layout of `data`.
+    pub mode: RenderMode,
+    /// Rows of coverage from top to bottom; 255 is fully covered.
+    pub data: Vec<u8>,
+}
+
+impl Bitmap {
+    /// Returns the number of bytes per row.
+    pub fn stride(&self) -> usize {
+        match self.mode {
+            RenderMode::Gray => self.width,
+            RenderMode::Lcd => self.width * 3,
+        }
+    }
+}
+
+/// Accumulates the area covered by lines on a pixel grid with y down.
+#[derive(Debug, Clone)]
+pub struct Rasterizer {
+    width: usize,
+    height: usize,
+    /// Signed area deltas; each row has two extra cells for lines on the
+    /// right edge.
+    cells: Vec<f32>,
+    start: (f32, f32),
+    current: (f32, f32),
+}
+
+impl Rasterizer {
+    /// Creates a rasterizer for a `width` by `height` pixel grid.
+    pub fn new(width: usize, height: usize) -> Rasterizer {
+        Rasterizer {
+            width,
+            height,
+            cells: vec![0.0; (width + 2) * height],
+            start: (0.0, 0.0),
+            current: (0.0, 0.0),
+        }
+    }
+
+    /// Returns the width of the grid in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Removes everything drawn so far.
+    pub fn clear(&mut self) {
+        self.cells.fill(0.0);
+    }
+
+    /// Starts a contour, closing the previous one.
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.start = (x, y);
+        self.current = (x, y);
+    }
+
+    /// Draws a line from the current point.
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        self.line(self.current, (x, y));
+        self.current = (x, y);
+    }
+
+    /// Draws a quadratic curve from the current point.
+    pub fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let p0 = self.current;
+        let deviation = length(p0.0 - 2.0 * x1 + x, p0.1 - 2.0 * y1 + y);
+        let segments = segment_count(deviation / 8.0);
+        for i in 1..=segments {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            let point = (
+                u * u * p0.0 + 2.0 * u * t * x1 + t * t * x,
+                u * u * p0.1 + 2.0 * u * t * y1 + t * t * y,
+            );
+            self.line_to(point.0, point.1);
+        }
+    }
+
+    /// Draws a cubic curve from the current point.
+    pub fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p0 = self.current;
+        let deviation = length(p0.0 - 2.0 * x1 + x2, p0.1 - 2.0 * y1 + y2)
+            .max(length(x1 - 2.0 * x2 + x, y1 - 2.0 * y2 + y));
+        let segments = segment_count(deviation * 0.75);
+        for i in 1..=segments {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            let point = (
+                a * p0.0 + b * x1 + c * x2 + d * x,
+                a * p0.1 + b * y1 + c * y2 + d * y,
+            );
+            self.line_to(point.0, point.1);
+        }
+    }
+
+    /// Closes the current contour with a line back to its start.
+    pub fn close(&mut self) {
+        if self.current != self.start {
+            self.line(self.current, self.start);
+        }
+        self.current = self.start;
+    }
+
+    /// Draws path commands whose coordinates are already in grid pixels.
+    pub fn draw_path(&mut self, commands: &[PathCommand]) {
+        for command in commands {
+            match *command {
+                PathCommand::MoveTo(x, y) => self.move_to(x, y),
+                PathCommand::LineTo(x, y) => self.line_to(x, y),
+                PathCommand::QuadTo(x1, y1, x, y) => self.quad_to(x1, y1, x, y),
+                PathCommand::CurveTo(x1, y1, x2, y2, x, y) => self.curve_to(x1, y1, x2, y2, x, y),
+                PathCommand::Close => self.close(),
+            }
+        }
+        self.close();
+    }
+
+    /// Draws a line, splitting it where it leaves the grid horizontally.
+    /// Parts left of the grid still cover every pixel to their right, so
+    /// they are moved onto the left edge; parts right of it onto the right.
+    fn line(&mut self, p0: (f32, f32), p1: (f32, f32)) {
+        let width = self.width as f32;
+        let mut cuts = [0.0; 4];
+        let mut count = 1;
+        for edge in [0.0, width] {
+            let t = (edge - p0.0) / (p1.0 - p0.0);
+            if t > 0.0 && t < 1.0 {
+                cuts[count] = t;
+                count += 1;
+            }
+        }
+        cuts[count] = 1.0;
+        cuts[..=count].sort_by(f32::total_cmp);
+        let at = |t: f32| {
+            (
+                (p0.0 + (p1.0 - p0.0) * t).clamp(0.0, width),
+                p0.1 + (p1.1 - p0.1) * t,
+            )
+        };
+        for pair in cuts[..=count].windows(2) {
+            self.accumulate(at(pair[0]), at(pair[1]));
+        }
+    }
+
+    /// Adds the signed area of a line within `[0, width]` to the cells.
+    fn accumulate(&mut self, p0: (f32, f32), p1: (f32, f32)) {
+        if p0.1 == p1.1 {
+            return;
+        }
+        let (direction, p0, p1) = if p0.1 < p1.1 {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+        let (width, stride) = (self.width as f32, self.width + 2);
+        let dxdy = (p1.0 - p0.0) / (p1.1 - p0.1);
+        let first_row = p0.1.max(0.0) as usize;
+        let last_row = (p1.1.ceil().max(0.0) as usize).min(self.height);
+        for row in first_row..last_row {
+            let top = (row as f32).max(p0.1);
+            let dy = ((row + 1) as f32).min(p1.1) - top;
+            // Clamped against rounding drift past the edges.
+            let x = (p0.0 + (top - p0.1) * dxdy).clamp(0.0, width);
+            let x_next = (x + dxdy * dy).clamp(0.0, width);
+            let d = dy * direction;
+            let line = row * stride;
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x0i = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1i = x1_ceil as usize;
+            let cells = &mut self.cells[line..line + stride];
+            if x1i <= x0i + 1 {
+                // Within one pixel: split by the mean position in it.
+                let fraction = 0.5 * (x + x_next) - x0_floor;
+                cells[x0i] += d - d * fraction;
+                cells[x0i + 1] += d * fraction;
+            } else {
+                let s = (x1 - x0).recip();
+                let x0_fraction = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0_fraction) * (1.0 - x0_fraction);
+                let x1_fraction = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1_fraction * x1_fraction;
+                cells[x0i] += d * a0;
+                if x1i == x0i + 2 {
+                    cells[x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0_fraction);
+                    cells[x0i + 1] += d * (a1 - a0);
+                    for cell in &mut cells[x0i + 2..x1i - 1] {
+                        *cell += d * s;
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    cells[x1i - 1] += d * (1.0 - a2 - am);
+                }
+                cells[x1i] += d * am;
+            }
+        }
+    }
+
+    /// Returns the coverage of every pixel, row by row from the top.
+    pub fn coverage(&self, fill_rule: FillRule) -> Vec<u8> {
+        let stride = self.width + 2;
+        let mut output = Vec::with_capacity(self.width * self.height);
+        for row in self.cells.chunks_exact(stride) {
+            let mut winding = 0.0f32;
+            for &cell in &row[..self.width] {
+                winding += cell;
+                let area = winding.abs();
+                let area = match fill_rule {
+                    FillRule::NonZero => area.min(1.0),
+                    FillRule::EvenOdd => {
+                        let area = area % 2.0;
+                        if area > 1.0 { 2.0 - area } else { area }
+                    }
+                };
+                output.push((area * 255.0 + 0.5) as u8);
+            }
+        }
+        output
+    }
+}
+
+fn length(x: f32, y: f32) -> f32 {
+    (x * x + y * y).sqrt()
+}
+
+/// Returns the number of lines keeping a curve whose chord deviates by
+/// `deviation / n²` within the tolerance.
+fn segment_count(deviation: f32) -> usize {
+    ((deviation / TOLERANCE).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+}
+
+/// Renders an outline with y up, as produced by the `outline` module, into
+/// a bitmap covering its scaled bounds.
+pub fn render(commands: &[PathCommand], options: &RasterOptions) -> Result<Bitmap, Error> {
+    let (scale, (dx, dy)) = (options.scale, options.offset);
+    let mut bounds: Option<(f32, f32, f32, f32)> = None;
+    let mut finite = true;
+    for command in commands {
+        command.map(|x, y| {
+            let (x, y) = (x * scale + dx, y * scale + dy);
+            finite &= x.is_finite() && y.is_finite();
+            bounds = Some(match bounds {
+                None => (x, y, x, y),
+                Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+            });
+            (x, y)
+        });
+    }
+    let Some((x_min, y_min, x_max, y_max)) = bounds else {
+        return Ok(Bitmap {
+            mode: options.mode,
+            ..Default::default()
+        });
+    };
+    if !finite {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+
+    let lcd = options.mode == RenderMode::Lcd;
+    // The LCD filter spreads coverage by up to two subpixels on each side.
+    let padding = if lcd { 1.0 } else { 0.0 };
+    let left = x_min.floor() - padding;
+    let top = y_max.ceil();
+    let width = (x_max.ceil() + padding - left) as usize;
+    let height = (top - y_min.floor()) as usize;
+    let factor = if lcd { 3 } else { 1 };
+    if width.saturating_mul(factor).saturating_mul(height) > MAX_SAMPLES {
+        return Err(Error::Io(IoError::InvalidData));
+    }
+
+    let mut rasterizer = Rasterizer::new(width * factor, height);
+    let transformed: Vec<PathCommand> = commands
+        .iter()
+        .map(|command| {
+            command.map(|x, y| {
+                (
+                    (x * scale + dx - left) * factor as f32,
+                    top - (y * scale + dy),
+                )
+            })
+        })
+        .collect();
+    rasterizer.draw_path(&transformed);
+    let mut data = rasterizer.coverage(options.fill_rule);
+    if lcd {
+        data = lcd_filter(&data, width * 3);
+    }
+    Ok(Bitmap {
+        width,
+        height,
+        left: left as i32,
+        top: top as i32,
+        mode: options.mode,
+        data,
+    })
+}
+
+/// Spreads subpixel coverage over its neighbours to reduce color fringes.
+fn lcd_filter(data: &[u8], stride: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len());
+    for row in data.chunks_exact(stride.max(1)) {
+        for i in 0..row.len() {
+            let sum: u32 = LCD_FILTER
+                .iter()
+                .enumerate()
+                .filter_map(|(k, &weight)| {
+                    let j = (i + k).checked_sub(2)?;
+                    row.get(j).map(|&value| weight * value as u32)
+                })
+                .sum();
+            output.push(((sum + 128) >> 8).min(255) as u8);
+        }
+    }
+    output
+}
@@ -0,0 +1,387 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `CFF ` subsetting: `seac` closure, subroutine subsetting and desubroutinization
+//! with optional hint removal.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::components::cff1::{
+    CS_CALLGSUBR, CS_CALLSUBR, CS_CNTRMASK, CS_ENDCHAR, CS_HINTMASK, CS_HSTEM, CS_HSTEMHM,
+    CS_RETURN, CS_VSTEM, CS_VSTEMHM, CffEncoding, CffTable, CharStringToken,
+    read_char_string_token, standard_encoding_sid, subr_bias, write_char_string,
+};
+use crate::error::{Error, IoError};
+use crate::io::stream::FontDataStream;
+use crate::io::writer::FontDataWriter;
+
+use super::GlyphMap;
+
+/// Maximum subroutine nesting allowed by the Type 2 charstring format.
+const MAX_SUBR_DEPTH: usize = 10;
+
+/// Which subroutine INDEX a call refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum SubrKind {
+    Global,
+    Local,
+}
+
+/// Interprets a charstring far enough to inline subroutines, size hint masks
+/// and find `seac`-style `endchar` accents.
+struct CharStringWalker<'a> {
+    global_subrs: &'a [Vec<u8>],
+    local_subrs: &'a [Vec<u8>],
+    stem_count: usize,
+    stack_len: usize,
+    stack: Vec<f64>,
+    /// The desubroutinized program.
+    flattened: Vec<CharStringToken>,
+    /// Tokens of each called subroutine as lexed on its first call.
+    subrs: HashMap<(SubrKind, usize), Vec<CharStringToken>>,
+    ended: bool,
+    seac: Option<(u8, u8)>,
+}
+
+impl<'a> CharStringWalker<'a> {
+    fn new(global_subrs: &'a [Vec<u8>], local_subrs: &'a [Vec<u8>]) -> Self {
+        CharStringWalker {
+            global_subrs,
+            local_subrs,
+            stem_count: 0,
+            stack_len: 0,
+            stack: Vec::new(),
+            flattened: Vec::new(),
+            subrs: HashMap::new(),
+            ended: false,
+            seac: None,
+        }
+    }
+
+    /// Walks `data` and returns its own (not inlined) tokens.
+    fn walk(&mut self, data: &[u8], depth: usize) -> Result<Vec<CharStringToken>, Error> {
+        if depth > MAX_SUBR_DEPTH {
+            return Err(Error::Io(IoError::InvalidData));
+        }
+        let mut stream = FontDataStream::new(data);
+        let mut tokens = Vec::new();
+        while !stream.is_eof() && !self.ended {
+            let token =
+                read_char_string_token(&mut stream, &mut self.stem_count, &mut self.stack_len)?;
+            tokens.push(token.clone());
+            match token {
+                CharStringToken::Number(value) => {
+                    self.stack.push(value);
+                    self.flattened.push(token);
+                }
+                CharStringToken::Operator(operator @ (CS_CALLSUBR | CS_CALLGSUBR)) => {
+                    let (kind, subrs) = if operator == CS_CALLSUBR {
+                        (SubrKind::Local, self.local_subrs)
+                    } else {
+                        (SubrKind::Global, self.global_subrs)
+                    };
+                    let number = self.stack.pop().ok_or(Error::Io(IoError::InvalidData))?;
+                    self.flattened.pop();
+                    let index = usize::try_from(number as i32 + subr_bias(subrs.len()))
+                        .map_err(|_| Error::Io(IoError::InvalidData))?;
+                    let subr = subrs.get(index).ok_or(Error::Io(IoError::InvalidData))?;
+                    let subr_tokens = self.walk(subr, depth + 1)?;
+                    self.subrs.entry((kind, index)).or_insert(subr_tokens);
+                }
+                CharStringToken::Operator(CS_RETURN) => break,
+                CharStringToken::Operator(CS_ENDCHAR) => {
+                    if self.stack.len() >= 4 {
+                        let n = self.stack.len();
+                        self.seac = Some((self.stack[n - 2] as u8, self.stack[n - 1] as u8));
+                    }
+                    self.flattened.push(token);
+                    self.ended = true;
+                }
+                _ => {
+                    self.stack.clear();
+                    self.flattened.push(token);
+                }
+            }
+        }
+        Ok(tokens)
+    }
+}
+
+fn walk_glyph<'a>(
+    cff: &'a CffTable,
+    glyph: u16,
+) -> Result<(CharStringWalker<'a>, Vec<CharStringToken>), Error> {
+    let data =
+        cff.char_strings
+            .get(glyph as usize)
+            .ok_or(Error::Io(IoError::InvalidGlyphIndex {
+                index: glyph as u32,
+            }))?;
+    let mut walker = CharStringWalker::new(&cff.global_subrs, cff.local_subrs(glyph));
+    let tokens = walker.walk(data, 0)?;
+    Ok((walker, tokens))
+}
+
+/// Returns the base and accent glyphs of a glyph built with the deprecated
+/// `seac` form of `endchar`.
+pub(crate) fn seac_components(cff: &CffTable, glyph: u16) -> Result<Vec<u16>, Error> {
+    if cff.is_cid() {
+        return Ok(Vec::new());
+    }
+    let (walker, _) = walk_glyph(cff, glyph)?;
+    Ok(match walker.seac {
+        Some((base, accent)) => [base, accent]
+            .into_iter()
+            .filter_map(|code| cff.glyph_for_charset_id(standard_encoding_sid(code)))
+            .filter(|&g| g != 0)
+            .collect(),
+        None => Vec::new(),
+    })
+}
+
+/// Removes stem hints and masks, moving an advance width carried by a hint
+/// operator to the first remaining stack-clearing operator.
+fn strip_hints(tokens: Vec<CharStringToken>) -> Vec<CharStringToken> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut args = Vec::new();
+    let mut first_clearing = true;
+    let mut width = None;
+    for token in tokens {
+        let operator = match token {
+            CharStringToken::Number(_) => {
+                args.push(token);
+                continue;
+            }
+            CharStringToken::Operator(operator) | CharStringToken::Mask(operator, _) => operator,
+        };
+        match operator {
+            CS_HSTEM | CS_VSTEM | CS_HSTEMHM | CS_VSTEMHM | CS_HINTMASK | CS_CNTRMASK => {
+                if first_clearing {
+                    first_clearing = false;
+                    if args.len() % 2 == 1 {
+                        width = Some(args.swap_remove(0));
+                    }
+                }
+                args.clear();
+            }
+            _ => {
+                first_clearing = false;
+                output.extend(width.take());
+                output.append(&mut args);
+                output.push(token);
+            }
+        }
+    }
+    output
+}
+
+/// Rewrites the subroutine numbers of `tokens` for the renumbered INDEXes.
+fn renumber_calls(
+    tokens: &[CharStringToken],
+    global: &BTreeMap<usize, usize>,
+    local: &BTreeMap<usize, usize>,
+    old_bias: (i32, i32),
+    new_bias: (i32, i32),
+) -> Result<Vec<u8>, Error> {
+    let mut output = tokens.to_vec();
+    for i in 1..output.len() {
+        let (map, old, new) = match output[i] {
+            CharStringToken::Operator(CS_CALLGSUBR) => (global, old_bias.0, new_bias.0),
+            CharStringToken::Operator(CS_CALLSUBR) => (local, old_bias.1, new_bias.1),
+            _ => continue,
+        };
+        let CharStringToken::Number(number) = output[i - 1] else {
+            return Err(Error::Io(IoError::InvalidData));
+        };
+        let index = map
+            .get(&((number as i32 + old) as usize))
+            .ok_or(Error::Io(IoError::InvalidData))?;
+        output[i - 1] = CharStringToken::Number((*index as i32 - new) as f64);
+    }
+    let mut writer = FontDataWriter::new();
+    write_char_string(&mut writer, &output);
+    writer.into_bytes()
+}
+
+fn encode(tokens: &[CharStringToken]) -> Result<Vec<u8>, Error> {
+    let mut writer = FontDataWriter::new();
+    write_char_string(&mut writer, tokens);
+    writer.into_bytes()
+}
+
+/// Builds the subset `CFF ` table.
+///
+/// With `drop_hinting` charstrings are desubroutinized and stripped of hints;
+/// otherwise only the subroutines reachable from kept glyphs are retained and
+/// renumbered.
+pub(crate) fn subset_cff(
+    cff: &CffTable,
+    map: &GlyphMap,
+    drop_hinting: bool,
+) -> Result<CffTable, Error> {
+    let old_ids = map.old_ids();
+    let fd_of = |glyph: u16| cff.fd_select.get(glyph as usize).copied().unwrap_or(0);
+
+    let mut walks = Vec::with_capacity(old_ids.len());
+    for old in &old_ids {
+        walks.push(match old {
+            Some(old) => Some(walk_glyph(cff, *old)?),
+            None => None,
+        });
+    }
+
+    let mut subset = cff.clone();
+    subset.charset = old_ids
+        .iter()
+        .map(|old| {
+            old.and_then(|g| cff.charset.get(g as usize).copied())
+                .unwrap_or(0)
+        })
+        .collect();
+    if cff.is_cid() {
+        subset.fd_select = old_ids.iter().map(|old| old.map_or(0, fd_of)).collect();
+    }
+    if let CffEncoding::Custom { codes, supplements } = &cff.encoding {
+        subset.encoding = CffEncoding::Custom {
+            codes: codes
+                .iter()
+                .filter_map(|&(code, glyph)| Some((code, map.new_id(glyph)?)))
+                .collect(),
+            supplements: supplements.clone(),
+        };
+    }
+    let empty_glyph = vec![CS_ENDCHAR as u8];
+
+    // Collect the subroutines used by each Font DICT (a single one for
+    // name-keyed fonts) and number them in their original order.
+    let mut used_global: BTreeMap<usize, Vec<CharStringToken>> = BTreeMap::new();
+    let mut used_local: BTreeMap<u8, BTreeMap<usize, Vec<CharStringToken>>> = BTreeMap::new();
+    for (walk, old) in walks.iter().zip(&old_ids) {
+        let (Some((walker, _)), Some(old)) = (walk, old) else {
+            continue;
+        };
+        for ((kind, index), tokens) in &walker.subrs {
+            let target = match kind {
+                SubrKind::Global => &mut used_global,
+                SubrKind::Local => used_local.entry(fd_of(*old)).or_default(),
+            };
+            target.entry(*index).or_insert_with(|| tokens.clone());
+        }
+    }
+    // A global subroutine calling local ones cannot be renumbered when Font
+    // DICTs have different local subroutines; such fonts are desubroutinized.
+    let ambiguous_locals = cff.fd_array.len() > 1
+        && used_global
+            .values()
+            .any(|tokens| tokens.contains(&CharStringToken::Operator(CS_CALLSUBR)));
+    if drop_hinting || ambiguous_locals {
+        subset.char_strings = walks
+            .into_iter()
+            .map(|walk| match walk {
+                Some((walker, _)) if drop_hinting => encode(&strip_hints(walker.flattened)),
+                Some((walker, _)) => encode(&walker.flattened),
+                None => Ok(empty_glyph.clone()),
+            })
+            .collect::<Result<_, _>>()?;
+        subset.global_subrs.clear();
+        if let Some(private) = &mut subset.private {
+            private.local_subrs.clear();
+        }
+        for fd in &mut subset.fd_array {
+            fd.private.local_subrs.clear();
+        }
+        return Ok(subset);
+    }
+
+    let numbering = |used: &BTreeMap<usize, Vec<CharStringToken>>| -> BTreeMap<usize, usize> {
+        used.keys()
+            .enumerate()
+            .map(|(new, &old)| (old, new))
+            .collect()
+    };
+    let global_numbers = numbering(&used_global);
+    let global_bias = (
+        subr_bias(cff.global_subrs.len()),
+        subr_bias(used_global.len()),
+    );
+    let fds: BTreeSet<u8> = if cff.is_cid() {
+        (0..cff.fd_array.len() as u8).collect()
+    } else {
+        [0].into()
+    };
+    let mut local_numbers = BTreeMap::new();
+    let mut local_subrs = BTreeMap::new();
+    for fd in fds {
+        let empty = BTreeMap::new();
+        let used = used_local.get(&fd).unwrap_or(&empty);
+        let old_count = if cff.is_cid() {
+            cff.fd_array
+                .get(fd as usize)
+                .map_or(0, |f| f.private.local_subrs.len())
+        } else {
+            cff.private.as_ref().map_or(0, |p| p.local_subrs.len())
+        };
+        let numbers = numbering(used);
+        let bias = (subr_bias(old_count), subr_bias(used.len()));
+        let subrs = used
+            .values()
+            .map(|tokens| {
+                renumber_calls(
+                    tokens,
+                    &global_numbers,
+                    &numbers,
+                    (global_bias.0, bias.0),
+                    (global_bias.1, bias.1),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        local_numbers.insert(fd, (numbers, bias));
+        local_subrs.insert(fd, subrs);
+    }
+
+    let no_locals = (BTreeMap::new(), (0, 0));
+    let locals_of = |fd: u8| local_numbers.get(&fd).unwrap_or(&no_locals);
+    subset.global_subrs = used_global
+        .values()
+        .map(|tokens| {
+            // Local calls from global subroutines only occur here with a
+            // single Font DICT.
+            let (numbers, bias) = locals_of(0);
+            renumber_calls(
+                tokens,
+                &global_numbers,
+                numbers,
+                (global_bias.0, bias.0),
+                (global_bias.1, bias.1),
+            )
+        })
+        .collect::<Result<_, _>>()?;
+    subset.char_strings = walks
+        .iter()
+        .zip(&old_ids)
+        .map(|(walk, old)| match (walk, old) {
+            (Some((_, tokens)), Some(old)) => {
+                let (numbers, bias) = locals_of(fd_of(*old));
+                renumber_calls(
+                    tokens,
+                    &global_numbers,
+                    numbers,
+                    (global_bias.0, bias.0),
+                    (global_bias.1, bias.1),
+                )
+            }
+            _ => Ok(empty_glyph.clone()),
+        })
+        .collect::<Result<_, _>>()?;
+    for (fd, subrs) in local_subrs {
+        if cff.is_cid() {
+            subset.fd_array[fd as usize].private.local_subrs = subrs;
+        } else if let Some(private) = &mut subset.private {
+            private.local_subrs = subrs;
+        }
+    }
+    Ok(subset)
+}
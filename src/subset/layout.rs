@@ -0,0 +1,504 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! `GSUB`/`GPOS`/`GDEF` subsetting: substitution closure and glyph remapping.
+//! Lookup indices are preserved so features and nested lookups stay valid.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::components::gdef::GdefTable;
+use crate::components::gpos::{GposTable, PositioningSubtable};
+use crate::components::gsub::{GsubTable, Ligature, ReverseChainSingleSubst, SubstitutionSubtable};
+use crate::components::layout::{
+    ChainedSequenceContext, ChainedSequenceRule, ClassDef, Coverage, LayoutTable, Lookup,
+    SequenceContext, SequenceRule,
+};
+
+use super::GlyphMap;
+
+/// Adds every glyph that a `GSUB` lookup can produce from `glyphs`.
+///
+/// Contextual lookups are not evaluated; the lookups they invoke are visited
+/// on their own, which over-approximates the closure.
+pub(crate) fn close_over_gsub(gsub: &GsubTable, glyphs: &mut BTreeSet<u16>) {
+    loop {
+        let before = glyphs.len();
+        for subtable in gsub.lookups.iter().flat_map(|lookup| &lookup.subtables) {
+            let mut added = Vec::new();
+            match subtable {
+                SubstitutionSubtable::Single(map) => {
+                    added.extend(
+                        map.iter()
+                            .filter(|(g, _)| glyphs.contains(g))
+                            .map(|(_, &s)| s),
+                    );
+                }
+                SubstitutionSubtable::Multiple(map) | SubstitutionSubtable::Alternate(map) => {
+                    for (glyph, outputs) in map {
+                        if glyphs.contains(glyph) {
+                            added.extend(outputs);
+                        }
+                    }
+                }
+                SubstitutionSubtable::Ligature(map) => {
+                    for (first, ligatures) in map {
+                        if !glyphs.contains(first) {
+                            continue;
+                        }
+                        for ligature in ligatures {
+                            if ligature
+                                .component_glyph_ids
+                                .iter()
+                                .all(|g| glyphs.contains(g))
+                            {
+                                added.push(ligature.ligature_glyph);
+                            }
+                        }
+                    }
+                }
+                SubstitutionSubtable::ReverseChainSingle(subst) => {
+                    for (glyph, &substitute) in subst
+                        .coverage
+                        .glyphs
+                        .iter()
+                        .zip(&subst.substitute_glyph_ids)
+                    {
+                        if glyphs.contains(glyph) {
+                            added.push(substitute);
+                        }
+                    }
+                }
+                SubstitutionSubtable::Context(_) | SubstitutionSubtable::ChainContext(_) => {}
+            }
+            glyphs.extend(added);
+        }
+        if glyphs.len() == before {
+            break;
+        }
+    }
+}
+
+fn subset_coverage(coverage: &Coverage, map: &GlyphMap) -> Coverage {
+    Coverage::new(coverage.glyphs.iter().filter_map(|&g| map.new_id(g)))
+}
+
+fn subset_class_def(class_def: &ClassDef, map: &GlyphMap) -> ClassDef {
+    ClassDef {
+        classes: class_def
+            .classes
+            .iter()
+            .filter_map(|(&g, &class)| Some((map.new_id(g)?, class)))
+            .collect(),
+    }
+}
+
+/// Remaps the keys of a glyph-keyed map, dropping removed glyphs.
+fn subset_keys<T: Clone>(values: &BTreeMap<u16, T>, map: &GlyphMap) -> BTreeMap<u16, T> {
+    values
+        .iter()
+        .filter_map(|(&g, value)| Some((map.new_id(g)?, value.clone())))
+        .collect()
+}
+
+fn subset_glyphs(glyphs: &[u16], map: &GlyphMap) -> Option<Vec<u16>> {
+    glyphs.iter().map(|&g| map.new_id(g)).collect()
+}
+
+/// Pairs each covered glyph with its coverage-indexed record, keeping only
+/// the glyphs that survive.
+fn subset_indexed<T: Clone>(
+    coverage: &Coverage,
+    records: &[T],
+    map: &GlyphMap,
+) -> (Coverage, Vec<T>) {
+    let (glyphs, records) = coverage
+        .glyphs
+        .iter()
+        .zip(records)
+        .filter_map(|(&g, record)| Some((map.new_id(g)?, record.clone())))
+        .unzip();
+    (Coverage { glyphs }, records)
+}
+
+fn subset_sequence_context(context: &SequenceContext, map: &GlyphMap) -> Option<SequenceContext> {
+    let context = match context {
+        SequenceContext::Glyphs {
+            coverage,
+            rule_sets,
+        } => {
+            let (coverage, rule_sets) = subset_indexed(coverage, rule_sets, map);
+            let rule_sets = rule_sets
+                .into_iter()
+                .map(|rules| {
+                    rules
+                        .into_iter()
+                        .filter_map(|rule| {
+                            Some(SequenceRule {
+                                input_sequence: subset_glyphs(&rule.input_sequence, map)?,
+                                seq_lookup_records: rule.seq_lookup_records,
+                            })
+                        })
+                        .collect()
+                })
+                .collect();
+            SequenceContext::Glyphs {
+                coverage,
+                rule_sets,
+            }
+        }
+        SequenceContext::Classes {
+            coverage,
+            class_def,
+            rule_sets,
+        } => SequenceContext::Classes {
+            coverage: subset_coverage(coverage, map),
+            class_def: subset_class_def(class_def, map),
+            rule_sets: rule_sets.clone(),
+        },
+        SequenceContext::Coverages {
+            coverages,
+            seq_lookup_records,
+        } => SequenceContext::Coverages {
+            coverages: coverages.iter().map(|c| subset_coverage(c, map)).collect(),
+            seq_lookup_records: seq_lookup_records.clone(),
+        },
+    };
+    let empty = match &context {
+        SequenceContext::Glyphs { coverage, .. } | SequenceContext::Classes { coverage, .. } => {
+            coverage.is_empty()
+        }
+        SequenceContext::Coverages { coverages, .. } => coverages.iter().any(Coverage::is_empty),
+    };
+    (!empty).then_some(context)
+}
+
+fn subset_chained_context(
+    context: &ChainedSequenceContext,
+    map: &GlyphMap,
+) -> Option<ChainedSequenceContext> {
+    let context = match context {
+        ChainedSequenceContext::Glyphs {
+            coverage,
+            rule_sets,
+        } => {
+            let (coverage, rule_sets) = subset_indexed(coverage, rule_sets, map);
+            let rule_sets = rule_sets
+                .into_iter()
+                .map(|rules| {
+                    rules
+                        .into_iter()
+                        .filter_map(|rule| {
+                            Some(ChainedSequenceRule {
+                                backtrack_sequence: subset_glyphs(&rule.backtrack_sequence, map)?,
+                                input_sequence: subset_glyphs(&rule.input_sequence, map)?,
+                                lookahead_sequence: subset_glyphs(&rule.lookahead_sequence, map)?,
+                                seq_lookup_records: rule.seq_lookup_records,
+                            })
+                        })
+                        .collect()
+                })
+                .collect();
+            ChainedSequenceContext::Glyphs {
+                coverage,
+                rule_sets,
+            }
+        }
+        ChainedSequenceContext::Classes {
+            coverage,
+            backtrack_class_def,
+            input_class_def,
+            lookahead_class_def,
+            rule_sets,
+        } => ChainedSequenceContext::Classes {
+            coverage: subset_coverage(coverage, map),
+            backtrack_class_def: subset_class_def(backtrack_class_def, map),
+            input_class_def: subset_class_def(input_class_def, map),
+            lookahead_class_def: subset_class_def(lookahead_class_def, map),
+            rule_sets: rule_sets.clone(),
+        },
+        ChainedSequenceContext::Coverages {
+            backtrack_coverages,
+            input_coverages,
+            lookahead_coverages,
+            seq_lookup_records,
+        } => {
+            let subset_all = |coverages: &[Coverage]| -> Vec<Coverage> {
+                coverages.iter().map(|c| subset_coverage(c, map)).collect()
+            };
+            ChainedSequenceContext::Coverages {
+                backtrack_coverages: subset_all(backtrack_coverages),
+                input_coverages: subset_all(input_coverages),
+                lookahead_coverages: subset_all(lookahead_coverages),
+                seq_lookup_records: seq_lookup_records.clone(),
+            }
+        }
+    };
+    let empty = match &context {
+        ChainedSequenceContext::Glyphs { coverage, .. }
+        | ChainedSequenceContext::Classes { coverage, .. } => coverage.is_empty(),
+        ChainedSequenceContext::Coverages {
+            backtrack_coverages,
+            input_coverages,
+            lookahead_coverages,
+            ..
+        } => backtrack_coverages
+            .iter()
+            .chain(input_coverages)
+            .chain(lookahead_coverages)
+            .any(Coverage::is_empty),
+    };
+    (!empty).then_some(context)
+}
+
+/// Subsets every lookup of a layout table; subtables left without glyphs are
+/// removed but lookups are kept so indices stay valid.
+fn subset_layout_table<T: Clone>(
+    table: &LayoutTable<T>,
+    subset_subtable: impl Fn(&T) -> Option<T>,
+) -> LayoutTable<T> {
+    LayoutTable {
+        major_version: table.major_version,
+        minor_version: table.minor_version,
+        scripts: table.scripts.clone(),
+        features: table.features.clone(),
+        lookups: table
+            .lookups
+            .iter()
+            .map(|lookup| Lookup {
+                lookup_type: lookup.lookup_type,
+                lookup_flag: lookup.lookup_flag,
+                subtables: lookup
+                    .subtables
+                    .iter()
+                    .filter_map(&subset_subtable)
+                    .collect(),
+                mark_filtering_set: lookup.mark_filtering_set,
+            })
+            .collect(),
+        feature_variations: table.feature_variations.clone(),
+    }
+}
+
+/// Subsets and renumbers the `GSUB` table.
+pub(crate) fn subset_gsub(gsub: &GsubTable, map: &GlyphMap) -> GsubTable {
+    subset_layout_table(gsub, |subtable| {
+        let subtable = match subtable {
+            SubstitutionSubtable::Single(values) => SubstitutionSubtable::Single(
+                values
+                    .iter()
+                    .filter_map(|(&g, &s)| Some((map.new_id(g)?, map.new_id(s)?)))
+                    .collect(),
+            ),
+            SubstitutionSubtable::Multiple(values) => SubstitutionSubtable::Multiple(
+                values
+                    .iter()
+                    .filter_map(|(&g, outputs)| {
+                        Some((map.new_id(g)?, subset_glyphs(outputs, map)?))
+                    })
+                    .collect(),
+            ),
+            SubstitutionSubtable::Alternate(values) => SubstitutionSubtable::Alternate(
+                values
+                    .iter()
+                    .filter_map(|(&g, alternates)| {
+                        let alternates: Vec<u16> =
+                            alternates.iter().filter_map(|&a| map.new_id(a)).collect();
+                        Some((map.new_id(g)?, alternates)).filter(|(_, a)| !a.is_empty())
+                    })
+                    .collect(),
+            ),
+            SubstitutionSubtable::Ligature(values) => SubstitutionSubtable::Ligature(
+                values
+                    .iter()
+                    .filter_map(|(&g, ligatures)| {
+                        let ligatures: Vec<Ligature> = ligatures
+                            .iter()
+                            .filter_map(|ligature| {
+                                Some(Ligature {
+                                    ligature_glyph: map.new_id(ligature.ligature_glyph)?,
+                                    component_glyph_ids: subset_glyphs(
+                                        &ligature.component_glyph_ids,
+                                        map,
+                                    )?,
+                                })
+                            })
+                            .collect();
+                        Some((map.new_id(g)?, ligatures)).filter(|(_, l)| !l.is_empty())
+                    })
+                    .collect(),
+            ),
+            SubstitutionSubtable::Context(context) => {
+                return subset_sequence_context(context, map).map(SubstitutionSubtable::Context);
+            }
+            SubstitutionSubtable::ChainContext(context) => {
+                return subset_chained_context(context, map)
+                    .map(SubstitutionSubtable::ChainContext);
+            }
+            SubstitutionSubtable::ReverseChainSingle(subst) => {
+                let (glyphs, substitutes): (Vec<u16>, Vec<u16>) = subst
+                    .coverage
+                    .glyphs
+                    .iter()
+                    .zip(&subst.substitute_glyph_ids)
+                    .filter_map(|(&g, &s)| Some((map.new_id(g)?, map.new_id(s)?)))
+                    .unzip();
+                let subset_all = |coverages: &[Coverage]| -> Vec<Coverage> {
+                    coverages.iter().map(|c| subset_coverage(c, map)).collect()
+                };
+                let subst = ReverseChainSingleSubst {
+                    coverage: Coverage { glyphs },
+                    backtrack_coverages: subset_all(&subst.backtrack_coverages),
+                    lookahead_coverages: subset_all(&subst.lookahead_coverages),
+                    substitute_glyph_ids: substitutes,
+                };
+                let empty = subst.coverage.is_empty()
+                    || subst
+                        .backtrack_coverages
+                        .iter()
+                        .chain(&subst.lookahead_coverages)
+                        .any(Coverage::is_empty);
+                return (!empty).then_some(SubstitutionSubtable::ReverseChainSingle(subst));
+            }
+        };
+        let empty = match &subtable {
+            SubstitutionSubtable::Single(values) => values.is_empty(),
+            SubstitutionSubtable::Multiple(values) | SubstitutionSubtable::Alternate(values) => {
+                values.is_empty()
+            }
+            SubstitutionSubtable::Ligature(values) => values.is_empty(),
+            _ => false,
+        };
+        (!empty).then_some(subtable)
+    })
+}
+
+/// Subsets and renumbers the `GPOS` table.
+pub(crate) fn subset_gpos(gpos: &GposTable, map: &GlyphMap) -> GposTable {
+    subset_layout_table(gpos, |subtable| {
+        let subtable = match subtable {
+            PositioningSubtable::Single {
+                value_format,
+                values,
+            } => PositioningSubtable::Single {
+                value_format: *value_format,
+                values: subset_keys(values, map),
+            },
+            PositioningSubtable::Pair {
+                value_format1,
+                value_format2,
+                pair_sets,
+            } => PositioningSubtable::Pair {
+                value_format1: *value_format1,
+                value_format2: *value_format2,
+                pair_sets: pair_sets
+                    .iter()
+                    .filter_map(|(&g, pairs)| {
+                        let pairs: Vec<_> = pairs
+                            .iter()
+                            .filter_map(|pair| {
+                                let mut pair = pair.clone();
+                                pair.second_glyph = map.new_id(pair.second_glyph)?;
+                                Some(pair)
+                            })
+                            .collect();
+                        Some((map.new_id(g)?, pairs)).filter(|(_, p)| !p.is_empty())
+                    })
+                    .collect(),
+            },
+            PositioningSubtable::PairClass {
+                value_format1,
+                value_format2,
+                coverage,
+                class_def1,
+                class_def2,
+                class1_records,
+            } => PositioningSubtable::PairClass {
+                value_format1: *value_format1,
+                value_format2: *value_format2,
+                coverage: subset_coverage(coverage, map),
+                class_def1: subset_class_def(class_def1, map),
+                class_def2: subset_class_def(class_def2, map),
+                class1_records: class1_records.clone(),
+            },
+            PositioningSubtable::Cursive(records) => {
+                PositioningSubtable::Cursive(subset_keys(records, map))
+            }
+            PositioningSubtable::MarkToBase {
+                mark_class_count,
+                marks,
+                bases,
+            } => PositioningSubtable::MarkToBase {
+                mark_class_count: *mark_class_count,
+                marks: subset_keys(marks, map),
+                bases: subset_keys(bases, map),
+            },
+            PositioningSubtable::MarkToLigature {
+                mark_class_count,
+                marks,
+                ligatures,
+            } => PositioningSubtable::MarkToLigature {
+                mark_class_count: *mark_class_count,
+                marks: subset_keys(marks, map),
+                ligatures: subset_keys(ligatures, map),
+            },
+            PositioningSubtable::MarkToMark {
+                mark_class_count,
+                marks,
+                mark2s,
+            } => PositioningSubtable::MarkToMark {
+                mark_class_count: *mark_class_count,
+                marks: subset_keys(marks, map),
+                mark2s: subset_keys(mark2s, map),
+            },
+            PositioningSubtable::Context(context) => {
+                return subset_sequence_context(context, map).map(PositioningSubtable::Context);
+            }
+            PositioningSubtable::ChainContext(context) => {
+                return subset_chained_context(context, map).map(PositioningSubtable::ChainContext);
+            }
+        };
+        let empty = match &subtable {
+            PositioningSubtable::Single { values, .. } => values.is_empty(),
+            PositioningSubtable::Pair { pair_sets, .. } => pair_sets.is_empty(),
+            PositioningSubtable::PairClass { coverage, .. } => coverage.is_empty(),
+            PositioningSubtable::Cursive(records) => records.is_empty(),
+            PositioningSubtable::MarkToBase { marks, bases, .. } => {
+                marks.is_empty() || bases.is_empty()
+            }
+            PositioningSubtable::MarkToLigature {
+                marks, ligatures, ..
+            } => marks.is_empty() || ligatures.is_empty(),
+            PositioningSubtable::MarkToMark { marks, mark2s, .. } => {
+                marks.is_empty() || mark2s.is_empty()
+            }
+            _ => false,
+        };
+        (!empty).then_some(subtable)
+    })
+}
+
+/// Subsets and renumbers the `GDEF` table.
+pub(crate) fn subset_gdef(gdef: &GdefTable, map: &GlyphMap) -> GdefTable {
+    GdefTable {
+        major_version: gdef.major_version,
+        minor_version: gdef.minor_version,
+        glyph_class_def: gdef
+            .glyph_class_def
+            .as_ref()
+            .map(|c| subset_class_def(c, map)),
+        attach_list: gdef.attach_list.as_ref().map(|a| subset_keys(a, map)),
+        lig_caret_list: gdef.lig_caret_list.as_ref().map(|l| subset_keys(l, map)),
+        mark_attach_class_def: gdef
+            .mark_attach_class_def
+            .as_ref()
+            .map(|c| subset_class_def(c, map)),
+        mark_glyph_sets: gdef
+            .mark_glyph_sets
+            .as_ref()
+            .map(|sets| sets.iter().map(|c| subset_coverage(c, map)).collect()),
+        item_var_store: gdef.item_var_store.clone(),
+    }
+}
@@ -0,0 +1,572 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! Font subsetting. A subset keeps the glyphs needed for a set of code points
+//! and glyph ids, closed over composite glyphs, `seac` accents and `GSUB`
+//! substitutions, and rewrites every glyph-dependent table with the new glyph
+//! numbering. Glyph-dependent tables that cannot be rewritten make subsetting
+//! fail unless `SubsetOptions::drop_unsupported_tables` allows dropping them.
+
+mod cff;
+mod layout;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::common::snft::{SnftTable, write_snft};
+use crate::components::cff1::{read_cff, write_cff};
+use crate::components::cmap::{
+    CmapSubtable, CmapTable, EncodingRecord, VariationSelector, read_cmap, write_cmap,
+};
+use crate::components::gdef::{read_gdef, write_gdef};
+use crate::components::glyf::{GlyfTable, Glyph, WE_HAVE_INSTRUCTIONS, read_glyf, write_glyf};
+use crate::components::gpos::{read_gpos, write_gpos};
+use crate::components::gsub::{GsubTable, read_gsub, write_gsub};
+use crate::components::head::{read_head, write_head};
+use crate::components::hhea::{read_hhea, write_hhea};
+use crate::components::hmtx::{HmtxTable, LongHorMetric, read_hmtx, write_hmtx};
+use crate::components::loca::{read_loca, write_loca};
+use crate::components::maxp::{read_maxp, write_maxp};
+use crate::components::name::{read_name, write_name};
+use crate::components::post::{read_post, write_post};
+use crate::error::{Error, IoError};
+use crate::io::writer::{FontDataWriter, serialize};
+
+/// Glyph-dependent tables the subsetter cannot rewrite.
+pub const UNSUPPORTED_TABLES: [[u8; 4]; 26] = [
+    *b"BASE", *b"CBDT", *b"CBLC", *b"COLR", *b"CPAL", *b"EBDT", *b"EBLC", *b"EBSC", *b"HVAR",
+    *b"JSTF", *b"LTSH", *b"MATH", *b"SVG ", *b"VORG", *b"VVAR", *b"ankr", *b"feat", *b"gvar",
+    *b"hdmx", *b"kern", *b"kerx", *b"morx", *b"mort", *b"sbix", *b"trak", *b"just",
+];
+
+/// Font variation tables. `gvar`, `HVAR` and `VVAR` cannot be rewritten and
+/// the others describe the design space they vary over, so the group is kept
+/// or dropped as a whole.
+const VARIATION_TABLES: [[u8; 4]; 8] = [
+    *b"HVAR", *b"MVAR", *b"STAT", *b"VVAR", *b"avar", *b"cvar", *b"fvar", *b"gvar",
+];
+
+/// The digital signature, which no longer matches once the font changes; it
+/// is always dropped.
+const SIGNATURE_TABLE: [u8; 4] = *b"DSIG";
+
+/// Tables holding TrueType hinting data, dropped with `drop_hinting`.
+const HINTING_TABLES: [[u8; 4]; 5] = [*b"cvt ", *b"cvar", *b"fpgm", *b"prep", *b"VDMX"];
+
+/// Options controlling subsetting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubsetOptions {
+    /// Code points to keep; they are the only ones left in `cmap`.
+    pub unicodes: BTreeSet<u32>,
+    /// Additional glyph ids to keep.
+    pub glyph_ids: BTreeSet<u16>,
+    /// Keep original glyph ids, leaving removed glyphs empty.
+    pub retain_gids: bool,
+    /// Remove TrueType instructions and CFF hints.
+    pub drop_hinting: bool,
+    /// Add the glyphs reachable through `GSUB` substitutions.
+    pub layout_closure: bool,
+    /// Name IDs to keep in `name`; `None` keeps every record.
+    pub name_ids: Option<BTreeSet<u16>>,
+    /// Drop the `UNSUPPORTED_TABLES` of the font instead of failing. Dropping
+    /// a variation table drops every other one too, leaving a static font at
+    /// the default location.
+    pub drop_unsupported_tables: bool,
+}
+
+impl Default for SubsetOptions {
+    fn default() -> Self {
+        SubsetOptions {
+            unicodes: BTreeSet::new(),
+            glyph_ids: BTreeSet::new(),
+            retain_gids: false,
+            drop_hinting: false,
+            layout_closure: true,
+            name_ids: None,
+            drop_unsupported_tables: false,
+        }
+    }
+}
+
+/// Mapping between the glyph ids of a font and of its subset.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GlyphMap {
+    old_to_new: BTreeMap<u16, u16>,
+    num_glyphs: u16,
+}
+
+impl GlyphMap {
+    /// Numbers the kept glyphs consecutively, or keeps their ids with `retain_gids`.
+    pub fn new(glyphs: &BTreeSet<u16>, retain_gids: bool) -> Self {
+        let old_to_new: BTreeMap<u16, u16> = if retain_gids {
+            glyphs.iter().map(|&g| (g, g)).collect()
+        } else {
+            glyphs
+                .iter()
+                .enumerate()
+                .map(|(new, &old)| (old, new as u16))
+                .collect()
+        };
+        let num_glyphs = old_to_new.values().next_back().map_or(0, |&g| g + 1);
+        GlyphMap {
+            old_to_new,
+            num_glyphs,
+        }
+    }
+
+    /// Returns the new id of a kept glyph.
+    pub fn new_id(&self, old_id: u16) -> Option<u16> {
+        self.old_to_new.get(&old_id).copied()
+    }
+
+    /// Returns the number of glyphs in the subset.
+    pub fn num_glyphs(&self) -> u16 {
+        self.num_glyphs
+    }
+
+    /// Returns the original id of every subset glyph (`None` for empty slots
+    /// left by `retain_gids`).
+    pub fn old_ids(&self) -> Vec<Option<u16>> {
+        let mut old_ids = vec![None; self.num_glyphs as usize];
+        for (&old, &new) in &self.old_to_new {
+            old_ids[new as usize] = Some(old);
+        }
+        old_ids
+    }
+}
+
+/// Parsed tables needed to compute the glyph closure.
+struct SourceFont {
+    num_glyphs: u16,
+    cmap: CmapTable,
+    gsub: Option<GsubTable>,
+    glyf: Option<GlyfTable>,
+    cff: Option<crate::components::cff1::CffTable>,
+}
+
+impl SourceFont {
+    fn read(snft: &SnftTable, source: &[u8]) -> Result<Self, Error> {
+        if snft.has_table(b"CFF2") {
+            // CFF2 outlines are not supported by the subsetter.
+            return Err(Error::Io(IoError::InvalidTag(*b"CFF2")));
+        }
+        let num_glyphs = read_maxp(&mut snft.table_stream(b"maxp", source)?)?.num_glyphs;
+        let cmap = match snft.has_table(b"cmap") {
+            true => read_cmap(&mut snft.table_stream(b"cmap", source)?)?,
+            false => CmapTable::default(),
+        };
+        let gsub = match snft.has_table(b"GSUB") {
+            true => Some(read_gsub(&mut snft.table_stream(b"GSUB", source)?)?),
+            false => None,
+        };
+        let glyf = match snft.has_table(b"glyf") {
+            true => {
+                let head = read_head(&mut snft.table_stream(b"head", source)?)?;
+                let loca = read_loca(
+                    &mut snft.table_stream(b"loca", source)?,
+                    head.has_long_loca(),
+                    num_glyphs,
+                )?;
+                Some(read_glyf(&mut snft.table_stream(b"glyf", source)?, &loca)?)
+            }
+            false => None,
+        };
+        let cff = match snft.has_table(b"CFF ") {
+            true => Some(read_cff(&mut snft.table_stream(b"CFF ", source)?)?),
+            false => None,
+        };
+        Ok(SourceFont {
+            num_glyphs,
+            cmap,
+            gsub,
+            glyf,
+            cff,
+        })
+    }
+
+    /// Computes the glyphs to keep.
+    fn glyph_closure(&self, options: &SubsetOptions) -> Result<BTreeSet<u16>, Error> {
+        let mut glyphs: BTreeSet<u16> = [0].into();
+        glyphs.extend(options.glyph_ids.iter().filter(|&&g| g < self.num_glyphs));
+        let symbol = self.cmap.symbol_mappings();
+        glyphs.extend(
+            options
+                .unicodes
+                .iter()
+                .filter_map(|&cp| self.cmap.glyph_id(cp).or_else(|| symbol?.get(&cp).copied())),
+        );
+        for selector in self.cmap.variation_selectors().unwrap_or_default() {
+            glyphs.extend(
+                selector
+                    .non_default_uvs
+                    .iter()
+                    .filter(|(cp, _)| options.unicodes.contains(cp))
+                    .map(|(_, &g)| g),
+            );
+        }
+        if let (true, Some(gsub)) = (options.layout_closure, &self.gsub) {
+            layout::close_over_gsub(gsub, &mut glyphs);
+        }
+        glyphs.retain(|&g| g < self.num_glyphs);
+
+        let mut pending: Vec<u16> = glyphs.iter().copied().collect();
+        while let Some(glyph) = pending.pop() {
+            let mut components = Vec::new();
+            if let Some(glyf) = &self.glyf {
+                components.extend(
+                    glyf.glyph(glyph)
+                        .map(Glyph::component_glyph_ids)
+                        .unwrap_or_default(),
+                );
+            }
+            if let Some(cff) = &self.cff {
+                components.extend(cff::seac_components(cff, glyph)?);
+            }
+            for component in components {
+                if component < self.num_glyphs && glyphs.insert(component) {
+                    pending.push(component);
+                }
+            }
+        }
+        Ok(glyphs)
+    }
+}
+
+/// Builds the Unicode `cmap` of the subset: format 4 for the BMP, format 12
+/// when supplementary code points remain and format 14 for variation sequences.
+/// A symbol subtable (3, 0) of the font is kept as format 4.
+fn subset_cmap(cmap: &CmapTable, map: &GlyphMap, unicodes: &BTreeSet<u32>) -> CmapTable {
+    let mappings: BTreeMap<u32, u16> = unicodes
+        .iter()
+        .filter_map(|&cp| Some((cp, map.new_id(cmap.glyph_id(cp)?)?)))
+        .collect();
+    let mut records = Vec::new();
+    let mut add = |platform_id, encoding_id, subtable: CmapSubtable| {
+        records.push(EncodingRecord {
+            platform_id,
+            encoding_id,
+            subtable,
+        })
+    };
+    let bmp: BTreeMap<u32, u16> = mappings.range(..0x10000).map(|(&c, &g)| (c, g)).collect();
+    let format4 = CmapSubtable::Mapping {
+        format: 4,
+        language: 0,
+        mappings: bmp,
+    };
+    add(0, 3, format4.clone());
+    add(3, 1, format4);
+    if mappings.keys().next_back().is_some_and(|&cp| cp > 0xFFFF) {
+        let format12 = CmapSubtable::Mapping {
+            format: 12,
+            language: 0,
+            mappings: mappings.clone(),
+        };
+        add(0, 4, format12.clone());
+        add(3, 10, format12);
+    }
+    if let Some(symbol) = cmap.symbol_mappings() {
+        let mappings = symbol
+            .iter()
+            .filter(|(code, _)| unicodes.contains(code))
+            .filter_map(|(&code, &g)| Some((code, map.new_id(g)?)))
+            .collect();
+        add(
+            3,
+            0,
+            CmapSubtable::Mapping {
+                format: 4,
+                language: 0,
+                mappings,
+            },
+        );
+    }
+
+    let selectors: Vec<VariationSelector> = cmap
+        .variation_selectors()
+        .unwrap_or_default()
+        .iter()
+        .map(|selector| {
+            let mut default_uvs: Vec<(u32, u8)> = Vec::new();
+            for &(start, count) in &selector.default_uvs {
+                for cp in start..=start + count as u32 {
+                    if !mappings.contains_key(&cp) {
+                        continue;
+                    }
+                    match default_uvs.last_mut() {
+                        Some((first, additional))
+                            if *first + *additional as u32 + 1 == cp && *additional < 255 =>
+                        {
+                            *additional += 1
+                        }
+                        _ => default_uvs.push((cp, 0)),
+                    }
+                }
+            }
+            VariationSelector {
+                var_selector: selector.var_selector,
+                default_uvs,
+                non_default_uvs: selector
+                    .non_default_uvs
+                    .iter()
+                    .filter(|(cp, _)| unicodes.contains(cp))
+                    .filter_map(|(&cp, &g)| Some((cp, map.new_id(g)?)))
+                    .collect(),
+            }
+        })
+        .filter(|s| !s.default_uvs.is_empty() || !s.non_default_uvs.is_empty())
+        .collect();
+    if !selectors.is_empty() {
+        add(0, 5, CmapSubtable::Variations(selectors));
+    }
+    CmapTable {
+        version: 0,
+        records,
+    }
+}
+
+/// Picks the metrics of the kept glyphs and drops repeated trailing advances.
+fn subset_metrics(metrics: &HmtxTable, map: &GlyphMap) -> HmtxTable {
+    let all: Vec<LongHorMetric> = map
+        .old_ids()
+        .iter()
+        .map(|old| {
+            old.and_then(|g| metrics.metric(g))
+                .unwrap_or(LongHorMetric {
+                    advance_width: 0,
+                    lsb: 0,
+                })
+        })
+        .collect();
+    let last_advance = all.last().map_or(0, |m| m.advance_width);
+    let long_count = all
+        .iter()
+        .rposition(|m| m.advance_width != last_advance)
+        .map_or(1, |i| i + 2)
+        .min(all.len());
+    HmtxTable {
+        h_metrics: all[..long_count].to_vec(),
+        left_side_bearings: all[long_count..].iter().map(|m| m.lsb).collect(),
+    }
+}
+
+fn subset_glyf(glyf: &GlyfTable, map: &GlyphMap, drop_hinting: bool) -> GlyfTable {
+    let glyphs = map
+        .old_ids()
+        .iter()
+        .map(|old| {
+            let mut glyph = old.and_then(|g| glyf.glyph(g)).cloned().unwrap_or_default();
+            match &mut glyph {
+                Glyph::Simple(simple) if drop_hinting => simple.instructions.clear(),
+                Glyph::Composite(composite) => {
+                    for component in &mut composite.components {
+                        component.glyph_index = map.new_id(component.glyph_index).unwrap_or(0);
+                    }
+                    if drop_hinting {
+                        composite.instructions.clear();
+                        if let Some(last) = composite.components.last_mut() {
+                            last.flags &= !WE_HAVE_INSTRUCTIONS;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            glyph
+        })
+        .collect();
+    GlyfTable { glyphs }
+}
+
+/// Subsets a font.
+///
+/// # Arguments
+/// * `snft` - The table directory of the font.
+/// * `source` - The complete source font bytes the directory refers to.
+/// * `options` - The code points and glyphs to keep and how to rewrite them.
+///
+/// Glyph 0 (`.notdef`) is always kept. The result is a complete SNFT font.
+///
+/// # Errors
+/// * Returns `Error` if a required table is missing or cannot be parsed, or if
+///   the font has `CFF2` outlines.
+/// * Returns `IoError::InvalidTag` naming the first of the
+///   `UNSUPPORTED_TABLES` in the font, unless `drop_unsupported_tables` is set.
+pub fn subset_font(
+    snft: &SnftTable,
+    source: &[u8],
+    options: &SubsetOptions,
+) -> Result<Vec<u8>, Error> {
+    if !options.drop_unsupported_tables
+        && let Some(entry) = snft
+            .all_tables()
+            .iter()
+            .find(|entry| UNSUPPORTED_TABLES.contains(&entry.tag))
+    {
+        return Err(Error::Io(IoError::InvalidTag(entry.tag)));
+    }
+    let drop_variations = snft.all_tables().iter().any(|entry| {
+        VARIATION_TABLES.contains(&entry.tag) && UNSUPPORTED_TABLES.contains(&entry.tag)
+    });
+    let font = SourceFont::read(snft, source)?;
+    let glyphs = font.glyph_closure(options)?;
+    let map = GlyphMap::new(&glyphs, options.retain_gids);
+    let table = |tag: &[u8; 4]| snft.table_stream(tag, source);
+
+    let mut tables: Vec<([u8; 4], Vec<u8>)> = Vec::new();
+    let mut long_loca = None;
+    if let Some(glyf) = &font.glyf {
+        let subset = subset_glyf(glyf, &map, options.drop_hinting);
+        let mut writer = FontDataWriter::new();
+        let loca = write_glyf(&mut writer, &subset);
+        tables.push((*b"glyf", writer.into_bytes()?));
+        let long = !loca.fits_short_format();
+        tables.push((*b"loca", serialize(|w| write_loca(w, &loca, long))?));
+        long_loca = Some(long);
+    }
+    let mut first_last_char = None;
+
+    for entry in snft.all_tables() {
+        let tag = entry.tag;
+        if tag == SIGNATURE_TABLE
+            || UNSUPPORTED_TABLES.contains(&tag)
+            || (drop_variations && VARIATION_TABLES.contains(&tag))
+            || (options.drop_hinting && HINTING_TABLES.contains(&tag))
+        {
+            continue;
+        }
+        let data = match &tag {
+            b"glyf" | b"loca" => continue,
+            b"head" => {
+                let mut head = read_head(&mut table(b"head")?)?;
+                if let Some(long) = long_loca {
+                    head.index_to_loc_format = long as i16;
+                }
+                serialize(|w| {
+                    write_head(w, &head);
+                    Ok(())
+                })?
+            }
+            b"maxp" => {
+                let mut maxp = read_maxp(&mut table(b"maxp")?)?;
+                maxp.num_glyphs = map.num_glyphs();
+                if let (true, Some(truetype)) = (options.drop_hinting, &mut maxp.truetype) {
+                    truetype.max_zones = 1;
+                    truetype.max_twilight_points = 0;
+                    truetype.max_storage = 0;
+                    truetype.max_function_defs = 0;
+                    truetype.max_instruction_defs = 0;
+                    truetype.max_stack_elements = 0;
+                    truetype.max_size_of_instructions = 0;
+                }
+                serialize(|w| {
+                    write_maxp(w, &maxp);
+                    Ok(())
+                })?
+            }
+            b"hhea" | b"hmtx" => {
+                let mut hhea = read_hhea(&mut table(b"hhea")?)?;
+                let hmtx = read_hmtx(
+                    &mut table(b"hmtx")?,
+                    hhea.number_of_h_metrics,
+                    font.num_glyphs,
+                )?;
+                let hmtx = subset_metrics(&hmtx, &map);
+                if tag == *b"hhea" {
+                    hhea.number_of_h_metrics = hmtx.h_metrics.len() as u16;
+                    serialize(|w| {
+                        write_hhea(w, &hhea);
+                        Ok(())
+                    })?
+                } else {
+                    serialize(|w| {
+                        write_hmtx(w, &hmtx);
+                        Ok(())
+                    })?
+                }
+            }
+            b"vhea" | b"vmtx" => {
+                // `vhea`/`vmtx` share the layout of `hhea`/`hmtx`; the long
+                // metric count is the last field of the header.
+                let mut vhea = table(b"vhea")?.remaining_bytes().to_vec();
+                let count = table(b"vhea")?.read_at_u16(34)?;
+                let vmtx = read_hmtx(&mut table(b"vmtx")?, count, font.num_glyphs)?;
+                let vmtx = subset_metrics(&vmtx, &map);
+                if tag == *b"vhea" {
+                    vhea[34..36].copy_from_slice(&(vmtx.h_metrics.len() as u16).to_be_bytes());
+                    vhea
+                } else {
+                    serialize(|w| {
+                        write_hmtx(w, &vmtx);
+                        Ok(())
+                    })?
+                }
+            }
+            b"CFF " => {
+                let cff = font.cff.as_ref().ok_or(Error::InvalidFormat)?;
+                let subset = cff::subset_cff(cff, &map, options.drop_hinting)?;
+                serialize(|w| write_cff(w, &subset))?
+            }
+            b"cmap" => {
+                let cmap = subset_cmap(&font.cmap, &map, &options.unicodes);
+                first_last_char = cmap.unicode_mappings().and_then(|m| {
+                    let first = *m.keys().next()?;
+                    let last = *m.keys().next_back()?;
+                    Some((first.min(0xFFFF) as u16, last.min(0xFFFF) as u16))
+                });
+                serialize(|w| write_cmap(w, &cmap))?
+            }
+            b"post" => {
+                let mut post = read_post(&mut table(b"post")?)?;
+                if let Some(names) = &post.glyph_names {
+                    let names = map
+                        .old_ids()
+                        .iter()
+                        .map(|old| old.and_then(|g| names.get(g as usize).cloned()))
+                        .map(|name| name.unwrap_or_else(|| ".notdef".to_string()))
+                        .collect();
+                    post.glyph_names = Some(names);
+                    serialize(|w| write_post(w, &post))?
+                } else {
+                    table(b"post")?.remaining_bytes().to_vec()
+                }
+            }
+            b"name" => match &options.name_ids {
+                Some(name_ids) => {
+                    let mut name = read_name(&mut table(b"name")?)?;
+                    name.records.retain(|r| name_ids.contains(&r.name_id));
+                    serialize(|w| write_name(w, &name))?
+                }
+                None => table(b"name")?.remaining_bytes().to_vec(),
+            },
+            b"GSUB" => {
+                let gsub = font.gsub.as_ref().ok_or(Error::InvalidFormat)?;
+                serialize(|w| write_gsub(w, &layout::subset_gsub(gsub, &map)))?
+            }
+            b"GPOS" => {
+                let gpos = read_gpos(&mut table(b"GPOS")?)?;
+                serialize(|w| write_gpos(w, &layout::subset_gpos(&gpos, &map)))?
+            }
+            b"GDEF" => {
+                let gdef = read_gdef(&mut table(b"GDEF")?)?;
+                serialize(|w| write_gdef(w, &layout::subset_gdef(&gdef, &map)))?
+            }
+            _ => table(&tag)?.remaining_bytes().to_vec(),
+        };
+        tables.push((tag, data));
+    }
+
+    // usFirstCharIndex and usLastCharIndex follow the new cmap.
+    if let Some((_, os2)) = tables.iter_mut().find(|(tag, _)| tag == b"OS/2")
+        && os2.len() >= 68
+    {
+        let (first, last) = first_last_char.unwrap_or((0xFFFF, 0));
+        os2[64..66].copy_from_slice(&first.to_be_bytes());
+        os2[66..68].copy_from_slice(&last.to_be_bytes());
+    }
+
+    let mut writer = FontDataWriter::new();
+    write_snft(&mut writer, snft.header.version, &tables)?;
+    writer.into_bytes()
+}
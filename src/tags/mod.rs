@@ -0,0 +1,9 @@
+//! Aurora Font Library
+//!
+//! Author: Colton McGraw <github.com/ColtMcG1>
+//! License: Apache-2.0
+//! Date: January 2026
+//!
+//! OpenType tag enumerations.
+
+pub mod script;
@@ -11,6 +11,7 @@
 //! This enumeration is crucial for font rendering engines and text layout systems to correctly interpret and display text in different scripts.
 
 /// Enumeration of script tags used in font files.
+#[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ScriptTag {
     Adlam,
@@ -201,4 +202,216 @@ pub enum ScriptTag {
     Yezidi,
     Yi,
     Zanabazar_Square,
-}
\ No newline at end of file
+}
+
+/// OpenType tag of every script. Hiragana and Katakana share `kana`.
+#[allow(deprecated)]
+const SCRIPT_TAGS: [(ScriptTag, [u8; 4]); 187] = [
+    (ScriptTag::Adlam, *b"adlm"),
+    (ScriptTag::Ahom, *b"ahom"),
+    (ScriptTag::Anatolian_Hieroglyphs, *b"hluw"),
+    (ScriptTag::Arabic, *b"arab"),
+    (ScriptTag::Armenian, *b"armn"),
+    (ScriptTag::Avestan, *b"avst"),
+    (ScriptTag::Balinese, *b"bali"),
+    (ScriptTag::Bamum, *b"bamu"),
+    (ScriptTag::Bassa_Vah, *b"bass"),
+    (ScriptTag::Batak, *b"batk"),
+    (ScriptTag::Bengla, *b"beng"),
+    (ScriptTag::Bengla_2, *b"bng2"),
+    (ScriptTag::Beria_Erfe, *b"berf"),
+    (ScriptTag::Bhiksuski, *b"bhks"),
+    (ScriptTag::Bopomofo, *b"bopo"),
+    (ScriptTag::Brahmi, *b"brah"),
+    (ScriptTag::Braille, *b"brai"),
+    (ScriptTag::Buginese, *b"bugi"),
+    (ScriptTag::Buhid, *b"buhd"),
+    (ScriptTag::Byzantine_Musical, *b"byzm"),
+    (ScriptTag::Canadian_Syllabics, *b"cans"),
+    (ScriptTag::Carian, *b"cari"),
+    (ScriptTag::Caucasian_Albanian, *b"aghb"),
+    (ScriptTag::Chakma, *b"cakm"),
+    (ScriptTag::Cham, *b"cham"),
+    (ScriptTag::Cherokee, *b"cher"),
+    (ScriptTag::Chorasmian, *b"chrs"),
+    (ScriptTag::CJK_Ideographic, *b"hani"),
+    (ScriptTag::Coptic, *b"copt"),
+    (ScriptTag::Cypriot_Syllabary, *b"cprt"),
+    (ScriptTag::Crypo_Minoan, *b"cpmn"),
+    (ScriptTag::Cyrillic, *b"cyrl"),
+    (ScriptTag::Default, *b"DFLT"),
+    (ScriptTag::Deseret, *b"dsrt"),
+    (ScriptTag::Devanagari, *b"deva"),
+    (ScriptTag::Devanagari_2, *b"dev2"),
+    (ScriptTag::Dives_Akuru, *b"diak"),
+    (ScriptTag::Dogra, *b"dogr"),
+    (ScriptTag::Duployan, *b"dupl"),
+    (ScriptTag::Egyptian_Hieroglyphs, *b"egyp"),
+    (ScriptTag::Elbasan, *b"elba"),
+    (ScriptTag::Elymaic, *b"elym"),
+    (ScriptTag::Ethiopic, *b"ethi"),
+    (ScriptTag::Garay, *b"gara"),
+    (ScriptTag::Georgian, *b"geor"),
+    (ScriptTag::Glagolitic, *b"glag"),
+    (ScriptTag::Gothic, *b"goth"),
+    (ScriptTag::Grantha, *b"gran"),
+    (ScriptTag::Greek, *b"grek"),
+    (ScriptTag::Gujarati, *b"gujr"),
+    (ScriptTag::Gunjala_v2, *b"gjr2"),
+    (ScriptTag::Gunjala_Gondi, *b"gong"),
+    (ScriptTag::Gurmukhi, *b"guru"),
+    (ScriptTag::Gurmukhi_2, *b"gur2"),
+    (ScriptTag::Gurung_Khema, *b"gukh"),
+    (ScriptTag::Hangul, *b"hang"),
+    (ScriptTag::Hangul_Jamo, *b"jamo"),
+    (ScriptTag::Hanifi_Rohingya, *b"rohg"),
+    (ScriptTag::Hanunoo, *b"hano"),
+    (ScriptTag::Hatran, *b"hatr"),
+    (ScriptTag::Hebrew, *b"hebr"),
+    (ScriptTag::Hiragana, *b"kana"),
+    (ScriptTag::Imperial_Aramaic, *b"armi"),
+    (ScriptTag::Inscriptional_Pahlavi, *b"phli"),
+    (ScriptTag::Inscriptional_Parthian, *b"prti"),
+    (ScriptTag::Javanese, *b"java"),
+    (ScriptTag::Kaithi, *b"kthi"),
+    (ScriptTag::Kannada, *b"knda"),
+    (ScriptTag::Kannada_2, *b"knd2"),
+    (ScriptTag::Katakana, *b"kana"),
+    (ScriptTag::Kawi, *b"kawi"),
+    (ScriptTag::Kayah_Li, *b"kali"),
+    (ScriptTag::Kharoshthi, *b"khar"),
+    (ScriptTag::Khitan_Small_Script, *b"kits"),
+    (ScriptTag::Khmer, *b"khmr"),
+    (ScriptTag::Khojki, *b"khoj"),
+    (ScriptTag::Khudawadi, *b"sind"),
+    (ScriptTag::Kirat_Rai, *b"krai"),
+    (ScriptTag::Lao, *b"lao "),
+    (ScriptTag::Latin, *b"latn"),
+    (ScriptTag::Lepcha, *b"lepc"),
+    (ScriptTag::Limbu, *b"limb"),
+    (ScriptTag::Linear_A, *b"lina"),
+    (ScriptTag::Linear_B, *b"linb"),
+    (ScriptTag::Lisu, *b"lisu"),
+    (ScriptTag::Lycian, *b"lyci"),
+    (ScriptTag::Lydian, *b"lydi"),
+    (ScriptTag::Mahajani, *b"mahj"),
+    (ScriptTag::Makasar, *b"maka"),
+    (ScriptTag::Malayalam, *b"mlym"),
+    (ScriptTag::Malayalam_2, *b"mlm2"),
+    (ScriptTag::Mandaic, *b"mand"),
+    (ScriptTag::Manichaean, *b"mani"),
+    (ScriptTag::Marchen, *b"marc"),
+    (ScriptTag::Masaram_Gondi, *b"gonm"),
+    (ScriptTag::Mathimatical_Text_Layout, *b"math"),
+    (ScriptTag::Medefaidrin, *b"medf"),
+    (ScriptTag::Meetei_Mayek, *b"mtei"),
+    (ScriptTag::Mende_Kikakui, *b"mend"),
+    (ScriptTag::Meroitic_Cursive, *b"merc"),
+    (ScriptTag::Meroitic_Hieroglyphs, *b"mero"),
+    (ScriptTag::Miao, *b"plrd"),
+    (ScriptTag::Modi, *b"modi"),
+    (ScriptTag::Mongolian, *b"mong"),
+    (ScriptTag::Mro, *b"mroo"),
+    (ScriptTag::Multani, *b"mult"),
+    (ScriptTag::Musical_Symbols, *b"musc"),
+    (ScriptTag::Myanmar, *b"mymr"),
+    (ScriptTag::Myanmar_2, *b"mym2"),
+    (ScriptTag::Nabataean, *b"nbat"),
+    (ScriptTag::Nag_Mundari, *b"nagm"),
+    (ScriptTag::Nandinagari, *b"nand"),
+    (ScriptTag::Newa, *b"newa"),
+    (ScriptTag::New_Tai_Lue, *b"talu"),
+    (ScriptTag::Nko, *b"nko "),
+    (ScriptTag::Nushu, *b"nshu"),
+    (ScriptTag::Nyiakeng_Puachue_Hmong, *b"hmnp"),
+    (ScriptTag::Odia, *b"orya"),
+    (ScriptTag::Odia_2, *b"ory2"),
+    (ScriptTag::Ogham, *b"ogam"),
+    (ScriptTag::Ol_Chiki, *b"olck"),
+    (ScriptTag::Ol_Onal, *b"onao"),
+    (ScriptTag::Old_Italic, *b"ital"),
+    (ScriptTag::Old_Hungarian, *b"hung"),
+    (ScriptTag::Old_North_Arabian, *b"narb"),
+    (ScriptTag::Old_Permic, *b"perm"),
+    (ScriptTag::Old_Persian_Cuneiform, *b"xpeo"),
+    (ScriptTag::Old_Sogdian, *b"sogo"),
+    (ScriptTag::Old_South_Arabian, *b"sarb"),
+    (ScriptTag::Old_Turkic, *b"orkh"),
+    (ScriptTag::Old_Uyghur, *b"ougr"),
+    (ScriptTag::Osage, *b"osge"),
+    (ScriptTag::Osmanya, *b"osma"),
+    (ScriptTag::Pahawh_Hmong, *b"hmng"),
+    (ScriptTag::Palmyrene, *b"palm"),
+    (ScriptTag::Pau_Cin_Hau, *b"pauc"),
+    (ScriptTag::Phags_Pa, *b"phag"),
+    (ScriptTag::Phoenician, *b"phnx"),
+    (ScriptTag::Psalter_Pahlavi, *b"phlp"),
+    (ScriptTag::Rejang, *b"rjng"),
+    (ScriptTag::Runic, *b"runr"),
+    (ScriptTag::Samaritan, *b"samr"),
+    (ScriptTag::Saurashtra, *b"saur"),
+    (ScriptTag::Sharada, *b"shrd"),
+    (ScriptTag::Shavian, *b"shaw"),
+    (ScriptTag::Siddham, *b"sidd"),
+    (ScriptTag::Sidetic, *b"sidt"),
+    (ScriptTag::Sign_Writing, *b"sgnw"),
+    (ScriptTag::Sinhala, *b"sinh"),
+    (ScriptTag::Sogdian, *b"sogd"),
+    (ScriptTag::Sora_Sompeng, *b"sora"),
+    (ScriptTag::Soyombo, *b"soyo"),
+    (ScriptTag::Sumero_Akkadian_Cuneiform, *b"xsux"),
+    (ScriptTag::Sundanese, *b"sund"),
+    (ScriptTag::Suduwar, *b"sunu"),
+    (ScriptTag::Syloti_Nagri, *b"sylo"),
+    (ScriptTag::Syriac, *b"syrc"),
+    (ScriptTag::Tagalog, *b"tglg"),
+    (ScriptTag::Tagbanwa, *b"tagb"),
+    (ScriptTag::Tai_Le, *b"tale"),
+    (ScriptTag::Tai_Tham, *b"lana"),
+    (ScriptTag::Tai_Viet, *b"tavt"),
+    (ScriptTag::Tai_Yo, *b"tayo"),
+    (ScriptTag::Takri, *b"takr"),
+    (ScriptTag::Tamil, *b"taml"),
+    (ScriptTag::Tamil_2, *b"tml2"),
+    (ScriptTag::Tangsa, *b"tnsa"),
+    (ScriptTag::Tangut, *b"tang"),
+    (ScriptTag::Telugu, *b"telu"),
+    (ScriptTag::Telugu_2, *b"tel2"),
+    (ScriptTag::Thaana, *b"thaa"),
+    (ScriptTag::Thai, *b"thai"),
+    (ScriptTag::Tibetan, *b"tibt"),
+    (ScriptTag::Tifinagh, *b"tfng"),
+    (ScriptTag::Tirhuta, *b"tirh"),
+    (ScriptTag::Todhri, *b"todr"),
+    (ScriptTag::Tolong_Siki, *b"tols"),
+    (ScriptTag::Toto, *b"toto"),
+    (ScriptTag::Tulu_Tigalari, *b"tutg"),
+    (ScriptTag::Ugaritic_Cuneiform, *b"ugar"),
+    (ScriptTag::Vai, *b"vai "),
+    (ScriptTag::Vithkuqi, *b"vith"),
+    (ScriptTag::Wancho, *b"wcho"),
+    (ScriptTag::Warang_Citi, *b"wara"),
+    (ScriptTag::Yezidi, *b"yezi"),
+    (ScriptTag::Yi, *b"yi  "),
+    (ScriptTag::Zanabazar_Square, *b"zanb"),
+];
+
+impl ScriptTag {
+    /// Returns the OpenType script tag.
+    pub fn tag(self) -> [u8; 4] {
+        SCRIPT_TAGS
+            .iter()
+            .find(|(script, _)| *script == self)
+            .map(|(_, tag)| *tag)
+            .unwrap_or(*b"DFLT")
+    }
+
+    /// Returns the script with an OpenType script tag; `kana` gives
+    /// `Hiragana`.
+    pub fn from_tag(tag: [u8; 4]) -> Option<ScriptTag> {
+        SCRIPT_TAGS
+            .iter()
+            .find(|(_, script_tag)| *script_tag == tag)
+            .map(|(script, _)| *script)
+    }
+}
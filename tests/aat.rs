@@ -0,0 +1,944 @@
+//! Tests for the AAT `morx`, `kerx`, `feat`, `trak` and `ankr` tables.
+
+/// Builds a state table from its rows and entries; glyphs are mapped to the
+/// given classes.
+fn machine<T>(
+    classes: &[(u16, u16)],
+    states: Vec<Vec<u16>>,
+    entries: Vec<(u16, u16, T)>,
+) -> aurora_font::components::aat::StateTable<T> {
+    use aurora_font::components::aat::{AatLookup, StateEntry, StateTable};
+
+    StateTable {
+        class_count: states[0].len() as u16,
+        classes: AatLookup::new(classes.iter().copied()),
+        states,
+        entries: entries
+            .into_iter()
+            .map(|(new_state, flags, data)| StateEntry {
+                new_state,
+                flags,
+                data,
+            })
+            .collect(),
+    }
+}
+
+/// Wraps subtables in a single chain whose feature `(1, 1)` turns them off.
+fn chain(
+    subtables: Vec<aurora_font::components::morx::MorxData>,
+) -> aurora_font::components::morx::MorxTable {
+    use aurora_font::components::morx::{MorxChain, MorxFeature, MorxSubtable, MorxTable};
+
+    MorxTable {
+        version: 2,
+        chains: vec![MorxChain {
+            default_flags: 1,
+            features: vec![MorxFeature {
+                feature_type: 1,
+                feature_setting: 1,
+                enable_flags: 0,
+                disable_flags: !1,
+            }],
+            subtables: subtables
+                .into_iter()
+                .map(|data| MorxSubtable {
+                    vertical: false,
+                    descending: false,
+                    both_orientations: false,
+                    logical_order: false,
+                    sub_feature_flags: 1,
+                    data,
+                })
+                .collect(),
+        }],
+    }
+}
+
+/// Lays out a format 6 (single table) lookup ended by the 0xFFFF glyph.
+fn single_lookup(pairs: &[(u16, u16)]) -> Vec<u8> {
+    use aurora_font::io::writer::FontDataWriter;
+
+    let units = pairs.len() as u16 + 1;
+    let selector = 15 - units.leading_zeros() as u16;
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(6); // format
+    writer.write_u16(4); // unitSize
+    writer.write_u16(units); // nUnits
+    writer.write_u16(4 << selector); // searchRange
+    writer.write_u16(selector); // entrySelector
+    writer.write_u16(4 * units - (4 << selector)); // rangeShift
+    for &(glyph, value) in pairs {
+        writer.write_u16(glyph);
+        writer.write_u16(value);
+    }
+    writer.write_u16(0xFFFF);
+    writer.write_u16(0);
+    writer.into_bytes().unwrap()
+}
+
+/// Lays out a version 2 `morx` table with one chain like the one `chain`
+/// builds; each subtable is a coverage type and its body.
+fn morx_bytes(subtables: &[(u8, Vec<u8>)]) -> Vec<u8> {
+    use aurora_font::io::writer::FontDataWriter;
+
+    let lengths: usize = subtables.iter().map(|(_, body)| 12 + body.len()).sum();
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(2); // version
+    writer.write_u16(0); // unused
+    writer.write_u32(1); // nChains
+    writer.write_u32(1); // 8: defaultFlags
+    writer.write_u32(16 + 12 + lengths as u32); // chainLength
+    writer.write_u32(1); // nFeatureEntries
+    writer.write_u32(subtables.len() as u32); // nSubtables
+    writer.write_u16(1); // 24: featureType
+    writer.write_u16(1); // featureSetting
+    writer.write_u32(0); // enableFlags
+    writer.write_u32(!1); // disableFlags
+    for (subtable_type, body) in subtables {
+        writer.write_u32(12 + body.len() as u32); // length
+        writer.write_u32(*subtable_type as u32); // coverage
+        writer.write_u32(1); // subFeatureFlags
+        writer.write_bytes(body);
+    }
+    writer.into_bytes().unwrap()
+}
+
+/// Lays out a `kerx` table; each subtable is a coverage and its data after
+/// the subtable header.
+fn kerx_bytes(version: u16, subtables: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    use aurora_font::io::writer::FontDataWriter;
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(version);
+    writer.write_u16(0); // padding
+    writer.write_u32(subtables.len() as u32); // nTables
+    for (coverage, data) in subtables {
+        writer.write_u32(12 + data.len() as u32); // length
+        writer.write_u32(*coverage);
+        writer.write_u32(0); // tupleCount
+        writer.write_bytes(data);
+    }
+    writer.into_bytes().unwrap()
+}
+
+#[test]
+fn test_lookup_formats() {
+    use aurora_font::components::aat::{AatLookup, read_lookup};
+    use aurora_font::io::stream::FontDataStream;
+
+    // Format 2: a run of equal values and two single glyphs.
+    #[rustfmt::skip]
+    let data = [
+        0, 2, 0, 6, 0, 4, // format, unitSize, nUnits
+        0, 24, 0, 2, 0, 0, // searchRange, entrySelector, rangeShift
+        0, 20, 0, 10, 0, 4, // lastGlyph, firstGlyph, value
+        0, 30, 0, 30, 0, 5,
+        0, 31, 0, 31, 0, 6,
+        0xFF, 0xFF, 0xFF, 0xFF, 0, 0,
+    ];
+    let lookup = read_lookup(&mut FontDataStream::new(&data), 100).unwrap();
+    let runs = AatLookup::new((10..=20).map(|glyph| (glyph, 4)).chain([(30, 5), (31, 6)]));
+    assert_eq!(lookup, runs);
+
+    // Format 4: glyphs 5 and 6 take their values from the array at 24.
+    #[rustfmt::skip]
+    let data = [
+        0, 4, 0, 6, 0, 2, // format, unitSize, nUnits
+        0, 12, 0, 1, 0, 0, // searchRange, entrySelector, rangeShift
+        0, 6, 0, 5, 0, 24, // lastGlyph, firstGlyph, offset
+        0xFF, 0xFF, 0xFF, 0xFF, 0, 0,
+        0, 7, 0, 8, // 24: values
+    ];
+    let lookup = read_lookup(&mut FontDataStream::new(&data), 100).unwrap();
+    assert_eq!(lookup, AatLookup::new([(5, 7), (6, 8)]));
+
+    // Format 6: scattered single glyphs; a lone terminator is empty.
+    let sparse = [(3, 9), (70, 1), (99, 2)];
+    let data = single_lookup(&sparse);
+    let lookup = read_lookup(&mut FontDataStream::new(&data), 100).unwrap();
+    assert_eq!(lookup, AatLookup::new(sparse));
+    let data = single_lookup(&[]);
+    let lookup = read_lookup(&mut FontDataStream::new(&data), 100).unwrap();
+    assert_eq!(lookup, AatLookup::default());
+
+    // Format 8: a trimmed array of values for glyphs 5 to 7.
+    let data = [0, 8, 0, 5, 0, 3, 0, 1, 0, 2, 0, 3];
+    let lookup = read_lookup(&mut FontDataStream::new(&data), 100).unwrap();
+    assert_eq!(lookup, AatLookup::new([(5, 1), (6, 2), (7, 3)]));
+    assert_eq!(lookup.get(8), None);
+}
+
+#[test]
+fn test_morx_ligature() {
+    use aurora_font::components::morx::{
+        LIGATURE_ACTION_LAST, LIGATURE_ACTION_OFFSET, LIGATURE_PERFORM_ACTION,
+        LIGATURE_SET_COMPONENT, LigatureSubtable, MorxData, read_morx,
+    };
+    use aurora_font::io::stream::FontDataStream;
+    use aurora_font::io::writer::FontDataWriter;
+
+    // "f" (10) followed by "i" (11) forms "fi" (20).
+    let offset = (-10i32) as u32 & LIGATURE_ACTION_OFFSET;
+    let expected = chain(vec![MorxData::Ligature(LigatureSubtable {
+        machine: machine(
+            &[(10, 4), (11, 5)],
+            vec![vec![0, 0, 0, 0, 1, 0], vec![0, 0, 0, 0, 1, 2]],
+            vec![
+                (0, 0, 0),
+                (1, LIGATURE_SET_COMPONENT, 0),
+                (0, LIGATURE_SET_COMPONENT | LIGATURE_PERFORM_ACTION, 0),
+            ],
+        ),
+        actions: vec![offset, LIGATURE_ACTION_LAST | offset],
+        components: vec![0, 1],
+        ligatures: vec![0, 20],
+    })]);
+
+    let mut body = FontDataWriter::new();
+    body.write_u32(6); // nClasses
+    body.write_u32(28); // classTable
+    body.write_u32(52); // stateArray
+    body.write_u32(76); // entryTable
+    body.write_u32(96); // ligActionOffset
+    body.write_u32(104); // componentOffset
+    body.write_u32(108); // ligatureOffset
+    body.write_bytes(&single_lookup(&[(10, 4), (11, 5)])); // 28
+    // 52: state array
+    for state in [[0, 0, 0, 0, 1, 0], [0, 0, 0, 0, 1, 2]] {
+        for entry in state {
+            body.write_u16(entry);
+        }
+    }
+    for (new_state, flags) in [
+        (0, 0),
+        (1, LIGATURE_SET_COMPONENT),
+        (0, LIGATURE_SET_COMPONENT | LIGATURE_PERFORM_ACTION),
+    ] {
+        body.write_u16(new_state); // 76: newState, flags, ligActionIndex
+        body.write_u16(flags);
+        body.write_u16(0);
+    }
+    body.write_zeros(2);
+    body.write_u32(offset); // 96: ligature actions
+    body.write_u32(LIGATURE_ACTION_LAST | offset);
+    body.write_u16(0); // 104: components
+    body.write_u16(1);
+    body.write_u16(0); // 108: ligatures
+    body.write_u16(20);
+    let bytes = morx_bytes(&[(2, body.into_bytes().unwrap())]);
+    let morx = read_morx(&mut FontDataStream::new(&bytes), 100).unwrap();
+    assert_eq!(morx, expected);
+
+    let mut glyphs = vec![5, 10, 11, 6, 10, 10, 11];
+    morx.apply(&mut glyphs, &[]);
+    assert_eq!(glyphs, vec![5, 20, 6, 10, 20]);
+
+    // The feature setting turns the chain's subtables off.
+    let mut glyphs = vec![10, 11];
+    morx.apply(&mut glyphs, &[(1, 1)]);
+    assert_eq!(glyphs, vec![10, 11]);
+}
+
+#[test]
+fn test_morx_rearrangement_and_contextual() {
+    use aurora_font::components::aat::AatLookup;
+    use aurora_font::components::morx::{
+        ContextualEntry, ContextualSubtable, ENTRY_SET_MARK, MorxData, REARRANGEMENT_MARK_FIRST,
+        REARRANGEMENT_MARK_LAST, read_morx,
+    };
+    use aurora_font::io::stream::FontDataStream;
+    use aurora_font::io::writer::FontDataWriter;
+
+    // Swaps the ends of a range running from glyph 30 to glyph 31 (AxD =>
+    // DxA).
+    let rearrangement = MorxData::Rearrangement(machine(
+        &[(30, 4), (31, 5)],
+        vec![vec![0, 0, 0, 0, 1, 0], vec![0, 2, 2, 2, 2, 3]],
+        vec![
+            (0, 0, ()),
+            (1, REARRANGEMENT_MARK_FIRST, ()),
+            (1, 0, ()),
+            (0, REARRANGEMENT_MARK_LAST | 3, ()),
+        ],
+    ));
+    // Replaces glyph 40 by 41 when glyph 42 follows it.
+    let none = ContextualEntry {
+        mark_index: 0xFFFF,
+        current_index: 0xFFFF,
+    };
+    let contextual = MorxData::Contextual(ContextualSubtable {
+        machine: machine(
+            &[(40, 4), (42, 5)],
+            vec![vec![0, 0, 0, 0, 1, 0], vec![0, 0, 0, 0, 1, 2]],
+            vec![
+                (0, 0, none),
+                (1, ENTRY_SET_MARK, none),
+                (
+                    0,
+                    0,
+                    ContextualEntry {
+                        mark_index: 0,
+                        current_index: 0xFFFF,
+                    },
+                ),
+            ],
+        ),
+        substitutions: vec![AatLookup::new([(40, 41)])],
+    });
+
+    let mut first = FontDataWriter::new();
+    first.write_u32(6); // nClasses
+    first.write_u32(16); // classTable
+    first.write_u32(40); // stateArray
+    first.write_u32(64); // entryTable
+    first.write_bytes(&single_lookup(&[(30, 4), (31, 5)])); // 16
+    // 40: state array
+    for state in [[0, 0, 0, 0, 1, 0], [0, 2, 2, 2, 2, 3]] {
+        for entry in state {
+            first.write_u16(entry);
+        }
+    }
+    for (new_state, flags) in [
+        (0, 0),
+        (1, REARRANGEMENT_MARK_FIRST),
+        (1, 0),
+        (0, REARRANGEMENT_MARK_LAST | 3),
+    ] {
+        first.write_u16(new_state); // 64: newState, flags
+        first.write_u16(flags);
+    }
+
+    let mut second = FontDataWriter::new();
+    second.write_u32(6); // nClasses
+    second.write_u32(20); // classTable
+    second.write_u32(44); // stateArray
+    second.write_u32(68); // entryTable
+    second.write_u32(92); // substitutionTable
+    second.write_bytes(&single_lookup(&[(40, 4), (42, 5)])); // 20
+    // 44: state array
+    for state in [[0, 0, 0, 0, 1, 0], [0, 0, 0, 0, 1, 2]] {
+        for entry in state {
+            second.write_u16(entry);
+        }
+    }
+    for (new_state, flags, mark_index) in [(0, 0, 0xFFFF), (1, ENTRY_SET_MARK, 0xFFFF), (0, 0, 0)] {
+        second.write_u16(new_state); // 68: newState, flags, markIndex, currentIndex
+        second.write_u16(flags);
+        second.write_u16(mark_index);
+        second.write_u16(0xFFFF);
+    }
+    second.write_u32(4); // 92: offset of substitution lookup 0
+    second.write_bytes(&single_lookup(&[(40, 41)])); // 96
+
+    let bytes = morx_bytes(&[
+        (0, first.into_bytes().unwrap()),
+        (1, second.into_bytes().unwrap()),
+    ]);
+    let morx = read_morx(&mut FontDataStream::new(&bytes), 100).unwrap();
+    assert_eq!(morx, chain(vec![rearrangement, contextual]));
+
+    let mut glyphs = vec![7, 30, 1, 2, 31, 40, 42, 40, 8];
+    morx.apply(&mut glyphs, &[]);
+    assert_eq!(glyphs, vec![7, 31, 1, 2, 30, 41, 42, 40, 8]);
+}
+
+#[test]
+fn test_morx_noncontextual_and_insertion() {
+    use aurora_font::components::aat::AatLookup;
+    use aurora_font::components::morx::{InsertionEntry, InsertionSubtable, MorxData, read_morx};
+    use aurora_font::io::stream::FontDataStream;
+    use aurora_font::io::writer::FontDataWriter;
+
+    let noncontextual = MorxData::Noncontextual(AatLookup::new([(1, 2), (3, 4)]));
+    // Inserts glyphs 50 and 51 after every glyph 60.
+    let none = InsertionEntry {
+        current_insert_index: 0xFFFF,
+        marked_insert_index: 0xFFFF,
+    };
+    let insertion = MorxData::Insertion(InsertionSubtable {
+        machine: machine(
+            &[(60, 4)],
+            vec![vec![0, 0, 0, 0, 1]],
+            vec![
+                (0, 0, none),
+                (
+                    0,
+                    2 << 5,
+                    InsertionEntry {
+                        current_insert_index: 0,
+                        marked_insert_index: 0xFFFF,
+                    },
+                ),
+            ],
+        ),
+        glyphs: vec![50, 51],
+    });
+
+    let mut second = FontDataWriter::new();
+    second.write_u32(5); // nClasses
+    second.write_u32(20); // classTable
+    second.write_u32(40); // stateArray
+    second.write_u32(50); // entryTable
+    second.write_u32(68); // insertionAction
+    second.write_bytes(&single_lookup(&[(60, 4)])); // 20
+    // 40: state array
+    for entry in [0, 0, 0, 0, 1] {
+        second.write_u16(entry);
+    }
+    for (flags, current_insert_index) in [(0, 0xFFFF), (2 << 5, 0)] {
+        second.write_u16(0); // 50: newState, flags, currentInsertIndex, markedInsertIndex
+        second.write_u16(flags);
+        second.write_u16(current_insert_index);
+        second.write_u16(0xFFFF);
+    }
+    second.write_zeros(2);
+    second.write_u16(50); // 68: insertion glyphs
+    second.write_u16(51);
+
+    let bytes = morx_bytes(&[
+        (4, single_lookup(&[(1, 2), (3, 4)])),
+        (5, second.into_bytes().unwrap()),
+    ]);
+    let morx = read_morx(&mut FontDataStream::new(&bytes), 100).unwrap();
+    assert_eq!(morx, chain(vec![noncontextual, insertion]));
+
+    let mut glyphs = vec![1, 60, 3, 60];
+    morx.apply(&mut glyphs, &[]);
+    assert_eq!(glyphs, vec![2, 60, 50, 51, 4, 60, 50, 51]);
+}
+
+#[test]
+fn test_kerx_pairs_and_classes() {
+    use std::collections::BTreeMap;
+
+    use aurora_font::components::aat::AatLookup;
+    use aurora_font::components::kerx::{
+        KerxClassTable, KerxData, KerxSubtable, KerxTable, read_kerx,
+    };
+    use aurora_font::io::stream::FontDataStream;
+    use aurora_font::io::writer::FontDataWriter;
+
+    let subtable = |cross_stream: bool, data: KerxData| KerxSubtable {
+        vertical: false,
+        cross_stream,
+        variation: false,
+        tuple_count: 0,
+        data,
+    };
+    let expected = KerxTable {
+        version: 2,
+        subtables: vec![
+            subtable(false, KerxData::Pairs(BTreeMap::from([((1, 2), -80)]))),
+            subtable(
+                false,
+                KerxData::Classes(KerxClassTable {
+                    left_classes: AatLookup::new([(1, 1), (3, 2)]),
+                    right_classes: AatLookup::new([(2, 1)]),
+                    values: vec![vec![0, 0], vec![0, -20], vec![5, 15]],
+                }),
+            ),
+            subtable(
+                false,
+                KerxData::IndexedClasses(KerxClassTable {
+                    left_classes: AatLookup::new([(4, 1)]),
+                    right_classes: AatLookup::new([(4, 2)]),
+                    values: vec![vec![0, 0, 0], vec![0, 0, -40]],
+                }),
+            ),
+            subtable(true, KerxData::Pairs(BTreeMap::from([((2, 3), 50)]))),
+        ],
+    };
+
+    // Format 0 pairs, padded to a multiple of four bytes.
+    let pairs = |left: u16, right: u16, value: i16| {
+        let mut data = FontDataWriter::new();
+        data.write_u32(1); // nPairs
+        data.write_u32(6); // searchRange
+        data.write_u32(0); // entrySelector
+        data.write_u32(0); // rangeShift
+        data.write_u16(left);
+        data.write_u16(right);
+        data.write_i16(value);
+        data.write_zeros(2);
+        data.into_bytes().unwrap()
+    };
+
+    // Format 2: class values are byte offsets of rows and of columns.
+    let mut classes = FontDataWriter::new();
+    classes.write_u32(4); // 12: rowWidth
+    classes.write_u32(28); // leftClassTable
+    classes.write_u32(52); // rightClassTable
+    classes.write_u32(72); // kerningArray
+    classes.write_bytes(&single_lookup(&[(1, 4), (3, 8)])); // 28
+    classes.write_bytes(&single_lookup(&[(2, 2)])); // 52
+    for value in [0, 0, 0, -20, 5, 15] {
+        classes.write_i16(value); // 72
+    }
+
+    // Format 6: row classes are indices of the rows' first values.
+    let mut indexed = FontDataWriter::new();
+    indexed.write_u32(0); // 12: flags
+    indexed.write_u16(2); // rowCount
+    indexed.write_u16(3); // columnCount
+    indexed.write_u32(32); // rowIndexTable
+    indexed.write_u32(52); // columnIndexTable
+    indexed.write_u32(72); // kerningArray
+    indexed.write_bytes(&single_lookup(&[(4, 3)])); // 32
+    indexed.write_bytes(&single_lookup(&[(4, 2)])); // 52
+    for value in [0, 0, 0, 0, 0, -40] {
+        indexed.write_i16(value); // 72
+    }
+
+    let bytes = kerx_bytes(
+        2,
+        &[
+            (0, pairs(1, 2, -80)),
+            (2, classes.into_bytes().unwrap()),
+            (6, indexed.into_bytes().unwrap()),
+            (0x4000_0000, pairs(2, 3, 50)),
+        ],
+    );
+    let kerx = read_kerx(&mut FontDataStream::new(&bytes), 100).unwrap();
+    assert_eq!(kerx, expected);
+
+    assert_eq!(kerx.kerning(1, 2), -100);
+    assert_eq!(kerx.kerning(3, 2), 15);
+    assert_eq!(kerx.kerning(3, 9), 5);
+    assert_eq!(kerx.kerning(4, 4), -40);
+    assert_eq!(kerx.kerning(2, 3), 0);
+
+    let positions = kerx.apply(&[1, 2, 3], None);
+    assert_eq!(positions[0].x_advance, -100);
+    assert_eq!(positions[1].x_advance, 0);
+    assert_eq!(positions[2].y_offset, 50);
+}
+
+#[test]
+fn test_kerx_contextual_and_attachment() {
+    use std::collections::BTreeMap;
+
+    use aurora_font::components::ankr::AnkrTable;
+    use aurora_font::components::kerx::{
+        AttachmentActions, KERX_ENTRY_MARK, KERX_ENTRY_PUSH, KerxAttachmentSubtable,
+        KerxContextualSubtable, KerxData, KerxPosition, KerxSubtable, KerxTable, read_kerx,
+    };
+    use aurora_font::io::stream::FontDataStream;
+    use aurora_font::io::writer::FontDataWriter;
+
+    let subtable = |data: KerxData| KerxSubtable {
+        vertical: false,
+        cross_stream: false,
+        variation: false,
+        tuple_count: 0,
+        data,
+    };
+    // Glyph 80 is kerned by -50 when glyph 81 follows it.
+    let contextual = subtable(KerxData::Contextual(KerxContextualSubtable {
+        machine: machine(
+            &[(80, 4), (81, 5)],
+            vec![vec![0, 0, 0, 0, 1, 0], vec![0, 0, 0, 0, 1, 2]],
+            vec![(0, 0, 0xFFFF), (1, KERX_ENTRY_PUSH, 0xFFFF), (0, 0, 0)],
+        ),
+        values: vec![-50 | 1],
+    }));
+    // Glyph 71 is attached to the preceding glyph 70 by their first anchors.
+    let attachment = subtable(KerxData::Attachment(KerxAttachmentSubtable {
+        machine: machine(
+            &[(70, 4), (71, 5)],
+            vec![vec![0, 0, 0, 0, 1, 2]],
+            vec![(0, 0, 0xFFFF), (0, KERX_ENTRY_MARK, 0xFFFF), (0, 0, 0)],
+        ),
+        actions: AttachmentActions::Anchors(vec![(0, 0)]),
+    }));
+
+    // Offsets are measured from the state table header after the subtable
+    // header.
+    let mut first = FontDataWriter::new();
+    first.write_u32(6); // nClasses
+    first.write_u32(20); // classTable
+    first.write_u32(44); // stateArray
+    first.write_u32(68); // entryTable
+    first.write_u32(88); // valueTable
+    first.write_bytes(&single_lookup(&[(80, 4), (81, 5)])); // 20
+    // 44: state array
+    for state in [[0, 0, 0, 0, 1, 0], [0, 0, 0, 0, 1, 2]] {
+        for entry in state {
+            first.write_u16(entry);
+        }
+    }
+    for (new_state, flags, value_index) in [(0, 0, 0xFFFF), (1, KERX_ENTRY_PUSH, 0xFFFF), (0, 0, 0)]
+    {
+        first.write_u16(new_state); // 68: newState, flags, valueIndex
+        first.write_u16(flags);
+        first.write_u16(value_index);
+    }
+    first.write_zeros(2);
+    first.write_i16(-50 | 1); // 88: values, the last with its lowest bit set
+    first.write_zeros(2);
+
+    let mut second = FontDataWriter::new();
+    second.write_u32(6); // nClasses
+    second.write_u32(20); // classTable
+    second.write_u32(44); // stateArray
+    second.write_u32(56); // entryTable
+    second.write_u32(0x4000_0000 | 76); // flags: anchor points at 76
+    second.write_bytes(&single_lookup(&[(70, 4), (71, 5)])); // 20
+    // 44: state array
+    for entry in [0, 0, 0, 0, 1, 2] {
+        second.write_u16(entry);
+    }
+    for (flags, action_index) in [(0, 0xFFFF), (KERX_ENTRY_MARK, 0xFFFF), (0, 0)] {
+        second.write_u16(0); // 56: newState, flags, actionIndex
+        second.write_u16(flags);
+        second.write_u16(action_index);
+    }
+    second.write_zeros(2);
+    second.write_u16(0); // 76: marked and current anchor points
+    second.write_u16(0);
+
+    let bytes = kerx_bytes(
+        4,
+        &[
+            (1, first.into_bytes().unwrap()),
+            (4, second.into_bytes().unwrap()),
+        ],
+    );
+    let kerx = read_kerx(&mut FontDataStream::new(&bytes), 100).unwrap();
+    assert_eq!(
+        kerx,
+        KerxTable {
+            version: 4,
+            subtables: vec![contextual, attachment],
+        }
+    );
+    let ankr = AnkrTable {
+        version: 0,
+        flags: 0,
+        anchors: BTreeMap::from([(70, vec![(500, 600)]), (71, vec![(100, 0)])]),
+    };
+
+    let positions = kerx.apply(&[80, 81, 70, 71], Some(&ankr));
+    assert_eq!(positions[0].x_advance, -50);
+    assert_eq!(positions[0].x_offset, -50);
+    assert_eq!(positions[1], KerxPosition::default());
+    assert_eq!(
+        positions[3],
+        KerxPosition {
+            x_advance: 0,
+            x_offset: 400,
+            y_offset: 600,
+            attached_to: Some(2),
+        }
+    );
+
+    // Without anchors nothing is attached.
+    assert_eq!(kerx.apply(&[70, 71], None)[1], KerxPosition::default());
+}
+
+#[test]
+fn test_feat_trak_ankr() {
+    use std::collections::BTreeMap;
+
+    use aurora_font::components::ankr::{AnkrTable, read_ankr};
+    use aurora_font::components::feat::{FeatTable, FeatureName, FeatureSettingName, read_feat};
+    use aurora_font::components::trak::{Track, TrackData, TrakTable, read_trak};
+    use aurora_font::io::stream::FontDataStream;
+    use aurora_font::io::writer::FontDataWriter;
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u32(0x00010000); // version
+    writer.write_u16(2); // featureNameCount
+    writer.write_u16(0); // reserved
+    writer.write_u32(0); // reserved
+    writer.write_u16(1); // 12: feature
+    writer.write_u16(2); // nSettings
+    writer.write_u32(36); // settingTable
+    writer.write_u16(0xC001); // featureFlags: exclusive, default setting 1
+    writer.write_u16(259); // nameIndex
+    writer.write_u16(3); // 24
+    writer.write_u16(1);
+    writer.write_u32(44);
+    writer.write_u16(0);
+    writer.write_u16(262);
+    for (setting, name_id) in [(0, 260), (2, 261), (4, 263)] {
+        writer.write_u16(setting); // 36: setting, nameIndex
+        writer.write_u16(name_id);
+    }
+    let bytes = writer.into_bytes().unwrap();
+    let read = read_feat(&mut FontDataStream::new(&bytes)).unwrap();
+    let feat = FeatTable {
+        version: 0x00010000,
+        features: vec![
+            FeatureName {
+                feature_type: 1,
+                settings: vec![
+                    FeatureSettingName {
+                        setting: 0,
+                        name_id: 260,
+                    },
+                    FeatureSettingName {
+                        setting: 2,
+                        name_id: 261,
+                    },
+                ],
+                exclusive: true,
+                default_index: Some(1),
+                name_id: 259,
+            },
+            FeatureName {
+                feature_type: 3,
+                settings: vec![FeatureSettingName {
+                    setting: 4,
+                    name_id: 263,
+                }],
+                exclusive: false,
+                default_index: None,
+                name_id: 262,
+            },
+        ],
+    };
+    assert_eq!(read, feat);
+    assert_eq!(read.feature(1).unwrap().default_setting(), Some(2));
+    assert_eq!(read.feature(3).unwrap().default_setting(), Some(4));
+    assert!(read.feature(2).is_none());
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u32(0x00010000); // version
+    writer.write_u16(0); // format
+    writer.write_u16(12); // horizOffset
+    writer.write_u16(0); // vertOffset
+    writer.write_u16(0); // reserved
+    writer.write_u16(2); // 12: nTracks
+    writer.write_u16(3); // nSizes
+    writer.write_u32(36); // sizeTableOffset
+    writer.write_fixed(-1.0); // 20: track, nameIndex, offset
+    writer.write_u16(264);
+    writer.write_u16(48);
+    writer.write_fixed(0.0);
+    writer.write_u16(265);
+    writer.write_u16(54);
+    for size in [9.0, 12.0, 24.0] {
+        writer.write_fixed(size); // 36
+    }
+    for value in [-10, -20, -40, 30, 0, -24] {
+        writer.write_i16(value); // 48
+    }
+    let bytes = writer.into_bytes().unwrap();
+    let read = read_trak(&mut FontDataStream::new(&bytes)).unwrap();
+    let trak = TrakTable {
+        version: 0x00010000,
+        format: 0,
+        horizontal: Some(TrackData {
+            sizes: vec![9.0, 12.0, 24.0],
+            tracks: vec![
+                Track {
+                    track: -1.0,
+                    name_id: 264,
+                    values: vec![-10, -20, -40],
+                },
+                Track {
+                    track: 0.0,
+                    name_id: 265,
+                    values: vec![30, 0, -24],
+                },
+            ],
+        }),
+        vertical: None,
+    };
+    assert_eq!(read, trak);
+    assert_eq!(read.tracking(12.0), 0.0);
+    assert_eq!(read.tracking(10.5), 15.0);
+    assert_eq!(read.tracking(18.0), -12.0);
+    assert_eq!(read.tracking(36.0), -48.0);
+    let horizontal = read.horizontal.as_ref().unwrap();
+    assert_eq!(horizontal.tracking(-1.0, 18.0), -30.0);
+    assert_eq!(horizontal.tracking(2.0, 12.0), 0.0);
+
+    // Lookup values are offsets into the glyph data table.
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(0); // version
+    writer.write_u16(0); // flags
+    writer.write_u32(12); // lookupTableOffset
+    writer.write_u32(36); // glyphDataTableOffset
+    writer.write_bytes(&single_lookup(&[(3, 0), (9, 12)])); // 12
+    writer.write_u32(2); // 36: numPoints, anchor points
+    for value in [10, 20, -5, 7] {
+        writer.write_i16(value);
+    }
+    writer.write_u32(1); // 48
+    writer.write_i16(0);
+    writer.write_i16(100);
+    let bytes = writer.into_bytes().unwrap();
+    let read = read_ankr(&mut FontDataStream::new(&bytes), 100).unwrap();
+    let ankr = AnkrTable {
+        version: 0,
+        flags: 0,
+        anchors: BTreeMap::from([(3, vec![(10, 20), (-5, 7)]), (9, vec![(0, 100)])]),
+    };
+    assert_eq!(read, ankr);
+    assert_eq!(read.anchor(3, 1), Some((-5, 7)));
+    assert_eq!(read.anchor(3, 2), None);
+    assert_eq!(read.anchor(4, 0), None);
+}
+
+#[test]
+fn test_morx_insertion_placement() {
+    use aurora_font::components::morx::{
+        ENTRY_SET_MARK, INSERTION_CURRENT_BEFORE, INSERTION_MARKED_BEFORE, InsertionEntry,
+        InsertionSubtable,
+    };
+
+    let none = InsertionEntry {
+        current_insert_index: 0xFFFF,
+        marked_insert_index: 0xFFFF,
+    };
+    // Inserts I, N, S before every A (glyph 1).
+    let before = InsertionSubtable {
+        machine: machine(
+            &[(1, 4)],
+            vec![vec![0, 0, 0, 0, 1]],
+            vec![
+                (0, 0, none),
+                (
+                    0,
+                    INSERTION_CURRENT_BEFORE | 3 << 5,
+                    InsertionEntry {
+                        current_insert_index: 0,
+                        marked_insert_index: 0xFFFF,
+                    },
+                ),
+            ],
+        ),
+        glyphs: vec![9, 14, 19],
+    };
+    let mut glyphs = vec![1];
+    before.apply(&mut glyphs);
+    assert_eq!(glyphs, vec![9, 14, 19, 1]);
+
+    // Glyph 61 is marked and gets 70 inserted before it; glyph 62 then
+    // inserts 71 before the mark. The mark is set before the current
+    // insertion, so it holds the inserted 70.
+    let marked = InsertionSubtable {
+        machine: machine(
+            &[(61, 4), (62, 5)],
+            vec![vec![0, 0, 0, 0, 1, 2]],
+            vec![
+                (0, 0, none),
+                (
+                    0,
+                    ENTRY_SET_MARK | INSERTION_CURRENT_BEFORE | 1 << 5,
+                    InsertionEntry {
+                        current_insert_index: 0,
+                        marked_insert_index: 0xFFFF,
+                    },
+                ),
+                (
+                    0,
+                    INSERTION_MARKED_BEFORE | 1,
+                    InsertionEntry {
+                        current_insert_index: 0xFFFF,
+                        marked_insert_index: 1,
+                    },
+                ),
+            ],
+        ),
+        glyphs: vec![70, 71],
+    };
+    let mut glyphs = vec![61, 62];
+    marked.apply(&mut glyphs);
+    assert_eq!(glyphs, vec![71, 70, 61, 62]);
+}
+
+#[test]
+fn test_morx_insertion_is_bounded() {
+    use aurora_font::components::aat::ENTRY_DONT_ADVANCE;
+    use aurora_font::components::morx::{
+        INSERTION_CURRENT_BEFORE, InsertionEntry, InsertionSubtable,
+    };
+
+    // Every glyph 60 inserts another 60 before itself and is processed
+    // again, which never ends without a limit.
+    let insertion = InsertionSubtable {
+        machine: machine(
+            &[(60, 4)],
+            vec![vec![0, 0, 0, 0, 1]],
+            vec![
+                (
+                    0,
+                    0,
+                    InsertionEntry {
+                        current_insert_index: 0xFFFF,
+                        marked_insert_index: 0xFFFF,
+                    },
+                ),
+                (
+                    0,
+                    ENTRY_DONT_ADVANCE | INSERTION_CURRENT_BEFORE | 1 << 5,
+                    InsertionEntry {
+                        current_insert_index: 0,
+                        marked_insert_index: 0xFFFF,
+                    },
+                ),
+            ],
+        ),
+        glyphs: vec![60],
+    };
+    let mut glyphs = vec![60, 1];
+    insertion.apply(&mut glyphs);
+    assert!(glyphs.len() > 2);
+    assert!(glyphs.len() <= 16384);
+    assert_eq!(glyphs.last(), Some(&1));
+}
+
+#[test]
+fn test_morx_contextual_default_mark() {
+    use aurora_font::components::aat::AatLookup;
+    use aurora_font::components::morx::{ContextualEntry, ContextualSubtable, ENTRY_SET_MARK};
+
+    // Glyph 42 substitutes the marked glyph 40 => 41; the end of the run
+    // substitutes the last glyph 42 => 43.
+    let contextual = |flags: u16| ContextualSubtable {
+        machine: machine(
+            &[(42, 4)],
+            vec![vec![1, 0, 0, 0, 2]],
+            vec![
+                (
+                    0,
+                    0,
+                    ContextualEntry {
+                        mark_index: 0xFFFF,
+                        current_index: 0xFFFF,
+                    },
+                ),
+                (
+                    0,
+                    0,
+                    ContextualEntry {
+                        mark_index: 0xFFFF,
+                        current_index: 1,
+                    },
+                ),
+                (
+                    0,
+                    flags,
+                    ContextualEntry {
+                        mark_index: 0,
+                        current_index: 0xFFFF,
+                    },
+                ),
+            ],
+        ),
+        substitutions: vec![AatLookup::new([(40, 41)]), AatLookup::new([(42, 43)])],
+    };
+
+    // The mark starts at the first glyph, and without a mark the end of the
+    // run substitutes nothing.
+    let mut glyphs = vec![40, 42];
+    contextual(0).apply(&mut glyphs);
+    assert_eq!(glyphs, vec![41, 42]);
+
+    let mut glyphs = vec![40, 42];
+    contextual(ENTRY_SET_MARK).apply(&mut glyphs);
+    assert_eq!(glyphs, vec![41, 43]);
+}
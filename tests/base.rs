@@ -0,0 +1,253 @@
+//! Tests for the `BASE` table and script tags.
+
+/// Lays out a table whose horizontal axis has hanging, ideographic and roman
+/// baselines for the default, Devanagari, CJK and Latin scripts, and whose
+/// vertical axis has an ideographic baseline for CJK.
+fn base_data() -> Vec<u8> {
+    use aurora_font::io::writer::FontDataWriter;
+
+    let mut w = FontDataWriter::new();
+    let coord = |w: &mut FontDataWriter, coordinate: i16| {
+        w.write_u16(1);
+        w.write_i16(coordinate);
+    };
+    // A BaseScript with only format 1 values: 6 + 4 + 6 * count bytes.
+    let values_script = |w: &mut FontDataWriter, default: u16, coordinates: &[i16]| {
+        w.write_u16(6);
+        w.write_u16(0);
+        w.write_u16(0);
+        w.write_u16(default);
+        w.write_u16(coordinates.len() as u16);
+        let start = 4 + 2 * coordinates.len() as u16;
+        (0..coordinates.len() as u16).for_each(|i| w.write_u16(start + 4 * i));
+        coordinates.iter().for_each(|&c| coord(w, c));
+    };
+
+    // Header: version 1.0, horizontal axis at 8, vertical axis at 234.
+    w.write_u32(0x0001_0000);
+    w.write_u16(8);
+    w.write_u16(234);
+
+    // Horizontal axis at 8: tag list at +4, script list at +18.
+    w.write_u16(4);
+    w.write_u16(18);
+    w.write_u16(3);
+    w.write_tag(*b"hang");
+    w.write_tag(*b"ideo");
+    w.write_tag(*b"romn");
+    w.write_u16(4);
+    for (tag, offset) in [(b"DFLT", 26), (b"deva", 54), (b"hani", 110), (b"latn", 180)] {
+        w.write_tag(*tag);
+        w.write_u16(offset);
+    }
+    // DFLT.
+    values_script(&mut w, 2, &[700, -120, 0]);
+    // deva: values at +6 with a glyph point and a device coordinate, and
+    // default extents at +42.
+    w.write_u16(6);
+    w.write_u16(42);
+    w.write_u16(0);
+    w.write_u16(0);
+    w.write_u16(3);
+    w.write_u16(10);
+    w.write_u16(18);
+    w.write_u16(22);
+    w.write_u16(2); // 650 at point 7 of glyph 40
+    w.write_i16(650);
+    w.write_u16(40);
+    w.write_u16(7);
+    coord(&mut w, -120);
+    w.write_u16(3); // 0 with a device at +6
+    w.write_i16(0);
+    w.write_u16(6);
+    w.write_u16(10);
+    w.write_u16(11);
+    w.write_u16(2);
+    w.write_u16(0x1F00);
+    w.write_u16(6);
+    w.write_u16(10);
+    w.write_u16(0);
+    coord(&mut w, -300);
+    coord(&mut w, 900);
+    // hani: values at +12, default extents at +34 and ZHS extents at +48
+    // with a maximum for the vert feature.
+    w.write_u16(12);
+    w.write_u16(34);
+    w.write_u16(1);
+    w.write_tag(*b"ZHS ");
+    w.write_u16(48);
+    w.write_u16(1);
+    w.write_u16(3);
+    [10, 14, 18].iter().for_each(|&o| w.write_u16(o));
+    [880, -120, 0].iter().for_each(|&c| coord(&mut w, c));
+    w.write_u16(6);
+    w.write_u16(10);
+    w.write_u16(0);
+    coord(&mut w, -120);
+    coord(&mut w, 880);
+    w.write_u16(14);
+    w.write_u16(0);
+    w.write_u16(1);
+    w.write_tag(*b"vert");
+    w.write_u16(0);
+    w.write_u16(18);
+    coord(&mut w, -140);
+    coord(&mut w, 900);
+    // latn.
+    values_script(&mut w, 2, &[620, -100, 0]);
+
+    // Vertical axis at 234: tag list at +4, script list at +10.
+    w.write_u16(4);
+    w.write_u16(10);
+    w.write_u16(1);
+    w.write_tag(*b"ideo");
+    w.write_u16(1);
+    w.write_tag(*b"hani");
+    w.write_u16(8);
+    values_script(&mut w, 0, &[500]);
+    w.into_bytes().unwrap()
+}
+
+fn read_base_data() -> aurora_font::components::base::BaseTable {
+    use aurora_font::components::base::read_base;
+    use aurora_font::io::stream::FontDataStream;
+
+    read_base(&mut FontDataStream::new(&base_data())).unwrap()
+}
+
+#[test]
+fn test_read_base() {
+    use aurora_font::components::base::{
+        BASELINE_HANGING, BASELINE_IDEOGRAPHIC, BASELINE_ROMAN, BaseCoord, FeatMinMaxRecord,
+    };
+    use aurora_font::components::layout::DeviceTable;
+
+    let base = read_base_data();
+    assert_eq!((base.major_version, base.minor_version), (1, 0));
+    assert!(base.item_var_store.is_none());
+
+    let horizontal = base.horizontal.as_ref().unwrap();
+    assert_eq!(
+        horizontal.baseline_tags,
+        [BASELINE_HANGING, BASELINE_IDEOGRAPHIC, BASELINE_ROMAN]
+    );
+    let tags: Vec<_> = horizontal.scripts.iter().map(|s| s.script_tag).collect();
+    assert_eq!(tags, [*b"DFLT", *b"deva", *b"hani", *b"latn"]);
+
+    let deva = &horizontal.scripts[1].script;
+    assert_eq!(
+        deva.base_values.as_ref().unwrap().coordinates,
+        [
+            BaseCoord::GlyphPoint {
+                coordinate: 650,
+                reference_glyph: 40,
+                base_coord_point: 7,
+            },
+            BaseCoord::Coordinate(-120),
+            BaseCoord::Device(
+                0,
+                DeviceTable::Hinting {
+                    start_size: 10,
+                    end_size: 11,
+                    delta_format: 2,
+                    delta_values: vec![0x1F00],
+                },
+            ),
+        ]
+    );
+    assert!(deva.lang_sys_records.is_empty());
+
+    let hani = &horizontal.scripts[2].script;
+    assert_eq!(hani.base_values.as_ref().unwrap().default_baseline_index, 1);
+    let zhs = &hani.lang_sys_records[0];
+    assert_eq!(zhs.lang_sys_tag, *b"ZHS ");
+    assert_eq!(zhs.min_max.max, None);
+    assert_eq!(
+        zhs.min_max.features,
+        [FeatMinMaxRecord {
+            feature_tag: *b"vert",
+            min: None,
+            max: Some(BaseCoord::Coordinate(900)),
+        }]
+    );
+
+    let vertical = base.vertical.as_ref().unwrap();
+    assert_eq!(vertical.baseline_tags, [BASELINE_IDEOGRAPHIC]);
+    assert_eq!(vertical.scripts.len(), 1);
+}
+
+#[test]
+fn test_base_baseline() {
+    use aurora_font::components::base::{
+        BASELINE_HANGING, BASELINE_IDEOGRAPHIC, BASELINE_MATH, BASELINE_ROMAN, Baseline,
+    };
+    use aurora_font::tags::script::ScriptTag;
+
+    let base = read_base_data();
+    let baseline = |coordinate: i16, min: Option<i16>, max: Option<i16>| Baseline {
+        coordinate,
+        min,
+        max,
+    };
+
+    assert_eq!(
+        base.baseline(ScriptTag::Latin, None, BASELINE_IDEOGRAPHIC),
+        Some(baseline(-100, None, None))
+    );
+    assert_eq!(
+        base.baseline(ScriptTag::Devanagari, None, BASELINE_HANGING),
+        Some(baseline(650, Some(-300), Some(900)))
+    );
+    // Language extents replace the script's default extents.
+    assert_eq!(
+        base.baseline(ScriptTag::CJK_Ideographic, Some(*b"ZHS "), BASELINE_ROMAN),
+        Some(baseline(0, Some(-140), None))
+    );
+    assert_eq!(
+        base.baseline(ScriptTag::CJK_Ideographic, Some(*b"JAN "), BASELINE_ROMAN),
+        Some(baseline(0, Some(-120), Some(880)))
+    );
+    // Scripts without their own record use the default script.
+    assert_eq!(
+        base.baseline(ScriptTag::Arabic, None, BASELINE_HANGING),
+        Some(baseline(700, None, None))
+    );
+    assert_eq!(base.baseline(ScriptTag::Latin, None, BASELINE_MATH), None);
+
+    let vertical = base.vertical.as_ref().unwrap();
+    assert_eq!(
+        vertical.baseline(ScriptTag::CJK_Ideographic, None, BASELINE_IDEOGRAPHIC),
+        Some(baseline(500, None, None))
+    );
+    assert_eq!(
+        vertical.baseline(ScriptTag::Latin, None, BASELINE_IDEOGRAPHIC),
+        None
+    );
+}
+
+#[test]
+fn test_base_invalid() {
+    use aurora_font::components::base::read_base;
+    use aurora_font::io::stream::FontDataStream;
+
+    assert!(read_base(&mut FontDataStream::new(&[0, 2, 0, 0, 0, 0, 0, 0])).is_err());
+    // A horizontal axis pointing past the end of the table.
+    assert!(read_base(&mut FontDataStream::new(&[0, 1, 0, 0, 0, 40, 0, 0])).is_err());
+}
+
+#[test]
+fn test_script_tags() {
+    use aurora_font::tags::script::ScriptTag;
+
+    assert_eq!(ScriptTag::Latin.tag(), *b"latn");
+    assert_eq!(ScriptTag::Default.tag(), *b"DFLT");
+    assert_eq!(ScriptTag::Lao.tag(), *b"lao ");
+    assert_eq!(ScriptTag::Devanagari_2.tag(), *b"dev2");
+    assert_eq!(
+        ScriptTag::from_tag(*b"hani"),
+        Some(ScriptTag::CJK_Ideographic)
+    );
+    assert_eq!(ScriptTag::from_tag(*b"kana"), Some(ScriptTag::Hiragana));
+    assert_eq!(ScriptTag::Katakana.tag(), *b"kana");
+    assert_eq!(ScriptTag::from_tag(*b"zzzz"), None);
+}
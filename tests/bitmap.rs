@@ -0,0 +1,255 @@
+//! Tests for embedded bitmaps (`EBLC`/`EBDT`/`EBSC` and `CBLC`/`CBDT`).
+
+/// Lays out a location table with one strike over glyphs `start..=end`.
+/// Each subtable is given by its glyph range and its bytes, header included.
+fn location_table(
+    major_version: u16,
+    (start, end): (u16, u16),
+    ppem: u8,
+    bit_depth: u8,
+    subtables: &[(u16, u16, Vec<u8>)],
+) -> Vec<u8> {
+    use aurora_font::io::FontDataWriter;
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(major_version);
+    writer.write_u16(0);
+    writer.write_u32(1);
+    // BitmapSize: the subtable array follows the 48-byte record.
+    let array_size = 8 * subtables.len();
+    let tables_size: usize = subtables.iter().map(|(_, _, data)| data.len()).sum();
+    writer.write_u32(8 + 48);
+    writer.write_u32((array_size + tables_size) as u32);
+    writer.write_u32(subtables.len() as u32);
+    writer.write_u32(0);
+    writer.write_zeros(24);
+    writer.write_u16(start);
+    writer.write_u16(end);
+    writer.write_u8(ppem);
+    writer.write_u8(ppem);
+    writer.write_u8(bit_depth);
+    writer.write_u8(1);
+    let mut offset = array_size;
+    for (first, last, data) in subtables {
+        writer.write_u16(*first);
+        writer.write_u16(*last);
+        writer.write_u32(offset as u32);
+        offset += data.len();
+    }
+    for (_, _, data) in subtables {
+        writer.write_bytes(data);
+    }
+    writer.into_bytes().unwrap()
+}
+
+/// Builds a 1-bit 12 ppem strike and its `EBDT` data. Glyph 1 is byte
+/// aligned, glyph 2 bit aligned, glyph 3 takes its metrics from the location
+/// table and glyph 4 combines glyphs 1 and 3.
+fn build_mono() -> (Vec<u8>, Vec<u8>) {
+    let mut data = vec![0, 2, 0, 0];
+    // Glyph 1, format 1: 3x2 pixels, rows padded to bytes.
+    data.extend_from_slice(&[2, 3, 0, 2, 4, 0b1010_0000, 0b0100_0000]);
+    // Glyph 2, format 2: 3x2 pixels packed into six bits.
+    data.extend_from_slice(&[2, 3, 0, 2, 4, 0b1010_1000]);
+    // Glyph 3, format 5: 2x2 pixels packed into four bits.
+    data.push(0b1001_0000);
+    // Glyph 4, format 8: 5x2 pixels made of glyph 1 and glyph 3 three pixels right.
+    data.extend_from_slice(&[2, 5, 0, 2, 6, 0, 0, 2, 0, 1, 0, 0, 0, 3, 3, 0]);
+
+    // Each subtable starts with its index format, image format and image
+    // data offset.
+    #[rustfmt::skip]
+    let subtables = [
+        // Format 3: 16-bit offsets.
+        (1, 1, vec![0, 3, 0, 1, 0, 0, 0, 4, 0, 0, 0, 7]),
+        // Format 1: 32-bit offsets.
+        (2, 2, vec![0, 1, 0, 2, 0, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 6]),
+        // Format 2: one-byte images sharing big metrics.
+        (3, 3, vec![0, 2, 0, 5, 0, 0, 0, 17, 0, 0, 0, 1, 2, 2, 0, 2, 3, 0, 0, 0]),
+        // Format 4: glyph and offset pairs.
+        (4, 4, vec![0, 4, 0, 8, 0, 0, 0, 18, 0, 0, 0, 1, 0, 4, 0, 0, 0, 0, 0, 16]),
+    ];
+    (location_table(2, (1, 4), 12, 1, &subtables), data)
+}
+
+#[test]
+fn test_read_eblc() {
+    use aurora_font::components::cblc::read_cblc;
+    use aurora_font::components::ebdt::read_ebdt;
+    use aurora_font::components::eblc::{IndexSubTableData, read_eblc};
+    use aurora_font::io::FontDataStream;
+
+    let (mut eblc_data, ebdt_data) = build_mono();
+    let eblc = read_eblc(&mut FontDataStream::new(&eblc_data)).unwrap();
+    assert_eq!((eblc.major_version, eblc.minor_version), (2, 0));
+    assert!(!eblc.color);
+    let [strike] = eblc.sizes.as_slice() else {
+        panic!("expected one strike");
+    };
+    assert_eq!((strike.start_glyph_index, strike.end_glyph_index), (1, 4));
+    assert_eq!((strike.ppem_y, strike.bit_depth), (12, 1));
+    let formats: Vec<(u16, u16, u32)> = strike
+        .index_subtables
+        .iter()
+        .map(|s| (s.first_glyph_index, s.image_format, s.image_data_offset))
+        .collect();
+    assert_eq!(formats, [(1, 1, 4), (2, 2, 11), (3, 5, 17), (4, 8, 18)]);
+    assert_eq!(
+        strike.index_subtables[0].data,
+        IndexSubTableData::Format3(vec![0, 7])
+    );
+    assert_eq!(
+        strike.index_subtables[3].data,
+        IndexSubTableData::Format4(vec![(4, 0), (0, 16)])
+    );
+
+    // The table tag, not the version, tells the monochrome and color tables
+    // apart; `EBLC` has no version 3.
+    assert!(
+        read_cblc(&mut FontDataStream::new(&eblc_data))
+            .unwrap()
+            .color
+    );
+    eblc_data[1] = 3;
+    assert!(read_eblc(&mut FontDataStream::new(&eblc_data)).is_err());
+
+    let ebdt = read_ebdt(&mut FontDataStream::new(&ebdt_data)).unwrap();
+    assert_eq!(ebdt.major_version, 2);
+    assert_eq!(ebdt.data, ebdt_data);
+}
+
+#[test]
+fn test_monochrome_bitmaps() {
+    use aurora_font::components::ebdt::{BitmapData, read_ebdt};
+    use aurora_font::components::eblc::read_eblc;
+    use aurora_font::io::FontDataStream;
+
+    let (eblc, ebdt) = build_mono();
+    let eblc = read_eblc(&mut FontDataStream::new(&eblc)).unwrap();
+    let ebdt = read_ebdt(&mut FontDataStream::new(&ebdt)).unwrap();
+    let pixels = |glyph_id| {
+        let glyph = ebdt
+            .bitmap_glyph(&eblc, None, glyph_id, 12)
+            .unwrap()
+            .unwrap();
+        let BitmapData::Pixels(pixels) = glyph.data else {
+            panic!("expected pixels");
+        };
+        (glyph.metrics.width, glyph.metrics.height, pixels)
+    };
+
+    assert_eq!(pixels(1), (3, 2, vec![1, 0, 1, 0, 1, 0]));
+    assert_eq!(pixels(2), (3, 2, vec![1, 0, 1, 0, 1, 0]));
+    assert_eq!(pixels(3), (2, 2, vec![1, 0, 0, 1]));
+    assert_eq!(pixels(4), (5, 2, vec![1, 0, 1, 1, 0, 0, 1, 0, 0, 1]));
+
+    let glyph = ebdt.bitmap_glyph(&eblc, None, 1, 12).unwrap().unwrap();
+    assert_eq!(glyph.metrics.hori_advance, 4);
+    assert_eq!(glyph.metrics.hori_bearing_y, 2);
+    assert_eq!(glyph.scale, 1.0);
+
+    // Monochrome strikes only draw their own size.
+    assert!(ebdt.bitmap_glyph(&eblc, None, 1, 16).unwrap().is_none());
+    assert!(ebdt.bitmap_glyph(&eblc, None, 5, 12).unwrap().is_none());
+}
+
+#[test]
+fn test_ebsc_scaling() {
+    use aurora_font::components::ebdt::read_ebdt;
+    use aurora_font::components::eblc::read_eblc;
+    use aurora_font::components::ebsc::read_ebsc;
+    use aurora_font::io::FontDataStream;
+
+    // One scale drawing 24 ppem from the 12 ppem strike, after 24 bytes of
+    // line metrics.
+    let mut data = vec![0, 2, 0, 0, 0, 0, 0, 1];
+    data.extend_from_slice(&[0; 24]);
+    data.extend_from_slice(&[24, 24, 12, 12]);
+    let ebsc = read_ebsc(&mut FontDataStream::new(&data)).unwrap();
+    let scale = ebsc.scale(24).unwrap();
+    assert_eq!((scale.ppem_x, scale.substitute_ppem_y), (24, 12));
+    assert!(ebsc.scale(16).is_none());
+    assert!(read_ebsc(&mut FontDataStream::new(&data[..30])).is_err());
+
+    let (eblc, ebdt) = build_mono();
+    let eblc = read_eblc(&mut FontDataStream::new(&eblc)).unwrap();
+    let ebdt = read_ebdt(&mut FontDataStream::new(&ebdt)).unwrap();
+    let glyph = ebdt
+        .bitmap_glyph(&eblc, Some(&ebsc), 3, 24)
+        .unwrap()
+        .unwrap();
+    assert_eq!(glyph.ppem_y, 12);
+    assert_eq!(glyph.scale, 2.0);
+}
+
+#[test]
+fn test_color_bitmaps() {
+    use aurora_font::components::cbdt::{BitmapData, CbdtTable, read_cbdt};
+    use aurora_font::components::cblc::{BigGlyphMetrics, IndexSubTableData, read_cblc};
+    use aurora_font::io::FontDataStream;
+
+    let mut data = vec![0, 3, 0, 0];
+    // Glyph 5, format 17: small metrics and a PNG.
+    data.extend_from_slice(&[136, 136, 0, 101, 136, 0, 0, 0, 4]);
+    data.extend_from_slice(b"PNG5");
+    // Glyphs 7 and 9, format 19: PNGs with metrics in the location table.
+    for png in [b"PNG7", b"PNG9"] {
+        data.extend_from_slice(&[0, 0, 0, 4]);
+        data.extend_from_slice(png);
+    }
+    #[rustfmt::skip]
+    let subtables = [
+        // Format 1: 32-bit offsets to a format 17 image.
+        (5, 5, vec![0, 1, 0, 17, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 13]),
+        // Format 5: glyphs 7 and 9 with 8-byte format 19 images and shared
+        // big metrics.
+        (7, 9, vec![
+            0, 5, 0, 19, 0, 0, 0, 17, 0, 0, 0, 8,
+            136, 136, 0, 101, 136, 0, 0, 0,
+            0, 0, 0, 2, 0, 7, 0, 9,
+        ]),
+    ];
+    let mut bytes = location_table(3, (5, 9), 109, 32, &subtables);
+    let cblc = read_cblc(&mut FontDataStream::new(&bytes)).unwrap();
+    assert!(cblc.color);
+    assert_eq!(cblc.sizes[0].bit_depth, 32);
+    let metrics = BigGlyphMetrics {
+        height: 136,
+        width: 136,
+        hori_bearing_y: 101,
+        hori_advance: 136,
+        ..Default::default()
+    };
+    assert_eq!(
+        cblc.sizes[0].index_subtables[1].data,
+        IndexSubTableData::Format5 {
+            image_size: 8,
+            metrics,
+            glyph_ids: vec![7, 9],
+        }
+    );
+    let cbdt: CbdtTable = read_cbdt(&mut FontDataStream::new(&data)).unwrap();
+
+    // Color strikes scale to any size.
+    let glyph = cbdt.bitmap_glyph(&cblc, None, 5, 64).unwrap().unwrap();
+    assert_eq!(glyph.data, BitmapData::Png(b"PNG5".to_vec()));
+    assert_eq!(glyph.metrics, metrics);
+    assert_eq!(glyph.scale, 64.0 / 109.0);
+
+    let glyph = cbdt.bitmap_glyph(&cblc, None, 9, 128).unwrap().unwrap();
+    assert_eq!(glyph.data, BitmapData::Png(b"PNG9".to_vec()));
+    assert_eq!(glyph.metrics, metrics);
+    assert!(cbdt.bitmap_glyph(&cblc, None, 8, 109).unwrap().is_none());
+
+    // Early color fonts label both tables with major version 2; they still
+    // fall back to the nearest strike.
+    bytes[1] = 2;
+    data[1] = 2;
+    let cblc = read_cblc(&mut FontDataStream::new(&bytes)).unwrap();
+    let cbdt = read_cbdt(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(cblc.major_version, 2);
+    assert!(cblc.color);
+    let glyph = cbdt.bitmap_glyph(&cblc, None, 7, 32).unwrap().unwrap();
+    assert_eq!(glyph.data, BitmapData::Png(b"PNG7".to_vec()));
+    assert!(read_cbdt(&mut FontDataStream::new(&[0, 1, 0, 0])).is_err());
+}
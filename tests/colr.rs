@@ -0,0 +1,764 @@
+//! Tests for color glyphs and palettes.
+
+/// Builds a version 1 `CPAL` with a light and a dark palette of two entries.
+fn build_cpal() -> aurora_font::components::cpal::CpalTable {
+    use aurora_font::components::cpal::{
+        Color, CpalTable, NO_LABEL, USABLE_WITH_DARK_BACKGROUND, USABLE_WITH_LIGHT_BACKGROUND,
+    };
+
+    CpalTable {
+        version: 1,
+        num_palette_entries: 2,
+        palettes: vec![
+            vec![Color::rgba(255, 0, 0, 255), Color::rgba(0, 0, 255, 128)],
+            vec![
+                Color::rgba(255, 128, 128, 255),
+                Color::rgba(128, 128, 255, 255),
+            ],
+            vec![Color::rgba(255, 0, 0, 255), Color::rgba(0, 0, 255, 128)],
+        ],
+        palette_types: Some(vec![
+            USABLE_WITH_LIGHT_BACKGROUND,
+            USABLE_WITH_DARK_BACKGROUND,
+            0,
+        ]),
+        palette_labels: Some(vec![256, 257, NO_LABEL]),
+        palette_entry_labels: Some(vec![NO_LABEL, 258]),
+    }
+}
+
+/// The variable COLRv1 test font. Glyph 168 has eight version 0 layers and
+/// the others paint graphs; its `CPAL` has three palettes of 14 entries.
+const COLR_1_VARIABLE: &[u8] = include_bytes!("../assets/test-fonts/colr_1_variable.ttf");
+
+/// Reads the `COLR` and `CPAL` tables of the variable COLRv1 test font.
+fn read_colr_1_variable() -> (
+    aurora_font::components::colr::ColrTable,
+    aurora_font::components::cpal::CpalTable,
+) {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::colr::read_colr;
+    use aurora_font::components::cpal::read_cpal;
+    use aurora_font::io::FontDataStream;
+
+    let snft = read_snft(&mut FontDataStream::new(COLR_1_VARIABLE)).unwrap();
+    let table = |tag: &[u8; 4]| FontDataStream::new(snft.table_data(tag, COLR_1_VARIABLE).unwrap());
+    (
+        read_colr(&mut table(b"COLR")).unwrap(),
+        read_cpal(&mut table(b"CPAL")).unwrap(),
+    )
+}
+
+#[test]
+fn test_cpal_font() {
+    use aurora_font::components::cpal::{
+        Color, USABLE_WITH_DARK_BACKGROUND, USABLE_WITH_LIGHT_BACKGROUND,
+    };
+
+    let (_, cpal) = read_colr_1_variable();
+    assert_eq!(cpal.version, 1);
+    assert_eq!(cpal.num_palette_entries, 14);
+    assert_eq!(cpal.palettes.len(), 3);
+    assert_eq!(cpal.palette_type(0), 0);
+    assert_eq!(cpal.find_palette(USABLE_WITH_DARK_BACKGROUND), 1);
+    assert_eq!(cpal.find_palette(USABLE_WITH_LIGHT_BACKGROUND), 2);
+    assert_eq!(cpal.palette_label(0), None);
+    assert_eq!(cpal.palette_entry_label(0), None);
+    assert_eq!(cpal.color(0, 1), Some(Color::rgba(255, 165, 0, 255)));
+    assert_eq!(cpal.color(1, 0), Some(Color::rgba(42, 41, 74, 255)));
+    assert_eq!(cpal.color(2, 0), Some(Color::rgba(252, 113, 24, 255)));
+    assert_eq!(cpal.color(0, 14), None);
+    assert_eq!(cpal.color(3, 0), None);
+}
+
+#[test]
+fn test_read_cpal_labels() {
+    use aurora_font::components::cpal::{Color, USABLE_WITH_DARK_BACKGROUND, read_cpal};
+    use aurora_font::io::FontDataStream;
+
+    // Version 1 with two palettes of two entries, types and labels.
+    #[rustfmt::skip]
+    let data = [
+        0, 1, 0, 2, 0, 2, 0, 4, // version, numPaletteEntries, numPalettes, numColorRecords
+        0, 0, 0, 28,            // colorRecordsArrayOffset
+        0, 0, 0, 2,             // colorRecordIndices
+        0, 0, 0, 44,            // paletteTypesArrayOffset
+        0, 0, 0, 52,            // paletteLabelsArrayOffset
+        0, 0, 0, 56,            // paletteEntryLabelsArrayOffset
+        0, 0, 255, 255, 255, 0, 0, 128, // BGRA records
+        128, 128, 255, 255, 255, 128, 128, 255,
+        0, 0, 0, 1, 0, 0, 0, 2, // palette types
+        1, 0, 0xFF, 0xFF,       // palette labels
+        0xFF, 0xFF, 1, 2,       // palette entry labels
+    ];
+    let cpal = read_cpal(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(
+        cpal.palettes,
+        [
+            [Color::rgba(255, 0, 0, 255), Color::rgba(0, 0, 255, 128)],
+            [
+                Color::rgba(255, 128, 128, 255),
+                Color::rgba(128, 128, 255, 255)
+            ],
+        ]
+    );
+    assert_eq!(cpal.find_palette(USABLE_WITH_DARK_BACKGROUND), 1);
+    assert_eq!(cpal.palette_label(0), Some(256));
+    assert_eq!(cpal.palette_label(1), None);
+    assert_eq!(cpal.palette_entry_label(0), None);
+    assert_eq!(cpal.palette_entry_label(1), Some(258));
+
+    // A palette whose records run past the color records is rejected.
+    let mut data = data;
+    data[15] = 3;
+    assert!(read_cpal(&mut FontDataStream::new(&data)).is_err());
+}
+
+#[test]
+fn test_colr_layers_font() {
+    use aurora_font::components::colr::LayerRecord;
+    use aurora_font::components::cpal::Color;
+
+    let (colr, cpal) = read_colr_1_variable();
+    assert_eq!(colr.base_glyphs.len(), 1);
+    let layers = colr.layers(168).unwrap();
+    assert_eq!(layers.len(), 8);
+    assert_eq!(
+        layers[7],
+        LayerRecord {
+            glyph_id: 5,
+            palette_index: 10,
+        }
+    );
+    assert_eq!(colr.layers(9), None);
+
+    let white = Color::rgba(255, 255, 255, 255);
+    let colors = |palette| -> Vec<(u16, Color)> {
+        colr.color_layers(168, &cpal, palette, white)
+            .unwrap()
+            .iter()
+            .map(|layer| (layer.glyph_id, layer.color))
+            .collect()
+    };
+    assert_eq!(
+        colors(0),
+        [
+            (176, Color::rgba(255, 0, 0, 255)),
+            (175, Color::rgba(255, 165, 0, 255)),
+            (174, Color::rgba(255, 255, 0, 255)),
+            (173, Color::rgba(0, 128, 0, 255)),
+            (172, Color::rgba(0, 0, 255, 255)),
+            (171, Color::rgba(75, 0, 130, 255)),
+            (170, Color::rgba(238, 130, 238, 255)),
+            (5, Color::rgba(0, 0, 0, 255)),
+        ]
+    );
+    assert_eq!(colors(1)[0], (176, Color::rgba(42, 41, 74, 255)));
+}
+
+#[test]
+fn test_read_colr_layers() {
+    use aurora_font::components::colr::{ColorLayer, read_colr};
+    use aurora_font::components::cpal::Color;
+    use aurora_font::io::FontDataStream;
+
+    // Version 0 with two base glyphs sharing a layer, one in the foreground
+    // color and one past the end of the palette.
+    #[rustfmt::skip]
+    let data = [
+        0, 0, 0, 2,             // version, numBaseGlyphRecords
+        0, 0, 0, 14,            // baseGlyphRecordsOffset
+        0, 0, 0, 26,            // layerRecordsOffset
+        0, 4,                   // numLayerRecords
+        0, 3, 0, 1, 0, 3,       // glyph 3: layers 1 to 3
+        0, 5, 0, 0, 0, 2,       // glyph 5: layers 0 and 1
+        0, 11, 0xFF, 0xFF,      // glyph 11, foreground
+        0, 10, 0, 0,            // glyph 10, entry 0
+        0, 12, 0, 1,            // glyph 12, entry 1
+        0, 13, 0, 7,            // glyph 13, entry 7
+    ];
+    let colr = read_colr(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(colr.version, 0);
+    assert_eq!(colr.layers(4), None);
+
+    let cpal = build_cpal();
+    let black = Color::rgba(0, 0, 0, 255);
+    let resolved = |glyph_id, palette| colr.color_layers(glyph_id, &cpal, palette, black);
+    assert_eq!(
+        resolved(5, 1),
+        Some(vec![
+            ColorLayer {
+                glyph_id: 11,
+                color: black,
+            },
+            ColorLayer {
+                glyph_id: 10,
+                color: Color::rgba(255, 128, 128, 255),
+            },
+        ])
+    );
+    // Entries missing from the palette take the foreground color.
+    let colors: Vec<Color> = resolved(3, 0)
+        .unwrap()
+        .iter()
+        .map(|layer| layer.color)
+        .collect();
+    assert_eq!(
+        colors,
+        vec![
+            Color::rgba(255, 0, 0, 255),
+            Color::rgba(0, 0, 255, 128),
+            black,
+        ]
+    );
+    assert_eq!(resolved(1, 0), None);
+}
+
+/// Records the operations of a color glyph.
+#[derive(Default)]
+struct Recorder {
+    ops: Vec<String>,
+}
+
+impl aurora_font::components::colr::ColorPainter for Recorder {
+    fn push_transform(&mut self, transform: aurora_font::components::colr::Transform) {
+        self.ops.push(format!(
+            "transform {} {} {} {} {} {}",
+            transform.xx, transform.yx, transform.xy, transform.yy, transform.dx, transform.dy
+        ));
+    }
+    fn pop_transform(&mut self) {
+        self.ops.push("pop transform".into());
+    }
+    fn push_clip(&mut self, clip: aurora_font::components::colr::ClipShape) {
+        self.ops.push(format!("clip {clip:?}"));
+    }
+    fn pop_clip(&mut self) {
+        self.ops.push("pop clip".into());
+    }
+    fn fill(&mut self, brush: aurora_font::components::colr::Brush) {
+        self.ops.push(format!("fill {brush:?}"));
+    }
+    fn push_layer(&mut self, mode: aurora_font::components::colr::CompositeMode) {
+        self.ops.push(format!("layer {mode:?}"));
+    }
+    fn pop_layer(&mut self) {
+        self.ops.push("pop layer".into());
+    }
+}
+
+impl Recorder {
+    /// Whether every push was popped, in order.
+    fn balanced(&self) -> bool {
+        let mut stack = Vec::new();
+        for op in &self.ops {
+            match op.strip_prefix("pop ") {
+                Some(kind) => {
+                    if stack.pop() != Some(kind) {
+                        return false;
+                    }
+                }
+                None => {
+                    if let Some(kind @ ("transform" | "clip" | "layer")) = op.split(' ').next() {
+                        stack.push(kind);
+                    }
+                }
+            }
+        }
+        stack.is_empty()
+    }
+}
+
+/// Builds a version 1 `COLR` with one paint of every format in the layer
+/// list, a composite glyph with a clip box, a variable glyph and two cycles.
+fn build_colr_v1() -> aurora_font::components::colr::ColrTable {
+    use aurora_font::components::colr::{
+        Clip, ClipBox, ColorLine, ColorStop, ColrTable, CompositeMode, Extend, Paint, PaintId,
+        Transform,
+    };
+    use aurora_font::components::variation::{
+        DeltaSetIndexMap, ItemVariationData, ItemVariationStore, RegionAxis, VariationRegion,
+    };
+
+    fn push(paints: &mut Vec<Paint>, paint: Paint) -> PaintId {
+        paints.push(paint);
+        paints.len() - 1
+    }
+    let mut paints = Vec::new();
+    // Two solid fills shared by most of the paints.
+    let solid: Vec<PaintId> = (0..2)
+        .map(|palette_index| {
+            push(
+                &mut paints,
+                Paint::Solid {
+                    palette_index,
+                    alpha: 1.0,
+                    var_index_base: None,
+                },
+            )
+        })
+        .collect();
+    let line = |extend, variable: bool| ColorLine {
+        extend,
+        stops: vec![
+            ColorStop {
+                stop_offset: 1.0,
+                palette_index: 1,
+                alpha: 1.0,
+                var_index_base: variable.then_some(0),
+            },
+            ColorStop {
+                stop_offset: 0.0,
+                palette_index: 0,
+                alpha: 0.5,
+                var_index_base: variable.then_some(0),
+            },
+        ],
+    };
+    let var = |variable: bool| variable.then_some(0);
+    let mut layers = vec![Paint::ColrLayers {
+        first_layer_index: 1,
+        num_layers: 2,
+    }];
+    for variable in [false, true] {
+        layers.extend([
+            Paint::Solid {
+                palette_index: 0,
+                alpha: 0.5,
+                var_index_base: var(variable),
+            },
+            Paint::LinearGradient {
+                color_line: line(Extend::Pad, variable),
+                x0: 0,
+                y0: 0,
+                x1: 100,
+                y1: 100,
+                x2: 0,
+                y2: 100,
+                var_index_base: var(variable),
+            },
+            Paint::RadialGradient {
+                color_line: line(Extend::Repeat, variable),
+                x0: 10,
+                y0: 20,
+                radius0: 5,
+                x1: 30,
+                y1: 40,
+                radius1: 50,
+                var_index_base: var(variable),
+            },
+            Paint::SweepGradient {
+                color_line: line(Extend::Reflect, variable),
+                center_x: 50,
+                center_y: 50,
+                start_angle: 0.0,
+                end_angle: 1.0,
+                var_index_base: var(variable),
+            },
+            Paint::Transform {
+                paint: solid[1],
+                transform: Transform {
+                    xx: 2.0,
+                    yx: 0.0,
+                    xy: 0.5,
+                    yy: 1.0,
+                    dx: 10.0,
+                    dy: -10.0,
+                },
+                var_index_base: var(variable),
+            },
+            Paint::Translate {
+                paint: solid[1],
+                dx: 10,
+                dy: 20,
+                var_index_base: var(variable),
+            },
+        ]);
+        for center in [None, Some((50, 60))] {
+            layers.extend([
+                Paint::Scale {
+                    paint: solid[0],
+                    scale_x: 0.5,
+                    scale_y: 1.5,
+                    center,
+                    var_index_base: var(variable),
+                },
+                Paint::ScaleUniform {
+                    paint: solid[0],
+                    scale: 1.5,
+                    center,
+                    var_index_base: var(variable),
+                },
+                Paint::Rotate {
+                    paint: solid[0],
+                    angle: 0.5,
+                    center,
+                    var_index_base: var(variable),
+                },
+                Paint::Skew {
+                    paint: solid[0],
+                    x_skew_angle: 0.25,
+                    y_skew_angle: -0.25,
+                    center,
+                    var_index_base: var(variable),
+                },
+            ]);
+        }
+    }
+    layers.extend([
+        Paint::Glyph {
+            paint: solid[0],
+            glyph_id: 7,
+        },
+        Paint::ColrGlyph { glyph_id: 30 },
+        Paint::Composite {
+            source: solid[0],
+            mode: CompositeMode::Luminosity,
+            backdrop: solid[1],
+        },
+    ]);
+
+    let layer_list = layers
+        .into_iter()
+        .map(|paint| push(&mut paints, paint))
+        .collect();
+
+    let gradient = push(
+        &mut paints,
+        Paint::LinearGradient {
+            color_line: line(Extend::Pad, false),
+            x0: 0,
+            y0: 0,
+            x1: 100,
+            y1: 100,
+            x2: 0,
+            y2: 100,
+            var_index_base: None,
+        },
+    );
+    let gradient_glyph = push(
+        &mut paints,
+        Paint::Glyph {
+            paint: gradient,
+            glyph_id: 8,
+        },
+    );
+    let backdrop = push(
+        &mut paints,
+        Paint::Translate {
+            paint: gradient_glyph,
+            dx: 10,
+            dy: 0,
+            var_index_base: None,
+        },
+    );
+    let source = push(
+        &mut paints,
+        Paint::Glyph {
+            paint: solid[0],
+            glyph_id: 7,
+        },
+    );
+    let foreground = push(
+        &mut paints,
+        Paint::Solid {
+            palette_index: 0xFFFF,
+            alpha: 1.0,
+            var_index_base: Some(2),
+        },
+    );
+    let foreground_glyph = push(
+        &mut paints,
+        Paint::Glyph {
+            paint: foreground,
+            glyph_id: 9,
+        },
+    );
+    let roots = [
+        Paint::Composite {
+            source,
+            mode: CompositeMode::Multiply,
+            backdrop,
+        },
+        Paint::Translate {
+            paint: foreground_glyph,
+            dx: 0,
+            dy: 0,
+            var_index_base: Some(0),
+        },
+        Paint::ColrGlyph { glyph_id: 41 },
+        Paint::ColrGlyph { glyph_id: 40 },
+        Paint::ColrLayers {
+            first_layer_index: 0,
+            num_layers: 1,
+        },
+    ];
+    let base_glyph_paints = [30, 31, 40, 41, 42]
+        .into_iter()
+        .zip(roots)
+        .map(|(glyph_id, paint)| (glyph_id, push(&mut paints, paint)))
+        .collect();
+
+    ColrTable {
+        version: 1,
+        base_glyphs: Default::default(),
+        base_glyph_paints,
+        layer_list,
+        paints,
+        clips: vec![Clip {
+            start_glyph_id: 30,
+            end_glyph_id: 30,
+            clip_box: ClipBox {
+                x_min: 0,
+                y_min: -10,
+                x_max: 200,
+                y_max: 300,
+                var_index_base: None,
+            },
+        }],
+        // Variation indices 0 and 1 move by 100 at the axis maximum, index 2
+        // halves the alpha.
+        var_index_map: Some(DeltaSetIndexMap {
+            entries: vec![(0, 0), (0, 0), (0, 1)],
+        }),
+        item_variation_store: Some(ItemVariationStore {
+            format: 1,
+            axis_count: 1,
+            regions: vec![VariationRegion {
+                axes: vec![RegionAxis::new(0.0, 1.0, 1.0)],
+            }],
+            data: vec![ItemVariationData {
+                region_indexes: vec![0],
+                delta_sets: vec![vec![100], vec![-8192]],
+            }],
+        }),
+    }
+}
+
+#[test]
+fn test_colr_v1_font() {
+    use aurora_font::components::cpal::Color;
+
+    let (colr, cpal) = read_colr_1_variable();
+    assert_eq!(colr.version, 1);
+    assert_eq!(colr.base_glyph_paints.len(), 200);
+    assert_eq!(colr.layer_list.len(), 71);
+    assert_eq!(colr.clips.len(), 13);
+    assert!(colr.item_variation_store.is_some());
+
+    let black = Color::rgba(0, 0, 0, 255);
+    let paint = |glyph_id, coords: &[f32]| {
+        let mut recorder = Recorder::default();
+        let painted = colr.paint_glyph(glyph_id, &cpal, 0, black, coords, &mut recorder);
+        assert!(recorder.balanced());
+        (painted, recorder.ops)
+    };
+    let axes = |values: &[(usize, f32)]| {
+        let mut coords = vec![0.0; 44];
+        for &(axis, value) in values {
+            coords[axis] = value;
+        }
+        coords
+    };
+    let red = "Color { red: 255, green: 0, blue: 0, alpha: 255 }";
+    let blue = "Color { red: 0, green: 0, blue: 255, alpha: 255 }";
+
+    // A repeating linear gradient inside a clip box.
+    let (painted, ops) = paint(9, &[]);
+    assert_eq!(painted, Ok(true));
+    assert_eq!(
+        ops,
+        [
+            "clip Box { x_min: 100.0, y_min: 250.0, x_max: 900.0, y_max: 950.0 }".to_string(),
+            "clip Glyph(9)".into(),
+            format!(
+                "fill LinearGradient {{ p0: (100.0, 250.0), p1: (900.0, 250.0), stops: \
+                [GradientStop {{ offset: 0.2000122, color: {red} }}, \
+                GradientStop {{ offset: 0.7999878, color: {blue} }}], extend: Repeat }}"
+            ),
+            "pop clip".into(),
+            "pop clip".into(),
+        ]
+    );
+
+    // Scaling about the center, composited under a translucent square.
+    let (_, ops) = paint(84, &[]);
+    assert_eq!(
+        ops,
+        [
+            "layer SrcOver",
+            "clip Glyph(3)",
+            "fill Solid(Color { red: 0, green: 0, blue: 255, alpha: 128 })",
+            "pop clip",
+            "layer DestOver",
+            "transform 0.5 0 0 1.5 250 -250",
+            "clip Glyph(3)",
+            "fill Solid(Color { red: 255, green: 165, blue: 0, alpha: 179 })",
+            "pop clip",
+            "pop transform",
+            "pop layer",
+            "pop layer",
+        ]
+    );
+
+    // Radial gradients keep both circles.
+    let (_, ops) = paint(93, &[]);
+    assert!(ops[2].starts_with(
+        "fill RadialGradient { c0: (166.0, 768.0), r0: 0.0, c1: (166.0, 768.0), r1: 256.0"
+    ));
+    assert!(paint(131, &[]).1.contains(&"layer Xor".to_string()));
+
+    // Sweep angles vary with the SWPS and SWPE axes, a quarter turn each.
+    let sweep = |coords: &[f32]| {
+        let (_, ops) = paint(13, coords);
+        ops[2].split(", stops").next().unwrap().to_string()
+    };
+    assert_eq!(
+        sweep(&[]),
+        "fill SweepGradient { center: (500.0, 600.0), start_angle: -120.00366, end_angle: 120.00366"
+    );
+    assert_eq!(
+        sweep(&axes(&[(0, 0.5), (1, -0.5)])),
+        "fill SweepGradient { center: (500.0, 600.0), start_angle: -75.00366, end_angle: 75.00366"
+    );
+    // The TLDX axis moves the translation by up to 500 units.
+    assert_eq!(paint(114, &[]).1[5], "transform 1 0 0 1 0 100");
+    assert_eq!(
+        paint(114, &axes(&[(34, 0.2)])).1[5],
+        "transform 1 0 0 1 100 100"
+    );
+
+    // Glyph 179 paints itself.
+    assert!(paint(179, &[]).0.is_err());
+    // Version 0 glyphs are painted layer by layer.
+    let (_, ops) = paint(168, &[]);
+    assert_eq!(ops.len(), 8 * 3);
+    assert_eq!(
+        ops[..3],
+        ["clip Glyph(176)", &format!("fill Solid({red})"), "pop clip"]
+    );
+}
+
+#[test]
+fn test_colr_v1_paint() {
+    use aurora_font::components::cpal::Color;
+
+    let colr = build_colr_v1();
+    let cpal = build_cpal();
+    let black = Color::rgba(0, 0, 0, 255);
+    let paint = |glyph_id, coords: &[f32]| {
+        let mut recorder = Recorder::default();
+        let painted = colr.paint_glyph(glyph_id, &cpal, 0, black, coords, &mut recorder);
+        (painted, recorder.ops)
+    };
+
+    let (painted, ops) = paint(30, &[]);
+    assert_eq!(painted, Ok(true));
+    // The gradient runs along the normal of p0-p2, with sorted stops.
+    let gradient = "fill LinearGradient { p0: (0.0, 0.0), p1: (100.0, 0.0), stops: \
+        [GradientStop { offset: 0.0, color: Color { red: 255, green: 0, blue: 0, alpha: 128 } }, \
+        GradientStop { offset: 1.0, color: Color { red: 0, green: 0, blue: 255, alpha: 128 } }], \
+        extend: Pad }";
+    let red = "fill Solid(Color { red: 255, green: 0, blue: 0, alpha: 255 })";
+    assert_eq!(
+        ops,
+        vec![
+            "clip Box { x_min: 0.0, y_min: -10.0, x_max: 200.0, y_max: 300.0 }",
+            "layer SrcOver",
+            "transform 1 0 0 1 10 0",
+            "clip Glyph(8)",
+            gradient,
+            "pop clip",
+            "pop transform",
+            "layer Multiply",
+            "clip Glyph(7)",
+            red,
+            "pop clip",
+            "pop layer",
+            "pop layer",
+            "pop clip",
+        ]
+    );
+
+    // Variable translation and foreground alpha.
+    let (_, ops) = paint(31, &[0.5]);
+    assert_eq!(ops[0], "transform 1 0 0 1 50 50");
+    assert_eq!(
+        ops[2],
+        "fill Solid(Color { red: 0, green: 0, blue: 0, alpha: 191 })"
+    );
+    let (_, ops) = paint(31, &[]);
+    assert_eq!(ops[0], "transform 1 0 0 1 0 0");
+
+    // Cycles through base glyphs and through the layer list are rejected.
+    assert!(paint(40, &[]).0.is_err());
+    assert!(paint(42, &[]).0.is_ok());
+    let mut colr = colr.clone();
+    colr.layer_list[2] = colr.layer_list[0];
+    let mut recorder = Recorder::default();
+    assert!(
+        colr.paint_glyph(42, &cpal, 0, black, &[], &mut recorder)
+            .is_err()
+    );
+    // Painting stopped part way, with every push popped.
+    assert!(!recorder.ops.is_empty());
+    assert!(recorder.balanced());
+
+    assert_eq!(paint(5, &[]), (Ok(false), vec![]));
+}
+
+#[test]
+fn test_transform() {
+    use aurora_font::components::colr::Transform;
+
+    let rotate = Transform::rotate(std::f32::consts::FRAC_PI_2).around((10.0, 0.0));
+    let (x, y) = rotate.apply((20.0, 0.0));
+    assert!((x - 10.0).abs() < 1e-4 && (y - 10.0).abs() < 1e-4);
+    let skew = Transform::skew(std::f32::consts::FRAC_PI_4, 0.0);
+    assert_eq!(skew.apply((0.0, 10.0)), (-10.0, 10.0));
+    let scale = Transform::scale(2.0, 3.0).multiply(&Transform::translate(1.0, 1.0));
+    assert_eq!(scale.apply((0.0, 0.0)), (2.0, 3.0));
+}
+
+#[test]
+fn test_colr_shared_paints() {
+    use aurora_font::components::colr::read_colr;
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    // A version 1 header whose base glyph list holds glyph 1, followed by a
+    // chain of 24 PaintComposite tables whose source and backdrop both point
+    // at the next one, ending in a PaintSolid.
+    const CHAIN: u32 = 24;
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(1);
+    writer.write_u16(0);
+    writer.write_u32(0);
+    writer.write_u32(0);
+    writer.write_u16(0);
+    writer.write_u32(34);
+    writer.write_zeros(16);
+    writer.write_u32(1);
+    writer.write_u16(1);
+    writer.write_u32(10);
+    for _ in 0..CHAIN {
+        writer.write_u8(32);
+        writer.write_u24(8);
+        writer.write_u8(3);
+        writer.write_u24(8);
+    }
+    writer.write_u8(2);
+    writer.write_u16(0);
+    writer.write_f2dot14(1.0);
+    let bytes = writer.into_bytes().unwrap();
+
+    let colr = read_colr(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(colr.paints.len(), CHAIN as usize + 1);
+    // Painting expands the graph to 2^25 paints and stops at the limit.
+    let mut recorder = Recorder::default();
+    let black = aurora_font::components::cpal::Color::rgba(0, 0, 0, 255);
+    assert!(
+        colr.paint_glyph(1, &build_cpal(), 0, black, &[], &mut recorder)
+            .is_err()
+    );
+    assert!(recorder.balanced());
+}
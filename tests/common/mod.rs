@@ -0,0 +1,28 @@
+//! Helpers shared by the integration tests.
+
+/// Builds a minimal SNFT font with the given tables.
+pub fn build_snft(tables: &[([u8; 4], Vec<u8>)]) -> Vec<u8> {
+    use aurora_font::common::snft::SnftTableEntry;
+    use aurora_font::io::FontDataWriter;
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u32(0x00010000);
+    writer.write_u16(tables.len() as u16);
+    writer.write_u16(0);
+    writer.write_u16(0);
+    writer.write_u16(0);
+    let mut offset = 12 + 16 * tables.len() as u32;
+    for (tag, data) in tables {
+        let entry = SnftTableEntry::new(*tag, 0, 0, 0);
+        writer.write_tag(*tag);
+        writer.write_u32(entry.compute_checksum(data));
+        writer.write_u32(offset);
+        writer.write_u32(data.len() as u32);
+        offset += (data.len() as u32 + 3) & !3;
+    }
+    for (_, data) in tables {
+        writer.write_bytes(data);
+        writer.align(4);
+    }
+    writer.into_bytes().unwrap()
+}
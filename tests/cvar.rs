@@ -0,0 +1,77 @@
+//! Tests for control value table variations.
+
+/// A one-axis `cvar` laid out as in the specification: a tuple at the
+/// maximum using shared point numbers 0 and 2, and an intermediate tuple
+/// peaking at 0.5 with deltas for every control value.
+#[rustfmt::skip]
+const CVAR: [u8; 37] = [
+    0, 1, 0, 0,             // majorVersion, minorVersion
+    0x80, 2,                // SHARED_POINT_NUMBERS | tupleVariationCount 2
+    0, 24,                  // dataOffset
+    0, 3, 0x80, 0,          // variationDataSize 3, EMBEDDED_PEAK_TUPLE
+    0x40, 0,                // peak 1.0
+    0, 6, 0xE0, 0,          // variationDataSize 6, peak, intermediate, private points
+    0x20, 0, 0, 0, 0x40, 0, // peak 0.5, start 0.0, end 1.0
+    2, 1, 0, 2,             // shared points: 2 points, one run of 2 bytes: 0, +2
+    1, 20, 0xF8,            // deltas: one run of 2 bytes: 20, -8
+    0,                      // private points: all
+    0, 4, 0x81, 0, 4,       // deltas: 4, two zeros, 4
+];
+
+#[test]
+fn test_read_cvt() {
+    use aurora_font::components::cvt::read_cvt;
+    use aurora_font::io::FontDataStream;
+
+    let cvt = read_cvt(&mut FontDataStream::new(&[
+        0, 100, 0, 50, 0, 10, 0xFF, 0xEC,
+    ]))
+    .unwrap();
+    assert_eq!(cvt.values, [100, 50, 10, -20]);
+    // A trailing odd byte is not a value.
+    let cvt = read_cvt(&mut FontDataStream::new(&[0, 100, 0])).unwrap();
+    assert_eq!(cvt.values, [100]);
+}
+
+#[test]
+fn test_read_cvar() {
+    use aurora_font::components::cvar::read_cvar;
+    use aurora_font::io::FontDataStream;
+
+    let cvar = read_cvar(&mut FontDataStream::new(&CVAR), 1).unwrap();
+    assert_eq!((cvar.major_version, cvar.minor_version), (1, 0));
+    let [maximum, intermediate] = cvar.variations.as_slice() else {
+        panic!("expected two tuples");
+    };
+    assert_eq!(maximum.peak, [1.0]);
+    assert_eq!(maximum.intermediate, None);
+    assert_eq!(maximum.point_numbers, Some(vec![0, 2]));
+    assert_eq!(maximum.deltas, [vec![20, -8]]);
+    assert_eq!(intermediate.peak, [0.5]);
+    assert_eq!(intermediate.intermediate, Some((vec![0.0], vec![1.0])));
+    assert_eq!(intermediate.point_numbers, None);
+    assert_eq!(intermediate.deltas, [vec![4, 0, 0, 4]]);
+
+    // A major version other than 1 is rejected.
+    let mut data = CVAR;
+    data[1] = 2;
+    assert!(read_cvar(&mut FontDataStream::new(&data), 1).is_err());
+}
+
+#[test]
+fn test_cvt_at() {
+    use aurora_font::components::cvar::read_cvar;
+    use aurora_font::components::cvt::CvtTable;
+    use aurora_font::io::FontDataStream;
+
+    let cvar = read_cvar(&mut FontDataStream::new(&CVAR), 1).unwrap();
+    let cvt = CvtTable {
+        values: vec![100, 50, 10, -20],
+    };
+    assert_eq!(cvt.cvt_at(&cvar, &[0.0]), [100.0, 50.0, 10.0, -20.0]);
+    // Control values without a delta in a sparse tuple are not interpolated.
+    assert_eq!(cvt.cvt_at(&cvar, &[1.0]), [120.0, 50.0, 2.0, -20.0]);
+    assert_eq!(cvt.cvt_at(&cvar, &[0.5]), [114.0, 50.0, 6.0, -16.0]);
+    assert_eq!(cvt.cvt_at(&cvar, &[0.25]), [107.0, 50.0, 8.0, -18.0]);
+    assert_eq!(cvt.cvt_at(&cvar, &[-1.0]), [100.0, 50.0, 10.0, -20.0]);
+}
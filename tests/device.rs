@@ -0,0 +1,206 @@
+//! Tests for the `hdmx`, `VDMX` and `LTSH` device-metric tables.
+
+/// Builds device metrics for a three-glyph font: `gasp` disables
+/// anti-aliasing up to 8 ppem, `hdmx` has 12 and 16 ppem records, `VDMX`
+/// covers square pixels from 10 to 12 ppem and `LTSH` makes glyph 1 linear
+/// from 14 ppem.
+fn build_metrics() -> aurora_font::components::device::DeviceMetrics {
+    use aurora_font::components::device::DeviceMetrics;
+    use aurora_font::components::gasp::{GASP_DOGRAY, GASP_GRIDFIT, GaspRange, GaspTable};
+    use aurora_font::components::hdmx::{DeviceRecord, HdmxTable};
+    use aurora_font::components::ltsh::LtshTable;
+    use aurora_font::components::vdmx::{VdmxEntry, VdmxGroup, VdmxRatio, VdmxTable};
+
+    let entry = |y_pel_height: u16, y_max: i16, y_min: i16| VdmxEntry {
+        y_pel_height,
+        y_max,
+        y_min,
+    };
+    DeviceMetrics {
+        gasp: Some(GaspTable {
+            version: 1,
+            ranges: vec![
+                GaspRange {
+                    range_max_ppem: 8,
+                    range_gasp_behavior: GASP_GRIDFIT,
+                },
+                GaspRange {
+                    range_max_ppem: 0xFFFF,
+                    range_gasp_behavior: GASP_GRIDFIT | GASP_DOGRAY,
+                },
+            ],
+        }),
+        hdmx: Some(HdmxTable {
+            version: 0,
+            records: vec![
+                DeviceRecord {
+                    pixel_size: 12,
+                    max_width: 7,
+                    widths: vec![0, 7, 5],
+                },
+                DeviceRecord {
+                    pixel_size: 16,
+                    max_width: 9,
+                    widths: vec![0, 9, 6],
+                },
+            ],
+        }),
+        vdmx: Some(VdmxTable {
+            version: 1,
+            ratios: vec![
+                VdmxRatio {
+                    char_set: 1,
+                    x_ratio: 2,
+                    y_start_ratio: 1,
+                    y_end_ratio: 1,
+                    group_index: 0,
+                },
+                VdmxRatio {
+                    char_set: 1,
+                    x_ratio: 1,
+                    y_start_ratio: 1,
+                    y_end_ratio: 1,
+                    group_index: 1,
+                },
+                VdmxRatio {
+                    char_set: 1,
+                    x_ratio: 0,
+                    y_start_ratio: 0,
+                    y_end_ratio: 0,
+                    group_index: 0,
+                },
+            ],
+            groups: vec![
+                VdmxGroup {
+                    start_size: 10,
+                    end_size: 10,
+                    entries: vec![entry(10, 5, -2)],
+                },
+                VdmxGroup {
+                    start_size: 10,
+                    end_size: 12,
+                    entries: vec![entry(10, 9, -3), entry(11, 10, -3), entry(12, 11, -4)],
+                },
+            ],
+        }),
+        ltsh: Some(LtshTable {
+            version: 0,
+            y_pels: vec![1, 14, 0],
+        }),
+    }
+}
+
+#[test]
+fn test_device_tables_from_bytes() {
+    use aurora_font::components::hdmx::read_hdmx;
+    use aurora_font::components::ltsh::read_ltsh;
+    use aurora_font::components::vdmx::read_vdmx;
+    use aurora_font::io::stream::FontDataStream;
+
+    let metrics = build_metrics();
+
+    // Three widths plus the size and maximum pad to eight bytes per record.
+    #[rustfmt::skip]
+    let hdmx_data = [
+        0, 0, // version
+        0, 2, // numRecords
+        0, 0, 0, 8, // sizeDeviceRecord
+        12, 7, 0, 7, 5, 0, 0, 0, // pixelSize, maxWidth, widths, padding
+        16, 9, 0, 9, 6, 0, 0, 0,
+    ];
+    let hdmx = read_hdmx(&mut FontDataStream::new(&hdmx_data), 3).unwrap();
+    assert_eq!(Some(&hdmx), metrics.hdmx.as_ref());
+    assert_eq!(hdmx.advance_width(16, 2), Some(6));
+    assert_eq!(hdmx.advance_width(13, 2), None);
+
+    // The 2:1 and catch-all ratios share the group at offset 24.
+    #[rustfmt::skip]
+    let vdmx_data = [
+        0, 1, // version
+        0, 2, // numRecs
+        0, 3, // numRatios
+        1, 2, 1, 1, // ratRange: bCharSet, xRatio, yStartRatio, yEndRatio
+        1, 1, 1, 1,
+        1, 0, 0, 0,
+        0, 24, 0, 34, 0, 24, // offsets
+        0, 1, 10, 10, // 24: recs, startsz, endsz
+        0, 10, 0, 5, 0xFF, 0xFE, // yPelHeight, yMax, yMin
+        0, 3, 10, 12, // 34
+        0, 10, 0, 9, 0xFF, 0xFD,
+        0, 11, 0, 10, 0xFF, 0xFD,
+        0, 12, 0, 11, 0xFF, 0xFC,
+    ];
+    let vdmx = read_vdmx(&mut FontDataStream::new(&vdmx_data)).unwrap();
+    assert_eq!(Some(&vdmx), metrics.vdmx.as_ref());
+
+    #[rustfmt::skip]
+    let ltsh_data = [
+        0, 0, // version
+        0, 3, // numGlyphs
+        1, 14, 0, // yPels
+    ];
+    let ltsh = read_ltsh(&mut FontDataStream::new(&ltsh_data)).unwrap();
+    assert_eq!(Some(&ltsh), metrics.ltsh.as_ref());
+}
+
+#[test]
+fn test_device_tables_reject_invalid() {
+    use aurora_font::components::hdmx::read_hdmx;
+    use aurora_font::components::ltsh::read_ltsh;
+    use aurora_font::components::vdmx::read_vdmx;
+    use aurora_font::io::stream::FontDataStream;
+
+    // Records too short for the glyph count.
+    let hdmx = [0, 0, 0, 1, 0, 0, 0, 4, 12, 7, 0, 0];
+    assert!(read_hdmx(&mut FontDataStream::new(&hdmx), 3).is_err());
+    assert!(read_ltsh(&mut FontDataStream::new(&[0, 1, 0, 0])).is_err());
+    assert!(read_vdmx(&mut FontDataStream::new(&[0, 2, 0, 0, 0, 0])).is_err());
+
+    // A ratio whose group lies past the end of the table.
+    let vdmx = [0, 1, 0, 1, 0, 1, 1, 1, 1, 1, 0, 64];
+    assert!(read_vdmx(&mut FontDataStream::new(&vdmx)).is_err());
+}
+
+#[test]
+fn test_vdmx_ratio_selection() {
+    let vdmx = build_metrics().vdmx.unwrap();
+
+    // Square pixels skip the 2:1 range and use the 1:1 group.
+    assert_eq!(vdmx.bounds(10, 1, 1), Some((9, -3)));
+    assert_eq!(vdmx.bounds(12, 96, 96), Some((11, -4)));
+    // Pixels twice as tall as wide match the 2:1 range.
+    assert_eq!(vdmx.bounds(10, 1, 2), Some((5, -2)));
+    // Other ratios fall through to the catch-all range.
+    assert_eq!(vdmx.bounds(10, 3, 1), Some((5, -2)));
+    assert_eq!(vdmx.bounds(11, 3, 1), None);
+}
+
+#[test]
+fn test_rendering_hints() {
+    use aurora_font::components::device::DeviceMetrics;
+
+    let metrics = build_metrics();
+
+    let small = metrics.rendering_hints(8);
+    assert!(small.gridfit);
+    assert!(!small.grayscale);
+    assert_eq!(small.device_widths, None);
+    assert_eq!(small.vertical_bounds, None);
+
+    let hints = metrics.rendering_hints(12);
+    assert!(hints.gridfit && hints.grayscale);
+    assert!(!hints.symmetric_gridfit && !hints.symmetric_smoothing);
+    assert_eq!(hints.device_width(1), Some(7));
+    assert_eq!(hints.device_width(3), None);
+    assert_eq!(hints.vertical_bounds, Some((11, -4)));
+    assert!(hints.is_linear(0));
+    assert!(!hints.is_linear(1));
+    assert!(!hints.is_linear(2));
+    assert!(metrics.rendering_hints(14).is_linear(1));
+
+    // Without any tables, outlines are hinted and anti-aliased.
+    let default = DeviceMetrics::default().rendering_hints(12);
+    assert!(default.gridfit && default.grayscale);
+    assert!(default.linear.is_empty());
+    assert!(!default.is_linear(0));
+}
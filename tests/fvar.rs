@@ -0,0 +1,135 @@
+//! Tests for variation axes, named instances and coordinate normalization.
+
+/// Builds an `fvar` table with a `wght` axis (100, 400, 900) and a hidden
+/// `opsz` axis (8, 12, 72).
+fn build_fvar() -> aurora_font::components::fvar::FvarTable {
+    use aurora_font::components::fvar::{FvarTable, HIDDEN_AXIS, NamedInstance, VariationAxis};
+
+    let axis = |tag, min_value, default_value, max_value, flags, axis_name_id| VariationAxis {
+        tag,
+        min_value,
+        default_value,
+        max_value,
+        flags,
+        axis_name_id,
+    };
+    FvarTable {
+        major_version: 1,
+        minor_version: 0,
+        axes: vec![
+            axis(*b"wght", 100.0, 400.0, 900.0, 0, 256),
+            axis(*b"opsz", 8.0, 12.0, 72.0, HIDDEN_AXIS, 257),
+        ],
+        instances: vec![
+            NamedInstance {
+                subfamily_name_id: 258,
+                flags: 0,
+                coordinates: vec![700.0, 12.0],
+                post_script_name_id: Some(259),
+            },
+            NamedInstance {
+                subfamily_name_id: 260,
+                flags: 0,
+                coordinates: vec![400.0, 12.0],
+                post_script_name_id: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_fvar_round_trip() {
+    use aurora_font::components::fvar::{read_fvar, write_fvar};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let fvar = build_fvar();
+    let mut writer = FontDataWriter::new();
+    write_fvar(&mut writer, &fvar);
+    let bytes = writer.into_bytes().unwrap();
+    let parsed = read_fvar(&mut FontDataStream::new(&bytes)).unwrap();
+
+    assert!(!parsed.axes[0].is_hidden());
+    assert!(parsed.axes[1].is_hidden());
+    assert_eq!(parsed.instances[0].post_script_name_id, Some(259));
+    // Instances without a PostScript name are written as 0xFFFF.
+    assert_eq!(parsed.instances[1].post_script_name_id, Some(0xFFFF));
+    assert_eq!(parsed.instances[0].coordinates, vec![700.0, 12.0]);
+    assert_eq!(parsed.axis_index(b"opsz"), Some(1));
+    assert_eq!(parsed.default_coords(), vec![400.0, 12.0]);
+}
+
+#[test]
+fn test_normalize() {
+    use aurora_font::components::avar::AvarTable;
+
+    let fvar = build_fvar();
+    assert_eq!(fvar.normalize(&[400.0, 12.0], None), vec![0.0, 0.0]);
+    assert_eq!(fvar.normalize(&[250.0, 42.0], None), vec![-0.5, 0.5]);
+    // Out-of-range values are clamped and missing values take the default.
+    assert_eq!(fvar.normalize(&[1000.0], None), vec![1.0, 0.0]);
+
+    let avar = AvarTable {
+        major_version: 1,
+        minor_version: 0,
+        segment_maps: vec![
+            vec![(-1.0, -1.0), (0.0, 0.0), (0.5, 0.25), (1.0, 1.0)],
+            vec![],
+        ],
+        axis_index_map: None,
+        variation_store: None,
+    };
+    assert_eq!(fvar.normalize(&[650.0, 42.0], Some(&avar)), vec![0.25, 0.5]);
+    assert_eq!(
+        fvar.normalize(&[775.0, 12.0], Some(&avar)),
+        vec![0.625, 0.0]
+    );
+}
+
+#[test]
+fn test_avar_version_2() {
+    use aurora_font::components::avar::{AvarTable, read_avar, write_avar};
+    use aurora_font::components::variation::{
+        DeltaSetIndexMap, ItemVariationData, ItemVariationStore, RegionAxis, VariationRegion,
+    };
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    // At full weight the optical size moves by +0.5 (8192 in F2Dot14 units).
+    let avar = AvarTable {
+        major_version: 2,
+        minor_version: 0,
+        segment_maps: vec![vec![], vec![]],
+        axis_index_map: Some(DeltaSetIndexMap {
+            entries: vec![(0xFFFF, 0xFFFF), (0, 0)],
+        }),
+        variation_store: Some(ItemVariationStore {
+            format: 1,
+            axis_count: 2,
+            regions: vec![VariationRegion {
+                axes: vec![RegionAxis::new(0.0, 1.0, 1.0), RegionAxis::default()],
+            }],
+            data: vec![ItemVariationData {
+                region_indexes: vec![0],
+                delta_sets: vec![vec![8192]],
+            }],
+        }),
+    };
+    let mut writer = FontDataWriter::new();
+    write_avar(&mut writer, &avar).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let parsed = read_avar(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(parsed, avar);
+
+    let fvar = build_fvar();
+    assert_eq!(
+        fvar.normalize(&[900.0, 12.0], Some(&parsed)),
+        vec![1.0, 0.5]
+    );
+    assert_eq!(
+        fvar.normalize(&[650.0, 12.0], Some(&parsed)),
+        vec![0.5, 0.25]
+    );
+    assert_eq!(
+        fvar.normalize(&[900.0, 72.0], Some(&parsed)),
+        vec![1.0, 1.0]
+    );
+}
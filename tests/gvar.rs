@@ -0,0 +1,145 @@
+//! Tests for glyph variations.
+
+/// Builds a square glyph with corners at 0 and 100 on a single contour.
+fn square() -> aurora_font::components::glyf::Glyph {
+    use aurora_font::components::glyf::{Glyph, GlyphHeader, GlyphPoint, SimpleGlyph};
+
+    Glyph::Simple(SimpleGlyph {
+        header: GlyphHeader {
+            number_of_contours: 1,
+            x_min: 0,
+            y_min: 0,
+            x_max: 100,
+            y_max: 100,
+        },
+        end_points: vec![3],
+        instructions: vec![],
+        points: [(0, 0), (0, 100), (100, 100), (100, 0)]
+            .map(|(x, y)| GlyphPoint {
+                x,
+                y,
+                on_curve: true,
+            })
+            .to_vec(),
+        overlap: false,
+    })
+}
+
+/// Builds a two-axis `gvar` for two square glyphs: a dense tuple at
+/// `wght` 1, a sparse tuple at `wdth` -1 and an intermediate tuple.
+fn build_gvar() -> aurora_font::components::gvar::GvarTable {
+    use aurora_font::components::gvar::GvarTable;
+    use aurora_font::components::variation::TupleVariation;
+
+    let tuples = vec![
+        TupleVariation {
+            peak: vec![1.0, 0.0],
+            intermediate: None,
+            point_numbers: None,
+            deltas: vec![
+                vec![-10, -10, 10, 10, 0, 20, 0, 0],
+                vec![0, 0, 0, 0, 0, 0, 0, 0],
+            ],
+        },
+        // Only the top right corner and the right phantom point move.
+        TupleVariation {
+            peak: vec![0.0, -1.0],
+            intermediate: None,
+            point_numbers: Some(vec![2, 5]),
+            deltas: vec![vec![-40, -40], vec![0, 0]],
+        },
+        TupleVariation {
+            peak: vec![0.5, 0.0],
+            intermediate: Some((vec![0.0, 0.0], vec![1.0, 0.0])),
+            point_numbers: Some(vec![0, 1, 2, 3]),
+            deltas: vec![vec![0, 0, 0, 0], vec![0, 8, 8, 0]],
+        },
+    ];
+    GvarTable {
+        major_version: 1,
+        minor_version: 0,
+        axis_count: 2,
+        glyph_variations: vec![tuples.clone(), vec![], tuples],
+    }
+}
+
+#[test]
+fn test_gvar_round_trip() {
+    use aurora_font::components::gvar::{read_gvar, write_gvar};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let gvar = build_gvar();
+    let mut writer = FontDataWriter::new();
+    write_gvar(&mut writer, &gvar).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let parsed = read_gvar(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(parsed, gvar);
+    assert!(parsed.variations(1).is_empty());
+    assert!(parsed.variations(7).is_empty());
+}
+
+#[test]
+fn test_interpolate_untouched() {
+    use aurora_font::components::gvar::interpolate_untouched;
+
+    // A contour of three points on a line, with the middle one untouched,
+    // followed by a phantom point outside every contour.
+    let points = [(0.0, 0.0), (50.0, 0.0), (100.0, 0.0), (0.0, 0.0)];
+    let touched = [true, false, true, false];
+    let mut deltas = [(10.0, 0.0), (0.0, 0.0), (30.0, 4.0), (0.0, 0.0)];
+    interpolate_untouched(&points, &[2], &touched, &mut deltas);
+    assert_eq!(deltas[1], (20.0, 0.0));
+    assert_eq!(deltas[3], (0.0, 0.0));
+
+    // A single touched point moves the whole contour.
+    let touched = [false, true, false, false];
+    let mut deltas = [(0.0, 0.0), (5.0, -5.0), (0.0, 0.0), (0.0, 0.0)];
+    interpolate_untouched(&points, &[2], &touched, &mut deltas);
+    assert_eq!(deltas[..3], [(5.0, -5.0); 3]);
+}
+
+#[test]
+fn test_vary_glyph() {
+    use aurora_font::components::glyf::Glyph;
+    use aurora_font::components::gvar::vary_glyph;
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric};
+
+    let gvar = build_gvar();
+    let hmtx = HmtxTable {
+        h_metrics: vec![
+            LongHorMetric {
+                advance_width: 120,
+                lsb: 0,
+            };
+            3
+        ],
+        left_side_bearings: vec![],
+    };
+    let points = |glyph: &Glyph| match glyph {
+        Glyph::Simple(simple) => simple.points.iter().map(|p| (p.x, p.y)).collect(),
+        _ => Vec::new(),
+    };
+
+    let varied = vary_glyph(&square(), &gvar, &hmtx, None, 0, &[0.0, 0.0]);
+    assert_eq!(varied.glyph, square());
+    assert_eq!(varied.advance_width(), 120.0);
+
+    // The dense tuple at full weight, plus the intermediate tuple at half its peak.
+    let varied = vary_glyph(&square(), &gvar, &hmtx, None, 0, &[0.75, 0.0]);
+    assert_eq!(
+        points(&varied.glyph),
+        vec![(-8, 0), (-8, 104), (108, 104), (108, 0)]
+    );
+    assert_eq!(varied.advance_width(), 135.0);
+    let header = varied.glyph.header().unwrap();
+    assert_eq!((header.x_min, header.y_max), (-8, 104));
+
+    // The only touched point of the sparse tuple moves the whole contour.
+    let varied = vary_glyph(&square(), &gvar, &hmtx, None, 2, &[0.0, -0.5]);
+    assert_eq!(
+        points(&varied.glyph),
+        vec![(-20, 0), (-20, 100), (80, 100), (80, 0)]
+    );
+    assert_eq!(varied.advance_width(), 100.0);
+    assert_eq!(varied.advance_height(), 0.0);
+}
@@ -0,0 +1,259 @@
+//! Tests for the TrueType bytecode interpreter.
+
+/// Builds a hinter for a 1000 unit em at 10 ppem, where one font unit is
+/// 0.64/64 pixel.
+fn hinter(
+    fpgm: &[u8],
+    prep: &[u8],
+    cvt: &[f32],
+    mode: aurora_font::hinting::HintingMode,
+) -> aurora_font::hinting::Hinter {
+    use aurora_font::components::maxp::MaxpTrueType;
+    use aurora_font::hinting::Hinter;
+
+    let maxp = MaxpTrueType {
+        max_points: 16,
+        max_contours: 4,
+        max_composite_points: 16,
+        max_composite_contours: 4,
+        max_zones: 2,
+        max_twilight_points: 4,
+        max_storage: 4,
+        max_function_defs: 4,
+        max_instruction_defs: 0,
+        max_stack_elements: 16,
+        max_size_of_instructions: 64,
+        max_component_elements: 2,
+        max_component_depth: 1,
+    };
+    let mut hinter = Hinter::new(fpgm, prep, cvt, &maxp, 1000, mode).unwrap();
+    hinter.set_ppem(10).unwrap();
+    hinter
+}
+
+/// Builds a single contour glyph 0 with an advance of 1000 units, and
+/// glyph 1 placing glyph 0 at (110, 110) rounded to the grid.
+fn glyphs(
+    points: &[(i16, i16)],
+    instructions: &[u8],
+) -> (
+    aurora_font::components::glyf::GlyfTable,
+    aurora_font::components::hmtx::HmtxTable,
+) {
+    use aurora_font::components::glyf::{
+        ARGS_ARE_XY_VALUES, CompositeGlyph, GlyfTable, Glyph, GlyphComponent, GlyphPoint,
+        ROUND_XY_TO_GRID, SimpleGlyph,
+    };
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric};
+
+    let simple = SimpleGlyph {
+        end_points: vec![points.len() as u16 - 1],
+        instructions: instructions.to_vec(),
+        points: points
+            .iter()
+            .map(|&(x, y)| GlyphPoint {
+                x,
+                y,
+                on_curve: true,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let composite = CompositeGlyph {
+        components: vec![GlyphComponent {
+            flags: ARGS_ARE_XY_VALUES | ROUND_XY_TO_GRID,
+            glyph_index: 0,
+            arg1: 110,
+            arg2: 110,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let hmtx = HmtxTable {
+        h_metrics: vec![LongHorMetric {
+            advance_width: 1000,
+            lsb: 0,
+        }],
+        left_side_bearings: vec![0],
+    };
+    let glyf = GlyfTable {
+        glyphs: vec![Glyph::Simple(simple), Glyph::Composite(composite)],
+    };
+    (glyf, hmtx)
+}
+
+/// Returns the hinted points of glyph 0.
+fn hint(
+    hinter: &mut aurora_font::hinting::Hinter,
+    points: &[(i16, i16)],
+    instructions: &[u8],
+) -> Vec<(i32, i32)> {
+    let (glyf, hmtx) = glyphs(points, instructions);
+    let glyph = hinter.hint_glyph(&glyf, &hmtx, 0).unwrap();
+    glyph.points.iter().map(|p| (p.x, p.y)).collect()
+}
+
+#[test]
+fn test_unhinted_scaling() {
+    use aurora_font::hinting::HintingMode;
+
+    let mut hinter = hinter(&[], &[], &[], HintingMode::V35);
+    let (glyf, hmtx) = glyphs(&[(100, 0), (500, 1000)], &[]);
+    let glyph = hinter.hint_glyph(&glyf, &hmtx, 0).unwrap();
+    assert_eq!(glyph.points[0].x, 64);
+    assert_eq!((glyph.points[1].x, glyph.points[1].y), (320, 640));
+    assert_eq!(glyph.end_points, vec![1]);
+    assert_eq!(glyph.advance_width(), 640);
+}
+
+#[test]
+fn test_mdap_and_iup() {
+    use aurora_font::hinting::HintingMode;
+
+    // SVTCA[y], PUSHB 0, MDAP[rnd], IUP[y]
+    let program = [0x00, 0xB0, 0x00, 0x2F, 0x30];
+    for mode in [HintingMode::V35, HintingMode::V40] {
+        let mut hinter = hinter(&[], &[], &[], mode);
+        // 110 units is 70.4/64 pixel; the untouched point follows the touched one.
+        let points = hint(&mut hinter, &[(100, 110), (300, 110)], &program);
+        assert_eq!(points, vec![(64, 64), (192, 64)]);
+    }
+}
+
+#[test]
+fn test_mirp_with_cvt() {
+    use aurora_font::hinting::HintingMode;
+
+    // SVTCA[y], PUSHB 0, MDAP[rnd], PUSHB 1 0, MIRP[rnd]
+    let program = [0x00, 0xB0, 0x00, 0x2F, 0xB1, 0x01, 0x00, 0xE4];
+    let mut hinter = hinter(&[], &[], &[500.0], HintingMode::V35);
+    // The 480 unit stem is within the cut-in of the 500 unit control value.
+    let points = hint(&mut hinter, &[(0, 0), (0, 480)], &program);
+    assert_eq!(points[1].1, 320);
+
+    // Outside the cut-in the original distance is rounded instead.
+    let points = hint(&mut hinter, &[(0, 0), (0, 300)], &program);
+    assert_eq!(points[1].1, 192);
+}
+
+#[test]
+fn test_iup_interpolation() {
+    use aurora_font::hinting::HintingMode;
+
+    // SVTCA[y], PUSHB 0, MDAP, PUSHB 2 64, SHPIX, IUP[y]
+    let program = [0x00, 0xB0, 0x00, 0x2E, 0xB1, 0x02, 0x40, 0x38, 0x30];
+    let mut hinter = hinter(&[], &[], &[], HintingMode::V35);
+    let points = hint(&mut hinter, &[(0, 0), (0, 500), (0, 1000)], &program);
+    assert_eq!(points, vec![(0, 0), (0, 352), (0, 704)]);
+}
+
+#[test]
+fn test_functions_and_storage() {
+    use aurora_font::hinting::HintingMode;
+
+    // FDEF 0: storage[0] += 1
+    let fpgm = [
+        0xB0, 0x00, 0x2C, 0xB0, 0x00, 0xB0, 0x00, 0x43, 0xB0, 0x01, 0x60, 0x42, 0x2D,
+    ];
+    // LOOPCALL 0 three times.
+    let prep = [0xB1, 0x03, 0x00, 0x2A];
+    // SVTCA[y], PUSHB 0, PUSHB 0, RS, SHPIX, CALL 0
+    let program = [0x00, 0xB0, 0x00, 0xB0, 0x00, 0x43, 0x38, 0xB0, 0x00, 0x2B];
+    let mut hinter = hinter(&fpgm, &prep, &[], HintingMode::V35);
+    assert_eq!(hint(&mut hinter, &[(0, 0)], &program), vec![(0, 3)]);
+    // Storage written by a glyph does not leak into the next one.
+    assert_eq!(hint(&mut hinter, &[(0, 0)], &program), vec![(0, 3)]);
+}
+
+#[test]
+fn test_prep_graphics_state() {
+    use aurora_font::hinting::HintingMode;
+
+    // SVTCA[y], PUSHB 0, SZPS, PUSHB 10, SDB
+    let prep = [0x00, 0xB0, 0x00, 0x16, 0xB0, 0x0A, 0x5E];
+    // PUSHB 0 64, SHPIX, SVTCA[y], PUSHB 0x0F 0 1, DELTAP1
+    let program = [0xB1, 0x00, 0x40, 0x38, 0x00, 0xB2, 0x0F, 0x00, 0x01, 0x5D];
+    let mut hinter = hinter(&[], &prep, &[], HintingMode::V35);
+    // The glyph starts on the x axis in the glyph zone, while the delta base
+    // set by `prep` carries over.
+    assert_eq!(hint(&mut hinter, &[(0, 0)], &program), vec![(64, 64)]);
+}
+
+#[test]
+fn test_deltap_at_size() {
+    use aurora_font::hinting::HintingMode;
+
+    // SVTCA[y], PUSHB 0x1F 0 1, DELTAP1: +1 pixel at 9 + 1 ppem.
+    let program = [0x00, 0xB2, 0x1F, 0x00, 0x01, 0x5D];
+    let mut hinter = hinter(&[], &[], &[], HintingMode::V35);
+    assert_eq!(hint(&mut hinter, &[(0, 0)], &program), vec![(0, 64)]);
+    hinter.set_ppem(11).unwrap();
+    assert_eq!(hint(&mut hinter, &[(0, 0)], &program), vec![(0, 0)]);
+}
+
+#[test]
+fn test_v40_backward_compatibility() {
+    use aurora_font::hinting::HintingMode;
+
+    // SVTCA[x], PUSHB 0, MDAP[rnd]
+    let program = [0x01, 0xB0, 0x00, 0x2F];
+    let mut v35 = hinter(&[], &[], &[], HintingMode::V35);
+    assert_eq!(hint(&mut v35, &[(110, 0)], &program), vec![(64, 0)]);
+    let mut v40 = hinter(&[], &[], &[], HintingMode::V40);
+    assert_eq!(hint(&mut v40, &[(110, 0)], &program), vec![(70, 0)]);
+
+    // INSTCTRL selector 3 declares native ClearType hinting.
+    let prep = [0xB1, 0x04, 0x03, 0x8E];
+    let mut native = hinter(&[], &prep, &[], HintingMode::V40);
+    assert_eq!(hint(&mut native, &[(110, 0)], &program), vec![(64, 0)]);
+}
+
+#[test]
+fn test_composite_offset() {
+    use aurora_font::hinting::HintingMode;
+
+    let (glyf, hmtx) = glyphs(&[(0, 0)], &[]);
+    let mut v35 = hinter(&[], &[], &[], HintingMode::V35);
+    let glyph = v35.hint_glyph(&glyf, &hmtx, 1).unwrap();
+    assert_eq!((glyph.points[0].x, glyph.points[0].y), (64, 64));
+    // v40 only rounds the vertical offset.
+    let mut v40 = hinter(&[], &[], &[], HintingMode::V40);
+    let glyph = v40.hint_glyph(&glyf, &hmtx, 1).unwrap();
+    assert_eq!((glyph.points[0].x, glyph.points[0].y), (70, 64));
+}
+
+#[test]
+fn test_execution_limits() {
+    use aurora_font::components::maxp::MaxpTrueType;
+    use aurora_font::hinting::{Hinter, HintingMode};
+
+    let mut hinter = hinter(&[], &[], &[], HintingMode::V35);
+    // POP on an empty stack.
+    let (glyf, hmtx) = glyphs(&[(0, 0)], &[0x21]);
+    assert!(hinter.hint_glyph(&glyf, &hmtx, 0).is_err());
+    // PUSHW -3, JMPR back to the push never ends.
+    let (glyf, hmtx) = glyphs(&[(0, 0)], &[0xB8, 0xFF, 0xFD, 0x1C]);
+    assert!(hinter.hint_glyph(&glyf, &hmtx, 0).is_err());
+    // Longer than maxSizeOfInstructions.
+    let (glyf, hmtx) = glyphs(&[(0, 0)], &[0x4D; 65]);
+    assert!(hinter.hint_glyph(&glyf, &hmtx, 0).is_err());
+
+    // Function 4 is beyond maxFunctionDefs.
+    let maxp = MaxpTrueType {
+        max_points: 0,
+        max_contours: 0,
+        max_composite_points: 0,
+        max_composite_contours: 0,
+        max_zones: 1,
+        max_twilight_points: 0,
+        max_storage: 0,
+        max_function_defs: 4,
+        max_instruction_defs: 0,
+        max_stack_elements: 1,
+        max_size_of_instructions: 0,
+        max_component_elements: 0,
+        max_component_depth: 0,
+    };
+    let fpgm = [0xB0, 0x04, 0x2C, 0x2D];
+    assert!(Hinter::new(&fpgm, &[], &[], &maxp, 1000, HintingMode::V40).is_err());
+}
@@ -0,0 +1,477 @@
+//! Tests for variable font instancing.
+
+/// Builds a TrueType font with a `wght` axis (100, 400, 900) and one square
+/// glyph. At `wght` 900 a single touched point moves the whole contour by
+/// (100, 100), the advance grows by 100, the caret slope rise by 10 and the
+/// `GPOS` advance adjustment of the glyph by 50 and the second control value
+/// by 40.
+fn build_font() -> Vec<u8> {
+    build_font_with(Vec::new())
+}
+
+/// Builds the font of `build_font` with additional tables.
+fn build_font_with(extra: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    use aurora_font::common::snft::write_snft;
+    use aurora_font::components::cvar::{CvarTable, write_cvar};
+    use aurora_font::components::cvt::{CvtTable, write_cvt};
+    use aurora_font::components::fvar::{FvarTable, NamedInstance, VariationAxis, write_fvar};
+    use aurora_font::components::gdef::{GdefTable, write_gdef};
+    use aurora_font::components::glyf::{
+        GlyfTable, Glyph, GlyphHeader, GlyphPoint, SimpleGlyph, write_glyf,
+    };
+    use aurora_font::components::gpos::{
+        GposTable, PositioningSubtable, ValueRecord, X_ADVANCE_DEVICE, write_gpos,
+    };
+    use aurora_font::components::gvar::{GvarTable, write_gvar};
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric, write_hmtx};
+    use aurora_font::components::hvar::{HvarTable, write_hvar};
+    use aurora_font::components::layout::{DeviceTable, Lookup};
+    use aurora_font::components::loca::write_loca;
+    use aurora_font::components::mvar::{MetricValueRecord, MvarTable, write_mvar};
+    use aurora_font::components::variation::{
+        ItemVariationData, ItemVariationStore, RegionAxis, TupleVariation, VariationRegion,
+    };
+    use aurora_font::io::FontDataWriter;
+
+    let square = Glyph::Simple(SimpleGlyph {
+        header: GlyphHeader {
+            number_of_contours: 1,
+            x_min: 0,
+            y_min: 0,
+            x_max: 500,
+            y_max: 500,
+        },
+        end_points: vec![3],
+        instructions: vec![],
+        points: [(0, 0), (0, 500), (500, 500), (500, 0)]
+            .map(|(x, y)| GlyphPoint {
+                x,
+                y,
+                on_curve: true,
+            })
+            .to_vec(),
+        overlap: false,
+    });
+    let glyf = GlyfTable {
+        glyphs: vec![Glyph::Empty, square],
+    };
+    let mut writer = FontDataWriter::new();
+    let loca = write_glyf(&mut writer, &glyf);
+    let glyf_data = writer.into_bytes().unwrap();
+    let mut writer = FontDataWriter::new();
+    write_loca(&mut writer, &loca, false).unwrap();
+    let loca_data = writer.into_bytes().unwrap();
+
+    let metric = |advance_width| LongHorMetric {
+        advance_width,
+        lsb: 0,
+    };
+    let hmtx = HmtxTable {
+        h_metrics: vec![metric(500), metric(500)],
+        left_side_bearings: vec![],
+    };
+    let mut writer = FontDataWriter::new();
+    write_hmtx(&mut writer, &hmtx);
+    let hmtx_data = writer.into_bytes().unwrap();
+
+    let fvar = FvarTable {
+        major_version: 1,
+        minor_version: 0,
+        axes: vec![VariationAxis {
+            tag: *b"wght",
+            min_value: 100.0,
+            default_value: 400.0,
+            max_value: 900.0,
+            flags: 0,
+            axis_name_id: 256,
+        }],
+        instances: vec![NamedInstance {
+            subfamily_name_id: 2,
+            flags: 0,
+            coordinates: vec![400.0],
+            post_script_name_id: None,
+        }],
+    };
+    let mut writer = FontDataWriter::new();
+    write_fvar(&mut writer, &fvar);
+    let fvar_data = writer.into_bytes().unwrap();
+
+    // Point 2 and the right phantom point (5) are touched.
+    let gvar = GvarTable {
+        major_version: 1,
+        minor_version: 0,
+        axis_count: 1,
+        glyph_variations: vec![
+            vec![],
+            vec![TupleVariation {
+                peak: vec![1.0],
+                intermediate: None,
+                point_numbers: Some(vec![2, 5]),
+                deltas: vec![vec![100, 100], vec![100, 0]],
+            }],
+        ],
+    };
+    let mut writer = FontDataWriter::new();
+    write_gvar(&mut writer, &gvar).unwrap();
+    let gvar_data = writer.into_bytes().unwrap();
+
+    let store = |delta_sets| ItemVariationStore {
+        format: 1,
+        axis_count: 1,
+        regions: vec![VariationRegion {
+            axes: vec![RegionAxis::new(0.0, 1.0, 1.0)],
+        }],
+        data: vec![ItemVariationData {
+            region_indexes: vec![0],
+            delta_sets,
+        }],
+    };
+    let hvar = HvarTable {
+        major_version: 1,
+        minor_version: 0,
+        store: store(vec![vec![0], vec![100]]),
+        advance_map: None,
+        lsb_map: None,
+        rsb_map: None,
+    };
+    let mut writer = FontDataWriter::new();
+    write_hvar(&mut writer, &hvar).unwrap();
+    let hvar_data = writer.into_bytes().unwrap();
+
+    let mvar = MvarTable {
+        major_version: 1,
+        minor_version: 0,
+        records: vec![MetricValueRecord {
+            tag: *b"hcrs",
+            outer: 0,
+            inner: 0,
+        }],
+        store: Some(store(vec![vec![10]])),
+    };
+    let mut writer = FontDataWriter::new();
+    write_mvar(&mut writer, &mvar).unwrap();
+    let mvar_data = writer.into_bytes().unwrap();
+
+    let gdef = GdefTable {
+        major_version: 1,
+        minor_version: 3,
+        item_var_store: Some(store(vec![vec![50]])),
+        ..Default::default()
+    };
+    let mut writer = FontDataWriter::new();
+    write_gdef(&mut writer, &gdef).unwrap();
+    let gdef_data = writer.into_bytes().unwrap();
+
+    let value = ValueRecord {
+        x_advance_device: Some(DeviceTable::VariationIndex {
+            delta_set_outer_index: 0,
+            delta_set_inner_index: 0,
+        }),
+        ..Default::default()
+    };
+    let gpos = GposTable {
+        major_version: 1,
+        minor_version: 0,
+        scripts: vec![],
+        features: vec![],
+        lookups: vec![Lookup {
+            lookup_type: 1,
+            lookup_flag: 0,
+            subtables: vec![PositioningSubtable::Single {
+                value_format: X_ADVANCE_DEVICE,
+                values: [(1, value)].into(),
+            }],
+            mark_filtering_set: None,
+        }],
+        feature_variations: None,
+    };
+    let mut writer = FontDataWriter::new();
+    write_gpos(&mut writer, &gpos).unwrap();
+    let gpos_data = writer.into_bytes().unwrap();
+
+    let mut writer = FontDataWriter::new();
+    write_cvt(
+        &mut writer,
+        &CvtTable {
+            values: vec![10, 20, 30],
+        },
+    );
+    let cvt_data = writer.into_bytes().unwrap();
+    let cvar = CvarTable {
+        major_version: 1,
+        minor_version: 0,
+        variations: vec![TupleVariation {
+            peak: vec![1.0],
+            intermediate: None,
+            point_numbers: Some(vec![1]),
+            deltas: vec![vec![40]],
+        }],
+    };
+    let mut writer = FontDataWriter::new();
+    write_cvar(&mut writer, &cvar).unwrap();
+    let cvar_data = writer.into_bytes().unwrap();
+
+    let mut head = vec![0u8; 54];
+    head[0..2].copy_from_slice(&1u16.to_be_bytes());
+    head[12..16].copy_from_slice(&0x5F0F3CF5u32.to_be_bytes());
+    head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+    let mut hhea = vec![0u8; 36];
+    hhea[0..2].copy_from_slice(&1u16.to_be_bytes());
+    hhea[18..20].copy_from_slice(&1u16.to_be_bytes());
+    hhea[34..36].copy_from_slice(&2u16.to_be_bytes());
+    let mut maxp = vec![0u8; 6];
+    maxp[..4].copy_from_slice(&0x00005000u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&2u16.to_be_bytes());
+
+    let mut tables = vec![
+        (*b"GDEF", gdef_data),
+        (*b"GPOS", gpos_data),
+        (*b"HVAR", hvar_data),
+        (*b"MVAR", mvar_data),
+        (*b"cvar", cvar_data),
+        (*b"cvt ", cvt_data),
+        (*b"fvar", fvar_data),
+        (*b"glyf", glyf_data),
+        (*b"gvar", gvar_data),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx_data),
+        (*b"loca", loca_data),
+        (*b"maxp", maxp),
+    ];
+    tables.extend(extra);
+    tables.sort_by_key(|(tag, _)| *tag);
+    let mut writer = FontDataWriter::new();
+    write_snft(&mut writer, 0x00010000, &tables).unwrap();
+    writer.into_bytes().unwrap()
+}
+
+#[test]
+fn test_instance_pinned() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::cvt::read_cvt;
+    use aurora_font::components::gdef::read_gdef;
+    use aurora_font::components::glyf::{Glyph, read_glyf};
+    use aurora_font::components::gpos::{PositioningSubtable, X_ADVANCE, read_gpos};
+    use aurora_font::components::hhea::read_hhea;
+    use aurora_font::components::hmtx::read_hmtx;
+    use aurora_font::components::loca::read_loca;
+    use aurora_font::instancer::{AxisLimit, InstanceOptions, instantiate_font};
+    use aurora_font::io::FontDataStream;
+
+    let font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    for (value, shift) in [(900.0, 100), (650.0, 50), (400.0, 0), (100.0, 0)] {
+        let options = InstanceOptions {
+            axes: [(*b"wght", AxisLimit::Pin(value))].into(),
+        };
+        let output = instantiate_font(&snft, &font, &options).unwrap();
+        let instance = read_snft(&mut FontDataStream::new(&output)).unwrap();
+        for tag in [b"fvar", b"gvar", b"cvar", b"HVAR", b"MVAR"] {
+            assert!(!instance.has_table(tag));
+        }
+        let table = |tag: &[u8; 4]| FontDataStream::new(instance.table_data(tag, &output).unwrap());
+
+        // The untouched points follow the only touched point of the contour.
+        let loca = read_loca(&mut table(b"loca"), false, 2).unwrap();
+        let glyf = read_glyf(&mut table(b"glyf"), &loca).unwrap();
+        let Some(Glyph::Simple(glyph)) = glyf.glyph(1) else {
+            panic!("expected a simple glyph");
+        };
+        assert_eq!((glyph.points[0].x, glyph.points[0].y), (shift, shift));
+        assert_eq!(
+            (glyph.points[2].x, glyph.points[2].y),
+            (500 + shift, 500 + shift)
+        );
+        assert_eq!(
+            (glyph.header.x_min, glyph.header.x_max),
+            (shift, 500 + shift)
+        );
+
+        let hhea = read_hhea(&mut table(b"hhea")).unwrap();
+        assert_eq!(hhea.caret_slope_rise, 1 + shift / 10);
+        let hmtx = read_hmtx(&mut table(b"hmtx"), hhea.number_of_h_metrics, 2).unwrap();
+        assert_eq!(hmtx.advance_width(1), (500 + shift) as u16);
+        assert_eq!(hmtx.lsb(1), shift);
+
+        // The `GDEF` delta is folded into the value and the store removed.
+        let gpos = read_gpos(&mut table(b"GPOS")).unwrap();
+        let PositioningSubtable::Single {
+            value_format,
+            values,
+        } = &gpos.lookups[0].subtables[0]
+        else {
+            panic!("expected a single adjustment subtable");
+        };
+        assert_eq!(*value_format & !X_ADVANCE, 0);
+        assert_eq!(values[&1].x_advance, shift / 2);
+        assert_eq!(values[&1].x_advance_device, None);
+        assert_eq!(read_gdef(&mut table(b"GDEF")).unwrap().item_var_store, None);
+
+        let cvt = read_cvt(&mut table(b"cvt ")).unwrap();
+        assert_eq!(cvt.values, vec![10, 20 + shift * 2 / 5, 30]);
+    }
+}
+
+#[test]
+fn test_instance_partial() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::cvar::read_cvar;
+    use aurora_font::components::cvt::read_cvt;
+    use aurora_font::components::fvar::read_fvar;
+    use aurora_font::components::gdef::read_gdef;
+    use aurora_font::components::gpos::{PositioningSubtable, read_gpos};
+    use aurora_font::components::gvar::read_gvar;
+    use aurora_font::components::hvar::read_hvar;
+    use aurora_font::instancer::{AxisLimit, InstanceOptions, instantiate_font};
+    use aurora_font::io::FontDataStream;
+
+    let font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = InstanceOptions {
+        axes: [(*b"wght", AxisLimit::Range(300.0, 650.0))].into(),
+    };
+    let output = instantiate_font(&snft, &font, &options).unwrap();
+    let instance = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    let table = |tag: &[u8; 4]| FontDataStream::new(instance.table_data(tag, &output).unwrap());
+
+    let fvar = read_fvar(&mut table(b"fvar")).unwrap();
+    let axis = &fvar.axes[0];
+    assert_eq!(
+        (axis.min_value, axis.default_value, axis.max_value),
+        (300.0, 400.0, 650.0)
+    );
+
+    // The new maximum reaches half of the original peak.
+    let gvar = read_gvar(&mut table(b"gvar")).unwrap();
+    let tuples = gvar.variations(1);
+    assert_eq!(tuples.len(), 1);
+    assert_eq!(tuples[0].peak, vec![1.0]);
+    assert_eq!(tuples[0].deltas[0][..4], [50, 50, 50, 50]);
+
+    let gdef = read_gdef(&mut table(b"GDEF")).unwrap();
+    let gpos = read_gpos(&mut table(b"GPOS")).unwrap();
+    let PositioningSubtable::Single { values, .. } = &gpos.lookups[0].subtables[0] else {
+        panic!("expected a single adjustment subtable");
+    };
+    let device = values[&1].x_advance_device.as_ref().unwrap();
+    assert_eq!(gdef.variation_delta(device, &[1.0]), 25.0);
+
+    let hvar = read_hvar(&mut table(b"HVAR")).unwrap();
+    assert_eq!(hvar.advance_delta(1, &[1.0]), 50.0);
+    assert_eq!(hvar.advance_delta(1, &[-1.0]), 0.0);
+
+    let cvt = read_cvt(&mut table(b"cvt ")).unwrap();
+    let cvar = read_cvar(&mut table(b"cvar"), 1).unwrap();
+    assert_eq!(cvt.values, vec![10, 20, 30]);
+    assert_eq!(cvt.cvt_at(&cvar, &[1.0]), vec![10.0, 40.0, 30.0]);
+}
+
+#[test]
+fn test_instance_invalid_limits() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::instancer::{AxisLimit, InstanceOptions, instantiate_font};
+    use aurora_font::io::FontDataStream;
+
+    let font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let instance = |tag: [u8; 4], limit| {
+        let options = InstanceOptions {
+            axes: [(tag, limit)].into(),
+        };
+        instantiate_font(&snft, &font, &options)
+    };
+    assert!(instance(*b"wght", AxisLimit::Range(500.0, 900.0)).is_err());
+    assert!(instance(*b"wdth", AxisLimit::Pin(75.0)).is_err());
+}
+
+#[test]
+fn test_instance_stat_and_signature() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::stat::{AxisValue, read_stat};
+    use aurora_font::instancer::{AxisLimit, InstanceOptions, instantiate_font};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    // STAT 1.1 with one design axis and the values Light, Regular and Bold.
+    let mut stat = FontDataWriter::new();
+    stat.write_u16(1); // majorVersion
+    stat.write_u16(1); // minorVersion
+    stat.write_u16(8); // designAxisSize
+    stat.write_u16(1); // designAxisCount
+    stat.write_u32(20); // designAxesOffset
+    stat.write_u16(3); // axisValueCount
+    stat.write_u32(28); // offsetToAxisValueOffsets
+    stat.write_u16(2); // elidedFallbackNameID
+    stat.write_tag(*b"wght"); // 20: design axis
+    stat.write_u16(256);
+    stat.write_u16(0);
+    for offset in [6, 18, 30] {
+        stat.write_u16(offset); // 28: axis value offsets
+    }
+    for (name_id, value) in [(257, 300.0), (258, 400.0), (259, 700.0)] {
+        stat.write_u16(1); // format
+        stat.write_u16(0); // axisIndex
+        stat.write_u16(0); // flags
+        stat.write_u16(name_id);
+        stat.write_fixed(value);
+    }
+    let font = build_font_with(vec![
+        (*b"DSIG", vec![0, 0, 0, 1, 0, 0, 0, 0]),
+        (*b"STAT", stat.into_bytes().unwrap()),
+    ]);
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+
+    for (limit, names) in [
+        (AxisLimit::Pin(700.0), vec![259]),
+        (AxisLimit::Range(300.0, 650.0), vec![257, 258]),
+    ] {
+        let options = InstanceOptions {
+            axes: [(*b"wght", limit)].into(),
+        };
+        let output = instantiate_font(&snft, &font, &options).unwrap();
+        let instance = read_snft(&mut FontDataStream::new(&output)).unwrap();
+        assert!(!instance.has_table(b"DSIG"));
+        let data = instance.table_data(b"STAT", &output).unwrap();
+        let stat = read_stat(&mut FontDataStream::new(data)).unwrap();
+        assert_eq!(stat.design_axes.len(), 1);
+        let kept: Vec<u16> = stat
+            .axis_values
+            .iter()
+            .map(AxisValue::value_name_id)
+            .collect();
+        assert_eq!(kept, names);
+    }
+}
+
+#[test]
+fn test_instance_variable_colr() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::error::{Error, IoError};
+    use aurora_font::instancer::{AxisLimit, InstanceOptions, instantiate_font};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    // COLR version 1 without glyphs and with an empty variation store.
+    let mut colr = FontDataWriter::new();
+    colr.write_u16(1); // version
+    colr.write_u16(0); // numBaseGlyphRecords
+    colr.write_u32(0); // baseGlyphRecordsOffset
+    colr.write_u32(0); // layerRecordsOffset
+    colr.write_u16(0); // numLayerRecords
+    colr.write_u32(0); // baseGlyphListOffset
+    colr.write_u32(0); // layerListOffset
+    colr.write_u32(0); // clipListOffset
+    colr.write_u32(0); // varIndexMapOffset
+    colr.write_u32(34); // itemVariationStoreOffset
+    colr.write_u16(1); // 34: format
+    colr.write_u32(0); // variationRegionListOffset
+    colr.write_u16(0); // itemVariationDataCount
+    let font = build_font_with(vec![(*b"COLR", colr.into_bytes().unwrap())]);
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = InstanceOptions {
+        axes: [(*b"wght", AxisLimit::Pin(700.0))].into(),
+    };
+    assert!(matches!(
+        instantiate_font(&snft, &font, &options),
+        Err(Error::Io(IoError::InvalidTag(tag))) if tag == *b"COLR"
+    ));
+}
@@ -0,0 +1,180 @@
+//! Tests for the `JSTF` table.
+
+/// Lays out a table for Arabic with a kashida extender and two priorities:
+/// the first shrinks and extends through limited `GPOS` adjustments, the
+/// second only switches lookups for extension. Urdu uses the first alone.
+fn jstf_data() -> Vec<u8> {
+    use aurora_font::io::writer::FontDataWriter;
+
+    let mut w = FontDataWriter::new();
+    let u16s = |w: &mut FontDataWriter, values: &[u16]| values.iter().for_each(|&v| w.write_u16(v));
+    // A JstfMax with one single adjustment lookup: 22 + 2 * glyphs bytes.
+    let max = |w: &mut FontDataWriter, glyphs: &[u16], x_advance: i16| {
+        u16s(w, &[1, 4]);
+        u16s(w, &[1, 0, 1, 8]);
+        u16s(w, &[1, 8, 0x0004]);
+        w.write_i16(x_advance);
+        u16s(w, &[1, glyphs.len() as u16]);
+        u16s(w, glyphs);
+    };
+    // The limited priority: 86 bytes.
+    let limited = |w: &mut FontDataWriter| {
+        u16s(w, &[0, 0, 20, 0, 24, 0, 0, 52, 0, 56]);
+        u16s(w, &[1, 3]);
+        max(w, &[10, 11], -20);
+        u16s(w, &[1, 4]);
+        max(w, &[10, 11, 12], 30);
+    };
+
+    // Header: version 1.0, one script record.
+    w.write_u32(0x0001_0000);
+    w.write_u16(1);
+    w.write_tag(*b"arab");
+    w.write_u16(12);
+
+    // JstfScript at 12: extenders at +12, default language system at +16
+    // and Urdu at +142.
+    u16s(&mut w, &[12, 16, 1]);
+    w.write_tag(*b"URD ");
+    w.write_u16(142);
+    u16s(&mut w, &[1, 50]);
+    // Default language system: the limited priority at +6, kashida at +92.
+    u16s(&mut w, &[2, 6, 92]);
+    limited(&mut w);
+    u16s(&mut w, &[0, 0, 0, 0, 0, 20, 26, 0, 30, 0]);
+    u16s(&mut w, &[2, 7, 8]);
+    u16s(&mut w, &[1, 2]);
+    u16s(&mut w, &[1, 5]);
+    // Urdu: the limited priority at +4.
+    u16s(&mut w, &[1, 4]);
+    limited(&mut w);
+    w.into_bytes().unwrap()
+}
+
+fn read_jstf_data() -> aurora_font::components::jstf::JstfTable {
+    use aurora_font::components::jstf::read_jstf;
+    use aurora_font::io::stream::FontDataStream;
+
+    read_jstf(&mut FontDataStream::new(&jstf_data())).unwrap()
+}
+
+#[test]
+fn test_read_jstf() {
+    use aurora_font::components::gpos::{PositioningSubtable, ValueRecord, X_ADVANCE};
+    use aurora_font::components::jstf::JstfModifications;
+
+    let jstf = read_jstf_data();
+    assert_eq!((jstf.major_version, jstf.minor_version), (1, 0));
+    assert_eq!(jstf.scripts.len(), 1);
+    assert_eq!(jstf.scripts[0].script_tag, *b"arab");
+
+    let script = &jstf.scripts[0].script;
+    assert_eq!(script.extender_glyphs, [50]);
+    let priorities = &script.default_lang_sys.as_ref().unwrap().priorities;
+    assert_eq!(priorities.len(), 2);
+
+    let limited = &priorities[0];
+    assert_eq!(limited.shrinkage.gpos_enable, [3]);
+    assert_eq!(limited.extension.gpos_enable, [4]);
+    let lookup = &limited.shrinkage.max[0];
+    assert_eq!((lookup.lookup_type, lookup.lookup_flag), (1, 0));
+    let shrink = ValueRecord {
+        x_advance: -20,
+        ..Default::default()
+    };
+    assert_eq!(
+        lookup.subtables,
+        [PositioningSubtable::Single {
+            value_format: X_ADVANCE,
+            values: vec![(10, shrink.clone()), (11, shrink)]
+                .into_iter()
+                .collect(),
+        }]
+    );
+
+    assert_eq!(priorities[1].shrinkage, JstfModifications::default());
+    let kashida = &priorities[1].extension;
+    assert_eq!(kashida.gsub_enable, [7, 8]);
+    assert_eq!(kashida.gsub_disable, [2]);
+    assert!(kashida.gpos_enable.is_empty());
+    assert_eq!(kashida.gpos_disable, [5]);
+    assert!(kashida.max.is_empty());
+
+    let urdu = &script.lang_sys_records[0];
+    assert_eq!(urdu.lang_sys_tag, *b"URD ");
+    assert_eq!(urdu.lang_sys.priorities, std::slice::from_ref(limited));
+}
+
+#[test]
+fn test_jstf_suggest() {
+    use aurora_font::components::jstf::JustificationMode;
+    use aurora_font::tags::script::ScriptTag;
+
+    let jstf = read_jstf_data();
+    let arabic = jstf.script(ScriptTag::Arabic).unwrap();
+    assert!(arabic.is_extender(50));
+    assert!(!arabic.is_extender(10));
+    let glyphs = [10, 11, 12, 13];
+
+    assert!(
+        jstf.suggest(ScriptTag::Arabic, None, &glyphs, 1000.0, 1000.0)
+            .is_none()
+    );
+
+    // The limited priority covers 90 units of extension on its own.
+    let justification = jstf
+        .suggest(ScriptTag::Arabic, None, &glyphs, 1000.0, 1060.0)
+        .unwrap();
+    assert_eq!(justification.mode, JustificationMode::Extend);
+    assert_eq!(justification.steps.len(), 1);
+    assert_eq!(justification.steps[0].max_adjustment, Some(90.0));
+    assert_eq!(justification.steps[0].modifications.gpos_enable, [4]);
+    assert_eq!(justification.remaining, 0.0);
+
+    // Beyond the limit, the kashida priority follows without a limit.
+    let justification = jstf
+        .suggest(ScriptTag::Arabic, None, &glyphs, 1000.0, 1200.0)
+        .unwrap();
+    let indices: Vec<_> = justification
+        .steps
+        .iter()
+        .map(|step| step.priority_index)
+        .collect();
+    assert_eq!(indices, [0, 1]);
+    assert_eq!(justification.steps[1].max_adjustment, None);
+    assert_eq!(justification.remaining, 110.0);
+
+    // The kashida priority does not shrink, so it is skipped.
+    let justification = jstf
+        .suggest(ScriptTag::Arabic, None, &glyphs, 1100.0, 1000.0)
+        .unwrap();
+    assert_eq!(justification.mode, JustificationMode::Shrink);
+    assert_eq!(justification.steps.len(), 1);
+    assert_eq!(justification.steps[0].max_adjustment, Some(40.0));
+    assert_eq!(justification.remaining, 60.0);
+
+    // Language systems replace the default priorities.
+    let justification = jstf
+        .suggest(ScriptTag::Arabic, Some(*b"URD "), &glyphs, 1000.0, 1200.0)
+        .unwrap();
+    assert_eq!(justification.steps.len(), 1);
+    assert!(
+        jstf.suggest(ScriptTag::Latin, None, &glyphs, 1000.0, 1200.0)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_jstf_invalid() {
+    use aurora_font::components::jstf::read_jstf;
+    use aurora_font::io::stream::FontDataStream;
+
+    assert!(read_jstf(&mut FontDataStream::new(&[0, 2, 0, 0, 0, 0])).is_err());
+    // A script record pointing past the end of the table.
+    assert!(
+        read_jstf(&mut FontDataStream::new(&[
+            0, 1, 0, 0, 0, 1, b'a', b'r', b'a', b'b', 0, 40
+        ]))
+        .is_err()
+    );
+}
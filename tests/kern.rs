@@ -0,0 +1,192 @@
+//! Tests for the legacy `kern` table.
+
+/// A Microsoft table with a pair list kerning glyphs 1 and 2, and a class
+/// subtable kerning glyphs 3 and 4 against glyphs 5 and 6.
+#[rustfmt::skip]
+const MICROSOFT: [u8; 78] = [
+    0, 0, 0, 2,                       // version 0, nTables
+    // Format 0: version, length, coverage (horizontal), then the search header.
+    0, 0, 0, 32, 0, 1,
+    0, 3, 0, 12, 0, 1, 0, 6,
+    0, 1, 0, 2, 0xFF, 0xB0,           // 1, 2: -80
+    0, 2, 0, 1, 0xFF, 0xE2,           // 2, 1: -30
+    0, 3, 0, 5, 0, 10,                // 3, 5: 10
+    // Format 2: rowWidth, left and right class tables, kerning array.
+    0, 0, 0, 42, 2, 1,
+    0, 6, 0, 14, 0, 22, 0, 30,
+    0, 3, 0, 2, 0, 36, 0, 30,         // glyph 3 is row 1, glyph 4 row 0
+    0, 5, 0, 2, 0, 2, 0, 4,           // glyphs 5 and 6 are columns 1 and 2
+    0, 0, 0, 0, 0, 0,
+    0, 0, 0xFF, 0xEC, 0, 15,
+];
+
+/// An Apple table with a contextual subtable kerning glyph 10 before glyph
+/// 11, an index subtable, and cross-stream and variation pair lists.
+#[rustfmt::skip]
+const APPLE: [u8; 142] = [
+    0, 1, 0, 0, 0, 0, 0, 4,           // version 1.0, nTables
+    // Format 1: length, coverage, tupleIndex, then the state table header.
+    0, 0, 0, 56, 0, 1, 0, 0,
+    0, 6, 0, 8, 0, 14, 0, 32,
+    0, 10, 0, 2, 4, 5,                // glyph 10 is class 4, glyph 11 class 5
+    0, 0, 0, 0, 1, 0,                 // states
+    0, 0, 0, 0, 1, 0,
+    0, 0, 0, 0, 1, 2,
+    0, 14, 0, 0,                      // entries: new state offset, flags
+    0, 26, 0x80, 0,                   // push, go to state 2
+    0, 14, 0x80, 44,                  // push, kern with the values at 44
+    0, 0, 0xFF, 0xCF,                 // 0, -50 (end of list)
+    // Format 3: glyphCount, counts, values, classes and indices.
+    0, 0, 0, 34, 0, 3, 0, 0,
+    0, 4, 3, 3, 2, 0,
+    0, 0, 0xFF, 0xD8, 0, 24,
+    0, 1, 2, 0,
+    0, 0, 1, 1,
+    0, 0, 0, 1, 0, 2,
+    // Format 0, cross-stream.
+    0, 0, 0, 22, 0x40, 0, 0, 0,
+    0, 1, 0, 6, 0, 0, 0, 0,
+    0, 1, 0, 2, 0, 100,
+    // Format 0, variation tuple 1.
+    0, 0, 0, 22, 0x20, 0, 0, 1,
+    0, 1, 0, 6, 0, 0, 0, 0,
+    0, 1, 0, 2, 0, 100,
+];
+
+#[test]
+fn test_kern_microsoft() {
+    use aurora_font::components::kern::{KERN_VERSION_MICROSOFT, read_kern};
+    use aurora_font::io::stream::FontDataStream;
+
+    let kern = read_kern(&mut FontDataStream::new(&MICROSOFT)).unwrap();
+    assert_eq!(kern.version, KERN_VERSION_MICROSOFT);
+    let formats: Vec<u8> = kern.subtables.iter().map(|s| s.data.format()).collect();
+    assert_eq!(formats, [0, 2]);
+    assert!(kern.subtables.iter().all(|s| s.horizontal && !s.minimum));
+
+    assert_eq!(kern.kerning(1, 2), -80);
+    assert_eq!(kern.kerning(2, 1), -30);
+    assert_eq!(kern.kerning(2, 2), 0);
+    // Both subtables add up.
+    assert_eq!(kern.kerning(3, 5), -10);
+    assert_eq!(kern.kerning(3, 6), 15);
+    assert_eq!(kern.kerning(4, 6), 0);
+    // Right glyphs outside the class table use column 0.
+    assert_eq!(kern.kerning(3, 7), 0);
+}
+
+#[test]
+fn test_kern_microsoft_override_and_minimum() {
+    use aurora_font::components::kern::{KernData, KernSubtable, read_kern};
+    use aurora_font::io::stream::FontDataStream;
+    use std::collections::BTreeMap;
+
+    let mut kern = read_kern(&mut FontDataStream::new(&MICROSOFT)).unwrap();
+    kern.subtables.push(KernSubtable {
+        horizontal: true,
+        minimum: false,
+        cross_stream: false,
+        replace: true,
+        variation: false,
+        tuple_index: 0,
+        data: KernData::Pairs(BTreeMap::from([((3, 6), 5)])),
+    });
+    kern.subtables.push(KernSubtable {
+        horizontal: true,
+        minimum: true,
+        cross_stream: false,
+        replace: false,
+        variation: false,
+        tuple_index: 0,
+        data: KernData::Pairs(BTreeMap::from([((3, 6), -500)])),
+    });
+    // The override replaces the sum so far only for the pairs it lists, and
+    // minimum subtables are not kerning.
+    assert_eq!(kern.kerning(3, 6), 5);
+    assert_eq!(kern.kerning(3, 5), -10);
+}
+
+#[test]
+fn test_kern_apple() {
+    use aurora_font::components::kern::{KERN_VERSION_APPLE, KernData, read_kern};
+    use aurora_font::io::stream::FontDataStream;
+
+    let kern = read_kern(&mut FontDataStream::new(&APPLE)).unwrap();
+    assert_eq!(kern.version, KERN_VERSION_APPLE);
+    let coverage: Vec<_> = kern
+        .subtables
+        .iter()
+        .map(|s| (s.data.format(), s.cross_stream, s.variation, s.tuple_index))
+        .collect();
+    assert_eq!(
+        coverage,
+        [
+            (1, false, false, 0),
+            (3, false, false, 0),
+            (0, true, false, 0),
+            (0, false, true, 1)
+        ]
+    );
+
+    // Cross-stream and variation subtables do not contribute to kerning.
+    assert_eq!(kern.kerning(1, 2), -40);
+    assert_eq!(kern.kerning(2, 3), 24);
+    assert_eq!(kern.kerning(0, 3), 0);
+    assert_eq!(kern.kerning(10, 11), 0);
+
+    let KernData::Contextual(table) = &kern.subtables[0].data else {
+        unreachable!();
+    };
+    assert_eq!(table.states.len(), 3);
+    assert_eq!(table.entries[2].values, [0, -50]);
+    assert_eq!(table.apply(&[10, 11, 10, 5, 11]), [-50, 0, 0, 0, 0]);
+    assert_eq!(table.apply(&[11, 10, 10, 11]), [0, 0, -50, 0]);
+    assert_eq!(table.apply(&[]), Vec::<i16>::new());
+}
+
+#[test]
+fn test_kern_rejects_invalid() {
+    use aurora_font::components::kern::read_kern;
+    use aurora_font::io::stream::FontDataStream;
+
+    assert!(read_kern(&mut FontDataStream::new(&[0, 2, 0, 0])).is_err());
+    assert!(read_kern(&mut FontDataStream::new(&[0, 1, 0, 1, 0, 0, 0, 0])).is_err());
+    // A subtable of unknown format 4.
+    let data = [0, 0, 0, 1, 0, 0, 0, 6, 4, 1];
+    assert!(read_kern(&mut FontDataStream::new(&data)).is_err());
+    // A class subtable whose left offsets fall between rows.
+    let mut data = MICROSOFT;
+    data[55] = 33;
+    assert!(read_kern(&mut FontDataStream::new(&data)).is_err());
+}
+
+#[test]
+fn test_kern_declared_length() {
+    use aurora_font::components::kern::read_kern;
+    use aurora_font::io::stream::FontDataStream;
+
+    // Two Microsoft format 0 subtables, the first padded past its one pair.
+    #[rustfmt::skip]
+    let data = [
+        0, 0, 0, 2,
+        0, 0, 0, 24, 0, 1, 0, 1, 0, 6, 0, 0, 0, 0, 0, 1, 0, 2, 0xFF, 0xB0,
+        0xFF, 0xFF, 0xFF, 0xFF,
+        0, 0, 0, 20, 0, 1, 0, 1, 0, 6, 0, 0, 0, 0, 0, 3, 0, 4, 0, 25,
+    ];
+    let kern = read_kern(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(kern.subtables.len(), 2);
+    assert_eq!(kern.kerning(1, 2), -80);
+    assert_eq!(kern.kerning(3, 4), 25);
+
+    // The same in an Apple table, whose lengths are 32-bit.
+    #[rustfmt::skip]
+    let data = [
+        0, 1, 0, 0, 0, 0, 0, 2,
+        0, 0, 0, 24, 0, 0, 0, 0, 0, 1, 0, 6, 0, 0, 0, 0, 0, 5, 0, 6, 0xFF, 0xF4,
+        0xFF, 0xFF,
+        0, 0, 0, 22, 0, 0, 0, 0, 0, 1, 0, 6, 0, 0, 0, 0, 0, 7, 0, 8, 0, 9,
+    ];
+    let kern = read_kern(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(kern.kerning(5, 6), -12);
+    assert_eq!(kern.kerning(7, 8), 9);
+}
@@ -0,0 +1,247 @@
+//! Tests for the `MATH` table.
+
+/// Lays out a table with a few constants, per-glyph information for glyphs 3
+/// and 4, and constructions for glyphs 5 and 8.
+fn math_data() -> Vec<u8> {
+    use aurora_font::io::writer::FontDataWriter;
+
+    let mut w = FontDataWriter::new();
+    let coverage = |w: &mut FontDataWriter, glyphs: &[u16]| {
+        w.write_u16(1);
+        w.write_u16(glyphs.len() as u16);
+        glyphs.iter().for_each(|&g| w.write_u16(g));
+    };
+    let record = |w: &mut FontDataWriter, value: i16, device: u16| {
+        w.write_i16(value);
+        w.write_u16(device);
+    };
+    let device = |w: &mut FontDataWriter| {
+        // Sizes 12 to 13, 8-bit deltas +5 and -2.
+        w.write_u16(12);
+        w.write_u16(13);
+        w.write_u16(3);
+        w.write_u16(0x05FE);
+    };
+
+    // Header: version 1.0, then the constants, glyph info and variants.
+    w.write_u32(0x0001_0000);
+    w.write_u16(10);
+    w.write_u16(232);
+    w.write_u16(336);
+
+    // MathConstants at 10: four scalars, 51 value records, one scalar.
+    w.write_i16(70);
+    w.write_i16(50);
+    w.write_u16(1300);
+    w.write_u16(1800);
+    for index in 0..51 {
+        match index {
+            1 => record(&mut w, 250, 214), // axisHeight
+            34 => record(&mut w, 40, 0),   // fractionRuleThickness
+            50 => record(&mut w, -555, 0), // radicalKernAfterDegree
+            _ => record(&mut w, 0, 0),
+        }
+    }
+    w.write_i16(60);
+    device(&mut w);
+
+    // MathGlyphInfo at 232.
+    w.write_u16(8);
+    w.write_u16(22);
+    w.write_u16(50);
+    w.write_u16(58);
+    // Italics corrections at +8.
+    w.write_u16(8);
+    w.write_u16(1);
+    record(&mut w, 45, 0);
+    coverage(&mut w, &[3]);
+    // Top accent attachments at +22, the second with a device at +20.
+    w.write_u16(12);
+    w.write_u16(2);
+    record(&mut w, 200, 0);
+    record(&mut w, 310, 20);
+    coverage(&mut w, &[3, 4]);
+    device(&mut w);
+    // Extended shapes at +50.
+    coverage(&mut w, &[5, 6]);
+    // Kern info at +58: top-right and bottom-left kerns for glyph 4.
+    w.write_u16(12);
+    w.write_u16(1);
+    w.write_u16(18);
+    w.write_u16(0);
+    w.write_u16(0);
+    w.write_u16(40);
+    coverage(&mut w, &[4]);
+    w.write_u16(2);
+    [100, 200, 10, 20, 30]
+        .iter()
+        .for_each(|&v| record(&mut w, v, 0));
+    w.write_u16(0);
+    record(&mut w, -15, 0);
+
+    // MathVariants at 336.
+    w.write_u16(20);
+    w.write_u16(14);
+    w.write_u16(20);
+    w.write_u16(1);
+    w.write_u16(1);
+    w.write_u16(26);
+    w.write_u16(74);
+    coverage(&mut w, &[5]);
+    coverage(&mut w, &[8]);
+    // Vertical construction at +26 with its assembly at +12.
+    w.write_u16(12);
+    w.write_u16(2);
+    [5, 400, 7, 600].iter().for_each(|&v| w.write_u16(v));
+    record(&mut w, 12, 0);
+    w.write_u16(3);
+    for part in [
+        [10, 0, 100, 300, 0],
+        [11, 100, 100, 200, 1],
+        [12, 100, 0, 300, 0],
+    ] {
+        part.iter().for_each(|&v| w.write_u16(v));
+    }
+    // Horizontal construction at +74 without an assembly.
+    w.write_u16(0);
+    w.write_u16(1);
+    w.write_u16(9);
+    w.write_u16(500);
+    w.into_bytes().unwrap()
+}
+
+fn read_math_data() -> aurora_font::components::math::MathTable {
+    use aurora_font::components::math::read_math;
+    use aurora_font::io::stream::FontDataStream;
+
+    read_math(&mut FontDataStream::new(&math_data())).unwrap()
+}
+
+#[test]
+fn test_read_math() {
+    use aurora_font::components::layout::DeviceTable;
+    use aurora_font::components::math::GlyphPart;
+
+    let math = read_math_data();
+    assert_eq!((math.major_version, math.minor_version), (1, 0));
+
+    let constants = math.constants.as_ref().unwrap();
+    assert_eq!(constants.script_percent_scale_down, 70);
+    assert_eq!(constants.script_script_percent_scale_down, 50);
+    assert_eq!(constants.delimited_sub_formula_min_height, 1300);
+    assert_eq!(constants.display_operator_min_height, 1800);
+    assert_eq!(constants.fraction_rule_thickness.value, 40);
+    assert!(constants.fraction_rule_thickness.device.is_none());
+    assert_eq!(constants.radical_kern_after_degree.value, -555);
+    assert_eq!(constants.radical_degree_bottom_raise_percent, 60);
+    assert_eq!(
+        constants.axis_height.device,
+        Some(DeviceTable::Hinting {
+            start_size: 12,
+            end_size: 13,
+            delta_format: 3,
+            delta_values: vec![0x05FE],
+        })
+    );
+    assert_eq!(constants.axis_height.value_at(12), 255);
+    assert_eq!(constants.axis_height.value_at(13), 248);
+    assert_eq!(constants.axis_height.value_at(20), 250);
+
+    let variants = math.variants.as_ref().unwrap();
+    assert_eq!(variants.min_connector_overlap, 20);
+    let assembly = variants.vertical[&5].assembly.as_ref().unwrap();
+    assert_eq!(assembly.italics_correction.value, 12);
+    assert_eq!(
+        assembly.parts[1],
+        GlyphPart {
+            glyph_id: 11,
+            start_connector_length: 100,
+            end_connector_length: 100,
+            full_advance: 200,
+            extender: true,
+        }
+    );
+    assert!(variants.horizontal[&8].assembly.is_none());
+}
+
+#[test]
+fn test_math_glyph_info() {
+    let math = read_math_data();
+    assert_eq!(math.italics_correction(3), Some(45));
+    assert_eq!(math.italics_correction(4), None);
+    assert_eq!(math.top_accent_attachment(4), Some(310));
+    assert!(math.is_extended_shape(6));
+    assert!(!math.is_extended_shape(3));
+
+    let info = &math.glyph_info.as_ref().unwrap().kern_info[&4];
+    let top_right = info.top_right.as_ref().unwrap();
+    assert_eq!(top_right.kern(50), 10);
+    assert_eq!(top_right.kern(150), 20);
+    assert_eq!(top_right.kern(250), 30);
+    assert_eq!(info.bottom_left.as_ref().unwrap().kern(-400), -15);
+    assert!(info.top_left.is_none());
+}
+
+#[test]
+fn test_math_stretch() {
+    use aurora_font::components::math::StretchedGlyph;
+
+    let math = read_math_data();
+    let glyph = |glyph_id: u16, offset: i32| StretchedGlyph { glyph_id, offset };
+
+    // Variants are used while one is large enough.
+    let stretch = math.stretch(5, 300, true).unwrap();
+    assert_eq!(stretch.glyphs, vec![glyph(5, 0)]);
+    assert_eq!(stretch.size, 400);
+    assert_eq!(
+        math.stretch(5, 500, true).unwrap().glyphs,
+        vec![glyph(7, 0)]
+    );
+
+    // Three extenders overlapping by 50 reach 1000 units exactly.
+    let stretch = math.stretch(5, 1000, true).unwrap();
+    assert_eq!(
+        stretch.glyphs,
+        vec![
+            glyph(10, 0),
+            glyph(11, 250),
+            glyph(11, 400),
+            glyph(11, 550),
+            glyph(12, 700),
+        ]
+    );
+    assert_eq!(stretch.size, 1000);
+    assert_eq!(stretch.italics_correction, 12);
+
+    // One extender reaches 650 units with the parts overlapping by 75.
+    let stretch = math.stretch(5, 650, true).unwrap();
+    assert_eq!(
+        stretch.glyphs,
+        vec![glyph(10, 0), glyph(11, 225), glyph(12, 350)]
+    );
+    assert_eq!(stretch.size, 650);
+
+    // Without an assembly the largest variant is the best available.
+    assert_eq!(
+        math.stretch(8, 900, false).unwrap().glyphs,
+        vec![glyph(9, 0)]
+    );
+    assert!(math.stretch(8, 900, true).is_none());
+    assert!(math.stretch(5, 900, false).is_none());
+}
+
+#[test]
+fn test_math_invalid() {
+    use aurora_font::components::math::read_math;
+    use aurora_font::io::stream::FontDataStream;
+
+    // Only version 1 is supported.
+    assert!(read_math(&mut FontDataStream::new(&[0, 2, 0, 0, 0, 0, 0, 0, 0, 0])).is_err());
+    assert!(read_math(&mut FontDataStream::new(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0])).is_err());
+    // The constants offset points past the end of the table.
+    assert!(read_math(&mut FontDataStream::new(&[0, 1, 0, 0, 0, 10])).is_err());
+
+    // Null offsets leave the subtables out.
+    let math = read_math(&mut FontDataStream::new(&[0, 1, 0, 0, 0, 0, 0, 0, 0, 0])).unwrap();
+    assert!(math.constants.is_none() && math.glyph_info.is_none() && math.variants.is_none());
+}
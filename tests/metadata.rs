@@ -0,0 +1,130 @@
+//! Tests for the `meta`, `DSIG` and `PCLT` informational tables.
+
+#[test]
+fn test_meta() {
+    use aurora_font::components::meta::{MetaData, read_meta};
+    use aurora_font::io::stream::FontDataStream;
+
+    // Version 1, a `dlng` map at offset 40 with loose spacing and an empty
+    // item, and an `appl` map after it.
+    let mut data = vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+    data.extend_from_slice(b"dlng");
+    data.extend_from_slice(&[0, 0, 0, 40, 0, 0, 0, 21]);
+    data.extend_from_slice(b"appl");
+    data.extend_from_slice(&[0, 0, 0, 61, 0, 0, 0, 3]);
+    data.extend_from_slice(b"Arab,  Latn ,,zh-Hant");
+    data.extend_from_slice(&[1, 2, 3]);
+    let meta = read_meta(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(meta.design_languages(), ["Arab", "Latn", "zh-Hant"]);
+    assert!(meta.supported_languages().is_empty());
+    assert_eq!(meta.get(*b"appl"), Some(&MetaData::Opaque(vec![1, 2, 3])));
+
+    // An `slng` map at offset 40 and a `bild` map at offset 54.
+    let mut data = vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+    data.extend_from_slice(b"slng");
+    data.extend_from_slice(&[0, 0, 0, 40, 0, 0, 0, 14]);
+    data.extend_from_slice(b"bild");
+    data.extend_from_slice(&[0, 0, 0, 54, 0, 0, 0, 4]);
+    data.extend_from_slice(b"Arab,Latn,Cyrl");
+    data.extend_from_slice(b"1234");
+    let meta = read_meta(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!((meta.version, meta.flags), (1, 0));
+    assert!(meta.design_languages().is_empty());
+    assert_eq!(meta.supported_languages(), ["Arab", "Latn", "Cyrl"]);
+    assert_eq!(
+        meta.get(*b"bild"),
+        Some(&MetaData::Opaque(b"1234".to_vec()))
+    );
+
+    assert!(read_meta(&mut FontDataStream::new(&[0, 0, 0, 2, 0, 0, 0, 0])).is_err());
+}
+
+#[test]
+fn test_dsig() {
+    use aurora_font::components::dsig::{DSIG_CANNOT_BE_RESIGNED, read_dsig};
+    use aurora_font::io::stream::FontDataStream;
+
+    #[rustfmt::skip]
+    let data = [
+        0, 0, 0, 1, 0, 2, 0, 1,           // version, numSignatures, flags
+        0, 0, 0, 1, 0, 0, 0, 12, 0, 0, 0, 32, // format, length, offset
+        0, 0, 0, 1, 0, 0, 0, 13, 0, 0, 0, 44,
+        0, 0, 0, 0, 0, 0, 0, 4,           // reserved words, signature length
+        0x30, 0x82, 0x01, 0x00,
+        0, 0, 0, 0, 0, 0, 0, 5,
+        0x30, 0x03, 0x02, 0x01, 0x01,
+    ];
+    let dsig = read_dsig(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(dsig.version, 1);
+    assert_eq!(dsig.flags, DSIG_CANNOT_BE_RESIGNED);
+    assert!(dsig.is_signed());
+    let signatures: Vec<_> = dsig
+        .signatures
+        .iter()
+        .map(|s| (s.format, s.signature.as_slice()))
+        .collect();
+    assert_eq!(
+        signatures,
+        [
+            (1, &[0x30, 0x82, 0x01, 0x00][..]),
+            (1, &[0x30, 0x03, 0x02, 0x01, 0x01][..])
+        ]
+    );
+
+    // The placeholder left behind once a signature is removed.
+    let dsig = read_dsig(&mut FontDataStream::new(&[0, 0, 0, 1, 0, 0, 0, 0])).unwrap();
+    assert!(!dsig.is_signed());
+
+    // A signature block pointing past the end of the table.
+    assert!(
+        read_dsig(&mut FontDataStream::new(&[
+            0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 12, 0, 0, 0, 20
+        ]))
+        .is_err()
+    );
+}
+
+#[test]
+fn test_pclt() {
+    use aurora_font::components::pclt::{PcltTable, read_pclt};
+    use aurora_font::io::stream::FontDataStream;
+
+    #[rustfmt::skip]
+    let data: [u8; 54] = [
+        0, 1, 0, 0,                       // version 1.0
+        0x80, 0x00, 0x12, 0x34,           // fontNumber
+        0x02, 0x00, 0x04, 0x4C,           // pitch 512, xHeight 1100
+        0x00, 0x01, 0x40, 0x03,           // style, typeFamily
+        0x05, 0xAA, 0x01, 0x15,           // capHeight 1450, symbolSet
+        b'A', b'u', b'r', b'o', b'r', b'a', b' ', b'S',
+        b'a', b'n', b's', b' ', b' ', b' ', b' ', b' ',
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+        b'A', b'U', b'R', b'S', b'A', b'N',
+        0xFF, 0x00, 0x40,                 // strokeWeight, widthType, serifStyle
+        0,                                // reserved
+    ];
+    let pclt = read_pclt(&mut FontDataStream::new(&data)).unwrap();
+    assert_eq!(
+        pclt,
+        PcltTable {
+            major_version: 1,
+            minor_version: 0,
+            font_number: 0x8000_1234,
+            pitch: 512,
+            x_height: 1100,
+            style: 0x0001,
+            type_family: 0x4003,
+            cap_height: 1450,
+            symbol_set: 0x0115,
+            typeface: *b"Aurora Sans     ",
+            character_complement: [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE],
+            file_name: *b"AURSAN",
+            stroke_weight: -1,
+            width_type: 0,
+            serif_style: 0x40,
+        }
+    );
+    assert_eq!(pclt.typeface_name(), "Aurora Sans");
+
+    assert!(read_pclt(&mut FontDataStream::new(&data[..40])).is_err());
+}
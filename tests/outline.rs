@@ -0,0 +1,129 @@
+//! Tests for glyph outline path commands.
+
+#[test]
+fn test_contour_path_implied_points() {
+    use aurora_font::outline::{PathCommand, contour_path};
+
+    // Two off-curve points in a row imply an on-curve point between them.
+    let points = [
+        (0.0, 0.0, true),
+        (0.0, 100.0, false),
+        (100.0, 100.0, false),
+        (100.0, 0.0, true),
+    ];
+    let path = contour_path(&points, &[3]);
+    assert_eq!(
+        path,
+        vec![
+            PathCommand::MoveTo(0.0, 0.0),
+            PathCommand::QuadTo(0.0, 100.0, 50.0, 100.0),
+            PathCommand::QuadTo(100.0, 100.0, 100.0, 0.0),
+            PathCommand::Close,
+        ]
+    );
+}
+
+#[test]
+fn test_contour_path_all_off_curve() {
+    use aurora_font::outline::{PathCommand, contour_path};
+
+    let points = [
+        (0.0, 0.0, false),
+        (0.0, 100.0, false),
+        (100.0, 100.0, false),
+        (100.0, 0.0, false),
+    ];
+    let path = contour_path(&points, &[3]);
+    assert_eq!(path[0], PathCommand::MoveTo(50.0, 0.0));
+    assert_eq!(path.len(), 6);
+    assert_eq!(path[4], PathCommand::QuadTo(100.0, 0.0, 50.0, 0.0));
+}
+
+#[test]
+fn test_glyf_path_composite() {
+    use aurora_font::components::glyf::{
+        ARGS_ARE_XY_VALUES, CompositeGlyph, GlyfTable, Glyph, GlyphComponent, GlyphPoint,
+        SimpleGlyph,
+    };
+    use aurora_font::outline::{PathCommand, glyf_path};
+
+    let simple = SimpleGlyph {
+        end_points: vec![2],
+        points: [(0, 0), (100, 0), (0, 100)]
+            .iter()
+            .map(|&(x, y)| GlyphPoint {
+                x,
+                y,
+                on_curve: true,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let composite = CompositeGlyph {
+        components: vec![
+            GlyphComponent {
+                flags: ARGS_ARE_XY_VALUES,
+                glyph_index: 0,
+                ..Default::default()
+            },
+            GlyphComponent {
+                flags: ARGS_ARE_XY_VALUES,
+                glyph_index: 0,
+                arg1: 200,
+                arg2: -50,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let glyf = GlyfTable {
+        glyphs: vec![Glyph::Simple(simple), Glyph::Composite(composite)],
+    };
+    let path = glyf_path(&glyf, 1).unwrap();
+    assert_eq!(path.len(), 8);
+    assert_eq!(path[4], PathCommand::MoveTo(200.0, -50.0));
+    assert_eq!(path[5], PathCommand::LineTo(300.0, -50.0));
+    assert!(glyf_path(&glyf, 2).is_err());
+}
+
+#[test]
+fn test_char_string_path() {
+    use aurora_font::components::cff1::CharStringToken::{Number, Operator};
+    use aurora_font::outline::{PathCommand, char_string_path};
+
+    // 500 width, 10 20 rmoveto, 100 hlineto, 50 60 70 80 90 100 rrcurveto, endchar
+    let tokens = [
+        Number(500.0),
+        Number(10.0),
+        Number(20.0),
+        Operator(21),
+        Number(100.0),
+        Operator(6),
+        Number(50.0),
+        Number(60.0),
+        Number(70.0),
+        Number(80.0),
+        Number(90.0),
+        Number(100.0),
+        Operator(8),
+        Operator(14),
+    ];
+    assert_eq!(
+        char_string_path(&tokens),
+        vec![
+            PathCommand::MoveTo(10.0, 20.0),
+            PathCommand::LineTo(110.0, 20.0),
+            PathCommand::CurveTo(160.0, 80.0, 230.0, 160.0, 320.0, 260.0),
+            PathCommand::Close,
+        ]
+    );
+}
+
+#[test]
+fn test_path_command_map() {
+    use aurora_font::outline::PathCommand;
+
+    let command = PathCommand::QuadTo(1.0, 2.0, 3.0, 4.0).map(|x, y| (x * 2.0, -y));
+    assert_eq!(command, PathCommand::QuadTo(2.0, -2.0, 6.0, -4.0));
+    assert_eq!(PathCommand::Close.map(|x, y| (y, x)), PathCommand::Close);
+}
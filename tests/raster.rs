@@ -0,0 +1,116 @@
+//! Tests for the anti-aliased glyph rasterizer.
+
+/// Returns a closed square path with corners at `x0, y0` and `x1, y1`.
+#[cfg(feature = "raster")]
+fn square(x0: f32, y0: f32, x1: f32, y1: f32) -> Vec<aurora_font::outline::PathCommand> {
+    use aurora_font::outline::PathCommand;
+
+    vec![
+        PathCommand::MoveTo(x0, y0),
+        PathCommand::LineTo(x1, y0),
+        PathCommand::LineTo(x1, y1),
+        PathCommand::LineTo(x0, y1),
+        PathCommand::Close,
+    ]
+}
+
+#[test]
+#[cfg(feature = "raster")]
+fn test_square_coverage() {
+    use aurora_font::raster::{RasterOptions, render};
+
+    let bitmap = render(&square(0.0, 0.0, 4.0, 4.0), &RasterOptions::default()).unwrap();
+    assert_eq!((bitmap.width, bitmap.height), (4, 4));
+    assert_eq!((bitmap.left, bitmap.top), (0, 4));
+    assert!(bitmap.data.iter().all(|&c| c == 255));
+
+    // Half pixel edges are half covered.
+    let bitmap = render(&square(0.5, 0.0, 2.5, 1.0), &RasterOptions::default()).unwrap();
+    assert_eq!(bitmap.data, vec![128, 255, 128]);
+}
+
+#[test]
+#[cfg(feature = "raster")]
+fn test_fill_rules() {
+    use aurora_font::raster::{FillRule, RasterOptions, render};
+
+    // Two squares wound the same way overlap in the middle column.
+    let mut path = square(0.0, 0.0, 2.0, 1.0);
+    path.extend(square(1.0, 0.0, 3.0, 1.0));
+    let non_zero = render(&path, &RasterOptions::default()).unwrap();
+    assert_eq!(non_zero.data, vec![255, 255, 255]);
+    let options = RasterOptions {
+        fill_rule: FillRule::EvenOdd,
+        ..Default::default()
+    };
+    let even_odd = render(&path, &options).unwrap();
+    assert_eq!(even_odd.data, vec![255, 0, 255]);
+}
+
+#[test]
+#[cfg(feature = "raster")]
+fn test_subpixel_offset() {
+    use aurora_font::raster::{RasterOptions, render};
+
+    let options = RasterOptions {
+        scale: 0.5,
+        offset: (0.25, 0.0),
+        ..Default::default()
+    };
+    let bitmap = render(&square(0.0, 0.0, 2.0, 2.0), &options).unwrap();
+    assert_eq!((bitmap.width, bitmap.height, bitmap.left), (2, 1, 0));
+    assert_eq!(bitmap.data, vec![191, 64]);
+}
+
+#[test]
+#[cfg(feature = "raster")]
+fn test_lcd_mode() {
+    use aurora_font::raster::{RasterOptions, RenderMode, render};
+
+    let options = RasterOptions {
+        mode: RenderMode::Lcd,
+        ..Default::default()
+    };
+    let bitmap = render(&square(0.0, 0.0, 2.0, 1.0), &options).unwrap();
+    // One padding pixel on each side.
+    assert_eq!((bitmap.width, bitmap.left), (4, -1));
+    assert_eq!(bitmap.stride(), 12);
+    assert_eq!(bitmap.data.len(), 12);
+    assert_eq!(bitmap.data[0], 0);
+    assert_eq!(bitmap.data[6], 255);
+    assert!(bitmap.data[2] > 0 && bitmap.data[2] < 255);
+}
+
+#[test]
+#[cfg(feature = "raster")]
+fn test_curves_and_limits() {
+    use aurora_font::outline::PathCommand;
+    use aurora_font::raster::{RasterOptions, Rasterizer, render};
+
+    // A circle-like quad path is filled symmetrically.
+    let path = [
+        PathCommand::MoveTo(4.0, 0.0),
+        PathCommand::QuadTo(8.0, 0.0, 8.0, 4.0),
+        PathCommand::QuadTo(8.0, 8.0, 4.0, 8.0),
+        PathCommand::QuadTo(0.0, 8.0, 0.0, 4.0),
+        PathCommand::QuadTo(0.0, 0.0, 4.0, 0.0),
+        PathCommand::Close,
+    ];
+    let bitmap = render(&path, &RasterOptions::default()).unwrap();
+    assert_eq!(bitmap.data[3 * 8 + 3], 255);
+    assert_eq!(bitmap.data[0], bitmap.data[7]);
+    assert_eq!(bitmap.data[0], bitmap.data[63]);
+    assert!(bitmap.data[0] < 64);
+
+    let empty = render(&[], &RasterOptions::default()).unwrap();
+    assert!(empty.data.is_empty());
+    let huge = square(0.0, 0.0, 1.0e6, 1.0e6);
+    assert!(render(&huge, &RasterOptions::default()).is_err());
+    let nan = square(0.0, 0.0, f32::NAN, 1.0);
+    assert!(render(&nan, &RasterOptions::default()).is_err());
+
+    // Lines outside the grid still cover pixels to their right.
+    let mut rasterizer = Rasterizer::new(2, 1);
+    rasterizer.draw_path(&square(-5.0, 0.0, 1.0, 1.0));
+    assert_eq!(rasterizer.coverage(Default::default()), vec![255, 0]);
+}
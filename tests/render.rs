@@ -0,0 +1,365 @@
+//! Tests for text run rendering.
+
+/// Builds a 1000 unit em font. Glyph 1 is a 500 unit square; glyph 2 is a red
+/// `COLR` version 0 square; glyph 3 is a green 2x2 `sbix` image at 10 ppem;
+/// glyph 4 is a square filled with a red to blue `COLR` version 1 gradient.
+/// `gasp` turns off anti-aliasing up to 8 ppem.
+#[cfg(feature = "full")]
+fn build_font() -> Vec<u8> {
+    use aurora_font::common::snft::write_snft;
+    use aurora_font::components::cpal::Color;
+    use aurora_font::components::glyf::{
+        GlyfTable, Glyph, GlyphHeader, GlyphPoint, SimpleGlyph, write_glyf,
+    };
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric, write_hmtx};
+    use aurora_font::components::loca::write_loca;
+    use aurora_font::io::FontDataWriter;
+    use aurora_font::optional::render::RgbaImage;
+
+    let square = Glyph::Simple(SimpleGlyph {
+        header: GlyphHeader {
+            number_of_contours: 1,
+            x_min: 0,
+            y_min: 0,
+            x_max: 500,
+            y_max: 500,
+        },
+        end_points: vec![3],
+        instructions: vec![],
+        points: [(0, 0), (0, 500), (500, 500), (500, 0)]
+            .map(|(x, y)| GlyphPoint {
+                x,
+                y,
+                on_curve: true,
+            })
+            .to_vec(),
+        overlap: false,
+    });
+    let glyf = GlyfTable {
+        glyphs: vec![
+            Glyph::Empty,
+            square.clone(),
+            Glyph::Empty,
+            square.clone(),
+            square,
+        ],
+    };
+    let mut writer = FontDataWriter::new();
+    let loca = write_glyf(&mut writer, &glyf);
+    let glyf_data = writer.into_bytes().unwrap();
+    let mut writer = FontDataWriter::new();
+    write_loca(&mut writer, &loca, false).unwrap();
+    let loca_data = writer.into_bytes().unwrap();
+
+    let hmtx = HmtxTable {
+        h_metrics: vec![LongHorMetric {
+            advance_width: 500,
+            lsb: 0,
+        }],
+        left_side_bearings: vec![0; 4],
+    };
+    let mut writer = FontDataWriter::new();
+    write_hmtx(&mut writer, &hmtx);
+    let hmtx_data = writer.into_bytes().unwrap();
+
+    // Glyph 2 has one layer in palette entry 0; glyph 4 paints glyph 1 with
+    // a linear gradient from entry 0 to entry 1. The header places the base
+    // glyph record at 34, the layer record at 40 and the base glyph list at
+    // 44, with no layer list, clip list or variations.
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(1);
+    writer.write_u16(1);
+    writer.write_u32(34);
+    writer.write_u32(40);
+    writer.write_u16(1);
+    writer.write_u32(44);
+    writer.write_zeros(16);
+    for value in [2, 0, 1, 1, 0] {
+        writer.write_u16(value);
+    }
+    writer.write_u32(1);
+    writer.write_u16(4);
+    writer.write_u32(10);
+    // PaintGlyph, then PaintLinearGradient and its color line.
+    writer.write_u8(10);
+    writer.write_u24(6);
+    writer.write_u16(1);
+    writer.write_u8(4);
+    writer.write_u24(16);
+    for value in [0, 0, 500, 0, 0, 500] {
+        writer.write_i16(value);
+    }
+    writer.write_u8(0);
+    writer.write_u16(2);
+    for (stop_offset, palette_index) in [(0.0, 0), (1.0, 1)] {
+        writer.write_f2dot14(stop_offset);
+        writer.write_u16(palette_index);
+        writer.write_f2dot14(1.0);
+    }
+    let colr_data = writer.into_bytes().unwrap();
+    // One palette of opaque red and blue, as BGRA records.
+    let cpal_data = vec![
+        0, 0, 0, 2, 0, 1, 0, 2, 0, 0, 0, 14, 0, 0, 0, 0, 255, 255, 255, 0, 0, 255,
+    ];
+
+    // One 10 ppem strike; only glyph 3 has an image.
+    let mut green = RgbaImage::new(2, 2);
+    green.fill(Color::rgba(0, 255, 0, 255));
+    let png = green.to_png();
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(1);
+    writer.write_u16(1);
+    writer.write_u32(1);
+    writer.write_u32(12);
+    writer.write_u16(10);
+    writer.write_u16(72);
+    let end = 28 + 8 + png.len() as u32;
+    for offset in [28, 28, 28, 28, end, end] {
+        writer.write_u32(offset);
+    }
+    writer.write_i16(0);
+    writer.write_i16(0);
+    writer.write_tag(*b"png ");
+    writer.write_bytes(&png);
+    let sbix_data = writer.into_bytes().unwrap();
+
+    // Grid-fitting up to 8 ppem, grid-fitting and grayscale above.
+    #[rustfmt::skip]
+    let gasp_data = vec![
+        0, 1, // version
+        0, 2, // numRanges
+        0, 8, 0, 1, // rangeMaxPPEM, rangeGaspBehavior
+        0xFF, 0xFF, 0, 3,
+    ];
+
+    let mut head = vec![0u8; 54];
+    head[0..2].copy_from_slice(&1u16.to_be_bytes());
+    head[12..16].copy_from_slice(&0x5F0F3CF5u32.to_be_bytes());
+    head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+    let mut hhea = vec![0u8; 36];
+    hhea[0..2].copy_from_slice(&1u16.to_be_bytes());
+    hhea[34..36].copy_from_slice(&1u16.to_be_bytes());
+    // Version 1.0 with room for 4 points, 2 zones and 16 stack elements.
+    let mut maxp = vec![0u8; 32];
+    maxp[..4].copy_from_slice(&0x00010000u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&5u16.to_be_bytes());
+    maxp[6..8].copy_from_slice(&4u16.to_be_bytes());
+    maxp[8..10].copy_from_slice(&1u16.to_be_bytes());
+    maxp[14..16].copy_from_slice(&2u16.to_be_bytes());
+    maxp[24..26].copy_from_slice(&16u16.to_be_bytes());
+
+    let mut writer = FontDataWriter::new();
+    write_snft(
+        &mut writer,
+        0x00010000,
+        &[
+            (*b"COLR", colr_data),
+            (*b"CPAL", cpal_data),
+            (*b"gasp", gasp_data),
+            (*b"glyf", glyf_data),
+            (*b"head", head),
+            (*b"hhea", hhea),
+            (*b"hmtx", hmtx_data),
+            (*b"loca", loca_data),
+            (*b"maxp", maxp),
+            (*b"sbix", sbix_data),
+        ],
+    )
+    .unwrap();
+    writer.into_bytes().unwrap()
+}
+
+/// Renders one unhinted glyph at `size` pixels per em with its origin at
+/// `origin` on a 20x20 image.
+#[cfg(feature = "full")]
+fn render_glyph(glyph_id: u16, origin: (f32, f32), size: f32) -> aurora_font::optional::RgbaImage {
+    use aurora_font::optional::render::{RenderOptions, RgbaImage, ShapedGlyph, TextRenderer};
+
+    let font = build_font();
+    let mut renderer = TextRenderer::new(&font).unwrap();
+    let mut image = RgbaImage::new(20, 20);
+    let glyphs = [ShapedGlyph {
+        glyph_id,
+        x_advance: 500.0,
+        ..Default::default()
+    }];
+    let options = RenderOptions {
+        size,
+        hinting: false,
+        ..Default::default()
+    };
+    renderer
+        .render_run(&mut image, &glyphs, origin, &options)
+        .unwrap();
+    image
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn test_render_outline_run() {
+    use aurora_font::components::cpal::Color;
+    use aurora_font::optional::render::{RenderOptions, RgbaImage, ShapedGlyph, TextRenderer};
+
+    let font = build_font();
+    let mut renderer = TextRenderer::new(&font).unwrap();
+    let mut image = RgbaImage::new(20, 20);
+    let square = ShapedGlyph {
+        glyph_id: 1,
+        x_advance: 600.0,
+        ..Default::default()
+    };
+    let options = RenderOptions {
+        size: 10.0,
+        color: Color::rgba(0, 0, 255, 255),
+        ..Default::default()
+    };
+    let pen = renderer
+        .render_run(&mut image, &[square, square], (2.0, 12.0), &options)
+        .unwrap();
+    assert_eq!(pen, (14.0, 12.0));
+    // Squares cover x 2..7 and 8..13, y 7..12.
+    assert_eq!(image.pixel(4, 9), Some(Color::rgba(0, 0, 255, 255)));
+    assert_eq!(image.pixel(10, 11), Some(Color::rgba(0, 0, 255, 255)));
+    assert_eq!(image.pixel(7, 9).unwrap().alpha, 0);
+    assert_eq!(image.pixel(4, 12).unwrap().alpha, 0);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn test_render_subpixel_and_gasp() {
+    // Half a pixel to the right, the left column is half covered.
+    let image = render_glyph(1, (2.5, 12.0), 10.0);
+    assert_eq!(image.pixel(2, 9).unwrap().alpha, 128);
+    assert_eq!(image.pixel(7, 9).unwrap().alpha, 128);
+
+    // At 8 ppem `gasp` turns anti-aliasing off.
+    let image = render_glyph(1, (2.5, 12.0), 8.0);
+    for x in 0..20 {
+        let alpha = image.pixel(x, 10).unwrap().alpha;
+        assert!(alpha == 0 || alpha == 255);
+    }
+    assert_eq!(image.pixel(4, 10).unwrap().alpha, 255);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn test_render_hinted() {
+    use aurora_font::optional::render::{RenderOptions, RgbaImage, ShapedGlyph, TextRenderer};
+
+    let font = build_font();
+    let mut renderer = TextRenderer::new(&font).unwrap();
+    let mut image = RgbaImage::new(20, 20);
+    let glyphs = [ShapedGlyph {
+        glyph_id: 1,
+        ..Default::default()
+    }];
+    let options = RenderOptions {
+        size: 10.0,
+        ..Default::default()
+    };
+    renderer
+        .render_run(&mut image, &glyphs, (2.4, 12.0), &options)
+        .unwrap();
+    // Hinted glyphs start on a whole pixel.
+    assert_eq!(image.pixel(1, 9).unwrap().alpha, 0);
+    assert_eq!(image.pixel(2, 9).unwrap().alpha, 255);
+    assert_eq!(image.pixel(6, 9).unwrap().alpha, 255);
+    assert_eq!(image.pixel(7, 9).unwrap().alpha, 0);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn test_render_color_glyphs() {
+    use aurora_font::components::cpal::Color;
+
+    let image = render_glyph(2, (2.0, 12.0), 10.0);
+    assert_eq!(image.pixel(4, 9), Some(Color::rgba(255, 0, 0, 255)));
+    assert_eq!(image.pixel(8, 9).unwrap().alpha, 0);
+
+    // The gradient runs from red on the left to blue on the right.
+    let image = render_glyph(4, (2.0, 12.0), 10.0);
+    let (left, right) = (image.pixel(2, 9).unwrap(), image.pixel(6, 9).unwrap());
+    assert!(left.red > 200 && left.blue < 50, "{left:?}");
+    assert!(right.blue > 200 && right.red < 50, "{right:?}");
+    assert_eq!(left.alpha, 255);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn test_render_sbix_bitmap() {
+    use aurora_font::components::cpal::Color;
+
+    // The 2x2 image sits on the baseline at the glyph origin.
+    let image = render_glyph(3, (2.0, 12.0), 10.0);
+    assert_eq!(image.pixel(2, 10), Some(Color::rgba(0, 255, 0, 255)));
+    assert_eq!(image.pixel(3, 11), Some(Color::rgba(0, 255, 0, 255)));
+    assert_eq!(image.pixel(2, 9).unwrap().alpha, 0);
+    assert_eq!(image.pixel(4, 10).unwrap().alpha, 0);
+
+    // At 20 ppem the image is scaled up.
+    let image = render_glyph(3, (2.0, 12.0), 20.0);
+    assert_eq!(image.pixel(5, 8).unwrap().green, 255);
+    assert_eq!(image.pixel(6, 8).unwrap().alpha, 0);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn test_render_ignores_broken_bitmaps() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::cpal::Color;
+    use aurora_font::io::FontDataStream;
+    use aurora_font::optional::render::{RenderOptions, RgbaImage, ShapedGlyph, TextRenderer};
+
+    // An `sbix` table with an unknown version is skipped like a missing one.
+    let mut font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let offset = snft.get_table_by_tag(b"sbix").unwrap().offset as usize;
+    font[offset + 1] = 9;
+    let mut renderer = TextRenderer::new(&font).unwrap();
+    let mut image = RgbaImage::new(20, 20);
+    let glyphs = [ShapedGlyph {
+        glyph_id: 3,
+        ..Default::default()
+    }];
+    let options = RenderOptions {
+        size: 10.0,
+        ..Default::default()
+    };
+    renderer
+        .render_run(&mut image, &glyphs, (2.0, 12.0), &options)
+        .unwrap();
+    // The glyph falls back to its outline instead of the green bitmap.
+    assert_eq!(image.pixel(2, 10), Some(Color::rgba(0, 0, 0, 255)));
+}
+
+#[test]
+fn test_gasp_behavior() {
+    use aurora_font::components::gasp::{GASP_DOGRAY, GaspRange, GaspTable, read_gasp};
+    use aurora_font::io::FontDataStream;
+
+    #[rustfmt::skip]
+    let bytes = [
+        0, 0, // version
+        0, 2, // numRanges
+        0, 8, 0, 0, // rangeMaxPPEM, rangeGaspBehavior
+        0, 20, 0, 2,
+    ];
+    let parsed = read_gasp(&mut FontDataStream::new(&bytes)).unwrap();
+    let gasp = GaspTable {
+        version: 0,
+        ranges: vec![
+            GaspRange {
+                range_max_ppem: 8,
+                range_gasp_behavior: 0,
+            },
+            GaspRange {
+                range_max_ppem: 20,
+                range_gasp_behavior: GASP_DOGRAY,
+            },
+        ],
+    };
+    assert_eq!(parsed, gasp);
+    assert_eq!(parsed.behavior(8), 0);
+    assert_eq!(parsed.behavior(9), GASP_DOGRAY);
+    assert_eq!(parsed.behavior(21), 0);
+}
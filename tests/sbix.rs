@@ -0,0 +1,92 @@
+//! Tests for `sbix` bitmap glyphs.
+
+/// Lays out an `sbix` table with 64 and 20 ppem strikes over four glyphs.
+/// Glyph 0 has no image, glyph 2 duplicates glyph 1 and glyph 3 duplicates
+/// itself.
+fn sbix_data() -> Vec<u8> {
+    use aurora_font::io::FontDataWriter;
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(1);
+    writer.write_u16(1);
+    writer.write_u32(2);
+    writer.write_u32(16);
+    writer.write_u32(78);
+    for (ppem, image) in [(64, b"\x89PNG large"), (20, b"\x89PNG small")] {
+        writer.write_u16(ppem);
+        writer.write_u16(72);
+        // Glyph data offsets from the start of the strike, one past the last
+        // glyph included.
+        for offset in [24, 24, 42, 52, 62] {
+            writer.write_u32(offset);
+        }
+        writer.write_i16(-1);
+        writer.write_i16(2);
+        writer.write_tag(*b"png ");
+        writer.write_bytes(image);
+        for glyph_id in [1, 3] {
+            writer.write_i16(0);
+            writer.write_i16(0);
+            writer.write_tag(*b"dupe");
+            writer.write_u16(glyph_id);
+        }
+    }
+    writer.into_bytes().unwrap()
+}
+
+#[test]
+fn test_read_sbix() {
+    use aurora_font::components::sbix::{GRAPHIC_TYPE_DUPE, GRAPHIC_TYPE_PNG, read_sbix};
+    use aurora_font::io::FontDataStream;
+
+    let data = sbix_data();
+    let sbix = read_sbix(&mut FontDataStream::new(&data), 4).unwrap();
+    assert_eq!((sbix.version, sbix.flags), (1, 1));
+    let strikes: Vec<(u16, u16)> = sbix.strikes.iter().map(|s| (s.ppem, s.ppi)).collect();
+    assert_eq!(strikes, [(64, 72), (20, 72)]);
+    let glyphs = &sbix.strikes[1].glyphs;
+    assert_eq!(glyphs.len(), 4);
+    assert!(glyphs[0].is_none());
+    let glyph = glyphs[1].as_ref().unwrap();
+    assert_eq!((glyph.origin_offset_x, glyph.origin_offset_y), (-1, 2));
+    assert_eq!(glyph.graphic_type, GRAPHIC_TYPE_PNG);
+    assert_eq!(glyph.data, b"\x89PNG small");
+    let glyph = glyphs[2].as_ref().unwrap();
+    assert_eq!(glyph.graphic_type, GRAPHIC_TYPE_DUPE);
+    assert_eq!(glyph.data, [0, 1]);
+
+    // Glyph data records shorter than their header are rejected.
+    let mut data = data;
+    data[16 + 4 + 3 * 4..][..4].copy_from_slice(&46u32.to_be_bytes());
+    assert!(read_sbix(&mut FontDataStream::new(&data), 4).is_err());
+    // As are tables with fewer offsets than glyphs.
+    assert!(read_sbix(&mut FontDataStream::new(&sbix_data()[..40]), 4).is_err());
+}
+
+#[test]
+fn test_bitmap_glyph() {
+    use aurora_font::components::sbix::{GRAPHIC_TYPE_PNG, read_sbix};
+    use aurora_font::io::FontDataStream;
+
+    let sbix = read_sbix(&mut FontDataStream::new(&sbix_data()), 4).unwrap();
+    // The smallest strike at least as large as the request is picked.
+    let bitmap = sbix.bitmap_glyph(1, 32).unwrap().unwrap();
+    assert_eq!(bitmap.ppem, 64);
+    assert_eq!(bitmap.glyph.data, b"\x89PNG large");
+    assert_eq!(bitmap.glyph.graphic_type, GRAPHIC_TYPE_PNG);
+    assert_eq!(
+        (bitmap.glyph.origin_offset_x, bitmap.glyph.origin_offset_y),
+        (-1, 2)
+    );
+    assert_eq!(sbix.bitmap_glyph(1, 12).unwrap().unwrap().ppem, 20);
+    // Past the largest strike, the largest is used.
+    assert_eq!(sbix.bitmap_glyph(1, 128).unwrap().unwrap().ppem, 64);
+
+    // `dupe` records resolve to the image they point at.
+    let bitmap = sbix.bitmap_glyph(2, 20).unwrap().unwrap();
+    assert_eq!(bitmap.glyph.data, b"\x89PNG small");
+
+    assert!(sbix.bitmap_glyph(0, 20).unwrap().is_none());
+    assert!(sbix.bitmap_glyph(9, 20).unwrap().is_none());
+    assert!(sbix.bitmap_glyph(3, 20).is_err());
+}
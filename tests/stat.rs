@@ -0,0 +1,313 @@
+//! Tests for style attributes and style name synthesis.
+
+/// Builds a `STAT` table with weight, width and italic axes, and the `name`
+/// table holding its value names.
+fn build_stat() -> (
+    aurora_font::components::stat::StatTable,
+    aurora_font::components::name::NameTable,
+) {
+    use aurora_font::components::name::NameTable;
+    use aurora_font::components::stat::{
+        AxisRecord, AxisValue, ELIDABLE_AXIS_VALUE_NAME, OLDER_SIBLING_FONT_ATTRIBUTE, StatTable,
+    };
+
+    let mut name = NameTable::default();
+    for (name_id, value) in [
+        (2, "Regular"),
+        (256, "Weight"),
+        (257, "Width"),
+        (258, "Italic"),
+        (259, "Regular"),
+        (260, "Bold"),
+        (261, "Light"),
+        (262, "Normal"),
+        (263, "Condensed"),
+        (264, "Roman"),
+        (265, "Black Compressed"),
+        (266, "Heavy"),
+    ] {
+        name.set(name_id, value);
+    }
+
+    let stat = StatTable {
+        major_version: 1,
+        minor_version: 1,
+        // The italic axis is named last although it is declared first.
+        design_axes: vec![
+            AxisRecord {
+                tag: *b"ital",
+                axis_name_id: 258,
+                axis_ordering: 2,
+            },
+            AxisRecord {
+                tag: *b"wght",
+                axis_name_id: 256,
+                axis_ordering: 0,
+            },
+            AxisRecord {
+                tag: *b"wdth",
+                axis_name_id: 257,
+                axis_ordering: 1,
+            },
+        ],
+        axis_values: vec![
+            AxisValue::Linked {
+                axis_index: 1,
+                flags: ELIDABLE_AXIS_VALUE_NAME,
+                value_name_id: 259,
+                value: 400.0,
+                linked_value: 700.0,
+            },
+            // An older font of the family named weight 700 "Heavy".
+            AxisValue::Value {
+                axis_index: 1,
+                flags: OLDER_SIBLING_FONT_ATTRIBUTE,
+                value_name_id: 266,
+                value: 700.0,
+            },
+            AxisValue::Value {
+                axis_index: 1,
+                flags: 0,
+                value_name_id: 260,
+                value: 700.0,
+            },
+            AxisValue::Range {
+                axis_index: 1,
+                flags: 0,
+                value_name_id: 261,
+                nominal_value: 300.0,
+                range_min_value: 200.0,
+                range_max_value: 350.0,
+            },
+            AxisValue::Value {
+                axis_index: 2,
+                flags: ELIDABLE_AXIS_VALUE_NAME,
+                value_name_id: 262,
+                value: 100.0,
+            },
+            AxisValue::Value {
+                axis_index: 2,
+                flags: 0,
+                value_name_id: 263,
+                value: 75.0,
+            },
+            AxisValue::Value {
+                axis_index: 0,
+                flags: ELIDABLE_AXIS_VALUE_NAME,
+                value_name_id: 264,
+                value: 0.0,
+            },
+            AxisValue::Value {
+                axis_index: 0,
+                flags: 0,
+                value_name_id: 258,
+                value: 1.0,
+            },
+            AxisValue::Location {
+                flags: 0,
+                value_name_id: 265,
+                values: vec![(1, 900.0), (2, 50.0)],
+            },
+        ],
+        elided_fallback_name_id: Some(259),
+    };
+    (stat, name)
+}
+
+/// The variable COLRv1 test font, whose `STAT` names its 44 axes and no
+/// values.
+const COLR_1_VARIABLE: &[u8] = include_bytes!("../assets/test-fonts/colr_1_variable.ttf");
+
+#[test]
+fn test_stat_font() {
+    use std::collections::BTreeMap;
+
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::name::read_name;
+    use aurora_font::components::stat::read_stat;
+    use aurora_font::io::FontDataStream;
+
+    let snft = read_snft(&mut FontDataStream::new(COLR_1_VARIABLE)).unwrap();
+    let table = |tag: &[u8; 4]| FontDataStream::new(snft.table_data(tag, COLR_1_VARIABLE).unwrap());
+    let stat = read_stat(&mut table(b"STAT")).unwrap();
+    let name = read_name(&mut table(b"name")).unwrap();
+
+    assert_eq!((stat.major_version, stat.minor_version), (1, 1));
+    assert_eq!(stat.design_axes.len(), 44);
+    assert_eq!(&stat.design_axes[0].tag, b"SWPS");
+    assert_eq!(
+        name.get(stat.design_axes[0].axis_name_id).as_deref(),
+        Some("Sweep Start Angle Offset")
+    );
+    assert_eq!(stat.axis_index(b"APH3"), Some(43));
+    assert_eq!(stat.design_axes[43].axis_ordering, 43);
+    assert!(stat.axis_values.is_empty());
+    assert_eq!(stat.elided_fallback_name_id, Some(2));
+
+    // Without axis values, every instance takes the elided fallback name.
+    let location = BTreeMap::from([(*b"SWPS", 45.0), (*b"ROTA", 150.0)]);
+    assert!(stat.axis_values_at(&location).is_empty());
+    assert_eq!(
+        stat.style_name(&location, &name).as_deref(),
+        Some("Regular")
+    );
+}
+
+#[test]
+fn test_read_stat_axis_values() {
+    use std::collections::BTreeMap;
+
+    use aurora_font::components::stat::{AxisValue, ELIDABLE_AXIS_VALUE_NAME, read_stat};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    // Version 1.2 with weight and italic axes and one value of each format.
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(1);
+    writer.write_u16(2);
+    writer.write_u16(8); // designAxisSize
+    writer.write_u16(2); // designAxisCount
+    writer.write_u32(20); // designAxesOffset
+    writer.write_u16(4); // axisValueCount
+    writer.write_u32(36); // offsetToAxisValueOffsets
+    writer.write_u16(2); // elidedFallbackNameID
+    writer.write_tag(*b"wght");
+    writer.write_u16(256);
+    writer.write_u16(0);
+    writer.write_tag(*b"ital");
+    writer.write_u16(257);
+    writer.write_u16(1);
+    for offset in [8, 20, 40, 56] {
+        writer.write_u16(offset);
+    }
+    // Format 1: Bold, wght 700.
+    writer.write_u16(1);
+    writer.write_u16(0);
+    writer.write_u16(0);
+    writer.write_u16(258);
+    writer.write_fixed(700.0);
+    // Format 2: Light, wght 200 to 350.
+    writer.write_u16(2);
+    writer.write_u16(0);
+    writer.write_u16(0);
+    writer.write_u16(259);
+    writer.write_fixed(300.0);
+    writer.write_fixed(200.0);
+    writer.write_fixed(350.0);
+    // Format 3: Regular, wght 400 linked to 700, elidable.
+    writer.write_u16(3);
+    writer.write_u16(0);
+    writer.write_u16(ELIDABLE_AXIS_VALUE_NAME);
+    writer.write_u16(260);
+    writer.write_fixed(400.0);
+    writer.write_fixed(700.0);
+    // Format 4: Black Italic, wght 900 and ital 1.
+    writer.write_u16(4);
+    writer.write_u16(2);
+    writer.write_u16(0);
+    writer.write_u16(261);
+    writer.write_u16(0);
+    writer.write_fixed(900.0);
+    writer.write_u16(1);
+    writer.write_fixed(1.0);
+    let bytes = writer.into_bytes().unwrap();
+
+    let stat = read_stat(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(stat.design_axes.len(), 2);
+    assert_eq!(stat.elided_fallback_name_id, Some(2));
+    assert_eq!(
+        stat.axis_values,
+        vec![
+            AxisValue::Value {
+                axis_index: 0,
+                flags: 0,
+                value_name_id: 258,
+                value: 700.0,
+            },
+            AxisValue::Range {
+                axis_index: 0,
+                flags: 0,
+                value_name_id: 259,
+                nominal_value: 300.0,
+                range_min_value: 200.0,
+                range_max_value: 350.0,
+            },
+            AxisValue::Linked {
+                axis_index: 0,
+                flags: ELIDABLE_AXIS_VALUE_NAME,
+                value_name_id: 260,
+                value: 400.0,
+                linked_value: 700.0,
+            },
+            AxisValue::Location {
+                flags: 0,
+                value_name_id: 261,
+                values: vec![(0, 900.0), (1, 1.0)],
+            },
+        ]
+    );
+
+    let names = |location: &[([u8; 4], f32)]| {
+        let location = BTreeMap::from_iter(location.iter().copied());
+        stat.axis_values_at(&location)
+            .iter()
+            .map(|value| value.value_name_id())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&[(*b"wght", 700.0)]), [258]);
+    assert_eq!(names(&[(*b"wght", 250.0)]), [259]);
+    assert_eq!(names(&[(*b"wght", 400.0), (*b"ital", 0.0)]), [260]);
+    assert_eq!(names(&[(*b"wght", 900.0), (*b"ital", 1.0)]), [261]);
+    assert_eq!(names(&[(*b"wght", 500.0)]), Vec::<u16>::new());
+}
+
+#[test]
+fn test_style_name() {
+    use std::collections::BTreeMap;
+
+    let (stat, name) = build_stat();
+    let style = |location: &[(&[u8; 4], f32)]| {
+        let location: BTreeMap<[u8; 4], f32> =
+            location.iter().map(|&(tag, value)| (*tag, value)).collect();
+        stat.style_name(&location, &name)
+    };
+
+    assert_eq!(
+        style(&[(b"wght", 700.0), (b"wdth", 75.0), (b"ital", 1.0)]).as_deref(),
+        Some("Bold Condensed Italic")
+    );
+    // Elidable names are dropped.
+    assert_eq!(
+        style(&[(b"wght", 700.0), (b"wdth", 100.0), (b"ital", 0.0)]).as_deref(),
+        Some("Bold")
+    );
+    assert_eq!(
+        style(&[(b"wght", 400.0), (b"wdth", 100.0), (b"ital", 1.0)]).as_deref(),
+        Some("Italic")
+    );
+    // Every name elided falls back to the elided fallback name.
+    assert_eq!(
+        style(&[(b"wght", 400.0), (b"wdth", 100.0), (b"ital", 0.0)]).as_deref(),
+        Some("Regular")
+    );
+    // Ranges match coordinates between their bounds.
+    assert_eq!(
+        style(&[(b"wght", 250.0), (b"wdth", 75.0)]).as_deref(),
+        Some("Light Condensed")
+    );
+    // A combination of values names both of its axes at once.
+    assert_eq!(
+        style(&[(b"wght", 900.0), (b"wdth", 50.0), (b"ital", 1.0)]).as_deref(),
+        Some("Black Compressed Italic")
+    );
+    // Locations without a matching value are left out of the name.
+    assert_eq!(
+        style(&[(b"wght", 500.0), (b"ital", 1.0)]).as_deref(),
+        Some("Italic")
+    );
+
+    // Values of older sibling fonts are never chosen.
+    let location = BTreeMap::from([(*b"wght", 700.0)]);
+    let values = stat.axis_values_at(&location);
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].value_name_id(), 260);
+}
@@ -0,0 +1,573 @@
+//! Tests for font subsetting.
+
+/// Builds a TrueType font with the glyphs `.notdef`, `x`, `f`, `i` and a
+/// composite `f_i` ligature reachable through `GSUB`.
+fn build_font() -> Vec<u8> {
+    use std::collections::BTreeMap;
+
+    use aurora_font::common::snft::write_snft;
+    use aurora_font::components::cmap::{CmapSubtable, CmapTable, EncodingRecord, write_cmap};
+    use aurora_font::components::glyf::{
+        ARG_1_AND_2_ARE_WORDS, ARGS_ARE_XY_VALUES, ComponentTransform, CompositeGlyph, GlyfTable,
+        Glyph, GlyphComponent, GlyphHeader, GlyphPoint, MORE_COMPONENTS, SimpleGlyph, write_glyf,
+    };
+    use aurora_font::components::gsub::{GsubTable, Ligature, SubstitutionSubtable, write_gsub};
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric, write_hmtx};
+    use aurora_font::components::layout::{
+        Feature, FeatureRecord, LangSys, Lookup, Script, ScriptRecord,
+    };
+    use aurora_font::components::loca::write_loca;
+    use aurora_font::io::FontDataWriter;
+
+    let square = |size: i16| {
+        Glyph::Simple(SimpleGlyph {
+            header: GlyphHeader {
+                number_of_contours: 1,
+                x_min: 0,
+                y_min: 0,
+                x_max: size,
+                y_max: size,
+            },
+            end_points: vec![3],
+            instructions: vec![0xB0, 0x01],
+            points: [(0, 0), (0, size), (size, size), (size, 0)]
+                .map(|(x, y)| GlyphPoint {
+                    x,
+                    y,
+                    on_curve: true,
+                })
+                .to_vec(),
+            overlap: false,
+        })
+    };
+    let component = |flags, glyph_index, x| GlyphComponent {
+        flags: flags | ARG_1_AND_2_ARE_WORDS | ARGS_ARE_XY_VALUES,
+        glyph_index,
+        arg1: x,
+        arg2: 0,
+        transform: ComponentTransform::None,
+    };
+    let ligature = Glyph::Composite(CompositeGlyph {
+        header: GlyphHeader {
+            number_of_contours: -1,
+            x_min: 0,
+            y_min: 0,
+            x_max: 700,
+            y_max: 400,
+        },
+        components: vec![component(MORE_COMPONENTS, 2, 0), component(0, 3, 400)],
+        instructions: vec![],
+    });
+    let glyf = GlyfTable {
+        glyphs: vec![
+            Glyph::Empty,
+            square(500),
+            square(300),
+            square(200),
+            ligature,
+        ],
+    };
+    let mut writer = FontDataWriter::new();
+    let loca = write_glyf(&mut writer, &glyf);
+    let glyf_data = writer.into_bytes().unwrap();
+    let mut writer = FontDataWriter::new();
+    write_loca(&mut writer, &loca, false).unwrap();
+    let loca_data = writer.into_bytes().unwrap();
+
+    let metric = |advance_width| LongHorMetric {
+        advance_width,
+        lsb: 0,
+    };
+    let hmtx = HmtxTable {
+        h_metrics: vec![
+            metric(500),
+            metric(600),
+            metric(350),
+            metric(250),
+            metric(700),
+        ],
+        left_side_bearings: vec![],
+    };
+    let mut writer = FontDataWriter::new();
+    write_hmtx(&mut writer, &hmtx);
+    let hmtx_data = writer.into_bytes().unwrap();
+
+    let mappings: BTreeMap<u32, u16> = [('x' as u32, 1), ('f' as u32, 2), ('i' as u32, 3)].into();
+    let cmap = CmapTable {
+        version: 0,
+        records: vec![EncodingRecord {
+            platform_id: 3,
+            encoding_id: 1,
+            subtable: CmapSubtable::Mapping {
+                format: 4,
+                language: 0,
+                mappings,
+            },
+        }],
+    };
+    let mut writer = FontDataWriter::new();
+    write_cmap(&mut writer, &cmap).unwrap();
+    let cmap_data = writer.into_bytes().unwrap();
+
+    let ligatures = [(
+        2,
+        vec![Ligature {
+            ligature_glyph: 4,
+            component_glyph_ids: vec![3],
+        }],
+    )];
+    let gsub = GsubTable {
+        major_version: 1,
+        minor_version: 0,
+        scripts: vec![ScriptRecord {
+            script_tag: *b"DFLT",
+            script: Script {
+                default_lang_sys: Some(LangSys {
+                    required_feature_index: 0xFFFF,
+                    feature_indices: vec![0],
+                }),
+                lang_sys_records: vec![],
+            },
+        }],
+        features: vec![FeatureRecord {
+            feature_tag: *b"liga",
+            feature: Feature {
+                feature_params: None,
+                lookup_list_indices: vec![0],
+            },
+        }],
+        lookups: vec![Lookup {
+            lookup_type: 4,
+            lookup_flag: 0,
+            subtables: vec![SubstitutionSubtable::Ligature(ligatures.into())],
+            mark_filtering_set: None,
+        }],
+        feature_variations: None,
+    };
+    let mut writer = FontDataWriter::new();
+    write_gsub(&mut writer, &gsub).unwrap();
+    let gsub_data = writer.into_bytes().unwrap();
+
+    let mut head = vec![0u8; 54];
+    head[0..2].copy_from_slice(&1u16.to_be_bytes());
+    head[12..16].copy_from_slice(&0x5F0F3CF5u32.to_be_bytes());
+    head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+    let mut hhea = vec![0u8; 36];
+    hhea[0..2].copy_from_slice(&1u16.to_be_bytes());
+    hhea[34..36].copy_from_slice(&5u16.to_be_bytes());
+    let mut maxp = vec![0u8; 6];
+    maxp[..4].copy_from_slice(&0x00005000u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&5u16.to_be_bytes());
+
+    let mut writer = FontDataWriter::new();
+    write_snft(
+        &mut writer,
+        0x00010000,
+        &[
+            (*b"GSUB", gsub_data),
+            (*b"cmap", cmap_data),
+            (*b"glyf", glyf_data),
+            (*b"head", head),
+            (*b"hhea", hhea),
+            (*b"hmtx", hmtx_data),
+            (*b"loca", loca_data),
+            (*b"maxp", maxp),
+        ],
+    )
+    .unwrap();
+    writer.into_bytes().unwrap()
+}
+
+#[test]
+fn test_glyph_map() {
+    use aurora_font::subset::GlyphMap;
+
+    let glyphs = [0, 3, 7].into();
+    let map = GlyphMap::new(&glyphs, false);
+    assert_eq!(map.num_glyphs(), 3);
+    assert_eq!(map.new_id(7), Some(2));
+    assert_eq!(map.new_id(5), None);
+    assert_eq!(map.old_ids(), vec![Some(0), Some(3), Some(7)]);
+
+    let map = GlyphMap::new(&glyphs, true);
+    assert_eq!(map.num_glyphs(), 8);
+    assert_eq!(map.new_id(7), Some(7));
+    assert_eq!(map.old_ids()[1], None);
+}
+
+#[test]
+fn test_subset_font() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::cmap::read_cmap;
+    use aurora_font::components::glyf::{Glyph, read_glyf};
+    use aurora_font::components::gsub::{SubstitutionSubtable, read_gsub};
+    use aurora_font::components::hhea::read_hhea;
+    use aurora_font::components::hmtx::read_hmtx;
+    use aurora_font::components::loca::read_loca;
+    use aurora_font::components::maxp::read_maxp;
+    use aurora_font::io::FontDataStream;
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    let font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = SubsetOptions {
+        unicodes: ['f' as u32, 'i' as u32].into(),
+        drop_hinting: true,
+        ..Default::default()
+    };
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    let table = |tag: &[u8; 4]| FontDataStream::new(subset.table_data(tag, &output).unwrap());
+
+    // `x` is dropped; the ligature is kept through GSUB and renumbered.
+    let maxp = read_maxp(&mut table(b"maxp")).unwrap();
+    assert_eq!(maxp.num_glyphs, 4);
+    let cmap = read_cmap(&mut table(b"cmap")).unwrap();
+    assert_eq!(cmap.glyph_id('f' as u32), Some(1));
+    assert_eq!(cmap.glyph_id('i' as u32), Some(2));
+    assert_eq!(cmap.glyph_id('x' as u32), None);
+
+    let loca = read_loca(&mut table(b"loca"), false, 4).unwrap();
+    let glyf = read_glyf(&mut table(b"glyf"), &loca).unwrap();
+    assert!(glyf.glyph(1).unwrap().instructions().is_empty());
+    assert_eq!(
+        glyf.glyph(3).map(Glyph::component_glyph_ids),
+        Some(vec![1, 2])
+    );
+
+    let hhea = read_hhea(&mut table(b"hhea")).unwrap();
+    let hmtx = read_hmtx(&mut table(b"hmtx"), hhea.number_of_h_metrics, 4).unwrap();
+    let advances: Vec<u16> = (0..4).map(|g| hmtx.advance_width(g)).collect();
+    assert_eq!(advances, vec![500, 350, 250, 700]);
+
+    let gsub = read_gsub(&mut table(b"GSUB")).unwrap();
+    let SubstitutionSubtable::Ligature(ligatures) = &gsub.lookups[0].subtables[0] else {
+        panic!("expected a ligature subtable");
+    };
+    assert_eq!(ligatures[&1][0].ligature_glyph, 3);
+    assert_eq!(ligatures[&1][0].component_glyph_ids, vec![2]);
+}
+
+#[test]
+fn test_subset_retain_gids() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::cmap::read_cmap;
+    use aurora_font::components::glyf::{Glyph, read_glyf};
+    use aurora_font::components::loca::read_loca;
+    use aurora_font::io::FontDataStream;
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    let font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = SubsetOptions {
+        unicodes: ['f' as u32].into(),
+        retain_gids: true,
+        layout_closure: false,
+        ..Default::default()
+    };
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    let table = |tag: &[u8; 4]| FontDataStream::new(subset.table_data(tag, &output).unwrap());
+
+    let cmap = read_cmap(&mut table(b"cmap")).unwrap();
+    assert_eq!(cmap.glyph_id('f' as u32), Some(2));
+    let loca = read_loca(&mut table(b"loca"), false, 3).unwrap();
+    let glyf = read_glyf(&mut table(b"glyf"), &loca).unwrap();
+    assert_eq!(glyf.glyph(1), Some(&Glyph::Empty));
+    assert!(!glyf.glyph(2).unwrap().instructions().is_empty());
+}
+
+#[test]
+fn test_subset_cff() {
+    use std::collections::BTreeMap;
+
+    use aurora_font::common::snft::{read_snft, write_snft};
+    use aurora_font::components::cff1::{
+        CffEncoding, CffTable, Dict, PrivateDict, read_cff, write_cff,
+    };
+    use aurora_font::components::cmap::{CmapSubtable, CmapTable, EncodingRecord, write_cmap};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    // `A` calls global subroutine 2 and `C` local subroutine 1 (bias 107);
+    // `D` is a `seac` of `A` and `C`, and `B` is not referenced.
+    let cff = CffTable {
+        major_version: 1,
+        minor_version: 0,
+        name: b"Test".to_vec(),
+        top_dict: Dict::default(),
+        strings: vec![],
+        global_subrs: vec![vec![11], vec![11], vec![189, 189, 5, 11]],
+        char_strings: vec![
+            vec![14],
+            vec![149, 159, 21, 34, 29, 14],
+            vec![149, 159, 21, 14],
+            vec![149, 159, 21, 33, 10, 14],
+            vec![139, 139, 139, 204, 206, 14],
+        ],
+        charset: vec![0, 34, 35, 36, 37],
+        encoding: CffEncoding::Standard,
+        private: Some(PrivateDict {
+            dict: Dict::default(),
+            local_subrs: vec![vec![11], vec![169, 169, 5, 11]],
+        }),
+        fd_array: vec![],
+        fd_select: vec![],
+    };
+    let mut writer = FontDataWriter::new();
+    write_cff(&mut writer, &cff).unwrap();
+    let cff_data = writer.into_bytes().unwrap();
+
+    let cmap = CmapTable {
+        version: 0,
+        records: vec![EncodingRecord {
+            platform_id: 3,
+            encoding_id: 1,
+            subtable: CmapSubtable::Mapping {
+                format: 4,
+                language: 0,
+                mappings: BTreeMap::from([
+                    ('A' as u32, 1),
+                    ('B' as u32, 2),
+                    ('C' as u32, 3),
+                    ('D' as u32, 4),
+                ]),
+            },
+        }],
+    };
+    let mut writer = FontDataWriter::new();
+    write_cmap(&mut writer, &cmap).unwrap();
+    let cmap_data = writer.into_bytes().unwrap();
+    let mut maxp = vec![0u8; 6];
+    maxp[..4].copy_from_slice(&0x00005000u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&5u16.to_be_bytes());
+
+    let mut writer = FontDataWriter::new();
+    write_snft(
+        &mut writer,
+        u32::from_be_bytes(*b"OTTO"),
+        &[
+            (*b"CFF ", cff_data),
+            (*b"cmap", cmap_data),
+            (*b"maxp", maxp),
+        ],
+    )
+    .unwrap();
+    let font = writer.into_bytes().unwrap();
+
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = SubsetOptions {
+        unicodes: ['D' as u32].into(),
+        ..Default::default()
+    };
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    let cff = read_cff(&mut FontDataStream::new(
+        subset.table_data(b"CFF ", &output).unwrap(),
+    ))
+    .unwrap();
+
+    assert_eq!(cff.charset, vec![0, 34, 36, 37]);
+    assert_eq!(cff.global_subrs, vec![vec![189, 189, 5, 11]]);
+    assert_eq!(
+        cff.private.unwrap().local_subrs,
+        vec![vec![169, 169, 5, 11]]
+    );
+    assert_eq!(cff.char_strings[1], vec![149, 159, 21, 32, 29, 14]);
+    assert_eq!(cff.char_strings[2], vec![149, 159, 21, 32, 10, 14]);
+}
+
+/// Rebuilds a font with tables added or replaced.
+fn with_tables(font: &[u8], tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    use aurora_font::common::snft::{read_snft, write_snft};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let snft = read_snft(&mut FontDataStream::new(font)).unwrap();
+    let mut all: Vec<([u8; 4], Vec<u8>)> = snft
+        .all_tables()
+        .iter()
+        .filter(|entry| tables.iter().all(|(tag, _)| *tag != entry.tag))
+        .map(|entry| {
+            (
+                entry.tag,
+                snft.table_data(&entry.tag, font).unwrap().to_vec(),
+            )
+        })
+        .collect();
+    all.extend(tables);
+    all.sort_by_key(|(tag, _)| *tag);
+    let mut writer = FontDataWriter::new();
+    write_snft(&mut writer, 0x00010000, &all).unwrap();
+    writer.into_bytes().unwrap()
+}
+
+#[test]
+fn test_subset_unsupported_tables() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::error::{Error, IoError};
+    use aurora_font::io::FontDataStream;
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    // A version 0 `kern` table without subtables and an unsigned `DSIG`.
+    let font = with_tables(
+        &build_font(),
+        vec![
+            (*b"DSIG", vec![0, 0, 0, 1, 0, 0, 0, 0]),
+            (*b"kern", vec![0, 0, 0, 0]),
+        ],
+    );
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let mut options = SubsetOptions {
+        unicodes: ['f' as u32].into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        subset_font(&snft, &font, &options),
+        Err(Error::Io(IoError::InvalidTag(*b"kern")))
+    );
+
+    options.drop_unsupported_tables = true;
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    assert!(!subset.has_table(b"kern"));
+    assert!(!subset.has_table(b"DSIG"));
+    assert!(subset.has_table(b"glyf"));
+}
+
+#[test]
+fn test_subset_symbol_cmap() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::cmap::{
+        CmapSubtable, CmapTable, EncodingRecord, read_cmap, write_cmap,
+    };
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    // A symbol font mapping its codes in the private use area.
+    let cmap = CmapTable {
+        version: 0,
+        records: vec![EncodingRecord {
+            platform_id: 3,
+            encoding_id: 0,
+            subtable: CmapSubtable::Mapping {
+                format: 4,
+                language: 0,
+                mappings: [(0xF078, 1), (0xF066, 2)].into(),
+            },
+        }],
+    };
+    let mut writer = FontDataWriter::new();
+    write_cmap(&mut writer, &cmap).unwrap();
+    let font = with_tables(
+        &build_font(),
+        vec![(*b"cmap", writer.into_bytes().unwrap())],
+    );
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = SubsetOptions {
+        unicodes: [0xF066].into(),
+        layout_closure: false,
+        ..Default::default()
+    };
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    let cmap = read_cmap(&mut FontDataStream::new(
+        subset.table_data(b"cmap", &output).unwrap(),
+    ))
+    .unwrap();
+    assert_eq!(cmap.symbol_mappings(), Some(&[(0xF066, 1)].into()));
+}
+
+#[test]
+fn test_subset_feature_variations() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::components::gsub::{read_gsub, write_gsub};
+    use aurora_font::components::layout::{
+        Condition, Feature, FeatureVariationRecord, FeatureVariations,
+    };
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    // Past half of the first axis, `liga` runs no lookups.
+    let font = build_font();
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let mut gsub = read_gsub(&mut FontDataStream::new(
+        snft.table_data(b"GSUB", &font).unwrap(),
+    ))
+    .unwrap();
+    let variations = FeatureVariations {
+        major_version: 1,
+        minor_version: 0,
+        records: vec![FeatureVariationRecord {
+            conditions: vec![Condition {
+                axis_index: 0,
+                filter_range_min_value: 0.5,
+                filter_range_max_value: 1.0,
+            }],
+            substitutions: vec![(
+                0,
+                Feature {
+                    feature_params: None,
+                    lookup_list_indices: vec![],
+                },
+            )],
+        }],
+    };
+    gsub.minor_version = 1;
+    gsub.feature_variations = Some(variations.clone());
+    let mut writer = FontDataWriter::new();
+    write_gsub(&mut writer, &gsub).unwrap();
+    let font = with_tables(&font, vec![(*b"GSUB", writer.into_bytes().unwrap())]);
+
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = SubsetOptions {
+        unicodes: ['f' as u32, 'i' as u32].into(),
+        ..Default::default()
+    };
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    let gsub = read_gsub(&mut FontDataStream::new(
+        subset.table_data(b"GSUB", &output).unwrap(),
+    ))
+    .unwrap();
+    // Feature and lookup indices are kept, so the records carry over as is.
+    assert_eq!(gsub.minor_version, 1);
+    assert_eq!(gsub.feature_variations, Some(variations));
+}
+
+#[test]
+fn test_subset_variation_tables() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::io::FontDataStream;
+    use aurora_font::subset::{SubsetOptions, subset_font};
+
+    // The tables are only copied or dropped, so their contents do not matter.
+    let variable = [
+        *b"HVAR", *b"MVAR", *b"STAT", *b"avar", *b"cvar", *b"fvar", *b"gvar",
+    ];
+    let font = with_tables(
+        &build_font(),
+        variable.iter().map(|&tag| (tag, vec![0; 8])).collect(),
+    );
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = SubsetOptions {
+        unicodes: ['f' as u32].into(),
+        drop_unsupported_tables: true,
+        ..Default::default()
+    };
+    // Without `gvar` and `HVAR` the rest of the group goes as well.
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    for tag in &variable {
+        assert!(!subset.has_table(tag));
+    }
+
+    // Without tables to drop, `fvar` and `STAT` are kept.
+    let font = with_tables(
+        &build_font(),
+        vec![(*b"STAT", vec![0; 8]), (*b"fvar", vec![0; 8])],
+    );
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let output = subset_font(&snft, &font, &options).unwrap();
+    let subset = read_snft(&mut FontDataStream::new(&output)).unwrap();
+    assert!(subset.has_table(b"STAT") && subset.has_table(b"fvar"));
+}
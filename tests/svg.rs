@@ -0,0 +1,154 @@
+//! Tests for the `SVG ` table and the inflate decoder behind its compressed documents.
+
+/// `<svg xmlns="http://www.w3.org/2000/svg"><path id="glyph3" d="M0 0h100v100z"/></svg>`
+/// gzip-compressed by a reference encoder.
+const GZIP_DOCUMENT: [u8; 97] = [
+    0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xb3, 0x29, 0x2e, 0x4b, 0x57, 0xa8,
+    0xc8, 0xcd, 0xc9, 0x2b, 0xb6, 0x55, 0xca, 0x28, 0x29, 0x29, 0xb0, 0xd2, 0xd7, 0x2f, 0x2f, 0x2f,
+    0xd7, 0x2b, 0x37, 0xd6, 0xcb, 0x2f, 0x4a, 0xd7, 0x37, 0x32, 0x30, 0x30, 0xd0, 0x07, 0xaa, 0x50,
+    0xb2, 0xb3, 0x29, 0x48, 0x2c, 0xc9, 0x50, 0xc8, 0x4c, 0xb1, 0x55, 0x4a, 0xcf, 0xa9, 0x2c, 0xc8,
+    0x30, 0x56, 0x52, 0x00, 0x32, 0x7d, 0x0d, 0x14, 0x0c, 0x32, 0x0c, 0x0d, 0x0c, 0xca, 0x80, 0xb8,
+    0x4a, 0x49, 0xdf, 0xce, 0x06, 0xa4, 0xd6, 0x0e, 0x00, 0x83, 0xc8, 0x74, 0x47, 0x53, 0x00, 0x00,
+    0x00,
+];
+
+const PLAIN_DOCUMENT: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\">\
+<path id=\"glyph1\" d=\"M0 0h10v10z\"/><path id=\"glyph2\" d=\"M0 0h20v20z\"/></svg>";
+
+/// Builds a raw `SVG ` table whose first two records point at the same plain
+/// document and whose last record holds a gzip-compressed one.
+fn build_svg() -> Vec<u8> {
+    let records_size = 2 + 3 * 12;
+    let plain_offset = records_size as u32;
+    let gzip_offset = plain_offset + PLAIN_DOCUMENT.len() as u32;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&0u16.to_be_bytes());
+    bytes.extend_from_slice(&10u32.to_be_bytes());
+    bytes.extend_from_slice(&0u32.to_be_bytes());
+    bytes.extend_from_slice(&3u16.to_be_bytes());
+    for (start, end, offset, length) in [
+        (1u16, 1u16, plain_offset, PLAIN_DOCUMENT.len() as u32),
+        (2, 2, plain_offset, PLAIN_DOCUMENT.len() as u32),
+        (3, 3, gzip_offset, GZIP_DOCUMENT.len() as u32),
+    ] {
+        bytes.extend_from_slice(&start.to_be_bytes());
+        bytes.extend_from_slice(&end.to_be_bytes());
+        bytes.extend_from_slice(&offset.to_be_bytes());
+        bytes.extend_from_slice(&length.to_be_bytes());
+    }
+    bytes.extend_from_slice(PLAIN_DOCUMENT);
+    bytes.extend_from_slice(&GZIP_DOCUMENT);
+    bytes
+}
+
+#[test]
+fn test_svg_glyph_documents() {
+    use aurora_font::components::svg::read_svg;
+    use aurora_font::io::FontDataStream;
+
+    let bytes = build_svg();
+    let svg = read_svg(&mut FontDataStream::new(&bytes)).unwrap();
+    // Glyphs 1 and 2 share one document.
+    assert_eq!(svg.documents.len(), 2);
+    assert_eq!(svg.records[0].document_index, svg.records[1].document_index);
+
+    let glyph = svg.glyph_document(2).unwrap().unwrap();
+    assert_eq!(&*glyph.document, PLAIN_DOCUMENT);
+    assert_eq!(glyph.element_id, "glyph2");
+
+    let glyph = svg.glyph_document(3).unwrap().unwrap();
+    assert_eq!(
+        &*glyph.document,
+        b"<svg xmlns=\"http://www.w3.org/2000/svg\"><path id=\"glyph3\" d=\"M0 0h100v100z\"/></svg>"
+    );
+    assert_eq!(glyph.element_id, "glyph3");
+
+    assert!(svg.glyph_document(0).unwrap().is_none());
+    assert!(svg.glyph_document(4).unwrap().is_none());
+}
+
+#[test]
+fn test_svg_shared_documents() {
+    use aurora_font::components::svg::read_svg;
+    use aurora_font::io::FontDataStream;
+
+    // Two copies of the plain document, then the gzip document referenced by
+    // two records.
+    let records_size = 2 + 4 * 12;
+    let first = records_size as u32;
+    let second = first + PLAIN_DOCUMENT.len() as u32;
+    let gzip = second + PLAIN_DOCUMENT.len() as u32;
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&[0, 0, 0, 0, 0, 10, 0, 0, 0, 0, 0, 4]);
+    for (glyph_id, offset, length) in [
+        (1u16, first, PLAIN_DOCUMENT.len()),
+        (2, second, PLAIN_DOCUMENT.len()),
+        (3, gzip, GZIP_DOCUMENT.len()),
+        (4, gzip, GZIP_DOCUMENT.len()),
+    ] {
+        bytes.extend_from_slice(&glyph_id.to_be_bytes());
+        bytes.extend_from_slice(&glyph_id.to_be_bytes());
+        bytes.extend_from_slice(&offset.to_be_bytes());
+        bytes.extend_from_slice(&(length as u32).to_be_bytes());
+    }
+    bytes.extend_from_slice(PLAIN_DOCUMENT);
+    bytes.extend_from_slice(PLAIN_DOCUMENT);
+    bytes.extend_from_slice(&GZIP_DOCUMENT);
+
+    let svg = read_svg(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(svg.documents.len(), 2);
+    let indices: Vec<usize> = svg.records.iter().map(|r| r.document_index).collect();
+    assert_eq!(indices, [0, 0, 1, 1]);
+    assert_eq!(svg.glyph_document(4).unwrap().unwrap().element_id, "glyph4");
+}
+
+#[test]
+fn test_gunzip_rejects_corruption() {
+    use aurora_font::decompress::{gunzip, is_gzip};
+
+    assert!(is_gzip(&GZIP_DOCUMENT));
+    assert!(!is_gzip(PLAIN_DOCUMENT));
+
+    let mut corrupt = GZIP_DOCUMENT;
+    // Flip a byte of the CRC-32 trailer.
+    corrupt[89] ^= 0xff;
+    assert!(gunzip(&corrupt, 1 << 20).is_err());
+    assert!(gunzip(&GZIP_DOCUMENT[..40], 1 << 20).is_err());
+    assert!(gunzip(&GZIP_DOCUMENT, 1 << 20).is_ok());
+    assert!(gunzip(&GZIP_DOCUMENT, 40).is_err());
+}
+
+#[test]
+fn test_inflate_round_trip() {
+    use aurora_font::compress::{deflate, zlib_compress};
+    use aurora_font::decompress::{inflate, zlib_decompress};
+
+    // Output of a reference zlib encoder at its highest level.
+    let reference = [
+        0x78, 0xda, 0x4b, 0x4c, 0x4a, 0x4e, 0x44, 0x45, 0x0a, 0x19, 0xa9, 0x39, 0x39, 0xf9, 0xc8,
+        0x24, 0x00, 0xf9, 0xf3, 0x0d, 0x81,
+    ];
+    assert_eq!(
+        zlib_decompress(&reference, 36).unwrap(),
+        b"abcabcabcabcabcabc hello hello hello"
+    );
+
+    let mut data = Vec::new();
+    for i in 0..20_000u32 {
+        data.extend_from_slice(format!("<path id=\"glyph{}\"/>", i % 97).as_bytes());
+        data.push((i.wrapping_mul(2_654_435_761) >> 24) as u8);
+    }
+    assert_eq!(inflate(&deflate(&data), data.len()).unwrap(), data);
+    assert_eq!(
+        zlib_decompress(&zlib_compress(&data), data.len()).unwrap(),
+        data
+    );
+    assert_eq!(inflate(&deflate(&[]), 0).unwrap(), Vec::<u8>::new());
+
+    // Output past the limit is rejected rather than allocated.
+    assert!(inflate(&deflate(&data), data.len() - 1).is_err());
+    assert!(zlib_decompress(&reference, 35).is_err());
+    let zeros = deflate(&vec![0; 1 << 20]);
+    assert!(inflate(&zeros, 1 << 16).is_err());
+}
@@ -0,0 +1,209 @@
+//! Tests for item variation stores and the metrics variation tables.
+
+/// Builds a two-axis store with byte, word and long delta columns.
+fn build_store() -> aurora_font::components::variation::ItemVariationStore {
+    use aurora_font::components::variation::{
+        ItemVariationData, ItemVariationStore, RegionAxis, VariationRegion,
+    };
+
+    let region = |axes: [(f32, f32, f32); 2]| VariationRegion {
+        axes: axes
+            .iter()
+            .map(|&(start, peak, end)| RegionAxis::new(start, peak, end))
+            .collect(),
+    };
+    ItemVariationStore {
+        format: 1,
+        axis_count: 2,
+        regions: vec![
+            region([(0.0, 1.0, 1.0), (0.0, 0.0, 0.0)]),
+            region([(0.0, 0.0, 0.0), (-1.0, -1.0, 0.0)]),
+            region([(0.0, 0.5, 1.0), (-1.0, -1.0, 0.0)]),
+        ],
+        data: vec![
+            ItemVariationData {
+                region_indexes: vec![0, 1],
+                delta_sets: vec![vec![10, -4], vec![100, 0], vec![-7, 3]],
+            },
+            ItemVariationData {
+                region_indexes: vec![2, 0, 1],
+                delta_sets: vec![vec![1, 300, 70_000], vec![-2, -300, 5]],
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_item_variation_store_round_trip() {
+    use aurora_font::components::variation::{
+        read_item_variation_store, write_item_variation_store,
+    };
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let store = build_store();
+    let mut writer = FontDataWriter::new();
+    write_item_variation_store(&mut writer, &store).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let parsed = read_item_variation_store(&mut FontDataStream::new(&bytes)).unwrap();
+
+    // Columns may be reordered by width; the deltas of every item are kept.
+    assert_eq!(parsed.regions, store.regions);
+    for coords in [[1.0, 0.0], [0.5, -1.0], [0.25, -0.5], [-1.0, 1.0]] {
+        for (outer, data) in store.data.iter().enumerate() {
+            for inner in 0..data.delta_sets.len() {
+                let (outer, inner) = (outer as u16, inner as u16);
+                assert_eq!(
+                    parsed.delta(outer, inner, &coords),
+                    store.delta(outer, inner, &coords)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_item_variation_store_delta() {
+    let store = build_store();
+    assert_eq!(store.delta(0, 0, &[0.0, 0.0]), 0.0);
+    assert_eq!(store.delta(0, 0, &[1.0, 0.0]), 10.0);
+    assert_eq!(store.delta(0, 0, &[0.5, -0.5]), 3.0);
+    // Region 2 peaks at 0.5 on the first axis and is zero at 1.
+    assert_eq!(store.delta(1, 0, &[0.5, -1.0]), 1.0 + 150.0 + 70_000.0);
+    assert_eq!(store.delta(1, 1, &[1.0, -1.0]), -300.0 + 5.0);
+    // Unknown items have no delta.
+    assert_eq!(store.delta(2, 0, &[1.0, 0.0]), 0.0);
+    assert_eq!(store.delta(0, 9, &[1.0, 0.0]), 0.0);
+}
+
+#[test]
+fn test_delta_set_index_map_round_trip() {
+    use aurora_font::components::variation::{
+        DeltaSetIndexMap, read_delta_set_index_map, write_delta_set_index_map,
+    };
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    for entries in [
+        vec![(0, 0), (0, 1), (0, 2)],
+        vec![(1, 300), (0, 7), (2, 65_535)],
+        (0..70_000u32).map(|i| (0, (i % 3) as u16)).collect(),
+    ] {
+        let map = DeltaSetIndexMap { entries };
+        let mut writer = FontDataWriter::new();
+        write_delta_set_index_map(&mut writer, &map);
+        let bytes = writer.into_bytes().unwrap();
+        let parsed = read_delta_set_index_map(&mut FontDataStream::new(&bytes)).unwrap();
+        assert_eq!(parsed, map);
+    }
+    // Indices past the end use the last entry.
+    let map = DeltaSetIndexMap {
+        entries: vec![(0, 4), (1, 2)],
+    };
+    assert_eq!(map.get(9), Some((1, 2)));
+}
+
+#[test]
+fn test_metrics_variations() {
+    use aurora_font::components::hvar::{HvarTable, read_hvar, write_hvar};
+    use aurora_font::components::mvar::{MetricValueRecord, MvarTable, read_mvar, write_mvar};
+    use aurora_font::components::variation::DeltaSetIndexMap;
+    use aurora_font::components::vvar::{VvarTable, read_vvar, write_vvar};
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let coords = [1.0, 0.0];
+    let hvar = HvarTable {
+        major_version: 1,
+        minor_version: 0,
+        store: build_store(),
+        advance_map: None,
+        lsb_map: Some(DeltaSetIndexMap {
+            entries: vec![(0, 1), (0, 2)],
+        }),
+        rsb_map: None,
+    };
+    let mut writer = FontDataWriter::new();
+    write_hvar(&mut writer, &hvar).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let hvar = read_hvar(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(hvar.advance_delta(1, &coords), 100.0);
+    assert_eq!(hvar.lsb_delta(0, &coords), Some(100.0));
+    assert_eq!(hvar.rsb_delta(0, &coords), None);
+
+    let vvar = VvarTable {
+        major_version: 1,
+        minor_version: 0,
+        store: build_store(),
+        advance_map: Some(DeltaSetIndexMap {
+            entries: vec![(1, 0), (0, 2)],
+        }),
+        tsb_map: None,
+        bsb_map: None,
+        vorg_map: Some(DeltaSetIndexMap {
+            entries: vec![(0, 0)],
+        }),
+    };
+    let mut writer = FontDataWriter::new();
+    write_vvar(&mut writer, &vvar).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let parsed = read_vvar(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(parsed.advance_map, vvar.advance_map);
+    assert_eq!(parsed.advance_delta(0, &coords), 300.0);
+    assert_eq!(parsed.advance_delta(1, &coords), -7.0);
+    assert_eq!(parsed.tsb_delta(0, &coords), None);
+    assert_eq!(parsed.vorg_delta(5, &coords), Some(10.0));
+
+    let mvar = MvarTable {
+        major_version: 1,
+        minor_version: 0,
+        records: vec![
+            MetricValueRecord {
+                tag: *b"hasc",
+                outer: 0,
+                inner: 1,
+            },
+            MetricValueRecord {
+                tag: *b"xhgt",
+                outer: 1,
+                inner: 1,
+            },
+        ],
+        store: Some(build_store()),
+    };
+    let mut writer = FontDataWriter::new();
+    write_mvar(&mut writer, &mvar).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let mvar = read_mvar(&mut FontDataStream::new(&bytes)).unwrap();
+    assert_eq!(mvar.delta(b"hasc", &coords), Some(100.0));
+    assert_eq!(mvar.delta(b"xhgt", &[0.0, -1.0]), Some(5.0));
+    assert_eq!(mvar.delta(b"cpht", &coords), None);
+}
+
+#[test]
+fn test_gdef_variation_delta() {
+    use aurora_font::components::gdef::{GdefTable, read_gdef, write_gdef};
+    use aurora_font::components::layout::DeviceTable;
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let gdef = GdefTable {
+        major_version: 1,
+        minor_version: 3,
+        item_var_store: Some(build_store()),
+        ..Default::default()
+    };
+    let mut writer = FontDataWriter::new();
+    write_gdef(&mut writer, &gdef).unwrap();
+    let bytes = writer.into_bytes().unwrap();
+    let gdef = read_gdef(&mut FontDataStream::new(&bytes)).unwrap();
+
+    let device = DeviceTable::VariationIndex {
+        delta_set_outer_index: 0,
+        delta_set_inner_index: 2,
+    };
+    assert_eq!(gdef.variation_delta(&device, &[1.0, -1.0]), -4.0);
+    let hinting = DeviceTable::Hinting {
+        start_size: 10,
+        end_size: 10,
+        delta_format: 1,
+        delta_values: vec![0x4000],
+    };
+    assert_eq!(gdef.variation_delta(&hinting, &[1.0, -1.0]), 0.0);
+}
@@ -0,0 +1,127 @@
+//! Tests for WOFF encoding.
+
+mod common;
+
+#[test]
+fn test_adler32() {
+    use aurora_font::compress::adler32;
+
+    assert_eq!(adler32(b""), 1);
+    assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+}
+
+#[test]
+fn test_encode_woff() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::common::woff::{WoffEncodeOptions, encode_woff};
+    use aurora_font::decompress::zlib_decompress;
+    use aurora_font::io::FontDataStream;
+
+    let compressible: Vec<u8> = (0..600u32).map(|i| (i % 7) as u8).collect();
+    let tiny = vec![1, 2, 3];
+    let font = common::build_snft(&[(*b"zzzz", compressible.clone()), (*b"aaaa", tiny.clone())]);
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+
+    let options = WoffEncodeOptions {
+        major_version: 1,
+        minor_version: 0,
+        metadata: Some(b"<metadata version=\"1.0\"/>"),
+        private_data: Some(b"private"),
+    };
+    let woff = encode_woff(&snft, &font, &options).unwrap();
+
+    let mut stream = FontDataStream::new(&woff);
+    assert_eq!(&stream.read_tag().unwrap(), b"wOFF");
+    assert_eq!(stream.read_u32().unwrap(), 0x00010000);
+    assert_eq!(stream.read_u32().unwrap() as usize, woff.len());
+    assert_eq!(stream.read_u16().unwrap(), 2);
+    stream.skip(2);
+    assert_eq!(stream.read_u32().unwrap(), 12 + 16 * 2 + 600 + 4);
+    stream.skip(4);
+    let meta_offset = stream.read_u32().unwrap() as usize;
+    let meta_length = stream.read_u32().unwrap() as usize;
+    assert_eq!(stream.read_u32().unwrap(), 25);
+    assert!(meta_offset > 0 && meta_length > 0);
+    let metadata = &woff[meta_offset..meta_offset + meta_length];
+    assert_eq!(
+        zlib_decompress(metadata, 25).unwrap(),
+        options.metadata.unwrap()
+    );
+    let priv_offset = stream.read_u32().unwrap() as usize;
+    assert_eq!(priv_offset % 4, 0);
+    assert_eq!(stream.read_u32().unwrap(), 7);
+    assert_eq!(&woff[priv_offset..], b"private");
+
+    // Directory is sorted by tag; the tiny table does not shrink and is stored raw.
+    assert_eq!(&stream.read_tag().unwrap(), b"aaaa");
+    let offset = stream.read_u32().unwrap() as usize;
+    assert_eq!(stream.read_u32().unwrap(), 3);
+    assert_eq!(stream.read_u32().unwrap(), 3);
+    assert_eq!(stream.read_u32().unwrap(), snft.tables[1].checksum);
+    assert_eq!(&woff[offset..offset + 3], tiny.as_slice());
+
+    assert_eq!(&stream.read_tag().unwrap(), b"zzzz");
+    let offset = stream.read_u32().unwrap() as usize;
+    let comp_length = stream.read_u32().unwrap() as usize;
+    assert!(comp_length < 600);
+    assert_eq!(stream.read_u32().unwrap(), 600);
+    assert_eq!(stream.read_u32().unwrap(), snft.tables[0].checksum);
+    assert_eq!(offset % 4, 0);
+    assert_eq!(woff[offset], 0x78);
+    let table = &woff[offset..offset + comp_length];
+    assert_eq!(zlib_decompress(table, 600).unwrap(), compressible);
+}
+
+#[test]
+fn test_encode_woff_tables_decompress() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::common::woff::{WoffEncodeOptions, encode_woff};
+    use aurora_font::decompress::zlib_decompress;
+    use aurora_font::io::FontDataStream;
+
+    // Repetitive, mixed and pseudo-random tables; only some shrink.
+    let mut seed = 1u32;
+    let noise: Vec<u8> = (0..257)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) as u8
+        })
+        .collect();
+    let tables = [
+        (*b"cmap", vec![0; 1000]),
+        (
+            *b"glyf",
+            (0..2000u32).map(|i| (i * i % 251) as u8).collect(),
+        ),
+        (*b"head", noise),
+        (*b"name", b"Aurora Aurora Aurora Aurora Aurora".to_vec()),
+    ];
+    let font = common::build_snft(&tables);
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = WoffEncodeOptions {
+        major_version: 1,
+        minor_version: 0,
+        metadata: None,
+        private_data: None,
+    };
+    let woff = encode_woff(&snft, &font, &options).unwrap();
+
+    let mut stream = FontDataStream::new(&woff);
+    stream.skip(12);
+    assert_eq!(stream.read_u16().unwrap(), 4);
+    stream.skip(30);
+    for (tag, data) in &tables {
+        assert_eq!(&stream.read_tag().unwrap(), tag);
+        let offset = stream.read_u32().unwrap() as usize;
+        let comp_length = stream.read_u32().unwrap() as usize;
+        let orig_length = stream.read_u32().unwrap() as usize;
+        stream.skip(4);
+        assert_eq!(orig_length, data.len());
+        let stored = &woff[offset..offset + comp_length];
+        if comp_length < orig_length {
+            assert_eq!(&zlib_decompress(stored, orig_length).unwrap(), data);
+        } else {
+            assert_eq!(stored, data.as_slice());
+        }
+    }
+}
@@ -0,0 +1,576 @@
+//! Tests for WOFF2 encoding and decoding of the encoder output.
+
+mod common;
+
+#[test]
+fn test_uint_base128() {
+    use aurora_font::common::woff2::write_uint_base128;
+    use aurora_font::io::FontDataWriter;
+
+    let encode = |value| {
+        let mut writer = FontDataWriter::new();
+        write_uint_base128(&mut writer, value);
+        writer.into_bytes().unwrap()
+    };
+    assert_eq!(encode(0), [0x00]);
+    assert_eq!(encode(127), [0x7F]);
+    assert_eq!(encode(128), [0x81, 0x00]);
+    assert_eq!(encode(63), [0x3F]);
+    assert_eq!(encode(u32::MAX), [0x8F, 0xFF, 0xFF, 0xFF, 0x7F]);
+}
+
+#[test]
+fn test_255_uint16() {
+    use aurora_font::common::woff2::write_255_uint16;
+    use aurora_font::io::FontDataWriter;
+
+    let encode = |value| {
+        let mut writer = FontDataWriter::new();
+        write_255_uint16(&mut writer, value);
+        writer.into_bytes().unwrap()
+    };
+    assert_eq!(encode(252), [252]);
+    assert_eq!(encode(253), [255, 0]);
+    assert_eq!(encode(505), [255, 252]);
+    assert_eq!(encode(506), [254, 0]);
+    assert_eq!(encode(761), [254, 255]);
+    assert_eq!(encode(762), [253, 0x02, 0xFA]);
+}
+
+#[test]
+fn test_write_triplet() {
+    use aurora_font::common::woff2::write_triplet;
+    use aurora_font::io::FontDataWriter;
+
+    let encode = |dx, dy, on_curve| {
+        let mut flags = FontDataWriter::new();
+        let mut glyphs = FontDataWriter::new();
+        write_triplet(&mut flags, &mut glyphs, dx, dy, on_curve);
+        (flags.as_bytes()[0], glyphs.into_bytes().unwrap())
+    };
+    assert_eq!(encode(0, 5, true), (1, vec![5]));
+    assert_eq!(encode(0, -300, true), (2, vec![44]));
+    assert_eq!(encode(-7, 0, false), (138, vec![7]));
+    assert_eq!(encode(3, -2, true), (21, vec![0x21]));
+    assert_eq!(encode(100, 200, true), (87, vec![99, 199]));
+    assert_eq!(encode(1000, -2000, true), (121, vec![0x3E, 0x87, 0xD0]));
+    assert_eq!(
+        encode(-5000, 5000, true),
+        (126, vec![0x13, 0x88, 0x13, 0x88])
+    );
+}
+
+#[test]
+fn test_transform_hmtx() {
+    use aurora_font::common::woff2::transform_hmtx_table;
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric};
+
+    let hmtx = HmtxTable {
+        h_metrics: vec![
+            LongHorMetric {
+                advance_width: 500,
+                lsb: 10,
+            },
+            LongHorMetric {
+                advance_width: 600,
+                lsb: 20,
+            },
+        ],
+        left_side_bearings: vec![30],
+    };
+    assert_eq!(
+        transform_hmtx_table(&hmtx, &[10, 20, 30]).unwrap(),
+        [0x03, 0x01, 0xF4, 0x02, 0x58]
+    );
+    assert_eq!(
+        transform_hmtx_table(&hmtx, &[10, 20, 0]).unwrap(),
+        [0x01, 0x01, 0xF4, 0x02, 0x58, 0x00, 0x1E]
+    );
+    assert_eq!(transform_hmtx_table(&hmtx, &[0, 20, 0]), None);
+}
+
+#[test]
+fn test_encode_woff2() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::common::woff2::{Woff2EncodeOptions, encode_woff2};
+    use aurora_font::components::glyf::{
+        GlyfTable, Glyph, GlyphHeader, GlyphPoint, SimpleGlyph, write_glyf,
+    };
+    use aurora_font::components::loca::write_loca;
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let points = [(0, 0), (0, 700), (500, 700), (500, 0)].map(|(x, y)| GlyphPoint {
+        x,
+        y,
+        on_curve: true,
+    });
+    let glyf = GlyfTable {
+        glyphs: vec![
+            Glyph::Empty,
+            Glyph::Simple(SimpleGlyph {
+                header: GlyphHeader {
+                    number_of_contours: 1,
+                    x_min: 0,
+                    y_min: 0,
+                    x_max: 500,
+                    y_max: 700,
+                },
+                end_points: vec![3],
+                instructions: vec![],
+                points: points.to_vec(),
+                overlap: false,
+            }),
+        ],
+    };
+    let mut writer = FontDataWriter::new();
+    let loca = write_glyf(&mut writer, &glyf);
+    let glyf_data = writer.into_bytes().unwrap();
+    let mut writer = FontDataWriter::new();
+    write_loca(&mut writer, &loca, false).unwrap();
+    let loca_data = writer.into_bytes().unwrap();
+
+    let mut head = vec![0u8; 54];
+    head[12..16].copy_from_slice(&0x5F0F3CF5u32.to_be_bytes());
+    let mut maxp = vec![0u8; 6];
+    maxp[..4].copy_from_slice(&0x00005000u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&2u16.to_be_bytes());
+
+    let font = common::build_snft(&[
+        (*b"glyf", glyf_data.clone()),
+        (*b"head", head),
+        (*b"loca", loca_data.clone()),
+        (*b"maxp", maxp),
+    ]);
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let options = Woff2EncodeOptions {
+        major_version: 2,
+        minor_version: 1,
+        ..Default::default()
+    };
+    let woff2 = encode_woff2(&snft, &font, &options).unwrap();
+
+    let mut stream = FontDataStream::new(&woff2);
+    assert_eq!(&stream.read_tag().unwrap(), b"wOF2");
+    assert_eq!(stream.read_u32().unwrap(), 0x00010000);
+    assert_eq!(stream.read_u32().unwrap() as usize, woff2.len());
+    assert_eq!(stream.read_u16().unwrap(), 4);
+    stream.skip(2);
+    let total_sfnt_size = stream.read_u32().unwrap();
+    assert_eq!(total_sfnt_size as usize, font.len());
+    let compressed_size = stream.read_u32().unwrap() as usize;
+    assert_eq!(stream.read_u16().unwrap(), 2);
+    assert_eq!(stream.read_u16().unwrap(), 1);
+    assert_eq!(stream.read_u32().unwrap(), 0);
+    stream.skip(16);
+
+    // glyf (known tag 10) is transformed; loca (11) follows it with an empty transform.
+    assert_eq!(stream.read_u8().unwrap(), 10);
+    assert_eq!(stream.read_u8().unwrap() as usize, glyf_data.len());
+    let transform_length = stream.read_u8().unwrap();
+    assert!(transform_length > 0);
+    assert_eq!(stream.read_u8().unwrap(), 11);
+    assert_eq!(stream.read_u8().unwrap() as usize, loca_data.len());
+    assert_eq!(stream.read_u8().unwrap(), 0);
+    assert_eq!(stream.read_u8().unwrap(), 1);
+    assert_eq!(stream.read_u8().unwrap(), 54);
+    assert_eq!(stream.read_u8().unwrap(), 4);
+    assert_eq!(stream.read_u8().unwrap(), 6);
+    assert_eq!((stream.position() + compressed_size + 3) & !3, woff2.len());
+}
+
+/// Reads a `UIntBase128` value.
+fn read_uint_base128(stream: &mut aurora_font::io::FontDataStream) -> u32 {
+    let mut value = 0u32;
+    loop {
+        let byte = stream.read_u8().unwrap();
+        value = value << 7 | (byte & 0x7F) as u32;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+    }
+}
+
+/// Reads a `255UInt16` value.
+fn read_255_uint16(stream: &mut aurora_font::io::FontDataStream) -> u16 {
+    match stream.read_u8().unwrap() {
+        253 => stream.read_u16().unwrap(),
+        254 => 506 + stream.read_u8().unwrap() as u16,
+        255 => 253 + stream.read_u8().unwrap() as u16,
+        code => code as u16,
+    }
+}
+
+/// Decodes one point delta from the flags stream byte and the glyph stream.
+fn read_triplet(flag: u8, glyphs: &mut aurora_font::io::FontDataStream) -> (i32, i32, bool) {
+    let on_curve = flag & 0x80 == 0;
+    let flag = (flag & 0x7F) as i32;
+    let sign = |f: i32, v: i32| if f & 1 != 0 { v } else { -v };
+    let mut byte = || glyphs.read_u8().unwrap() as i32;
+    let (dx, dy) = match flag {
+        0..10 => (0, sign(flag, ((flag & 14) << 7) + byte())),
+        10..20 => (sign(flag, (((flag - 10) & 14) << 7) + byte()), 0),
+        20..84 => {
+            let (b0, b1) = (flag - 20, byte());
+            (
+                sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+                sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (b1 & 0x0F)),
+            )
+        }
+        84..120 => {
+            let b0 = flag - 84;
+            let (b1, b2) = (byte(), byte());
+            (
+                sign(flag, 1 + ((b0 / 12) << 8) + b1),
+                sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b2),
+            )
+        }
+        120..124 => {
+            let (b1, b2, b3) = (byte(), byte(), byte());
+            (
+                sign(flag, (b1 << 4) + (b2 >> 4)),
+                sign(flag >> 1, ((b2 & 0x0F) << 8) + b3),
+            )
+        }
+        _ => {
+            let (b1, b2, b3, b4) = (byte(), byte(), byte(), byte());
+            (sign(flag, b1 << 8 | b2), sign(flag >> 1, b3 << 8 | b4))
+        }
+    };
+    (dx, dy, on_curve)
+}
+
+/// Rebuilds the `glyf` model from a transformed `glyf` table; returns it with the index format.
+fn decode_transformed_glyf(data: &[u8]) -> (aurora_font::components::glyf::GlyfTable, i16) {
+    use aurora_font::components::glyf::{
+        ARG_1_AND_2_ARE_WORDS, GlyfTable, Glyph, GlyphHeader, GlyphPoint, MORE_COMPONENTS,
+        SimpleGlyph, WE_HAVE_A_SCALE, WE_HAVE_A_TWO_BY_TWO, WE_HAVE_AN_X_AND_Y_SCALE,
+        WE_HAVE_INSTRUCTIONS, read_glyph,
+    };
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+
+    let mut header = FontDataStream::new(data);
+    header.skip(2); // reserved
+    let option_flags = header.read_u16().unwrap();
+    let num_glyphs = header.read_u16().unwrap() as usize;
+    let index_format = header.read_u16().unwrap() as i16;
+    let mut offset = 36;
+    let mut streams = (0..7).map(|_| {
+        let length = header.read_u32().unwrap() as usize;
+        offset += length;
+        FontDataStream::new(&data[offset - length..offset])
+    });
+    let [
+        mut n_contours,
+        mut n_points,
+        mut flags,
+        mut glyphs,
+        mut composites,
+        mut bboxes,
+        mut instructions,
+    ] = std::array::from_fn(|_| streams.next().unwrap());
+    let bitmap_size = num_glyphs.div_ceil(32) * 4;
+    let bbox_bitmap = bboxes.read_bytes(bitmap_size).unwrap();
+    let overlap_bitmap = (option_flags & 1 != 0).then(|| &data[offset..offset + bitmap_size]);
+    let bit = |bitmap: &[u8], i: usize| bitmap[i / 8] & 0x80 >> (i % 8) != 0;
+
+    let mut table = GlyfTable::default();
+    for glyph_id in 0..num_glyphs {
+        let glyph = match n_contours.read_i16().unwrap() {
+            0 => Glyph::Empty,
+            -1 => {
+                // Reassemble the glyph record and let the regular reader parse it.
+                let mut record = FontDataWriter::new();
+                record.write_i16(-1);
+                record.write_bytes(bboxes.read_bytes(8).unwrap());
+                let mut has_instructions = false;
+                loop {
+                    let component_flags = composites.read_u16().unwrap();
+                    let mut length = 2;
+                    length += if component_flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                        4
+                    } else {
+                        2
+                    };
+                    if component_flags & WE_HAVE_A_SCALE != 0 {
+                        length += 2;
+                    } else if component_flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                        length += 4;
+                    } else if component_flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                        length += 8;
+                    }
+                    record.write_u16(component_flags);
+                    record.write_bytes(composites.read_bytes(length).unwrap());
+                    has_instructions |= component_flags & WE_HAVE_INSTRUCTIONS != 0;
+                    if component_flags & MORE_COMPONENTS == 0 {
+                        break;
+                    }
+                }
+                if has_instructions {
+                    let length = read_255_uint16(&mut glyphs);
+                    record.write_u16(length);
+                    record.write_bytes(instructions.read_bytes(length as usize).unwrap());
+                }
+                let record = record.into_bytes().unwrap();
+                read_glyph(&mut FontDataStream::new(&record)).unwrap()
+            }
+            contours => {
+                let mut end_points = Vec::new();
+                let mut total = 0u16;
+                for _ in 0..contours {
+                    total += read_255_uint16(&mut n_points);
+                    end_points.push(total - 1);
+                }
+                let (mut x, mut y) = (0i32, 0i32);
+                let points = (0..total)
+                    .map(|_| {
+                        let (dx, dy, on_curve) =
+                            read_triplet(flags.read_u8().unwrap(), &mut glyphs);
+                        (x, y) = (x + dx, y + dy);
+                        GlyphPoint {
+                            x: x as i16,
+                            y: y as i16,
+                            on_curve,
+                        }
+                    })
+                    .collect();
+                let length = read_255_uint16(&mut glyphs) as usize;
+                let mut glyph = SimpleGlyph {
+                    header: GlyphHeader {
+                        number_of_contours: contours,
+                        ..Default::default()
+                    },
+                    end_points,
+                    instructions: instructions.read_bytes(length).unwrap().to_vec(),
+                    points,
+                    overlap: overlap_bitmap.is_some_and(|bitmap| bit(bitmap, glyph_id)),
+                };
+                let bounds = if bit(bbox_bitmap, glyph_id) {
+                    let mut read = || bboxes.read_i16().unwrap();
+                    (read(), read(), read(), read())
+                } else {
+                    glyph.compute_bounds()
+                };
+                let header = &mut glyph.header;
+                (header.x_min, header.y_min, header.x_max, header.y_max) = bounds;
+                Glyph::Simple(glyph)
+            }
+        };
+        table.glyphs.push(glyph);
+    }
+    (table, index_format)
+}
+
+#[test]
+fn test_decode_woff2_round_trip() {
+    use aurora_font::common::snft::read_snft;
+    use aurora_font::common::woff2::{WOFF2_KNOWN_TAGS, Woff2EncodeOptions, encode_woff2};
+    use aurora_font::components::glyf::{
+        ARG_1_AND_2_ARE_WORDS, ARGS_ARE_XY_VALUES, CompositeGlyph, GlyfTable, Glyph,
+        GlyphComponent, GlyphHeader, GlyphPoint, ROUND_XY_TO_GRID, SimpleGlyph, write_glyf,
+    };
+    use aurora_font::components::hmtx::{HmtxTable, LongHorMetric, write_hmtx};
+    use aurora_font::components::loca::write_loca;
+    use aurora_font::io::{FontDataStream, FontDataWriter};
+    use std::io::Read;
+
+    let point = |x, y, on_curve| GlyphPoint { x, y, on_curve };
+    let glyf = GlyfTable {
+        glyphs: vec![
+            Glyph::Empty,
+            Glyph::Simple(SimpleGlyph {
+                header: GlyphHeader {
+                    number_of_contours: 1,
+                    x_min: 0,
+                    y_min: 0,
+                    x_max: 500,
+                    y_max: 700,
+                },
+                end_points: vec![3],
+                instructions: vec![],
+                points: vec![
+                    point(0, 0, true),
+                    point(0, 700, true),
+                    point(500, 700, true),
+                    point(500, 0, true),
+                ],
+                overlap: false,
+            }),
+            // Off-curve points, large deltas, instructions and a stored bbox
+            // wider than the outline.
+            Glyph::Simple(SimpleGlyph {
+                header: GlyphHeader {
+                    number_of_contours: 2,
+                    x_min: 40,
+                    y_min: -20,
+                    x_max: 450,
+                    y_max: 1500,
+                },
+                end_points: vec![2, 5],
+                instructions: vec![0xB0, 0x01, 0x2F],
+                points: vec![
+                    point(50, 0, true),
+                    point(250, 400, false),
+                    point(450, 0, true),
+                    point(200, 100, true),
+                    point(213, 99, false),
+                    point(250, 1500, true),
+                ],
+                overlap: true,
+            }),
+            Glyph::Composite(CompositeGlyph {
+                header: GlyphHeader {
+                    number_of_contours: -1,
+                    x_min: 600,
+                    y_min: 0,
+                    x_max: 1100,
+                    y_max: 700,
+                },
+                components: vec![GlyphComponent {
+                    flags: ARG_1_AND_2_ARE_WORDS | ARGS_ARE_XY_VALUES | ROUND_XY_TO_GRID,
+                    glyph_index: 1,
+                    arg1: 600,
+                    arg2: 0,
+                    ..Default::default()
+                }],
+                instructions: vec![],
+            }),
+        ],
+    };
+    let mut writer = FontDataWriter::new();
+    let loca = write_glyf(&mut writer, &glyf);
+    let glyf_data = writer.into_bytes().unwrap();
+    let mut writer = FontDataWriter::new();
+    write_loca(&mut writer, &loca, false).unwrap();
+    let loca_data = writer.into_bytes().unwrap();
+
+    // Side bearings match the glyph xMin values, so `hmtx` is transformed.
+    let hmtx = HmtxTable {
+        h_metrics: vec![
+            LongHorMetric {
+                advance_width: 500,
+                lsb: 0,
+            },
+            LongHorMetric {
+                advance_width: 600,
+                lsb: 0,
+            },
+        ],
+        left_side_bearings: vec![40, 600],
+    };
+    let mut writer = FontDataWriter::new();
+    write_hmtx(&mut writer, &hmtx);
+    let hmtx_data = writer.into_bytes().unwrap();
+
+    let mut head = vec![0u8; 54];
+    head[12..16].copy_from_slice(&0x5F0F3CF5u32.to_be_bytes());
+    let mut hhea = vec![0u8; 36];
+    hhea[34..36].copy_from_slice(&2u16.to_be_bytes());
+    let mut maxp = vec![0u8; 6];
+    maxp[..4].copy_from_slice(&0x00005000u32.to_be_bytes());
+    maxp[4..6].copy_from_slice(&4u16.to_be_bytes());
+
+    let font = common::build_snft(&[
+        (*b"glyf", glyf_data.clone()),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx_data.clone()),
+        (*b"loca", loca_data.clone()),
+        (*b"maxp", maxp),
+    ]);
+    let snft = read_snft(&mut FontDataStream::new(&font)).unwrap();
+    let woff2 = encode_woff2(&snft, &font, &Woff2EncodeOptions::default()).unwrap();
+
+    let mut stream = FontDataStream::new(&woff2);
+    stream.skip(12);
+    let num_tables = stream.read_u16().unwrap();
+    stream.skip(6);
+    let compressed_size = stream.read_u32().unwrap() as usize;
+    stream.seek(48);
+    let mut directory = Vec::new();
+    for _ in 0..num_tables {
+        let flags = stream.read_u8().unwrap();
+        let tag = match flags & 0x3F {
+            63 => stream.read_tag().unwrap(),
+            index => WOFF2_KNOWN_TAGS[index as usize],
+        };
+        let version = flags >> 6;
+        let orig_length = read_uint_base128(&mut stream);
+        let transformed = match &tag {
+            b"glyf" | b"loca" => version == 0,
+            _ => version != 0,
+        };
+        let length = if transformed {
+            read_uint_base128(&mut stream)
+        } else {
+            orig_length
+        };
+        directory.push((tag, version, length as usize));
+    }
+    let compressed = stream.read_bytes(compressed_size).unwrap();
+    let mut decompressed = Vec::new();
+    brotli_decompressor::Decompressor::new(compressed, 4096)
+        .read_to_end(&mut decompressed)
+        .unwrap();
+
+    let total = directory.iter().map(|&(.., length)| length).sum::<usize>();
+    assert_eq!(total, decompressed.len());
+    let table = |tag: &[u8; 4]| {
+        let index = directory.iter().position(|(t, ..)| t == tag).unwrap();
+        let start = directory[..index]
+            .iter()
+            .map(|&(.., length)| length)
+            .sum::<usize>();
+        let (_, version, length) = directory[index];
+        (version, &decompressed[start..start + length])
+    };
+    let (glyf_version, transformed_glyf) = table(b"glyf");
+    let (_, transformed_loca) = table(b"loca");
+    let (hmtx_version, transformed_hmtx) = table(b"hmtx");
+    assert_eq!(glyf_version, 0);
+    assert!(transformed_loca.is_empty());
+    assert_eq!(hmtx_version, 1);
+
+    // glyf and loca are rebuilt from the transformed streams.
+    let (decoded, index_format) = decode_transformed_glyf(transformed_glyf);
+    assert_eq!(decoded, glyf);
+    let mut writer = FontDataWriter::new();
+    let decoded_loca = write_glyf(&mut writer, &decoded);
+    assert_eq!(writer.into_bytes().unwrap(), glyf_data);
+    let mut writer = FontDataWriter::new();
+    write_loca(&mut writer, &decoded_loca, index_format != 0).unwrap();
+    assert_eq!(writer.into_bytes().unwrap(), loca_data);
+
+    // hmtx restores the omitted side bearings from the glyph xMin values.
+    let mut stream = FontDataStream::new(transformed_hmtx);
+    let flags = stream.read_u8().unwrap();
+    let x_mins: Vec<i16> = decoded
+        .glyphs
+        .iter()
+        .map(|g| g.header().map_or(0, |h| h.x_min))
+        .collect();
+    let advances: Vec<u16> = (0..2).map(|_| stream.read_u16().unwrap()).collect();
+    let mut lsb = |i: usize, omitted: bool| {
+        if omitted {
+            x_mins[i]
+        } else {
+            stream.read_i16().unwrap()
+        }
+    };
+    let h_metrics = advances
+        .iter()
+        .enumerate()
+        .map(|(i, &advance_width)| LongHorMetric {
+            advance_width,
+            lsb: lsb(i, flags & 1 != 0),
+        })
+        .collect();
+    let left_side_bearings = (2..4).map(|i| lsb(i, flags & 2 != 0)).collect();
+    let decoded_hmtx = HmtxTable {
+        h_metrics,
+        left_side_bearings,
+    };
+    assert!(stream.is_eof());
+    let mut writer = FontDataWriter::new();
+    write_hmtx(&mut writer, &decoded_hmtx);
+    assert_eq!(writer.into_bytes().unwrap(), hmtx_data);
+}
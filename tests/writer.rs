@@ -0,0 +1,83 @@
+//! Tests for the font data writer.
+
+#[test]
+fn test_writer_primitives_roundtrip() {
+    use aurora_font::io::{ByteOrder, FontDataStream, FontDataWriter};
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u8(0xAB);
+    writer.write_u16(0x1234);
+    writer.write_u24(0x56789A);
+    writer.write_u32(0xDEADBEEF);
+    writer.write_i16(-2);
+    writer.write_fixed(1.5);
+    writer.write_f2dot14(-0.5);
+    writer.write_tag(*b"glyf");
+    let bytes = writer.into_bytes().unwrap();
+    assert_eq!(&bytes[..6], &[0xAB, 0x12, 0x34, 0x56, 0x78, 0x9A]);
+
+    let mut stream = FontDataStream::new(&bytes);
+    assert_eq!(stream.read_u8().unwrap(), 0xAB);
+    assert_eq!(stream.read_u16().unwrap(), 0x1234);
+    assert_eq!(stream.read_u24().unwrap(), 0x56789A);
+    assert_eq!(stream.read_u32().unwrap(), 0xDEADBEEF);
+    assert_eq!(stream.read_i16().unwrap(), -2);
+    assert_eq!(stream.read_fixed().unwrap(), 1.5);
+    assert_eq!(stream.read_f2dot14().unwrap(), -0.5);
+    assert_eq!(&stream.read_tag().unwrap(), b"glyf");
+    assert!(stream.is_eof());
+
+    let mut little = FontDataWriter::new().with_endianness(ByteOrder::LittleEndian);
+    little.write_u16(0x1234);
+    little.write_u24(0x56789A);
+    assert_eq!(little.as_bytes(), &[0x34, 0x12, 0x9A, 0x78, 0x56]);
+}
+
+#[test]
+fn test_writer_offset_patching() {
+    use aurora_font::error::{Error, IoError};
+    use aurora_font::io::FontDataWriter;
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(1);
+    let offset16 = writer.reserve_offset16(0);
+    let offset32 = writer.reserve_offset32(0);
+    writer.patch_offset_here(offset16).unwrap();
+    writer.write_u16(0xAAAA);
+    writer.patch_offset_here(offset32).unwrap();
+    writer.write_u16(0xBBBB);
+    assert_eq!(
+        writer.as_bytes(),
+        &[0, 1, 0, 8, 0, 0, 0, 10, 0xAA, 0xAA, 0xBB, 0xBB]
+    );
+
+    let mut overflow = FontDataWriter::new();
+    let offset = overflow.reserve_offset16(0);
+    let result = overflow.patch_offset(offset, 0x1_0000);
+    assert!(matches!(
+        result,
+        Err(Error::Io(IoError::OffsetOverflow { .. }))
+    ));
+}
+
+#[test]
+fn test_writer_shared_subtables() {
+    use aurora_font::io::FontDataWriter;
+
+    let mut child = FontDataWriter::new();
+    child.write_u16(0xCAFE);
+    let child = child.into_bytes().unwrap();
+
+    let mut writer = FontDataWriter::new();
+    writer.write_u16(3);
+    let first = writer.reserve_offset16(0);
+    let second = writer.reserve_offset16(0);
+    let third = writer.reserve_offset16(0);
+    writer.defer_subtable(first, child.clone());
+    writer.defer_subtable(second, vec![0xBE, 0xEF]);
+    writer.defer_subtable(third, child);
+    assert_eq!(writer.deferred_count(), 3);
+
+    let bytes = writer.into_bytes().unwrap();
+    assert_eq!(bytes, vec![0, 3, 0, 8, 0, 10, 0, 8, 0xCA, 0xFE, 0xBE, 0xEF]);
+}